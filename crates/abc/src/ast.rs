@@ -281,6 +281,19 @@ impl Default for Tempo {
     }
 }
 
+impl Tempo {
+    /// Tempo expressed in quarter notes per minute, as MIDI expects.
+    ///
+    /// `Q:3/8=40` (dotted quarters) is 60 quarter notes per minute.
+    pub fn quarter_notes_per_minute(&self) -> f64 {
+        let (num, den) = self.beat_unit;
+        if den == 0 {
+            return self.bpm as f64;
+        }
+        self.bpm as f64 * num as f64 * 4.0 / den as f64
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum Clef {
     #[default]
@@ -355,10 +368,25 @@ pub enum Element {
     Decoration(Decoration),
     Slur(SlurBoundary),
     VoiceSwitch(String), // Switch to voice with given ID
+    /// A `w:` line, aligned to the notes of the preceding music line
+    Lyrics(Vec<Syllable>),
     Space,
     LineBreak,
 }
 
+/// One token of a `w:` lyric line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Syllable {
+    /// Text sung on one note; `hyphen` means the word continues on the next syllable
+    Text { text: String, hyphen: bool },
+    /// `_`: the previous syllable is held over this note
+    Hold,
+    /// `*`: this note gets no syllable
+    Skip,
+    /// `|`: jump to the first note of the next bar
+    Bar,
+}
+
 /// A single note
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Note {
@@ -616,6 +644,26 @@ mod tests {
         assert_eq!(Mode::parse("invalid"), None);
     }
 
+    #[test]
+    fn test_tempo_quarter_notes_per_minute() {
+        let quarter = Tempo::default();
+        assert_eq!(quarter.quarter_notes_per_minute(), 120.0);
+
+        let dotted_quarter = Tempo {
+            beat_unit: (3, 8),
+            bpm: 40,
+            text: None,
+        };
+        assert_eq!(dotted_quarter.quarter_notes_per_minute(), 60.0);
+
+        let half = Tempo {
+            beat_unit: (1, 2),
+            bpm: 60,
+            text: None,
+        };
+        assert_eq!(half.quarter_notes_per_minute(), 120.0);
+    }
+
     #[test]
     fn test_meter_to_fraction() {
         assert_eq!(Meter::Common.to_fraction(), (4, 4));
@@ -59,6 +59,26 @@ impl Feedback {
         self.span = Some((start, end));
         self
     }
+
+    /// Where in the input this feedback applies
+    pub fn position(&self) -> SourcePosition {
+        SourcePosition {
+            line: self.line,
+            column: self.column,
+            span: self.span,
+        }
+    }
+}
+
+/// Location in the parsed input, suitable for LSP-style diagnostics.
+///
+/// `line` and `column` are 1-based (column counts characters); `span` is a
+/// half-open range of byte offsets into the original input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+    pub span: Option<(usize, usize)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -71,12 +91,29 @@ pub enum FeedbackLevel {
     Info,
 }
 
+impl FeedbackLevel {
+    fn severity(self) -> u8 {
+        match self {
+            FeedbackLevel::Error => 2,
+            FeedbackLevel::Warning => 1,
+            FeedbackLevel::Info => 0,
+        }
+    }
+
+    /// Whether this level is at least as severe as `threshold`
+    /// (Error > Warning > Info).
+    pub fn is_at_least(self, threshold: FeedbackLevel) -> bool {
+        self.severity() >= threshold.severity()
+    }
+}
+
 /// Collector for feedback during parsing
 #[derive(Debug, Default)]
 pub struct FeedbackCollector {
     feedback: Vec<Feedback>,
     current_line: usize,
     current_column: usize,
+    current_span: Option<(usize, usize)>,
 }
 
 impl FeedbackCollector {
@@ -85,31 +122,40 @@ impl FeedbackCollector {
             feedback: Vec::new(),
             current_line: 1,
             current_column: 1,
+            current_span: None,
         }
     }
 
-    /// Update position tracking (call when advancing through input)
+    /// Update position tracking (call when advancing through input).
+    /// Clears any span set by [`set_span`](Self::set_span).
     pub fn set_position(&mut self, line: usize, column: usize) {
         self.current_line = line;
         self.current_column = column;
+        self.current_span = None;
+    }
+
+    /// Set the byte range of input currently being parsed
+    pub fn set_span(&mut self, start: usize, end: usize) {
+        self.current_span = Some((start, end));
+    }
+
+    fn located(&self, feedback: Feedback) -> Feedback {
+        match self.current_span {
+            Some((start, end)) => feedback.with_span(start, end),
+            None => feedback,
+        }
     }
 
     /// Add an error at current position
     pub fn error(&mut self, message: impl Into<String>) {
-        self.feedback.push(Feedback::error(
-            message,
-            self.current_line,
-            self.current_column,
-        ));
+        let feedback = Feedback::error(message, self.current_line, self.current_column);
+        self.feedback.push(self.located(feedback));
     }
 
     /// Add a warning at current position
     pub fn warning(&mut self, message: impl Into<String>) {
-        self.feedback.push(Feedback::warning(
-            message,
-            self.current_line,
-            self.current_column,
-        ));
+        let feedback = Feedback::warning(message, self.current_line, self.current_column);
+        self.feedback.push(self.located(feedback));
     }
 
     /// Add a warning with suggestion at current position
@@ -118,19 +164,15 @@ impl FeedbackCollector {
         message: impl Into<String>,
         suggestion: impl Into<String>,
     ) {
-        self.feedback.push(
-            Feedback::warning(message, self.current_line, self.current_column)
-                .with_suggestion(suggestion),
-        );
+        let feedback = Feedback::warning(message, self.current_line, self.current_column)
+            .with_suggestion(suggestion);
+        self.feedback.push(self.located(feedback));
     }
 
     /// Add info at current position
     pub fn info(&mut self, message: impl Into<String>) {
-        self.feedback.push(Feedback::info(
-            message,
-            self.current_line,
-            self.current_column,
-        ));
+        let feedback = Feedback::info(message, self.current_line, self.current_column);
+        self.feedback.push(self.located(feedback));
     }
 
     /// Check if any errors were recorded
@@ -187,6 +229,11 @@ impl<T> ParseResult<T> {
             .iter()
             .filter(|f| f.level == FeedbackLevel::Error)
     }
+
+    /// Errors paired with their location in the input, for editor diagnostics
+    pub fn errors_with_positions(&self) -> impl Iterator<Item = (SourcePosition, &Feedback)> {
+        self.errors().map(|f| (f.position(), f))
+    }
 }
 
 #[cfg(test)]
@@ -203,6 +250,14 @@ mod tests {
         assert_eq!(fb.suggestion, Some("Add M:4/4 after the title".to_string()));
     }
 
+    #[test]
+    fn test_feedback_level_severity() {
+        assert!(FeedbackLevel::Error.is_at_least(FeedbackLevel::Warning));
+        assert!(FeedbackLevel::Warning.is_at_least(FeedbackLevel::Warning));
+        assert!(!FeedbackLevel::Info.is_at_least(FeedbackLevel::Warning));
+        assert!(FeedbackLevel::Info.is_at_least(FeedbackLevel::Info));
+    }
+
     #[test]
     fn test_feedback_collector() {
         let mut collector = FeedbackCollector::new();
@@ -218,6 +273,28 @@ mod tests {
         assert_eq!(feedback[1].column, 10);
     }
 
+    #[test]
+    fn test_feedback_collector_span() {
+        let mut collector = FeedbackCollector::new();
+
+        collector.set_position(3, 7);
+        collector.set_span(42, 43);
+        collector.error("Unexpected character");
+        collector.set_position(4, 1);
+        collector.warning("No span here");
+
+        let feedback = collector.into_feedback();
+        assert_eq!(
+            feedback[0].position(),
+            SourcePosition {
+                line: 3,
+                column: 7,
+                span: Some((42, 43)),
+            }
+        );
+        assert_eq!(feedback[1].span, None);
+    }
+
     #[test]
     fn test_parse_result() {
         let result: ParseResult<i32> = ParseResult::new(
@@ -231,5 +308,10 @@ mod tests {
         assert!(result.has_errors());
         assert_eq!(result.warnings().count(), 1);
         assert_eq!(result.errors().count(), 1);
+
+        let positions: Vec<_> = result.errors_with_positions().collect();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].0.line, 2);
+        assert_eq!(positions[0].1.message, "test error");
     }
 }
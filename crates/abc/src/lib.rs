@@ -24,22 +24,47 @@
 //! }
 //! ```
 
+use std::collections::HashMap;
+
 pub mod ast;
 pub mod feedback;
+pub mod lyrics;
 pub mod midi;
 pub mod parser;
 
 pub use ast::*;
-pub use feedback::{Feedback, FeedbackLevel, ParseResult};
+pub use feedback::{Feedback, FeedbackLevel, ParseResult, SourcePosition};
+pub use lyrics::{align_lyrics, AlignedSyllable};
 
 /// Parse ABC notation into a Tune AST.
 ///
 /// This is a generous parser that will attempt to continue parsing
 /// even when encountering issues, collecting feedback along the way.
+/// It is the lenient default; see [`parse_strict`] to reject questionable input.
 pub fn parse(input: &str) -> ParseResult<Tune> {
     parser::parse(input)
 }
 
+/// Parse ABC notation, failing if any feedback reaches `min_level`.
+///
+/// Useful for validating agent-generated notation before rendering:
+/// `parse_strict(abc, FeedbackLevel::Warning)` rejects anything the lenient
+/// parser had to guess about. On failure, returns the offending feedback.
+pub fn parse_strict(input: &str, min_level: FeedbackLevel) -> Result<Tune, Vec<Feedback>> {
+    let result = parse(input);
+    let rejected: Vec<Feedback> = result
+        .feedback
+        .into_iter()
+        .filter(|f| f.level.is_at_least(min_level))
+        .collect();
+
+    if rejected.is_empty() {
+        Ok(result.value)
+    } else {
+        Err(rejected)
+    }
+}
+
 /// Parameters for MIDI generation
 #[derive(Debug, Clone)]
 pub struct MidiParams {
@@ -52,6 +77,9 @@ pub struct MidiParams {
     /// MIDI program number (0-127). If Some, a program change is emitted at track start.
     /// See General MIDI for standard mappings (e.g., 0=Piano, 33=Bass, 56=Trumpet).
     pub program: Option<u8>,
+    /// Per-voice overrides keyed by voice ID (the `V:` identifier).
+    /// Only consulted for multi-voice tunes rendered as SMF format 1.
+    pub voice_overrides: HashMap<String, VoiceMidiParams>,
 }
 
 impl Default for MidiParams {
@@ -61,15 +89,36 @@ impl Default for MidiParams {
             ticks_per_beat: 480,
             channel: 0,
             program: None, // No program change by default (uses synth's default)
+            voice_overrides: HashMap::new(),
         }
     }
 }
 
-/// Convert a parsed Tune to MIDI bytes (SMF format 0)
+/// MIDI settings for a single voice, overriding the tune-wide [`MidiParams`].
+#[derive(Debug, Clone, Default)]
+pub struct VoiceMidiParams {
+    /// MIDI channel (0-15). Channel 9 marks the voice as percussion.
+    pub channel: Option<u8>,
+    /// MIDI program number (0-127), takes priority over `%%MIDI program`.
+    pub program: Option<u8>,
+    /// MIDI velocity for this voice's notes (1-127)
+    pub velocity: Option<u8>,
+}
+
+/// Convert a parsed Tune to MIDI bytes.
+///
+/// Single-voice tunes produce SMF format 0. Tunes with several voices
+/// produce SMF format 1 with one track and one channel per voice.
 pub fn to_midi(tune: &Tune, params: &MidiParams) -> Vec<u8> {
     midi::generate(tune, params)
 }
 
+/// Convert a parsed Tune to SMF format 1, one track per voice,
+/// even when the tune only has a single voice.
+pub fn to_midi_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
+    midi::generate_multitrack(tune, params)
+}
+
 /// Transpose a tune by the given number of semitones
 pub fn transpose(tune: &Tune, semitones: i8) -> Tune {
     let mut result = tune.clone();
@@ -341,13 +390,42 @@ fn format_element(output: &mut String, element: &Element) {
             }
             output.push('}');
         }
-        Element::InlineField(_)
-        | Element::Decoration(_)
-        | Element::Slur(_)
-        | Element::VoiceSwitch(_) => {}
+        Element::InlineField(field) => {
+            output.push_str(&format!("[{}:{}]", field.field_type, field.value));
+        }
+        Element::Lyrics(syllables) => {
+            output.push_str("w:");
+            format_lyrics(output, syllables);
+        }
+        Element::Decoration(_) | Element::Slur(_) | Element::VoiceSwitch(_) => {}
     }
 }
 
+fn format_lyrics(output: &mut String, syllables: &[Syllable]) {
+    let mut line = String::new();
+    for syllable in syllables {
+        match syllable {
+            Syllable::Text { text, hyphen } => {
+                for c in text.chars() {
+                    match c {
+                        ' ' => line.push('~'),
+                        '-' | '_' | '*' | '|' | '~' | '\\' => {
+                            line.push('\\');
+                            line.push(c);
+                        }
+                        _ => line.push(c),
+                    }
+                }
+                line.push(if *hyphen { '-' } else { ' ' });
+            }
+            Syllable::Hold => line.push_str("_ "),
+            Syllable::Skip => line.push_str("* "),
+            Syllable::Bar => line.push_str("| "),
+        }
+    }
+    output.push_str(line.trim_end());
+}
+
 fn format_duration(output: &mut String, duration: &Duration) {
     if duration.numerator == 1 && duration.denominator == 1 {
         return;
@@ -431,6 +509,21 @@ mod tests {
         to_abc(&result.value)
     }
 
+    #[test]
+    fn parse_strict_rejects_warnings_at_threshold() {
+        let clean = "X:1\nT:Test\nM:4/4\nL:1/8\nK:C\nCDEF|\n";
+        assert!(parse_strict(clean, FeedbackLevel::Warning).is_ok());
+
+        // Missing M: is a warning; missing L: is only info
+        let sloppy = "X:1\nT:Test\nK:C\nCDEF|\n";
+        let rejected = parse_strict(sloppy, FeedbackLevel::Warning).unwrap_err();
+        assert!(rejected.iter().all(|f| f.level == FeedbackLevel::Warning));
+        assert!(rejected.iter().any(|f| f.message.contains("M:")));
+
+        assert!(parse_strict(sloppy, FeedbackLevel::Error).is_ok());
+        assert!(parse_strict(clean, FeedbackLevel::Info).is_ok());
+    }
+
     #[test]
     fn tuplet_round_trip_preserves_ratio() {
         let abc = "X:1\nT:Test\nM:4/4\nL:1/8\nK:C\n(3:2ABC\n";
@@ -452,10 +545,38 @@ mod tests {
         assert!(output.contains("{/A}"), "expected acciaccatura {{/A}}, got: {}", output);
     }
 
+    #[test]
+    fn inline_field_round_trip() {
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/8\nK:C\nCDEF|[K:D][M:3/4]FAd|\n";
+        let output = round_trip(abc);
+        assert!(output.contains("[K:D][M:3/4]"), "expected inline fields, got: {}", output);
+    }
+
     #[test]
     fn chord_symbol_round_trip() {
         let abc = "X:1\nT:Test\nM:4/4\nL:1/8\nK:C\n\"Am\"A2\n";
         let output = round_trip(abc);
         assert!(output.contains("\"Am\""), "expected chord symbol \"Am\", got: {}", output);
     }
+
+    #[test]
+    fn lyrics_round_trip() {
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/8\nK:C\nCDEF|GABc|\nw:A-ma-zing_ * grace | how~sweet\n";
+        let output = round_trip(abc);
+        assert!(
+            output.contains("w:A-ma-zing _ * grace | how~sweet\n"),
+            "expected lyrics line, got: {}",
+            output
+        );
+
+        let lyrics = |abc: &str| -> Vec<Element> {
+            parse(abc).value.voices[0]
+                .elements
+                .iter()
+                .filter(|e| matches!(e, Element::Lyrics(_)))
+                .cloned()
+                .collect()
+        };
+        assert_eq!(lyrics(&output), lyrics(abc));
+    }
 }
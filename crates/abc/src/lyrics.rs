@@ -0,0 +1,202 @@
+//! Syllable-to-note alignment for `w:` lyric lines.
+//!
+//! Each [`Element::Lyrics`] line lines up with the notes of the music line
+//! just above it. Several `w:` lines under the same music are successive
+//! verses. Notes and chords each take one syllable; rests and grace notes
+//! take none.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ast::{Element, Syllable, Voice};
+
+/// A syllable placed on a note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignedSyllable {
+    /// Index among the voice's sung notes (notes and chords, in order)
+    pub note: usize,
+    /// Verse number, counting `w:` lines under the same music line from 0
+    pub verse: usize,
+    pub text: String,
+    /// The word continues on the next syllable
+    pub hyphen: bool,
+    /// Held over from the previous note (`_`) rather than sung fresh
+    pub held: bool,
+}
+
+/// What a lyric can land on within a music line
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slot {
+    Note(usize),
+    Bar,
+}
+
+/// Place every syllable of every `w:` line in `voice` on its note.
+///
+/// Skipped notes get no entry. Syllables left over once the line runs out
+/// of notes are dropped.
+pub fn align_lyrics(voice: &Voice) -> Vec<AlignedSyllable> {
+    let mut aligned = Vec::new();
+    let mut note_count = 0;
+    let mut line = Vec::new();
+    let mut previous_line = Vec::new();
+    let mut verse = 0;
+
+    for element in &voice.elements {
+        match element {
+            Element::Lyrics(syllables) => {
+                align_line(&previous_line, syllables, verse, &mut aligned);
+                verse += 1;
+            }
+            Element::LineBreak => {
+                if !line.is_empty() {
+                    previous_line = std::mem::take(&mut line);
+                    verse = 0;
+                }
+            }
+            other => collect_slots(other, &mut note_count, &mut line),
+        }
+    }
+
+    aligned
+}
+
+fn collect_slots(element: &Element, note_count: &mut usize, slots: &mut Vec<Slot>) {
+    match element {
+        Element::Note(_) | Element::Chord(_) => {
+            slots.push(Slot::Note(*note_count));
+            *note_count += 1;
+        }
+        Element::Bar(_) => slots.push(Slot::Bar),
+        Element::Tuplet(tuplet) => {
+            for inner in &tuplet.elements {
+                collect_slots(inner, note_count, slots);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn align_line(
+    slots: &[Slot],
+    syllables: &[Syllable],
+    verse: usize,
+    aligned: &mut Vec<AlignedSyllable>,
+) {
+    let mut pos = 0;
+    let mut previous: Option<String> = None;
+
+    for syllable in syllables {
+        if let Syllable::Bar = syllable {
+            pos = slots[pos..]
+                .iter()
+                .position(|s| *s == Slot::Bar)
+                .map_or(slots.len(), |i| pos + i + 1);
+            continue;
+        }
+
+        let next_note = slots[pos..].iter().enumerate().find_map(|(i, s)| match s {
+            Slot::Note(note) => Some((i, *note)),
+            Slot::Bar => None,
+        });
+        let Some((offset, note)) = next_note else {
+            break;
+        };
+        pos += offset + 1;
+
+        match syllable {
+            Syllable::Text { text, hyphen } if !text.is_empty() => {
+                aligned.push(AlignedSyllable {
+                    note,
+                    verse,
+                    text: text.clone(),
+                    hyphen: *hyphen,
+                    held: false,
+                });
+                previous = Some(text.clone());
+            }
+            Syllable::Hold => {
+                if let Some(text) = &previous {
+                    aligned.push(AlignedSyllable {
+                        note,
+                        verse,
+                        text: text.clone(),
+                        hyphen: false,
+                        held: true,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn lyrics_of(abc: &str) -> Vec<AlignedSyllable> {
+        let result = parse(abc);
+        assert!(!result.has_errors(), "parse errors: {:?}", result.feedback);
+        align_lyrics(&result.value.voices[0])
+    }
+
+    fn placed(aligned: &[AlignedSyllable]) -> Vec<(usize, &str, bool)> {
+        aligned
+            .iter()
+            .map(|a| (a.note, a.text.as_str(), a.held))
+            .collect()
+    }
+
+    #[test]
+    fn test_syllables_follow_notes() {
+        let aligned = lyrics_of("X:1\nK:C\nCD E2|z F\nw:A-ma-zing grace\n");
+        assert_eq!(
+            placed(&aligned),
+            vec![
+                (0, "A", false),
+                (1, "ma", false),
+                (2, "zing", false),
+                (3, "grace", false)
+            ]
+        );
+        assert!(aligned[0].hyphen);
+        assert!(!aligned[2].hyphen);
+    }
+
+    #[test]
+    fn test_holds_and_skips() {
+        let aligned = lyrics_of("X:1\nK:C\nCDEF|GA\nw:how_ * sweet the sound\n");
+        assert_eq!(
+            placed(&aligned),
+            vec![
+                (0, "how", false),
+                (1, "how", true),
+                (3, "sweet", false),
+                (4, "the", false),
+                (5, "sound", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bar_jumps_to_next_bar() {
+        let aligned = lyrics_of("X:1\nK:C\n|:CDE|FG:|\nw:so | long\n");
+        assert_eq!(placed(&aligned), vec![(0, "so", false), (3, "long", false)]);
+    }
+
+    #[test]
+    fn test_verses_and_lines() {
+        let abc = "X:1\nK:C\nCD|\nw:one two\nw:three four\nEF|\nw:five\n";
+        let aligned = lyrics_of(abc);
+        let verses: Vec<_> = aligned.iter().map(|a| (a.note, a.verse)).collect();
+        assert_eq!(verses, vec![(0, 0), (1, 0), (0, 1), (1, 1), (2, 0)]);
+        assert_eq!(aligned[4].text, "five");
+    }
+
+    #[test]
+    fn test_extra_syllables_are_dropped() {
+        let aligned = lyrics_of("X:1\nK:C\nCD\nw:one two three\n");
+        assert_eq!(aligned.len(), 2);
+    }
+}
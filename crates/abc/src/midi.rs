@@ -1,19 +1,32 @@
 //! MIDI generation from ABC AST.
 //!
-//! Generates Standard MIDI File (SMF) format 0 (single track).
+//! Generates Standard MIDI File (SMF) format 0 for single-voice tunes and
+//! format 1 (one track per voice) for multi-voice tunes.
 
 use std::collections::HashMap;
 
-use crate::ast::{Accidental, Bar, Element, Key, Mode, NoteName, Tune, UnitLength, Voice};
-use crate::MidiParams;
+use crate::ast::{
+    Accidental, Bar, Clef, Element, InfoField, Key, Meter, Mode, NoteName, Tempo, Tune,
+    UnitLength, Voice, VoiceDef,
+};
+use crate::feedback::FeedbackCollector;
+use crate::parser;
+use crate::{MidiParams, VoiceMidiParams};
 
-/// Get the combined pitch offset from voice properties (transpose + octave)
-fn get_voice_pitch_offset(voice: &Voice, voice_defs: &[crate::ast::VoiceDef]) -> i16 {
-    // Find the voice definition that matches this voice
-    let voice_def = voice
+/// GM reserves channel 9 for percussion
+const PERCUSSION_CHANNEL: u8 = 9;
+
+/// Find the V: header definition matching this voice
+fn find_voice_def<'a>(voice: &Voice, voice_defs: &'a [VoiceDef]) -> Option<&'a VoiceDef> {
+    voice
         .id
         .as_ref()
-        .and_then(|vid| voice_defs.iter().find(|vd| &vd.id == vid));
+        .and_then(|vid| voice_defs.iter().find(|vd| &vd.id == vid))
+}
+
+/// Get the combined pitch offset from voice properties (transpose + octave)
+fn get_voice_pitch_offset(voice: &Voice, voice_defs: &[VoiceDef]) -> i16 {
+    let voice_def = find_voice_def(voice, voice_defs);
 
     let transpose_offset = voice_def
         .and_then(|vd| vd.transpose)
@@ -110,12 +123,8 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
     // Single voice - use format 0
     let mut writer = MidiWriter::new(params.ticks_per_beat, params.channel);
 
-    // Set tempo
-    if let Some(tempo) = &tune.header.tempo {
-        writer.tempo(tempo.bpm);
-    } else {
-        writer.tempo(120); // Default
-    }
+    // Set tempo from Q:, normalized to quarter notes
+    writer.tempo(&tune.header.tempo.clone().unwrap_or_default());
 
     // Set program: ABC %%MIDI program takes priority, then params.program
     let program = tune.header.midi_program.or(params.program);
@@ -123,15 +132,11 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
         writer.program_change(program);
     }
 
-    // Compute key signature accidentals
-    let key_accidentals = compute_key_accidentals(&tune.header.key);
-
-    // Compute ticks per unit note
-    let unit_length = tune.header.unit_length.unwrap_or_default();
-    let unit_ticks = compute_unit_ticks(&unit_length, params.ticks_per_beat);
-
     // Process all voices (merge into single track for format 0)
     for voice in &tune.voices {
+        // Key, unit length and meter start from the header; inline fields may change them
+        let mut context = VoiceContext::from_header(tune, params.ticks_per_beat);
+
         // Get pitch offset from voice properties (transpose, octave)
         let pitch_offset = get_voice_pitch_offset(voice, &tune.header.voice_defs);
 
@@ -139,7 +144,7 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
         let elements = expand_repeats(&voice.elements);
 
         // Bar-scoped accidentals reset at each bar line
-        let mut bar_accidentals = key_accidentals.clone();
+        let mut bar_accidentals = context.key_accidentals.clone();
 
         // Track held (tied) notes: midi_pitch -> accumulated ticks
         let mut held_notes: HashMap<u8, u32> = HashMap::new();
@@ -155,7 +160,7 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                         &bar_accidentals,
                     );
                     let midi_pitch = apply_pitch_offset(base_pitch, pitch_offset);
-                    let ticks = note.duration.to_ticks(unit_ticks);
+                    let ticks = note.duration.to_ticks(context.unit_ticks);
 
                     if let Some(held_ticks) = held_notes.remove(&midi_pitch) {
                         // Continue a tied note - add duration, advance time
@@ -184,7 +189,7 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                 }
 
                 Element::Chord(chord) => {
-                    let ticks = chord.duration.to_ticks(unit_ticks);
+                    let ticks = chord.duration.to_ticks(context.unit_ticks);
 
                     // Note on for all notes
                     for note in &chord.notes {
@@ -220,24 +225,26 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
 
                 Element::Rest(rest) => {
                     if let Some(bars) = rest.multi_measure {
-                        // Multi-measure rest - advance by bars * beats per bar
-                        let (beats_per_bar, _) = tune
-                            .header
-                            .meter
-                            .as_ref()
-                            .map(|m| m.to_fraction())
-                            .unwrap_or((4, 4));
-                        let ticks_per_bar = params.ticks_per_beat as u32 * beats_per_bar as u32;
-                        writer.advance(ticks_per_bar * bars as u32);
+                        // Multi-measure rest - advance by whole bars of the meter
+                        writer.advance(context.bar_ticks * bars as u32);
                     } else {
-                        let ticks = rest.duration.to_ticks(unit_ticks);
+                        let ticks = rest.duration.to_ticks(context.unit_ticks);
                         writer.advance(ticks);
                     }
                 }
 
                 Element::Bar(_) => {
                     // Reset bar accidentals
-                    bar_accidentals = key_accidentals.clone();
+                    bar_accidentals = context.key_accidentals.clone();
+                }
+
+                Element::InlineField(field) => {
+                    if let Some(tempo) = context.apply(field, params.ticks_per_beat) {
+                        writer.tempo(&tempo);
+                    }
+                    if field.field_type == 'K' {
+                        bar_accidentals = context.key_accidentals.clone();
+                    }
                 }
 
                 Element::Tuplet(tuplet) => {
@@ -254,7 +261,7 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                                 &bar_accidentals,
                             );
                             let midi_pitch = apply_pitch_offset(base_pitch, pitch_offset);
-                            let base_ticks = note.duration.to_ticks(unit_ticks);
+                            let base_ticks = note.duration.to_ticks(context.unit_ticks);
                             let ticks = (base_ticks * scale_num) / scale_den;
 
                             writer.note(midi_pitch, params.velocity, ticks);
@@ -280,25 +287,32 @@ pub fn generate(tune: &Tune, params: &MidiParams) -> Vec<u8> {
     writer.finish()
 }
 
-/// Generate multi-track MIDI (SMF format 1) for tunes with multiple voices.
-fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
-    let key_accidentals = compute_key_accidentals(&tune.header.key);
-    let unit_length = tune.header.unit_length.unwrap_or_default();
-    let unit_ticks = compute_unit_ticks(&unit_length, params.ticks_per_beat);
+/// Channel for the nth melodic voice, cycling through the 15 non-drum channels
+fn melodic_channel(melodic_index: usize) -> u8 {
+    let channel = (melodic_index % 15) as u8;
+    if channel >= PERCUSSION_CHANNEL {
+        channel + 1
+    } else {
+        channel
+    }
+}
 
+/// Generate multi-track MIDI (SMF format 1) for tunes with multiple voices.
+///
+/// Each voice gets its own track and channel. Melodic voices are assigned
+/// channels in order, skipping channel 9; voices with `clef=perc` go to
+/// channel 9. Entries in `params.voice_overrides` win over both.
+pub fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
     let mut tracks: Vec<Vec<u8>> = Vec::new();
 
-    // Track 0: Tempo track (meta events only)
-    let mut tempo_writer = MidiWriter::new(params.ticks_per_beat, 0);
-    if let Some(tempo) = &tune.header.tempo {
-        tempo_writer.tempo(tempo.bpm);
-    } else {
-        tempo_writer.tempo(120);
-    }
-    tracks.push(tempo_writer.encode_track());
+    // Inline [Q:] changes from any voice, gathered into the tempo track
+    let mut tempo_changes: Vec<(u32, Tempo)> = Vec::new();
+
+    let default_override = VoiceMidiParams::default();
+    let mut melodic_index = 0;
 
     // One track per voice
-    for (voice_idx, voice) in tune.voices.iter().enumerate() {
+    for voice in &tune.voices {
         if voice.elements.is_empty() {
             continue;
         }
@@ -306,23 +320,39 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
         // Get pitch offset from voice properties (transpose, octave)
         let pitch_offset = get_voice_pitch_offset(voice, &tune.header.voice_defs);
 
-        // Use different MIDI channel per voice (0-15, skip 9 which is percussion)
-        let channel = if voice_idx >= 9 {
-            voice_idx + 1
-        } else {
-            voice_idx
-        } as u8
-            % 16;
+        let voice_override = voice
+            .id
+            .as_ref()
+            .and_then(|id| params.voice_overrides.get(id))
+            .unwrap_or(&default_override);
+        let is_percussion = find_voice_def(voice, &tune.header.voice_defs)
+            .and_then(|vd| vd.clef)
+            == Some(Clef::Percussion);
+
+        let channel = match voice_override.channel {
+            Some(channel) => channel & 0x0F,
+            None if is_percussion => PERCUSSION_CHANNEL,
+            None => {
+                let channel = melodic_channel(melodic_index);
+                melodic_index += 1;
+                channel
+            }
+        };
+        let velocity = voice_override.velocity.unwrap_or(params.velocity);
         let mut writer = MidiWriter::new(params.ticks_per_beat, channel);
 
-        // Set program: ABC %%MIDI program takes priority, then params.program
-        let program = tune.header.midi_program.or(params.program);
+        // Set program: voice override, then ABC %%MIDI program, then params.program
+        let program = voice_override
+            .program
+            .or(tune.header.midi_program)
+            .or(params.program);
         if let Some(program) = program {
             writer.program_change_channel(program, channel);
         }
 
+        let mut context = VoiceContext::from_header(tune, params.ticks_per_beat);
         let elements = expand_repeats(&voice.elements);
-        let mut bar_accidentals = key_accidentals.clone();
+        let mut bar_accidentals = context.key_accidentals.clone();
         let mut held_notes: HashMap<u8, u32> = HashMap::new();
 
         for element in &elements {
@@ -335,7 +365,7 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                         &bar_accidentals,
                     );
                     let midi_pitch = apply_pitch_offset(base_pitch, pitch_offset);
-                    let ticks = note.duration.to_ticks(unit_ticks);
+                    let ticks = note.duration.to_ticks(context.unit_ticks);
 
                     if let Some(held_ticks) = held_notes.remove(&midi_pitch) {
                         writer.advance(ticks);
@@ -345,11 +375,11 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                             writer.note_off_channel(midi_pitch, channel);
                         }
                     } else if note.tie {
-                        writer.note_on_channel(midi_pitch, params.velocity, channel);
+                        writer.note_on_channel(midi_pitch, velocity, channel);
                         writer.advance(ticks);
                         held_notes.insert(midi_pitch, ticks);
                     } else {
-                        writer.note_channel(midi_pitch, params.velocity, ticks, channel);
+                        writer.note_channel(midi_pitch, velocity, ticks, channel);
                     }
 
                     if let Some(acc) = note.accidental {
@@ -358,7 +388,7 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                 }
 
                 Element::Chord(chord) => {
-                    let ticks = chord.duration.to_ticks(unit_ticks);
+                    let ticks = chord.duration.to_ticks(context.unit_ticks);
                     for note in &chord.notes {
                         let base_pitch = note_to_midi_pitch(
                             note.pitch,
@@ -367,7 +397,7 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                             &bar_accidentals,
                         );
                         let midi_pitch = apply_pitch_offset(base_pitch, pitch_offset);
-                        writer.note_on_channel(midi_pitch, params.velocity, channel);
+                        writer.note_on_channel(midi_pitch, velocity, channel);
                         if let Some(acc) = note.accidental {
                             bar_accidentals.insert(note.pitch, acc);
                         }
@@ -387,21 +417,23 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
 
                 Element::Rest(rest) => {
                     if let Some(bars) = rest.multi_measure {
-                        let (beats_per_bar, _) = tune
-                            .header
-                            .meter
-                            .as_ref()
-                            .map(|m| m.to_fraction())
-                            .unwrap_or((4, 4));
-                        let ticks_per_bar = params.ticks_per_beat as u32 * beats_per_bar as u32;
-                        writer.advance(ticks_per_bar * bars as u32);
+                        writer.advance(context.bar_ticks * bars as u32);
                     } else {
-                        writer.advance(rest.duration.to_ticks(unit_ticks));
+                        writer.advance(rest.duration.to_ticks(context.unit_ticks));
                     }
                 }
 
                 Element::Bar(_) => {
-                    bar_accidentals = key_accidentals.clone();
+                    bar_accidentals = context.key_accidentals.clone();
+                }
+
+                Element::InlineField(field) => {
+                    if let Some(tempo) = context.apply(field, params.ticks_per_beat) {
+                        tempo_changes.push((writer.current_tick, tempo));
+                    }
+                    if field.field_type == 'K' {
+                        bar_accidentals = context.key_accidentals.clone();
+                    }
                 }
 
                 Element::Tuplet(tuplet) => {
@@ -416,9 +448,9 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
                                 &bar_accidentals,
                             );
                             let midi_pitch = apply_pitch_offset(base_pitch, pitch_offset);
-                            let base_ticks = note.duration.to_ticks(unit_ticks);
+                            let base_ticks = note.duration.to_ticks(context.unit_ticks);
                             let ticks = (base_ticks * scale_num) / scale_den;
-                            writer.note_channel(midi_pitch, params.velocity, ticks, channel);
+                            writer.note_channel(midi_pitch, velocity, ticks, channel);
                             if let Some(acc) = note.accidental {
                                 bar_accidentals.insert(note.pitch, acc);
                             }
@@ -438,6 +470,17 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
         tracks.push(writer.encode_track());
     }
 
+    // Track 0: Tempo track (meta events only)
+    let mut tempo_writer = MidiWriter::new(params.ticks_per_beat, 0);
+    tempo_writer.tempo(&tune.header.tempo.clone().unwrap_or_default());
+    tempo_changes.sort_by_key(|(tick, _)| *tick);
+    tempo_changes.dedup();
+    for (tick, tempo) in &tempo_changes {
+        tempo_writer.current_tick = *tick;
+        tempo_writer.tempo(tempo);
+    }
+    tracks.insert(0, tempo_writer.encode_track());
+
     // Build multi-track MIDI file
     let mut out = Vec::new();
 
@@ -458,6 +501,51 @@ fn generate_multitrack(tune: &Tune, params: &MidiParams) -> Vec<u8> {
     out
 }
 
+/// Musical context that inline fields (`[K:]`, `[M:]`, `[L:]`, `[Q:]`) can
+/// change partway through a voice.
+struct VoiceContext {
+    key_accidentals: HashMap<NoteName, Accidental>,
+    unit_ticks: u32,
+    bar_ticks: u32,
+}
+
+impl VoiceContext {
+    fn from_header(tune: &Tune, ticks_per_beat: u16) -> Self {
+        let unit_length = tune.header.unit_length.unwrap_or_default();
+        VoiceContext {
+            key_accidentals: compute_key_accidentals(&tune.header.key),
+            unit_ticks: compute_unit_ticks(&unit_length, ticks_per_beat),
+            bar_ticks: compute_bar_ticks(tune.header.meter.as_ref(), ticks_per_beat),
+        }
+    }
+
+    /// Apply an inline field from this point forward.
+    ///
+    /// Returns the new tempo for `Q:` so the caller can emit a tempo event.
+    /// Malformed values were already reported by the parser, so feedback
+    /// from re-parsing them here is discarded.
+    fn apply(&mut self, field: &InfoField, ticks_per_beat: u16) -> Option<Tempo> {
+        let mut collector = FeedbackCollector::new();
+        match field.field_type {
+            'K' => {
+                let key = parser::parse_key_field(&field.value, &mut collector);
+                self.key_accidentals = compute_key_accidentals(&key);
+            }
+            'M' => {
+                let meter = parser::parse_meter(&field.value, &mut collector);
+                self.bar_ticks = compute_bar_ticks(Some(&meter), ticks_per_beat);
+            }
+            'L' => {
+                let unit_length = parser::parse_unit_length(&field.value, &mut collector);
+                self.unit_ticks = compute_unit_ticks(&unit_length, ticks_per_beat);
+            }
+            'Q' => return Some(parser::parse_tempo(&field.value, &mut collector)),
+            _ => {}
+        }
+        None
+    }
+}
+
 /// Convert note to MIDI pitch, applying accidentals from context
 fn note_to_midi_pitch(
     pitch: NoteName,
@@ -574,6 +662,19 @@ fn compute_unit_ticks(unit_length: &UnitLength, ticks_per_beat: u16) -> u32 {
     (ticks_per_whole * unit_length.numerator as u32) / unit_length.denominator as u32
 }
 
+/// Compute MIDI ticks per bar from the meter (4/4 when absent)
+fn compute_bar_ticks(meter: Option<&Meter>, ticks_per_beat: u16) -> u32 {
+    let (numerator, denominator) = meter.map(|m| m.to_fraction()).unwrap_or((4, 4));
+    let ticks_per_whole = ticks_per_beat as u32 * 4;
+    (ticks_per_whole * numerator as u32) / denominator.max(1) as u32
+}
+
+/// Microseconds per quarter note for the MIDI Set Tempo meta event
+fn microseconds_per_quarter(tempo: &Tempo) -> u32 {
+    let quarters_per_minute = tempo.quarter_notes_per_minute().max(1.0);
+    (60_000_000.0 / quarters_per_minute).round() as u32
+}
+
 /// MIDI file writer
 struct MidiWriter {
     ticks_per_beat: u16,
@@ -597,8 +698,8 @@ impl MidiWriter {
         }
     }
 
-    fn tempo(&mut self, bpm: u16) {
-        let us_per_beat = 60_000_000u32 / bpm as u32;
+    fn tempo(&mut self, tempo: &Tempo) {
+        let us_per_beat = microseconds_per_quarter(tempo).min(0xFF_FFFF);
         self.meta_event(
             0x51,
             vec![
@@ -732,7 +833,7 @@ fn encode_variable_length(mut value: u32) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::{Duration, Header, Meter, Note, Rest, Tempo, Voice};
+    use crate::ast::{Duration, Header, Note, Rest, Voice};
 
     #[test]
     fn test_variable_length_encoding() {
@@ -1033,6 +1134,7 @@ mod tests {
             ticks_per_beat: 480,
             channel: 9,
             program: None,
+            voice_overrides: HashMap::new(),
         };
         let midi_ch9 = generate(&result.value, &params_ch9);
         // Look for note-on: 0x99 = channel 9 note-on
@@ -1075,6 +1177,7 @@ mod tests {
             ticks_per_beat: 480,
             channel: 0,
             program: Some(56), // Trumpet
+            voice_overrides: HashMap::new(),
         };
         let midi = generate(&result.value, &params);
 
@@ -1094,6 +1197,7 @@ mod tests {
             ticks_per_beat: 480,
             channel: 0,
             program: Some(0), // Piano - but ABC says 52
+            voice_overrides: HashMap::new(),
         };
         let midi = generate(&result.value, &params);
 
@@ -1156,4 +1260,214 @@ mod tests {
             );
         }
     }
+
+    fn note_on_channels(midi: &[u8]) -> Vec<u8> {
+        let mut channels: Vec<u8> = midi
+            .windows(3)
+            .filter(|w| w[0] & 0xF0 == 0x90 && w[1] < 0x80 && w[2] == 80)
+            .map(|w| w[0] & 0x0F)
+            .collect();
+        channels.dedup();
+        channels
+    }
+
+    #[test]
+    fn test_multitrack_skips_drum_channel() {
+        let mut abc = String::from("X:1\nT:Test\nM:4/4\nL:1/4\n");
+        for v in 1..=11 {
+            abc.push_str(&format!("V:{}\n", v));
+        }
+        abc.push_str("K:C\n");
+        for v in 1..=11 {
+            abc.push_str(&format!("V:{}\nc|\n", v));
+        }
+        let result = crate::parse(&abc);
+        assert!(!result.has_errors(), "Parse errors: {:?}", result.feedback);
+
+        let midi = generate(&result.value, &MidiParams::default());
+        let channels = note_on_channels(&midi);
+        assert_eq!(channels, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 11]);
+    }
+
+    #[test]
+    fn test_multitrack_percussion_voice_uses_channel_9() {
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/4\nV:1\nV:2 clef=perc\nV:3\nK:C\nV:1\nc|\nV:2\nC|\nV:3\ne|\n";
+        let result = crate::parse(abc);
+        assert!(!result.has_errors(), "Parse errors: {:?}", result.feedback);
+
+        let midi = generate(&result.value, &MidiParams::default());
+        assert_eq!(note_on_channels(&midi), vec![0, 9, 1]);
+    }
+
+    #[test]
+    fn test_multitrack_voice_overrides() {
+        let abc = "X:1\nT:Test\n%%MIDI program 52\nM:4/4\nL:1/4\nV:S\nV:B\nK:C\nV:S\nc|\nV:B\nC,|\n";
+        let result = crate::parse(abc);
+        assert!(!result.has_errors(), "Parse errors: {:?}", result.feedback);
+
+        let mut params = MidiParams::default();
+        params.voice_overrides.insert(
+            "B".to_string(),
+            VoiceMidiParams {
+                channel: Some(5),
+                program: Some(32),
+                velocity: None,
+            },
+        );
+        let midi = generate(&result.value, &params);
+
+        assert!(midi.windows(2).any(|w| w[0] == 0xC0 && w[1] == 52));
+        assert!(midi.windows(2).any(|w| w[0] == 0xC5 && w[1] == 32));
+        assert_eq!(note_on_channels(&midi), vec![0, 5]);
+    }
+
+    #[test]
+    fn test_multitrack_single_voice_is_format_1() {
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/4\nK:C\ncde|\n";
+        let result = crate::parse(abc);
+        let midi = generate_multitrack(&result.value, &MidiParams::default());
+        assert_eq!(&midi[8..10], &[0, 1]); // format 1
+        assert_eq!(&midi[10..12], &[0, 2]); // tempo track + one voice
+    }
+
+    /// Sum of delta times in the last track, i.e. the track's length in ticks
+    fn last_track_ticks(midi: &[u8]) -> u32 {
+        let track_start = midi
+            .windows(4)
+            .rposition(|w| w == b"MTrk")
+            .expect("MIDI should contain a track")
+            + 8;
+        let mut pos = track_start;
+        let mut total = 0u32;
+        while pos < midi.len() {
+            let mut delta = 0u32;
+            loop {
+                let byte = midi[pos];
+                pos += 1;
+                delta = (delta << 7) | (byte & 0x7F) as u32;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            total += delta;
+            match midi[pos] {
+                0xFF => {
+                    let len = midi[pos + 2] as usize;
+                    pos += 3 + len;
+                }
+                status if status & 0xF0 == 0xC0 => pos += 2,
+                _ => pos += 3,
+            }
+        }
+        total
+    }
+
+    #[test]
+    fn test_unit_length_scales_note_ticks() {
+        let eighths = crate::parse("X:1\nT:Test\nM:4/4\nL:1/8\nK:C\ncde2 f/g/|\n");
+        let sixteenths = crate::parse("X:1\nT:Test\nM:4/4\nL:1/16\nK:C\ncde2 f/g/|\n");
+        assert!(!eighths.has_errors() && !sixteenths.has_errors());
+
+        let eighth_ticks = last_track_ticks(&generate(&eighths.value, &MidiParams::default()));
+        let sixteenth_ticks =
+            last_track_ticks(&generate(&sixteenths.value, &MidiParams::default()));
+
+        // c d e2 f/ g/ = 5 units
+        assert_eq!(eighth_ticks, 5 * 240);
+        assert_eq!(sixteenth_ticks, 5 * 120);
+        assert_eq!(eighth_ticks, sixteenth_ticks * 2);
+    }
+
+    #[test]
+    fn test_tempo_honors_beat_unit() {
+        let quarter = crate::parse("X:1\nT:Test\nM:6/8\nL:1/8\nQ:1/4=60\nK:C\ncde|\n");
+        let dotted = crate::parse("X:1\nT:Test\nM:6/8\nL:1/8\nQ:3/8=40\nK:C\ncde|\n");
+
+        // Set Tempo meta event: FF 51 03 tt tt tt, 1_000_000us = 0F 42 40
+        let expected = [0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40];
+        for tune in [&quarter.value, &dotted.value] {
+            let midi = generate(tune, &MidiParams::default());
+            assert!(
+                midi.windows(6).any(|w| w == expected),
+                "expected 60 quarter notes per minute tempo event"
+            );
+        }
+    }
+
+    #[test]
+    fn test_multi_measure_rest_uses_meter() {
+        let abc = "X:1\nT:Test\nM:6/8\nL:1/8\nK:C\nZ2|c|\n";
+        let result = crate::parse(abc);
+        assert!(!result.has_errors(), "Parse errors: {:?}", result.feedback);
+
+        let midi = generate(&result.value, &MidiParams::default());
+        // Two bars of 6/8 (2 * 1440) plus one eighth note
+        assert_eq!(last_track_ticks(&midi), 2 * 1440 + 240);
+    }
+
+    #[test]
+    fn test_compute_bar_ticks() {
+        let six_eight = Meter::Simple {
+            numerator: 6,
+            denominator: 8,
+        };
+        assert_eq!(compute_bar_ticks(Some(&six_eight), 480), 1440);
+        assert_eq!(compute_bar_ticks(Some(&Meter::Cut), 480), 1920);
+        assert_eq!(compute_bar_ticks(None, 480), 1920);
+    }
+
+    #[test]
+    fn test_inline_key_change_applies_forward() {
+        // F is natural in C, then sharp after [K:D]
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/4\nK:C\nF|[K:D]F|\n";
+        let result = crate::parse(abc);
+        assert!(!result.has_errors(), "Parse errors: {:?}", result.feedback);
+
+        let midi = generate(&result.value, &MidiParams::default());
+        assert!(midi.windows(2).any(|w| w[0] == 0x90 && w[1] == 65));
+        assert!(midi.windows(2).any(|w| w[0] == 0x90 && w[1] == 66));
+    }
+
+    #[test]
+    fn test_inline_unit_length_change() {
+        // Two quarter notes, then two eighth notes under [L:1/8]
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/4\nK:C\ncc[L:1/8]cc|\n";
+        let result = crate::parse(abc);
+        let midi = generate(&result.value, &MidiParams::default());
+        assert_eq!(last_track_ticks(&midi), 2 * 480 + 2 * 240);
+    }
+
+    #[test]
+    fn test_inline_tempo_change_emits_tempo_event() {
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/4\nQ:1/4=120\nK:C\ncd|[Q:1/4=60]ef|\n";
+        let result = crate::parse(abc);
+        let midi = generate(&result.value, &MidiParams::default());
+
+        // 500_000us (120bpm) then 1_000_000us (60bpm)
+        assert!(midi.windows(6).any(|w| w == [0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20]));
+        assert!(midi.windows(6).any(|w| w == [0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40]));
+    }
+
+    #[test]
+    fn test_inline_tempo_change_goes_to_tempo_track() {
+        let abc = "X:1\nT:Test\nM:4/4\nL:1/4\nV:1\nV:2\nK:C\nV:1\ncd|[Q:1/4=60]ef|\nV:2\nCD|EF|\n";
+        let result = crate::parse(abc);
+        let midi = generate(&result.value, &MidiParams::default());
+
+        let first_track = midi.windows(4).position(|w| w == b"MTrk").unwrap();
+        let second_track = first_track
+            + 4
+            + midi[first_track + 4..]
+                .windows(4)
+                .position(|w| w == b"MTrk")
+                .unwrap();
+        let tempo_track = &midi[first_track..second_track];
+        assert_eq!(
+            tempo_track
+                .windows(3)
+                .filter(|w| w == &[0xFF, 0x51, 0x03])
+                .count(),
+            2
+        );
+    }
 }
@@ -5,6 +5,9 @@ use winnow::prelude::*;
 use crate::ast::{Bar, Element, InfoField, Tuplet};
 use crate::feedback::FeedbackCollector;
 
+use super::header::{parse_meter, parse_tempo, parse_unit_length};
+use super::key::parse_key_field;
+use super::lyrics::parse_lyrics;
 use super::note::{parse_chord, parse_chord_symbol, parse_note, parse_rest};
 
 /// Skip whitespace (spaces and tabs) at the start of input, returning count
@@ -15,14 +18,21 @@ fn skip_spaces(input: &mut &str) -> usize {
 }
 
 /// Parse the body section of an ABC tune.
-pub fn parse_body(input: &str, collector: &mut FeedbackCollector) -> Vec<Element> {
+///
+/// The body starts `base_offset` bytes into the original input, on line
+/// `first_line`, so feedback positions refer to the whole tune.
+pub fn parse_body(
+    input: &str,
+    base_offset: usize,
+    first_line: usize,
+    collector: &mut FeedbackCollector,
+) -> Vec<Element> {
     let mut elements = Vec::new();
     let mut remaining = input;
-    let mut line_num = 1;
+    let mut line_num = first_line;
+    let mut line_start = 0;
 
     while !remaining.is_empty() {
-        collector.set_position(line_num, 1);
-
         // Skip leading whitespace (but not newlines)
         let space_count = skip_spaces(&mut remaining);
 
@@ -34,20 +44,45 @@ pub fn parse_body(input: &str, collector: &mut FeedbackCollector) -> Vec<Element
         if remaining.starts_with('\n') {
             remaining = &remaining[1..];
             line_num += 1;
+            line_start = input.len() - remaining.len();
             elements.push(Element::LineBreak);
             continue;
         }
         if remaining.starts_with("\r\n") {
             remaining = &remaining[2..];
             line_num += 1;
+            line_start = input.len() - remaining.len();
             elements.push(Element::LineBreak);
             continue;
         }
 
+        let offset = input.len() - remaining.len();
+        let column = input[line_start..offset].chars().count() + 1;
+        let token_len = remaining.chars().next().map_or(0, char::len_utf8);
+        collector.set_position(line_num, column);
+        collector.set_span(base_offset + offset, base_offset + offset + token_len);
+
+        // Field lines in the body (K:, M:, L:, Q:) change context like inline fields
+        if offset == line_start {
+            if let Some(value) = remaining.strip_prefix("w:") {
+                let line_end = value.find(['\r', '\n']).unwrap_or(value.len());
+                elements.push(Element::Lyrics(parse_lyrics(&value[..line_end])));
+                remaining = &value[line_end..];
+                continue;
+            }
+            if let Some(field) = try_parse_field_line(&mut remaining) {
+                validate_inline_field(&field, collector);
+                elements.push(Element::InlineField(field));
+                continue;
+            }
+        }
+
         // Check for comment or directive
         if remaining.starts_with('%') {
             // Check for %%MIDI directive in body - warn that it's ignored
             if remaining.starts_with("%%MIDI") {
+                let line_len = remaining.find(['\r', '\n']).unwrap_or(remaining.len());
+                collector.set_span(base_offset + offset, base_offset + offset + line_len);
                 collector.warning(
                     "%%MIDI directive found after K: field - move it before K: to take effect"
                 );
@@ -120,6 +155,7 @@ fn try_parse_element(input: &mut &str, collector: &mut FeedbackCollector) -> Opt
             }
             // Other inline field [M:3/4]
             if let Some(field) = try_parse_inline_field(input) {
+                validate_inline_field(&field, collector);
                 return Some(Element::InlineField(field));
             }
         }
@@ -350,6 +386,42 @@ fn try_parse_inline_field(input: &mut &str) -> Option<InfoField> {
     None
 }
 
+/// Try to parse a whole-line field (e.g. `K:D` on its own line in the body).
+///
+/// Only fields that change musical context are recognized; anything else
+/// falls through to normal element parsing.
+fn try_parse_field_line(input: &mut &str) -> Option<InfoField> {
+    let mut chars = input.chars();
+    let field_type = chars.next().filter(|c| matches!(c, 'K' | 'M' | 'L' | 'Q'))?;
+    if chars.next() != Some(':') {
+        return None;
+    }
+
+    let line_end = input.find(['\r', '\n']).unwrap_or(input.len());
+    let value = input[2..line_end].trim().to_string();
+    *input = &input[line_end..];
+    Some(InfoField { field_type, value })
+}
+
+/// Report malformed K:/M:/L:/Q: values where they appear in the body
+fn validate_inline_field(field: &InfoField, collector: &mut FeedbackCollector) {
+    match field.field_type {
+        'K' => {
+            parse_key_field(&field.value, collector);
+        }
+        'M' => {
+            parse_meter(&field.value, collector);
+        }
+        'L' => {
+            parse_unit_length(&field.value, collector);
+        }
+        'Q' => {
+            parse_tempo(&field.value, collector);
+        }
+        _ => {}
+    }
+}
+
 /// Try to parse a decoration
 fn try_parse_decoration(input: &mut &str) -> Option<crate::ast::Decoration> {
     use crate::ast::Decoration;
@@ -452,7 +524,7 @@ mod tests {
     #[test]
     fn test_parse_simple_body() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body("CDEF|", &mut collector);
+        let elements = parse_body("CDEF|", 0, 1, &mut collector);
 
         let notes: Vec<_> = elements
             .iter()
@@ -469,7 +541,7 @@ mod tests {
     #[test]
     fn test_parse_bar_types() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body("|:C:|D||E|]", &mut collector);
+        let elements = parse_body("|:C:|D||E|]", 0, 1, &mut collector);
 
         let bars: Vec<_> = elements
             .iter()
@@ -488,7 +560,7 @@ mod tests {
     #[test]
     fn test_parse_triplet() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body("(3CDE", &mut collector);
+        let elements = parse_body("(3CDE", 0, 1, &mut collector);
 
         let tuplets: Vec<_> = elements
             .iter()
@@ -507,7 +579,7 @@ mod tests {
     #[test]
     fn test_parse_grace_notes() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body("{g}A", &mut collector);
+        let elements = parse_body("{g}A", 0, 1, &mut collector);
 
         let graces: Vec<_> = elements
             .iter()
@@ -520,7 +592,7 @@ mod tests {
     #[test]
     fn test_parse_acciaccatura() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body("{/g}A", &mut collector);
+        let elements = parse_body("{/g}A", 0, 1, &mut collector);
 
         let graces: Vec<_> = elements
             .iter()
@@ -537,7 +609,7 @@ mod tests {
     #[test]
     fn test_parse_decorations() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body(".C~D!trill!E", &mut collector);
+        let elements = parse_body(".C~D!trill!E", 0, 1, &mut collector);
 
         let decorations: Vec<_> = elements
             .iter()
@@ -553,7 +625,7 @@ mod tests {
     #[test]
     fn test_parse_inline_field() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body("CD[M:3/4]EF", &mut collector);
+        let elements = parse_body("CD[M:3/4]EF", 0, 1, &mut collector);
 
         let fields: Vec<_> = elements
             .iter()
@@ -568,10 +640,40 @@ mod tests {
         assert_eq!(fields[0].value, "3/4");
     }
 
+    #[test]
+    fn test_parse_field_line_in_body() {
+        let mut collector = FeedbackCollector::new();
+        let elements = parse_body("CD|\nK:D\nFA|", 0, 1, &mut collector);
+
+        let fields: Vec<_> = elements
+            .iter()
+            .filter_map(|e| match e {
+                Element::InlineField(f) => Some(f),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].field_type, 'K');
+        assert_eq!(fields[0].value, "D");
+        assert!(collector.feedback().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_inline_field_warns() {
+        let mut collector = FeedbackCollector::new();
+        parse_body("CD[M:seven]EF", 0, 1, &mut collector);
+
+        assert!(collector
+            .feedback()
+            .iter()
+            .any(|f| f.message.contains("Invalid meter")));
+    }
+
     #[test]
     fn test_parse_comments() {
         let mut collector = FeedbackCollector::new();
-        let elements = parse_body("CD % comment\nEF", &mut collector);
+        let elements = parse_body("CD % comment\nEF", 0, 1, &mut collector);
 
         let notes: Vec<_> = elements
             .iter()
@@ -589,7 +691,7 @@ mod tests {
         use crate::feedback::FeedbackLevel;
 
         let mut collector = FeedbackCollector::new();
-        let _elements = parse_body("CD\n%%MIDI program 56\nEF", &mut collector);
+        let _elements = parse_body("CD\n%%MIDI program 56\nEF", 0, 1, &mut collector);
 
         // Should have a warning about %%MIDI in body
         let warnings: Vec<_> = collector
@@ -607,4 +709,31 @@ mod tests {
             "Warning should suggest moving before K:"
         );
     }
+
+    #[test]
+    fn test_parse_lyrics_line() {
+        use crate::ast::Syllable;
+
+        let mut collector = FeedbackCollector::new();
+        let elements = parse_body("CDE|\nw:doe a deer\nF|", 0, 1, &mut collector);
+
+        let lyrics: Vec<_> = elements
+            .iter()
+            .filter_map(|e| match e {
+                Element::Lyrics(syllables) => Some(syllables),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lyrics.len(), 1);
+        assert_eq!(lyrics[0].len(), 3);
+        assert!(matches!(&lyrics[0][2], Syllable::Text { text, .. } if text == "deer"));
+
+        // Lyric text must not leak into the music as notes
+        let notes = elements
+            .iter()
+            .filter(|e| matches!(e, Element::Note(_)))
+            .count();
+        assert_eq!(notes, 4);
+        assert!(collector.feedback().is_empty());
+    }
 }
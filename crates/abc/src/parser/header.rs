@@ -16,9 +16,11 @@ pub fn parse_header<'a>(input: &'a str, collector: &mut FeedbackCollector) -> (&
     let mut line_num = 1;
 
     for line in input.lines() {
-        collector.set_position(line_num, 1);
-
+        let line_start = input.len() - remaining.len();
         let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        collector.set_position(line_num, line[..indent].chars().count() + 1);
+        collector.set_span(line_start + indent, line_start + indent + trimmed.len());
 
         // Handle %%MIDI directives before skipping comments
         if let Some(directive) = trimmed.strip_prefix("%%MIDI") {
@@ -170,7 +172,7 @@ pub fn parse_header<'a>(input: &'a str, collector: &mut FeedbackCollector) -> (&
 }
 
 /// Parse meter field value (e.g., "4/4", "C", "C|", "6/8")
-fn parse_meter(value: &str, collector: &mut FeedbackCollector) -> Meter {
+pub(crate) fn parse_meter(value: &str, collector: &mut FeedbackCollector) -> Meter {
     let trimmed = value.trim();
 
     match trimmed {
@@ -196,7 +198,7 @@ fn parse_meter(value: &str, collector: &mut FeedbackCollector) -> Meter {
 }
 
 /// Parse unit length field value (e.g., "1/8", "1/16")
-fn parse_unit_length(value: &str, collector: &mut FeedbackCollector) -> UnitLength {
+pub(crate) fn parse_unit_length(value: &str, collector: &mut FeedbackCollector) -> UnitLength {
     if let Some((num, den)) = parse_fraction(value.trim()) {
         UnitLength {
             numerator: num,
@@ -209,7 +211,7 @@ fn parse_unit_length(value: &str, collector: &mut FeedbackCollector) -> UnitLeng
 }
 
 /// Parse tempo field value (e.g., "1/4=120", "120", "\"Allegro\" 1/4=120")
-fn parse_tempo(value: &str, collector: &mut FeedbackCollector) -> Tempo {
+pub(crate) fn parse_tempo(value: &str, collector: &mut FeedbackCollector) -> Tempo {
     let trimmed = value.trim();
 
     // Check for text in quotes
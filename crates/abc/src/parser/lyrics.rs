@@ -0,0 +1,114 @@
+//! Lyric line (`w:`) parsing for ABC notation.
+
+use crate::ast::Syllable;
+
+/// Split the value of a `w:` line into syllables.
+///
+/// Spaces end a word and `-` ends a syllable within one. `_` holds the
+/// previous syllable, `*` skips a note and `|` jumps to the next bar.
+/// `~` joins words under a single note and `\-` is a literal hyphen.
+pub fn parse_lyrics(value: &str) -> Vec<Syllable> {
+    let mut syllables = Vec::new();
+    let mut text = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' => flush_word(&mut syllables, &mut text),
+            '-' => {
+                // A bare hyphen (as in `a--b`) spends a note on the word break
+                syllables.push(Syllable::Text {
+                    text: std::mem::take(&mut text),
+                    hyphen: true,
+                });
+            }
+            '_' => {
+                flush_word(&mut syllables, &mut text);
+                syllables.push(Syllable::Hold);
+            }
+            '*' => {
+                flush_word(&mut syllables, &mut text);
+                syllables.push(Syllable::Skip);
+            }
+            '|' => {
+                flush_word(&mut syllables, &mut text);
+                syllables.push(Syllable::Bar);
+            }
+            '~' => text.push(' '),
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    text.push(escaped);
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+    flush_word(&mut syllables, &mut text);
+
+    syllables
+}
+
+/// Push the syllable collected so far as the end of a word.
+fn flush_word(syllables: &mut Vec<Syllable>, text: &mut String) {
+    if !text.is_empty() {
+        syllables.push(Syllable::Text {
+            text: std::mem::take(text),
+            hyphen: false,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(t: &str, hyphen: bool) -> Syllable {
+        Syllable::Text {
+            text: t.to_string(),
+            hyphen,
+        }
+    }
+
+    #[test]
+    fn test_parse_words_and_syllables() {
+        assert_eq!(
+            parse_lyrics("A-ma-zing grace"),
+            vec![
+                text("A", true),
+                text("ma", true),
+                text("zing", false),
+                text("grace", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_holds_skips_and_bars() {
+        assert_eq!(
+            parse_lyrics("sweet_ the * sound | that"),
+            vec![
+                text("sweet", false),
+                Syllable::Hold,
+                text("the", false),
+                Syllable::Skip,
+                text("sound", false),
+                Syllable::Bar,
+                text("that", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_joins_and_escapes() {
+        assert_eq!(
+            parse_lyrics("of~the well\\-known a--b"),
+            vec![
+                text("of the", false),
+                text("well-known", false),
+                text("a", true),
+                text("", true),
+                text("b", false),
+            ]
+        );
+    }
+}
@@ -6,12 +6,16 @@
 mod body;
 mod header;
 mod key;
+mod lyrics;
 mod note;
 
 use crate::ast::{Element, Tune, Voice};
 use crate::feedback::{FeedbackCollector, ParseResult};
 use std::collections::HashMap;
 
+pub(crate) use header::{parse_meter, parse_tempo, parse_unit_length};
+pub(crate) use key::parse_key_field;
+
 /// Parse ABC notation into a Tune AST.
 pub fn parse(input: &str) -> ParseResult<Tune> {
     let mut collector = FeedbackCollector::new();
@@ -19,8 +23,10 @@ pub fn parse(input: &str) -> ParseResult<Tune> {
     // Parse header
     let (remaining, header) = header::parse_header(input, &mut collector);
 
-    // Parse body
-    let elements = body::parse_body(remaining, &mut collector);
+    // Parse body, keeping feedback positions relative to the whole input
+    let body_offset = input.len() - remaining.len();
+    let body_line = input[..body_offset].matches('\n').count() + 1;
+    let elements = body::parse_body(remaining, body_offset, body_line, &mut collector);
 
     // Route elements to voices based on VoiceSwitch elements
     let voices = route_elements_to_voices(&header.voice_defs, elements);
@@ -499,4 +505,34 @@ mod tests {
         let track_count = u16::from_be_bytes([midi[10], midi[11]]);
         assert_eq!(track_count, 3, "Expected 3 tracks (tempo + 2 voices)");
     }
+
+    #[test]
+    fn test_body_feedback_positions() {
+        let abc = "X:1\nT:Test\nM:4/4\nK:C\nCDEF|\nGA @B|\n";
+        let result = parse(abc);
+
+        let warning = result
+            .warnings()
+            .find(|f| f.message.contains("unknown character"))
+            .expect("should warn about '@'");
+        assert_eq!(warning.line, 6);
+        assert_eq!(warning.column, 4);
+        let (start, end) = warning.span.expect("body feedback should carry a span");
+        assert_eq!(&abc[start..end], "@");
+    }
+
+    #[test]
+    fn test_header_feedback_positions() {
+        let abc = "X:1\nT:Test\n  M:seven\nK:C\nC|\n";
+        let result = parse(abc);
+
+        let warning = result
+            .warnings()
+            .find(|f| f.message.contains("Invalid meter"))
+            .expect("should warn about the meter");
+        assert_eq!(warning.line, 3);
+        assert_eq!(warning.column, 3);
+        let (start, end) = warning.span.expect("header feedback should carry a span");
+        assert_eq!(&abc[start..end], "M:seven");
+    }
 }
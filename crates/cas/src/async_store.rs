@@ -0,0 +1,244 @@
+//! AsyncFileStore: the filesystem CAS for tokio-based servers.
+//!
+//! Uses the same on-disk layout, hashing, and path logic as [`FileStore`],
+//! but performs I/O with `tokio::fs` so axum handlers can `.await` it
+//! instead of blocking a runtime worker.
+//!
+//! The sync [`FileStore`] stays the right choice for chaosgarden's RT thread,
+//! which must never touch the tokio runtime.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::fs;
+
+use crate::config::CasConfig;
+use crate::hash::ContentHash;
+use crate::metadata::{CasMetadata, CasReference};
+use crate::mime::resolve_mime;
+use crate::store::FileStore;
+
+/// Async counterpart of [`ContentStore`](crate::ContentStore).
+///
+/// Method names mirror the sync trait so switching a caller over is just
+/// a matter of adding `.await`.
+#[async_trait]
+pub trait AsyncContentStore: Send + Sync {
+    /// Store data with associated MIME type, returning the content hash.
+    ///
+    /// If the data already exists, returns the hash without writing. An empty
+    /// `mime_type` is filled in by [`detect_mime`](crate::detect_mime).
+    async fn store(&self, data: &[u8], mime_type: &str) -> Result<ContentHash>;
+
+    /// Retrieve data by its content hash.
+    ///
+    /// Returns `Ok(None)` if the hash doesn't exist.
+    async fn retrieve(&self, hash: &ContentHash) -> Result<Option<Vec<u8>>>;
+
+    /// Check if content exists without retrieving it.
+    async fn exists(&self, hash: &ContentHash) -> bool;
+
+    /// Get the filesystem path for content (if available).
+    async fn path(&self, hash: &ContentHash) -> Option<PathBuf>;
+
+    /// Get full metadata about stored content.
+    async fn inspect(&self, hash: &ContentHash) -> Result<Option<CasReference>>;
+}
+
+/// Filesystem-based content store with async I/O.
+#[derive(Debug, Clone)]
+pub struct AsyncFileStore {
+    config: CasConfig,
+}
+
+impl AsyncFileStore {
+    /// Create a new AsyncFileStore with the given configuration.
+    ///
+    /// Directory setup happens synchronously, once, at construction.
+    pub fn new(config: CasConfig) -> Result<Self> {
+        let store = FileStore::new(config)?;
+        Ok(Self::from(store))
+    }
+
+    /// Create an AsyncFileStore at a specific path.
+    pub fn at_path(path: impl Into<PathBuf>) -> Result<Self> {
+        Self::new(CasConfig::with_base_path(path))
+    }
+
+    /// Get the configuration.
+    pub fn config(&self) -> &CasConfig {
+        &self.config
+    }
+}
+
+impl From<FileStore> for AsyncFileStore {
+    fn from(store: FileStore) -> Self {
+        Self {
+            config: store.config().clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncContentStore for AsyncFileStore {
+    async fn store(&self, data: &[u8], mime_type: &str) -> Result<ContentHash> {
+        if self.config.read_only {
+            anyhow::bail!("CAS is in read-only mode");
+        }
+
+        let hash = ContentHash::from_data(data);
+        let obj_path = self.config.object_path(&hash);
+
+        if let Some(parent) = obj_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context("failed to create object prefix directory")?;
+        }
+
+        // Write object (skip if exists - content-addressed = idempotent)
+        if !fs::try_exists(&obj_path).await.unwrap_or(false) {
+            fs::write(&obj_path, data)
+                .await
+                .context("failed to write object file")?;
+        }
+
+        if self.config.store_metadata {
+            let meta_path = self.config.metadata_path(&hash);
+            if let Some(parent) = meta_path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .context("failed to create metadata prefix directory")?;
+            }
+
+            if !fs::try_exists(&meta_path).await.unwrap_or(false) {
+                let metadata = CasMetadata {
+                    mime_type: resolve_mime(data, mime_type).to_string(),
+                    size: data.len() as u64,
+                };
+                let json =
+                    serde_json::to_string(&metadata).context("failed to serialize metadata")?;
+                fs::write(&meta_path, json)
+                    .await
+                    .context("failed to write metadata file")?;
+            }
+        }
+
+        Ok(hash)
+    }
+
+    async fn retrieve(&self, hash: &ContentHash) -> Result<Option<Vec<u8>>> {
+        let path = self.config.object_path(hash);
+
+        match fs::read(&path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).context("failed to read object file"),
+        }
+    }
+
+    async fn exists(&self, hash: &ContentHash) -> bool {
+        fs::try_exists(self.config.object_path(hash))
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn path(&self, hash: &ContentHash) -> Option<PathBuf> {
+        let path = self.config.object_path(hash);
+        if fs::try_exists(&path).await.unwrap_or(false) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    async fn inspect(&self, hash: &ContentHash) -> Result<Option<CasReference>> {
+        let obj_path = self.config.object_path(hash);
+        let meta_path = self.config.metadata_path(hash);
+
+        let file_size = match fs::metadata(&obj_path).await {
+            Ok(meta) => meta.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context("failed to stat object file"),
+        };
+
+        let reference = match fs::read_to_string(&meta_path).await {
+            Ok(json) => {
+                let metadata: CasMetadata =
+                    serde_json::from_str(&json).context("failed to parse metadata")?;
+                CasReference::new(hash.clone(), metadata.mime_type, metadata.size)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                CasReference::new(hash.clone(), "application/octet-stream", file_size)
+            }
+            Err(e) => return Err(e).context("failed to read metadata file"),
+        };
+
+        Ok(Some(reference.with_path(obj_path.to_string_lossy())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::ContentStore;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_store_and_retrieve() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store = AsyncFileStore::at_path(temp_dir.path())?;
+
+        let hash = store.store(b"Hello, async World!", "text/plain").await?;
+        assert!(store.exists(&hash).await);
+
+        let retrieved = store.retrieve(&hash).await?.expect("should exist");
+        assert_eq!(retrieved, b"Hello, async World!");
+
+        let reference = store.inspect(&hash).await?.expect("should be inspectable");
+        assert_eq!(reference.mime_type, "text/plain");
+        assert_eq!(reference.size_bytes, 19);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_missing_content() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store = AsyncFileStore::at_path(temp_dir.path())?;
+
+        let missing: ContentHash = "00000000000000000000000000000000".parse()?;
+        assert!(!store.exists(&missing).await);
+        assert!(store.path(&missing).await.is_none());
+        assert!(store.retrieve(&missing).await?.is_none());
+        assert!(store.inspect(&missing).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_shares_layout_with_sync_store() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let sync_store = FileStore::at_path(temp_dir.path())?;
+        let async_store = AsyncFileStore::from(sync_store.clone());
+
+        let hash = async_store.store(b"written async", "text/plain").await?;
+        assert_eq!(sync_store.retrieve(&hash)?, Some(b"written async".to_vec()));
+
+        let hash = sync_store.store(b"written sync", "text/plain")?;
+        assert_eq!(async_store.path(&hash).await, sync_store.path(&hash));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_only_prevents_writes() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store = AsyncFileStore::new(CasConfig::read_only(temp_dir.path()))?;
+
+        let result = store.store(b"should fail", "text/plain").await;
+        assert!(result.unwrap_err().to_string().contains("read-only"));
+
+        Ok(())
+    }
+}
@@ -11,6 +11,8 @@ use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::{Path, PathBuf};
 
+use crate::hash::ContentHash;
+
 /// Configuration for Content Addressable Storage.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CasConfig {
@@ -134,6 +136,18 @@ impl CasConfig {
     pub fn staging_dir(&self) -> PathBuf {
         self.base_path.join("staging")
     }
+
+    /// Get the path where an object would be stored.
+    pub fn object_path(&self, hash: &ContentHash) -> PathBuf {
+        self.objects_dir().join(hash.prefix()).join(hash.remainder())
+    }
+
+    /// Get the path where an object's metadata sidecar would be stored.
+    pub fn metadata_path(&self, hash: &ContentHash) -> PathBuf {
+        self.metadata_dir()
+            .join(hash.prefix())
+            .join(format!("{}.json", hash.remainder()))
+    }
 }
 
 #[cfg(test)]
@@ -45,16 +45,27 @@
 //! - Writers (hootenanny, workers) create content
 //! - Readers (chaosgarden) only need read access
 //! - No locking required
+//!
+//! # Async API
+//!
+//! With the `async` feature, [`AsyncFileStore`] implements [`AsyncContentStore`]
+//! over the same layout using `tokio::fs`, for use from async handlers.
 
+#[cfg(feature = "async")]
+pub mod async_store;
 pub mod config;
 pub mod hash;
 pub mod metadata;
+pub mod mime;
 pub mod staging;
 pub mod store;
 
 // Re-exports for convenience
+#[cfg(feature = "async")]
+pub use async_store::{AsyncContentStore, AsyncFileStore};
 pub use config::CasConfig;
 pub use hash::{ContentHash, HashError};
 pub use metadata::{CasMetadata, CasReference};
+pub use mime::detect_mime;
 pub use staging::{CasAddress, SealResult, StagingChunk, StagingId};
 pub use store::{ContentStore, FileStore};
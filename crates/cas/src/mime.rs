@@ -0,0 +1,122 @@
+//! Content-type sniffing for stored bytes.
+//!
+//! Workers don't always know what they're storing. [`detect_mime`] recognizes
+//! the formats Hootenanny deals in by their magic bytes, and
+//! [`FileStore::store`](crate::FileStore) falls back to it when handed an
+//! empty MIME type.
+
+/// MIME type used when content can't be identified.
+pub const DEFAULT_MIME: &str = "application/octet-stream";
+
+/// Guess a MIME type from the leading bytes of `data`.
+///
+/// Recognizes Standard MIDI Files, WAV, Ogg, FLAC, and ABC notation (UTF-8
+/// text whose header block ends in a `K:` field). Returns `None` for
+/// anything else.
+pub fn detect_mime(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"MThd") {
+        Some("audio/midi")
+    } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE" {
+        Some("audio/wav")
+    } else if data.starts_with(b"OggS") {
+        Some("audio/ogg")
+    } else if data.starts_with(b"fLaC") {
+        Some("audio/flac")
+    } else if looks_like_abc(data) {
+        Some("text/vnd.abc")
+    } else {
+        None
+    }
+}
+
+/// The caller's MIME type, or a sniffed one if the caller left it empty.
+pub fn resolve_mime<'a>(data: &[u8], mime_type: &'a str) -> &'a str {
+    if mime_type.is_empty() {
+        detect_mime(data).unwrap_or(DEFAULT_MIME)
+    } else {
+        mime_type
+    }
+}
+
+/// ABC tunes open with a block of `X:`-style header fields ending at `K:`.
+///
+/// Requires at least one other field before `K:` so a stray "K:" line in
+/// arbitrary text doesn't qualify. Blank lines and `%` comments are skipped.
+fn looks_like_abc(data: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return false;
+    };
+
+    let mut fields = 0;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+        let mut chars = line.chars();
+        let (Some(field), Some(':')) = (chars.next(), chars.next()) else {
+            return false;
+        };
+        if !field.is_ascii_alphabetic() {
+            return false;
+        }
+        if field == 'K' {
+            return fields > 0;
+        }
+        fields += 1;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_midi() {
+        let smf = b"MThd\x00\x00\x00\x06\x00\x01\x00\x02\x01\xe0";
+        assert_eq!(detect_mime(smf), Some("audio/midi"));
+    }
+
+    #[test]
+    fn detects_wav() {
+        let header = b"RIFF\x24\x00\x00\x00WAVEfmt ";
+        assert_eq!(detect_mime(header), Some("audio/wav"));
+        // RIFF container that isn't WAVE (e.g. AVI)
+        assert_eq!(detect_mime(b"RIFF\x24\x00\x00\x00AVI LIST"), None);
+    }
+
+    #[test]
+    fn detects_ogg_and_flac() {
+        assert_eq!(detect_mime(b"OggS\x00\x02\x00\x00"), Some("audio/ogg"));
+        assert_eq!(detect_mime(b"fLaC\x00\x00\x00\x22"), Some("audio/flac"));
+    }
+
+    #[test]
+    fn detects_abc() {
+        let tune = "X:1\nT:Reel\n% comment\nM:4/4\nL:1/8\nK:D\n|:DFA dAF:|\n";
+        assert_eq!(detect_mime(tune.as_bytes()), Some("text/vnd.abc"));
+
+        // Fragments without X: still count
+        assert_eq!(detect_mime(b"M:3/4\nK:G\nGAB"), Some("text/vnd.abc"));
+    }
+
+    #[test]
+    fn rejects_unknown_content() {
+        assert_eq!(detect_mime(b""), None);
+        assert_eq!(detect_mime(b"Hello, World!"), None);
+        assert_eq!(detect_mime(b"{\"key\": \"value\"}"), None);
+        assert_eq!(detect_mime(b"K:C\nCDEF"), None);
+        assert_eq!(detect_mime(b"T:Title\nnot a header\nK:C"), None);
+        assert_eq!(detect_mime(&[0xff, 0xfe, b'X', b':']), None);
+    }
+
+    #[test]
+    fn resolve_prefers_caller_mime() {
+        assert_eq!(
+            resolve_mime(b"MThd", "application/x-custom"),
+            "application/x-custom"
+        );
+        assert_eq!(resolve_mime(b"MThd", ""), "audio/midi");
+        assert_eq!(resolve_mime(b"???", ""), DEFAULT_MIME);
+    }
+}
@@ -25,6 +25,7 @@ use anyhow::{Context, Result};
 use crate::config::CasConfig;
 use crate::hash::ContentHash;
 use crate::metadata::{CasMetadata, CasReference};
+use crate::mime::{resolve_mime, DEFAULT_MIME};
 use crate::staging::{CasAddress, SealResult, StagingChunk, StagingId};
 
 /// Trait for content storage backends.
@@ -34,7 +35,8 @@ use crate::staging::{CasAddress, SealResult, StagingChunk, StagingId};
 pub trait ContentStore: Send + Sync {
     /// Store data with associated MIME type, returning the content hash.
     ///
-    /// If the data already exists, returns the hash without writing.
+    /// If the data already exists, returns the hash without writing. An empty
+    /// `mime_type` is filled in by [`detect_mime`](crate::detect_mime).
     fn store(&self, data: &[u8], mime_type: &str) -> Result<ContentHash>;
 
     /// Retrieve data by its content hash.
@@ -97,18 +99,12 @@ impl FileStore {
 
     /// Get the path where an object would be stored.
     fn object_path(&self, hash: &ContentHash) -> PathBuf {
-        self.config
-            .objects_dir()
-            .join(hash.prefix())
-            .join(hash.remainder())
+        self.config.object_path(hash)
     }
 
     /// Get the path where metadata would be stored.
     fn metadata_path(&self, hash: &ContentHash) -> PathBuf {
-        self.config
-            .metadata_dir()
-            .join(hash.prefix())
-            .join(format!("{}.json", hash.remainder()))
+        self.config.metadata_path(hash)
     }
 
     /// Get the path where a staging file would be stored.
@@ -278,7 +274,7 @@ impl ContentStore for FileStore {
 
             if !meta_path.exists() {
                 let metadata = CasMetadata {
-                    mime_type: mime_type.to_string(),
+                    mime_type: resolve_mime(data, mime_type).to_string(),
                     size: data.len() as u64,
                 };
                 let json = serde_json::to_string(&metadata).context("failed to serialize metadata")?;
@@ -338,7 +334,7 @@ impl ContentStore for FileStore {
                 .len();
 
             Ok(Some(
-                CasReference::new(hash.clone(), "application/octet-stream", file_size)
+                CasReference::new(hash.clone(), DEFAULT_MIME, file_size)
                     .with_path(obj_path.to_string_lossy()),
             ))
         }
@@ -389,6 +385,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_empty_mime_is_sniffed() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store = FileStore::at_path(temp_dir.path())?;
+
+        let midi = store.store(b"MThd\x00\x00\x00\x06\x00\x00\x00\x01\x01\xe0", "")?;
+        let unknown = store.store(b"\x00\x01\x02\x03", "")?;
+
+        let reference = store.inspect(&midi)?.expect("should be inspectable");
+        assert_eq!(reference.mime_type, "audio/midi");
+        let reference = store.inspect(&unknown)?.expect("should be inspectable");
+        assert_eq!(reference.mime_type, DEFAULT_MIME);
+
+        Ok(())
+    }
+
     #[test]
     fn test_deduplication() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -14,10 +14,10 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
-use chaosgarden::{GardenDaemon, DaemonConfig};
+use chaosgarden::{Decision, GardenDaemon, DaemonConfig};
 use chaosgarden::ipc::capnp_server::CapnpGardenServer;
 use chaosgarden::nodes::FileCasClient;
-use hooteconf::HootConfig;
+use hooteconf::{ApprovalTimeoutDecision, HootConfig};
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
@@ -37,7 +37,15 @@ async fn main() -> Result<()> {
     let server = CapnpGardenServer::new(hoote_config.clone());
 
     // Create real daemon with state management
-    let daemon_config = DaemonConfig::default();
+    let defaults = &hoote_config.bootstrap.defaults;
+    let daemon_config = DaemonConfig {
+        approval_timeout: defaults.approval_timeout(),
+        approval_timeout_decision: match defaults.approval_timeout_decision {
+            ApprovalTimeoutDecision::Approve => Decision::Approved,
+            ApprovalTimeoutDecision::Reject => Decision::Rejected,
+        },
+        ..DaemonConfig::default()
+    };
     let mut daemon = GardenDaemon::with_config(daemon_config);
 
     // Initialize content resolver for timeline playback (loads audio from CAS)
@@ -52,6 +60,12 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Writable CAS for bounce-to-disk recordings
+    match cas::FileStore::at_path(&cas_path) {
+        Ok(store) => daemon.set_recording_store(store),
+        Err(e) => info!("Warning: CAS at {} is not writable: {} (recording disabled)", cas_path, e),
+    }
+
     let handler = Arc::new(daemon);
     info!("GardenDaemon initialized");
 
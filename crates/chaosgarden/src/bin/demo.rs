@@ -157,6 +157,9 @@ fn print_latent_event(event: &LatentEvent) {
         LatentEvent::Rejected { reason, .. } => {
             println!("   ├─ ✗ Rejected: {:?}", reason);
         }
+        LatentEvent::ApprovalTimedOut { decision, .. } => {
+            println!("   ├─ ⏱ Approval timed out → {:?}", decision);
+        }
         LatentEvent::Failed { error, .. } => {
             println!("   ├─ ⚠ Failed: {}", error);
         }
@@ -6,29 +6,44 @@
 //! - Latent lifecycle management
 //! - Snapshot export for external query evaluation
 
+use std::collections::HashSet;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
+use tokio::sync::broadcast;
 use tracing::{debug, info, trace, warn};
 use uuid::Uuid;
 
 use crate::ipc::{
-    Beat as IpcBeat, ContentType as IpcContentType, PendingApproval as IpcPendingApproval,
+    Beat as IpcBeat, ContentType as IpcContentType, IOPubEvent,
+    NodeDescriptor as IpcNodeDescriptor, PendingApproval as IpcPendingApproval, PortRef,
     RegionSummary, SampleFormat as IpcSampleFormat, ShellReply, ShellRequest,
     StreamDefinition as IpcStreamDefinition, StreamFormat as IpcStreamFormat,
 };
-use crate::external_io::{audio_ring_pair, AudioRingConsumer, AudioRingProducer};
+use crate::external_io::{
+    audio_ring_pair, AudioRingConsumer, AudioRingProducer, MidiOutputNode, MidiScheduler,
+    OverrunWatch, DEFAULT_MIDI_LOOK_AHEAD, DEFAULT_OVERRUN_WARN_THRESHOLD,
+};
 use crate::mixer::{MixerChannel, MixerState};
 use crate::monitor_input::{MonitorInputConfig, MonitorInputStream};
-use crate::nodes::ContentResolver;
+use crate::nodes::{AudioFileNode, ContentResolver, PlaylistNode};
 use crate::pipewire_output::{MonitorMixState, PipeWireOutputConfig, PipeWireOutputStream};
 use crate::rave_streaming::RaveStreamingClient;
-use crate::playback::{CompiledGraph, PlaybackEngine};
-use crate::primitives::{Behavior, ContentType};
+use crate::playback::{
+    CompiledGraph, LoopRegion, PendingMidiEvent, PlaybackEngine, DEFAULT_DECLICK_FRAMES,
+    DEFAULT_SECTION_CROSSFADE_BEATS,
+};
+use crate::primitives::{Behavior, BoxedNode, ContentType};
+use crate::recorder::{RecordSource, Recorder, Recording};
 use crate::stream_io::{
     SampleFormat, StreamDefinition, StreamFormat, StreamManager, StreamUri,
 };
-use crate::{Beat, Graph, LatentConfig, LatentManager, Region, TempoMap, Tick, TickClock};
+use crate::graph::{GraphDelta, GraphSnapshot};
+use crate::{
+    Beat, Decision, Graph, LatentConfig, LatentManager, Region, Section, TempoMap, Tick,
+    TickClock,
+};
 
 /// Transport state
 #[derive(Debug, Clone, Default)]
@@ -37,12 +52,49 @@ pub struct TransportState {
     pub position: Beat,
 }
 
+/// Buffered IOPub events per subscriber before it starts lagging
+const IOPUB_CAPACITY: usize = 256;
+
+/// A bounce-to-disk recording in progress
+struct ActiveRecording {
+    recorder: Recorder,
+    /// Audio regions heard while recording, for lineage
+    heard_regions: HashSet<Uuid>,
+    /// Transport stopped; finish once the stop ramp has been captured
+    finish_after_ramp: bool,
+}
+
+/// Timeline MIDI bound for one output port pattern (None = every output)
+struct MidiLane {
+    scheduler: MidiScheduler,
+    output: MidiOutputNode,
+}
+
+impl MidiLane {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            scheduler: MidiScheduler::new(sample_rate, DEFAULT_MIDI_LOOK_AHEAD),
+            output: MidiOutputNode::new("timeline-midi".to_string()),
+        }
+    }
+}
+
 /// Configuration for the daemon
 #[derive(Debug, Clone)]
 pub struct DaemonConfig {
     pub sample_rate: u32,
     pub buffer_size: usize,
     pub auto_approve_tools: Vec<String>,
+    /// Fade-out length in frames on stop and at the end of audio files
+    /// (0 = hard cut)
+    pub declick_frames: usize,
+    /// Crossfade length in beats at section changes, for sections without
+    /// their own `crossfade_beats` hint (0 = hard cut)
+    pub section_crossfade_beats: f64,
+    /// How long resolved latent regions wait for approval (None = forever)
+    pub approval_timeout: Option<Duration>,
+    /// What an approval timeout decides
+    pub approval_timeout_decision: Decision,
 }
 
 impl Default for DaemonConfig {
@@ -51,6 +103,10 @@ impl Default for DaemonConfig {
             sample_rate: 44100,
             buffer_size: 256,
             auto_approve_tools: vec![],
+            declick_frames: DEFAULT_DECLICK_FRAMES,
+            section_crossfade_beats: DEFAULT_SECTION_CROSSFADE_BEATS,
+            approval_timeout: None,
+            approval_timeout_decision: Decision::Rejected,
         }
     }
 }
@@ -103,21 +159,40 @@ pub struct GardenDaemon {
     // Audio mixer state (control plane for all mixing)
     // The mixer holds channels with gain/pan/mute/solo controls.
     // Ring buffers are transport and stay separate.
-    #[allow(dead_code)]
     mixer: MixerState,
     // Reference to the monitor channel in the mixer (for convenience)
     monitor_channel: Arc<MixerChannel>,
+    // Meters the engine's rendered output (the timeline isn't faded by the mixer)
+    timeline_channel: Arc<MixerChannel>,
 
     // === Playback Engine (Phase 3) ===
     // Content resolver for loading audio from CAS
     content_resolver: Option<Arc<dyn ContentResolver>>,
     // Playback engine processes regions and produces audio
     playback_engine: RwLock<Option<PlaybackEngine>>,
+    // Transport loop region, kept here so it survives engine re-creation
+    loop_region: RwLock<Option<LoopRegion>>,
+    // Stop/end-of-file fade length applied to each new engine
+    declick_frames: usize,
+    // Arrangement sections, kept here so they survive engine re-creation
+    sections: RwLock<Vec<Section>>,
+    // Default crossfade at section changes applied to each new engine
+    section_crossfade_beats: f64,
     // Compiled graph for RT processing (currently empty - placeholder for future graph routing)
     compiled_graph: RwLock<Option<CompiledGraph>>,
     // Timeline audio producer (written by tick(), consumer is in RT callback)
     // Lock-free SPSC ring - producer writes rendered audio, consumer reads in RT
     timeline_producer: Mutex<Option<AudioRingProducer>>,
+    // Warns when the timeline ring keeps overrunning (reset with each new ring)
+    timeline_overrun_watch: Mutex<OverrunWatch>,
+
+    // Bounce to disk: writable CAS for finished recordings, and the
+    // recording in progress (its tap lives in the playback engine)
+    recording_store: Option<cas::FileStore>,
+    recording: Mutex<Option<ActiveRecording>>,
+
+    // Events for the IOPub socket (the server holds a subscription)
+    iopub: broadcast::Sender<IOPubEvent>,
 
     // Streaming tap for WebSocket/HTTP audio streaming (lock-free SPSC)
     // Consumer is read by get_audio_snapshot(), producer is moved to RT callback
@@ -129,6 +204,9 @@ pub struct GardenDaemon {
     // Monotonic version counter for snapshot invalidation
     snapshot_version: std::sync::atomic::AtomicU64,
 
+    // Graph as of the last graph broadcast, baseline for the next delta
+    last_graph_broadcast: Mutex<GraphSnapshot>,
+
     // RAVE streaming client for realtime neural audio processing
     rave_streaming: Mutex<RaveStreamingClient>,
     // RAVE audio ring buffers (created when streaming starts, consumed by RT callback)
@@ -140,6 +218,8 @@ pub struct GardenDaemon {
 
     // MIDI I/O manager (direct ALSA for low latency)
     midi_manager: crate::midi_io::MidiIOManager,
+    // Timeline MIDI waiting for the tick clock, per port pattern
+    midi_lanes: Mutex<std::collections::HashMap<Option<String>, MidiLane>>,
 }
 
 impl GardenDaemon {
@@ -158,10 +238,15 @@ impl GardenDaemon {
             auto_approve_tools: config.auto_approve_tools.iter().cloned().collect(),
             default_mix_in: Default::default(),
             max_concurrent_jobs: 4,
+            approval_timeout: config.approval_timeout,
+            timeout_decision: config.approval_timeout_decision,
         };
 
-        // Create a no-op IOPub publisher for now
-        let publisher = Arc::new(NoOpPublisher);
+        // Latent events go out on IOPub with the daemon's own events
+        let iopub = broadcast::channel(IOPUB_CAPACITY).0;
+        let publisher = Arc::new(LatentIOPub {
+            iopub: iopub.clone(),
+        });
         let latent_manager = Arc::new(RwLock::new(LatentManager::new(latent_config, publisher)));
 
         // Create stream manager and publisher
@@ -177,6 +262,7 @@ impl GardenDaemon {
         // Default: enabled but muted until attach_input is called
         monitor_channel.enabled.store(false, Ordering::Relaxed);
         monitor_channel.set_gain(0.8); // Default 80% gain
+        let timeline_channel = mixer.add_channel(MixerChannel::new("timeline"));
 
         // Create streaming tap (lock-free SPSC) - ~500ms of stereo audio at 48kHz
         // Producer moves to RT callback when output is attached, consumer stays for snapshots
@@ -199,21 +285,35 @@ impl GardenDaemon {
             monitor_consumer: Mutex::new(None),
             mixer,
             monitor_channel,
+            timeline_channel,
             // Playback engine fields - initialized lazily when content_resolver is set
             content_resolver: None,
             playback_engine: RwLock::new(None),
+            loop_region: RwLock::new(None),
+            declick_frames: config.declick_frames,
+            sections: RwLock::new(Vec::new()),
+            section_crossfade_beats: config.section_crossfade_beats,
             compiled_graph: RwLock::new(None),
             timeline_producer: Mutex::new(None),
+            timeline_overrun_watch: Mutex::new(OverrunWatch::new(
+                "timeline",
+                DEFAULT_OVERRUN_WARN_THRESHOLD,
+            )),
+            recording_store: None,
+            recording: Mutex::new(None),
+            iopub,
             streaming_tap_consumer: Mutex::new(streaming_tap_consumer),
             streaming_tap_producer: Mutex::new(Some(streaming_tap_producer)),
             streaming_tap_sample_rate,
             snapshot_version: std::sync::atomic::AtomicU64::new(0),
+            last_graph_broadcast: Mutex::new(GraphSnapshot::default()),
             rave_streaming: Mutex::new(RaveStreamingClient::new()),
             rave_input_producer: Arc::new(Mutex::new(None::<AudioRingProducer>)),
             rave_output_consumer: Arc::new(Mutex::new(None::<AudioRingConsumer>)),
             midi_manager: crate::midi_io::MidiIOManager::with_publisher(
                 Arc::new(crate::midi_io::LoggingMidiPublisher)
             ),
+            midi_lanes: Mutex::new(std::collections::HashMap::new()),
         }
     }
 
@@ -224,12 +324,16 @@ impl GardenDaemon {
     pub fn set_content_resolver(&mut self, resolver: Arc<dyn ContentResolver>) {
         // Clone the Arc<RwLock<TempoMap>>, then get an Arc<TempoMap> from it
         let tempo_map_snapshot = Arc::new(self.tempo_map.read().unwrap().clone());
-        let engine = PlaybackEngine::with_resolver(
+        let mut engine = PlaybackEngine::with_resolver(
             48000,  // Default sample rate (will match PipeWire)
             256,    // Default buffer size
             tempo_map_snapshot,
             Arc::clone(&resolver),
         );
+        engine.set_loop(*self.loop_region.read().unwrap());
+        engine.set_declick_frames(self.declick_frames);
+        engine.set_sections(self.sections.read().unwrap().clone());
+        engine.set_crossfade_beats(self.section_crossfade_beats);
         self.content_resolver = Some(resolver);
         *self.playback_engine.write().unwrap() = Some(engine);
 
@@ -242,6 +346,21 @@ impl GardenDaemon {
         info!("Content resolver set, playback engine initialized");
     }
 
+    /// Set the writable CAS that finished recordings are stored in
+    pub fn set_recording_store(&mut self, store: cas::FileStore) {
+        self.recording_store = Some(store);
+    }
+
+    /// Subscribe to events published on IOPub
+    pub fn subscribe(&self) -> broadcast::Receiver<IOPubEvent> {
+        self.iopub.subscribe()
+    }
+
+    /// Publish an IOPub event (dropped if nobody is subscribed)
+    fn publish(&self, event: IOPubEvent) {
+        let _ = self.iopub.send(event);
+    }
+
     // === Transport control methods ===
     // These are called by handle_shell (tested) and will be wired to Cap'n Proto
     // server once playback integration is complete. See 13-wire-daemon.md.
@@ -269,6 +388,7 @@ impl GardenDaemon {
         if let Some(ref mut engine) = *self.playback_engine.write().unwrap() {
             engine.pause();
         }
+        self.clear_scheduled_midi();
 
         info!("Playback paused at beat {}", transport.position.0);
     }
@@ -288,17 +408,202 @@ impl GardenDaemon {
         info!("Playback stopped");
     }
 
-    fn seek(&self, beat: Beat) {
+    /// Seek the transport, returning the resolved beat position.
+    fn seek(&self, beat: Beat) -> Beat {
+        let beat = Beat(beat.0.max(0.0));
         self.tick_clock.write().unwrap().seek(beat);
         let mut transport = self.transport.write().unwrap();
         transport.position = beat;
 
         // Sync playback engine
         if let Some(ref mut engine) = *self.playback_engine.write().unwrap() {
-            engine.seek(beat);
+            let resolved = engine.seek(beat);
+            info!(
+                "Seeked to beat {} (sample {})",
+                resolved.beats.0, resolved.samples.0
+            );
+        } else {
+            info!("Seeked to beat {}", beat.0);
+        }
+        self.clear_scheduled_midi();
+
+        beat
+    }
+
+    /// Announce the transport state and position on IOPub
+    fn publish_transport(&self, state: &str) {
+        let position = self.transport.read().unwrap().position;
+        let tempo_map = self.tempo_map.read().unwrap();
+        let tick = tempo_map.beat_to_tick(position);
+        self.publish(IOPubEvent::TransportStateChanged {
+            state: state.to_string(),
+            position_beats: position.0,
+            tempo_bpm: tempo_map.tempo_at(tick),
+            beats_per_bar: u32::from(tempo_map.time_sig_at(tick).numerator),
+        });
+    }
+
+    /// Set the transport loop region (end None = loop at the last region's end)
+    pub fn set_loop(
+        &self,
+        start_beats: f64,
+        end_beats: Option<f64>,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let loop_region = LoopRegion::new(Beat(start_beats), end_beats.map(Beat), enabled)
+            .map_err(|e| e.to_string())?;
+        *self.loop_region.write().unwrap() = Some(loop_region);
+
+        if let Some(ref mut engine) = *self.playback_engine.write().unwrap() {
+            engine.set_loop(Some(loop_region));
+        }
+
+        info!(
+            "Loop {} at beats {}..{}",
+            if enabled { "enabled" } else { "disabled" },
+            start_beats,
+            end_beats.map_or("end".to_string(), |end| end.to_string())
+        );
+        Ok(())
+    }
+
+    /// Set the arrangement sections playback crossfades between, usually
+    /// a project's `timeline.sections`
+    pub fn set_sections(&self, sections: Vec<Section>) {
+        info!("Set {} timeline sections", sections.len());
+        if let Some(ref mut engine) = *self.playback_engine.write().unwrap() {
+            engine.set_sections(sections.clone());
+        }
+        *self.sections.write().unwrap() = sections;
+    }
+
+    /// Arm or disarm bounce-to-disk
+    ///
+    /// `bus` is the graph node ID of a bus's mixer node; None records the
+    /// master output. Disarming stores the recording in CAS and returns its
+    /// details; arming returns the source being recorded.
+    pub fn set_recording(
+        &self,
+        enabled: bool,
+        bus: Option<String>,
+    ) -> Result<serde_json::Value, String> {
+        if !enabled {
+            return Ok(match self.finish_recording()? {
+                Some(recording) => recording_json(&recording),
+                None => serde_json::json!({ "recording": false }),
+            });
+        }
+
+        if let Some(current) = self.recording.lock().unwrap().as_ref() {
+            return Err(format!("already recording {}", current.recorder.source()));
+        }
+        if self.recording_store.is_none() {
+            return Err("no CAS store configured for recording".to_string());
+        }
+
+        let source = match bus {
+            Some(bus) => {
+                let id = Uuid::parse_str(&bus)
+                    .map_err(|e| format!("invalid bus id {}: {}", bus, e))?;
+                let has_output = self
+                    .compiled_graph
+                    .read()
+                    .unwrap()
+                    .as_ref()
+                    .is_some_and(|graph| graph.node_output(id).is_some());
+                if !has_output {
+                    return Err(format!("bus {} has no audio output in the graph", id));
+                }
+                RecordSource::Node(id)
+            }
+            None => RecordSource::Master,
+        };
+
+        // Lock order is engine, then recording (process_playback does the same)
+        let mut engine_guard = self.playback_engine.write().unwrap();
+        let engine = engine_guard
+            .as_mut()
+            .ok_or_else(|| "playback engine not initialized".to_string())?;
+        if engine.is_recording() {
+            return Err("already recording".to_string());
+        }
+        let (recorder, tap) =
+            Recorder::start(source, engine.sample_rate()).map_err(|e| e.to_string())?;
+        engine.arm_record(tap);
+
+        *self.recording.lock().unwrap() = Some(ActiveRecording {
+            recorder,
+            heard_regions: HashSet::new(),
+            finish_after_ramp: false,
+        });
+        info!("Recording armed on {}", source);
+        Ok(serde_json::json!({ "recording": true, "source": source.to_string() }))
+    }
+
+    /// Disarm recording and store it in CAS, publishing `ArtifactCreated`.
+    /// Returns None if nothing was recording.
+    fn finish_recording(&self) -> Result<Option<Recording>, String> {
+        let store = self
+            .recording_store
+            .as_ref()
+            .ok_or_else(|| "no CAS store configured for recording".to_string())?;
+        let Some((recorder, parents)) = self.detach_recording() else {
+            return Ok(None);
+        };
+        store_recording(store, recorder, parents, &self.iopub).map(Some)
+    }
+
+    /// Finish a stopped recording without blocking the caller
+    ///
+    /// Joining the worker and writing the WAV can take a while, so tick()
+    /// hands that to a thread and keeps feeding the timeline ring.
+    fn finish_recording_in_background(&self) {
+        let Some(store) = self.recording_store.clone() else {
+            return;
+        };
+        let Some((recorder, parents)) = self.detach_recording() else {
+            return;
+        };
+        let iopub = self.iopub.clone();
+        let spawned = std::thread::Builder::new()
+            .name("chaosgarden-bounce".to_string())
+            .spawn(move || {
+                if let Err(e) = store_recording(&store, recorder, parents, &iopub) {
+                    warn!("Failed to store recording: {}", e);
+                }
+            });
+        if let Err(e) = spawned {
+            warn!("Failed to spawn recording finisher: {}", e);
+        }
+    }
+
+    /// Take the active recording and detach its tap from the engine
+    ///
+    /// Returns the recorder along with the content hashes of the audio
+    /// regions heard while recording (its lineage parents).
+    fn detach_recording(&self) -> Option<(Recorder, Vec<String>)> {
+        let active = self.recording.lock().unwrap().take()?;
+
+        // Detach the tap before finishing so the worker sees a closed stream
+        if let Some(ref mut engine) = *self.playback_engine.write().unwrap() {
+            drop(engine.take_record_tap());
         }
 
-        info!("Seeked to beat {}", beat.0);
+        let mut parents: Vec<String> = self
+            .regions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|region| active.heard_regions.contains(&region.id))
+            .filter_map(|region| match &region.behavior {
+                Behavior::PlayContent { content_hash, .. } => Some(content_hash.clone()),
+                _ => None,
+            })
+            .collect();
+        parents.sort();
+        parents.dedup();
+
+        Some((active.recorder, parents))
     }
 
     fn set_tempo(&self, bpm: f64) {
@@ -313,6 +618,85 @@ impl GardenDaemon {
         (transport.playing, transport.position, tempo)
     }
 
+    /// Edit the audio graph and announce the change on IOPub.
+    ///
+    /// Every graph edit goes through here, so subscribers can keep a cached
+    /// graph current with `GardenSnapshot::apply_graph_delta`. The baseline
+    /// starts empty, so the first delta carries the whole graph.
+    pub fn update_graph<R>(&self, edit: impl FnOnce(&mut Graph) -> R) -> R {
+        // Held across the edit so deltas go out in the order they were made
+        let mut last = self.last_graph_broadcast.lock().unwrap();
+        let (result, current) = {
+            let mut graph = self.graph.write().unwrap();
+            let result = edit(&mut graph);
+            (result, graph.snapshot())
+        };
+
+        let delta = current.diff(&last);
+        if !delta.is_empty() {
+            self.publish(IOPubEvent::GraphDelta {
+                delta: graph_delta_to_snapshot(&delta),
+            });
+        }
+        *last = current;
+        result
+    }
+
+    /// Build a node from its shell descriptor and add it to the graph.
+    ///
+    /// `audio_file` takes `{"content_hash": ...}` and `playlist` takes
+    /// `{"content_hashes": [...]}`; both need a content resolver.
+    fn add_node(&self, node: &IpcNodeDescriptor) -> Result<Uuid, String> {
+        let resolver = self
+            .content_resolver
+            .clone()
+            .ok_or("No content resolver configured")?;
+        let boxed: BoxedNode = match node.node_type.as_str() {
+            "audio_file" => {
+                let hash = node
+                    .config
+                    .get("content_hash")
+                    .and_then(|v| v.as_str())
+                    .ok_or("audio_file node needs config.content_hash")?;
+                Box::new(AudioFileNode::new(hash, resolver))
+            }
+            "playlist" => {
+                let hashes = node.config.get("content_hashes").cloned().unwrap_or_default();
+                let hashes: Vec<String> = serde_json::from_value(hashes)
+                    .map_err(|e| format!("playlist node needs config.content_hashes: {}", e))?;
+                Box::new(PlaylistNode::new(hashes, resolver))
+            }
+            other => return Err(format!("Unknown node type: {}", other)),
+        };
+
+        let node_id = boxed.descriptor().id;
+        self.update_graph(|graph| graph.add_node(boxed));
+        info!("Added {} node {} ({})", node.node_type, node_id, node.name);
+        Ok(node_id)
+    }
+
+    fn remove_node(&self, node_id: Uuid) -> bool {
+        let removed = self.update_graph(|graph| graph.remove_node(node_id)).is_some();
+        if removed {
+            info!("Removed node {}", node_id);
+        } else {
+            warn!("Node {} not found for removal", node_id);
+        }
+        removed
+    }
+
+    fn connect_nodes(&self, source: &PortRef, dest: &PortRef) -> Result<(), String> {
+        self.update_graph(|graph| {
+            graph.connect(source.node_id, &source.port_name, dest.node_id, &dest.port_name)
+        })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+
+    fn disconnect_nodes(&self, source: &PortRef, dest: &PortRef) -> bool {
+        self.update_graph(|graph| graph.disconnect(source.node_id, dest.node_id))
+    }
+
     /// Build a full state snapshot for Trustfall query evaluation in hootenanny.
     ///
     /// This collects all queryable state into a GardenSnapshot struct that can be
@@ -340,37 +724,8 @@ impl GardenDaemon {
         // Graph
         let graph = self.graph.read().unwrap();
         let graph_snapshot = graph.snapshot();
-        let nodes: Vec<GraphNode> = graph_snapshot
-            .nodes
-            .iter()
-            .map(|n| GraphNode {
-                id: n.id.to_string(),
-                name: n.name.clone(),
-                type_id: n.type_id.clone(),
-                inputs: n.inputs.iter().map(|p| Port {
-                    name: p.name.clone(),
-                    signal_type: signal_type_to_snapshot(&p.signal_type),
-                }).collect(),
-                outputs: n.outputs.iter().map(|p| Port {
-                    name: p.name.clone(),
-                    signal_type: signal_type_to_snapshot(&p.signal_type),
-                }).collect(),
-                latency_samples: n.latency_samples as u32,
-                can_realtime: n.capabilities.realtime,
-                can_offline: n.capabilities.offline,
-            })
-            .collect();
-
-        let edges: Vec<GraphEdge> = graph_snapshot
-            .edges
-            .iter()
-            .map(|e| GraphEdge {
-                source_id: e.source_id.to_string(),
-                source_port: e.source_port.clone(),
-                dest_id: e.dest_id.to_string(),
-                dest_port: e.dest_port.clone(),
-            })
-            .collect();
+        let nodes: Vec<GraphNode> = graph_snapshot.nodes.iter().map(node_to_snapshot).collect();
+        let edges: Vec<GraphEdge> = graph_snapshot.edges.iter().map(edge_to_snapshot).collect();
 
         // Latent jobs and approvals
         let latent_manager = self.latent_manager.read().unwrap();
@@ -394,14 +749,9 @@ impl GardenDaemon {
             })
             .collect();
 
-        let pending_approvals: Vec<ApprovalInfo> = latent_manager
-            .pending_approvals()
+        let pending_approvals: Vec<ApprovalInfo> = wall_clock_approvals(&latent_manager)
             .iter()
-            .map(|a| ApprovalInfo {
-                region_id: a.region_id.to_string(),
-                content_hash: a.content_hash.clone(),
-                content_type: content_type_to_snapshot(&a.content_type),
-            })
+            .map(ApprovalInfo::from_pending)
             .collect();
 
         // Tempo map
@@ -563,9 +913,53 @@ impl GardenDaemon {
             transport.playing
         };
 
+        // Keep rendering after a stop until the engine's declick ramp is done
+        let declicking = !is_playing
+            && self
+                .playback_engine
+                .read()
+                .unwrap()
+                .as_ref()
+                .is_some_and(|engine| engine.is_stopping());
+
         // Process playback engine if playing and we have all the pieces
-        if is_playing {
+        if is_playing || declicking {
             self.process_playback();
+        } else {
+            self.timeline_channel.record_levels(&[], 1.0);
+        }
+        if is_playing {
+            self.flush_midi(self.quantum_frames());
+        }
+
+        for meter in self.mixer.meter_updates(Instant::now()) {
+            self.publish(meter);
+        }
+
+        // A stop finishes the recording once the fade-out has been captured
+        let finish = self
+            .recording
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|active| active.finish_after_ramp && !declicking);
+        if finish {
+            self.finish_recording_in_background();
+        }
+
+        self.expire_approvals(Instant::now());
+    }
+
+    /// Apply the timeout decision to latent approvals nobody answered
+    fn expire_approvals(&self, now: Instant) {
+        let expired = self.latent_manager.read().unwrap().has_expired_approvals(now);
+        if !expired {
+            return;
+        }
+        let mut regions = self.regions.write().unwrap();
+        let mut latent_manager = self.latent_manager.write().unwrap();
+        for event in latent_manager.expire_approvals(now, &mut regions) {
+            info!("Latent approval timed out: {:?}", event);
         }
     }
 
@@ -618,27 +1012,110 @@ impl GardenDaemon {
                 // the final mix to both PipeWire output and streaming tap
                 // AudioBuffer.samples is interleaved [L, R, L, R, ...]
                 producer.write(&output_buffer.samples);
+                self.timeline_channel
+                    .record_levels(&output_buffer.samples, 1.0);
+
+                if let Ok(mut watch) = self.timeline_overrun_watch.lock() {
+                    if let Some(hooteproto::Broadcast::Log { message, .. }) =
+                        watch.check(producer.stats())
+                    {
+                        warn!("{}", message);
+                        self.publish(IOPubEvent::Warning { message });
+                    }
+                }
             }
             Err(e) => {
                 debug!("Playback process error: {}", e);
             }
         }
 
-        // Process MIDI events and send to external hardware
-        // Errors are logged at trace level to avoid impacting the hot path
-        let pending_midi = engine.pending_midi_events();
-        for event in pending_midi {
-            let result = match &event.port_pattern {
-                Some(pattern) => self.midi_manager.send_to(pattern, &event.message),
-                None => self.midi_manager.send_to_all(&event.message),
-            };
-            if let Err(e) = result {
-                trace!("MIDI send error: {:?}", e);
+        if let Some(active) = self.recording.lock().unwrap().as_mut() {
+            active.heard_regions.extend(engine.active_audio_region_ids());
+        }
+
+        // A loop wrap jumps the engine back; keep the tick clock in step so
+        // transport position follows it
+        for marker in engine.take_markers() {
+            debug!("Marker reached: {:?}", marker);
+            if let hooteproto::Broadcast::MarkerReached {
+                position_beats,
+                marker_type,
+                metadata,
+            } = marker
+            {
+                if marker_type == "loop" {
+                    self.tick_clock.write().unwrap().seek(engine.position().beats);
+                }
+                self.publish(IOPubEvent::MarkerReached {
+                    position_beats,
+                    marker_type,
+                    metadata,
+                });
             }
         }
+
+        // The engine renders ahead of the tick clock, so its MIDI is
+        // scheduled at the engine position and sent as the clock reaches it
+        let beat = engine.position().beats;
+        let sample_rate = engine.sample_rate();
+        self.schedule_midi(beat, sample_rate, engine.pending_midi_events());
         // Note: producer_guard dropped here, releasing the Mutex
     }
 
+    /// Queue timeline MIDI at `beat` on the scheduler for its port
+    fn schedule_midi(&self, beat: Beat, sample_rate: u32, events: Vec<PendingMidiEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        let mut lanes = self.midi_lanes.lock().unwrap();
+        for event in events {
+            lanes
+                .entry(event.port_pattern)
+                .or_insert_with(|| MidiLane::new(sample_rate))
+                .scheduler
+                .schedule(beat, event.message);
+        }
+    }
+
+    /// Send scheduled MIDI falling within the next `frames` frames
+    ///
+    /// Events inside the look-ahead window but past this quantum stay on
+    /// their lane's output node for a later tick.
+    fn flush_midi(&self, frames: usize) {
+        let mut lanes = self.midi_lanes.lock().unwrap();
+        if lanes.is_empty() {
+            return;
+        }
+        let clock = self.tick_clock.read().unwrap();
+        for (pattern, lane) in lanes.iter_mut() {
+            lane.scheduler.flush(&clock, &lane.output);
+            // Errors are logged at trace level to avoid impacting the hot path
+            for event in lane.output.take_block(frames) {
+                let result = match pattern {
+                    Some(pattern) => self.midi_manager.send_to(pattern, &event.message),
+                    None => self.midi_manager.send_to_all(&event.message),
+                };
+                if let Err(e) = result {
+                    trace!("MIDI send error: {:?}", e);
+                }
+            }
+        }
+    }
+
+    /// Drop scheduled MIDI that hasn't been sent (the timeline moved)
+    fn clear_scheduled_midi(&self) {
+        self.midi_lanes.lock().unwrap().clear();
+    }
+
+    /// Frames rendered per tick (the engine's block size)
+    fn quantum_frames(&self) -> usize {
+        self.playback_engine
+            .read()
+            .unwrap()
+            .as_ref()
+            .map_or(256, |engine| engine.buffer_size())
+    }
+
     // === Audio output attachment methods ===
 
     /// Attach a PipeWire audio output device
@@ -695,8 +1172,7 @@ impl GardenDaemon {
             // Use the mixer channel's atomics for RT-safe control
             let monitor_state = MonitorMixState {
                 consumer,
-                enabled: Arc::clone(&self.monitor_channel.enabled),
-                gain: Arc::clone(&self.monitor_channel.gain),
+                channel: Arc::clone(&self.monitor_channel),
             };
 
             info!("Creating output stream with RT monitor mixing (lock-free)");
@@ -716,6 +1192,8 @@ impl GardenDaemon {
 
         // Store the timeline producer for tick() to write to (lock-free!)
         *self.timeline_producer.lock().unwrap() = Some(timeline_producer);
+        *self.timeline_overrun_watch.lock().unwrap() =
+            OverrunWatch::new("timeline", DEFAULT_OVERRUN_WARN_THRESHOLD);
 
         *self.audio_output.write().unwrap() = Some(stream);
         info!("Audio output attached (lock-free timeline available for playback)");
@@ -1616,19 +2094,7 @@ impl GardenDaemon {
     }
 
     fn get_pending_approvals(&self) -> Vec<IpcPendingApproval> {
-        let latent_manager = self.latent_manager.read().unwrap();
-        latent_manager
-            .pending_approvals()
-            .into_iter()
-            .map(|pa| IpcPendingApproval {
-                region_id: pa.region_id,
-                artifact_id: pa.artifact_id.clone(),
-                content_hash: pa.content_hash.clone(),
-                content_type: convert_content_type_to_ipc(pa.content_type),
-                // NOTE: Using current time as approximation - PendingApproval doesn't track resolution timestamp yet
-                resolved_at: chrono::Utc::now(),
-            })
-            .collect()
+        wall_clock_approvals(&self.latent_manager.read().unwrap())
     }
 
     /// Handle stream start command
@@ -1768,26 +2234,34 @@ impl GardenDaemon {
         match req {
             ShellRequest::Play => {
                 self.play();
+                self.publish_transport("playing");
                 ShellReply::Ok {
                     result: serde_json::Value::Null,
                 }
             }
             ShellRequest::Pause => {
                 self.pause();
+                self.publish_transport("paused");
                 ShellReply::Ok {
                     result: serde_json::Value::Null,
                 }
             }
             ShellRequest::Stop => {
                 self.pause(); // Stop is same as pause in current implementation
+                if let Some(active) = self.recording.lock().unwrap().as_mut() {
+                    active.finish_after_ramp = true;
+                }
+                self.publish_transport("stopped");
                 ShellReply::Ok {
                     result: serde_json::Value::Null,
                 }
             }
             ShellRequest::Seek { beat } => {
-                self.seek(Beat(beat.0));
+                let position = self.seek(Beat(beat.0));
+                let playing = self.transport.read().unwrap().playing;
+                self.publish_transport(if playing { "playing" } else { "paused" });
                 ShellReply::Ok {
-                    result: serde_json::Value::Null,
+                    result: serde_json::json!({"position_beats": position.0}),
                 }
             }
             ShellRequest::SetTempo { bpm } => {
@@ -1851,6 +2325,36 @@ impl GardenDaemon {
                 ShellReply::PendingApprovals { approvals }
             }
 
+            // Graph edits, announced as GraphDelta on IOPub
+            ShellRequest::AddNode { node } => match self.add_node(&node) {
+                Ok(node_id) => ShellReply::NodeAdded { node_id },
+                Err(e) => ShellReply::Error { error: e, traceback: None },
+            },
+            ShellRequest::RemoveNode { node_id } => {
+                if self.remove_node(node_id) {
+                    ShellReply::Ok { result: serde_json::Value::Null }
+                } else {
+                    ShellReply::Error {
+                        error: format!("Node {} not found", node_id),
+                        traceback: None,
+                    }
+                }
+            }
+            ShellRequest::Connect { source, dest } => match self.connect_nodes(&source, &dest) {
+                Ok(()) => ShellReply::Ok { result: serde_json::Value::Null },
+                Err(e) => ShellReply::Error { error: e, traceback: None },
+            },
+            ShellRequest::Disconnect { source, dest } => {
+                if self.disconnect_nodes(&source, &dest) {
+                    ShellReply::Ok { result: serde_json::Value::Null }
+                } else {
+                    ShellReply::Error {
+                        error: format!("No connection from {} to {}", source.node_id, dest.node_id),
+                        traceback: None,
+                    }
+                }
+            }
+
             // Latent lifecycle management
             ShellRequest::UpdateLatentStarted { region_id, job_id } => {
                 match self.handle_latent_started(region_id, job_id) {
@@ -1960,36 +2464,15 @@ impl GardenDaemon {
             ShellRequest::GetGraph => {
                 let graph = self.graph.read().unwrap();
                 let graph_snapshot = graph.snapshot();
-                let nodes: Vec<hooteproto::garden_snapshot::GraphNode> = graph_snapshot
-                    .nodes
-                    .iter()
-                    .map(|n| hooteproto::garden_snapshot::GraphNode {
-                        id: n.id.to_string(),
-                        name: n.name.clone(),
-                        type_id: n.type_id.clone(),
-                        inputs: n.inputs.iter().map(|p| hooteproto::garden_snapshot::Port {
-                            name: p.name.clone(),
-                            signal_type: signal_type_to_snapshot(&p.signal_type),
-                        }).collect(),
-                        outputs: n.outputs.iter().map(|p| hooteproto::garden_snapshot::Port {
-                            name: p.name.clone(),
-                            signal_type: signal_type_to_snapshot(&p.signal_type),
-                        }).collect(),
-                        latency_samples: n.latency_samples as u32,
-                        can_realtime: n.capabilities.realtime,
-                        can_offline: n.capabilities.offline,
-                    })
-                    .collect();
-                let edges: Vec<hooteproto::garden_snapshot::GraphEdge> = graph_snapshot
-                    .edges
-                    .iter()
-                    .map(|e| hooteproto::garden_snapshot::GraphEdge {
-                        source_id: e.source_id.to_string(),
-                        source_port: e.source_port.clone(),
-                        dest_id: e.dest_id.to_string(),
-                        dest_port: e.dest_port.clone(),
-                    })
-                    .collect();
+                let nodes = graph_snapshot.nodes.iter().map(node_to_snapshot).collect();
+                let edges = graph_snapshot.edges.iter().map(edge_to_snapshot).collect();
+                ShellReply::GraphSnapshot { nodes, edges }
+            }
+            ShellRequest::ResyncGraph => {
+                // The delta baseline, so later deltas line up with the reply
+                let last = self.last_graph_broadcast.lock().unwrap();
+                let nodes = last.nodes.iter().map(node_to_snapshot).collect();
+                let edges = last.edges.iter().map(edge_to_snapshot).collect();
                 ShellReply::GraphSnapshot { nodes, edges }
             }
             ShellRequest::GetIOState => {
@@ -2061,6 +2544,63 @@ impl GardenDaemon {
     }
 }
 
+/// Wait for a detached recorder, store its WAV in CAS and publish
+/// `ArtifactCreated` tagged with the recording's lineage
+fn store_recording(
+    store: &cas::FileStore,
+    recorder: Recorder,
+    parents: Vec<String>,
+    iopub: &broadcast::Sender<IOPubEvent>,
+) -> Result<Recording, String> {
+    let recording = recorder.finish(store).map_err(|e| e.to_string())?;
+    if recording.dropped_samples > 0 {
+        warn!(
+            "Recording dropped {} samples (worker fell behind)",
+            recording.dropped_samples
+        );
+    }
+
+    let content_hash = recording.content_hash.to_string();
+    let mut tags = vec![
+        "type:audio".to_string(),
+        "source:capture".to_string(),
+        match recording.source {
+            RecordSource::Master => "bounce:master".to_string(),
+            RecordSource::Node(id) => format!("bounce:bus:{}", id),
+        },
+    ];
+    tags.extend(parents.iter().map(|hash| format!("parent:{}", hash)));
+
+    info!(
+        "Recording stored: {} ({:.2}s, {} parents)",
+        content_hash,
+        recording.duration_seconds(),
+        parents.len()
+    );
+    let _ = iopub.send(IOPubEvent::ArtifactCreated {
+        artifact_id: format!("artifact_{}", &content_hash[..12.min(content_hash.len())]),
+        content_hash,
+        tags,
+        creator: Some("chaosgarden".to_string()),
+        mime_type: Some(crate::RECORDING_MIME.to_string()),
+    });
+
+    Ok(recording)
+}
+
+/// Reply body for a finished recording
+fn recording_json(recording: &Recording) -> serde_json::Value {
+    serde_json::json!({
+        "recording": false,
+        "content_hash": recording.content_hash.to_string(),
+        "source": recording.source.to_string(),
+        "sample_rate": recording.sample_rate,
+        "frames": recording.frames,
+        "duration_seconds": recording.duration_seconds(),
+        "dropped_samples": recording.dropped_samples,
+    })
+}
+
 /// Convert IPC Behavior to internal Behavior
 fn convert_ipc_behavior_to_internal(ipc: &crate::ipc::Behavior) -> Behavior {
     match ipc {
@@ -2164,12 +2704,74 @@ pub trait StreamEventPublisher: Send + Sync {
     fn publish_stream_error(&self, stream_uri: String, error: String, recoverable: bool);
 }
 
-/// No-op IOPub publisher for daemon initialization
-struct NoOpPublisher;
+/// Pending approvals with their instants placed on the wall clock
+fn wall_clock_approvals(latent_manager: &LatentManager) -> Vec<IpcPendingApproval> {
+    let now = Instant::now();
+    let wall_now = chrono::Utc::now();
+    latent_manager
+        .pending_approvals()
+        .into_iter()
+        .map(|pa| IpcPendingApproval {
+            region_id: pa.region_id,
+            artifact_id: pa.artifact_id.clone(),
+            content_hash: pa.content_hash.clone(),
+            content_type: convert_content_type_to_ipc(pa.content_type),
+            resolved_at: wall_now
+                - chrono::Duration::from_std(now.saturating_duration_since(pa.resolved_at))
+                    .unwrap_or_default(),
+            expires_at: pa.remaining(now).map(|remaining| {
+                wall_now + chrono::Duration::from_std(remaining).unwrap_or_default()
+            }),
+        })
+        .collect()
+}
 
-impl crate::IOPubPublisher for NoOpPublisher {
-    fn publish(&self, _event: crate::LatentEvent) {
-        // No-op for now - will wire to actual IOPub socket later
+/// Publishes latent lifecycle events on the daemon's IOPub channel
+struct LatentIOPub {
+    iopub: broadcast::Sender<IOPubEvent>,
+}
+
+impl crate::IOPubPublisher for LatentIOPub {
+    fn publish(&self, event: crate::LatentEvent) {
+        use crate::LatentEvent;
+
+        let event = match event {
+            LatentEvent::JobStarted { region_id, job_id } => {
+                IOPubEvent::LatentSubmitted { region_id, job_id }
+            }
+            LatentEvent::Progress { region_id, progress } => {
+                IOPubEvent::LatentProgress { region_id, progress }
+            }
+            LatentEvent::Resolved {
+                region_id,
+                artifact_id,
+                content_hash,
+                ..
+            } => IOPubEvent::LatentResolved {
+                region_id,
+                artifact_id,
+                content_hash,
+            },
+            LatentEvent::Approved { region_id } => IOPubEvent::LatentApproved { region_id },
+            LatentEvent::Rejected { region_id, reason } => {
+                IOPubEvent::LatentRejected { region_id, reason }
+            }
+            LatentEvent::ApprovalTimedOut { region_id, decision } => {
+                IOPubEvent::LatentApprovalTimedOut {
+                    region_id,
+                    approved: decision == Decision::Approved,
+                }
+            }
+            LatentEvent::Failed { region_id, error } => IOPubEvent::LatentFailed { region_id, error },
+            LatentEvent::MixedIn {
+                region_id, at_beat, ..
+            } => IOPubEvent::MixedIn {
+                region_id,
+                at_beat: IpcBeat(at_beat.0),
+            },
+        };
+        // Dropped if nobody is subscribed
+        let _ = self.iopub.send(event);
     }
 }
 
@@ -2201,6 +2803,49 @@ impl StreamEventPublisher for NoOpStreamPublisher {
     }
 }
 
+/// Convert a node descriptor to its snapshot form
+fn node_to_snapshot(n: &crate::primitives::NodeDescriptor) -> hooteproto::garden_snapshot::GraphNode {
+    use hooteproto::garden_snapshot::{GraphNode, Port};
+    let port = |p: &crate::primitives::Port| Port {
+        name: p.name.clone(),
+        signal_type: signal_type_to_snapshot(&p.signal_type),
+    };
+    GraphNode {
+        id: n.id.to_string(),
+        name: n.name.clone(),
+        type_id: n.type_id.clone(),
+        inputs: n.inputs.iter().map(port).collect(),
+        outputs: n.outputs.iter().map(port).collect(),
+        latency_samples: n.latency_samples as u32,
+        can_realtime: n.capabilities.realtime,
+        can_offline: n.capabilities.offline,
+    }
+}
+
+/// Convert a graph edge to its snapshot form
+fn edge_to_snapshot(e: &crate::graph::EdgeSnapshot) -> hooteproto::garden_snapshot::GraphEdge {
+    hooteproto::garden_snapshot::GraphEdge {
+        source_id: e.source_id.to_string(),
+        source_port: e.source_port.clone(),
+        dest_id: e.dest_id.to_string(),
+        dest_port: e.dest_port.clone(),
+        gain: e.gain,
+        active: e.active,
+    }
+}
+
+/// Convert a graph delta to its wire form
+fn graph_delta_to_snapshot(delta: &GraphDelta) -> hooteproto::garden_snapshot::GraphDelta {
+    hooteproto::garden_snapshot::GraphDelta {
+        added_nodes: delta.added_nodes.iter().map(node_to_snapshot).collect(),
+        removed_nodes: delta.removed_nodes.iter().map(|id| id.to_string()).collect(),
+        changed_nodes: delta.changed_nodes.iter().map(node_to_snapshot).collect(),
+        added_edges: delta.added_edges.iter().map(edge_to_snapshot).collect(),
+        removed_edges: delta.removed_edges.iter().map(edge_to_snapshot).collect(),
+        changed_edges: delta.changed_edges.iter().map(edge_to_snapshot).collect(),
+    }
+}
+
 /// Convert chaosgarden SignalType to snapshot SignalType
 fn signal_type_to_snapshot(signal: &crate::primitives::SignalType) -> hooteproto::garden_snapshot::SignalType {
     use crate::primitives::SignalType;
@@ -2695,6 +3340,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pending_approval_times_out() {
+        let daemon = GardenDaemon::with_config(DaemonConfig {
+            approval_timeout: Some(Duration::from_secs(60)),
+            ..DaemonConfig::default()
+        });
+
+        let behavior = crate::ipc::Behavior::Latent {
+            job_id: "job_timeout".to_string(),
+        };
+        let region_id = match daemon.handle_shell(ShellRequest::CreateRegion {
+            position: IpcBeat(0.0),
+            duration: IpcBeat(8.0),
+            behavior,
+        }) {
+            ShellReply::RegionCreated { region_id } => region_id,
+            _ => panic!("expected RegionCreated"),
+        };
+        daemon.handle_shell(ShellRequest::UpdateLatentStarted {
+            region_id,
+            job_id: "job_timeout".to_string(),
+        });
+        daemon.handle_shell(ShellRequest::UpdateLatentResolved {
+            region_id,
+            artifact_id: "artifact_timeout".to_string(),
+            content_hash: "hash_timeout".to_string(),
+            content_type: IpcContentType::Midi,
+        });
+
+        match daemon.handle_shell(ShellRequest::GetPendingApprovals) {
+            ShellReply::PendingApprovals { approvals } => {
+                assert_eq!(approvals.len(), 1);
+                let expires_at = approvals[0].expires_at.expect("timeout configured");
+                assert!(expires_at > approvals[0].resolved_at);
+            }
+            other => panic!("expected PendingApprovals, got {:?}", other),
+        }
+
+        let snapshot = daemon.build_snapshot(0);
+        let approval = &snapshot.pending_approvals[0];
+        assert!(approval.expires_at.expect("timeout configured") > approval.resolved_at);
+
+        let mut events = daemon.subscribe();
+        daemon.expire_approvals(Instant::now() + Duration::from_secs(61));
+
+        assert!(daemon.get_pending_approvals().is_empty());
+        match events.try_recv() {
+            Ok(IOPubEvent::LatentApprovalTimedOut { region_id: id, approved }) => {
+                assert_eq!(id, region_id);
+                assert!(!approved);
+            }
+            other => panic!("expected LatentApprovalTimedOut, got {:?}", other),
+        }
+        assert!(matches!(
+            events.try_recv(),
+            Ok(IOPubEvent::LatentRejected { .. })
+        ));
+        let regions = daemon.regions.read().unwrap();
+        assert_eq!(
+            regions[0].latent_status(),
+            Some(crate::primitives::LatentStatus::Rejected)
+        );
+    }
+
     // === Playback Engine Wiring Tests (Phase 3) ===
 
     #[test]
@@ -2716,6 +3425,28 @@ mod tests {
         assert!(daemon.compiled_graph.read().unwrap().is_some());
     }
 
+    #[test]
+    fn test_sections_survive_engine_creation() {
+        use crate::nodes::MemoryResolver;
+
+        let mut daemon = GardenDaemon::new();
+        daemon.set_sections(vec![Section::new("verse", Beat(0.0), Beat(16.0))]);
+        daemon.set_content_resolver(Arc::new(MemoryResolver::new()));
+
+        let names = |daemon: &GardenDaemon| -> Vec<String> {
+            let engine = daemon.playback_engine.read().unwrap();
+            let engine = engine.as_ref().unwrap();
+            engine.sections().iter().map(|s| s.name.clone()).collect()
+        };
+        assert_eq!(names(&daemon), vec!["verse"]);
+
+        daemon.set_sections(vec![
+            Section::new("verse", Beat(0.0), Beat(16.0)),
+            Section::new("chorus", Beat(16.0), Beat(32.0)),
+        ]);
+        assert_eq!(names(&daemon), vec!["verse", "chorus"]);
+    }
+
     #[test]
     fn test_play_syncs_playback_engine() {
         use crate::nodes::MemoryResolver;
@@ -2834,4 +3565,288 @@ mod tests {
         let regions = daemon.get_regions(None);
         assert_eq!(regions.len(), 0);
     }
+
+    #[test]
+    fn test_timeline_midi_waits_for_tick_clock() {
+        let daemon = GardenDaemon::new();
+        let note = |pitch| PendingMidiEvent {
+            message: crate::primitives::MidiMessage::NoteOn {
+                channel: 0,
+                pitch,
+                velocity: 100,
+            },
+            port_pattern: None,
+        };
+        daemon.schedule_midi(Beat(0.0), 48000, vec![note(60), note(64)]);
+        daemon.schedule_midi(Beat(8.0), 48000, vec![note(67)]);
+
+        daemon.flush_midi(256);
+        {
+            let lanes = daemon.midi_lanes.lock().unwrap();
+            let lane = &lanes[&None];
+            assert_eq!(lane.scheduler.pending(), 1, "beat 8 is beyond the look-ahead");
+            assert!(lane.output.event_queue().lock().unwrap().is_empty());
+        }
+
+        // Moving the timeline drops what hasn't gone out
+        daemon.handle_shell(ShellRequest::Seek { beat: IpcBeat(4.0) });
+        assert!(daemon.midi_lanes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_recording_arm_and_finish() {
+        use crate::nodes::MemoryResolver;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut daemon = GardenDaemon::new();
+        daemon.set_content_resolver(Arc::new(MemoryResolver::new()));
+        assert!(daemon.set_recording(true, None).is_err(), "no store yet");
+
+        daemon.set_recording_store(cas::FileStore::at_path(temp.path()).unwrap());
+        let bus = Uuid::new_v4().to_string();
+        assert!(daemon.set_recording(true, Some(bus)).is_err(), "unknown bus");
+
+        let armed = daemon.set_recording(true, None).unwrap();
+        assert_eq!(armed["source"], "master");
+        assert!(daemon.set_recording(true, None).is_err(), "already armed");
+
+        let mut events = daemon.subscribe();
+        let finished = daemon.set_recording(false, None).unwrap();
+        assert_eq!(finished["frames"], 0);
+        assert!(!daemon.playback_engine.read().unwrap().as_ref().unwrap().is_recording());
+
+        match events.try_recv() {
+            Ok(IOPubEvent::ArtifactCreated { content_hash, tags, .. }) => {
+                assert_eq!(finished["content_hash"], content_hash.as_str());
+                assert!(tags.contains(&"bounce:master".to_string()));
+            }
+            other => panic!("expected ArtifactCreated, got {:?}", other),
+        }
+
+        // Disarming again is a no-op
+        assert_eq!(daemon.set_recording(false, None).unwrap()["recording"], false);
+    }
+
+    #[test]
+    fn test_recording_finishes_off_tick_after_stop() {
+        use crate::nodes::MemoryResolver;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut daemon = GardenDaemon::with_config(DaemonConfig {
+            declick_frames: 0,
+            ..Default::default()
+        });
+        daemon.set_content_resolver(Arc::new(MemoryResolver::new()));
+        daemon.set_recording_store(cas::FileStore::at_path(temp.path()).unwrap());
+        let mut events = daemon.subscribe();
+
+        daemon.set_recording(true, None).unwrap();
+        daemon.handle_shell(ShellRequest::Play);
+        daemon.handle_shell(ShellRequest::Stop);
+        daemon.tick();
+
+        // tick() only detached the recording; the store happens elsewhere
+        assert!(daemon.recording.lock().unwrap().is_none());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let event = loop {
+            match events.try_recv() {
+                Ok(IOPubEvent::MeterUpdate { .. } | IOPubEvent::TransportStateChanged { .. }) => {}
+                Ok(event) => break event,
+                Err(broadcast::error::TryRecvError::Empty) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                Err(e) => panic!("no ArtifactCreated published: {:?}", e),
+            }
+        };
+        match event {
+            IOPubEvent::ArtifactCreated { tags, creator, .. } => {
+                assert!(tags.contains(&"source:capture".to_string()));
+                assert_eq!(creator.as_deref(), Some("chaosgarden"));
+            }
+            other => panic!("expected ArtifactCreated, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tick_publishes_meters() {
+        let daemon = GardenDaemon::new();
+        let mut events = daemon.subscribe();
+
+        daemon.tick();
+        let mut channels = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let IOPubEvent::MeterUpdate {
+                channel, peak_db, ..
+            } = event
+            {
+                assert_eq!(peak_db, crate::mixer::METER_FLOOR_DB);
+                channels.push(channel);
+            }
+        }
+        assert_eq!(channels, vec!["monitor", "timeline"]);
+
+        // Throttled to the meter interval
+        daemon.tick();
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_transport_changes_publish_state() {
+        let daemon = GardenDaemon::new();
+        let mut events = daemon.subscribe();
+
+        daemon.handle_shell(ShellRequest::Seek { beat: IpcBeat(-2.0) });
+        daemon.handle_shell(ShellRequest::Seek { beat: IpcBeat(4.0) });
+        daemon.handle_shell(ShellRequest::Play);
+        daemon.handle_shell(ShellRequest::Stop);
+
+        let states: Vec<(String, f64)> = std::iter::from_fn(|| events.try_recv().ok())
+            .filter_map(|event| match event {
+                IOPubEvent::TransportStateChanged {
+                    state,
+                    position_beats,
+                    tempo_bpm,
+                    beats_per_bar,
+                } => {
+                    assert_eq!(tempo_bpm, 120.0);
+                    assert_eq!(beats_per_bar, 4);
+                    Some((state, position_beats))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("paused".to_string(), 0.0),
+                ("paused".to_string(), 4.0),
+                ("playing".to_string(), 4.0),
+                ("stopped".to_string(), 4.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_loop_wrap_publishes_marker() {
+        use crate::nodes::MemoryResolver;
+
+        let mut daemon = GardenDaemon::new();
+        daemon.set_content_resolver(Arc::new(MemoryResolver::new()));
+        let (producer, _consumer) = audio_ring_pair(1 << 16);
+        *daemon.timeline_producer.lock().unwrap() = Some(producer);
+        // Shorter than one buffer, so the first block wraps
+        daemon.set_loop(0.0, Some(0.005), true).unwrap();
+        let mut events = daemon.subscribe();
+
+        daemon.handle_shell(ShellRequest::Play);
+        daemon.process_playback();
+
+        let marker = std::iter::from_fn(|| events.try_recv().ok()).find_map(|event| match event {
+            IOPubEvent::MarkerReached {
+                marker_type,
+                position_beats,
+                ..
+            } => Some((marker_type, position_beats)),
+            _ => None,
+        });
+        assert_eq!(marker, Some(("loop".to_string(), 0.005)));
+    }
+
+    #[test]
+    fn test_graph_updates_publish_deltas() {
+        use crate::nodes::{AudioFileNode, MemoryResolver};
+        use crate::primitives::Node;
+
+        let daemon = GardenDaemon::new();
+        let mut events = daemon.subscribe();
+        let mut cached = daemon.build_snapshot(0);
+        assert!(cached.nodes.is_empty());
+
+        let resolver = Arc::new(MemoryResolver::new());
+        let first = AudioFileNode::new("first", resolver.clone());
+        let second = AudioFileNode::new("second", resolver);
+        let first_id = first.descriptor().id;
+        daemon.update_graph(|graph| {
+            graph.add_node(Box::new(first));
+            graph.add_node(Box::new(second));
+        });
+        daemon.update_graph(|graph| graph.remove_node(first_id));
+        // An edit that changes nothing isn't announced
+        daemon.update_graph(|graph| graph.node_count());
+
+        for _ in 0..2 {
+            match events.try_recv() {
+                Ok(IOPubEvent::GraphDelta { delta }) => cached.apply_graph_delta(&delta),
+                other => panic!("expected GraphDelta, got {:?}", other),
+            }
+        }
+        assert!(events.try_recv().is_err());
+
+        let fresh = daemon.build_snapshot(1);
+        let ids = |nodes: &[hooteproto::garden_snapshot::GraphNode]| {
+            nodes.iter().map(|n| n.id.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&cached.nodes), ids(&fresh.nodes));
+        assert_eq!(cached.nodes.len(), 1);
+    }
+
+    #[test]
+    fn test_shell_graph_edits_match_resync() {
+        use crate::ipc::NodeDescriptor;
+        use crate::nodes::MemoryResolver;
+
+        let mut daemon = GardenDaemon::new();
+        daemon.set_content_resolver(Arc::new(MemoryResolver::new()));
+        let mut events = daemon.subscribe();
+        let mut cached = daemon.build_snapshot(0);
+
+        let add = |node_type: &str, config: serde_json::Value| {
+            let node = NodeDescriptor {
+                name: node_type.to_string(),
+                node_type: node_type.to_string(),
+                config,
+            };
+            match daemon.handle_shell(ShellRequest::AddNode { node }) {
+                ShellReply::NodeAdded { node_id } => node_id,
+                other => panic!("expected NodeAdded, got {:?}", other),
+            }
+        };
+        let file = add("audio_file", serde_json::json!({"content_hash": "abcd1234"}));
+        let playlist = add("playlist", serde_json::json!({"content_hashes": ["a", "b"]}));
+
+        // Neither node has an input, so this is refused and changes nothing
+        let port = |node_id| PortRef { node_id, port_name: "out".to_string() };
+        let reply = daemon.handle_shell(ShellRequest::Connect {
+            source: port(file),
+            dest: port(playlist),
+        });
+        assert!(matches!(reply, ShellReply::Error { .. }));
+
+        let reply = daemon.handle_shell(ShellRequest::RemoveNode { node_id: file });
+        assert!(matches!(reply, ShellReply::Ok { .. }));
+        let reply = daemon.handle_shell(ShellRequest::RemoveNode { node_id: file });
+        assert!(matches!(reply, ShellReply::Error { .. }));
+
+        let mut deltas = 0;
+        while let Ok(event) = events.try_recv() {
+            if let IOPubEvent::GraphDelta { delta } = event {
+                cached.apply_graph_delta(&delta);
+                deltas += 1;
+            }
+        }
+        assert_eq!(deltas, 3);
+
+        match daemon.handle_shell(ShellRequest::ResyncGraph) {
+            ShellReply::GraphSnapshot { nodes, edges } => {
+                let ids: Vec<_> = nodes.iter().map(|n| n.id.clone()).collect();
+                assert_eq!(ids, vec![playlist.to_string()]);
+                assert!(edges.is_empty());
+                assert_eq!(
+                    cached.nodes.iter().map(|n| n.id.clone()).collect::<Vec<_>>(),
+                    ids
+                );
+            }
+            other => panic!("expected GraphSnapshot, got {:?}", other),
+        }
+    }
 }
@@ -8,17 +8,20 @@
 //! - ExternalInputNode/ExternalOutputNode bridge PipeWire callbacks to graph
 //! - Ring buffers enable lock-free communication between RT and non-RT threads
 
-use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use hooteproto::Broadcast;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::primitives::{
-    MidiEvent, Node, NodeCapabilities, NodeDescriptor, Port, ProcessContext, ProcessError,
-    SignalBuffer, SignalType,
+    Beat, MidiEvent, MidiMessage, Node, NodeCapabilities, NodeDescriptor, Port, ProcessContext,
+    ProcessError, SignalBuffer, SignalType,
 };
+use crate::tick_clock::TickClock;
 
 /// Direction for MIDI devices
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -44,6 +47,49 @@ pub struct PipeWireInput {
     pub name: String,
     pub port_pattern: Option<String>,
     pub channels: u8,
+    #[serde(default)]
+    pub channel_map: ChannelMap,
+}
+
+/// How captured source channels map onto graph channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelMap {
+    /// Source channel N feeds graph channel N; extra graph channels are silent
+    #[default]
+    Identity,
+    /// Source channel 0 feeds every graph channel (mono mic into stereo bus)
+    MonoToAll,
+    /// First two channels swapped (L/R reversed at the source)
+    SwapStereo,
+    /// Every graph channel gets the average of all source channels
+    SumToMono,
+}
+
+impl ChannelMap {
+    /// Graph channel `out_ch` computed from one interleaved source frame
+    #[inline]
+    pub fn sample(&self, frame: &[f32], out_ch: usize) -> f32 {
+        match self {
+            ChannelMap::Identity => frame.get(out_ch).copied().unwrap_or(0.0),
+            ChannelMap::MonoToAll => frame.first().copied().unwrap_or(0.0),
+            ChannelMap::SwapStereo => {
+                let src_ch = match out_ch {
+                    0 => 1,
+                    1 => 0,
+                    n => n,
+                };
+                frame.get(src_ch).copied().unwrap_or(0.0)
+            }
+            ChannelMap::SumToMono => {
+                if frame.is_empty() {
+                    0.0
+                } else {
+                    frame.iter().sum::<f32>() / frame.len() as f32
+                }
+            }
+        }
+    }
 }
 
 /// MIDI device configuration
@@ -143,6 +189,7 @@ impl ExternalIOManager {
             name: name.to_string(),
             port_pattern: None,
             channels,
+            channel_map: ChannelMap::default(),
         };
 
         // TODO: PipeWire input stream creation
@@ -151,6 +198,20 @@ impl ExternalIOManager {
         Ok(id)
     }
 
+    /// Set how an input's source channels map onto graph channels
+    pub fn set_input_channel_map(
+        &mut self,
+        id: Uuid,
+        channel_map: ChannelMap,
+    ) -> Result<(), ExternalIOError> {
+        let input = self
+            .inputs
+            .get_mut(&id)
+            .ok_or(ExternalIOError::DeviceNotFound(id))?;
+        input.channel_map = channel_map;
+        Ok(())
+    }
+
     /// Connect an input to specific PipeWire ports matching a pattern
     pub fn connect_input(&mut self, id: Uuid, port_pattern: &str) -> Result<(), ExternalIOError> {
         let input = self
@@ -267,11 +328,9 @@ impl ExternalIOManager {
             .get(&input_id)
             .ok_or(ExternalIOError::DeviceNotFound(input_id))?;
 
-        Ok(ExternalInputNode::new(
-            input.name.clone(),
-            input.channels,
-            self.buffer_size,
-        ))
+        let mut node = ExternalInputNode::new(input.name.clone(), input.channels, self.buffer_size);
+        node.set_channel_map(input.channel_map);
+        Ok(node)
     }
 
     /// Create a MidiInputNode for use in the graph
@@ -294,6 +353,99 @@ impl ExternalIOManager {
     }
 }
 
+/// Snapshot of a ring buffer's xrun counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RingStats {
+    /// Times the ring ran dry while the consumer still wanted samples
+    pub underruns: u64,
+    /// Writes that didn't fit (the overflow was dropped)
+    pub overruns: u64,
+    /// Total samples dropped by overruns
+    pub dropped_samples: u64,
+}
+
+/// Atomic xrun counters shared by both ends of a ring
+///
+/// Only relaxed loads and stores - safe to update from the RT thread.
+#[derive(Debug, Default)]
+struct RingCounters {
+    underruns: AtomicU64,
+    overruns: AtomicU64,
+    dropped_samples: AtomicU64,
+    /// Set by a successful write, cleared by the first short read after it,
+    /// so a ring that runs dry counts one underrun rather than one per callback
+    primed: AtomicBool,
+}
+
+impl RingCounters {
+    fn record_write(&self, requested: usize, written: usize) {
+        if written > 0 {
+            self.primed.store(true, Ordering::Relaxed);
+        }
+        if written < requested {
+            self.overruns.fetch_add(1, Ordering::Relaxed);
+            self.dropped_samples
+                .fetch_add((requested - written) as u64, Ordering::Relaxed);
+        }
+    }
+
+    fn record_read(&self, requested: usize, read: usize) {
+        if read < requested && self.primed.swap(false, Ordering::Relaxed) {
+            self.underruns.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self) -> RingStats {
+        RingStats {
+            underruns: self.underruns.load(Ordering::Relaxed),
+            overruns: self.overruns.load(Ordering::Relaxed),
+            dropped_samples: self.dropped_samples.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Overruns accumulated before an [`OverrunWatch`] raises a warning
+pub const DEFAULT_OVERRUN_WARN_THRESHOLD: u64 = 8;
+
+/// Turns a ring's overrun count into throttled `Broadcast::Log` warnings
+///
+/// Polled from the control thread. Warns once each time another
+/// `threshold` overruns have piled up since the last warning.
+#[derive(Debug, Clone)]
+pub struct OverrunWatch {
+    source: String,
+    threshold: u64,
+    reported: u64,
+}
+
+impl OverrunWatch {
+    /// Watch a ring identified by `source` in log messages
+    pub fn new(source: impl Into<String>, threshold: u64) -> Self {
+        Self {
+            source: source.into(),
+            threshold: threshold.max(1),
+            reported: 0,
+        }
+    }
+
+    /// Check fresh stats, returning a warning if the threshold was crossed
+    pub fn check(&mut self, stats: RingStats) -> Option<Broadcast> {
+        let pending = stats.overruns.saturating_sub(self.reported);
+        if pending < self.threshold {
+            return None;
+        }
+        self.reported = stats.overruns;
+        Some(Broadcast::Log {
+            level: "warn".to_string(),
+            message: format!(
+                "{} ring overran {} times ({} total, {} samples dropped) - consumer is stalling",
+                self.source, pending, stats.overruns, stats.dropped_samples
+            ),
+            source: "chaosgarden".to_string(),
+        })
+    }
+}
+
 /// Simple lock-free ring buffer for RT audio data
 ///
 /// Used to transfer samples between PipeWire callback and graph processing.
@@ -306,6 +458,7 @@ pub struct RingBuffer {
     capacity: usize,
     write_pos: std::sync::atomic::AtomicUsize,
     read_pos: std::sync::atomic::AtomicUsize,
+    counters: RingCounters,
 }
 
 impl RingBuffer {
@@ -317,6 +470,7 @@ impl RingBuffer {
             capacity,
             write_pos: std::sync::atomic::AtomicUsize::new(0),
             read_pos: std::sync::atomic::AtomicUsize::new(0),
+            counters: RingCounters::default(),
         }
     }
 
@@ -335,6 +489,7 @@ impl RingBuffer {
 
         self.write_pos
             .store(write.wrapping_add(to_write), Ordering::Release);
+        self.counters.record_write(samples.len(), to_write);
         to_write
     }
 
@@ -353,6 +508,7 @@ impl RingBuffer {
 
         self.read_pos
             .store(read.wrapping_add(to_read), Ordering::Release);
+        self.counters.record_read(output.len(), to_read);
         to_read
     }
 
@@ -367,6 +523,11 @@ impl RingBuffer {
     pub fn space(&self) -> usize {
         self.capacity - self.available()
     }
+
+    /// Underrun/overrun counters since creation
+    pub fn stats(&self) -> RingStats {
+        self.counters.snapshot()
+    }
 }
 
 /// Lock-free audio ring buffer producer (writer)
@@ -376,6 +537,7 @@ impl RingBuffer {
 /// Thread-safe: can be used from RT thread without any locking.
 pub struct AudioRingProducer {
     inner: rtrb::Producer<f32>,
+    counters: Arc<RingCounters>,
 }
 
 impl AudioRingProducer {
@@ -387,6 +549,7 @@ impl AudioRingProducer {
         let to_write = samples.len().min(available);
 
         if to_write == 0 {
+            self.counters.record_write(samples.len(), 0);
             return 0;
         }
 
@@ -399,6 +562,7 @@ impl AudioRingProducer {
                 break;
             }
         }
+        self.counters.record_write(samples.len(), written);
         written
     }
 
@@ -406,6 +570,11 @@ impl AudioRingProducer {
     pub fn space(&self) -> usize {
         self.inner.slots()
     }
+
+    /// Underrun/overrun counters shared with the consumer end
+    pub fn stats(&self) -> RingStats {
+        self.counters.snapshot()
+    }
 }
 
 /// Lock-free audio ring buffer consumer (reader)
@@ -415,6 +584,7 @@ impl AudioRingProducer {
 /// Thread-safe: can be used from RT thread without any locking.
 pub struct AudioRingConsumer {
     inner: rtrb::Consumer<f32>,
+    counters: Arc<RingCounters>,
 }
 
 impl AudioRingConsumer {
@@ -425,6 +595,7 @@ impl AudioRingConsumer {
         let to_read = output.len().min(available);
 
         if to_read == 0 {
+            self.counters.record_read(output.len(), 0);
             return 0;
         }
 
@@ -438,6 +609,7 @@ impl AudioRingConsumer {
                 break;
             }
         }
+        self.counters.record_read(output.len(), read);
         read
     }
 
@@ -445,6 +617,11 @@ impl AudioRingConsumer {
     pub fn available(&self) -> usize {
         self.inner.slots()
     }
+
+    /// Underrun/overrun counters shared with the producer end
+    pub fn stats(&self) -> RingStats {
+        self.counters.snapshot()
+    }
 }
 
 /// Create a new lock-free audio ring buffer pair
@@ -454,9 +631,16 @@ impl AudioRingConsumer {
 /// Capacity is rounded up to next power of 2.
 pub fn audio_ring_pair(capacity: usize) -> (AudioRingProducer, AudioRingConsumer) {
     let (producer, consumer) = rtrb::RingBuffer::new(capacity);
+    let counters = Arc::new(RingCounters::default());
     (
-        AudioRingProducer { inner: producer },
-        AudioRingConsumer { inner: consumer },
+        AudioRingProducer {
+            inner: producer,
+            counters: Arc::clone(&counters),
+        },
+        AudioRingConsumer {
+            inner: consumer,
+            counters,
+        },
     )
 }
 
@@ -565,7 +749,11 @@ impl Node for ExternalOutputNode {
 pub struct ExternalInputNode {
     descriptor: NodeDescriptor,
     ring_buffer: Arc<Mutex<RingBuffer>>,
+    /// Channels in the captured (source) stream
     channels: u8,
+    channel_map: ChannelMap,
+    /// Source frames staged here when they need remapping
+    scratch: Vec<f32>,
     active: AtomicBool,
 }
 
@@ -593,6 +781,8 @@ impl ExternalInputNode {
             descriptor,
             ring_buffer: Arc::new(Mutex::new(RingBuffer::new(ring_capacity))),
             channels,
+            channel_map: ChannelMap::default(),
+            scratch: vec![0.0; buffer_frames * channels as usize],
             active: AtomicBool::new(false),
         }
     }
@@ -602,6 +792,15 @@ impl ExternalInputNode {
         Arc::clone(&self.ring_buffer)
     }
 
+    /// Set how source channels map onto the graph's output channels
+    pub fn set_channel_map(&mut self, channel_map: ChannelMap) {
+        self.channel_map = channel_map;
+    }
+
+    pub fn channel_map(&self) -> ChannelMap {
+        self.channel_map
+    }
+
     /// Mark the node as active (connected to PipeWire)
     pub fn set_active(&self, active: bool) {
         self.active.store(active, Ordering::Release);
@@ -639,9 +838,16 @@ impl Node for ExternalInputNode {
             }
         };
 
-        let expected_samples = ctx.buffer_size * self.channels as usize;
+        let src_channels = self.channels as usize;
+        let expected_samples = ctx.buffer_size * src_channels;
 
-        if let Ok(mut ring) = self.ring_buffer.try_lock() {
+        let Ok(mut ring) = self.ring_buffer.try_lock() else {
+            audio.samples.fill(0.0);
+            return Ok(());
+        };
+
+        // Passthrough: the ring already holds the graph's layout
+        if self.channel_map == ChannelMap::Identity && audio.channels as usize == src_channels {
             if audio.samples.len() < expected_samples {
                 audio.samples.resize(expected_samples, 0.0);
             }
@@ -649,8 +855,27 @@ impl Node for ExternalInputNode {
             if read < expected_samples {
                 audio.samples[read..expected_samples].fill(0.0);
             }
-        } else {
-            audio.samples.fill(0.0);
+            return Ok(());
+        }
+
+        if self.scratch.len() < expected_samples {
+            self.scratch.resize(expected_samples, 0.0);
+        }
+        let read = ring.read(&mut self.scratch[..expected_samples]);
+        self.scratch[read..expected_samples].fill(0.0);
+
+        let out_channels = audio.channels as usize;
+        let out_samples = ctx.buffer_size * out_channels;
+        if audio.samples.len() < out_samples {
+            audio.samples.resize(out_samples, 0.0);
+        }
+
+        let source = self.scratch[..expected_samples].chunks_exact(src_channels.max(1));
+        let dest = audio.samples[..out_samples].chunks_exact_mut(out_channels.max(1));
+        for (src_frame, out_frame) in source.zip(dest) {
+            for (out_ch, sample) in out_frame.iter_mut().enumerate() {
+                *sample = self.channel_map.sample(src_frame, out_ch);
+            }
         }
 
         Ok(())
@@ -800,6 +1025,30 @@ impl MidiOutputNode {
         Arc::clone(&self.event_queue)
     }
 
+    /// Append events for the upcoming quantum (non-RT; takes the queue lock)
+    pub fn enqueue(&self, events: impl IntoIterator<Item = MidiEvent>) {
+        if let Ok(mut queue) = self.event_queue.lock() {
+            queue.extend(events);
+        }
+    }
+
+    /// Take the events falling within the next `frames` frames
+    ///
+    /// Later events stay queued, re-stamped relative to the following
+    /// quantum, so look-ahead offsets longer than one block carry over.
+    pub fn take_block(&self, frames: usize) -> Vec<MidiEvent> {
+        let Ok(mut queue) = self.event_queue.lock() else {
+            return Vec::new();
+        };
+        queue.sort_by_key(|event| event.frame);
+        let due = queue.partition_point(|event| event.frame < frames);
+        let block: Vec<MidiEvent> = queue.drain(..due).collect();
+        for event in queue.iter_mut() {
+            event.frame -= frames;
+        }
+        block
+    }
+
     /// Mark the node as active
     pub fn set_active(&self, active: bool) {
         self.active.store(active, Ordering::Release);
@@ -837,8 +1086,8 @@ impl Node for MidiOutputNode {
             }
         };
 
+        // Append: scheduled events from earlier quanta wait in the same queue
         if let Ok(mut queue) = self.event_queue.try_lock() {
-            queue.clear();
             queue.extend(midi.events.iter().cloned());
         }
 
@@ -852,14 +1101,123 @@ impl Node for MidiOutputNode {
     }
 }
 
+/// Default look-ahead for [`MidiScheduler`]
+pub const DEFAULT_MIDI_LOOK_AHEAD: Duration = Duration::from_millis(20);
+
+/// A MIDI message due at a musical position
+#[derive(Debug, Clone)]
+pub struct ScheduledMidiEvent {
+    pub beat: Beat,
+    pub message: MidiMessage,
+}
+
+/// Look-ahead scheduler feeding a [`MidiOutputNode`]
+///
+/// Events wait here until they come within `look_ahead` of the
+/// [`TickClock`] position, then go to the output node stamped with their
+/// frame offset from the quantum starting at that position. Events already
+/// past due are sent at frame 0 with a warning rather than dropped.
+pub struct MidiScheduler {
+    /// Sorted by beat; events at the same beat keep scheduling order
+    pending: VecDeque<ScheduledMidiEvent>,
+    look_ahead: Duration,
+    sample_rate: u32,
+    late_events: u64,
+}
+
+impl MidiScheduler {
+    pub fn new(sample_rate: u32, look_ahead: Duration) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            look_ahead,
+            sample_rate,
+            late_events: 0,
+        }
+    }
+
+    /// Schedule a message at a musical position
+    pub fn schedule(&mut self, beat: Beat, message: MidiMessage) {
+        let idx = self
+            .pending
+            .iter()
+            .position(|e| e.beat.0 > beat.0)
+            .unwrap_or(self.pending.len());
+        self.pending
+            .insert(idx, ScheduledMidiEvent { beat, message });
+    }
+
+    /// Send every event due within the look-ahead window to `output`
+    ///
+    /// Call once per quantum, before the output is drained with
+    /// [`MidiOutputNode::take_block`]. Offsets can run past the quantum;
+    /// the output carries those events forward. Returns the number of
+    /// events sent.
+    pub fn flush(&mut self, clock: &TickClock, output: &MidiOutputNode) -> usize {
+        let now = clock.beat_to_sample(clock.position(), self.sample_rate).0;
+        let horizon =
+            now + (self.look_ahead.as_secs_f64() * self.sample_rate as f64).round() as u64;
+
+        let mut due = Vec::new();
+        while let Some(event) = self.pending.front() {
+            let at = clock.beat_to_sample(event.beat, self.sample_rate).0;
+            if at >= horizon {
+                break;
+            }
+            let Some(event) = self.pending.pop_front() else {
+                break;
+            };
+
+            let frame = if at < now {
+                self.late_events += 1;
+                tracing::warn!(
+                    beat = event.beat.0,
+                    late_ms = (now - at) as f64 * 1000.0 / self.sample_rate as f64,
+                    "late MIDI event sent immediately"
+                );
+                0
+            } else {
+                (at - now) as usize
+            };
+            due.push(MidiEvent {
+                frame,
+                message: event.message,
+            });
+        }
+
+        let sent = due.len();
+        if sent > 0 {
+            output.enqueue(due);
+        }
+        sent
+    }
+
+    /// Drop everything still waiting (e.g. on transport stop)
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Events still waiting to be sent
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Events that were already past due when flushed
+    pub fn late_events(&self) -> u64 {
+        self.late_events
+    }
+
+    pub fn look_ahead(&self) -> Duration {
+        self.look_ahead
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::primitives::{
-        AudioBuffer, Beat, MidiBuffer, MidiMessage, ProcessingMode, Sample, TempoMap,
-        TransportState,
+        AudioBuffer, MidiBuffer, ProcessingMode, Sample, TempoMap, TransportState,
     };
-    use std::sync::Arc;
+    use std::sync::{Arc, RwLock};
 
     fn test_context(buffer_size: usize) -> ProcessContext {
         ProcessContext {
@@ -964,6 +1322,64 @@ mod tests {
         assert_eq!(all, [5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
     }
 
+    #[test]
+    fn test_ring_buffer_counts_xruns() {
+        let mut ring = RingBuffer::new(4);
+        let mut output = [0.0; 4];
+
+        // Empty reads before any data arrives are not underruns
+        ring.read(&mut output);
+        assert_eq!(ring.stats(), RingStats::default());
+
+        // Six samples into four slots: one overrun, two dropped
+        assert_eq!(ring.write(&[1.0; 6]), 4);
+        assert_eq!(ring.stats().overruns, 1);
+        assert_eq!(ring.stats().dropped_samples, 2);
+
+        // Draining then reading short is one underrun, not one per read
+        ring.read(&mut output);
+        ring.read(&mut output);
+        ring.read(&mut output);
+        assert_eq!(ring.stats().underruns, 1);
+    }
+
+    #[test]
+    fn test_audio_ring_pair_shares_stats() {
+        let (mut producer, mut consumer) = audio_ring_pair(4);
+
+        producer.write(&[0.5; 3]);
+        producer.write(&[0.5; 3]);
+
+        let mut output = [0.0; 8];
+        assert_eq!(consumer.read(&mut output), 4);
+
+        let stats = consumer.stats();
+        assert_eq!(stats.overruns, 1);
+        assert_eq!(stats.dropped_samples, 2);
+        assert_eq!(stats.underruns, 1);
+        assert_eq!(producer.stats(), stats);
+    }
+
+    #[test]
+    fn test_overrun_watch_threshold() {
+        let mut watch = OverrunWatch::new("streaming tap", 3);
+
+        let stats = |overruns| RingStats {
+            overruns,
+            ..RingStats::default()
+        };
+
+        assert!(watch.check(stats(2)).is_none());
+        assert!(matches!(
+            watch.check(stats(3)),
+            Some(Broadcast::Log { level, message, .. })
+                if level == "warn" && message.starts_with("streaming tap")
+        ));
+        // Already reported; needs another three before warning again
+        assert!(watch.check(stats(5)).is_none());
+        assert!(watch.check(stats(6)).is_some());
+    }
+
     #[test]
     fn test_external_output_node_descriptor() {
         let node = ExternalOutputNode::new("main-out".to_string(), 2, 256);
@@ -1045,6 +1461,67 @@ mod tests {
         }
     }
 
+    /// Feed `frames` of interleaved source audio through an input node
+    fn process_input(
+        channel_map: ChannelMap,
+        src_channels: u8,
+        out_channels: u8,
+        source: &[f32],
+    ) -> Vec<f32> {
+        let frames = source.len() / src_channels as usize;
+        let mut node = ExternalInputNode::new("in".to_string(), src_channels, frames);
+        node.set_channel_map(channel_map);
+        node.ring_buffer().lock().unwrap().write(source);
+        node.set_active(true);
+
+        let ctx = test_context(frames);
+        let mut outputs = vec![SignalBuffer::Audio(AudioBuffer::new(frames, out_channels))];
+        node.process(&ctx, &[], &mut outputs).unwrap();
+
+        match outputs.remove(0) {
+            SignalBuffer::Audio(audio) => audio.samples,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_channel_map_mono_to_stereo() {
+        let out = process_input(ChannelMap::MonoToAll, 1, 2, &[0.1, 0.2, 0.3]);
+        assert_eq!(out, vec![0.1, 0.1, 0.2, 0.2, 0.3, 0.3]);
+
+        // Identity keeps the old behavior: the second graph channel stays silent
+        let out = process_input(ChannelMap::Identity, 1, 2, &[0.1, 0.2]);
+        assert_eq!(out, vec![0.1, 0.0, 0.2, 0.0]);
+    }
+
+    #[test]
+    fn test_channel_map_swap_stereo() {
+        let out = process_input(ChannelMap::SwapStereo, 2, 2, &[0.1, 0.9, 0.2, 0.8]);
+        assert_eq!(out, vec![0.9, 0.1, 0.8, 0.2]);
+    }
+
+    #[test]
+    fn test_channel_map_sum_to_mono() {
+        let out = process_input(ChannelMap::SumToMono, 2, 1, &[0.2, 0.4, -0.5, 0.5]);
+        assert!((out[0] - 0.3).abs() < 1e-6);
+        assert!(out[1].abs() < 1e-6);
+
+        let out = process_input(ChannelMap::SumToMono, 2, 2, &[0.2, 0.4]);
+        assert!((out[0] - 0.3).abs() < 1e-6 && (out[1] - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_manager_applies_input_channel_map() {
+        let mut manager = ExternalIOManager::new(48000, 256).unwrap();
+        let id = manager.create_input("mic", 1).unwrap();
+        manager
+            .set_input_channel_map(id, ChannelMap::MonoToAll)
+            .unwrap();
+
+        let node = manager.create_input_node(id).unwrap();
+        assert_eq!(node.channel_map(), ChannelMap::MonoToAll);
+    }
+
     #[test]
     fn test_midi_input_node() {
         let mut node = MidiInputNode::new("controller".to_string());
@@ -1125,4 +1602,96 @@ mod tests {
         let node = manager.create_input_node(id).unwrap();
         assert_eq!(node.descriptor().name, "microphone");
     }
+
+    fn note_on(pitch: u8) -> MidiMessage {
+        MidiMessage::NoteOn {
+            channel: 0,
+            pitch,
+            velocity: 100,
+        }
+    }
+
+    fn scheduler_fixture() -> (MidiScheduler, TickClock, MidiOutputNode) {
+        // 120 BPM: one beat is 24000 samples at 48kHz, 20ms look-ahead is 960
+        let clock = TickClock::new(Arc::new(RwLock::new(TempoMap::default())));
+        let scheduler = MidiScheduler::new(48000, DEFAULT_MIDI_LOOK_AHEAD);
+        (scheduler, clock, MidiOutputNode::new("synth".to_string()))
+    }
+
+    #[test]
+    fn test_scheduler_stamps_frame_offsets() {
+        let (mut scheduler, clock, output) = scheduler_fixture();
+        scheduler.schedule(Beat(0.02), note_on(62));
+        scheduler.schedule(Beat(0.01), note_on(60));
+
+        assert_eq!(scheduler.flush(&clock, &output), 2);
+
+        let queue = output.event_queue();
+        let queue = queue.lock().unwrap();
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[0].frame, 240);
+        assert_eq!(queue[1].frame, 480);
+        assert_eq!(scheduler.late_events(), 0);
+    }
+
+    #[test]
+    fn test_scheduler_holds_events_beyond_look_ahead() {
+        let (mut scheduler, mut clock, output) = scheduler_fixture();
+        scheduler.schedule(Beat(1.0), note_on(60));
+
+        assert_eq!(scheduler.flush(&clock, &output), 0);
+        assert_eq!(scheduler.pending(), 1);
+
+        // 10ms before the event, inside the window
+        clock.seek(Beat(0.98));
+        assert_eq!(scheduler.flush(&clock, &output), 1);
+        assert_eq!(scheduler.pending(), 0);
+
+        let queue = output.event_queue();
+        assert_eq!(queue.lock().unwrap()[0].frame, 480);
+    }
+
+    #[test]
+    fn test_scheduler_sends_late_events_immediately() {
+        let (mut scheduler, mut clock, output) = scheduler_fixture();
+        scheduler.schedule(Beat(0.5), note_on(60));
+        scheduler.schedule(Beat(4.0), note_on(64));
+
+        clock.seek(Beat(1.0));
+        assert_eq!(scheduler.flush(&clock, &output), 1);
+        assert_eq!(scheduler.late_events(), 1);
+        assert_eq!(scheduler.pending(), 1);
+
+        let queue = output.event_queue();
+        assert_eq!(queue.lock().unwrap()[0].frame, 0);
+    }
+
+    #[test]
+    fn test_output_carries_events_past_the_quantum() {
+        let (mut scheduler, clock, mut output) = scheduler_fixture();
+        output.set_active(true);
+        scheduler.schedule(Beat(0.01), note_on(60));
+        scheduler.schedule(Beat(0.03), note_on(64));
+        assert_eq!(scheduler.flush(&clock, &output), 2);
+
+        // The graph adds its own event for this quantum; nothing is lost
+        let mut midi_buf = MidiBuffer::new();
+        midi_buf.events.push(MidiEvent {
+            frame: 10,
+            message: note_on(67),
+        });
+        let inputs = vec![SignalBuffer::Midi(midi_buf)];
+        output.process(&test_context(256), &inputs, &mut []).unwrap();
+
+        let block = output.take_block(256);
+        assert_eq!(block.len(), 2);
+        assert_eq!((block[0].frame, block[1].frame), (10, 240));
+
+        // 720 frames out: two quanta later, at offset 208
+        assert!(output.take_block(256).is_empty());
+        let block = output.take_block(256);
+        assert_eq!(block.len(), 1);
+        assert_eq!(block[0].frame, 208);
+        assert!(output.event_queue().lock().unwrap().is_empty());
+    }
 }
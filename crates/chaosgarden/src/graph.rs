@@ -83,7 +83,7 @@ impl std::fmt::Display for GraphError {
 impl std::error::Error for GraphError {}
 
 /// Serializable snapshot of a graph edge
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EdgeSnapshot {
     pub source_id: Uuid,
     pub source_port: String,
@@ -93,13 +93,101 @@ pub struct EdgeSnapshot {
     pub active: bool,
 }
 
+impl EdgeSnapshot {
+    /// Identity of the connection, ignoring gain and active state
+    fn key(&self) -> (Uuid, &str, Uuid, &str) {
+        (
+            self.source_id,
+            &self.source_port,
+            self.dest_id,
+            &self.dest_port,
+        )
+    }
+}
+
 /// Serializable snapshot of the entire graph
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GraphSnapshot {
     pub nodes: Vec<NodeDescriptor>,
     pub edges: Vec<EdgeSnapshot>,
 }
 
+impl GraphSnapshot {
+    /// Changes needed to turn `previous` into `self`
+    ///
+    /// Nodes are matched by id and edges by their endpoints; a node whose
+    /// descriptor differs, or an edge whose gain/active state differs, is
+    /// reported as changed rather than removed and re-added.
+    pub fn diff(&self, previous: &GraphSnapshot) -> GraphDelta {
+        let prev_nodes: HashMap<Uuid, &NodeDescriptor> =
+            previous.nodes.iter().map(|n| (n.id, n)).collect();
+        let curr_nodes: HashMap<Uuid, &NodeDescriptor> =
+            self.nodes.iter().map(|n| (n.id, n)).collect();
+
+        let mut delta = GraphDelta::default();
+
+        for node in &self.nodes {
+            match prev_nodes.get(&node.id) {
+                None => delta.added_nodes.push(node.clone()),
+                Some(prev) if *prev != node => delta.changed_nodes.push(node.clone()),
+                Some(_) => {}
+            }
+        }
+        delta.removed_nodes = previous
+            .nodes
+            .iter()
+            .filter(|n| !curr_nodes.contains_key(&n.id))
+            .map(|n| n.id)
+            .collect();
+
+        let prev_edges: HashMap<_, &EdgeSnapshot> =
+            previous.edges.iter().map(|e| (e.key(), e)).collect();
+        let curr_edges: HashMap<_, &EdgeSnapshot> =
+            self.edges.iter().map(|e| (e.key(), e)).collect();
+
+        for edge in &self.edges {
+            match prev_edges.get(&edge.key()) {
+                None => delta.added_edges.push(edge.clone()),
+                Some(prev) if *prev != edge => delta.changed_edges.push(edge.clone()),
+                Some(_) => {}
+            }
+        }
+        delta.removed_edges = previous
+            .edges
+            .iter()
+            .filter(|e| !curr_edges.contains_key(&e.key()))
+            .cloned()
+            .collect();
+
+        delta
+    }
+}
+
+/// Difference between two graph snapshots
+///
+/// Broadcast in place of a full [`GraphSnapshot`] once subscribers have a
+/// baseline; a subscriber without one fetches the full snapshot instead.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphDelta {
+    pub added_nodes: Vec<NodeDescriptor>,
+    pub removed_nodes: Vec<Uuid>,
+    pub changed_nodes: Vec<NodeDescriptor>,
+    pub added_edges: Vec<EdgeSnapshot>,
+    pub removed_edges: Vec<EdgeSnapshot>,
+    pub changed_edges: Vec<EdgeSnapshot>,
+}
+
+impl GraphDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
 /// The audio processing graph
 ///
 /// Wraps petgraph's StableGraph with Uuid-based node lookup and
@@ -1007,4 +1095,79 @@ mod tests {
         assert_eq!(graph.node_count(), 0);
         assert!(graph.node(src_id).is_none());
     }
+
+    fn two_node_snapshot() -> (GraphSnapshot, Uuid, Uuid) {
+        let mut graph = Graph::new();
+        let src = TestNode::source("src");
+        let src_id = src.descriptor.id;
+        let sink = TestNode::sink("sink");
+        let sink_id = sink.descriptor.id;
+
+        graph.add_node(Box::new(src));
+        graph.add_node(Box::new(sink));
+        graph.connect(src_id, "output", sink_id, "input").unwrap();
+
+        (graph.snapshot(), src_id, sink_id)
+    }
+
+    #[test]
+    fn test_diff_identical_is_empty() {
+        let (snapshot, _, _) = two_node_snapshot();
+        assert!(snapshot.diff(&snapshot.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_node_added() {
+        let (previous, src_id, _) = two_node_snapshot();
+        let mut current = previous.clone();
+        let fx = TestNode::new("fx", "effect.gain").descriptor;
+        current.nodes.push(fx.clone());
+        current.edges.push(EdgeSnapshot {
+            source_id: src_id,
+            source_port: "output".to_string(),
+            dest_id: fx.id,
+            dest_port: "input".to_string(),
+            gain: 1.0,
+            active: true,
+        });
+
+        let delta = current.diff(&previous);
+        assert_eq!(delta.added_nodes, vec![fx.clone()]);
+        assert_eq!(delta.added_edges.len(), 1);
+        assert_eq!(delta.added_edges[0].dest_id, fx.id);
+        assert!(delta.removed_nodes.is_empty());
+        assert!(delta.changed_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_diff_node_removed() {
+        let (previous, _, sink_id) = two_node_snapshot();
+        let mut current = previous.clone();
+        current.nodes.retain(|n| n.id != sink_id);
+        current.edges.clear();
+
+        let delta = current.diff(&previous);
+        assert_eq!(delta.removed_nodes, vec![sink_id]);
+        assert_eq!(delta.removed_edges, previous.edges);
+        assert!(delta.added_nodes.is_empty());
+    }
+
+    #[test]
+    fn test_diff_node_and_edge_modified() {
+        let (previous, src_id, _) = two_node_snapshot();
+        let mut current = previous.clone();
+        for node in current.nodes.iter_mut().filter(|n| n.id == src_id) {
+            node.name = "renamed".to_string();
+            node.latency_samples = 64;
+        }
+        current.edges[0].gain = 0.5;
+
+        let delta = current.diff(&previous);
+        assert_eq!(delta.changed_nodes.len(), 1);
+        assert_eq!(delta.changed_nodes[0].name, "renamed");
+        assert_eq!(delta.changed_edges.len(), 1);
+        assert_eq!(delta.changed_edges[0].gain, 0.5);
+        assert!(delta.added_nodes.is_empty() && delta.removed_nodes.is_empty());
+        assert!(delta.added_edges.is_empty() && delta.removed_edges.is_empty());
+    }
 }
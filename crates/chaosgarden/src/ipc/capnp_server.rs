@@ -14,7 +14,8 @@ use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
 use hooteproto::{
     capnp_envelope_to_payload, envelope_capnp, payload_to_capnp_envelope,
-    garden_listener::{GardenListener, SplitRouter},
+    garden::{IOPubEvent, Message},
+    garden_listener::{GardenListener, SplitPublisher, SplitRouter},
     request::ToolRequest,
     responses::{
         GardenRegionInfo, GardenRegionsResponse, GardenStatusResponse, ToolResponse,
@@ -25,6 +26,7 @@ use hooteproto::{
 };
 use std::sync::Arc;
 use tokio::select;
+use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
 use crate::daemon::GardenDaemon;
@@ -65,6 +67,9 @@ impl CapnpGardenServer {
 
         info!("🎵 chaosgarden server ready (4 sockets bound)");
 
+        let session = Uuid::new_v4();
+        let mut events = handler.subscribe();
+
         // Main event loop - handle all sockets concurrently
         loop {
             select! {
@@ -134,12 +139,52 @@ impl CapnpGardenServer {
                         }
                     }
                 }
+
+                // Daemon events - broadcast on IOPub
+                event = events.recv() => {
+                    match event {
+                        Ok(event) => {
+                            if let Err(e) = self.publish_event(&sockets.iopub, session, event).await {
+                                error!("Error publishing IOPub event: {}", e);
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("IOPub fell behind, dropped {} events", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            warn!("IOPub event channel closed");
+                            break;
+                        }
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Send a daemon event to IOPub subscribers as a JSON HOOT01 frame
+    async fn publish_event(
+        &self,
+        socket: &SplitPublisher,
+        session: Uuid,
+        event: IOPubEvent,
+    ) -> Result<()> {
+        let msg = Message::new(session, "iopub_event", event);
+        let frame = HootFrame {
+            command: Command::Reply,
+            content_type: ContentType::Json,
+            request_id: msg.header.msg_id,
+            service: "chaosgarden".to_string(),
+            traceparent: None,
+            body: Bytes::from(serde_json::to_vec(&msg)?),
+        };
+        let multipart = frames_to_multipart(&frame.to_frames());
+        socket.tx.lock().await.send(multipart).await
+            .context("Failed to send IOPub event")?;
+        Ok(())
+    }
+
     /// Handle messages on ROUTER sockets (control/shell)
     async fn handle_router_message(
         &self,
@@ -332,6 +377,58 @@ impl CapnpGardenServer {
                 }
             }
 
+            Payload::TransportSetLoop { start_beats, end_beats, enabled } => {
+                match handler.set_loop(start_beats, end_beats, enabled) {
+                    Ok(()) => Payload::TypedResponse(ResponseEnvelope::ack(format!(
+                        "loop_set: {}",
+                        if enabled { "enabled" } else { "disabled" }
+                    ))),
+                    Err(e) => Payload::Error {
+                        code: "invalid_loop".to_string(),
+                        message: e,
+                        details: None,
+                    },
+                }
+            }
+
+            Payload::TransportRecord { enabled, bus } => {
+                match handler.set_recording(enabled, bus) {
+                    Ok(result) => Payload::TypedResponse(ResponseEnvelope::ack(format!(
+                        "record: {}",
+                        result
+                    ))),
+                    Err(e) => Payload::Error {
+                        code: "record_failed".to_string(),
+                        message: e,
+                        details: None,
+                    },
+                }
+            }
+
+            Payload::TimelineSetSections { sections } => {
+                if let Some(bad) = sections.iter().find(|s| s.end_beats <= s.start_beats) {
+                    return Payload::Error {
+                        code: "invalid_section".to_string(),
+                        message: format!("Section '{}' must end after it starts", bad.name),
+                        details: None,
+                    };
+                }
+                let count = sections.len();
+                let sections = sections
+                    .into_iter()
+                    .map(|s| {
+                        let (start, end) = (crate::Beat(s.start_beats), crate::Beat(s.end_beats));
+                        let section = crate::Section::new(&s.name, start, end);
+                        match s.crossfade_beats {
+                            Some(beats) => section.with_crossfade(beats),
+                            None => section,
+                        }
+                    })
+                    .collect();
+                handler.set_sections(sections);
+                Payload::TypedResponse(ResponseEnvelope::ack(format!("sections: {}", count)))
+            }
+
             // Handle ToolRequest variants for garden commands
             Payload::ToolRequest(req) => self.dispatch_tool_request(handler, req),
 
@@ -413,6 +510,7 @@ impl CapnpGardenServer {
             ToolRequest::GardenInputStatus => ShellRequest::GetInputStatus,
             ToolRequest::GardenSetMonitor(r) => ShellRequest::SetMonitor { enabled: r.enabled, gain: r.gain },
             ToolRequest::GardenGetAudioSnapshot(r) => ShellRequest::GetAudioSnapshot { frames: r.frames },
+            ToolRequest::GardenGraph => ShellRequest::ResyncGraph,
 
             // Audio device discovery
             ToolRequest::AudioListDevices => ShellRequest::ListAudioDevices,
@@ -592,8 +690,15 @@ fn shell_reply_to_payload(reply: hooteproto::garden::ShellReply) -> Payload {
                 })
             ))
         }
-        ShellReply::PendingApprovals { approvals: _ } => {
-            Payload::TypedResponse(ResponseEnvelope::ack("pending_approvals".to_string()))
+        ShellReply::PendingApprovals { approvals } => {
+            Payload::TypedResponse(ResponseEnvelope::success(
+                ToolResponse::GardenPendingApprovals(hooteproto::responses::GardenPendingApprovalsResponse {
+                    approvals: approvals
+                        .iter()
+                        .map(hooteproto::garden_snapshot::ApprovalInfo::from_pending)
+                        .collect(),
+                })
+            ))
         }
         // Audio device discovery
         ShellReply::AudioDevices { sources, sinks } => {
@@ -699,6 +804,11 @@ fn shell_reply_to_payload(reply: hooteproto::garden::ShellReply) -> Payload {
                 })
             ))
         }
+        ShellReply::GraphSnapshot { nodes, edges } => {
+            Payload::TypedResponse(ResponseEnvelope::success(
+                ToolResponse::GardenGraph(hooteproto::responses::GardenGraphResponse { nodes, edges })
+            ))
+        }
         // Catch-all for other ShellReply variants (NodeAdded, etc.)
         other => {
             Payload::TypedResponse(ResponseEnvelope::ack(format!("{:?}", other)))
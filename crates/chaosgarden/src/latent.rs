@@ -9,7 +9,7 @@
 
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -40,6 +40,12 @@ pub enum LatentEvent {
         region_id: Uuid,
         reason: Option<String>,
     },
+    /// Nobody answered within `LatentConfig::approval_timeout`; followed by
+    /// the `Approved` or `Rejected` event for `decision`
+    ApprovalTimedOut {
+        region_id: Uuid,
+        decision: Decision,
+    },
     Failed {
         region_id: Uuid,
         error: String,
@@ -70,6 +76,18 @@ pub struct PendingApproval {
     pub content_type: ContentType,
     pub resolved_at: Instant,
     pub generated_by: Option<Uuid>,
+    /// When the timeout decision applies; `None` waits indefinitely
+    pub expires_at: Option<Instant>,
+}
+
+impl PendingApproval {
+    /// Time left before the timeout decision applies, for UI countdowns
+    ///
+    /// `None` when no timeout is configured; zero once expired.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.expires_at
+            .map(|expires_at| expires_at.saturating_duration_since(now))
+    }
 }
 
 /// Records an approval/rejection decision for audit trail
@@ -102,12 +120,21 @@ pub trait IOPubPublisher: Send + Sync {
     fn publish(&self, event: LatentEvent);
 }
 
+/// Recorded as `decided_by` for decisions made by a timeout rather than a
+/// participant
+pub const TIMEOUT_DECIDER: Uuid = Uuid::nil();
+
 /// Configuration for the latent manager
 #[derive(Debug, Clone)]
 pub struct LatentConfig {
     pub auto_approve_tools: HashSet<String>,
     pub default_mix_in: MixInStrategy,
     pub max_concurrent_jobs: usize,
+    /// How long a resolved region waits for a decision; `None` waits forever
+    pub approval_timeout: Option<Duration>,
+    /// Applied when `approval_timeout` elapses. `Approved` approves; anything
+    /// else rejects.
+    pub timeout_decision: Decision,
 }
 
 impl Default for LatentConfig {
@@ -116,6 +143,8 @@ impl Default for LatentConfig {
             auto_approve_tools: HashSet::new(),
             default_mix_in: MixInStrategy::HardCut,
             max_concurrent_jobs: 4,
+            approval_timeout: None,
+            timeout_decision: Decision::Rejected,
         }
     }
 }
@@ -200,6 +229,7 @@ impl LatentManager {
             region.approve();
             self.publisher.publish(LatentEvent::Approved { region_id });
         } else {
+            let resolved_at = Instant::now();
             self.pending_approvals.insert(
                 region_id,
                 PendingApproval {
@@ -207,8 +237,9 @@ impl LatentManager {
                     artifact_id,
                     content_hash,
                     content_type,
-                    resolved_at: Instant::now(),
+                    resolved_at,
                     generated_by: None,
+                    expires_at: self.config.approval_timeout.map(|t| resolved_at + t),
                 },
             );
         }
@@ -303,6 +334,63 @@ impl LatentManager {
         Ok(event)
     }
 
+    /// Whether any pending approval has passed its deadline
+    pub fn has_expired_approvals(&self, now: Instant) -> bool {
+        self.pending_approvals
+            .values()
+            .any(|pa| pa.expires_at.is_some_and(|at| at <= now))
+    }
+
+    /// Apply the configured timeout decision to approvals past their deadline
+    ///
+    /// Call periodically (the daemon does so from its tick). Each expired
+    /// approval emits `ApprovalTimedOut`, then the usual `Approved` or
+    /// `Rejected` event, and is logged with [`TIMEOUT_DECIDER`] as the
+    /// decider. Returns the `ApprovalTimedOut` events.
+    pub fn expire_approvals(&mut self, now: Instant, regions: &mut [Region]) -> Vec<LatentEvent> {
+        let mut expired: Vec<Uuid> = self
+            .pending_approvals
+            .values()
+            .filter(|pa| pa.expires_at.is_some_and(|at| at <= now))
+            .map(|pa| pa.region_id)
+            .collect();
+        expired.sort();
+
+        let decision = match self.config.timeout_decision {
+            Decision::Approved => Decision::Approved,
+            _ => Decision::Rejected,
+        };
+        let reason = Some("approval timed out".to_string());
+
+        let mut events = Vec::with_capacity(expired.len());
+        for region_id in expired {
+            let event = LatentEvent::ApprovalTimedOut {
+                region_id,
+                decision,
+            };
+            self.publisher.publish(event.clone());
+
+            let result = match decision {
+                Decision::Approved => self.approve(region_id, TIMEOUT_DECIDER, regions),
+                _ => self.reject(region_id, TIMEOUT_DECIDER, reason.clone(), regions),
+            };
+            match result {
+                Ok(_) => {
+                    if let Some(entry) = self.decision_log.last_mut() {
+                        entry.reason = reason.clone();
+                    }
+                }
+                // Region was removed or changed state under us; nothing left
+                // to decide
+                Err(_) => {
+                    self.pending_approvals.remove(&region_id);
+                }
+            }
+            events.push(event);
+        }
+        events
+    }
+
     /// Schedule mixing-in of approved content
     pub fn schedule_mix_in(
         &mut self,
@@ -638,6 +726,106 @@ mod tests {
         assert!(manager.can_submit());
     }
 
+    fn resolve(manager: &mut LatentManager, regions: &mut [Region]) {
+        let region_id = regions[0].id;
+        manager.handle_job_started(region_id, "job_123".to_string(), regions);
+        manager.handle_resolved(
+            region_id,
+            "artifact_456".to_string(),
+            "hash_abc".to_string(),
+            ContentType::Midi,
+            regions,
+        );
+    }
+
+    #[test]
+    fn test_approval_timeout_rejects_by_default() {
+        let publisher = Arc::new(MockPublisher::new());
+        let config = LatentConfig {
+            approval_timeout: Some(Duration::from_secs(30)),
+            ..LatentConfig::default()
+        };
+        let mut manager = LatentManager::new(config, publisher.clone());
+        let mut regions = vec![create_latent_region()];
+        let region_id = regions[0].id;
+        resolve(&mut manager, &mut regions);
+
+        let pending = manager.pending_approvals()[0].clone();
+        assert_eq!(
+            pending.remaining(pending.resolved_at + Duration::from_secs(10)),
+            Some(Duration::from_secs(20))
+        );
+
+        let early = pending.resolved_at + Duration::from_secs(29);
+        assert!(!manager.has_expired_approvals(early));
+        assert!(manager.expire_approvals(early, &mut regions).is_empty());
+
+        let late = pending.resolved_at + Duration::from_secs(30);
+        assert_eq!(pending.remaining(late), Some(Duration::ZERO));
+        assert!(manager.has_expired_approvals(late));
+        let events = manager.expire_approvals(late, &mut regions);
+
+        assert!(matches!(
+            events[..],
+            [LatentEvent::ApprovalTimedOut {
+                decision: Decision::Rejected,
+                ..
+            }]
+        ));
+        assert_eq!(regions[0].latent_status(), Some(LatentStatus::Rejected));
+        assert!(manager.pending_approvals().is_empty());
+
+        let log = manager.decision_log();
+        assert_eq!(log[0].region_id, region_id);
+        assert_eq!(log[0].decided_by, TIMEOUT_DECIDER);
+        assert_eq!(log[0].reason.as_deref(), Some("approval timed out"));
+
+        let events = publisher.events();
+        assert!(matches!(
+            events[events.len() - 2..],
+            [
+                LatentEvent::ApprovalTimedOut { .. },
+                LatentEvent::Rejected { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_approval_timeout_can_auto_approve() {
+        let publisher = Arc::new(MockPublisher::new());
+        let config = LatentConfig {
+            approval_timeout: Some(Duration::from_millis(500)),
+            timeout_decision: Decision::Approved,
+            ..LatentConfig::default()
+        };
+        let mut manager = LatentManager::new(config, publisher.clone());
+        let mut regions = vec![create_latent_region()];
+        resolve(&mut manager, &mut regions);
+
+        let resolved_at = manager.pending_approvals()[0].resolved_at;
+        manager.expire_approvals(resolved_at + Duration::from_secs(1), &mut regions);
+
+        assert_eq!(regions[0].latent_status(), Some(LatentStatus::Approved));
+        assert_eq!(manager.decision_log()[0].decision, Decision::Approved);
+    }
+
+    #[test]
+    fn test_no_timeout_waits_indefinitely() {
+        let publisher = Arc::new(MockPublisher::new());
+        let mut manager = LatentManager::new(LatentConfig::default(), publisher.clone());
+        let mut regions = vec![create_latent_region()];
+        resolve(&mut manager, &mut regions);
+
+        let pending = manager.pending_approvals()[0].clone();
+        assert_eq!(pending.remaining(Instant::now()), None);
+
+        let much_later = pending.resolved_at + Duration::from_secs(86_400);
+        assert!(manager
+            .expire_approvals(much_later, &mut regions)
+            .is_empty());
+        assert_eq!(manager.pending_approvals().len(), 1);
+    }
+
     #[test]
     fn test_approve_wrong_state() {
         let publisher = Arc::new(MockPublisher::new());
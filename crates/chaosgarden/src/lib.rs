@@ -30,6 +30,7 @@ pub mod pipewire_input;
 pub mod playback;
 pub mod rave_streaming;
 pub mod primitives;
+pub mod recorder;
 pub mod stream_io;
 pub mod tick_clock;
 
@@ -39,25 +40,31 @@ pub use capabilities::{
     ParticipantKind, SatisfactionResult,
 };
 pub use external_io::{
-    audio_ring_pair, AudioRingConsumer, AudioRingProducer, ExternalIOError, ExternalIOManager,
-    ExternalInputNode, ExternalOutputNode, MidiDevice, MidiDirection, MidiInputNode,
-    MidiOutputNode, PipeWireInput, PipeWireOutput, RingBuffer,
+    audio_ring_pair, AudioRingConsumer, AudioRingProducer, ChannelMap, ExternalIOError,
+    ExternalIOManager, ExternalInputNode, ExternalOutputNode, MidiDevice, MidiDirection,
+    MidiInputNode, MidiOutputNode, MidiScheduler, OverrunWatch, PipeWireInput, PipeWireOutput,
+    RingBuffer, RingStats, ScheduledMidiEvent, DEFAULT_MIDI_LOOK_AHEAD,
+    DEFAULT_OVERRUN_WARN_THRESHOLD,
 };
-pub use graph::{Edge, Graph, GraphError, GraphSnapshot};
+pub use graph::{Edge, Graph, GraphDelta, GraphError, GraphSnapshot};
 pub use ipc::GardenEndpoints;
 pub use latent::{
     ApprovalDecision, Decision, IOPubPublisher, LatentConfig, LatentError, LatentEvent,
-    LatentManager, MixInSchedule, MixInStrategy, PendingApproval,
+    LatentManager, MixInSchedule, MixInStrategy, PendingApproval, TIMEOUT_DECIDER,
 };
 pub use patterns::{
     Bus, BusOutput, Project, Section, SectionHints, Send, Timeline, Track, TrackOutput,
 };
 pub use nodes::{
     decode_audio, decode_wav, AudioFileNode, ContentResolver, DecodedAudio, FileCasClient,
-    MemoryResolver,
+    MemoryResolver, PlaylistNode,
+};
+pub use playback::{
+    ActiveMidiRegion, CompiledGraph, LoopRegion, PendingMidiEvent, PlaybackEngine, PlaybackPosition,
+    DEFAULT_DECLICK_FRAMES, DEFAULT_SECTION_CROSSFADE_BEATS,
 };
-pub use playback::{ActiveMidiRegion, CompiledGraph, PendingMidiEvent, PlaybackEngine, PlaybackPosition};
 pub use primitives::*;
+pub use recorder::{RecordSource, RecordTap, Recorder, Recording, RECORDING_MIME};
 pub use daemon::{DaemonConfig, GardenDaemon};
 pub use monitor_input::{MonitorInputConfig, MonitorInputError, MonitorInputStream, MonitorStats};
 pub use pipewire_output::{MonitorMixState, PipeWireOutputConfig, PipeWireOutputError, PipeWireOutputStream, StreamStats};
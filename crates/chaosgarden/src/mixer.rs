@@ -10,16 +10,65 @@
 //! - **MixerChannel**: Control state for one input (gain, pan, mute, solo)
 //! - **MixerState**: Collection of channels + master controls
 //! - **mix_buffers()**: The RT-safe mixing function (pure math, no I/O)
+//! - **Metering**: Post-fader peak/RMS per channel, recorded by whichever
+//!   path renders the channel and published as throttled
+//!   `IOPubEvent::MeterUpdate`s
 //!
 //! Ring buffers are NOT part of the mixer - they're a transport concern.
 //! The RT callback reads from rings into temp buffers, then calls mix_buffers().
 
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use portable_atomic::AtomicF32;
 use uuid::Uuid;
 
+use crate::ipc::IOPubEvent;
+
+/// Level reported for silence (and anything quieter)
+pub const METER_FLOOR_DB: f32 = -96.0;
+
+/// Per-block multiplier applied to the held peak before a new block is compared
+///
+/// At 256-frame blocks and 48kHz this releases about 8dB per 100ms, slow
+/// enough for the eye to catch a transient.
+pub const PEAK_HOLD_DECAY: f32 = 0.95;
+
+/// Default spacing between meter broadcasts (20 updates per second)
+pub const DEFAULT_METER_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Convert a linear amplitude to dBFS, clamped at [`METER_FLOOR_DB`]
+pub fn linear_to_db(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return METER_FLOOR_DB;
+    }
+    (20.0 * amplitude.log10()).max(METER_FLOOR_DB)
+}
+
+/// Running peak and sum of squares over one processing block
+#[derive(Debug, Default, Clone, Copy)]
+struct BlockLevel {
+    peak: f32,
+    sum_squares: f32,
+    samples: usize,
+}
+
+impl BlockLevel {
+    fn add(&mut self, sample: f32) {
+        self.peak = self.peak.max(sample.abs());
+        self.sum_squares += sample * sample;
+        self.samples += 1;
+    }
+
+    fn rms(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        (self.sum_squares / self.samples as f32).sqrt()
+    }
+}
+
 /// A single input channel in the mixer
 ///
 /// All fields are Arc to allow sharing with RT callback without copying.
@@ -40,6 +89,10 @@ pub struct MixerChannel {
     pub mute: Arc<AtomicBool>,
     /// Solo flag (when any channel is solo'd, only solo'd channels play)
     pub solo: Arc<AtomicBool>,
+    /// Post-fader peak (linear) with decaying hold, written by the mix functions
+    pub peak: Arc<AtomicF32>,
+    /// Post-fader RMS (linear) of the most recent block
+    pub rms: Arc<AtomicF32>,
 }
 
 impl MixerChannel {
@@ -53,6 +106,8 @@ impl MixerChannel {
             pan: Arc::new(AtomicF32::new(0.0)),
             mute: Arc::new(AtomicBool::new(false)),
             solo: Arc::new(AtomicBool::new(false)),
+            peak: Arc::new(AtomicF32::new(0.0)),
+            rms: Arc::new(AtomicF32::new(0.0)),
         }
     }
 
@@ -66,6 +121,8 @@ impl MixerChannel {
             pan: Arc::new(AtomicF32::new(0.0)),
             mute: Arc::new(AtomicBool::new(false)),
             solo: Arc::new(AtomicBool::new(false)),
+            peak: Arc::new(AtomicF32::new(0.0)),
+            rms: Arc::new(AtomicF32::new(0.0)),
         }
     }
 
@@ -104,6 +161,35 @@ impl MixerChannel {
     pub fn set_pan(&self, value: f32) {
         self.pan.store(value.clamp(-1.0, 1.0), Ordering::Relaxed);
     }
+
+    /// Held peak level in dBFS
+    pub fn peak_db(&self) -> f32 {
+        linear_to_db(self.peak.load(Ordering::Relaxed))
+    }
+
+    /// Most recent block RMS level in dBFS
+    pub fn rms_db(&self) -> f32 {
+        linear_to_db(self.rms.load(Ordering::Relaxed))
+    }
+
+    /// Meter a block of rendered samples scaled by `gain` (RT-safe)
+    ///
+    /// For paths that render a channel outside the mix functions. An empty
+    /// block counts as silence, so the meter falls while nothing plays.
+    pub fn record_levels(&self, samples: &[f32], gain: f32) {
+        let mut level = BlockLevel::default();
+        for &sample in samples {
+            level.add(sample * gain);
+        }
+        self.record_block(level);
+    }
+
+    /// Record one block's levels, letting the held peak decay toward them
+    fn record_block(&self, level: BlockLevel) {
+        let held = self.peak.load(Ordering::Relaxed) * PEAK_HOLD_DECAY;
+        self.peak.store(level.peak.max(held), Ordering::Relaxed);
+        self.rms.store(level.rms(), Ordering::Relaxed);
+    }
 }
 
 impl Clone for MixerChannel {
@@ -116,6 +202,8 @@ impl Clone for MixerChannel {
             pan: Arc::clone(&self.pan),
             mute: Arc::clone(&self.mute),
             solo: Arc::clone(&self.solo),
+            peak: Arc::clone(&self.peak),
+            rms: Arc::clone(&self.rms),
         }
     }
 }
@@ -132,6 +220,10 @@ pub struct MixerState {
     pub master_gain: Arc<AtomicF32>,
     /// Master mute
     pub master_mute: Arc<AtomicBool>,
+    /// Minimum spacing between meter broadcasts
+    meter_interval: Duration,
+    /// When meters were last published (None = never)
+    last_meter_emit: Mutex<Option<Instant>>,
 }
 
 impl Default for MixerState {
@@ -147,6 +239,50 @@ impl MixerState {
             channels: Vec::new(),
             master_gain: Arc::new(AtomicF32::new(1.0)),
             master_mute: Arc::new(AtomicBool::new(false)),
+            meter_interval: DEFAULT_METER_INTERVAL,
+            last_meter_emit: Mutex::new(None),
+        }
+    }
+
+    /// Minimum spacing between meter broadcasts
+    pub fn meter_interval(&self) -> Duration {
+        self.meter_interval
+    }
+
+    /// Change the spacing between meter broadcasts
+    pub fn set_meter_interval(&mut self, interval: Duration) {
+        self.meter_interval = interval;
+    }
+
+    /// Build `MeterUpdate` events for every channel, throttled to the meter interval
+    ///
+    /// Returns an empty Vec if the previous batch went out less than
+    /// `meter_interval` before `now`. Call from the control thread, never from RT.
+    pub fn meter_updates(&self, now: Instant) -> Vec<IOPubEvent> {
+        let Ok(mut last_emit) = self.last_meter_emit.lock() else {
+            return Vec::new();
+        };
+        if let Some(last) = *last_emit {
+            if now.saturating_duration_since(last) < self.meter_interval {
+                return Vec::new();
+            }
+        }
+        *last_emit = Some(now);
+
+        self.channels
+            .iter()
+            .map(|channel| IOPubEvent::MeterUpdate {
+                channel: channel.name.clone(),
+                peak_db: channel.peak_db(),
+                rms_db: channel.rms_db(),
+            })
+            .collect()
+    }
+
+    /// Let every channel's meter fall as if it produced a silent block
+    fn record_silence(&self) {
+        for channel in &self.channels {
+            channel.record_block(BlockLevel::default());
         }
     }
 
@@ -206,6 +342,7 @@ impl MixerState {
         output.fill(0.0);
 
         if self.master_mute.load(Ordering::Relaxed) {
+            self.record_silence();
             return;
         }
 
@@ -214,20 +351,26 @@ impl MixerState {
 
         for (idx, channel) in self.channels.iter().enumerate() {
             if !channel.should_play(any_solo) {
+                channel.record_block(BlockLevel::default());
                 continue;
             }
 
             let Some(input) = inputs.get(idx) else {
+                channel.record_block(BlockLevel::default());
                 continue;
             };
 
             let gain = channel.get_gain() * master_gain;
+            let mut level = BlockLevel::default();
 
             for (i, sample) in input.iter().enumerate() {
                 if i < output.len() {
-                    output[i] += sample * gain;
+                    let scaled = sample * gain;
+                    output[i] += scaled;
+                    level.add(scaled);
                 }
             }
+            channel.record_block(level);
         }
     }
 
@@ -242,6 +385,7 @@ impl MixerState {
         output.fill(0.0);
 
         if self.master_mute.load(Ordering::Relaxed) {
+            self.record_silence();
             return;
         }
 
@@ -250,10 +394,12 @@ impl MixerState {
 
         for (idx, channel) in self.channels.iter().enumerate() {
             if !channel.should_play(any_solo) {
+                channel.record_block(BlockLevel::default());
                 continue;
             }
 
             let Some(input) = inputs.get(idx) else {
+                channel.record_block(BlockLevel::default());
                 continue;
             };
 
@@ -269,13 +415,19 @@ impl MixerState {
             let right_gain = angle.sin() * gain;
 
             let output_frames = output.len() / 2;
+            let mut level = BlockLevel::default();
             for (i, &sample) in input.iter().enumerate() {
                 if i >= output_frames {
                     break;
                 }
-                output[i * 2] += sample * left_gain;
-                output[i * 2 + 1] += sample * right_gain;
+                let left = sample * left_gain;
+                let right = sample * right_gain;
+                output[i * 2] += left;
+                output[i * 2 + 1] += right;
+                level.add(left);
+                level.add(right);
             }
+            channel.record_block(level);
         }
     }
 
@@ -289,6 +441,7 @@ impl MixerState {
         output.fill(0.0);
 
         if self.master_mute.load(Ordering::Relaxed) {
+            self.record_silence();
             return;
         }
 
@@ -297,10 +450,12 @@ impl MixerState {
 
         for (idx, channel) in self.channels.iter().enumerate() {
             if !channel.should_play(any_solo) {
+                channel.record_block(BlockLevel::default());
                 continue;
             }
 
             let Some(input) = inputs.get(idx) else {
+                channel.record_block(BlockLevel::default());
                 continue;
             };
 
@@ -316,14 +471,20 @@ impl MixerState {
             let right_mix = angle.sin();
 
             let frames = input.len().min(output.len()) / 2;
+            let mut level = BlockLevel::default();
             for i in 0..frames {
                 let in_l = input[i * 2];
                 let in_r = input[i * 2 + 1];
 
                 // Cross-fade based on pan
-                output[i * 2] += (in_l * left_mix + in_r * (1.0 - right_mix)) * gain;
-                output[i * 2 + 1] += (in_r * right_mix + in_l * (1.0 - left_mix)) * gain;
+                let left = (in_l * left_mix + in_r * (1.0 - right_mix)) * gain;
+                let right = (in_r * right_mix + in_l * (1.0 - left_mix)) * gain;
+                output[i * 2] += left;
+                output[i * 2 + 1] += right;
+                level.add(left);
+                level.add(right);
             }
+            channel.record_block(level);
         }
     }
 }
@@ -337,6 +498,8 @@ pub struct MixerConfig {
     pub channel_prefix: String,
     /// Initial master gain
     pub master_gain: f32,
+    /// Minimum spacing between meter broadcasts (keeps SSE from flooding)
+    pub meter_interval: Duration,
 }
 
 impl Default for MixerConfig {
@@ -345,6 +508,7 @@ impl Default for MixerConfig {
             num_channels: 0,
             channel_prefix: "ch".to_string(),
             master_gain: 1.0,
+            meter_interval: DEFAULT_METER_INTERVAL,
         }
     }
 }
@@ -356,6 +520,7 @@ impl MixerConfig {
         mixer
             .master_gain
             .store(self.master_gain, Ordering::Relaxed);
+        mixer.set_meter_interval(self.meter_interval);
 
         for i in 0..self.num_channels {
             let name = format!("{}_{}", self.channel_prefix, i);
@@ -572,10 +737,12 @@ mod tests {
             num_channels: 4,
             channel_prefix: "voice".to_string(),
             master_gain: 0.8,
+            meter_interval: Duration::from_millis(100),
         };
 
         let mixer = config.build();
         assert_eq!(mixer.channel_count(), 4);
+        assert_eq!(mixer.meter_interval(), Duration::from_millis(100));
         assert!((mixer.master_gain.load(Ordering::Relaxed) - 0.8).abs() < 0.001);
         assert_eq!(mixer.channel(0).unwrap().name, "voice_0");
         assert_eq!(mixer.channel(3).unwrap().name, "voice_3");
@@ -594,4 +761,87 @@ mod tests {
         assert_eq!(mixer.channel_count(), 1);
         assert_eq!(mixer.channel(0).unwrap().id, ch_b.id);
     }
+
+    #[test]
+    fn test_meter_peak_and_rms() {
+        let mut mixer = MixerState::new();
+        let ch = mixer.add_channel(MixerChannel::new("a"));
+        ch.set_gain(0.5);
+
+        // Square wave at ±1.0: post-fader peak and RMS are both 0.5 (-6dB)
+        let input_a = [1.0, -1.0, 1.0, -1.0];
+        let inputs: Vec<&[f32]> = vec![&input_a];
+
+        let mut output = [0.0f32; 4];
+        mixer.mix_mono(&inputs, &mut output);
+
+        assert!((ch.peak.load(Ordering::Relaxed) - 0.5).abs() < 0.001);
+        assert!((ch.rms.load(Ordering::Relaxed) - 0.5).abs() < 0.001);
+        assert!((ch.peak_db() - -6.02).abs() < 0.01, "peak_db={}", ch.peak_db());
+    }
+
+    #[test]
+    fn test_meter_peak_hold_decays() {
+        let mut mixer = MixerState::new();
+        let ch = mixer.add_channel(MixerChannel::new("a"));
+
+        let loud = [1.0, 1.0];
+        let quiet = [0.1, 0.1];
+        let mut output = [0.0f32; 2];
+
+        mixer.mix_mono(&[&loud], &mut output);
+        mixer.mix_mono(&[&quiet], &mut output);
+
+        // Peak holds the transient (decayed once), RMS follows the block
+        let peak = ch.peak.load(Ordering::Relaxed);
+        assert!((peak - PEAK_HOLD_DECAY).abs() < 0.001, "peak={}", peak);
+        assert!((ch.rms.load(Ordering::Relaxed) - 0.1).abs() < 0.001);
+
+        // Muted channels keep decaying toward silence
+        ch.mute.store(true, Ordering::Relaxed);
+        mixer.mix_mono(&[&loud], &mut output);
+        assert!(ch.peak.load(Ordering::Relaxed) < peak);
+        assert_eq!(ch.rms_db(), METER_FLOOR_DB);
+    }
+
+    #[test]
+    fn test_record_levels_outside_mix() {
+        let ch = MixerChannel::new("timeline");
+
+        ch.record_levels(&[1.0, -1.0, 1.0, -1.0], 0.5);
+        assert!((ch.peak.load(Ordering::Relaxed) - 0.5).abs() < 0.001);
+        assert!((ch.rms.load(Ordering::Relaxed) - 0.5).abs() < 0.001);
+
+        // Nothing rendered: RMS drops out and the held peak decays
+        ch.record_levels(&[], 1.0);
+        assert!((ch.peak.load(Ordering::Relaxed) - 0.5 * PEAK_HOLD_DECAY).abs() < 0.001);
+        assert_eq!(ch.rms_db(), METER_FLOOR_DB);
+    }
+
+    #[test]
+    fn test_meter_updates_throttled() {
+        let mut mixer = MixerState::new();
+        mixer.add_channel(MixerChannel::new("a"));
+        mixer.add_channel(MixerChannel::new("b"));
+        mixer.set_meter_interval(Duration::from_millis(50));
+
+        let start = Instant::now();
+        let updates = mixer.meter_updates(start);
+        assert_eq!(updates.len(), 2);
+        assert!(matches!(
+            &updates[0],
+            IOPubEvent::MeterUpdate { channel, peak_db, .. }
+                if channel == "a" && *peak_db == METER_FLOOR_DB
+        ));
+
+        assert!(mixer.meter_updates(start + Duration::from_millis(20)).is_empty());
+        assert_eq!(mixer.meter_updates(start + Duration::from_millis(50)).len(), 2);
+    }
+
+    #[test]
+    fn test_linear_to_db() {
+        assert!((linear_to_db(1.0) - 0.0).abs() < 0.001);
+        assert_eq!(linear_to_db(0.0), METER_FLOOR_DB);
+        assert_eq!(linear_to_db(1e-9), METER_FLOOR_DB);
+    }
 }
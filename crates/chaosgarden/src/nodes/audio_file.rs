@@ -17,8 +17,8 @@ use anyhow::{anyhow, Context, Result};
 use uuid::Uuid;
 
 use crate::primitives::{
-    Node, NodeCapabilities, NodeDescriptor, Port, ProcessContext, ProcessError, SignalBuffer,
-    SignalType,
+    AudioBuffer, Node, NodeCapabilities, NodeDescriptor, Port, ProcessContext, ProcessError,
+    SignalBuffer, SignalType,
 };
 
 /// Decoded audio ready for playback
@@ -270,6 +270,9 @@ pub struct AudioFileNode {
 
     /// Gain (linear)
     gain: f32,
+
+    /// Frames of linear fade-out before end of file (0 = hard cut)
+    declick_frames: usize,
 }
 
 impl AudioFileNode {
@@ -299,6 +302,7 @@ impl AudioFileNode {
             playhead: 0,
             looping: false,
             gain: 1.0,
+            declick_frames: 0,
         }
     }
 
@@ -352,6 +356,11 @@ impl AudioFileNode {
         self.gain = gain;
     }
 
+    /// Fade the last `frames` frames of the file to zero (non-looping only)
+    pub fn set_declick_frames(&mut self, frames: usize) {
+        self.declick_frames = frames;
+    }
+
     /// Get current playhead position in frames
     pub fn playhead(&self) -> usize {
         self.playhead
@@ -372,67 +381,48 @@ impl AudioFileNode {
     pub fn content_hash(&self) -> &str {
         &self.content_hash
     }
-}
-
-impl Node for AudioFileNode {
-    fn descriptor(&self) -> &NodeDescriptor {
-        &self.descriptor
-    }
-
-    fn process(
-        &mut self,
-        ctx: &ProcessContext,
-        _inputs: &[SignalBuffer],
-        outputs: &mut [SignalBuffer],
-    ) -> Result<(), ProcessError> {
-        let audio = self.audio.as_ref().ok_or(ProcessError::Skipped {
-            reason: "not loaded",
-        })?;
 
-        let output = outputs
-            .first_mut()
-            .ok_or(ProcessError::Failed {
-                reason: "no output buffer".to_string(),
-            })?;
-
-        let out_buf = match output {
-            SignalBuffer::Audio(buf) => buf,
-            _ => {
-                return Err(ProcessError::Failed {
-                    reason: "expected audio output".to_string(),
-                })
-            }
+    /// Write up to `frames` frames into `out` starting at frame `offset`
+    ///
+    /// Returns how many frames were written; fewer than asked means the
+    /// file ended (non-looping). Frames not written are left untouched.
+    pub fn render(&mut self, out: &mut AudioBuffer, offset: usize, frames: usize) -> usize {
+        let Some(audio) = &self.audio else {
+            return 0;
         };
 
-        // Clear output first
-        out_buf.clear();
-
-        let frames_to_write = ctx.buffer_size;
         let total_frames = audio.frames();
-        let out_channels = out_buf.channels as usize;
+        let out_channels = out.channels as usize;
         let src_channels = audio.channels as usize;
 
         let mut frame_idx = 0;
-        while frame_idx < frames_to_write {
+        while frame_idx < frames {
             // Check if we've reached end of file
             if self.playhead >= total_frames {
-                if self.looping {
+                if self.looping && total_frames > 0 {
                     self.playhead = 0;
                 } else {
-                    // Fill rest with silence (already cleared)
                     break;
                 }
             }
 
             // How many frames can we copy in this iteration?
             let frames_available = total_frames - self.playhead;
-            let frames_remaining = frames_to_write - frame_idx;
+            let frames_remaining = frames - frame_idx;
             let frames_to_copy = frames_available.min(frames_remaining);
 
             // Copy samples with channel conversion
             for f in 0..frames_to_copy {
                 let src_frame = self.playhead + f;
-                let dst_frame = frame_idx + f;
+                let dst_frame = offset + frame_idx + f;
+
+                // Ramp the final frames down so the file doesn't end on a click
+                let frames_after = total_frames - src_frame - 1;
+                let gain = if !self.looping && frames_after < self.declick_frames {
+                    self.gain * frames_after as f32 / self.declick_frames as f32
+                } else {
+                    self.gain
+                };
 
                 for out_ch in 0..out_channels {
                     // Map output channel to source channel
@@ -445,8 +435,8 @@ impl Node for AudioFileNode {
                     let src_idx = src_frame * src_channels + src_ch;
                     let dst_idx = dst_frame * out_channels + out_ch;
 
-                    if src_idx < audio.samples.len() && dst_idx < out_buf.samples.len() {
-                        out_buf.samples[dst_idx] = audio.samples[src_idx] * self.gain;
+                    if src_idx < audio.samples.len() && dst_idx < out.samples.len() {
+                        out.samples[dst_idx] = audio.samples[src_idx] * gain;
                     }
                 }
             }
@@ -455,6 +445,46 @@ impl Node for AudioFileNode {
             frame_idx += frames_to_copy;
         }
 
+        frame_idx
+    }
+}
+
+impl Node for AudioFileNode {
+    fn descriptor(&self) -> &NodeDescriptor {
+        &self.descriptor
+    }
+
+    fn process(
+        &mut self,
+        ctx: &ProcessContext,
+        _inputs: &[SignalBuffer],
+        outputs: &mut [SignalBuffer],
+    ) -> Result<(), ProcessError> {
+        if self.audio.is_none() {
+            return Err(ProcessError::Skipped {
+                reason: "not loaded",
+            });
+        }
+
+        let output = outputs
+            .first_mut()
+            .ok_or(ProcessError::Failed {
+                reason: "no output buffer".to_string(),
+            })?;
+
+        let out_buf = match output {
+            SignalBuffer::Audio(buf) => buf,
+            _ => {
+                return Err(ProcessError::Failed {
+                    reason: "expected audio output".to_string(),
+                })
+            }
+        };
+
+        // Clear output first
+        out_buf.clear();
+        self.render(out_buf, 0, ctx.buffer_size);
+
         Ok(())
     }
 
@@ -470,7 +500,6 @@ impl Node for AudioFileNode {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::primitives::AudioBuffer;
 
     /// Generate a simple sine wave WAV file in memory
     fn generate_test_wav(frequency: f32, duration_secs: f32, sample_rate: u32) -> Vec<u8> {
@@ -709,6 +738,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_audio_file_node_declick_at_end() {
+        // 100 frames of mono DC at full scale
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 48000,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+            for _ in 0..100 {
+                writer.write_sample(1.0f32).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+
+        let mut resolver = MemoryResolver::new();
+        resolver.insert("dc_short", cursor.into_inner());
+
+        let mut node = AudioFileNode::new("dc_short", Arc::new(resolver));
+        node.preload().unwrap();
+        node.set_declick_frames(32);
+
+        let ctx = ProcessContext {
+            sample_rate: 48000,
+            buffer_size: 128,
+            position_samples: crate::primitives::Sample(0),
+            position_beats: crate::primitives::Beat(0.0),
+            tempo_map: Arc::new(crate::primitives::TempoMap::new(
+                120.0,
+                crate::primitives::TimeSignature::default(),
+            )),
+            mode: crate::primitives::ProcessingMode::Offline,
+            transport: crate::primitives::TransportState::Playing,
+        };
+
+        let mut outputs = vec![SignalBuffer::Audio(AudioBuffer::new(128, 2))];
+        node.process(&ctx, &[], &mut outputs).unwrap();
+
+        if let SignalBuffer::Audio(buf) = &outputs[0] {
+            let left: Vec<f32> = buf.samples.iter().step_by(2).copied().collect();
+            // Untouched until the last 32 frames
+            assert!(left[..68].iter().all(|&s| (s - 1.0).abs() < 1e-6));
+            // Then a monotonic ramp that lands on zero at the final frame
+            assert!(left[67..100].windows(2).all(|w| w[1] < w[0]));
+            assert_eq!(left[99], 0.0);
+        }
+    }
+
     #[test]
     fn test_audio_file_node_seek() {
         let mut resolver = MemoryResolver::new();
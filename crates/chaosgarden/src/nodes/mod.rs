@@ -3,11 +3,13 @@
 //! This module contains concrete node implementations for the chaosgarden graph.
 
 mod audio_file;
+mod playlist;
 
 pub use audio_file::{
     decode_audio, decode_wav, AudioFileNode, ContentResolver, DecodedAudio, FileCasClient,
     MemoryResolver,
 };
+pub use playlist::PlaylistNode;
 
 #[cfg(feature = "symphonia-decode")]
 pub use audio_file::decode_audio_symphonia;
@@ -0,0 +1,544 @@
+//! Gapless playlist node
+//!
+//! Plays a list of CAS audio artifacts back-to-back. A worker thread decodes
+//! one track ahead of the playhead, so at a boundary the RT thread only swaps
+//! in the already-decoded track and carries on filling the same buffer.
+//!
+//! Key design points:
+//! - Decoding happens on the worker thread, never in `process()`
+//! - Tracks that fail to resolve or decode are logged and skipped
+//! - Each transition queues a `MarkerReached` broadcast (drain with `take_markers()`)
+//! - Offline rendering waits for the next track instead of leaving a gap
+//! - `process()` doesn't allocate, free or log: markers are built by the
+//!   worker, and finished tracks and warnings go back to it
+
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use anyhow::{anyhow, Context, Result};
+use hooteproto::Broadcast;
+use uuid::Uuid;
+
+use super::audio_file::{AudioFileNode, ContentResolver};
+use crate::primitives::{
+    Node, NodeCapabilities, NodeDescriptor, Port, ProcessContext, ProcessError, ProcessingMode,
+    Sample, SignalBuffer, SignalType,
+};
+
+/// Transition markers held between `take_markers()` calls; extras are dropped
+const MARKER_QUEUE_CAPACITY: usize = 16;
+
+/// Room for finished tracks on their way back to the worker
+const RETURN_QUEUE_CAPACITY: usize = 4;
+
+/// A decoded playlist entry
+struct Track {
+    index: usize,
+    node: AudioFileNode,
+    /// Marker announcing this track, with the position filled in when it starts
+    marker: Option<Broadcast>,
+}
+
+/// Work handed back from the RT thread to the decode worker
+enum Returned {
+    /// A finished track, freed off the RT thread
+    Track(Track),
+    /// A track boundary came before the next track had decoded
+    Starved,
+}
+
+/// Gapless playback of a sequence of CAS audio artifacts
+///
+/// Call `preload()` before RT playback; it blocks until the first playable
+/// track is decoded and starts the worker on the rest.
+pub struct PlaylistNode {
+    descriptor: NodeDescriptor,
+    content_hashes: Vec<String>,
+    resolver: Arc<dyn ContentResolver>,
+
+    /// Track under the playhead
+    current: Option<Track>,
+
+    /// Decoded track waiting for the current one to end
+    next: Option<Track>,
+
+    /// Decoded tracks from the worker (rendezvous channel, so the worker
+    /// stays exactly one track ahead of `next`). The mutex only makes the
+    /// receiver `Sync`; access goes through `get_mut`, which never locks.
+    prefetch: Option<Mutex<Receiver<Track>>>,
+
+    /// Finished tracks and warnings going back to the worker
+    returns: Option<SyncSender<Returned>>,
+
+    /// Worker has run out of tracks
+    exhausted: bool,
+
+    /// Reached a boundary before the next track was decoded
+    starved: bool,
+
+    /// Gain (linear)
+    gain: f32,
+
+    /// Track transitions not yet collected by the caller (never grows past
+    /// its initial capacity)
+    pending_markers: Vec<Broadcast>,
+}
+
+impl PlaylistNode {
+    pub fn new(content_hashes: Vec<String>, resolver: Arc<dyn ContentResolver>) -> Self {
+        Self {
+            descriptor: NodeDescriptor {
+                id: Uuid::new_v4(),
+                name: format!("Playlist:{}", content_hashes.len()),
+                type_id: "playlist".to_string(),
+                inputs: vec![],
+                outputs: vec![Port {
+                    name: "out".to_string(),
+                    signal_type: SignalType::Audio,
+                }],
+                latency_samples: 0,
+                capabilities: NodeCapabilities {
+                    realtime: true,
+                    offline: true,
+                },
+            },
+            content_hashes,
+            resolver,
+            current: None,
+            next: None,
+            prefetch: None,
+            returns: None,
+            exhausted: false,
+            starved: false,
+            gain: 1.0,
+            pending_markers: Vec::with_capacity(MARKER_QUEUE_CAPACITY),
+        }
+    }
+
+    /// Decode the first playable track and start prefetching the rest
+    pub fn preload(&mut self) -> Result<()> {
+        let (prefetch, returns) =
+            spawn_decoder(self.content_hashes.clone(), Arc::clone(&self.resolver))?;
+        let mut first = prefetch
+            .recv()
+            .map_err(|_| anyhow!("no playable tracks in playlist"))?;
+        first.node.set_gain(self.gain);
+
+        tracing::debug!(
+            index = first.index,
+            hash = %first.node.content_hash(),
+            tracks = self.content_hashes.len(),
+            "playlist preloaded"
+        );
+
+        self.current = Some(first);
+        self.next = None;
+        self.prefetch = Some(Mutex::new(prefetch));
+        self.returns = Some(returns);
+        self.exhausted = false;
+        self.starved = false;
+        Ok(())
+    }
+
+    /// Check if the first track is loaded
+    pub fn is_loaded(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Number of entries in the playlist (including any that fail to decode)
+    pub fn len(&self) -> usize {
+        self.content_hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content_hashes.is_empty()
+    }
+
+    /// Index of the track under the playhead
+    pub fn current_index(&self) -> Option<usize> {
+        self.current.as_ref().map(|t| t.index)
+    }
+
+    /// Content hash of the track under the playhead
+    pub fn current_hash(&self) -> Option<&str> {
+        self.current.as_ref().map(|t| t.node.content_hash())
+    }
+
+    /// Playhead within the current track (in frames)
+    pub fn playhead(&self) -> usize {
+        self.current.as_ref().map_or(0, |t| t.node.playhead())
+    }
+
+    /// Set gain (linear, 1.0 = unity)
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+        if let Some(track) = self.current.as_mut() {
+            track.node.set_gain(gain);
+        }
+    }
+
+    /// Check if the last playable track has finished
+    pub fn is_finished(&self) -> bool {
+        match &self.current {
+            Some(track) => self.exhausted && self.next.is_none() && track.node.is_finished(),
+            None => true,
+        }
+    }
+
+    /// Drain track-transition markers queued since the last call
+    pub fn take_markers(&mut self) -> Vec<Broadcast> {
+        // Drain rather than take, so the queue keeps its capacity
+        self.pending_markers.drain(..).collect()
+    }
+
+    /// Pick up the next decoded track, waiting for it if `block` is set
+    fn fetch_next(&mut self, block: bool) {
+        if self.next.is_some() || self.exhausted {
+            return;
+        }
+        let Some(Ok(prefetch)) = self.prefetch.as_mut().map(Mutex::get_mut) else {
+            return;
+        };
+
+        let received = if block {
+            prefetch.recv().map_err(|_| TryRecvError::Disconnected)
+        } else {
+            prefetch.try_recv()
+        };
+
+        match received {
+            Ok(track) => self.next = Some(track),
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.exhausted = true,
+        }
+    }
+
+    /// Hand something back to the worker without blocking
+    ///
+    /// The queue only fills if the worker stalls for several tracks; the
+    /// item is then dropped here rather than holding up the RT thread.
+    fn send_back(&self, returned: Returned) {
+        if let Some(returns) = &self.returns {
+            let _ = returns.try_send(returned);
+        }
+    }
+
+    /// Move on to the next track at `frame` within this block
+    ///
+    /// Returns false if there is nothing to play yet (or ever).
+    fn advance(&mut self, ctx: &ProcessContext, frame: usize) -> bool {
+        self.fetch_next(ctx.mode == ProcessingMode::Offline);
+
+        let Some(mut track) = self.next.take() else {
+            if !self.exhausted && !self.starved {
+                self.send_back(Returned::Starved);
+                self.starved = true;
+            }
+            return false;
+        };
+
+        if self.pending_markers.len() < self.pending_markers.capacity() {
+            if let Some(mut marker) = track.marker.take() {
+                let sample = Sample(ctx.position_samples.0 + frame as u64);
+                let tick = ctx.tempo_map.sample_to_tick(sample, ctx.sample_rate);
+                if let Broadcast::MarkerReached { position_beats, .. } = &mut marker {
+                    *position_beats = ctx.tempo_map.tick_to_beat(tick).0;
+                }
+                self.pending_markers.push(marker);
+            }
+        }
+
+        track.node.set_gain(self.gain);
+        if let Some(finished) = self.current.replace(track) {
+            self.send_back(Returned::Track(finished));
+        }
+        self.starved = false;
+        true
+    }
+}
+
+/// Decode tracks in order on a worker thread, skipping failures
+///
+/// The rendezvous channel blocks the worker after each decode until the
+/// node takes the track. Between decodes, and once every track is sent, it
+/// frees whatever the node handed back; it exits once the node is dropped.
+fn spawn_decoder(
+    content_hashes: Vec<String>,
+    resolver: Arc<dyn ContentResolver>,
+) -> Result<(Receiver<Track>, SyncSender<Returned>)> {
+    let (tx, rx) = mpsc::sync_channel(0);
+    let (returns_tx, returns) = mpsc::sync_channel(RETURN_QUEUE_CAPACITY);
+
+    thread::Builder::new()
+        .name("playlist-decode".to_string())
+        .spawn(move || {
+            let mut previous_index = None;
+
+            for (index, content_hash) in content_hashes.into_iter().enumerate() {
+                let mut node = AudioFileNode::new(content_hash.clone(), Arc::clone(&resolver));
+                if let Err(e) = node.preload() {
+                    tracing::warn!(
+                        index,
+                        hash = %content_hash,
+                        error = %e,
+                        "skipping playlist track that failed to decode"
+                    );
+                    continue;
+                }
+
+                let marker = Broadcast::MarkerReached {
+                    position_beats: 0.0,
+                    marker_type: "playlist_track".to_string(),
+                    metadata: serde_json::json!({
+                        "index": index,
+                        "content_hash": content_hash,
+                        "previous_index": previous_index,
+                    }),
+                };
+                previous_index = Some(index);
+
+                let track = Track {
+                    index,
+                    node,
+                    marker: Some(marker),
+                };
+                if tx.send(track).is_err() {
+                    return;
+                }
+                returns.try_iter().for_each(handle_returned);
+            }
+
+            drop(tx);
+            returns.iter().for_each(handle_returned);
+        })
+        .context("failed to spawn playlist decode thread")?;
+
+    Ok((rx, returns_tx))
+}
+
+fn handle_returned(returned: Returned) {
+    match returned {
+        Returned::Track(track) => drop(track),
+        Returned::Starved => {
+            tracing::warn!("playlist reached a track boundary before the next track decoded")
+        }
+    }
+}
+
+impl Node for PlaylistNode {
+    fn descriptor(&self) -> &NodeDescriptor {
+        &self.descriptor
+    }
+
+    fn process(
+        &mut self,
+        ctx: &ProcessContext,
+        _inputs: &[SignalBuffer],
+        outputs: &mut [SignalBuffer],
+    ) -> Result<(), ProcessError> {
+        if self.current.is_none() {
+            return Err(ProcessError::Skipped {
+                reason: "not loaded",
+            });
+        }
+
+        let out_buf = match outputs.first_mut() {
+            Some(SignalBuffer::Audio(buf)) => buf,
+            Some(_) => {
+                return Err(ProcessError::Failed {
+                    reason: "expected audio output".to_string(),
+                })
+            }
+            None => {
+                return Err(ProcessError::Failed {
+                    reason: "no output buffer".to_string(),
+                })
+            }
+        };
+
+        out_buf.clear();
+
+        // Keep the worker busy decoding while the current track plays
+        self.fetch_next(false);
+
+        let mut frame_idx = 0;
+        while frame_idx < ctx.buffer_size {
+            let Some(track) = self.current.as_mut() else {
+                break;
+            };
+            frame_idx += track
+                .node
+                .render(out_buf, frame_idx, ctx.buffer_size - frame_idx);
+
+            if frame_idx < ctx.buffer_size && !self.advance(ctx, frame_idx) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rewinds the current track; the playlist itself doesn't restart
+    fn reset(&mut self) {
+        if let Some(track) = self.current.as_mut() {
+            track.node.reset();
+        }
+    }
+
+    fn shutdown(&mut self) {
+        self.current = None;
+        self.next = None;
+        self.prefetch = None;
+        self.returns = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::MemoryResolver;
+    use crate::primitives::{AudioBuffer, Beat, TempoMap, TimeSignature, TransportState};
+    use std::io::Cursor;
+
+    /// Mono WAV holding a constant value, so tracks are easy to tell apart
+    fn constant_wav(value: f32, frames: usize) -> Vec<u8> {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 48000,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+            for _ in 0..frames {
+                writer.write_sample(value).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+        cursor.into_inner()
+    }
+
+    fn offline_context(buffer_size: usize) -> ProcessContext {
+        ProcessContext {
+            sample_rate: 48000,
+            buffer_size,
+            position_samples: Sample(0),
+            position_beats: Beat(0.0),
+            tempo_map: Arc::new(TempoMap::new(120.0, TimeSignature::default())),
+            mode: ProcessingMode::Offline,
+            transport: TransportState::Playing,
+        }
+    }
+
+    fn playlist(entries: &[(&str, Option<f32>)]) -> PlaylistNode {
+        let mut resolver = MemoryResolver::new();
+        for (hash, value) in entries {
+            let data = match value {
+                Some(v) => constant_wav(*v, 100),
+                None => b"not audio".to_vec(),
+            };
+            resolver.insert(*hash, data);
+        }
+        let hashes = entries.iter().map(|(h, _)| h.to_string()).collect();
+        PlaylistNode::new(hashes, Arc::new(resolver))
+    }
+
+    #[test]
+    fn test_playlist_is_gapless() {
+        let mut node = playlist(&[("first", Some(0.5)), ("second", Some(-0.25))]);
+        node.preload().unwrap();
+
+        let ctx = offline_context(256);
+        let mut outputs = vec![SignalBuffer::Audio(AudioBuffer::new(256, 1))];
+        node.process(&ctx, &[], &mut outputs).unwrap();
+
+        let SignalBuffer::Audio(buf) = &outputs[0] else {
+            panic!("expected audio output");
+        };
+        assert!(buf.samples[..100].iter().all(|&s| s == 0.5));
+        assert!(buf.samples[100..200].iter().all(|&s| s == -0.25));
+        assert!(buf.samples[200..].iter().all(|&s| s == 0.0));
+
+        assert_eq!(node.current_index(), Some(1));
+        assert!(node.is_finished());
+    }
+
+    #[test]
+    fn test_playlist_marks_transitions() {
+        let mut node = playlist(&[("first", Some(0.5)), ("second", Some(-0.25))]);
+        node.preload().unwrap();
+
+        let ctx = offline_context(256);
+        let mut outputs = vec![SignalBuffer::Audio(AudioBuffer::new(256, 1))];
+        node.process(&ctx, &[], &mut outputs).unwrap();
+
+        let markers = node.take_markers();
+        assert_eq!(markers.len(), 1);
+        match &markers[0] {
+            Broadcast::MarkerReached {
+                position_beats,
+                marker_type,
+                metadata,
+            } => {
+                assert_eq!(marker_type, "playlist_track");
+                // Frame 100 at 120 BPM / 48kHz
+                assert!((position_beats - 100.0 / 24000.0).abs() < 1e-3);
+                assert_eq!(metadata["index"], 1);
+                assert_eq!(metadata["previous_index"], 0);
+            }
+            other => panic!("unexpected broadcast: {:?}", other),
+        }
+        assert!(node.take_markers().is_empty());
+    }
+
+    #[test]
+    fn test_playlist_marker_queue_stays_bounded() {
+        let names: Vec<String> = (0..MARKER_QUEUE_CAPACITY + 4)
+            .map(|i| format!("track-{}", i))
+            .collect();
+        let entries: Vec<(&str, Option<f32>)> =
+            names.iter().map(|n| (n.as_str(), Some(0.5))).collect();
+        let mut node = playlist(&entries);
+        node.preload().unwrap();
+
+        let ctx = offline_context(4096);
+        let mut outputs = vec![SignalBuffer::Audio(AudioBuffer::new(4096, 1))];
+        node.process(&ctx, &[], &mut outputs).unwrap();
+        assert_eq!(node.current_index(), Some(names.len() - 1));
+
+        assert_eq!(node.take_markers().len(), MARKER_QUEUE_CAPACITY);
+        assert_eq!(node.pending_markers.capacity(), MARKER_QUEUE_CAPACITY);
+    }
+
+    #[test]
+    fn test_playlist_skips_undecodable_tracks() {
+        let mut node = playlist(&[
+            ("broken", None),
+            ("first", Some(0.5)),
+            ("also-broken", None),
+            ("last", Some(-0.25)),
+        ]);
+        node.preload().unwrap();
+        assert_eq!(node.current_index(), Some(1));
+
+        let ctx = offline_context(256);
+        let mut outputs = vec![SignalBuffer::Audio(AudioBuffer::new(256, 1))];
+        node.process(&ctx, &[], &mut outputs).unwrap();
+
+        assert_eq!(node.current_index(), Some(3));
+        assert_eq!(node.current_hash(), Some("last"));
+        if let SignalBuffer::Audio(buf) = &outputs[0] {
+            assert_eq!(buf.samples[100], -0.25);
+        }
+    }
+
+    #[test]
+    fn test_playlist_without_playable_tracks_fails_preload() {
+        let mut node = playlist(&[("broken", None)]);
+        assert!(node.preload().is_err());
+        assert!(!node.is_loaded());
+    }
+}
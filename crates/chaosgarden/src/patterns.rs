@@ -211,6 +211,10 @@ pub struct SectionHints {
     pub density: Option<f64>,
     pub contrast_with: Option<Uuid>,
     pub style_hints: Vec<String>,
+    /// Beats to crossfade from the previous section into this one.
+    /// Overrides the playback engine's default when set.
+    #[serde(default)]
+    pub crossfade_beats: Option<f64>,
 }
 
 /// A named time range with semantic hints
@@ -266,6 +270,11 @@ impl Section {
         self
     }
 
+    pub fn with_crossfade(mut self, beats: f64) -> Self {
+        self.hints.crossfade_beats = Some(beats.max(0.0));
+        self
+    }
+
     /// Duration in beats
     pub fn duration(&self) -> Beat {
         Beat(self.end.0 - self.start.0)
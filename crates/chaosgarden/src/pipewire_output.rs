@@ -22,10 +22,10 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 
-use portable_atomic::AtomicF32;
 use tracing::{debug, error, info};
 
-use crate::external_io::{AudioRingConsumer, AudioRingProducer, RingBuffer};
+use crate::external_io::{AudioRingConsumer, AudioRingProducer, RingBuffer, RingStats};
+use crate::mixer::MixerChannel;
 
 /// Monitor input state for RT mixing (lock-free version)
 ///
@@ -38,10 +38,9 @@ use crate::external_io::{AudioRingConsumer, AudioRingProducer, RingBuffer};
 pub struct MonitorMixState {
     /// Lock-free ring buffer consumer (from monitor input)
     pub consumer: AudioRingConsumer,
-    /// Enable flag (RT-safe)
-    pub enabled: Arc<AtomicBool>,
-    /// Gain 0.0-1.0 (RT-safe)
-    pub gain: Arc<AtomicF32>,
+    /// Mixer channel supplying enable/gain and receiving the monitor's
+    /// levels (all RT-safe atomics)
+    pub channel: Arc<MixerChannel>,
 }
 
 /// Configuration for PipeWire output stream
@@ -111,6 +110,21 @@ pub struct StreamStats {
     pub rave_samples_written: std::sync::atomic::AtomicU64,
     pub rave_reads: std::sync::atomic::AtomicU64,
     pub rave_samples_read: std::sync::atomic::AtomicU64,
+    // Ring xrun counters, mirrored from RingStats each callback
+    pub timeline_underruns: std::sync::atomic::AtomicU64,
+    pub timeline_overruns: std::sync::atomic::AtomicU64,
+    pub tap_overruns: std::sync::atomic::AtomicU64,
+}
+
+impl StreamStats {
+    /// Xrun counters of the timeline ring (daemon → RT callback)
+    pub fn timeline_ring(&self) -> RingStats {
+        RingStats {
+            underruns: self.timeline_underruns.load(std::sync::atomic::Ordering::Relaxed),
+            overruns: self.timeline_overruns.load(std::sync::atomic::Ordering::Relaxed),
+            ..RingStats::default()
+        }
+    }
 }
 
 impl PipeWireOutputStream {
@@ -464,7 +478,8 @@ fn run_pipewire_loop(
 
             // === RT Mixer: Mix monitor input if enabled (lock-free!) ===
             if let Some(ref mut mon) = monitor {
-                if mon.enabled.load(Ordering::Relaxed) {
+                let mut metered = false;
+                if mon.channel.enabled.load(Ordering::Relaxed) {
                     // Lock-free read from SPSC ring buffer - never blocks!
                     let read = mon.consumer.read(temp_slice);
                     if read > 0 {
@@ -495,20 +510,29 @@ fn run_pipewire_loop(
 
                         if !rave_wrote {
                             // No RAVE or write failed - mix raw monitor directly to output
-                            let gain = mon.gain.load(Ordering::Relaxed);
+                            let gain = mon.channel.get_gain();
                             for i in 0..read {
                                 output_slice[i] += temp_slice[i] * gain;
                             }
+                            mon.channel.record_levels(&temp_slice[..read], gain);
+                            metered = true;
                             has_audio = true;
                         }
                         // If rave_wrote, don't set has_audio - RAVE output will provide it
                     }
                 }
+                if !metered {
+                    mon.channel.record_levels(&[], 1.0);
+                }
             }
 
             // === RT Mixer: Mix timeline audio (lock-free!) ===
             let timeline_read = if let Some(ref mut consumer) = timeline_consumer {
-                consumer.read(temp_slice)
+                let read = consumer.read(temp_slice);
+                let ring = consumer.stats();
+                stats.timeline_underruns.store(ring.underruns, Ordering::Relaxed);
+                stats.timeline_overruns.store(ring.overruns, Ordering::Relaxed);
+                read
             } else {
                 0
             };
@@ -555,6 +579,7 @@ fn run_pipewire_loop(
             // This is lock-free (SPSC) so it won't block the RT thread
             if let Some(ref mut tap) = streaming_tap {
                 tap.write(output_slice);
+                stats.tap_overruns.store(tap.stats().overruns, Ordering::Relaxed);
             }
 
             // Fill output buffer
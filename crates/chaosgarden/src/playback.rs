@@ -9,21 +9,26 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
 use std::sync::Arc;
 
+use hooteproto::Broadcast;
 use uuid::Uuid;
 
 use crate::graph::Graph;
 use crate::latent::MixInSchedule;
 use crate::midi_file::ParsedMidiFile;
 use crate::nodes::{AudioFileNode, ContentResolver};
+use crate::patterns::Section;
+use crate::recorder::{RecordSource, RecordTap};
 use crate::primitives::{
     AudioBuffer, Beat, Behavior, BoxedNode, ContentType, MidiBuffer, MidiMessage, Node,
     ProcessContext, ProcessError, ProcessingMode, Region, Sample, SignalBuffer, SignalType,
-    TempoMap, TransportState,
+    TempoMap, Tick, TransportState,
 };
 
 /// Pre-compiled graph ready for realtime execution
 pub struct CompiledGraph {
     nodes: Vec<BoxedNode>,
+    /// Descriptor IDs, parallel to `nodes`
+    node_ids: Vec<Uuid>,
     order: Vec<usize>,
     buffers: Vec<SignalBuffer>,
     buffer_map: Vec<BufferSlot>,
@@ -50,6 +55,7 @@ impl CompiledGraph {
             .collect();
 
         let mut nodes = Vec::with_capacity(node_ids.len());
+        let mut compiled_ids = Vec::with_capacity(node_ids.len());
         let mut id_to_compiled_idx: std::collections::HashMap<Uuid, usize> =
             std::collections::HashMap::new();
 
@@ -57,6 +63,7 @@ impl CompiledGraph {
             if let Some(node) = graph.remove_node(id) {
                 id_to_compiled_idx.insert(id, compiled_idx);
                 nodes.push(node);
+                compiled_ids.push(id);
             }
         }
 
@@ -88,6 +95,7 @@ impl CompiledGraph {
 
         Ok(Self {
             nodes,
+            node_ids: compiled_ids,
             order,
             buffers,
             buffer_map,
@@ -118,6 +126,19 @@ impl CompiledGraph {
             self.buffers.last()
         }
     }
+
+    /// Audio output buffer of a node, e.g. a bus's mixer node
+    pub fn node_output(&self, node_id: Uuid) -> Option<&AudioBuffer> {
+        let idx = self.node_ids.iter().position(|id| *id == node_id)?;
+        let slot = self.buffer_map.get(idx)?;
+        if self.nodes[idx].descriptor().outputs.is_empty() {
+            return None;
+        }
+        match self.buffers.get(slot.buffer_idx) {
+            Some(SignalBuffer::Audio(buffer)) => Some(buffer),
+            _ => None,
+        }
+    }
 }
 
 /// Errors during graph compilation
@@ -145,6 +166,111 @@ pub struct PlaybackPosition {
     pub beats: Beat,
 }
 
+impl PlaybackPosition {
+    /// Position at `beats`, with the sample offset resolved through the tempo map
+    pub fn at_beat(beats: Beat, tempo_map: &TempoMap, sample_rate: u32) -> Self {
+        let beats = Beat(beats.0.max(0.0));
+        Self {
+            samples: Self::beats_to_samples(beats, tempo_map, sample_rate),
+            beats,
+        }
+    }
+
+    /// Convert a beat position to a sample offset, integrating every tempo
+    /// change before it
+    ///
+    /// Works in beats rather than ticks so the result isn't quantized to the
+    /// tempo map's PPQ — at 960 PPQ and 48 kHz a tick is ~25 samples.
+    pub fn beats_to_samples(beats: Beat, tempo_map: &TempoMap, sample_rate: u32) -> Sample {
+        let mut seconds = 0.0;
+        let mut segment_start = 0.0;
+        let mut bpm = tempo_map.tempo_at(Tick::zero());
+
+        for change in &tempo_map.tempo_changes {
+            let change_beat = tempo_map.tick_to_beat(change.tick).0;
+            if change_beat >= beats.0 {
+                break;
+            }
+            seconds += (change_beat - segment_start) * 60.0 / bpm;
+            segment_start = change_beat;
+            bpm = change.bpm;
+        }
+        seconds += (beats.0 - segment_start) * 60.0 / bpm;
+
+        Sample((seconds * sample_rate as f64).round().max(0.0) as u64)
+    }
+}
+
+/// Default declick ramp length (about 3ms at 44.1/48kHz)
+pub const DEFAULT_DECLICK_FRAMES: usize = 128;
+
+/// Default crossfade at section changes, for sections without their own hint
+pub const DEFAULT_SECTION_CROSSFADE_BEATS: f64 = 1.0;
+
+/// Linear ramp-to-zero rendered after a stop or pause, instead of cutting
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Declick {
+    total_frames: usize,
+    frames_left: usize,
+    /// Rewind to the start once the ramp finishes (stop rather than pause)
+    reset_on_finish: bool,
+}
+
+impl Declick {
+    /// Scale `output` along the ramp; frames past its end are silenced
+    fn apply(&mut self, output: &mut AudioBuffer) {
+        let channels = output.channels as usize;
+        for frame in output.samples.chunks_mut(channels) {
+            let gain = if self.frames_left > 0 {
+                self.frames_left -= 1;
+                self.frames_left as f32 / self.total_frames as f32
+            } else {
+                0.0
+            };
+            for sample in frame {
+                *sample *= gain;
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.frames_left == 0
+    }
+}
+
+/// Transport loop region
+///
+/// When enabled and the playhead crosses `end`, playback wraps to `start`.
+/// A playhead already past `end` (e.g. after a seek) plays on unlooped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopRegion {
+    pub start: Beat,
+    /// Loop end; None loops at the end of the last region on the timeline
+    pub end: Option<Beat>,
+    pub enabled: bool,
+}
+
+impl LoopRegion {
+    /// Validate and build a loop region
+    pub fn new(start: Beat, end: Option<Beat>, enabled: bool) -> Result<Self, PlaybackError> {
+        if start.0 < 0.0 {
+            return Err(PlaybackError::InvalidLoop(format!(
+                "loop start {} is negative",
+                start.0
+            )));
+        }
+        if let Some(end) = end {
+            if end.0 <= start.0 {
+                return Err(PlaybackError::InvalidLoop(format!(
+                    "loop end {} must be after start {}",
+                    end.0, start.0
+                )));
+            }
+        }
+        Ok(Self { start, end, enabled })
+    }
+}
+
 /// Tracks an in-progress crossfade
 // TODO(routing): Implement actual crossfade mixing when audio routing is added
 #[allow(dead_code)]
@@ -157,12 +283,86 @@ struct ActiveCrossfade {
     progress: f32,
 }
 
+/// A section boundary being crossfaded: the outgoing section's regions ramp
+/// down while the incoming section's ramp up over `[start, start + beats)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SectionFade {
+    outgoing: Uuid,
+    incoming: Uuid,
+    start: Beat,
+    beats: f64,
+}
+
+impl SectionFade {
+    /// Find the fade covering `beat`, if any.
+    ///
+    /// A fade starts where one section ends and the next begins. Its length
+    /// comes from the incoming section's `crossfade_beats` hint, falling back
+    /// to `default_beats`, and never exceeds the incoming section itself.
+    fn at(sections: &[Section], default_beats: f64, beat: Beat) -> Option<Self> {
+        const BOUNDARY_EPSILON: f64 = 1e-9;
+
+        sections.iter().find_map(|incoming| {
+            let beats = incoming
+                .hints
+                .crossfade_beats
+                .unwrap_or(default_beats)
+                .min(incoming.duration().0);
+            if beats <= 0.0 || beat.0 < incoming.start.0 || beat.0 >= incoming.start.0 + beats {
+                return None;
+            }
+            let outgoing = sections
+                .iter()
+                .find(|s| (s.end.0 - incoming.start.0).abs() < BOUNDARY_EPSILON)?;
+            Some(Self {
+                outgoing: outgoing.id,
+                incoming: incoming.id,
+                start: incoming.start,
+                beats,
+            })
+        })
+    }
+
+    /// Equal-power gains `(outgoing, incoming)` at `beat`, so the summed
+    /// level stays steady through the overlap.
+    fn gains(&self, beat: Beat) -> (f32, f32) {
+        let progress = ((beat.0 - self.start.0) / self.beats).clamp(0.0, 1.0);
+        let angle = progress * std::f64::consts::FRAC_PI_2;
+        (angle.cos() as f32, angle.sin() as f32)
+    }
+
+    /// Beat where the fade is over
+    fn end(&self) -> Beat {
+        Beat(self.start.0 + self.beats)
+    }
+
+    /// Gain at `beat` for a region that starts in `section` and ends at
+    /// `region_end`.
+    ///
+    /// Only outgoing regions that end by the close of the window fade out;
+    /// one that runs on into the next section carries on at full gain.
+    fn gain_for(&self, section: Option<Uuid>, region_end: Beat, beat: Beat) -> f32 {
+        let (outgoing, incoming) = self.gains(beat);
+        match section {
+            Some(id) if id == self.outgoing && region_end.0 <= self.end().0 => outgoing,
+            Some(id) if id == self.incoming => incoming,
+            _ => 1.0,
+        }
+    }
+}
+
 /// Tracks an active audio region with its AudioFileNode
 struct ActiveAudioRegion {
     region_id: Uuid,
     node: AudioFileNode,
     /// Gain from region's PlaybackParams
     gain: f32,
+    /// Section the region starts in, for section crossfades
+    section_id: Option<Uuid>,
+    /// Timeline position of the region, for re-seeking on transport jumps
+    region_start: Beat,
+    /// Where the region ends, to tell fading regions from continuing ones
+    region_end: Beat,
 }
 
 /// Tracks an active MIDI region with parsed events
@@ -211,6 +411,22 @@ pub struct PlaybackEngine {
     content_resolver: Option<Arc<dyn ContentResolver>>,
     /// Scratch buffer for mixing region audio
     region_buffer: AudioBuffer,
+    /// Assembles a block that was split at the loop end
+    split_buffer: AudioBuffer,
+    /// Arrangement sections, used to crossfade at section changes
+    sections: Vec<Section>,
+    /// Default crossfade length at section changes (0 = hard cut)
+    crossfade_beats: f64,
+    /// Transport loop region (None = no loop)
+    loop_region: Option<LoopRegion>,
+    /// Markers fired during processing, drained by `take_markers()`
+    pending_markers: Vec<Broadcast>,
+    /// Ramp length applied on stop and at the end of audio files (0 = hard cut)
+    declick_frames: usize,
+    /// Stop ramp in progress
+    declick: Option<Declick>,
+    /// Bounce-to-disk tap, fed after each block while recording is armed
+    record_tap: Option<RecordTap>,
 }
 
 impl PlaybackEngine {
@@ -231,6 +447,14 @@ impl PlaybackEngine {
             active_midi_regions: HashMap::new(),
             content_resolver: None,
             region_buffer: AudioBuffer::new(buffer_size, 2),
+            split_buffer: AudioBuffer::new(buffer_size, 2),
+            sections: Vec::new(),
+            crossfade_beats: DEFAULT_SECTION_CROSSFADE_BEATS,
+            loop_region: None,
+            pending_markers: Vec::with_capacity(4),
+            declick_frames: DEFAULT_DECLICK_FRAMES,
+            declick: None,
+            record_tap: None,
         }
     }
 
@@ -256,6 +480,14 @@ impl PlaybackEngine {
             active_midi_regions: HashMap::new(),
             content_resolver: Some(resolver),
             region_buffer: AudioBuffer::new(buffer_size, 2),
+            split_buffer: AudioBuffer::new(buffer_size, 2),
+            sections: Vec::new(),
+            crossfade_beats: DEFAULT_SECTION_CROSSFADE_BEATS,
+            loop_region: None,
+            pending_markers: Vec::with_capacity(4),
+            declick_frames: DEFAULT_DECLICK_FRAMES,
+            declick: None,
+            record_tap: None,
         }
     }
 
@@ -264,6 +496,63 @@ impl PlaybackEngine {
         self.content_resolver = Some(resolver);
     }
 
+    /// Set the arrangement sections used for section-change crossfades
+    pub fn set_sections(&mut self, sections: Vec<Section>) {
+        self.sections = sections;
+    }
+
+    /// Arrangement sections in use
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Set the default crossfade length at section changes, in beats
+    ///
+    /// Sections can override it with `SectionHints::crossfade_beats`.
+    pub fn set_crossfade_beats(&mut self, beats: f64) {
+        self.crossfade_beats = beats.max(0.0);
+    }
+
+    /// Set the declick ramp length in frames (0 = hard cut)
+    pub fn set_declick_frames(&mut self, frames: usize) {
+        self.declick_frames = frames;
+        for active in self.active_audio_nodes.values_mut() {
+            active.node.set_declick_frames(frames);
+        }
+    }
+
+    /// Set or clear the transport loop region
+    pub fn set_loop(&mut self, loop_region: Option<LoopRegion>) {
+        self.loop_region = loop_region;
+    }
+
+    /// Current transport loop region
+    pub fn loop_region(&self) -> Option<LoopRegion> {
+        self.loop_region
+    }
+
+    /// Drain markers fired since the last call (e.g. `"loop"` wraps)
+    pub fn take_markers(&mut self) -> Vec<Broadcast> {
+        self.pending_markers.drain(..).collect()
+    }
+
+    /// Arm recording: every processed block is pushed into `tap`
+    ///
+    /// Replaces (and returns) any tap already armed.
+    pub fn arm_record(&mut self, tap: RecordTap) -> Option<RecordTap> {
+        self.record_tap.replace(tap)
+    }
+
+    /// Disarm recording, handing back the tap so its recorder can finish
+    pub fn take_record_tap(&mut self) -> Option<RecordTap> {
+        self.record_tap.take()
+    }
+
+    /// Whether a record tap is armed
+    pub fn is_recording(&self) -> bool {
+        self.record_tap.is_some()
+    }
+
     /// Add a MIDI region for playback
     ///
     /// The region will start playing at the specified beat position.
@@ -306,6 +595,11 @@ impl PlaybackEngine {
         self.active_midi_regions.keys().copied().collect()
     }
 
+    /// IDs of the audio regions currently sounding
+    pub fn active_audio_region_ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.active_audio_nodes.keys().copied()
+    }
+
     /// Get pending MIDI events that should be sent based on current playback position
     ///
     /// This method should be called each tick to collect events that need to be
@@ -367,11 +661,63 @@ impl PlaybackEngine {
         graph: &mut CompiledGraph,
         regions: &[Region],
     ) -> Result<&AudioBuffer, PlaybackError> {
-        if self.transport != TransportState::Playing {
+        if self.transport != TransportState::Playing && self.declick.is_none() {
             self.output.clear();
             return Ok(&self.output);
         }
 
+        // A block that crosses the loop end is rendered in pieces, wrapping
+        // between them, so the loop start plays on the sample after the end
+        let block = self.buffer_size;
+        let channels = self.output.channels as usize;
+        let mut done = 0;
+        while done < block {
+            let frames = self
+                .frames_to_loop_end(regions)
+                .map_or(block - done, |f| f.clamp(1, block - done));
+
+            let segment_start = self.position.samples;
+            self.render_segment(graph, regions, frames);
+            self.wrap_loop(segment_start, regions);
+
+            if frames == block {
+                break;
+            }
+            self.split_buffer.samples.resize(block * channels, 0.0);
+            self.split_buffer.samples[done * channels..(done + frames) * channels]
+                .copy_from_slice(&self.output.samples);
+            done += frames;
+            if done == block {
+                std::mem::swap(&mut self.output, &mut self.split_buffer);
+            }
+        }
+
+        if let Some(declick) = self.declick.filter(Declick::is_finished) {
+            self.declick = None;
+            if declick.reset_on_finish {
+                self.rewind();
+            }
+        }
+
+        Ok(&self.output)
+    }
+
+    /// Render `frames` frames into `output` and advance past them
+    ///
+    /// Scratch and graph buffers are resized to the segment; shrinking and
+    /// regrowing stays within their capacity, so this doesn't allocate.
+    fn render_segment(&mut self, graph: &mut CompiledGraph, regions: &[Region], frames: usize) {
+        let channels = self.output.channels as usize;
+        self.output.samples.resize(frames * channels, 0.0);
+        self.region_buffer
+            .samples
+            .resize(frames * self.region_buffer.channels as usize, 0.0);
+        for buffer in &mut graph.buffers {
+            if let SignalBuffer::Audio(ab) = buffer {
+                ab.samples.resize(frames * ab.channels as usize, 0.0);
+            }
+        }
+
         self.apply_pending_mix_ins();
 
         // Activate/deactivate regions based on current position
@@ -379,7 +725,7 @@ impl PlaybackEngine {
 
         let ctx = ProcessContext {
             sample_rate: self.sample_rate,
-            buffer_size: self.buffer_size,
+            buffer_size: frames,
             position_samples: self.position.samples,
             position_beats: self.position.beats,
             tempo_map: self.tempo_map.clone(),
@@ -412,7 +758,7 @@ impl PlaybackEngine {
             }
 
             let mut outputs: Vec<SignalBuffer> =
-                vec![SignalBuffer::Audio(AudioBuffer::new(self.buffer_size, 2))];
+                vec![SignalBuffer::Audio(AudioBuffer::new(frames, 2))];
 
             if let Some(node) = graph.nodes.get_mut(node_idx) {
                 match node.process(&ctx, &inputs, &mut outputs) {
@@ -448,14 +794,28 @@ impl PlaybackEngine {
         // Process active audio regions and mix into output
         self.process_active_audio_regions(&ctx);
 
-        self.advance_position();
+        if let Some(declick) = self.declick.as_mut() {
+            declick.apply(&mut self.output);
+        }
 
-        Ok(&self.output)
+        if let Some(tap) = self.record_tap.as_mut() {
+            match tap.source() {
+                RecordSource::Master => tap.push(&self.output.samples),
+                RecordSource::Node(id) => {
+                    if let Some(buffer) = graph.node_output(id) {
+                        tap.push(&buffer.samples);
+                    }
+                }
+            }
+        }
+
+        self.advance_position(frames);
     }
 
     /// Update which regions are active based on current playback position
     fn update_active_regions(&mut self, regions: &[Region]) {
         let current_beat = self.position.beats;
+        let fade = SectionFade::at(&self.sections, self.crossfade_beats, current_beat);
 
         // Find regions that should be active at current position
         let mut should_be_active: HashSet<Uuid> = HashSet::new();
@@ -467,8 +827,15 @@ impl PlaybackEngine {
                 continue;
             }
 
+            // Outgoing regions that were still sounding at the boundary keep
+            // playing through the fade so the mixer can sum both sections
+            let fading_out = fade.is_some_and(|f| {
+                region.end().0 >= f.start.0
+                    && self.section_id_at(region.position) == Some(f.outgoing)
+            });
+
             // Check if region overlaps current position
-            if region.contains(current_beat) {
+            if region.contains(current_beat) || fading_out {
                 // Only activate PlayContent::Audio regions
                 if let Behavior::PlayContent {
                     content_type: ContentType::Audio,
@@ -515,6 +882,7 @@ impl PlaybackEngine {
                 } = &region.behavior
                 {
                     let mut node = AudioFileNode::new(content_hash.clone(), resolver.clone());
+                    node.set_declick_frames(self.declick_frames);
 
                     // Pre-load audio
                     match node.preload() {
@@ -522,10 +890,18 @@ impl PlaybackEngine {
                             // Calculate seek position within the region
                             let region_offset = current_beat.0 - region.position.0;
                             if region_offset > 0.0 {
-                                // Convert beat offset to seconds, then seek
-                                let tick = self.tempo_map.beat_to_tick(Beat(region_offset));
-                                let seconds = self.tempo_map.tick_to_second(tick);
-                                node.seek_seconds(seconds.0);
+                                // Elapsed time since the region started, through the tempo map
+                                let to_samples = |beat| {
+                                    PlaybackPosition::beats_to_samples(
+                                        beat,
+                                        &self.tempo_map,
+                                        self.sample_rate,
+                                    )
+                                    .0
+                                };
+                                let elapsed = to_samples(current_beat)
+                                    .saturating_sub(to_samples(region.position));
+                                node.seek_seconds(elapsed as f64 / self.sample_rate as f64);
                             }
 
                             tracing::debug!(
@@ -534,12 +910,16 @@ impl PlaybackEngine {
                                 "activated audio region"
                             );
 
+                            let section_id = self.section_id_at(region.position);
                             self.active_audio_nodes.insert(
                                 region.id,
                                 ActiveAudioRegion {
                                     region_id: region.id,
                                     node,
                                     gain: params.gain as f32,
+                                    section_id,
+                                    region_start: region.position,
+                                    region_end: region.end(),
                                 },
                             );
                         }
@@ -558,8 +938,20 @@ impl PlaybackEngine {
         }
     }
 
+    /// ID of the section containing `beat`
+    fn section_id_at(&self, beat: Beat) -> Option<Uuid> {
+        self.sections.iter().find(|s| s.contains(beat)).map(|s| s.id)
+    }
+
     /// Process all active audio regions and mix into output
     fn process_active_audio_regions(&mut self, ctx: &ProcessContext) {
+        // Gain ramps are computed at both ends of the buffer and interpolated
+        // per sample, so fades stay smooth regardless of buffer size
+        let start_beat = self.position.beats;
+        let end_beat = self.beat_after(ctx.buffer_size);
+        let fade = SectionFade::at(&self.sections, self.crossfade_beats, start_beat)
+            .or_else(|| SectionFade::at(&self.sections, self.crossfade_beats, end_beat));
+
         // Process each active audio node
         for active in self.active_audio_nodes.values_mut() {
             // Clear scratch buffer
@@ -575,7 +967,16 @@ impl PlaybackEngine {
                 Ok(()) => {
                     // Mix into main output with region gain
                     if let Some(SignalBuffer::Audio(buf)) = outputs.first() {
-                        self.output.mix(buf, active.gain);
+                        match fade {
+                            Some(fade) => self.output.mix_ramped(
+                                buf,
+                                active.gain
+                                    * fade.gain_for(active.section_id, active.region_end, start_beat),
+                                active.gain
+                                    * fade.gain_for(active.section_id, active.region_end, end_beat),
+                            ),
+                            None => self.output.mix(buf, active.gain),
+                        }
                     }
                 }
                 Err(ProcessError::Skipped { reason }) => {
@@ -622,45 +1023,78 @@ impl PlaybackEngine {
             .retain(|cf| cf.end_beat.0 > self.position.beats.0);
     }
 
-    fn advance_position(&mut self) {
-        let samples_per_buffer = self.buffer_size as u64;
-        self.position.samples = Sample(self.position.samples.0 + samples_per_buffer);
+    fn advance_position(&mut self, frames: usize) {
+        self.position.samples = Sample(self.position.samples.0 + frames as u64);
         self.position.beats = self.tempo_map.tick_to_beat(
             self.tempo_map
                 .sample_to_tick(self.position.samples, self.sample_rate),
         );
     }
 
-    /// Transport control: play
-    pub fn play(&mut self) {
-        self.transport = TransportState::Playing;
+    /// Enabled loop region and its resolved end beat, if it spans anything
+    fn active_loop(&self, regions: &[Region]) -> Option<(LoopRegion, Beat)> {
+        let loop_region = self.loop_region.filter(|l| l.enabled)?;
+        let end = match loop_region.end {
+            Some(end) => end,
+            None => Beat(regions.iter().map(|r| r.end().0).fold(0.0, f64::max)),
+        };
+        (end.0 > loop_region.start.0).then_some((loop_region, end))
     }
 
-    /// Transport control: stop
-    pub fn stop(&mut self) {
-        self.transport = TransportState::Stopped;
-        self.position = PlaybackPosition::default();
-        // Clear failed preload cache so regions can be retried
-        self.failed_preload.clear();
-        // Reset MIDI region playheads (but don't remove them)
-        for active in self.active_midi_regions.values_mut() {
-            active.playhead_tick = 0;
-            active.last_processed_tick = 0;
-        }
+    /// Frames left before the playhead reaches the loop end, if it's
+    /// heading for one
+    fn frames_to_loop_end(&self, regions: &[Region]) -> Option<usize> {
+        let (_, end) = self.active_loop(regions)?;
+        let end_samples =
+            PlaybackPosition::beats_to_samples(end, &self.tempo_map, self.sample_rate).0;
+        end_samples
+            .checked_sub(self.position.samples.0)
+            .filter(|&frames| frames > 0)
+            .map(|frames| usize::try_from(frames).unwrap_or(usize::MAX))
     }
 
-    /// Transport control: pause
-    pub fn pause(&mut self) {
-        self.transport = TransportState::Stopped;
+    /// Wrap to the loop start if the segment that started at `segment_start`
+    /// reached the loop end
+    ///
+    /// `process()` stops segments at the end, so this normally lands exactly
+    /// on the start; any overshoot still carries over so timing never drifts.
+    fn wrap_loop(&mut self, segment_start: Sample, regions: &[Region]) {
+        let Some((loop_region, end)) = self.active_loop(regions) else {
+            return;
+        };
+
+        let to_samples =
+            |beat| PlaybackPosition::beats_to_samples(beat, &self.tempo_map, self.sample_rate).0;
+        let end_samples = to_samples(end);
+        if segment_start.0 >= end_samples || self.position.samples.0 < end_samples {
+            return;
+        }
+
+        let start_samples = to_samples(loop_region.start);
+        let loop_length = end_samples.saturating_sub(start_samples).max(1);
+        let overshoot = (self.position.samples.0 - end_samples) % loop_length;
+
+        self.position.samples = Sample(start_samples + overshoot);
+        self.position.beats = self.tempo_map.tick_to_beat(
+            self.tempo_map
+                .sample_to_tick(self.position.samples, self.sample_rate),
+        );
+        self.relocate_playheads();
+
+        self.pending_markers.push(Broadcast::MarkerReached {
+            position_beats: end.0,
+            marker_type: "loop".to_string(),
+            metadata: serde_json::json!({
+                "start_beats": loop_region.start.0,
+                "end_beats": end.0,
+            }),
+        });
     }
 
-    /// Transport control: seek
-    pub fn seek(&mut self, beat: Beat) {
-        let tick = self.tempo_map.beat_to_tick(beat);
-        self.position.samples = self.tempo_map.tick_to_sample(tick, self.sample_rate);
-        self.position.beats = beat;
+    /// Move MIDI and audio playheads to the current position after a jump
+    fn relocate_playheads(&mut self) {
+        let beat = self.position.beats;
 
-        // Update MIDI region playheads based on new position
         for active in self.active_midi_regions.values_mut() {
             let beat_in_region = beat.0 - active.region_start_beat.0;
             if beat_in_region < 0.0 {
@@ -674,6 +1108,107 @@ impl PlaybackEngine {
                 active.last_processed_tick = midi_tick;
             }
         }
+
+        // Audio regions the jump leaves are dropped by the next
+        // update_active_regions(); the rest continue from the new offset
+        for active in self.active_audio_nodes.values_mut() {
+            let region_start = PlaybackPosition::beats_to_samples(
+                active.region_start,
+                &self.tempo_map,
+                self.sample_rate,
+            );
+            let elapsed = self.position.samples.0.saturating_sub(region_start.0);
+            active
+                .node
+                .seek_seconds(elapsed as f64 / self.sample_rate as f64);
+        }
+    }
+
+    /// Beat position `frames` past the playhead
+    fn beat_after(&self, frames: usize) -> Beat {
+        let end = Sample(self.position.samples.0 + frames as u64);
+        self.tempo_map
+            .tick_to_beat(self.tempo_map.sample_to_tick(end, self.sample_rate))
+    }
+
+    /// Transport control: play
+    ///
+    /// Cancels any stop ramp still in progress.
+    pub fn play(&mut self) {
+        self.transport = TransportState::Playing;
+        self.declick = None;
+    }
+
+    /// Transport control: stop
+    ///
+    /// While playing, output ramps to zero over the declick length before
+    /// the position rewinds; keep calling `process()` until `is_stopping()`
+    /// is false.
+    pub fn stop(&mut self) {
+        // Clear failed preload cache so regions can be retried
+        self.failed_preload.clear();
+        if self.begin_declick(true) {
+            return;
+        }
+        self.transport = TransportState::Stopped;
+        self.rewind();
+    }
+
+    /// Transport control: pause
+    ///
+    /// Ramps out like `stop()`, but keeps the position.
+    pub fn pause(&mut self) {
+        self.begin_declick(false);
+        self.transport = TransportState::Stopped;
+    }
+
+    /// Whether a stop ramp is still rendering
+    pub fn is_stopping(&self) -> bool {
+        self.declick.is_some()
+    }
+
+    /// Start (or join) a stop ramp, returning false if there is nothing to fade
+    ///
+    /// A stop that arrives mid-ramp keeps the current ramp, so the level
+    /// never jumps back to full volume.
+    fn begin_declick(&mut self, reset_on_finish: bool) -> bool {
+        if let Some(declick) = self.declick.as_mut() {
+            declick.reset_on_finish |= reset_on_finish;
+            return true;
+        }
+        if self.transport != TransportState::Playing || self.declick_frames == 0 {
+            return false;
+        }
+        self.transport = TransportState::Stopped;
+        self.declick = Some(Declick {
+            total_frames: self.declick_frames,
+            frames_left: self.declick_frames,
+            reset_on_finish,
+        });
+        true
+    }
+
+    /// Move the playhead back to the start
+    fn rewind(&mut self) {
+        self.position = PlaybackPosition::default();
+        // Reset MIDI region playheads (but don't remove them)
+        for active in self.active_midi_regions.values_mut() {
+            active.playhead_tick = 0;
+            active.last_processed_tick = 0;
+        }
+    }
+
+    /// Transport control: seek
+    ///
+    /// Returns the resolved position, which lands on the right sample even
+    /// inside tempo changes.
+    pub fn seek(&mut self, beat: Beat) -> PlaybackPosition {
+        // The jump is discontinuous anyway; finishing a ramp would only
+        // fade the wrong material
+        self.declick = None;
+        self.position = PlaybackPosition::at_beat(beat, &self.tempo_map, self.sample_rate);
+        self.relocate_playheads();
+        self.position
     }
 
     /// Get current position
@@ -718,6 +1253,7 @@ impl PlaybackEngine {
 pub enum PlaybackError {
     NoGraph,
     ProcessingFailed(String),
+    InvalidLoop(String),
 }
 
 impl std::fmt::Display for PlaybackError {
@@ -725,6 +1261,7 @@ impl std::fmt::Display for PlaybackError {
         match self {
             PlaybackError::NoGraph => write!(f, "no graph compiled"),
             PlaybackError::ProcessingFailed(msg) => write!(f, "processing failed: {}", msg),
+            PlaybackError::InvalidLoop(msg) => write!(f, "invalid loop region: {}", msg),
         }
     }
 }
@@ -1029,6 +1566,119 @@ mod tests {
         assert_eq!(engine.current_tempo(), 140.0);
     }
 
+    /// 120 BPM for the first 4 beats, then 60 BPM
+    fn two_segment_tempo_map() -> TempoMap {
+        let mut map = TempoMap::new(120.0, crate::primitives::TimeSignature::default());
+        let tick = map.beat_to_tick(Beat(4.0));
+        map.add_tempo_change(tick, 60.0);
+        map
+    }
+
+    #[test]
+    fn test_beats_to_samples_integrates_tempo_map() {
+        let map = two_segment_tempo_map();
+
+        // 4 beats at 0.5 s, then 2 beats at 1 s = 4 s
+        assert_eq!(
+            PlaybackPosition::beats_to_samples(Beat(6.0), &map, 48000),
+            Sample(192_000)
+        );
+        // Before the change, tempo is still 120
+        assert_eq!(
+            PlaybackPosition::beats_to_samples(Beat(2.0), &map, 48000),
+            Sample(48_000)
+        );
+        // Sub-tick positions aren't quantized to the PPQ grid
+        assert_eq!(
+            PlaybackPosition::beats_to_samples(Beat(1.0 / 48_000.0), &map, 48000),
+            Sample(1)
+        );
+    }
+
+    #[test]
+    fn test_seek_lands_on_tempo_mapped_sample() {
+        let mut engine = PlaybackEngine::new(48000, 256, Arc::new(two_segment_tempo_map()));
+
+        let resolved = engine.seek(Beat(5.0));
+        assert_eq!(resolved.beats.0, 5.0);
+        assert_eq!(resolved.samples, Sample(144_000));
+        assert_eq!(engine.position().samples, Sample(144_000));
+
+        // Negative beats clamp to the start
+        assert_eq!(engine.seek(Beat(-1.0)).samples, Sample(0));
+    }
+
+    /// Play a silent graph until the engine fires a marker, returning the
+    /// position of the block after the marker
+    fn play_until_marker(
+        engine: &mut PlaybackEngine,
+        max_blocks: usize,
+    ) -> Option<(Sample, Broadcast)> {
+        let mut graph = Graph::new();
+        graph.add_node(Box::new(SilentNode::new("master")));
+        let mut compiled = CompiledGraph::compile(&mut graph, 256).unwrap();
+
+        engine.play();
+        for _ in 0..max_blocks {
+            engine.process(&mut compiled, &[]).unwrap();
+            if let Some(marker) = engine.take_markers().pop() {
+                return Some((engine.position().samples, marker));
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn test_loop_wraps_at_block_boundary() {
+        // 120 BPM at 48kHz: beat 4 = 96000 samples = exactly 375 blocks of 256
+        let mut engine = PlaybackEngine::new(48000, 256, Arc::new(TempoMap::default()));
+        engine.set_loop(Some(LoopRegion::new(Beat(2.0), Some(Beat(4.0)), true).unwrap()));
+
+        let (position, marker) = play_until_marker(&mut engine, 400).expect("loop should wrap");
+        assert_eq!(position, Sample(48_000));
+        assert_eq!(engine.position().beats.0, 2.0);
+        assert!(matches!(
+            marker,
+            Broadcast::MarkerReached { marker_type, position_beats, .. }
+                if marker_type == "loop" && position_beats == 4.0
+        ));
+    }
+
+    #[test]
+    fn test_loop_wrap_carries_overshoot() {
+        // Beat 1 = 24000 samples falls 192 samples into block 93 (23808..24064)
+        let mut engine = PlaybackEngine::new(48000, 256, Arc::new(TempoMap::default()));
+        engine.set_loop(Some(LoopRegion::new(Beat(0.5), Some(Beat(1.0)), true).unwrap()));
+
+        let (position, _) = play_until_marker(&mut engine, 100).expect("loop should wrap");
+        assert_eq!(position, Sample(12_000 + 64));
+    }
+
+    #[test]
+    fn test_seek_past_loop_end_plays_on() {
+        let mut engine = PlaybackEngine::new(48000, 256, Arc::new(TempoMap::default()));
+        engine.set_loop(Some(LoopRegion::new(Beat(0.0), Some(Beat(1.0)), true).unwrap()));
+        engine.seek(Beat(2.0));
+
+        assert!(play_until_marker(&mut engine, 200).is_none());
+        assert!(engine.position().beats.0 > 2.0);
+    }
+
+    #[test]
+    fn test_disabled_loop_does_not_wrap() {
+        let mut engine = PlaybackEngine::new(48000, 256, Arc::new(TempoMap::default()));
+        engine.set_loop(Some(LoopRegion::new(Beat(0.0), Some(Beat(1.0)), false).unwrap()));
+
+        assert!(play_until_marker(&mut engine, 200).is_none());
+    }
+
+    #[test]
+    fn test_loop_region_validation() {
+        assert!(LoopRegion::new(Beat(4.0), Some(Beat(4.0)), true).is_err());
+        assert!(LoopRegion::new(Beat(-1.0), None, true).is_err());
+        assert!(LoopRegion::new(Beat(0.0), None, true).is_ok());
+    }
+
     #[test]
     fn test_mark_failed_skips_node() {
         let mut graph = Graph::new();
@@ -1041,6 +1691,46 @@ mod tests {
         assert!(compiled.failed_nodes.contains(&0));
     }
 
+    #[test]
+    fn test_record_tap_captures_master_and_node() {
+        use crate::recorder::Recorder;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let store = cas::FileStore::at_path(temp.path()).unwrap();
+
+        let mut graph = Graph::new();
+        let tone = ToneNode::new("bus", 440.0);
+        let bus_id = tone.descriptor.id;
+        graph.add_node(Box::new(tone));
+        let mut compiled = CompiledGraph::compile(&mut graph, 256).unwrap();
+        assert!(compiled.node_output(bus_id).is_some());
+        assert!(compiled.node_output(Uuid::new_v4()).is_none());
+
+        let mut engine = PlaybackEngine::new(48000, 256, Arc::new(TempoMap::default()));
+        let (master, master_tap) = Recorder::start(RecordSource::Master, 48000).unwrap();
+        engine.arm_record(master_tap);
+
+        // Nothing is captured while stopped
+        engine.process(&mut compiled, &[]).unwrap();
+        engine.play();
+        for _ in 0..4 {
+            engine.process(&mut compiled, &[]).unwrap();
+        }
+
+        let (bus, bus_tap) = Recorder::start(RecordSource::Node(bus_id), 48000).unwrap();
+        let master_tap = engine.arm_record(bus_tap).unwrap();
+        assert!(engine.is_recording());
+        engine.process(&mut compiled, &[]).unwrap();
+        drop(engine.take_record_tap());
+        drop(master_tap);
+
+        let master = master.finish(&store).unwrap();
+        assert_eq!(master.frames, 4 * 256);
+        let bus = bus.finish(&store).unwrap();
+        assert_eq!(bus.frames, 256);
+        assert_ne!(master.content_hash, bus.content_hash);
+    }
+
     // === Region wiring tests ===
 
     use crate::nodes::MemoryResolver;
@@ -1236,6 +1926,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_loop_wraps_mid_block() {
+        // Each frame holds its own index, so the output shows where it read from
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 48000,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+            for i in 0..48_000 {
+                writer.write_sample(i as f32 / 48_000.0).unwrap();
+                writer.write_sample(i as f32 / 48_000.0).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+        let mut resolver = MemoryResolver::new();
+        resolver.insert("ramp", cursor.into_inner());
+
+        let tempo_map = Arc::new(TempoMap::default());
+        let mut engine = PlaybackEngine::with_resolver(48000, 256, tempo_map, Arc::new(resolver));
+        engine.set_declick_frames(0);
+        // Beat 0.5 = 12000 samples falls 224 frames into block 46 (11776..12032)
+        engine.set_loop(Some(LoopRegion::new(Beat(0.0), Some(Beat(0.5)), true).unwrap()));
+
+        let mut graph = Graph::new();
+        graph.add_node(Box::new(SilentNode::new("master")));
+        let mut compiled = CompiledGraph::compile(&mut graph, 256).unwrap();
+        let regions = [Region::play_audio(Beat(0.0), Beat(1.0), "ramp".to_string())];
+
+        engine.play();
+        for _ in 0..46 {
+            engine.process(&mut compiled, &regions).unwrap();
+        }
+        assert!(engine.take_markers().is_empty());
+
+        let output = engine.process(&mut compiled, &regions).unwrap();
+        assert_eq!(output.frames(), 256);
+        let frame = |i: usize| output.samples[i * 2] * 48_000.0;
+        assert!((frame(223) - 11_999.0).abs() < 0.5);
+        assert!(frame(224).abs() < 0.5);
+        assert!((frame(255) - 31.0).abs() < 0.5);
+
+        assert_eq!(engine.position().samples, Sample(32));
+        assert_eq!(engine.take_markers().len(), 1);
+    }
+
     #[test]
     fn test_no_resolver_skips_regions() {
         let tempo_map = Arc::new(TempoMap::default());
@@ -1328,4 +2067,200 @@ mod tests {
             "stop should clear failed_preload for retry"
         );
     }
+
+    // === Declick tests ===
+
+    /// Play a DC region for two blocks, then stop and render the ramp
+    fn render_stop_tail(declick_frames: usize) -> (PlaybackEngine, CompiledGraph, Vec<f32>) {
+        let mut resolver = MemoryResolver::new();
+        resolver.insert("dc", generate_dc_wav(1.0, 1.0, 48000));
+
+        let tempo_map = Arc::new(TempoMap::default());
+        let mut engine = PlaybackEngine::with_resolver(48000, 256, tempo_map, Arc::new(resolver));
+        engine.set_declick_frames(declick_frames);
+
+        let mut graph = Graph::new();
+        graph.add_node(Box::new(SilentNode::new("master")));
+        let mut compiled = CompiledGraph::compile(&mut graph, 256).unwrap();
+
+        let region = Region::play_audio(Beat(0.0), Beat(2.0), "dc".to_string());
+        engine.play();
+        for _ in 0..2 {
+            engine.process(&mut compiled, std::slice::from_ref(&region)).unwrap();
+        }
+
+        engine.stop();
+        let mut left = Vec::new();
+        let output = engine.process(&mut compiled, std::slice::from_ref(&region)).unwrap();
+        left.extend(output.samples.iter().step_by(2));
+        (engine, compiled, left)
+    }
+
+    #[test]
+    fn test_stop_ramps_to_zero() {
+        let (mut engine, mut compiled, mut left) = render_stop_tail(384);
+        assert!(engine.is_stopping());
+        while engine.is_stopping() {
+            let output = engine.process(&mut compiled, &[]).unwrap();
+            left.extend(output.samples.iter().step_by(2));
+        }
+
+        assert!(left[0] > 0.99, "ramp starts near full level, got {}", left[0]);
+        assert!(
+            left.windows(2).all(|w| w[1] <= w[0]),
+            "tail must decrease monotonically"
+        );
+        assert_eq!(left[383], 0.0);
+        assert!(left[384..].iter().all(|&s| s == 0.0));
+
+        // Stop rewinds once the ramp is done
+        assert_eq!(engine.position().samples, Sample(0));
+        assert!(!engine.is_playing());
+    }
+
+    #[test]
+    fn test_stop_during_ramp_does_not_restart() {
+        let (mut engine, mut compiled, left) = render_stop_tail(512);
+        let level_before = *left.last().unwrap();
+
+        // A second stop mid-ramp continues from the current level
+        engine.stop();
+        let output = engine.process(&mut compiled, &[]).unwrap();
+        assert!(output.samples[0] <= level_before);
+    }
+
+    #[test]
+    fn test_zero_declick_stops_immediately() {
+        let (engine, _, left) = render_stop_tail(0);
+        assert!(!engine.is_stopping());
+        assert!(left.iter().all(|&s| s == 0.0));
+        assert_eq!(engine.position().samples, Sample(0));
+    }
+
+    // === Section crossfade tests ===
+
+    fn generate_dc_wav(level: f32, duration_secs: f32, sample_rate: u32) -> Vec<u8> {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+            for _ in 0..(sample_rate as f32 * duration_secs) as usize {
+                writer.write_sample(level).unwrap(); // L
+                writer.write_sample(level).unwrap(); // R
+            }
+            writer.finalize().unwrap();
+        }
+
+        cursor.into_inner()
+    }
+
+    /// Render the left channel from beat 4 (the A→B boundary) through beat 5.5
+    /// with a one-beat crossfade, playing only the given regions.
+    fn render_section_change(regions: &[Region], sections: Vec<Section>) -> Vec<f32> {
+        let mut resolver = MemoryResolver::new();
+        resolver.insert("dc", generate_dc_wav(1.0, 4.0, 48000));
+
+        let tempo_map = Arc::new(TempoMap::default());
+        let mut engine = PlaybackEngine::with_resolver(48000, 256, tempo_map, Arc::new(resolver));
+        engine.set_sections(sections);
+        engine.set_crossfade_beats(1.0);
+
+        let mut graph = Graph::new();
+        graph.add_node(Box::new(SilentNode::new("master")));
+        let mut compiled = CompiledGraph::compile(&mut graph, 256).unwrap();
+
+        engine.play();
+        engine.seek(Beat(4.0));
+
+        let mut left = Vec::new();
+        while engine.position().beats.0 < 5.5 {
+            let output = engine.process(&mut compiled, regions).unwrap();
+            left.extend(output.samples.iter().step_by(2));
+        }
+        left
+    }
+
+    #[test]
+    fn test_section_fade_gains_are_equal_power() {
+        let a = Section::new("a", Beat(0.0), Beat(4.0));
+        let b = Section::new("b", Beat(4.0), Beat(8.0)).with_crossfade(2.0);
+        let sections = vec![a.clone(), b.clone()];
+
+        assert!(SectionFade::at(&sections, 0.0, Beat(3.9)).is_none());
+        assert!(SectionFade::at(&sections, 0.0, Beat(6.0)).is_none());
+
+        let fade = SectionFade::at(&sections, 0.0, Beat(5.0)).expect("inside fade window");
+        assert_eq!(fade.outgoing, a.id);
+        assert_eq!(fade.incoming, b.id);
+        assert_eq!(fade.beats, 2.0);
+
+        let (out, inc) = fade.gains(Beat(5.0));
+        assert!((out - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert!((out * out + inc * inc - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_section_change_crossfade_envelope() {
+        let a = Section::new("a", Beat(0.0), Beat(4.0));
+        let b = Section::new("b", Beat(4.0), Beat(8.0));
+        let sections = vec![a, b];
+
+        // 1 beat at 120 BPM / 48 kHz = 24000 samples
+        let fade_samples = 24000;
+
+        let outgoing = Region::play_audio(Beat(0.0), Beat(4.0), "dc".to_string());
+        let env = render_section_change(&[outgoing], sections.clone());
+        assert!((env[0] - 1.0).abs() < 0.01, "outgoing starts at full gain");
+        assert!(
+            env.windows(2).take(fade_samples).all(|w| w[1] <= w[0] + 1e-6),
+            "outgoing ramps down monotonically"
+        );
+        assert!((env[fade_samples / 2] - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.01);
+        assert!(env[fade_samples + 256..].iter().all(|&s| s.abs() < 1e-6));
+
+        let incoming = Region::play_audio(Beat(4.0), Beat(4.0), "dc".to_string());
+        let env = render_section_change(&[incoming], sections);
+        assert!(env[0].abs() < 0.01, "incoming starts silent");
+        assert!(
+            env.windows(2).take(fade_samples).all(|w| w[1] + 1e-6 >= w[0]),
+            "incoming ramps up monotonically"
+        );
+        assert!((env[fade_samples / 2] - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.01);
+        assert!(env[fade_samples + 256..].iter().all(|&s| (s - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_region_outlasting_its_section_keeps_full_gain() {
+        let a = Section::new("a", Beat(0.0), Beat(4.0));
+        let b = Section::new("b", Beat(4.0), Beat(8.0));
+
+        // Starts in A and runs to beat 6, past the end of the fade: it carries
+        // on into B rather than fading out with A
+        let spanning = Region::play_audio(Beat(2.0), Beat(4.0), "dc".to_string());
+        let env = render_section_change(&[spanning], vec![a.clone(), b.clone()]);
+        assert!(
+            env.iter().all(|&s| (s - 1.0).abs() < 1e-6),
+            "no dip across the boundary"
+        );
+
+        // One that ends inside the window still fades and is then gone
+        let fade_samples = 24000;
+        let short = Region::play_audio(Beat(2.0), Beat(2.5), "dc".to_string());
+        let env = render_section_change(&[short], vec![a, b]);
+        assert!((env[fade_samples / 2] - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.01);
+        assert!(env[fade_samples + 256..].iter().all(|&s| s.abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_no_crossfade_without_sections() {
+        let outgoing = Region::play_audio(Beat(0.0), Beat(4.0), "dc".to_string());
+        let env = render_section_change(&[outgoing], Vec::new());
+        assert!(env.iter().all(|&s| s == 0.0), "region ends hard at its boundary");
+    }
 }
@@ -343,6 +343,27 @@ impl AudioBuffer {
         }
     }
 
+    /// Mix with a gain that moves linearly from `start_gain` at the first
+    /// frame toward `end_gain` at the frame after the last.
+    pub fn mix_ramped(&mut self, other: &AudioBuffer, start_gain: f32, end_gain: f32) {
+        if self.samples.len() != other.samples.len() || self.channels == 0 {
+            return;
+        }
+        let channels = self.channels as usize;
+        let step = (end_gain - start_gain) / self.frames().max(1) as f32;
+        for (frame, (s, o)) in self
+            .samples
+            .chunks_exact_mut(channels)
+            .zip(other.samples.chunks_exact(channels))
+            .enumerate()
+        {
+            let gain = start_gain + step * frame as f32;
+            for (s, o) in s.iter_mut().zip(o) {
+                *s += o * gain;
+            }
+        }
+    }
+
     pub fn clear(&mut self) {
         self.samples.fill(0.0);
     }
@@ -493,14 +514,14 @@ pub enum SignalBuffer {
 // =============================================================================
 
 /// Port definition for a node
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Port {
     pub name: String,
     pub signal_type: SignalType,
 }
 
 /// Node capabilities
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NodeCapabilities {
     pub realtime: bool,
     pub offline: bool,
@@ -520,7 +541,7 @@ impl NodeCapabilities {
 }
 
 /// Descriptor for a node in the graph
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NodeDescriptor {
     pub id: Uuid,
     pub name: String,
@@ -740,6 +761,16 @@ mod tests {
         assert_eq!(buf1.samples, vec![1.5; 8]);
     }
 
+    #[test]
+    fn test_audio_buffer_mix_ramped() {
+        let mut out = AudioBuffer::new(4, 2);
+        let mut src = AudioBuffer::new(4, 2);
+        src.samples = vec![1.0; 8];
+
+        out.mix_ramped(&src, 1.0, 0.0);
+        assert_eq!(out.samples, vec![1.0, 1.0, 0.75, 0.75, 0.5, 0.5, 0.25, 0.25]);
+    }
+
     #[test]
     fn test_control_buffer_interpolation() {
         let buf = ControlBuffer {
@@ -0,0 +1,253 @@
+//! Bounce to disk - capture rendered output into a CAS artifact
+//!
+//! While a recording is armed, the playback engine owns a [`RecordTap`] and
+//! copies each rendered block into it. The tap is the producer end of a
+//! lock-free ring, so the render path never allocates or blocks. A worker
+//! thread drains the ring into an in-memory WAV; [`Recorder::finish`] stops
+//! the worker and stores the WAV in CAS.
+//!
+//! ```text
+//! PlaybackEngine::process() ──push──▶ ring ──drain──▶ worker (WAV) ──finish──▶ CAS
+//! ```
+
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use cas::{ContentHash, ContentStore};
+use uuid::Uuid;
+
+use crate::external_io::{audio_ring_pair, AudioRingConsumer, AudioRingProducer};
+
+/// MIME type recordings are stored under
+pub const RECORDING_MIME: &str = "audio/wav";
+
+/// Interleaved stereo
+const CHANNELS: u16 = 2;
+
+/// Ring length, in seconds of audio the worker may fall behind by
+const RING_SECONDS: usize = 2;
+
+/// How long the worker sleeps when the ring is empty
+const DRAIN_INTERVAL: Duration = Duration::from_millis(20);
+
+/// What a recording captures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordSource {
+    /// Final engine output: graph master plus timeline regions
+    Master,
+    /// Output buffer of one graph node, e.g. a bus's mixer node
+    Node(Uuid),
+}
+
+impl std::fmt::Display for RecordSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Master => write!(f, "master"),
+            Self::Node(id) => write!(f, "node {}", id),
+        }
+    }
+}
+
+/// Render-side half of a recording, owned by the playback engine
+pub struct RecordTap {
+    source: RecordSource,
+    producer: AudioRingProducer,
+}
+
+impl RecordTap {
+    pub fn source(&self) -> RecordSource {
+        self.source
+    }
+
+    /// Copy one interleaved block into the ring
+    ///
+    /// Wait-free. If the worker has fallen behind, the overflow is dropped
+    /// and counted in the ring's overrun stats.
+    pub fn push(&mut self, samples: &[f32]) {
+        self.producer.write(samples);
+    }
+}
+
+/// A recording stored in CAS
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub content_hash: ContentHash,
+    pub source: RecordSource,
+    pub sample_rate: u32,
+    /// Stereo frames captured
+    pub frames: u64,
+    /// Samples lost because the worker fell behind the render loop
+    pub dropped_samples: u64,
+}
+
+impl Recording {
+    pub fn duration_seconds(&self) -> f64 {
+        self.frames as f64 / self.sample_rate as f64
+    }
+}
+
+/// Control-side half of a recording: owns the drain worker
+pub struct Recorder {
+    source: RecordSource,
+    sample_rate: u32,
+    done: Arc<AtomicBool>,
+    worker: JoinHandle<Result<WorkerOutput>>,
+}
+
+struct WorkerOutput {
+    wav: Vec<u8>,
+    samples: u64,
+    dropped_samples: u64,
+}
+
+impl Recorder {
+    /// Start a recording, returning the control handle and the tap to hand
+    /// to the playback engine
+    pub fn start(source: RecordSource, sample_rate: u32) -> Result<(Self, RecordTap)> {
+        let capacity = sample_rate as usize * CHANNELS as usize * RING_SECONDS;
+        let (producer, consumer) = audio_ring_pair(capacity);
+        let done = Arc::new(AtomicBool::new(false));
+
+        let worker_done = Arc::clone(&done);
+        let worker = std::thread::Builder::new()
+            .name("chaosgarden-record".to_string())
+            .spawn(move || drain(consumer, sample_rate, &worker_done))
+            .context("failed to spawn recording worker")?;
+
+        let recorder = Self {
+            source,
+            sample_rate,
+            done,
+            worker,
+        };
+        Ok((recorder, RecordTap { source, producer }))
+    }
+
+    pub fn source(&self) -> RecordSource {
+        self.source
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Stop the worker and store the captured WAV in CAS
+    ///
+    /// Detach the tap from the engine first; anything pushed afterwards is
+    /// lost.
+    pub fn finish(self, store: &impl ContentStore) -> Result<Recording> {
+        self.done.store(true, Ordering::Release);
+        let output = self
+            .worker
+            .join()
+            .map_err(|_| anyhow::anyhow!("recording worker panicked"))??;
+
+        let content_hash = store
+            .store(&output.wav, RECORDING_MIME)
+            .context("failed to store recording in CAS")?;
+
+        Ok(Recording {
+            content_hash,
+            source: self.source,
+            sample_rate: self.sample_rate,
+            frames: output.samples / CHANNELS as u64,
+            dropped_samples: output.dropped_samples,
+        })
+    }
+}
+
+/// Worker loop: move samples from the ring into a 32-bit float WAV
+fn drain(
+    mut consumer: AudioRingConsumer,
+    sample_rate: u32,
+    done: &AtomicBool,
+) -> Result<WorkerOutput> {
+    let spec = hound::WavSpec {
+        channels: CHANNELS,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut cursor, spec)?;
+    let mut scratch = vec![0.0f32; 4096];
+    let mut samples = 0u64;
+
+    loop {
+        // Read the flag before draining so a block pushed just before
+        // finish() is still picked up on this pass
+        let finished = done.load(Ordering::Acquire);
+        let read = consumer.read(&mut scratch);
+        for &sample in &scratch[..read] {
+            writer.write_sample(sample)?;
+        }
+        samples += read as u64;
+
+        if read == 0 {
+            if finished {
+                break;
+            }
+            std::thread::sleep(DRAIN_INTERVAL);
+        }
+    }
+
+    writer.finalize()?;
+    Ok(WorkerOutput {
+        wav: cursor.into_inner(),
+        samples,
+        dropped_samples: consumer.stats().dropped_samples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cas::FileStore;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recording_round_trips_through_cas() {
+        let temp = TempDir::new().unwrap();
+        let store = FileStore::at_path(temp.path()).unwrap();
+
+        let (recorder, mut tap) = Recorder::start(RecordSource::Master, 48000).unwrap();
+        let block: Vec<f32> = (0..512).map(|i| (i % 2) as f32 * 0.5).collect();
+        for _ in 0..10 {
+            tap.push(&block);
+        }
+        drop(tap);
+
+        let recording = recorder.finish(&store).unwrap();
+        assert_eq!(recording.frames, 2560);
+        assert_eq!(recording.dropped_samples, 0);
+        assert_eq!(recording.source, RecordSource::Master);
+
+        let wav = store.retrieve(&recording.content_hash).unwrap().unwrap();
+        let reader = hound::WavReader::new(Cursor::new(wav)).unwrap();
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.spec().sample_rate, 48000);
+        let samples: Vec<f32> = reader.into_samples().map(|s| s.unwrap()).collect();
+        assert_eq!(samples.len(), 5120);
+        assert_eq!(&samples[..4], &[0.0, 0.5, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_empty_recording_is_valid_wav() {
+        let temp = TempDir::new().unwrap();
+        let store = FileStore::at_path(temp.path()).unwrap();
+
+        let node = Uuid::new_v4();
+        let (recorder, tap) = Recorder::start(RecordSource::Node(node), 44100).unwrap();
+        assert_eq!(tap.source(), RecordSource::Node(node));
+        drop(tap);
+
+        let recording = recorder.finish(&store).unwrap();
+        assert_eq!(recording.frames, 0);
+        let wav = store.retrieve(&recording.content_hash).unwrap().unwrap();
+        assert!(hound::WavReader::new(Cursor::new(wav)).is_ok());
+    }
+}
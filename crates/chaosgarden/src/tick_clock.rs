@@ -9,7 +9,8 @@
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
-use crate::{Beat, Second, TempoMap, Tick};
+use crate::playback::PlaybackPosition;
+use crate::{Beat, Sample, Second, TempoMap, Tick};
 
 /// Monotonic clock that tracks playback position in musical time
 ///
@@ -118,6 +119,12 @@ impl TickClock {
         self.current_position
     }
 
+    /// Sample offset of `beat` from the start of the timeline, through the tempo map
+    pub fn beat_to_sample(&self, beat: Beat, sample_rate: u32) -> Sample {
+        let tempo_map = self.tempo_map.read().unwrap();
+        PlaybackPosition::beats_to_samples(beat, &tempo_map, sample_rate)
+    }
+
     /// Get tempo at current position
     pub fn current_tempo(&self) -> f64 {
         let tempo_map = self.tempo_map.read().unwrap();
@@ -1,13 +1,218 @@
 //! Backend connection to hootenanny using shared HootClient
 //!
 //! Uses hooteproto's HootClient for lazy connection, reconnection, and request correlation.
+//! Concurrent identical tool calls are coalesced into one backend request.
+//! Pool-level health survives client recreation, so callers can fail fast
+//! while hootenanny is unreachable instead of waiting out every timeout.
 
 use anyhow::Result;
-use hooteproto::{ClientConfig, ConnectionState, HootClient, Payload};
-use std::sync::Arc;
-use tracing::info;
+use hooteproto::{ClientConfig, ConnectionState, HealthTracker, HootClient, Payload};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info};
 use uuid::Uuid;
 
+/// Tools whose every call has its own side effect, so they are never coalesced.
+///
+/// Everything else is read-ish or deterministic enough that concurrent
+/// identical calls can share one backend request and its result.
+const NON_IDEMPOTENT_TOOLS: &[&str] = &[
+    // Transport and timeline edits
+    "play",
+    "pause",
+    "stop",
+    "seek",
+    "tempo",
+    "timeline_region_create",
+    "timeline_region_delete",
+    "timeline_region_move",
+    "timeline_clear",
+    // Device wiring and live I/O
+    "audio_output_attach",
+    "audio_output_detach",
+    "audio_input_attach",
+    "audio_input_detach",
+    "audio_monitor",
+    "audio_capture",
+    "midi_input_attach",
+    "midi_input_detach",
+    "midi_output_attach",
+    "midi_output_detach",
+    "midi_send",
+    "midi_play",
+    "midi_stop",
+    "rave_stream_start",
+    "rave_stream_stop",
+    // Jobs, artifacts, and stateful sessions
+    "job_cancel",
+    "artifact_upload",
+    "add_annotation",
+    "config",
+    "kernel_eval",
+    "kernel_session",
+    "kernel_reset",
+];
+
+/// Whether concurrent identical calls to `tool` may share one request.
+pub fn is_coalescable(tool: &str) -> bool {
+    !NON_IDEMPOTENT_TOOLS.contains(&tool)
+}
+
+/// A tool call as the coalescer sees it: the tool name and its
+/// canonicalized arguments.
+///
+/// The whole call is the key, not a hash of it, so two different calls can
+/// never share a result.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CoalesceKey {
+    tool: String,
+    args: String,
+}
+
+/// Key a tool call by name and canonicalized arguments.
+///
+/// Object keys are sorted and null fields dropped, so `{"a": 1, "b": null}`
+/// and `{"a": 1}` coalesce.
+pub fn coalesce_key(tool: &str, args: &Value) -> CoalesceKey {
+    CoalesceKey {
+        tool: tool.to_string(),
+        args: canonicalize(args).to_string(),
+    }
+}
+
+fn canonicalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().filter(|(_, v)| !v.is_null()).collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), canonicalize(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonicalize).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Shared outcome of a coalesced request (errors flattened so they can be cloned).
+type SharedResult = std::result::Result<Payload, String>;
+
+/// Collapses concurrent identical requests into one.
+///
+/// The first caller for a key runs the request; callers that arrive while
+/// it is in flight wait for and share its result. If the leading caller is
+/// dropped before finishing, a waiter takes over and runs the request itself.
+#[derive(Default)]
+pub struct RequestCoalescer {
+    in_flight: Mutex<HashMap<CoalesceKey, broadcast::Sender<SharedResult>>>,
+}
+
+impl RequestCoalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `request` under `key`, or join an identical request already in flight.
+    pub async fn run<F, Fut>(&self, key: CoalesceKey, request: F) -> Result<Payload>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Payload>>,
+    {
+        loop {
+            let waiting = {
+                let mut in_flight = self.in_flight.lock().expect("coalescer lock poisoned");
+                match in_flight.get(&key) {
+                    Some(tx) => Some(tx.subscribe()),
+                    None => {
+                        let (tx, _) = broadcast::channel(1);
+                        in_flight.insert(key.clone(), tx);
+                        None
+                    }
+                }
+            };
+
+            let Some(mut rx) = waiting else {
+                break;
+            };
+            debug!("Joining in-flight {} request", key.tool);
+            match rx.recv().await {
+                Ok(shared) => return shared.map_err(anyhow::Error::msg),
+                // Leader was dropped without a result; try to lead ourselves
+                Err(_) => continue,
+            }
+        }
+
+        let guard = InFlightGuard { coalescer: self, key };
+        let result = request().await;
+        if let Some(tx) = guard.finish() {
+            let shared = match &result {
+                Ok(payload) => Ok(payload.clone()),
+                Err(e) => Err(format!("{:#}", e)),
+            };
+            // No receivers just means nobody joined
+            let _ = tx.send(shared);
+        }
+        result
+    }
+
+    /// Number of distinct requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().expect("coalescer lock poisoned").len()
+    }
+}
+
+/// Removes a leader's in-flight entry even if its future is dropped.
+struct InFlightGuard<'a> {
+    coalescer: &'a RequestCoalescer,
+    key: CoalesceKey,
+}
+
+impl InFlightGuard<'_> {
+    /// Take the entry's sender so the result can be shared.
+    fn finish(self) -> Option<broadcast::Sender<SharedResult>> {
+        let tx = self.take();
+        std::mem::forget(self);
+        tx
+    }
+
+    fn take(&self) -> Option<broadcast::Sender<SharedResult>> {
+        self.coalescer
+            .in_flight
+            .lock()
+            .expect("coalescer lock poisoned")
+            .remove(&self.key)
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        // Dropping the sender wakes waiters with Closed, and one re-runs
+        self.take();
+    }
+}
+
+/// Interval between heartbeats to hootenanny
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Callback fired when a heartbeat first succeeds after a gap
+type ConnectedCallback = Arc<dyn Fn() + Send + Sync + 'static>;
+
+/// Heartbeat task wiring, kept so a recreated client gets its own task.
+struct Heartbeat {
+    /// Process-wide shutdown, resubscribed for each client's task
+    shutdown: broadcast::Receiver<()>,
+    on_connected: Option<ConnectedCallback>,
+    /// Stops the current client's task when that client is replaced
+    stop: broadcast::Sender<()>,
+}
+
 /// Pool of backend connections
 ///
 /// Simplified to only connect to hootenanny, which proxies to vibeweaver and chaosgarden.
@@ -15,6 +220,12 @@ pub struct BackendPool {
     pub hootenanny: Option<Arc<HootClient>>,
     /// Stored config for client recreation after Dead state
     hootenanny_config: Option<ClientConfig>,
+    /// Shared across client recreation so in-flight calls still coalesce
+    coalescer: Arc<RequestCoalescer>,
+    /// Dead from the moment a client is given up on until a replacement
+    /// answers a heartbeat (a fresh client alone only reports Unknown)
+    health: Arc<HealthTracker>,
+    heartbeat: Option<Heartbeat>,
 }
 
 impl BackendPool {
@@ -23,6 +234,9 @@ impl BackendPool {
         Self {
             hootenanny: None,
             hootenanny_config: None,
+            coalescer: Arc::new(RequestCoalescer::new()),
+            health: Arc::new(HealthTracker::new()),
+            heartbeat: None,
         }
     }
 
@@ -64,6 +278,9 @@ impl BackendPool {
             .clone()
             .ok_or_else(|| anyhow::anyhow!("No config stored for recreation"))?;
 
+        // Fail fast until the replacement proves itself
+        self.health.set_state(ConnectionState::Dead);
+
         // Take the old client (if any) to ensure it's dropped
         if let Some(old_client) = self.hootenanny.take() {
             info!(
@@ -78,6 +295,7 @@ impl BackendPool {
         info!("Recreating hootenanny client for {}", config.endpoint);
         let client = HootClient::new(config).await;
         self.hootenanny = Some(client);
+        self.spawn_client_heartbeat();
 
         Ok(())
     }
@@ -87,6 +305,11 @@ impl BackendPool {
         self.hootenanny.clone()
     }
 
+    /// Coalescer for concurrent identical tool calls
+    pub fn coalescer(&self) -> Arc<RequestCoalescer> {
+        Arc::clone(&self.coalescer)
+    }
+
     /// Send a request to hootenanny
     pub async fn request(&self, payload: Payload) -> Result<Payload> {
         let client = self
@@ -121,28 +344,79 @@ impl BackendPool {
     }
 
     /// Check if hootenanny is alive
+    ///
+    /// False while the current client is Dead, and after a recreation until
+    /// the new client's first successful heartbeat.
     pub fn all_alive(&self) -> bool {
-        self.hootenanny
-            .as_ref()
-            .map(|c| c.health.is_alive())
-            .unwrap_or(true)
+        self.health.is_alive()
+            && self
+                .hootenanny
+                .as_ref()
+                .map(|c| c.health.is_alive())
+                .unwrap_or(true)
     }
 
     /// Spawn health monitoring task for hootenanny with callback on connect
+    ///
+    /// The wiring is remembered: `recreate_hootenanny` stops the old client's
+    /// task and starts one for the replacement.
     pub fn spawn_health_task(
-        &self,
-        shutdown: tokio::sync::broadcast::Receiver<()>,
+        &mut self,
+        shutdown: broadcast::Receiver<()>,
         on_connected: Option<Box<dyn Fn() + Send + Sync + 'static>>,
     ) {
-        if let Some(ref client) = self.hootenanny {
-            hooteproto::spawn_health_task(
-                client.clone(),
-                std::time::Duration::from_secs(5),
-                client.config().max_failures,
-                shutdown,
-                on_connected,
-            );
-        }
+        let (stop, _) = broadcast::channel(1);
+        self.heartbeat = Some(Heartbeat {
+            shutdown,
+            on_connected: on_connected.map(Arc::from),
+            stop,
+        });
+        self.spawn_client_heartbeat();
+    }
+
+    /// Start the heartbeat task for the current client, stopping any previous one.
+    fn spawn_client_heartbeat(&mut self) {
+        let (Some(client), Some(heartbeat)) = (&self.hootenanny, &mut self.heartbeat) else {
+            return;
+        };
+
+        // No receivers just means there was no previous task
+        let _ = heartbeat.stop.send(());
+        let (stop, stop_rx) = broadcast::channel(1);
+        heartbeat.stop = stop.clone();
+
+        // Relay process shutdown into this client's stop channel
+        let mut shutdown = heartbeat.shutdown.resubscribe();
+        let mut stopped = stop.subscribe();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown.recv() => {
+                    let _ = stop.send(());
+                }
+                _ = stopped.recv() => {}
+            }
+        });
+
+        let health = Arc::clone(&self.health);
+        let on_connected = heartbeat.on_connected.clone();
+        let callback: Box<dyn Fn() + Send + Sync + 'static> = Box::new(move || {
+            if health.get_state() == ConnectionState::Dead {
+                info!("Hootenanny backend available again");
+            }
+            health.reset_failures();
+            health.set_state(ConnectionState::Connected);
+            if let Some(ref on_connected) = on_connected {
+                on_connected();
+            }
+        });
+
+        hooteproto::spawn_health_task(
+            client.clone(),
+            HEARTBEAT_INTERVAL,
+            client.config().max_failures,
+            stop_rx,
+            Some(callback),
+        );
     }
 }
 
@@ -151,3 +425,121 @@ impl Default for BackendPool {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn coalesce_key_ignores_key_order_and_nulls() {
+        let a = serde_json::json!({"prompt": "jazz", "temperature": 1.0, "seed": null});
+        let b = serde_json::json!({"temperature": 1.0, "prompt": "jazz"});
+        assert_eq!(coalesce_key("orpheus_generate", &a), coalesce_key("orpheus_generate", &b));
+        assert_ne!(coalesce_key("orpheus_generate", &a), coalesce_key("musicgen_generate", &a));
+
+        let c = serde_json::json!({"prompt": "jazz", "temperature": 0.9});
+        assert_ne!(coalesce_key("orpheus_generate", &a), coalesce_key("orpheus_generate", &c));
+    }
+
+    #[tokio::test]
+    async fn different_calls_never_share_a_result() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let slow = {
+            let coalescer = Arc::clone(&coalescer);
+            let key = coalesce_key("artifact_get", &serde_json::json!({"id": "a"}));
+            tokio::spawn(async move {
+                coalescer
+                    .run(key, || async {
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(Payload::Ping)
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let other = coalesce_key("artifact_get", &serde_json::json!({"id": "b"}));
+        let result = coalescer
+            .run(other, || async { Err(anyhow::anyhow!("not found")) })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(slow.await.unwrap().unwrap(), Payload::Ping);
+    }
+
+    #[test]
+    fn side_effecting_tools_are_not_coalescable() {
+        assert!(is_coalescable("orpheus_generate"));
+        assert!(is_coalescable("artifact_list"));
+        assert!(!is_coalescable("play"));
+        assert!(!is_coalescable("artifact_upload"));
+    }
+
+    #[tokio::test]
+    async fn concurrent_identical_requests_share_one_call() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let key = coalesce_key("artifact_list", &serde_json::json!({}));
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let coalescer = Arc::clone(&coalescer);
+                let calls = Arc::clone(&calls);
+                let key = key.clone();
+                tokio::spawn(async move {
+                    coalescer
+                        .run(key, || async move {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok(Payload::Ping)
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap(), Payload::Ping);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(coalescer.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn waiter_takes_over_when_leader_is_dropped() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let key = coalesce_key("artifact_list", &serde_json::json!({}));
+
+        let leader = {
+            let coalescer = Arc::clone(&coalescer);
+            let key = key.clone();
+            tokio::spawn(async move {
+                coalescer
+                    .run(key, std::future::pending::<Result<Payload>>)
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let waiter = {
+            let coalescer = Arc::clone(&coalescer);
+            tokio::spawn(async move { coalescer.run(key, || async { Ok(Payload::Ping) }).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        leader.abort();
+
+        assert_eq!(waiter.await.unwrap().unwrap(), Payload::Ping);
+        assert_eq!(coalescer.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn recreated_backend_is_unavailable_until_heartbeat() {
+        let mut pool = BackendPool::new();
+        pool.setup_hootenanny("tcp://127.0.0.1:1", 100).await;
+        assert!(pool.all_alive());
+
+        pool.recreate_hootenanny().await.unwrap();
+        assert!(!pool.all_alive());
+        assert!(!pool.needs_recreation());
+    }
+}
@@ -0,0 +1,614 @@
+//! JSON-RPC batches, split in front of rmcp
+//!
+//! MCP 2025-03-26 lets a client send several messages as one JSON array.
+//! rmcp only parses single messages, so both transports pass through here
+//! first: a batch is split into its messages, each goes to rmcp on its own
+//! (concurrently), and the replies are gathered into one array in batch
+//! order. Notifications get no reply, and a batch of only notifications
+//! gets no response at all. `initialize` may not be batched.
+//!
+//! Server-to-client messages sent while a batched request runs (progress,
+//! logging) reach a stdio client as usual but are dropped over HTTP, where
+//! only the final reply makes it into the array.
+
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Method, Request, Response, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use futures::future::{join_all, BoxFuture};
+use futures::StreamExt;
+use rmcp::model::ErrorCode;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, DuplexStream};
+use tokio::sync::mpsc;
+use tower::{Service, ServiceExt};
+use tracing::{debug, warn};
+
+/// Bytes buffered between the stdio relay and rmcp
+const RELAY_BUFFER: usize = 64 * 1024;
+
+/// What to do with one message of a batch
+#[derive(Debug)]
+enum Slot {
+    /// Pass to rmcp; a request's reply is awaited under its id
+    Forward { message: Value, id: Option<Value> },
+    /// Answered here without reaching rmcp
+    Reply(Value),
+}
+
+/// A JSON-RPC error reply.
+fn error_reply(id: Value, code: ErrorCode, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code.0, "message": message },
+    })
+}
+
+/// The messages of a batch, or `None` if `body` isn't an array.
+///
+/// `Err` is the single reply owed for a batch that can't be split: one
+/// that doesn't parse, or an empty one.
+fn split(body: &[u8]) -> Option<Result<Vec<Value>, Value>> {
+    if body.iter().find(|b| !b.is_ascii_whitespace()) != Some(&b'[') {
+        return None;
+    }
+    Some(match serde_json::from_slice::<Vec<Value>>(body) {
+        Ok(messages) if messages.is_empty() => Err(error_reply(
+            Value::Null,
+            ErrorCode::INVALID_REQUEST,
+            "Empty batch",
+        )),
+        Ok(messages) => Ok(messages),
+        Err(e) => Err(error_reply(
+            Value::Null,
+            ErrorCode::PARSE_ERROR,
+            &format!("Invalid batch: {}", e),
+        )),
+    })
+}
+
+fn plan(messages: Vec<Value>) -> Vec<Slot> {
+    messages
+        .into_iter()
+        .map(|message| {
+            let Some(object) = message.as_object() else {
+                return Slot::Reply(error_reply(
+                    Value::Null,
+                    ErrorCode::INVALID_REQUEST,
+                    "Batch entries must be JSON-RPC messages",
+                ));
+            };
+            let id = object.get("id").cloned();
+            match (object.get("method").and_then(Value::as_str), id) {
+                (Some("initialize"), id) => Slot::Reply(error_reply(
+                    id.unwrap_or_default(),
+                    ErrorCode::INVALID_REQUEST,
+                    "initialize must not be part of a batch",
+                )),
+                (Some(_), Some(id)) => Slot::Forward {
+                    message,
+                    id: Some(id),
+                },
+                // Notifications, and replies to our own requests
+                _ => Slot::Forward { message, id: None },
+            }
+        })
+        .collect()
+}
+
+/// Whether `message` is the reply to request `id`.
+fn is_reply_to(message: &Value, id: &Value) -> bool {
+    message.get("id") == Some(id)
+        && message.get("method").is_none()
+        && (message.get("result").is_some() || message.get("error").is_some())
+}
+
+/// Streamable HTTP service that splits batches before `S` sees them.
+#[derive(Clone)]
+pub struct BatchService<S> {
+    inner: S,
+}
+
+impl<S> BatchService<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+impl<S> Service<Request<Body>> for BatchService<S>
+where
+    S: Service<Request<Body>, Error = Infallible> + Clone + Send + 'static,
+    S::Response: IntoResponse,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Each call drives its own clone of the inner service
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let inner = self.inner.clone();
+        Box::pin(async move { Ok(handle(inner, request).await) })
+    }
+}
+
+async fn forward<S>(inner: S, request: Request<Body>) -> Response<Body>
+where
+    S: Service<Request<Body>, Error = Infallible>,
+    S::Response: IntoResponse,
+{
+    let Ok(response) = inner.oneshot(request).await;
+    response.into_response()
+}
+
+async fn handle<S>(inner: S, request: Request<Body>) -> Response<Body>
+where
+    S: Service<Request<Body>, Error = Infallible> + Clone,
+    S::Response: IntoResponse,
+{
+    if request.method() != Method::POST {
+        return forward(inner, request).await;
+    }
+
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let messages = match split(&body) {
+        None => return forward(inner, Request::from_parts(parts, Body::from(body))).await,
+        Some(Err(reply)) => return (StatusCode::BAD_REQUEST, Json(reply)).into_response(),
+        Some(Ok(messages)) => messages,
+    };
+    debug!("Splitting a batch of {} messages", messages.len());
+
+    let replies = join_all(plan(messages).into_iter().map(|slot| {
+        let inner = inner.clone();
+        let parts = &parts;
+        async move {
+            let (message, id) = match slot {
+                Slot::Reply(reply) => return Some(reply),
+                Slot::Forward { message, id } => (message, id),
+            };
+            let mut request = Request::new(Body::from(message.to_string()));
+            *request.method_mut() = parts.method.clone();
+            *request.uri_mut() = parts.uri.clone();
+            *request.version_mut() = parts.version;
+            *request.headers_mut() = parts.headers.clone();
+            request.headers_mut().remove(header::CONTENT_LENGTH);
+
+            let response = forward(inner, request).await;
+            match id {
+                Some(id) => Some(reply_from(response, id).await),
+                None => None,
+            }
+        }
+    }))
+    .await;
+
+    let replies: Vec<Value> = replies.into_iter().flatten().collect();
+    if replies.is_empty() {
+        StatusCode::ACCEPTED.into_response()
+    } else {
+        Json(replies).into_response()
+    }
+}
+
+/// The reply to request `id` in rmcp's response to it, read from its SSE
+/// stream (or JSON body).
+async fn reply_from(response: Response<Body>, id: Value) -> Value {
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+
+    if !status.is_success() || is_json {
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap_or_default();
+        if let Ok(reply) = serde_json::from_slice::<Value>(&body) {
+            if is_reply_to(&reply, &id) {
+                return reply;
+            }
+        }
+        return error_reply(
+            id,
+            ErrorCode::INVALID_REQUEST,
+            &format!("{}: {}", status, String::from_utf8_lossy(&body)),
+        );
+    }
+
+    let mut body = response.into_body().into_data_stream();
+    let mut buffer = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    while let Some(Ok(chunk)) = body.next().await {
+        buffer.extend_from_slice(&chunk);
+        while let Some(line) = take_line(&mut buffer) {
+            if let Some(value) = line.strip_prefix("data:") {
+                data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            } else if line.is_empty() {
+                // End of an event
+                let event = std::mem::take(&mut data).join("\n");
+                if let Ok(message) = serde_json::from_str::<Value>(&event) {
+                    if is_reply_to(&message, &id) {
+                        return message;
+                    }
+                }
+            }
+        }
+    }
+    error_reply(
+        id,
+        ErrorCode::INTERNAL_ERROR,
+        "Response stream ended without a reply",
+    )
+}
+
+/// The next complete line in `buffer`, without its line ending.
+fn take_line(buffer: &mut Vec<u8>) -> Option<String> {
+    let end = buffer.iter().position(|&b| b == b'\n')?;
+    let line: Vec<u8> = buffer.drain(..=end).collect();
+    let line = String::from_utf8_lossy(&line);
+    Some(line.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Batches sent over stdio whose replies are still being gathered.
+#[derive(Default)]
+struct Pending {
+    next_batch: u64,
+    /// Replies so far, in batch order, and how many are still to come
+    batches: HashMap<u64, (Vec<Option<Value>>, usize)>,
+    /// Request id (as JSON text) → (batch, slot) awaiting its reply
+    waiting: HashMap<String, (u64, usize)>,
+}
+
+impl Pending {
+    /// Start gathering replies for `slots`. Returns the finished array
+    /// straight away if nothing needs to wait for rmcp.
+    fn open(&mut self, slots: &[Slot]) -> Option<Vec<Value>> {
+        let batch = self.next_batch;
+        self.next_batch += 1;
+
+        let mut replies = Vec::new();
+        let mut outstanding = 0;
+        for slot in slots {
+            match slot {
+                Slot::Reply(reply) => replies.push(Some(reply.clone())),
+                Slot::Forward { id: Some(id), .. } => {
+                    self.waiting.insert(id.to_string(), (batch, replies.len()));
+                    replies.push(None);
+                    outstanding += 1;
+                }
+                Slot::Forward { id: None, .. } => {}
+            }
+        }
+
+        if outstanding > 0 {
+            self.batches.insert(batch, (replies, outstanding));
+            None
+        } else {
+            Some(replies.into_iter().flatten().collect())
+        }
+    }
+
+    /// Hold `message` if it replies to a batched request. `Err` gives back
+    /// a message that isn't part of any batch; `Ok(Some)` is a batch whose
+    /// last reply just arrived.
+    fn gather(&mut self, message: Value) -> Result<Option<Vec<Value>>, Value> {
+        let is_reply = message.get("method").is_none();
+        let slot = message
+            .get("id")
+            .filter(|_| is_reply)
+            .and_then(|id| self.waiting.remove(&id.to_string()));
+        let Some((batch, index)) = slot else {
+            return Err(message);
+        };
+
+        let (replies, outstanding) = self
+            .batches
+            .get_mut(&batch)
+            .expect("waiting ids belong to open batches");
+        replies[index] = Some(message);
+        *outstanding -= 1;
+        if *outstanding > 0 {
+            return Ok(None);
+        }
+        let (replies, _) = self.batches.remove(&batch).expect("batch is open");
+        Ok(Some(replies.into_iter().flatten().collect()))
+    }
+}
+
+/// Relay between a stdio client and rmcp that splits batches.
+///
+/// Returns the end rmcp serves on. Lines from `input` are passed through
+/// unless they hold a batch, whose messages are passed on one per line;
+/// replies to them are held back and written to `output` as one array.
+pub fn stdio_relay<R, W>(input: R, output: W) -> DuplexStream
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let (server, relay) = tokio::io::duplex(RELAY_BUFFER);
+    let (from_server, to_server) = tokio::io::split(relay);
+    let pending = Arc::new(Mutex::new(Pending::default()));
+    let (out_tx, out_rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(write_lines(output, out_rx));
+    tokio::spawn(client_to_server(
+        input,
+        to_server,
+        Arc::clone(&pending),
+        out_tx.clone(),
+    ));
+    tokio::spawn(server_to_client(from_server, pending, out_tx));
+    server
+}
+
+async fn write_lines<W: AsyncWrite + Unpin>(
+    mut output: W,
+    mut lines: mpsc::UnboundedReceiver<String>,
+) {
+    while let Some(line) = lines.recv().await {
+        let written = async {
+            output.write_all(line.as_bytes()).await?;
+            output.write_all(b"\n").await?;
+            output.flush().await
+        };
+        if let Err(e) = written.await {
+            warn!("Failed to write to stdout: {}", e);
+            return;
+        }
+    }
+}
+
+async fn client_to_server<R, W>(
+    input: R,
+    mut to_server: W,
+    pending: Arc<Mutex<Pending>>,
+    out: mpsc::UnboundedSender<String>,
+) where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut lines = BufReader::new(input).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let messages = match split(line.as_bytes()) {
+            None => vec![line],
+            Some(Err(reply)) => {
+                let _ = out.send(reply.to_string());
+                continue;
+            }
+            Some(Ok(messages)) => {
+                debug!("Splitting a batch of {} messages", messages.len());
+                let slots = plan(messages);
+                // Register before forwarding so no reply can slip past
+                if let Some(replies) = pending.lock().unwrap().open(&slots) {
+                    if !replies.is_empty() {
+                        let _ = out.send(Value::Array(replies).to_string());
+                    }
+                }
+                slots
+                    .into_iter()
+                    .filter_map(|slot| match slot {
+                        Slot::Forward { message, .. } => Some(message.to_string()),
+                        Slot::Reply(_) => None,
+                    })
+                    .collect()
+            }
+        };
+        for message in messages {
+            let sent = async {
+                to_server.write_all(message.as_bytes()).await?;
+                to_server.write_all(b"\n").await?;
+                to_server.flush().await
+            };
+            if sent.await.is_err() {
+                return;
+            }
+        }
+    }
+    // Closing our end tells rmcp the client is gone
+    let _ = to_server.shutdown().await;
+}
+
+async fn server_to_client<R: AsyncRead + Unpin>(
+    from_server: R,
+    pending: Arc<Mutex<Pending>>,
+    out: mpsc::UnboundedSender<String>,
+) {
+    let mut lines = BufReader::new(from_server).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Ok(message) = serde_json::from_str::<Value>(&line) else {
+            let _ = out.send(line);
+            continue;
+        };
+        let gathered = pending.lock().unwrap().gather(message);
+        let line = match gathered {
+            Err(_) => line,
+            Ok(None) => continue,
+            Ok(Some(replies)) => Value::Array(replies).to_string(),
+        };
+        if out.send(line).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::BackendPool;
+    use crate::handler::ZmqHandler;
+    use rmcp::transport::streamable_http_server::{
+        session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    };
+    use rmcp::ServiceExt as _;
+    use tokio::sync::RwLock;
+
+    fn handler() -> ZmqHandler {
+        ZmqHandler::new(Arc::new(RwLock::new(BackendPool::new())))
+    }
+
+    fn initialize() -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "0" },
+            },
+        })
+    }
+
+    fn list_and_ping() -> Value {
+        json!([
+            { "jsonrpc": "2.0", "id": 1, "method": "tools/list" },
+            { "jsonrpc": "2.0", "method": "notifications/cancelled", "params": { "requestId": 99 } },
+            { "jsonrpc": "2.0", "id": "two", "method": "ping" },
+        ])
+    }
+
+    fn assert_list_then_ping(replies: &Value) {
+        let replies = replies.as_array().expect("batch reply is an array");
+        assert_eq!(replies.len(), 2, "{replies:?}");
+        assert_eq!(replies[0]["id"], json!(1));
+        assert!(replies[0]["result"]["tools"].is_array());
+        assert_eq!(replies[1]["id"], json!("two"));
+        assert_eq!(replies[1]["result"], json!({}));
+    }
+
+    #[test]
+    fn batches_are_planned_per_message() {
+        assert_eq!(split(b"{\"jsonrpc\": \"2.0\"}"), None);
+        assert!(split(b" []").unwrap().is_err());
+        assert!(split(b"[1,").unwrap().is_err());
+
+        let slots = plan(vec![
+            json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "initialize"}),
+            json!(7),
+        ]);
+        assert!(matches!(&slots[0], Slot::Forward { id: Some(id), .. } if *id == json!(1)));
+        assert!(matches!(&slots[1], Slot::Forward { id: None, .. }));
+        assert!(matches!(&slots[2], Slot::Reply(r) if r["id"] == json!(2)));
+        assert!(matches!(&slots[3], Slot::Reply(r) if r["id"].is_null()));
+    }
+
+    #[test]
+    fn replies_are_gathered_in_batch_order() {
+        let mut pending = Pending::default();
+        let slots = plan(vec![
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "initialize"}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "ping"}),
+        ]);
+        assert_eq!(pending.open(&slots), None);
+
+        let unrelated = json!({"jsonrpc": "2.0", "id": 9, "result": {}});
+        assert_eq!(pending.gather(unrelated.clone()), Err(unrelated));
+        let progress = json!({"jsonrpc": "2.0", "method": "notifications/progress"});
+        assert!(pending.gather(progress).is_err());
+
+        assert_eq!(pending.gather(json!({"id": 3, "result": {}})), Ok(None));
+        let replies = pending
+            .gather(json!({"id": 1, "result": {}}))
+            .unwrap()
+            .unwrap();
+        let ids: Vec<_> = replies.iter().map(|r| r["id"].clone()).collect();
+        assert_eq!(ids, vec![json!(1), json!(2), json!(3)]);
+
+        let notifications = plan(vec![
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        ]);
+        assert_eq!(pending.open(&notifications), Some(vec![]));
+    }
+
+    #[tokio::test]
+    async fn http_batch_of_list_and_ping() {
+        let service = BatchService::new(StreamableHttpService::new(
+            || Ok(handler()),
+            Arc::new(LocalSessionManager::default()),
+            StreamableHttpServerConfig::default(),
+        ));
+        let post = |body: Value, session: Option<&str>| {
+            let mut request = Request::post("/")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::ACCEPT, "application/json, text/event-stream");
+            if let Some(session) = session {
+                request = request.header("Mcp-Session-Id", session);
+            }
+            request.body(Body::from(body.to_string())).unwrap()
+        };
+
+        let response = service
+            .clone()
+            .oneshot(post(initialize(), None))
+            .await
+            .unwrap();
+        let session = response.headers()["Mcp-Session-Id"]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(reply_from(response, json!(0)).await["id"], json!(0));
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        let response = service
+            .clone()
+            .oneshot(post(json!([initialized]), Some(&session)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let response = service
+            .clone()
+            .oneshot(post(list_and_ping(), Some(&session)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_list_then_ping(&serde_json::from_slice(&body).unwrap());
+    }
+
+    async fn send<W: AsyncWrite + Unpin>(to_server: &mut W, message: Value) {
+        let line = format!("{}\n", message);
+        to_server.write_all(line.as_bytes()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn stdio_batch_of_list_and_ping() {
+        let (client, stdio) = tokio::io::duplex(RELAY_BUFFER);
+        let (stdin, stdout) = tokio::io::split(stdio);
+        let server = tokio::spawn(async move {
+            let service = handler().serve(stdio_relay(stdin, stdout)).await.unwrap();
+            service.waiting().await
+        });
+
+        let (from_server, mut to_server) = tokio::io::split(client);
+        let mut lines = BufReader::new(from_server).lines();
+        send(&mut to_server, initialize()).await;
+        let reply: Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(reply["id"], json!(0));
+
+        let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        send(&mut to_server, initialized).await;
+        send(&mut to_server, list_and_ping()).await;
+        let replies = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_list_then_ping(&replies);
+
+        server.abort();
+    }
+}
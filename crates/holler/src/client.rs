@@ -1,13 +1,21 @@
 //! ZMQ DEALER client for communicating with Hootenanny backends
+//!
+//! [`Client::request`] is the raw Payload round trip. For tool calls,
+//! [`Client::call_tool`] turns backend errors into `Err`, and
+//! [`Client::call_tool_typed`] / [`Client::call_tool_with`] deserialize the
+//! result so callers don't hand-parse JSON.
 
-use anyhow::{Context as AnyhowContext, Result};
+use anyhow::{bail, Context as AnyhowContext, Result};
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
+use hooteproto::request::ToolRequest;
 use hooteproto::socket_config::{create_dealer_and_connect, ZmqContext, Multipart};
 use hooteproto::{
     capnp_envelope_to_payload, envelope_capnp, payload_to_capnp_envelope, Command, ContentType,
-    Envelope, HootFrame, Payload,
+    Envelope, HootFrame, Payload, ResponseEnvelope,
 };
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::pin::Pin;
 use std::time::Duration;
 use tokio::sync::Mutex;
@@ -63,11 +71,77 @@ impl Client {
 
     /// Send a Payload and receive the response
     pub async fn request(&self, payload: Payload) -> Result<Envelope> {
+        let request_id = self.send(&payload).await?;
+
+        // Receive the response with timeout
+        let response = tokio::time::timeout(self.timeout, self.recv_frames())
+            .await
+            .context("Receive timeout")??
+            .ok_or_else(|| anyhow::anyhow!("Socket stream ended"))?;
+
+        // Parse HootFrame from response
+        let response_frame = HootFrame::from_frames(&response)
+            .context("Failed to parse response HootFrame")?;
+
+        // Parse Cap'n Proto response
+        let reader = response_frame
+            .read_capnp()
+            .context("Failed to read capnp from response")?;
+
+        let envelope_reader = reader
+            .get_root::<envelope_capnp::envelope::Reader>()
+            .context("Failed to get envelope root")?;
+
+        let response_payload =
+            capnp_envelope_to_payload(envelope_reader).context("Failed to convert capnp to payload")?;
+
+        Ok(Envelope {
+            id: request_id,
+            payload: response_payload,
+            traceparent: response_frame.traceparent.clone(),
+        })
+    }
+
+    /// Call a tool, returning its response envelope.
+    ///
+    /// Backend errors, whether a `Payload::Error` or an error envelope, are
+    /// returned as `Err`.
+    pub async fn call_tool(&self, request: ToolRequest) -> Result<ResponseEnvelope> {
+        let tool = request.name();
+        let response = self.request(Payload::ToolRequest(request)).await?;
+        into_envelope(tool, response.payload)
+    }
+
+    /// Call a tool and deserialize its result into `T`.
+    ///
+    /// `T` is matched against the tool's response (for immediate results) or
+    /// the envelope itself (for `job_started` and `ack`).
+    pub async fn call_tool_typed<T: DeserializeOwned>(&self, request: ToolRequest) -> Result<T> {
+        let tool = request.name();
+        let envelope = self.call_tool(request).await?;
+        typed_result(tool, envelope)
+    }
+
+    /// Call a tool by name with MCP-style arguments, as the gateway would.
+    pub async fn call_tool_with<P: Serialize, T: DeserializeOwned>(
+        &self,
+        name: &str,
+        args: &P,
+    ) -> Result<T> {
+        let args = serde_json::to_value(args)
+            .with_context(|| format!("Failed to serialize arguments for {}", name))?;
+        let payload = crate::dispatch::json_to_payload(name, args)?;
+        let response = self.request(payload).await?;
+        typed_result(name, into_envelope(name, response.payload)?)
+    }
+
+    /// Send a Payload without waiting for a reply, returning its request ID
+    pub async fn send(&self, payload: &Payload) -> Result<Uuid> {
         // Generate request ID
         let request_id = Uuid::new_v4();
 
         // Convert payload to Cap'n Proto envelope
-        let message = payload_to_capnp_envelope(request_id, &payload)
+        let message = payload_to_capnp_envelope(request_id, payload)
             .context("Failed to convert payload to capnp")?;
 
         // Serialize to bytes
@@ -96,38 +170,96 @@ impl Client {
                 .map_err(|e| anyhow::anyhow!("Failed to send message: {}", e))?;
         }
 
-        // Receive the response with timeout
-        let response = {
-            let mut rx = self.socket_rx.lock().await;
-            tokio::time::timeout(self.timeout, rx.next())
-                .await
-                .context("Receive timeout")?
-                .ok_or_else(|| anyhow::anyhow!("Socket stream ended"))?
-                .map_err(|e| anyhow::anyhow!("Failed to receive response: {}", e))?
-        };
+        Ok(request_id)
+    }
 
-        // Parse HootFrame from response
-        let response_frames = multipart_to_frames(response);
+    /// Receive the next raw message, or `None` once the socket stream ends
+    ///
+    /// No timeout is applied; wrap the call in one as needed.
+    pub async fn recv_frames(&self) -> Result<Option<Vec<Bytes>>> {
+        let mut rx = self.socket_rx.lock().await;
+        match rx.next().await {
+            Some(Ok(multipart)) => Ok(Some(multipart_to_frames(multipart))),
+            Some(Err(e)) => Err(anyhow::anyhow!("Failed to receive response: {}", e)),
+            None => Ok(None),
+        }
+    }
+}
 
-        let response_frame = HootFrame::from_frames(&response_frames)
-            .context("Failed to parse response HootFrame")?;
+/// Unwrap a tool call's reply, turning either form of backend error into `Err`
+fn into_envelope(tool: &str, payload: Payload) -> Result<ResponseEnvelope> {
+    match payload {
+        Payload::TypedResponse(ResponseEnvelope::Error(err)) => {
+            bail!("{} failed: Error {}: {}", tool, err.code(), err.message())
+        }
+        Payload::TypedResponse(envelope) => Ok(envelope),
+        Payload::Error { code, message, .. } => {
+            bail!("{} failed: Error {}: {}", tool, code, message)
+        }
+        other => bail!("Unexpected response to {}: {:?}", tool, other),
+    }
+}
 
-        // Parse Cap'n Proto response
-        let reader = response_frame
-            .read_capnp()
-            .context("Failed to read capnp from response")?;
+/// Deserialize a successful reply into the caller's type
+fn typed_result<T: DeserializeOwned>(tool: &str, envelope: ResponseEnvelope) -> Result<T> {
+    let json = match envelope {
+        ResponseEnvelope::Success { response } => response.to_json(),
+        other => other.to_json(),
+    };
+    serde_json::from_value(json).with_context(|| {
+        format!(
+            "{} result doesn't match {}",
+            tool,
+            std::any::type_name::<T>()
+        )
+    })
+}
 
-        let envelope_reader = reader
-            .get_root::<envelope_capnp::envelope::Reader>()
-            .context("Failed to get envelope root")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hooteproto::responses::{ArtifactListResponse, JobStartedResponse};
+    use hooteproto::{ToolError, ToolResponse, ToolTiming};
 
-        let response_payload =
-            capnp_envelope_to_payload(envelope_reader).context("Failed to convert capnp to payload")?;
+    #[test]
+    fn success_deserializes_the_response() {
+        let envelope =
+            ResponseEnvelope::success(ToolResponse::ArtifactList(ArtifactListResponse {
+                artifacts: vec![],
+                count: 0,
+            }));
+        let list: ArtifactListResponse = typed_result("artifact_list", envelope).unwrap();
+        assert_eq!(list.count, 0);
+    }
 
-        Ok(Envelope {
-            id: request_id,
-            payload: response_payload,
-            traceparent: response_frame.traceparent.clone(),
-        })
+    #[test]
+    fn job_start_deserializes_the_envelope() {
+        let envelope =
+            ResponseEnvelope::job_started("job-7", "orpheus_generate", ToolTiming::AsyncMedium);
+        let started: JobStartedResponse = typed_result("orpheus_generate", envelope).unwrap();
+        assert_eq!(started.job_id, "job-7");
+    }
+
+    #[test]
+    fn shape_mismatch_names_tool_and_type() {
+        let envelope = ResponseEnvelope::ack("done");
+        let err = typed_result::<ArtifactListResponse>("garden_play", envelope).unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("garden_play"), "{}", message);
+        assert!(message.contains("ArtifactListResponse"), "{}", message);
+    }
+
+    #[test]
+    fn backend_errors_are_err() {
+        let err = ToolError::not_found("artifact", "artifact_123");
+        let payload = Payload::TypedResponse(ResponseEnvelope::Error(err));
+        assert!(into_envelope("artifact_get", payload).is_err());
+
+        let payload = Payload::Error {
+            code: "timeout".to_string(),
+            message: "no reply".to_string(),
+            details: None,
+        };
+        assert!(into_envelope("artifact_get", payload).is_err());
     }
 }
@@ -1,7 +1,9 @@
 //! CLI command implementations
 
 use anyhow::{bail, Context, Result};
-use hooteproto::Payload;
+use bytes::Bytes;
+use hooteproto::{capnp_envelope_to_payload, envelope_capnp, ContentType, HootFrame, Payload};
+use std::time::{Duration, Instant};
 use hooteproto::request::{JobStatusRequest, JobListRequest, JobPollRequest, ToolRequest};
 
 use crate::client::Client;
@@ -63,56 +65,140 @@ pub async fn send(endpoint: &str, json: &str, timeout_ms: u64) -> Result<()> {
     Ok(())
 }
 
+/// Send a raw Payload and print every reply frame until the socket goes quiet
+///
+/// `idle_timeout_ms` is measured between messages rather than for the whole
+/// exchange, so long-running jobs that keep emitting frames stay attached.
+pub async fn send_stream(endpoint: &str, json: &str, idle_timeout_ms: u64) -> Result<()> {
+    validate_endpoint(endpoint)?;
+    let payload: Payload = serde_json::from_str(json)
+        .context("Failed to parse JSON as Payload")?;
+
+    let client = Client::connect(endpoint, idle_timeout_ms).await?;
+    let request_id = client.send(&payload).await?;
+    println!("# sent request {}", request_id);
+
+    let idle = Duration::from_millis(idle_timeout_ms);
+    let started = Instant::now();
+    let mut count = 0usize;
+
+    loop {
+        let frames = match tokio::time::timeout(idle, client.recv_frames()).await {
+            Ok(Ok(Some(frames))) => frames,
+            Ok(Ok(None)) => {
+                println!("# socket closed after {} message(s)", count);
+                return Ok(());
+            }
+            Ok(Err(e)) => return Err(e),
+            Err(_) => {
+                println!(
+                    "# idle for {}ms, stopping after {} message(s)",
+                    idle_timeout_ms, count
+                );
+                return Ok(());
+            }
+        };
+
+        count += 1;
+        println!(
+            "[{:>9.3}s] message {} ({} frames)",
+            started.elapsed().as_secs_f64(),
+            count,
+            frames.len()
+        );
+        print_message(&frames);
+    }
+}
+
+/// Print one received multipart message, decoding HOOT01 frames when possible
+fn print_message(frames: &[Bytes]) {
+    let frame = match HootFrame::from_frames(frames) {
+        Ok(frame) => frame,
+        Err(_) => {
+            for (i, raw) in frames.iter().enumerate() {
+                println!("  frame {} ({} bytes):", i, raw.len());
+                print_body(raw);
+            }
+            return;
+        }
+    };
+
+    println!(
+        "  HOOT01 {:?} {:?} request={} service={}",
+        frame.command, frame.content_type, frame.request_id, frame.service
+    );
+    match frame.content_type {
+        ContentType::Empty => {}
+        ContentType::CapnProto => match decode_capnp_payload(&frame) {
+            Ok(json) => print_indented(&json),
+            Err(e) => {
+                println!("  (capnp decode failed: {})", e);
+                print_hex(&frame.body);
+            }
+        },
+        ContentType::Json => print_body(&frame.body),
+        ContentType::RawBinary => print_hex(&frame.body),
+    }
+}
+
+fn decode_capnp_payload(frame: &HootFrame) -> Result<String> {
+    let reader = frame.read_capnp()?;
+    let envelope = reader.get_root::<envelope_capnp::envelope::Reader>()?;
+    let payload = capnp_envelope_to_payload(envelope)?;
+    Ok(serde_json::to_string_pretty(&payload)?)
+}
+
+/// Pretty-print JSON bodies, hex-dump anything else
+fn print_body(body: &[u8]) {
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(value) => match serde_json::to_string_pretty(&value) {
+            Ok(json) => print_indented(&json),
+            Err(_) => print_hex(body),
+        },
+        Err(_) => print_hex(body),
+    }
+}
+
+fn print_indented(text: &str) {
+    for line in text.lines() {
+        println!("    {}", line);
+    }
+}
+
+/// Hex dump, 16 bytes per line with an offset column
+fn print_hex(body: &[u8]) {
+    for (i, chunk) in body.chunks(16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+        println!("    {:08x}  {}", i * 16, hex.join(" "));
+    }
+}
+
 /// Get status of a specific job
 pub async fn job_status(endpoint: &str, job_id: &str, timeout_ms: u64) -> Result<()> {
     validate_endpoint(endpoint)?;
-    let payload = Payload::ToolRequest(ToolRequest::JobStatus(JobStatusRequest {
+    let request = ToolRequest::JobStatus(JobStatusRequest {
         job_id: job_id.to_string(),
-    }));
+    });
 
     let client = Client::connect(endpoint, timeout_ms).await?;
-    let response = client.request(payload).await?;
+    let envelope = client.call_tool(request).await?;
 
-    match response.payload {
-        Payload::TypedResponse(envelope) => {
-            let result = envelope.to_json();
-            let output = serde_json::to_string_pretty(&result)?;
-            println!("{}", output);
-            Ok(())
-        }
-        Payload::Error { code, message, .. } => {
-            bail!("Error {}: {}", code, message);
-        }
-        other => {
-            bail!("Unexpected response: {:?}", other);
-        }
-    }
+    println!("{}", serde_json::to_string_pretty(&envelope.to_json())?);
+    Ok(())
 }
 
 /// List all jobs
 pub async fn job_list(endpoint: &str, status: Option<&str>, timeout_ms: u64) -> Result<()> {
     validate_endpoint(endpoint)?;
-    let payload = Payload::ToolRequest(ToolRequest::JobList(JobListRequest {
+    let request = ToolRequest::JobList(JobListRequest {
         status: status.map(|s| s.to_string()),
-    }));
+    });
 
     let client = Client::connect(endpoint, timeout_ms).await?;
-    let response = client.request(payload).await?;
+    let envelope = client.call_tool(request).await?;
 
-    match response.payload {
-        Payload::TypedResponse(envelope) => {
-            let result = envelope.to_json();
-            let output = serde_json::to_string_pretty(&result)?;
-            println!("{}", output);
-            Ok(())
-        }
-        Payload::Error { code, message, .. } => {
-            bail!("Error {}: {}", code, message);
-        }
-        other => {
-            bail!("Unexpected response: {:?}", other);
-        }
-    }
+    println!("{}", serde_json::to_string_pretty(&envelope.to_json())?);
+    Ok(())
 }
 
 /// Poll for job completion
@@ -124,27 +210,15 @@ pub async fn job_poll(
 ) -> Result<()> {
     validate_endpoint(endpoint)?;
     // Mode is "any" or "all" string in CLI
-    let payload = Payload::ToolRequest(ToolRequest::JobPoll(JobPollRequest {
+    let request = ToolRequest::JobPoll(JobPollRequest {
         job_ids,
         timeout_ms,
         mode: Some(mode.to_string()),
-    }));
+    });
 
     let client = Client::connect(endpoint, timeout_ms + 5000).await?;
-    let response = client.request(payload).await?;
+    let envelope = client.call_tool(request).await?;
 
-    match response.payload {
-        Payload::TypedResponse(envelope) => {
-            let result = envelope.to_json();
-            let output = serde_json::to_string_pretty(&result)?;
-            println!("{}", output);
-            Ok(())
-        }
-        Payload::Error { code, message, .. } => {
-            bail!("Error {}: {}", code, message);
-        }
-        other => {
-            bail!("Unexpected response: {:?}", other);
-        }
-    }
+    println!("{}", serde_json::to_string_pretty(&envelope.to_json())?);
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,194 @@
+//! MCP completion: argument suggestions for prompts and resource templates
+//!
+//! `completion/complete` names a prompt or a resource template, the argument
+//! being filled in, and what the user has typed so far. References to
+//! prompts or templates holler doesn't have are rejected; arguments that
+//! take free text (like a prompt's `style`) get no suggestions.
+//!
+//! Artifact ids and soundfont hashes come from the backend's artifact list,
+//! so only recent artifacts are offered. Completion is best-effort: a
+//! backend failure yields an empty list rather than an error.
+
+use rmcp::model::{CompletionInfo, Reference};
+use rmcp::ErrorData as McpError;
+use tracing::debug;
+
+use crate::prompts::PromptRegistry;
+use crate::resources::{ResourceRegistry, UriTemplate, ARTIFACT_TEMPLATE, SOUNDFONT_TEMPLATE};
+
+/// Artifacts fetched per completion request
+const CANDIDATE_LIMIT: usize = 200;
+
+/// Tag carried by soundfont artifacts
+const SOUNDFONT_TAG: &str = "type:soundfont";
+
+/// Where an argument's suggestions come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    ArtifactIds,
+    SoundfontHashes,
+}
+
+/// Suggestion source for each (prompt, argument) pair that has one
+const PROMPT_SOURCES: &[(&str, &str, Source)] =
+    &[("render_pipeline", "soundfont", Source::SoundfontHashes)];
+
+/// Suggestion source for each (template, variable) pair that has one
+const TEMPLATE_SOURCES: &[(&str, &str, Source)] = &[
+    (ARTIFACT_TEMPLATE, "id", Source::ArtifactIds),
+    (SOUNDFONT_TEMPLATE, "hash", Source::SoundfontHashes),
+];
+
+/// Find the suggestion source for `argument` of `reference`.
+///
+/// Errors if the reference names no prompt or template of ours, or the
+/// argument isn't one it takes. `Ok(None)` means the argument is free text.
+pub fn source_for(reference: &Reference, argument: &str) -> Result<Option<Source>, McpError> {
+    match reference {
+        Reference::Prompt(prompt) => {
+            let prompts = PromptRegistry::list();
+            let known = prompts
+                .iter()
+                .find(|p| p.name == prompt.name)
+                .ok_or_else(|| {
+                    McpError::invalid_params(format!("Unknown prompt: {}", prompt.name), None)
+                })?;
+            let takes_argument = known.arguments.iter().flatten().any(|a| a.name == argument);
+            if !takes_argument {
+                return Err(McpError::invalid_params(
+                    format!("Prompt {} has no argument {:?}", prompt.name, argument),
+                    None,
+                ));
+            }
+            Ok(lookup(PROMPT_SOURCES, &prompt.name, argument))
+        }
+        Reference::Resource(resource) => {
+            let template = ResourceRegistry::list_resource_templates()
+                .into_iter()
+                .find(|t| t.uri_template == resource.uri)
+                .ok_or_else(|| {
+                    McpError::invalid_params(
+                        format!("Unknown resource template: {}", resource.uri),
+                        None,
+                    )
+                })?;
+            let takes_argument = UriTemplate::new(&template.uri_template)
+                .variables()
+                .any(|v| v == argument);
+            if !takes_argument {
+                return Err(McpError::invalid_params(
+                    format!("Template {} has no variable {{{}}}", resource.uri, argument),
+                    None,
+                ));
+            }
+            Ok(lookup(TEMPLATE_SOURCES, &resource.uri, argument))
+        }
+    }
+}
+
+fn lookup(table: &[(&str, &str, Source)], owner: &str, argument: &str) -> Option<Source> {
+    table
+        .iter()
+        .find(|(o, a, _)| *o == owner && *a == argument)
+        .map(|(_, _, source)| *source)
+}
+
+/// Fetch the candidate values for `source` from the backend.
+pub async fn candidates(source: Source, resources: &ResourceRegistry) -> Vec<String> {
+    let tag = match source {
+        Source::ArtifactIds => None,
+        Source::SoundfontHashes => Some(SOUNDFONT_TAG),
+    };
+    let artifacts = match resources.list_artifacts(tag, CANDIDATE_LIMIT).await {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            debug!("No completion candidates for {:?}: {}", source, e);
+            return Vec::new();
+        }
+    };
+    artifacts
+        .into_iter()
+        .map(|a| match source {
+            Source::ArtifactIds => a.id,
+            Source::SoundfontHashes => a.content_hash,
+        })
+        .collect()
+}
+
+/// Keep the candidates starting with `partial`, without duplicates, capped
+/// at the number of values the spec allows in one response.
+pub fn complete_from(
+    candidates: impl IntoIterator<Item = String>,
+    partial: &str,
+) -> CompletionInfo {
+    let mut matches: Vec<String> = Vec::new();
+    for candidate in candidates {
+        if candidate.starts_with(partial) && !matches.contains(&candidate) {
+            matches.push(candidate);
+        }
+    }
+
+    let total = matches.len();
+    matches.truncate(CompletionInfo::MAX_VALUES);
+    CompletionInfo {
+        has_more: Some(total > matches.len()),
+        total: Some(total as u32),
+        values: matches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::ResourceReference;
+
+    fn template(uri: &str) -> Reference {
+        Reference::Resource(ResourceReference {
+            uri: uri.to_string(),
+        })
+    }
+
+    #[test]
+    fn sources_follow_the_reference() {
+        assert_eq!(
+            source_for(&template(ARTIFACT_TEMPLATE), "id").unwrap(),
+            Some(Source::ArtifactIds)
+        );
+        assert_eq!(
+            source_for(&template(SOUNDFONT_TEMPLATE), "hash").unwrap(),
+            Some(Source::SoundfontHashes)
+        );
+        assert_eq!(
+            source_for(&Reference::for_prompt("render_pipeline"), "soundfont").unwrap(),
+            Some(Source::SoundfontHashes)
+        );
+        // Free text: valid, but nothing to suggest
+        assert_eq!(
+            source_for(&Reference::for_prompt("render_pipeline"), "style").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn unknown_references_and_arguments_are_rejected() {
+        assert!(source_for(&Reference::for_prompt("no_such_prompt"), "style").is_err());
+        assert!(source_for(&Reference::for_prompt("render_pipeline"), "tempo").is_err());
+        assert!(source_for(&template("holler://status"), "id").is_err());
+        assert!(source_for(&template(ARTIFACT_TEMPLATE), "hash").is_err());
+    }
+
+    #[test]
+    fn completion_filters_by_prefix_and_caps() {
+        let ids = ["artifact_a1", "artifact_b2", "artifact_a1", "artifact_a3"];
+        let info = complete_from(ids.iter().map(|s| s.to_string()), "artifact_a");
+        assert_eq!(info.values, vec!["artifact_a1", "artifact_a3"]);
+        assert_eq!(info.total, Some(2));
+        assert_eq!(info.has_more, Some(false));
+
+        let many = (0..150).map(|i| format!("artifact_{:03}", i));
+        let info = complete_from(many, "");
+        assert_eq!(info.values.len(), CompletionInfo::MAX_VALUES);
+        assert_eq!(info.total, Some(150));
+        assert_eq!(info.has_more, Some(true));
+    }
+}
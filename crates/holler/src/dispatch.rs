@@ -5,12 +5,22 @@
 //!
 //! hooteproto should have NO serde_json::Value in Payload variants.
 //! All JSON parsing happens here in holler.
+//!
+//! The return leg lives here too: backend `ToolError`s are mapped to MCP
+//! error codes so clients can tell bad arguments from a busy model.
 
 use anyhow::{Context, Result};
-use hooteproto::Payload;
 use hooteproto::request::{self, ToolRequest};
+use hooteproto::{Payload, ToolError};
+use rmcp::model::ErrorCode;
+use rmcp::ErrorData;
 use serde::Deserialize;
 use serde_json::Value;
+use std::time::Duration;
+
+use crate::elicit::ElicitError;
+use crate::input_schema::SchemaError;
+use crate::tools_registry;
 
 /// Convert MCP tool call (name + JSON args) to typed Payload.
 pub fn json_to_payload(name: &str, args: Value) -> Result<Payload> {
@@ -622,8 +632,173 @@ pub fn json_to_payload(name: &str, args: Value) -> Result<Payload> {
         }
 
         // === Fallback: Unknown tool ===
-        _ => anyhow::bail!("Unknown tool: {}. All tools must have typed dispatch.", name),
+        _ => Err(UnknownTool(name.to_string()).into()),
+    }
+}
+
+/// [`json_to_payload`] was given a tool name it has no dispatch for.
+///
+/// Distinct from argument errors so callers can answer with
+/// METHOD_NOT_FOUND rather than INVALID_PARAMS.
+#[derive(Debug)]
+pub struct UnknownTool(pub String);
+
+impl std::fmt::Display for UnknownTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&tools_registry::unknown_tool_message(&self.0))
+    }
+}
+
+impl std::error::Error for UnknownTool {}
+
+// ============================================================================
+// Backend errors → MCP errors
+// Custom codes sit in JSON-RPC's implementation-defined server range.
+// ============================================================================
+
+/// External model/service failed or is unavailable; retrying later may work.
+pub const MODEL_UNAVAILABLE: ErrorCode = ErrorCode(-32010);
+
+/// Tool call exceeded its deadline.
+pub const TOOL_TIMEOUT: ErrorCode = ErrorCode(-32011);
+
+/// Caller isn't allowed to perform the operation.
+pub const PERMISSION_DENIED: ErrorCode = ErrorCode(-32012);
+
+/// Hootenanny itself isn't answering; holler is reconnecting in the background.
+pub const BACKEND_UNAVAILABLE: ErrorCode = ErrorCode(-32013);
+
+/// Holler is shutting down and no longer accepts tool calls.
+pub const SERVER_DRAINING: ErrorCode = ErrorCode(-32014);
+
+/// Caller is making tool calls faster than allowed; retrying later will work.
+pub const RATE_LIMITED: ErrorCode = ErrorCode(-32015);
+
+/// Tool call was cancelled (same code as LSP's RequestCancelled).
+pub const REQUEST_CANCELLED: ErrorCode = ErrorCode(-32800);
+
+/// Error for a tool name that isn't registered, with nearest-name suggestions.
+pub fn unknown_tool(name: &str) -> ErrorData {
+    ErrorData::new(
+        ErrorCode::METHOD_NOT_FOUND,
+        tools_registry::unknown_tool_message(name),
+        Some(serde_json::json!({
+            "tool": name,
+            "suggestions": tools_registry::suggest_tools(name, tools_registry::MAX_SUGGESTIONS),
+        })),
+    )
+}
+
+/// Error for arguments that don't match the tool's input schema.
+///
+/// The message names every mismatch; `data.errors` lists them as
+/// `{path, message}` pairs.
+pub fn invalid_arguments(name: &str, errors: &[SchemaError]) -> ErrorData {
+    let summary: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    ErrorData::invalid_params(
+        format!("Invalid arguments for {}: {}", name, summary.join("; ")),
+        Some(serde_json::json!({ "tool": name, "errors": errors })),
+    )
+}
+
+/// Error returned immediately while the backend is known to be down.
+pub fn backend_unavailable() -> ErrorData {
+    ErrorData::new(
+        BACKEND_UNAVAILABLE,
+        "hootenanny backend unavailable, retrying",
+        Some(serde_json::json!({ "backend": "hootenanny", "retrying": true })),
+    )
+}
+
+/// Error for tool calls that arrive after shutdown has begun.
+pub fn server_draining() -> ErrorData {
+    ErrorData::new(
+        SERVER_DRAINING,
+        "server draining, not accepting new tool calls",
+        Some(serde_json::json!({ "retrying": false })),
+    )
+}
+
+/// Error for a tool call that ran past its deadline in holler.
+///
+/// The backend request is abandoned, not cancelled; a job it started keeps
+/// running and can still be polled.
+pub fn tool_timeout(name: &str, after: Duration) -> ErrorData {
+    ErrorData::new(
+        TOOL_TIMEOUT,
+        format!("{} timed out after {}s", name, after.as_secs_f64()),
+        Some(serde_json::json!({ "tool": name, "timeout_ms": after.as_millis() as u64 })),
+    )
+}
+
+/// Error for a tool call the client cancelled with `notifications/cancelled`.
+pub fn request_cancelled(name: &str) -> ErrorData {
+    ErrorData::new(
+        REQUEST_CANCELLED,
+        format!("{} cancelled by client", name),
+        Some(serde_json::json!({ "tool": name })),
+    )
+}
+
+/// Error for a tool call refused by a rate limit.
+///
+/// `data.retry_after_ms` is set when the limit will let a call through again.
+pub fn rate_limited(name: &str, retry_after: Option<Duration>) -> ErrorData {
+    let message = match retry_after {
+        Some(after) => format!(
+            "{} rate limited, retry in {:.1}s",
+            name,
+            after.as_secs_f64()
+        ),
+        None => format!("{} rate limited", name),
+    };
+    ErrorData::new(
+        RATE_LIMITED,
+        message,
+        Some(serde_json::json!({
+            "tool": name,
+            "retry_after_ms": retry_after.map(|after| after.as_millis() as u64),
+        })),
+    )
+}
+
+/// Error for a destructive tool call the user didn't confirm.
+pub fn not_confirmed(name: &str, reason: &ElicitError) -> ErrorData {
+    ErrorData::new(
+        PERMISSION_DENIED,
+        format!("{} not run: {}", name, reason),
+        Some(serde_json::json!({ "tool": name, "reason": reason.to_string() })),
+    )
+}
+
+/// Map a typed backend error to an MCP error.
+///
+/// The message is the backend's own; `data` carries the serialized
+/// `ToolError` (category, code, and any details).
+pub fn tool_error_to_mcp(err: &ToolError) -> ErrorData {
+    let code = match err {
+        ToolError::Validation(_) => ErrorCode::INVALID_PARAMS,
+        ToolError::NotFound(_) => ErrorCode::RESOURCE_NOT_FOUND,
+        ToolError::Service(_) => MODEL_UNAVAILABLE,
+        ToolError::Internal(_) => ErrorCode::INTERNAL_ERROR,
+        ToolError::Cancelled(_) => REQUEST_CANCELLED,
+        ToolError::Timeout(_) => TOOL_TIMEOUT,
+        ToolError::Permission(_) => PERMISSION_DENIED,
+    };
+    ErrorData::new(code, err.message(), serde_json::to_value(err).ok())
+}
+
+/// Map a `Payload::Error` from the backend to an MCP error.
+///
+/// Typed errors arrive with the serialized `ToolError` in `details` and get
+/// [`tool_error_to_mcp`]. Anything else (protocol or transport failures) is
+/// an internal error that keeps the backend's code and details.
+pub fn payload_error_to_mcp(code: &str, message: &str, details: Option<&Value>) -> ErrorData {
+    if let Some(err) = details.and_then(|d| ToolError::deserialize(d).ok()) {
+        return tool_error_to_mcp(&err);
     }
+    let data = serde_json::json!({ "code": code, "details": details });
+    ErrorData::internal_error(format!("{}: {}", code, message), Some(data))
 }
 
 // ============================================================================
@@ -1130,4 +1305,80 @@ struct TimeConvertArgs {
     value: f64,
     from: String,
     to: String,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_errors_map_to_mcp_codes() {
+        let table = [
+            (ToolError::validation("bad_bpm", "bpm must be positive"), ErrorCode::INVALID_PARAMS),
+            (ToolError::not_found("artifact", "artifact_123"), ErrorCode::RESOURCE_NOT_FOUND),
+            (ToolError::service_retryable("orpheus", "unavailable", "GPU busy"), MODEL_UNAVAILABLE),
+            (ToolError::internal_with_details("render failed", "worker panicked"), ErrorCode::INTERNAL_ERROR),
+            (ToolError::cancelled("superseded"), REQUEST_CANCELLED),
+            (ToolError::timeout("orpheus_generate", 30_000), TOOL_TIMEOUT),
+            (ToolError::permission("delete", "artifact_123"), PERMISSION_DENIED),
+        ];
+
+        for (err, expected) in table {
+            let mcp = tool_error_to_mcp(&err);
+            assert_eq!(mcp.code, expected, "{:?}", err);
+            assert_eq!(mcp.message, err.message());
+            assert_eq!(mcp.data, serde_json::to_value(&err).ok());
+        }
+    }
+
+    #[test]
+    fn payload_error_with_typed_details_uses_variant_mapping() {
+        let err = ToolError::validation_field("bad_bpm", "bpm must be positive", "bpm");
+        let details = serde_json::to_value(&err).unwrap();
+
+        let mcp = payload_error_to_mcp(err.code(), &err.message(), Some(&details));
+        assert_eq!(mcp.code, ErrorCode::INVALID_PARAMS);
+        assert_eq!(mcp.data.unwrap()["field"], "bpm");
+    }
+
+    #[test]
+    fn bad_args_to_unlisted_tools_are_not_unknown() {
+        // Dispatched here but absent from the static tool list
+        let cases = [
+            ("abc_parse", serde_json::json!({ "abc": 5 })),
+            ("abc_transpose", serde_json::json!({ "abc": 5 })),
+            ("orpheus_loops", serde_json::json!({ "temperature": "hot" })),
+            ("convert_midi_to_wav", serde_json::json!({ "input_hash": 7 })),
+        ];
+        for (name, args) in cases {
+            let err = json_to_payload(name, args).unwrap_err();
+            assert!(!err.is::<UnknownTool>(), "{} reported as unknown: {}", name, err);
+        }
+    }
+
+    #[test]
+    fn untyped_payload_error_is_internal_with_details() {
+        let details = serde_json::json!({ "frame": 3 });
+        let mcp = payload_error_to_mcp("capnp_parse_error", "truncated", Some(&details));
+
+        assert_eq!(mcp.code, ErrorCode::INTERNAL_ERROR);
+        assert!(mcp.message.contains("truncated"));
+        let data = mcp.data.unwrap();
+        assert_eq!(data["code"], "capnp_parse_error");
+        assert_eq!(data["details"]["frame"], 3);
+    }
+
+    #[test]
+    fn unknown_tool_suggests_nearest_names() {
+        let err = json_to_payload("midi_rendr", Value::Null).unwrap_err();
+        assert!(err.is::<UnknownTool>());
+        assert!(err.to_string().contains("Did you mean"), "{}", err);
+        assert!(err.to_string().contains("midi_render"), "{}", err);
+
+        let mcp = unknown_tool("midi_rendr");
+        assert_eq!(mcp.code, ErrorCode::METHOD_NOT_FOUND);
+        let data = mcp.data.unwrap();
+        assert_eq!(data["tool"], "midi_rendr");
+        assert_eq!(data["suggestions"][0], "midi_render");
+    }
+}
@@ -0,0 +1,240 @@
+//! Asking the user for input mid tool call via MCP elicitation
+//!
+//! An [`Elicitor`] wraps the caller's session. [`Elicitor::elicit`] sends
+//! `elicitation/create` and waits for the user's answer, and
+//! [`Elicitor::confirm`] is the yes/no case used before destructive calls.
+//! Clients that didn't advertise the `elicitation` capability are never
+//! asked; the call fails with [`ElicitError::NotSupported`] instead.
+
+use rmcp::model::{
+    CreateElicitationRequestParam, CreateElicitationResult, ElicitationAction, ElicitationSchema,
+};
+use rmcp::{Peer, RoleServer};
+use serde_json::Value;
+use std::future::Future;
+
+/// Field carrying the answer in [`Elicitor::confirm`]'s schema
+const CONFIRM_FIELD: &str = "confirm";
+
+/// Why an elicitation produced no answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElicitError {
+    /// The client didn't advertise the elicitation capability
+    NotSupported,
+    /// The user explicitly said no
+    Declined,
+    /// The user dismissed the request
+    Cancelled,
+    /// The request itself failed, or the answer was malformed
+    Failed(String),
+}
+
+impl std::fmt::Display for ElicitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ElicitError::NotSupported => f.write_str("client does not support elicitation"),
+            ElicitError::Declined => f.write_str("user declined"),
+            ElicitError::Cancelled => f.write_str("user cancelled"),
+            ElicitError::Failed(e) => write!(f, "elicitation failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ElicitError {}
+
+/// The client end of an elicitation
+pub trait ElicitationPeer: Send + Sync + 'static {
+    /// Whether the client declared elicitation support at initialization
+    fn supports_elicitation(&self) -> bool;
+
+    fn create_elicitation(
+        &self,
+        param: CreateElicitationRequestParam,
+    ) -> impl Future<Output = Result<CreateElicitationResult, String>> + Send;
+}
+
+impl ElicitationPeer for Peer<RoleServer> {
+    fn supports_elicitation(&self) -> bool {
+        Peer::supports_elicitation(self)
+    }
+
+    async fn create_elicitation(
+        &self,
+        param: CreateElicitationRequestParam,
+    ) -> Result<CreateElicitationResult, String> {
+        Peer::create_elicitation(self, param)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Asks the user of one session for input.
+pub struct Elicitor<P> {
+    peer: P,
+}
+
+impl<P: ElicitationPeer> Elicitor<P> {
+    pub fn new(peer: P) -> Self {
+        Self { peer }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        self.peer.supports_elicitation()
+    }
+
+    /// Ask the user for values matching `schema`, returning what they entered.
+    pub async fn elicit(
+        &self,
+        message: &str,
+        schema: ElicitationSchema,
+    ) -> Result<Value, ElicitError> {
+        if !self.is_supported() {
+            return Err(ElicitError::NotSupported);
+        }
+
+        let result = self
+            .peer
+            .create_elicitation(CreateElicitationRequestParam {
+                message: message.to_string(),
+                requested_schema: schema,
+            })
+            .await
+            .map_err(ElicitError::Failed)?;
+
+        match result.action {
+            ElicitationAction::Accept => result
+                .content
+                .ok_or_else(|| ElicitError::Failed("accepted without content".to_string())),
+            ElicitationAction::Decline => Err(ElicitError::Declined),
+            ElicitationAction::Cancel => Err(ElicitError::Cancelled),
+        }
+    }
+
+    /// Ask a yes/no question; `Ok(false)` means the user answered no.
+    pub async fn confirm(&self, message: &str) -> Result<bool, ElicitError> {
+        let schema = ElicitationSchema::builder()
+            .required_bool(CONFIRM_FIELD)
+            .build()
+            .map_err(|e| ElicitError::Failed(e.to_string()))?;
+
+        let answer = self.elicit(message, schema).await?;
+        answer
+            .get(CONFIRM_FIELD)
+            .and_then(|v| v.as_bool())
+            .ok_or_else(|| ElicitError::Failed(format!("expected a boolean {:?}", CONFIRM_FIELD)))
+    }
+
+    /// Ask before running a destructive tool; `Ok(())` means go ahead.
+    ///
+    /// Clients without elicitation aren't asked. They see `destructiveHint`
+    /// in `tools/list` and can confirm with the user themselves.
+    pub async fn approve_destructive(&self, tool: &str) -> Result<(), ElicitError> {
+        if !self.is_supported() {
+            return Ok(());
+        }
+        let message = format!("{} can't be undone. Run it?", tool);
+        match self.confirm(&message).await? {
+            true => Ok(()),
+            false => Err(ElicitError::Declined),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Answers every elicitation with a canned result
+    struct FakeClient {
+        supported: bool,
+        answer: CreateElicitationResult,
+        asked: Mutex<Vec<String>>,
+    }
+
+    impl FakeClient {
+        fn answering(action: ElicitationAction, content: Option<Value>) -> Self {
+            Self {
+                supported: true,
+                answer: CreateElicitationResult { action, content },
+                asked: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl ElicitationPeer for FakeClient {
+        fn supports_elicitation(&self) -> bool {
+            self.supported
+        }
+
+        async fn create_elicitation(
+            &self,
+            param: CreateElicitationRequestParam,
+        ) -> Result<CreateElicitationResult, String> {
+            self.asked.lock().unwrap().push(param.message);
+            Ok(self.answer.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn confirm_reads_the_answer() {
+        let client =
+            FakeClient::answering(ElicitationAction::Accept, Some(json!({ "confirm": true })));
+        let elicitor = Elicitor::new(client);
+        assert_eq!(elicitor.confirm("Regenerate the bridge?").await, Ok(true));
+        assert_eq!(
+            *elicitor.peer.asked.lock().unwrap(),
+            vec!["Regenerate the bridge?".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn decline_and_cancel_are_errors() {
+        let declined = Elicitor::new(FakeClient::answering(ElicitationAction::Decline, None));
+        assert_eq!(declined.confirm("?").await, Err(ElicitError::Declined));
+
+        let cancelled = Elicitor::new(FakeClient::answering(ElicitationAction::Cancel, None));
+        assert_eq!(cancelled.confirm("?").await, Err(ElicitError::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn destructive_calls_need_a_yes() {
+        let yes =
+            FakeClient::answering(ElicitationAction::Accept, Some(json!({ "confirm": true })));
+        assert_eq!(
+            Elicitor::new(yes)
+                .approve_destructive("timeline_clear")
+                .await,
+            Ok(())
+        );
+
+        let no =
+            FakeClient::answering(ElicitationAction::Accept, Some(json!({ "confirm": false })));
+        let elicitor = Elicitor::new(no);
+        assert_eq!(
+            elicitor.approve_destructive("timeline_clear").await,
+            Err(ElicitError::Declined)
+        );
+        assert!(elicitor.peer.asked.lock().unwrap()[0].starts_with("timeline_clear"));
+
+        let mut unsupported = FakeClient::answering(ElicitationAction::Decline, None);
+        unsupported.supported = false;
+        assert_eq!(
+            Elicitor::new(unsupported)
+                .approve_destructive("timeline_clear")
+                .await,
+            Ok(())
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_clients_are_not_asked() {
+        let mut client = FakeClient::answering(ElicitationAction::Accept, Some(json!({})));
+        client.supported = false;
+        let elicitor = Elicitor::new(client);
+
+        assert_eq!(elicitor.confirm("?").await, Err(ElicitError::NotSupported));
+        assert!(elicitor.peer.asked.lock().unwrap().is_empty());
+    }
+}
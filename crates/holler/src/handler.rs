@@ -3,30 +3,59 @@
 //! Implements rmcp::ServerHandler to bridge MCP protocol to ZMQ backends.
 //! Tools are dynamically discovered from backends and calls are routed based on prefix.
 //! Tool lists are cached and refreshed when backends recover from failures.
+//! While the backend is down, tool calls fail fast and the cached list is served.
+//! Every call has a deadline and is abandoned early if the client cancels it.
+//! List methods are paginated with stateless offset cursors.
+//! Tool results link to the artifacts they name as MCP resources.
+//! A pluggable interceptor can refuse calls (auth, rate limits) before dispatch.
+//! Tools carry MCP annotations, and destructive ones are confirmed with the
+//! user first when the client supports elicitation.
 //!
 //! Now also supports MCP Resources and Prompts for richer agent interactions.
 
-use hooteproto::{Payload, ToolInfo};
+use hooteproto::timing::tool_timing;
+use hooteproto::{Broadcast, Payload, ResponseEnvelope, ToolInfo};
 use rmcp::{
     ErrorData as McpError,
     ServerHandler,
     model::{
-        CallToolRequestParam, CallToolResult, Content, GetPromptRequestParam, GetPromptResult,
-        Implementation, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult,
-        ListToolsResult, PaginatedRequestParam, ReadResourceRequestParam, ReadResourceResult,
-        ResourceContents, ServerCapabilities, ServerInfo, Tool,
+        CallToolRequestParam, CallToolResult, CompleteRequestParam, CompleteResult, Content,
+        GetPromptRequestParam, GetPromptResult, Implementation, ListPromptsResult,
+        ListResourceTemplatesResult, ListResourcesResult, ListToolsResult, LoggingLevel,
+        PaginatedRequestParam, ProgressToken, ProtocolVersion, ReadResourceRequestParam,
+        ReadResourceResult, ResourceContents, ServerCapabilities, ServerInfo, SetLevelRequestParam,
+        Tool,
     },
     service::RequestContext,
+    transport::common::http_header::HEADER_SESSION_ID,
     RoleServer,
 };
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
-use crate::backend::BackendPool;
+use crate::backend::{coalesce_key, is_coalescable, BackendPool};
+use crate::completion;
 use crate::dispatch;
+use crate::elicit::{ElicitError, ElicitationPeer, Elicitor};
+use crate::inflight::InFlightCalls;
+use crate::input_schema;
+use crate::intercept::{AllowAll, CallInfo, CallInterceptor};
+use crate::logging::SessionLog;
+use crate::pagination::{self, DEFAULT_PAGE_SIZE};
+use crate::progress;
 use crate::prompts::{self, PromptRegistry};
-use crate::resources::ResourceRegistry;
+use crate::resources::{self, ResourceRegistry};
+
+/// Deadline for tools whose timing class sets none (long-running and fire-and-forget).
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Headroom over a timing-class deadline, so a backend that uses its whole
+/// budget (job_poll caps at 30s) still gets its answer through.
+const DEADLINE_GRACE: Duration = Duration::from_secs(5);
 
 /// Shared tool cache for dynamic refresh across handler instances.
 ///
@@ -40,17 +69,21 @@ pub fn new_tool_cache() -> ToolCache {
 
 /// Refresh tools from hootenanny into the shared cache.
 ///
-/// Called on startup and when backend recovers from Dead → Ready.
+/// Called on startup and when backend recovers from Dead → Ready. An empty
+/// result leaves the cache alone, so `tools/list` keeps serving the last
+/// good list while the backend is down.
 pub async fn refresh_tools_into(cache: &ToolCache, backends: &Arc<RwLock<BackendPool>>) -> usize {
     let backends_guard = backends.read().await;
     let tools = collect_tools_async(&backends_guard).await;
     drop(backends_guard); // Release lock before writing to cache
     let count = tools.len();
 
-    if count > 0 {
-        info!("🔧 Refreshed {} tools from hootenanny", count);
+    if count == 0 {
+        debug!("Tool refresh returned nothing, keeping cached list");
+        return count;
     }
 
+    info!("🔧 Refreshed {} tools from hootenanny", count);
     *cache.write().await = tools;
     count
 }
@@ -82,6 +115,22 @@ pub struct ZmqHandler {
     artifact_base_url: Option<String>,
     /// Resource registry for MCP Resources
     resources: Arc<ResourceRegistry>,
+    /// Backend broadcasts, for forwarding job progress to callers
+    broadcasts: Option<broadcast::Sender<Broadcast>>,
+    /// Running tool calls, drained on shutdown
+    in_flight: InFlightCalls,
+    /// Check arguments against the tool's input schema before dispatch
+    validate_input: bool,
+    /// Deadline for calls whose tool's timing class sets none
+    call_timeout: Duration,
+    /// Ask the user before running tools annotated as destructive
+    confirm_destructive: bool,
+    /// Most items returned per page by the list methods
+    page_size: usize,
+    /// Admission check run before each tool call is dispatched
+    interceptor: Arc<dyn CallInterceptor>,
+    /// This session's `logging/setLevel` choice
+    log: SessionLog,
 }
 
 impl ZmqHandler {
@@ -94,6 +143,14 @@ impl ZmqHandler {
             daw_only: false,
             artifact_base_url: None,
             resources,
+            broadcasts: None,
+            in_flight: InFlightCalls::new(),
+            validate_input: true,
+            call_timeout: DEFAULT_CALL_TIMEOUT,
+            confirm_destructive: true,
+            page_size: DEFAULT_PAGE_SIZE,
+            interceptor: Arc::new(AllowAll),
+            log: SessionLog::new(),
         }
     }
 
@@ -114,9 +171,75 @@ impl ZmqHandler {
             daw_only,
             artifact_base_url,
             resources,
+            broadcasts: None,
+            in_flight: InFlightCalls::new(),
+            validate_input: true,
+            call_timeout: DEFAULT_CALL_TIMEOUT,
+            confirm_destructive: true,
+            page_size: DEFAULT_PAGE_SIZE,
+            interceptor: Arc::new(AllowAll),
+            log: SessionLog::new(),
         }
     }
 
+    /// Forward backend job progress to tool calls that pass a progress token.
+    pub fn with_broadcasts(mut self, broadcasts: broadcast::Sender<Broadcast>) -> Self {
+        self.broadcasts = Some(broadcasts);
+        self
+    }
+
+    /// Track tool calls in a counter shared with the shutdown path.
+    pub fn with_in_flight(mut self, in_flight: InFlightCalls) -> Self {
+        self.in_flight = in_flight;
+        self
+    }
+
+    /// Turn schema validation of tool arguments on or off (on by default).
+    ///
+    /// With it off, malformed arguments are still caught by the JSON →
+    /// Payload conversion, but only the first problem is reported.
+    pub fn with_input_validation(mut self, enabled: bool) -> Self {
+        self.validate_input = enabled;
+        self
+    }
+
+    /// Set the deadline for tool calls (120s by default).
+    ///
+    /// Tools with an `AsyncShort` or `AsyncMedium` timing class keep their
+    /// class's deadline; this covers the rest.
+    pub fn with_call_timeout(mut self, timeout: Duration) -> Self {
+        self.call_timeout = timeout;
+        self
+    }
+
+    /// Turn confirmation of destructive tool calls on or off (on by default).
+    ///
+    /// Only clients that support elicitation are ever asked.
+    pub fn with_destructive_confirmation(mut self, enabled: bool) -> Self {
+        self.confirm_destructive = enabled;
+        self
+    }
+
+    /// Set the most items per page of a list response (100 by default).
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Check every tool call with `interceptor` before dispatch (by default
+    /// every call is admitted; see [`crate::intercept::from_config`]).
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn CallInterceptor>) -> Self {
+        self.interceptor = interceptor;
+        self
+    }
+
+    /// How long a call to `name` may wait on the backend.
+    fn call_deadline(&self, name: &str) -> Duration {
+        tool_timing(name)
+            .gateway_timeout()
+            .map_or(self.call_timeout, |timeout| timeout + DEADLINE_GRACE)
+    }
+
     /// Refresh tools from hootenanny and update the cache.
     ///
     /// Called on startup and when backend recovers from Dead → Ready.
@@ -128,16 +251,55 @@ impl ZmqHandler {
     pub async fn get_cached_tools(&self) -> Vec<Tool> {
         self.cached_tools.read().await.clone()
     }
+
+    /// Whether the cached tool list marks `name` as destructive.
+    async fn is_destructive(&self, name: &str) -> bool {
+        let tools = self.cached_tools.read().await;
+        tools
+            .iter()
+            .find(|t| t.name == name)
+            .and_then(|t| t.annotations.as_ref())
+            .is_some_and(|a| a.destructive_hint == Some(true))
+    }
+
+    /// Schema errors for a call's arguments, or `None` if they pass.
+    ///
+    /// Tools missing from the cache are left to dispatch to reject.
+    async fn check_arguments(
+        &self,
+        name: &str,
+        arguments: &serde_json::Value,
+    ) -> Option<Vec<input_schema::SchemaError>> {
+        let schema = {
+            let tools = self.cached_tools.read().await;
+            let tool = tools.iter().find(|t| t.name == name)?;
+            serde_json::Value::Object((*tool.input_schema).clone())
+        };
+        // Omitted arguments are an empty object as far as the schema goes
+        let empty = serde_json::Value::Object(Default::default());
+        let arguments = if arguments.is_null() {
+            &empty
+        } else {
+            arguments
+        };
+
+        let errors = input_schema::validate(&schema, arguments);
+        (!errors.is_empty()).then_some(errors)
+    }
 }
 
 impl ServerHandler for ZmqHandler {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
-            protocol_version: Default::default(),
+            // 2025-06-18: elicitation and structured results. Clients still on
+            // 2025-03-26 may send JSON-RPC batches; crate::batch splits them
+            protocol_version: ProtocolVersion::V_2025_06_18,
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_resources()
                 .enable_prompts()
+                .enable_logging()
+                .enable_completions()
                 .build(),
             server_info: Implementation::from_build_env(),
             instructions: Some(
@@ -148,26 +310,50 @@ impl ServerHandler for ZmqHandler {
         }
     }
 
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        debug!(level = ?request.level, "Session logging level set");
+        self.log.set_level(request.level);
+        if let Some(ref broadcasts) = self.broadcasts {
+            self.log
+                .forward_backend_logs(context.peer.clone(), broadcasts.subscribe());
+        }
+        Ok(())
+    }
+
     async fn list_resources(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, McpError> {
+        let (resources, next_cursor) = pagination::paginate(
+            ResourceRegistry::list_resources(),
+            request.as_ref(),
+            self.page_size,
+        )?;
         Ok(ListResourcesResult {
-            resources: ResourceRegistry::list_resources(),
-            next_cursor: None,
+            resources,
+            next_cursor,
             meta: None,
         })
     }
 
     async fn list_resource_templates(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListResourceTemplatesResult, McpError> {
+        let (resource_templates, next_cursor) = pagination::paginate(
+            ResourceRegistry::list_resource_templates(),
+            request.as_ref(),
+            self.page_size,
+        )?;
         Ok(ListResourceTemplatesResult {
-            resource_templates: ResourceRegistry::list_resource_templates(),
-            next_cursor: None,
+            resource_templates,
+            next_cursor,
             meta: None,
         })
     }
@@ -190,12 +376,14 @@ impl ServerHandler for ZmqHandler {
 
     async fn list_prompts(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListPromptsResult, McpError> {
+        let (prompts, next_cursor) =
+            pagination::paginate(PromptRegistry::list(), request.as_ref(), self.page_size)?;
         Ok(ListPromptsResult {
-            prompts: PromptRegistry::list(),
-            next_cursor: None,
+            prompts,
+            next_cursor,
             meta: None,
         })
     }
@@ -209,9 +397,24 @@ impl ServerHandler for ZmqHandler {
         PromptRegistry::get(&request.name, &args)
     }
 
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        let argument = &request.argument;
+        let Some(source) = completion::source_for(&request.r#ref, &argument.name)? else {
+            return Ok(CompleteResult::default());
+        };
+        let candidates = completion::candidates(source, &self.resources).await;
+        Ok(CompleteResult {
+            completion: completion::complete_from(candidates, &argument.value),
+        })
+    }
+
     async fn list_tools(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
         let mut tools = self.cached_tools.read().await.clone();
@@ -222,9 +425,10 @@ impl ServerHandler for ZmqHandler {
             debug!("DAW-only mode: exposing {} tools", tools.len());
         }
 
+        let (tools, next_cursor) = pagination::paginate(tools, request.as_ref(), self.page_size)?;
         Ok(ListToolsResult {
             tools,
-            next_cursor: None,
+            next_cursor,
             meta: None,
         })
     }
@@ -232,7 +436,7 @@ impl ServerHandler for ZmqHandler {
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         let name = &request.name;
         let arguments = request.arguments
@@ -241,18 +445,76 @@ impl ServerHandler for ZmqHandler {
 
         info!(tool = %name, args = ?arguments, "📥 Tool call received");
 
+        // Held until the call returns so shutdown can wait for it
+        let Some(_call) = self.in_flight.begin() else {
+            warn!(tool = %name, "Rejecting tool call while draining");
+            return Err(dispatch::server_draining());
+        };
+
         // Handle help tool locally (doesn't need backend)
         if name == "help" {
             let help_args: crate::help::HelpArgs = serde_json::from_value(arguments).unwrap_or_default();
-            let response = crate::help::help(help_args);
-            let text = serde_json::to_string_pretty(&response).unwrap_or_default();
-            return Ok(CallToolResult::success(vec![Content::text(text)]));
+            let response = serde_json::to_value(crate::help::help(help_args)).unwrap_or_default();
+            return Ok(json_result(response));
+        }
+
+        if self.validate_input {
+            if let Some(errors) = self.check_arguments(name, &arguments).await {
+                warn!(tool = %name, errors = errors.len(), "Arguments failed schema validation");
+                return Err(dispatch::invalid_arguments(name, &errors));
+            }
+        }
+
+        // One deadline covers admission, confirmation and the backend request
+        let deadline = self.call_deadline(name);
+        let started = tokio::time::Instant::now();
+
+        let http = context.extensions.get::<axum::http::request::Parts>();
+        let call = CallInfo {
+            tool: name,
+            arguments: &arguments,
+            session: http
+                .and_then(|parts| parts.headers.get(HEADER_SESSION_ID))
+                .and_then(|id| id.to_str().ok()),
+            headers: http.map(|parts| &parts.headers),
+        };
+        match until_done(self.interceptor.before(&call), deadline, &context.ct).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                warn!(tool = %name, session = ?call.session, "Tool call refused: {}", e.message);
+                return Err(e);
+            }
+            Err(Abandoned::TimedOut) => {
+                warn!(tool = %name, timeout = ?deadline, "Tool call timed out before dispatch");
+                return Err(dispatch::tool_timeout(name, deadline));
+            }
+            Err(Abandoned::Cancelled) => {
+                info!(tool = %name, "Tool call cancelled by client");
+                return Err(dispatch::request_cancelled(name));
+            }
         }
 
-        let backend = {
+        let (backend, coalescer) = {
             let backends_guard = self.backends.read().await;
+            // Don't make the caller wait out a timeout we know is coming
+            if !backends_guard.all_alive() {
+                drop(backends_guard);
+                warn!(tool = %name, "Backend unavailable, failing fast");
+                self.log
+                    .log(
+                        &context.peer,
+                        LoggingLevel::Warning,
+                        "holler",
+                        serde_json::json!({
+                            "message": "hootenanny unavailable, tool call rejected",
+                            "tool": name,
+                        }),
+                    )
+                    .await;
+                return Err(dispatch::backend_unavailable());
+            }
             match backends_guard.route_tool(name) {
-                Some(b) => b,
+                Some(b) => (b, backends_guard.coalescer()),
                 None => {
                     return Err(McpError::invalid_params(
                         format!("No backend available for tool: {}", name),
@@ -262,12 +524,19 @@ impl ServerHandler for ZmqHandler {
             }
         };
 
+        // Key before the args are consumed by payload conversion
+        let key = is_coalescable(name).then(|| coalesce_key(name, &arguments));
+
         // Convert JSON args to typed Payload (JSON boundary is here in holler)
         let payload = match dispatch::json_to_payload(name, arguments) {
             Ok(p) => {
                 debug!("✅ JSON to Payload conversion succeeded for {}", name);
                 p
             }
+            Err(e) if e.is::<dispatch::UnknownTool>() => {
+                warn!(tool = %name, "Unknown tool");
+                return Err(dispatch::unknown_tool(name));
+            }
             Err(e) => {
                 warn!("❌ JSON to Payload conversion failed for {}: {}", name, e);
                 return Err(McpError::invalid_params(
@@ -277,29 +546,59 @@ impl ServerHandler for ZmqHandler {
             }
         };
 
-        debug!("📤 Sending {} to backend", name);
-        match backend.request(payload).await {
+        let elicitor = (self.confirm_destructive && self.is_destructive(name).await)
+            .then(|| Elicitor::new(context.peer.clone()));
+
+        // Subscribe before sending so progress from a fast job isn't missed
+        let progress_sub = context
+            .meta
+            .get_progress_token()
+            .and_then(|token| Some((token, self.broadcasts.as_ref()?.subscribe())));
+
+        let request = async {
+            debug!("📤 Sending {} to backend", name);
+            match key {
+                Some(key) => coalescer.run(key, || backend.request(payload)).await,
+                None => backend.request(payload).await,
+            }
+        };
+        let confirmed = confirmed_then(elicitor, name, request);
+        let remaining = deadline.saturating_sub(started.elapsed());
+        let response = match until_done(confirmed, remaining, &context.ct).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                info!(tool = %name, "Destructive call not confirmed: {}", e);
+                return Err(dispatch::not_confirmed(name, &e));
+            }
+            Err(Abandoned::TimedOut) => {
+                warn!(tool = %name, timeout = ?deadline, "Tool call timed out");
+                return Err(dispatch::tool_timeout(name, deadline));
+            }
+            Err(Abandoned::Cancelled) => {
+                info!(tool = %name, "Tool call cancelled by client");
+                return Err(dispatch::request_cancelled(name));
+            }
+        };
+        match response {
+            Ok(Payload::TypedResponse(ResponseEnvelope::Error(err))) => {
+                warn!(tool = %name, code = %err.code(), "Backend returned error");
+                Err(dispatch::tool_error_to_mcp(&err))
+            }
             Ok(Payload::TypedResponse(envelope)) => {
+                forward_job_progress(&envelope, progress_sub, context.peer.clone());
                 let mut result = envelope.to_json();
                 // Augment response with artifact URLs if base URL is configured
                 if let Some(ref base_url) = self.artifact_base_url {
                     augment_artifact_urls(&mut result, base_url);
                 }
-                let text = serde_json::to_string_pretty(&result).unwrap_or_default();
-                Ok(CallToolResult::success(vec![Content::text(text)]))
+                let links = resources::artifact_links(&result);
+                let mut result = json_result(result);
+                result.content.extend(links);
+                Ok(result)
             }
             Ok(Payload::Error { code, message, details }) => {
-                let error_text = if let Some(d) = details {
-                    format!(
-                        "{}: {}\n{}",
-                        code,
-                        message,
-                        serde_json::to_string_pretty(&d).unwrap_or_default()
-                    )
-                } else {
-                    format!("{}: {}", code, message)
-                };
-                Ok(CallToolResult::error(vec![Content::text(error_text)]))
+                warn!(tool = %name, code = %code, "Backend returned error");
+                Err(dispatch::payload_error_to_mcp(&code, &message, details.as_ref()))
             }
             Ok(other) => Err(McpError::internal_error(
                 format!("Unexpected response: {:?}", other),
@@ -313,6 +612,76 @@ impl ServerHandler for ZmqHandler {
     }
 }
 
+/// Why a backend request was given up on
+#[derive(Debug, PartialEq)]
+enum Abandoned {
+    TimedOut,
+    Cancelled,
+}
+
+/// Await `request` unless it outlives `deadline` or `cancel` fires first.
+///
+/// Either way the request future is dropped, so a backend that never
+/// answers can't hold the call open.
+async fn until_done<T>(
+    request: impl Future<Output = T>,
+    deadline: Duration,
+    cancel: &CancellationToken,
+) -> Result<T, Abandoned> {
+    tokio::select! {
+        result = tokio::time::timeout(deadline, request) => {
+            result.map_err(|_| Abandoned::TimedOut)
+        }
+        _ = cancel.cancelled() => Err(Abandoned::Cancelled),
+    }
+}
+
+/// Ask the user to confirm (when there's an `elicitor`), then await `request`.
+///
+/// Callers run this under [`until_done`] so the call's deadline and the
+/// client's cancellation cover the confirmation as well as the backend.
+async fn confirmed_then<P: ElicitationPeer, T>(
+    elicitor: Option<Elicitor<P>>,
+    name: &str,
+    request: impl Future<Output = T>,
+) -> Result<T, ElicitError> {
+    if let Some(elicitor) = elicitor {
+        elicitor.approve_destructive(name).await?;
+    }
+    Ok(request.await)
+}
+
+/// Forward progress to the caller if it asked for it and the call started a job.
+///
+/// Returns whether a forwarder was started.
+fn forward_job_progress(
+    envelope: &ResponseEnvelope,
+    progress_sub: Option<(ProgressToken, broadcast::Receiver<Broadcast>)>,
+    sink: impl progress::ProgressSink,
+) -> bool {
+    match (progress::started_job_id(envelope), progress_sub) {
+        (Some(job_id), Some((token, rx))) => {
+            progress::spawn_progress_forwarder(job_id.to_string(), token, sink, rx);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// A successful tool result carrying `value` both as pretty-printed text
+/// and, when it's a JSON object, as `structuredContent`.
+///
+/// Clients that predate structured results keep reading the text.
+fn json_result(value: serde_json::Value) -> CallToolResult {
+    let text = serde_json::to_string_pretty(&value).unwrap_or_default();
+    let mut result = CallToolResult::success(vec![Content::text(text)]);
+    // The spec only allows an object here
+    if value.is_object() {
+        result.structured_content = Some(value);
+    }
+    result
+}
+
 /// Collect tools from local registry.
 ///
 /// All tools are defined statically in tools_registry - no ZMQ round-trip needed.
@@ -328,7 +697,8 @@ fn tool_info_to_rmcp(info: ToolInfo) -> Tool {
     let schema = info.input_schema.as_object()
         .cloned()
         .unwrap_or_default();
-    Tool::new(info.name, info.description, Arc::new(schema))
+    let annotations = crate::tools_registry::annotations(&info.name);
+    Tool::new(info.name, info.description, Arc::new(schema)).annotate(annotations)
 }
 
 /// Augment JSON response with artifact URLs.
@@ -377,3 +747,229 @@ fn augment_artifact_urls(value: &mut serde_json::Value, base_url: &str) {
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hooteproto::{
+        capnp_envelope_to_payload, envelope_capnp, payload_to_capnp_envelope, ToolTiming,
+    };
+    use rmcp::model::{NumberOrString, ProgressNotificationParam};
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// Collects forwarded notifications in place of a client session
+    struct ChannelSink(mpsc::UnboundedSender<ProgressNotificationParam>);
+
+    impl progress::ProgressSink for ChannelSink {
+        async fn send_progress(&self, param: ProgressNotificationParam) -> Result<(), String> {
+            self.0.send(param).map_err(|e| e.to_string())
+        }
+    }
+
+    /// Round-trip a backend response through capnp, as the backend client does
+    fn decode_over_capnp(envelope: ResponseEnvelope) -> ResponseEnvelope {
+        let message =
+            payload_to_capnp_envelope(uuid::Uuid::new_v4(), &Payload::TypedResponse(envelope))
+                .unwrap();
+        let bytes = capnp::serialize::write_message_to_words(&message);
+        let reader = capnp::serialize::read_message(
+            &mut bytes.as_slice(),
+            capnp::message::ReaderOptions::new(),
+        )
+        .unwrap();
+        let root = reader.get_root::<envelope_capnp::envelope::Reader>().unwrap();
+        match capnp_envelope_to_payload(root).unwrap() {
+            Payload::TypedResponse(envelope) => envelope,
+            other => panic!("expected a typed response, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn decoded_job_start_forwards_progress() {
+        let started = ResponseEnvelope::job_started("job-42", "orpheus_generate", ToolTiming::AsyncLong);
+        let envelope = decode_over_capnp(started);
+        assert!(matches!(envelope, ResponseEnvelope::Success { .. }));
+
+        let (broadcasts, rx) = broadcast::channel(16);
+        let (sink_tx, mut notified) = mpsc::unbounded_channel();
+        let token = ProgressToken(NumberOrString::String("tok".into()));
+
+        assert!(forward_job_progress(&envelope, Some((token, rx)), ChannelSink(sink_tx)));
+
+        broadcasts
+            .send(Broadcast::Progress {
+                job_id: "job-42".to_string(),
+                percent: 0.5,
+                message: "halfway".to_string(),
+            })
+            .unwrap();
+
+        let param = tokio::time::timeout(Duration::from_secs(5), notified.recv())
+            .await
+            .expect("progress not forwarded")
+            .unwrap();
+        assert_eq!(param.progress, 0.5);
+        assert_eq!(param.message.as_deref(), Some("halfway"));
+    }
+
+    #[tokio::test]
+    async fn arguments_are_checked_against_cached_schema() {
+        let handler = ZmqHandler::new(Arc::new(RwLock::new(BackendPool::new())));
+        *handler.cached_tools.write().await = crate::tools_registry::list_tools()
+            .into_iter()
+            .map(tool_info_to_rmcp)
+            .collect();
+
+        let errors = handler
+            .check_arguments(
+                "orpheus_continue",
+                &serde_json::json!({ "temperature": "hot" }),
+            )
+            .await
+            .expect("missing input_hash and a string temperature");
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"input_hash"));
+        assert!(paths.contains(&"temperature"));
+
+        assert!(handler
+            .check_arguments("artifact_list", &serde_json::Value::Null)
+            .await
+            .is_none());
+        assert!(handler
+            .check_arguments("no_such_tool", &serde_json::json!({}))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn destructive_hint_comes_from_cached_tools() {
+        let handler = ZmqHandler::new(Arc::new(RwLock::new(BackendPool::new())));
+        *handler.cached_tools.write().await = crate::tools_registry::list_tools()
+            .into_iter()
+            .map(tool_info_to_rmcp)
+            .collect();
+
+        assert!(handler.is_destructive("timeline_clear").await);
+        assert!(!handler.is_destructive("artifact_list").await);
+        assert!(!handler.is_destructive("no_such_tool").await);
+    }
+
+    /// A client that accepts elicitations and never answers them
+    struct SilentClient;
+
+    impl ElicitationPeer for SilentClient {
+        fn supports_elicitation(&self) -> bool {
+            true
+        }
+
+        async fn create_elicitation(
+            &self,
+            _param: rmcp::model::CreateElicitationRequestParam,
+        ) -> Result<rmcp::model::CreateElicitationResult, String> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn unanswered_confirmations_time_out_or_are_cancelled() {
+        let cancel = CancellationToken::new();
+        let request = || async { "sent" };
+
+        let confirm = confirmed_then(
+            Some(Elicitor::new(SilentClient)),
+            "timeline_clear",
+            request(),
+        );
+        let result = until_done(confirm, Duration::from_millis(20), &cancel).await;
+        assert_eq!(result, Err(Abandoned::TimedOut));
+
+        cancel.cancel();
+        let confirm = confirmed_then(
+            Some(Elicitor::new(SilentClient)),
+            "timeline_clear",
+            request(),
+        );
+        let result = until_done(confirm, Duration::from_secs(60), &cancel).await;
+        assert_eq!(result, Err(Abandoned::Cancelled));
+
+        let unconfirmed =
+            confirmed_then(None::<Elicitor<SilentClient>>, "timeline_clear", request());
+        assert_eq!(unconfirmed.await, Ok("sent"));
+    }
+
+    #[tokio::test]
+    async fn stuck_requests_time_out_or_are_cancelled() {
+        let never = std::future::pending::<()>;
+        let cancel = CancellationToken::new();
+
+        let result = until_done(never(), Duration::from_millis(20), &cancel).await;
+        assert_eq!(result, Err(Abandoned::TimedOut));
+
+        cancel.cancel();
+        let result = until_done(never(), Duration::from_secs(60), &cancel).await;
+        assert_eq!(result, Err(Abandoned::Cancelled));
+
+        let result = until_done(
+            async { 7 },
+            Duration::from_secs(60),
+            &CancellationToken::new(),
+        )
+        .await;
+        assert_eq!(result, Ok(7));
+    }
+
+    #[test]
+    fn timing_class_overrides_default_deadline() {
+        let handler = ZmqHandler::new(Arc::new(RwLock::new(BackendPool::new())))
+            .with_call_timeout(Duration::from_secs(5));
+        assert_eq!(handler.call_deadline("job_poll"), Duration::from_secs(35));
+        assert_eq!(
+            handler.call_deadline("orpheus_generate"),
+            Duration::from_secs(125)
+        );
+        assert_eq!(
+            handler.call_deadline("musicgen_generate"),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn json_results_are_structured_and_text() {
+        let value = serde_json::json!({ "artifact_id": "artifact_abc", "duration_secs": 4.5 });
+        let result = json_result(value.clone());
+        assert_eq!(result.structured_content, Some(value.clone()));
+        assert_eq!(result.is_error, Some(false));
+        let text = result.content[0].as_text().expect("text content");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&text.text).unwrap(),
+            value
+        );
+
+        let list = json_result(serde_json::json!(["a", "b"]));
+        assert_eq!(list.structured_content, None);
+        assert_eq!(list.content.len(), 1);
+    }
+
+    #[test]
+    fn advertises_2025_06_18() {
+        let handler = ZmqHandler::new(Arc::new(RwLock::new(BackendPool::new())));
+        assert_eq!(
+            handler.get_info().protocol_version,
+            ProtocolVersion::V_2025_06_18
+        );
+    }
+
+    #[test]
+    fn no_forwarder_without_token_or_job() {
+        let (_, rx) = broadcast::channel::<Broadcast>(1);
+        let (sink_tx, _) = mpsc::unbounded_channel();
+        let token = ProgressToken(NumberOrString::Number(1));
+
+        let ack = ResponseEnvelope::ack("done");
+        assert!(!forward_job_progress(&ack, Some((token, rx)), ChannelSink(sink_tx.clone())));
+
+        let started = ResponseEnvelope::job_started("job-1", "tool", ToolTiming::AsyncLong);
+        assert!(!forward_job_progress(&started, None, ChannelSink(sink_tx)));
+    }
+}
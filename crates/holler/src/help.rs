@@ -188,7 +188,7 @@ fn find_category(tool_name: &str) -> Option<&'static str> {
         .map(|(name, _)| *name)
 }
 
-/// Find a similar tool name (simple substring match)
+/// Find a similar tool name (substring match, then edit distance)
 fn find_similar_tool(name: &str, tools: &[hooteproto::ToolInfo]) -> Option<String> {
     let name_lower = name.to_lowercase();
 
@@ -212,7 +212,7 @@ fn find_similar_tool(name: &str, tools: &[hooteproto::ToolInfo]) -> Option<Strin
         }
     }
 
-    None
+    crate::tools_registry::suggest_tools(&name_lower, 1).pop()
 }
 
 #[cfg(test)]
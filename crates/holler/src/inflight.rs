@@ -0,0 +1,164 @@
+//! In-flight tool call tracking for graceful shutdown
+//!
+//! Long tool calls (generations, renders) can outlive a shutdown signal.
+//! Every `tools/call` holds an [`InFlightCall`] guard while it runs, and the
+//! shutdown path awaits [`InFlightCalls::drain`] before cancelling the
+//! listener, so deploys don't leave half-finished jobs behind.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+#[derive(Default)]
+struct Inner {
+    active: AtomicUsize,
+    draining: AtomicBool,
+    idle: Notify,
+}
+
+impl Inner {
+    fn finish(&self) {
+        if self.active.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// Count of running tool calls, shared by every handler instance.
+#[derive(Clone, Default)]
+pub struct InFlightCalls {
+    inner: Arc<Inner>,
+}
+
+/// Marks one tool call as running until dropped.
+pub struct InFlightCall {
+    inner: Arc<Inner>,
+}
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        self.inner.finish();
+    }
+}
+
+impl InFlightCalls {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new call, or `None` once draining has begun.
+    pub fn begin(&self) -> Option<InFlightCall> {
+        if self.is_draining() {
+            return None;
+        }
+        self.inner.active.fetch_add(1, Ordering::SeqCst);
+
+        // Lost a race with drain(): back out so it isn't kept waiting on us
+        if self.is_draining() {
+            self.inner.finish();
+            return None;
+        }
+
+        Some(InFlightCall {
+            inner: Arc::clone(&self.inner),
+        })
+    }
+
+    /// Number of calls currently running.
+    pub fn active(&self) -> usize {
+        self.inner.active.load(Ordering::SeqCst)
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.inner.draining.load(Ordering::SeqCst)
+    }
+
+    /// Stop accepting calls and wait up to `timeout` for running ones to finish.
+    ///
+    /// Returns `true` if every call finished in time. Draining is one-way;
+    /// new calls stay rejected after this returns either way.
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        self.inner.draining.store(true, Ordering::SeqCst);
+
+        let idle = async {
+            loop {
+                let notified = self.inner.idle.notified();
+                tokio::pin!(notified);
+                // Register before checking so a finish in between isn't missed
+                notified.as_mut().enable();
+                if self.active() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+
+        tokio::time::timeout(timeout, idle).await.is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Stand-in for a long tool call holding its guard while it works.
+    fn spawn_slow_tool(calls: &InFlightCalls, work: Duration) -> tokio::task::JoinHandle<()> {
+        let call = calls.begin().expect("not draining yet");
+        tokio::spawn(async move {
+            tokio::time::sleep(work).await;
+            drop(call);
+        })
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_slow_tool() {
+        let calls = InFlightCalls::new();
+        let tool = spawn_slow_tool(&calls, Duration::from_millis(200));
+        assert_eq!(calls.active(), 1);
+
+        let started = Instant::now();
+        let drained = calls.drain(Duration::from_secs(5)).await;
+
+        assert!(drained);
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        assert_eq!(calls.active(), 0);
+        tool.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn drain_rejects_new_calls() {
+        let calls = InFlightCalls::new();
+        let _tool = spawn_slow_tool(&calls, Duration::from_millis(100));
+
+        let draining = {
+            let calls = calls.clone();
+            tokio::spawn(async move { calls.drain(Duration::from_secs(5)).await })
+        };
+        while !calls.is_draining() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(calls.begin().is_none());
+        assert!(draining.await.unwrap());
+        assert!(calls.begin().is_none());
+    }
+
+    #[tokio::test]
+    async fn drain_gives_up_after_timeout() {
+        let calls = InFlightCalls::new();
+        let _tool = spawn_slow_tool(&calls, Duration::from_secs(10));
+
+        assert!(!calls.drain(Duration::from_millis(50)).await);
+        assert_eq!(calls.active(), 1);
+    }
+
+    #[tokio::test]
+    async fn drain_with_nothing_running_is_immediate() {
+        let calls = InFlightCalls::new();
+        drop(calls.begin());
+        assert!(calls.drain(Duration::ZERO).await);
+    }
+}
@@ -0,0 +1,314 @@
+//! Tool argument validation against the advertised input schema
+//!
+//! Every tool in `tools_registry` carries a JSON Schema, so holler can catch
+//! malformed arguments before they reach the JSON → Payload conversion and
+//! report every problem at once instead of the first serde error.
+//!
+//! Only the subset our hand-written schemas use is checked: `type`
+//! (including `["T", "null"]` unions), `required`, `properties`,
+//! `additionalProperties: false`, `items`, `enum`, `minimum`/`maximum` and
+//! `oneOf`/`anyOf`. Unknown keywords are ignored.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// One argument that doesn't match the schema
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaError {
+    /// Dotted path to the offending value (`notes[2].pitch`), empty for the root
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Check `args` against `schema`, returning every mismatch found.
+pub fn validate(schema: &Value, args: &Value) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    check(schema, args, "", &mut errors);
+    errors
+}
+
+fn check(schema: &Value, value: &Value, path: &str, errors: &mut Vec<SchemaError>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    for key in ["oneOf", "anyOf"] {
+        if let Some(branches) = schema.get(key).and_then(|v| v.as_array()) {
+            let matches = branches
+                .iter()
+                .filter(|branch| validate_at(branch, value, path).is_empty())
+                .count();
+            let ok = if key == "oneOf" {
+                matches == 1
+            } else {
+                matches > 0
+            };
+            if !ok {
+                push(
+                    errors,
+                    path,
+                    format!("does not match {} of the allowed shapes", key),
+                );
+            }
+        }
+    }
+
+    if let Some(expected) = schema.get("type") {
+        if !type_matches(expected, value) {
+            push(
+                errors,
+                path,
+                format!(
+                    "expected {}, got {}",
+                    describe_type(expected),
+                    type_name(value)
+                ),
+            );
+            // Nothing below makes sense for the wrong type
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|v| v.as_array()) {
+        if !allowed.contains(value) {
+            push(
+                errors,
+                path,
+                format!("must be one of {}", Value::Array(allowed.clone())),
+            );
+        }
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(|v| v.as_f64()) {
+            if n < min {
+                push(
+                    errors,
+                    path,
+                    format!("must be at least {}, got {}", min, value),
+                );
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(|v| v.as_f64()) {
+            if n > max {
+                push(
+                    errors,
+                    path,
+                    format!("must be at most {}, got {}", max, value),
+                );
+            }
+        }
+    }
+
+    match value {
+        Value::Object(fields) => check_object(schema, fields, path, errors),
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    fields: &Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<SchemaError>,
+) {
+    if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
+        for name in required.iter().filter_map(|v| v.as_str()) {
+            if !fields.contains_key(name) {
+                push(errors, &join(path, name), "is required".to_string());
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(|v| v.as_object());
+    let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+
+    for (name, field) in fields {
+        match properties.and_then(|p| p.get(name)) {
+            Some(field_schema) => check(field_schema, field, &join(path, name), errors),
+            None if closed => push(
+                errors,
+                &join(path, name),
+                "is not a known field".to_string(),
+            ),
+            None => {}
+        }
+    }
+}
+
+/// Run a nested check without touching the caller's error list
+fn validate_at(schema: &Value, value: &Value, path: &str) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    check(schema, value, path, &mut errors);
+    errors
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => is_type(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(|v| v.as_str())
+            .any(|name| is_type(name, value)),
+        _ => true,
+    }
+}
+
+fn is_type(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        // Unknown type names aren't ours to reject
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or("any").to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+fn push(errors: &mut Vec<SchemaError>, path: &str, message: String) {
+    errors.push(SchemaError {
+        path: path.to_string(),
+        message,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["input_hash", "notes"],
+            "properties": {
+                "input_hash": { "type": "string" },
+                "temperature": { "type": "number", "minimum": 0 },
+                "mode": { "type": ["string", "null"], "enum": ["any", "all", null] },
+                "notes": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["pitch"],
+                        "additionalProperties": false,
+                        "properties": { "pitch": { "type": "integer" } }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Sorted, since field order depends on serde_json's map features
+    fn messages(errors: &[SchemaError]) -> Vec<String> {
+        let mut messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    fn valid_arguments_pass() {
+        let args = json!({
+            "input_hash": "abc",
+            "temperature": 1.0,
+            "mode": null,
+            "notes": [{ "pitch": 60 }],
+            "extra": "top level is open"
+        });
+        assert!(validate(&schema(), &args).is_empty());
+    }
+
+    #[test]
+    fn reports_every_error() {
+        let args = json!({
+            "temperature": -1,
+            "mode": "some",
+            "notes": [{ "pitch": 60 }, { "pitch": "C4", "velocity": 90 }, {}]
+        });
+        assert_eq!(
+            messages(&validate(&schema(), &args)),
+            vec![
+                "input_hash: is required",
+                "mode: must be one of [\"any\",\"all\",null]",
+                "notes[1].pitch: expected integer, got string",
+                "notes[1].velocity: is not a known field",
+                "notes[2].pitch: is required",
+                "temperature: must be at least 0, got -1",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrong_root_type_stops_early() {
+        let errors = validate(&schema(), &json!("not an object"));
+        assert_eq!(messages(&errors), vec!["expected object, got string"]);
+    }
+
+    #[test]
+    fn one_of_needs_exactly_one_match() {
+        let schema = json!({
+            "oneOf": [
+                { "type": "object", "required": ["hash"] },
+                { "type": "object", "required": ["path"] }
+            ]
+        });
+        assert!(validate(&schema, &json!({ "hash": "h" })).is_empty());
+        assert_eq!(
+            validate(&schema, &json!({ "hash": "h", "path": "p" })).len(),
+            1
+        );
+        assert_eq!(validate(&schema, &json!({})).len(), 1);
+    }
+}
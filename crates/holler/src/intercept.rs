@@ -0,0 +1,230 @@
+//! Admission checks run on every tool call before dispatch
+//!
+//! The handler asks its [`CallInterceptor`] about each `tools/call` after
+//! the arguments are validated and before anything reaches the backend, so
+//! an embedder can refuse unauthenticated sessions or throttle expensive
+//! tools without touching dispatch. [`AllowAll`] is the default;
+//! [`RateLimiter`] is a per-session token bucket, configured by
+//! `[gateway.rate_limit]`.
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use hooteconf::infra::RateLimitConfig;
+use rmcp::ErrorData as McpError;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::dispatch;
+
+/// Buckets kept before full (idle) ones are evicted
+const MAX_BUCKETS: usize = 1024;
+
+/// What an interceptor knows about a call
+#[derive(Debug, Clone, Copy)]
+pub struct CallInfo<'a> {
+    pub tool: &'a str,
+    /// Arguments as the client sent them (already schema-checked)
+    pub arguments: &'a serde_json::Value,
+    /// `Mcp-Session-Id` of the HTTP session; `None` over stdio
+    pub session: Option<&'a str>,
+    /// Headers of the HTTP request carrying the call; `None` over stdio
+    pub headers: Option<&'a HeaderMap>,
+}
+
+/// Decides whether a tool call may proceed.
+///
+/// `before` is async so an implementation can consult an auth service; it
+/// runs under the call's deadline and cancellation.
+#[async_trait]
+pub trait CallInterceptor: Send + Sync + 'static {
+    /// `Err` refuses the call and is returned to the client as is.
+    async fn before(&self, call: &CallInfo<'_>) -> Result<(), McpError>;
+}
+
+/// Admits every call.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+#[async_trait]
+impl CallInterceptor for AllowAll {
+    async fn before(&self, _call: &CallInfo<'_>) -> Result<(), McpError> {
+        Ok(())
+    }
+}
+
+/// The interceptor `[gateway.rate_limit]` asks for: a [`RateLimiter`] when
+/// enabled, otherwise [`AllowAll`].
+pub fn from_config(config: &RateLimitConfig) -> Arc<dyn CallInterceptor> {
+    if !config.enabled {
+        return Arc::new(AllowAll);
+    }
+    let limiter = RateLimiter::new(config.burst, config.per_second);
+    if config.tools.is_empty() {
+        Arc::new(limiter)
+    } else {
+        Arc::new(limiter.only_tools(config.tools.iter().cloned()))
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token-bucket rate limit per session.
+///
+/// Each session may make `burst` calls at once, then `per_second` calls a
+/// second after that. Calls without a session (stdio) share one bucket.
+pub struct RateLimiter {
+    burst: f64,
+    per_second: f64,
+    /// Only these tools are limited; `None` limits every tool
+    tools: Option<Vec<String>>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(burst: u32, per_second: f64) -> Self {
+        Self {
+            burst: f64::from(burst.max(1)),
+            per_second,
+            tools: None,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Limit only the named tools (generation, say) and let the rest through.
+    pub fn only_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tools = Some(tools.into_iter().map(Into::into).collect());
+        self
+    }
+
+    fn limits(&self, tool: &str) -> bool {
+        self.tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|t| t == tool))
+    }
+
+    /// Take a token for `session`, or say how long until one is available
+    /// (`None` if the bucket never refills).
+    fn take(&self, session: &str, now: Instant) -> Result<(), Option<Duration>> {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(session) {
+            buckets.retain(|_, bucket| self.refilled(bucket, now) < self.burst);
+        }
+
+        let bucket = buckets.entry(session.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        bucket.tokens = self.refilled(bucket, now);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else if self.per_second > 0.0 {
+            Err(Some(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.per_second,
+            )))
+        } else {
+            Err(None)
+        }
+    }
+
+    fn refilled(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.per_second).min(self.burst)
+    }
+}
+
+#[async_trait]
+impl CallInterceptor for RateLimiter {
+    async fn before(&self, call: &CallInfo<'_>) -> Result<(), McpError> {
+        if !self.limits(call.tool) {
+            return Ok(());
+        }
+        self.take(call.session.unwrap_or_default(), Instant::now())
+            .map_err(|retry_after| dispatch::rate_limited(call.tool, retry_after))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO_ARGS: serde_json::Value = serde_json::Value::Null;
+
+    fn call<'a>(tool: &'a str, session: &'a str) -> CallInfo<'a> {
+        CallInfo {
+            tool,
+            arguments: &NO_ARGS,
+            session: Some(session),
+            headers: None,
+        }
+    }
+
+    #[test]
+    fn buckets_drain_and_refill_per_session() {
+        let limiter = RateLimiter::new(2, 1.0);
+        let start = Instant::now();
+
+        assert!(limiter.take("a", start).is_ok());
+        assert!(limiter.take("a", start).is_ok());
+        let wait = limiter.take("a", start).unwrap_err();
+        assert_eq!(wait, Some(Duration::from_secs(1)));
+
+        // Another session has its own bucket
+        assert!(limiter.take("b", start).is_ok());
+
+        let later = start + Duration::from_millis(1500);
+        assert!(limiter.take("a", later).is_ok());
+        assert!(limiter.take("a", later).is_err());
+    }
+
+    #[tokio::test]
+    async fn only_listed_tools_are_limited() {
+        let limiter = RateLimiter::new(1, 0.0).only_tools(["orpheus_generate"]);
+
+        assert!(limiter.before(&call("orpheus_generate", "s")).await.is_ok());
+        let err = limiter
+            .before(&call("orpheus_generate", "s"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, dispatch::RATE_LIMITED);
+
+        for _ in 0..5 {
+            assert!(limiter.before(&call("job_poll", "s")).await.is_ok());
+        }
+        assert!(AllowAll
+            .before(&call("orpheus_generate", "s"))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn config_picks_the_interceptor() {
+        let mut config = RateLimitConfig::default();
+        let allow = from_config(&config);
+        for _ in 0..20 {
+            assert!(allow.before(&call("orpheus_generate", "s")).await.is_ok());
+        }
+
+        config.enabled = true;
+        config.burst = 1;
+        config.per_second = 0.0;
+        config.tools = vec!["orpheus_generate".to_string()];
+        let limited = from_config(&config);
+        assert!(limited.before(&call("orpheus_generate", "s")).await.is_ok());
+        assert!(limited
+            .before(&call("orpheus_generate", "s"))
+            .await
+            .is_err());
+        assert!(limited.before(&call("job_poll", "s")).await.is_ok());
+    }
+}
@@ -2,23 +2,43 @@
 //!
 //! This library provides:
 //! - `backend`: ZMQ backend connection using hooteproto::HootClient
+//! - `batch`: JSON-RPC batches split for rmcp on both transports
+//! - `completion`: argument suggestions for prompts and resource templates
 //! - `dispatch`: JSON → typed Payload conversion (JSON boundary)
+//! - `elicit`: asking the user for input (or confirmation) mid tool call
 //! - `handler`: MCP handler implementation
+//! - `inflight`: in-flight tool call tracking for graceful shutdown
+//! - `input_schema`: tool argument validation against input schemas
+//! - `intercept`: admission checks (auth, rate limits) before tool dispatch
+//! - `logging`: MCP logging notifications filtered by session level
+//! - `pagination`: stateless cursors for the MCP list methods
+//! - `replay`: recent broadcasts replayed to `/events` clients on reconnect
 //! - `serve`: MCP gateway server (HTTP transport)
 //! - `stdio`: MCP stdio transport for Claude Code
 //! - `client`: ZMQ client utilities
 //! - `subscriber`: ZMQ subscriber for broadcasts
+//! - `progress`: backend job progress → MCP progress notifications
 //! - `resources`: MCP Resources (curated views into session state)
 //! - `prompts`: MCP Prompts (query templates)
 
 pub mod backend;
+pub mod batch;
 pub mod client;
 pub mod commands;
+pub mod completion;
 pub mod dispatch;
+pub mod elicit;
 pub mod handler;
 pub mod help;
+pub mod inflight;
+pub mod input_schema;
+pub mod intercept;
+pub mod logging;
 pub mod manual_schemas;
+pub mod pagination;
+pub mod progress;
 pub mod prompts;
+pub mod replay;
 pub mod resources;
 pub mod serve;
 pub mod stdio;
@@ -0,0 +1,233 @@
+//! MCP logging: per-session level filtering and backend log forwarding
+//!
+//! A client opts in with `logging/setLevel`; until then a session gets no
+//! `notifications/message` at all. After that, holler's own messages (via
+//! [`SessionLog::log`]) and the backend's `Broadcast::Log`s at or above the
+//! chosen level are sent to that session only.
+
+use hooteproto::Broadcast;
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::{Peer, RoleServer};
+use serde_json::Value;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tracing::debug;
+
+/// Where log notifications are delivered
+pub trait LogSink: Send + Sync + 'static {
+    fn send_log(
+        &self,
+        param: LoggingMessageNotificationParam,
+    ) -> impl Future<Output = Result<(), String>> + Send;
+}
+
+impl LogSink for Peer<RoleServer> {
+    async fn send_log(&self, param: LoggingMessageNotificationParam) -> Result<(), String> {
+        self.notify_logging_message(param)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Rank of a level, lowest (debug) first
+pub fn severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// Map a backend level string (`"warn"`, `"error"`, ...) onto an MCP level.
+///
+/// Unrecognized levels are treated as info.
+pub fn parse_level(level: &str) -> LoggingLevel {
+    match level.to_ascii_lowercase().as_str() {
+        "trace" | "debug" => LoggingLevel::Debug,
+        "notice" => LoggingLevel::Notice,
+        "warn" | "warning" => LoggingLevel::Warning,
+        "error" => LoggingLevel::Error,
+        "critical" | "fatal" => LoggingLevel::Critical,
+        "alert" => LoggingLevel::Alert,
+        "emergency" => LoggingLevel::Emergency,
+        _ => LoggingLevel::Info,
+    }
+}
+
+/// One session's logging level, shared by its handler clones.
+#[derive(Clone, Default)]
+pub struct SessionLog {
+    level: Arc<Mutex<Option<LoggingLevel>>>,
+    forwarding: Arc<AtomicBool>,
+}
+
+impl SessionLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimum level the client asked for, if it has asked.
+    pub fn level(&self) -> Option<LoggingLevel> {
+        *self.level.lock().unwrap()
+    }
+
+    pub fn set_level(&self, level: LoggingLevel) {
+        *self.level.lock().unwrap() = Some(level);
+    }
+
+    /// Whether a message at `level` should reach this session.
+    pub fn should_log(&self, level: LoggingLevel) -> bool {
+        self.level()
+            .is_some_and(|min| severity(level) >= severity(min))
+    }
+
+    /// Send a message to the session if its level lets it through.
+    pub async fn log(&self, sink: &impl LogSink, level: LoggingLevel, logger: &str, data: Value) {
+        if !self.should_log(level) {
+            return;
+        }
+        let param = LoggingMessageNotificationParam {
+            level,
+            logger: Some(logger.to_string()),
+            data,
+        };
+        if let Err(e) = sink.send_log(param).await {
+            debug!("Log notification undeliverable: {}", e);
+        }
+    }
+
+    /// Start forwarding backend `Broadcast::Log`s to `sink`, once per session.
+    ///
+    /// Returns whether a forwarder was started.
+    pub fn forward_backend_logs(
+        &self,
+        sink: impl LogSink,
+        broadcasts: broadcast::Receiver<Broadcast>,
+    ) -> bool {
+        if self.forwarding.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        let session = self.clone();
+        tokio::spawn(async move { forward_logs(&session, &sink, broadcasts).await });
+        true
+    }
+}
+
+async fn forward_logs(
+    session: &SessionLog,
+    sink: &impl LogSink,
+    mut broadcasts: broadcast::Receiver<Broadcast>,
+) {
+    loop {
+        match broadcasts.recv().await {
+            Ok(Broadcast::Log {
+                level,
+                message,
+                source,
+            }) => {
+                let level = parse_level(&level);
+                if !session.should_log(level) {
+                    continue;
+                }
+                let param = LoggingMessageNotificationParam {
+                    level,
+                    logger: Some(source),
+                    data: Value::String(message),
+                };
+                if let Err(e) = sink.send_log(param).await {
+                    debug!("Session gone, stopped forwarding backend logs: {}", e);
+                    return;
+                }
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!("Log forwarder skipped {} broadcasts", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    struct ChannelSink(mpsc::UnboundedSender<LoggingMessageNotificationParam>);
+
+    impl LogSink for ChannelSink {
+        async fn send_log(&self, param: LoggingMessageNotificationParam) -> Result<(), String> {
+            self.0.send(param).map_err(|e| e.to_string())
+        }
+    }
+
+    fn backend_log(level: &str, message: &str) -> Broadcast {
+        Broadcast::Log {
+            level: level.to_string(),
+            message: message.to_string(),
+            source: "chaosgarden".to_string(),
+        }
+    }
+
+    #[test]
+    fn nothing_is_logged_until_a_level_is_set() {
+        let log = SessionLog::new();
+        assert!(!log.should_log(LoggingLevel::Emergency));
+
+        log.set_level(LoggingLevel::Warning);
+        assert!(!log.should_log(LoggingLevel::Info));
+        assert!(log.should_log(LoggingLevel::Warning));
+        assert!(log.should_log(LoggingLevel::Error));
+    }
+
+    #[tokio::test]
+    async fn log_respects_session_level() {
+        let log = SessionLog::new();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sink = ChannelSink(tx);
+
+        log.set_level(LoggingLevel::Notice);
+        log.log(&sink, LoggingLevel::Debug, "holler", Value::from("hidden"))
+            .await;
+        log.log(&sink, LoggingLevel::Error, "holler", Value::from("shown"))
+            .await;
+
+        let param = rx.try_recv().unwrap();
+        assert_eq!(param.level, LoggingLevel::Error);
+        assert_eq!(param.data, Value::from("shown"));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn backend_logs_are_filtered_and_forwarded_once() {
+        let log = SessionLog::new();
+        log.set_level(LoggingLevel::Warning);
+
+        let (broadcasts, _) = broadcast::channel(16);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        assert!(log.forward_backend_logs(ChannelSink(tx.clone()), broadcasts.subscribe()));
+        assert!(!log.forward_backend_logs(ChannelSink(tx), broadcasts.subscribe()));
+
+        broadcasts.send(backend_log("info", "tick")).unwrap();
+        broadcasts
+            .send(backend_log("warn", "ring overran"))
+            .unwrap();
+
+        let param = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("log not forwarded")
+            .unwrap();
+        assert_eq!(param.level, LoggingLevel::Warning);
+        assert_eq!(param.logger.as_deref(), Some("chaosgarden"));
+        assert_eq!(param.data, Value::from("ring overran"));
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -4,7 +4,7 @@
 //! - `holler serve` - Run the MCP gateway (HTTP transport, stateful)
 //! - `holler mcp` - Run MCP server over stdio (for Claude Code)
 //! - `holler ping <endpoint>` - Test connectivity to a backend
-//! - `holler send <endpoint> <json>` - Send raw hooteproto message
+//! - `holler send <endpoint> <json> [--stream]` - Send raw hooteproto message
 //! - `holler job <endpoint> <action>` - Query job status
 //!
 //! Configuration is loaded from (in order, later wins):
@@ -66,9 +66,13 @@ enum Commands {
         /// JSON payload (Payload type, not Envelope)
         json: String,
 
-        /// Timeout in milliseconds
+        /// Timeout in milliseconds (idle time between frames with --stream)
         #[arg(short, long, default_value = "30000")]
         timeout: u64,
+
+        /// Keep the socket open and print every frame received, with timestamps
+        #[arg(long)]
+        stream: bool,
     },
 
     /// Query job status
@@ -196,8 +200,13 @@ async fn main() -> Result<()> {
             endpoint,
             json,
             timeout,
+            stream,
         } => {
-            commands::send(&endpoint, &json, timeout).await?;
+            if stream {
+                commands::send_stream(&endpoint, &json, timeout).await?;
+            } else {
+                commands::send(&endpoint, &json, timeout).await?;
+            }
         }
         Commands::Job { endpoint, action } => match action {
             JobAction::Status { job_id, timeout } => {
@@ -225,12 +234,24 @@ async fn main() -> Result<()> {
                 for path in &sources.files {
                     println!("#   - {}", path.display());
                 }
+                if let Some(profile) = &sources.profile {
+                    println!("# Profile overlays ({}):", profile);
+                    for path in &sources.profile_files {
+                        println!("#   - {}", path.display());
+                    }
+                }
                 if !sources.env_overrides.is_empty() {
                     println!("# Environment overrides:");
                     for var in &sources.env_overrides {
                         println!("#   - {}", var);
                     }
                 }
+                if !sources.warnings.is_empty() {
+                    println!("# Warnings:");
+                    for warning in &sources.warnings {
+                        println!("#   - {}", warning);
+                    }
+                }
                 println!();
                 println!("{}", config.to_toml());
                 return Ok(());
@@ -246,6 +267,9 @@ async fn main() -> Result<()> {
             if !sources.env_overrides.is_empty() {
                 tracing::info!("   Environment overrides: {:?}", sources.env_overrides);
             }
+            for warning in &sources.warnings {
+                tracing::warn!("   Config warning: {}", warning);
+            }
             if daw_only {
                 tracing::info!("🎛️ DAW-only mode: exposing only DAW tools");
             }
@@ -267,6 +291,9 @@ async fn main() -> Result<()> {
                 hootenanny: config.infra.gateway.hootenanny,
                 hootenanny_pub: Some(config.infra.gateway.hootenanny_pub),
                 timeout_ms: config.infra.gateway.timeout_ms,
+                tool_timeout_ms: config.infra.gateway.tool_timeout_ms,
+                list_page_size: config.infra.gateway.list_page_size,
+                rate_limit: config.infra.gateway.rate_limit.clone(),
                 daw_only,
                 artifact_base_url,
                 tls,
@@ -285,6 +312,9 @@ async fn main() -> Result<()> {
             stdio::run(stdio::StdioConfig {
                 hootenanny: config.infra.gateway.hootenanny,
                 timeout_ms: config.infra.gateway.timeout_ms,
+                tool_timeout_ms: config.infra.gateway.tool_timeout_ms,
+                list_page_size: config.infra.gateway.list_page_size,
+                rate_limit: config.infra.gateway.rate_limit.clone(),
                 daw_only,
             })
             .await?;
@@ -0,0 +1,104 @@
+//! Cursor pagination for the MCP list methods
+//!
+//! `tools/list`, `resources/list`, `resources/templates/list` and
+//! `prompts/list` return at most one page of items plus a `nextCursor` when
+//! more remain. The cursor is opaque to clients but only encodes the offset
+//! of the next page, so holler keeps no per-client state. A list that
+//! changes between pages (a tool refresh) may skip or repeat an item.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rmcp::model::PaginatedRequestParam;
+use rmcp::ErrorData as McpError;
+
+/// Items per page unless configured otherwise
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+const CURSOR_PREFIX: &str = "offset:";
+
+/// Cursor for the page starting at `offset`.
+pub fn encode_cursor(offset: usize) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}{}", CURSOR_PREFIX, offset))
+}
+
+/// Offset a cursor from [`encode_cursor`] points at.
+pub fn decode_cursor(cursor: &str) -> Result<usize, McpError> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|text| text.strip_prefix(CURSOR_PREFIX)?.parse().ok())
+        .ok_or_else(|| McpError::invalid_params(format!("Invalid cursor: {}", cursor), None))
+}
+
+/// The page of `items` the request's cursor asks for, and the cursor for
+/// the page after it if there is one.
+///
+/// A `page_size` of 0 is treated as 1.
+pub fn paginate<T>(
+    mut items: Vec<T>,
+    request: Option<&PaginatedRequestParam>,
+    page_size: usize,
+) -> Result<(Vec<T>, Option<String>), McpError> {
+    let offset = match request.and_then(|r| r.cursor.as_deref()) {
+        Some(cursor) => decode_cursor(cursor)?,
+        None => 0,
+    };
+    if offset > items.len() {
+        return Err(McpError::invalid_params(
+            format!("Cursor is past the end of the list ({} items)", items.len()),
+            None,
+        ));
+    }
+
+    let end = offset.saturating_add(page_size.max(1)).min(items.len());
+    let next_cursor = (end < items.len()).then(|| encode_cursor(end));
+    items.truncate(end);
+    let page = items.split_off(offset);
+    Ok((page, next_cursor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(cursor: Option<String>) -> PaginatedRequestParam {
+        PaginatedRequestParam { cursor }
+    }
+
+    #[test]
+    fn pages_follow_the_cursor_to_the_end() {
+        let items: Vec<u32> = (0..7).collect();
+
+        let (page, next) = paginate(items.clone(), None, 3).unwrap();
+        assert_eq!(page, vec![0, 1, 2]);
+
+        let (page, next) = paginate(items.clone(), Some(&request(next)), 3).unwrap();
+        assert_eq!(page, vec![3, 4, 5]);
+
+        let (page, next) = paginate(items.clone(), Some(&request(next)), 3).unwrap();
+        assert_eq!(page, vec![6]);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn short_lists_fit_one_page() {
+        let (page, next) = paginate(vec!["a", "b"], Some(&request(None)), 100).unwrap();
+        assert_eq!(page, vec!["a", "b"]);
+        assert_eq!(next, None);
+
+        let (page, next) = paginate(Vec::<u8>::new(), None, 0).unwrap();
+        assert!(page.is_empty());
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn bad_cursors_are_invalid_params() {
+        assert_eq!(decode_cursor(&encode_cursor(42)).unwrap(), 42);
+        assert!(decode_cursor("not a cursor").is_err());
+        assert!(decode_cursor(&URL_SAFE_NO_PAD.encode("limit:5")).is_err());
+
+        let past_the_end = request(Some(encode_cursor(10)));
+        assert!(paginate(vec![1, 2, 3], Some(&past_the_end), 2).is_err());
+    }
+}
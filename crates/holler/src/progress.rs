@@ -0,0 +1,111 @@
+//! Bridge backend job progress to MCP progress notifications
+//!
+//! A tool call that carries a `progressToken` and starts a backend job gets a
+//! forwarding task: each `Broadcast::Progress` for that job becomes a
+//! `notifications/progress` on the caller's session, until the job's
+//! `JobStateChanged` reaches a terminal state.
+
+use hooteproto::responses::ToolResponse;
+use hooteproto::{Broadcast, ResponseEnvelope};
+use rmcp::model::{ProgressNotificationParam, ProgressToken};
+use rmcp::{Peer, RoleServer};
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+/// Stop forwarding if a job never reports a terminal state
+const MAX_FORWARD_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// Job states after which no more progress arrives
+pub fn is_terminal_state(state: &str) -> bool {
+    matches!(state, "complete" | "failed" | "cancelled")
+}
+
+/// Where forwarded progress notifications are delivered
+pub trait ProgressSink: Send + Sync + 'static {
+    fn send_progress(
+        &self,
+        param: ProgressNotificationParam,
+    ) -> impl Future<Output = Result<(), String>> + Send;
+}
+
+impl ProgressSink for Peer<RoleServer> {
+    async fn send_progress(&self, param: ProgressNotificationParam) -> Result<(), String> {
+        self.notify_progress(param).await.map_err(|e| e.to_string())
+    }
+}
+
+/// Job ID of a response that started a backend job
+///
+/// Over capnp a job start decodes as `Success` wrapping
+/// `ToolResponse::JobStarted` rather than the `JobStarted` envelope.
+pub fn started_job_id(envelope: &ResponseEnvelope) -> Option<&str> {
+    match envelope {
+        ResponseEnvelope::JobStarted { job_id, .. } => Some(job_id),
+        ResponseEnvelope::Success {
+            response: ToolResponse::JobStarted(started),
+        } => Some(&started.job_id),
+        _ => None,
+    }
+}
+
+/// Forward progress for `job_id` to `sink` until the job finishes.
+///
+/// `broadcasts` should be subscribed before the job is started, so updates
+/// sent before the tool call returns aren't missed.
+pub fn spawn_progress_forwarder(
+    job_id: String,
+    token: ProgressToken,
+    sink: impl ProgressSink,
+    broadcasts: broadcast::Receiver<Broadcast>,
+) {
+    tokio::spawn(async move {
+        let forwarding = forward_progress(&job_id, &token, &sink, broadcasts);
+        if tokio::time::timeout(MAX_FORWARD_DURATION, forwarding).await.is_err() {
+            warn!(
+                "Job {} never reached a terminal state, stopped forwarding progress",
+                job_id
+            );
+        }
+    });
+}
+
+async fn forward_progress(
+    job_id: &str,
+    token: &ProgressToken,
+    sink: &impl ProgressSink,
+    mut broadcasts: broadcast::Receiver<Broadcast>,
+) {
+    loop {
+        match broadcasts.recv().await {
+            Ok(Broadcast::Progress {
+                job_id: id,
+                percent,
+                message,
+            }) if id == job_id => {
+                let param = ProgressNotificationParam {
+                    progress_token: token.clone(),
+                    progress: percent as f64,
+                    total: Some(1.0),
+                    message: (!message.is_empty()).then_some(message),
+                };
+                if let Err(e) = sink.send_progress(param).await {
+                    debug!("Progress for job {} undeliverable, stopping: {}", job_id, e);
+                    return;
+                }
+            }
+            Ok(Broadcast::JobStateChanged {
+                job_id: id, state, ..
+            }) if id == job_id && is_terminal_state(&state) => {
+                debug!("Job {} {}, stopped forwarding progress", job_id, state);
+                return;
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!("Progress forwarder for job {} skipped {} broadcasts", job_id, skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Recent-broadcast buffer so `/events` clients can resume after a drop
+//!
+//! Every broadcast forwarded to SSE gets a monotonically increasing id, sent
+//! as the event's `id:` field, and the last [`EventLog`] capacity's worth are
+//! kept. A client reconnecting with `Last-Event-Id` first gets the buffered
+//! events after that id, then the live stream.
+//!
+//! Replay is best-effort: events older than the buffer window are gone, and
+//! ids restart from 1 when holler restarts. (MCP's own `/mcp` stream is
+//! resumed by rmcp's session manager, not here.)
+
+use hooteproto::Broadcast;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+/// A broadcast tagged with its event id
+pub type NumberedBroadcast = (u64, Broadcast);
+
+struct Ring {
+    next_id: u64,
+    capacity: usize,
+    events: VecDeque<NumberedBroadcast>,
+}
+
+/// Numbers broadcasts and remembers the most recent ones.
+#[derive(Clone)]
+pub struct EventLog {
+    ring: Arc<Mutex<Ring>>,
+    live: broadcast::Sender<NumberedBroadcast>,
+}
+
+impl EventLog {
+    /// Keep up to `capacity` events for replay.
+    pub fn new(capacity: usize) -> Self {
+        let (live, _) = broadcast::channel(capacity.max(1));
+        Self {
+            ring: Arc::new(Mutex::new(Ring {
+                next_id: 1,
+                capacity,
+                events: VecDeque::with_capacity(capacity),
+            })),
+            live,
+        }
+    }
+
+    /// Number a broadcast, remember it, and pass it on to live subscribers.
+    pub fn record(&self, broadcast: Broadcast) -> u64 {
+        let mut ring = self.ring.lock().unwrap();
+        let id = ring.next_id;
+        ring.next_id += 1;
+        if ring.events.len() == ring.capacity {
+            ring.events.pop_front();
+        }
+        if ring.capacity > 0 {
+            ring.events.push_back((id, broadcast.clone()));
+        }
+        // Sent under the lock so `resume` never sees an event twice or not at all
+        let _ = self.live.send((id, broadcast));
+        id
+    }
+
+    /// Buffered events after `last_id`, and a receiver for everything newer.
+    ///
+    /// Without a `last_id` nothing is replayed.
+    pub fn resume(
+        &self,
+        last_id: Option<u64>,
+    ) -> (
+        Vec<NumberedBroadcast>,
+        broadcast::Receiver<NumberedBroadcast>,
+    ) {
+        let ring = self.ring.lock().unwrap();
+        let live = self.live.subscribe();
+        let missed = match last_id {
+            Some(last_id) => ring
+                .events
+                .iter()
+                .filter(|(id, _)| *id > last_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        (missed, live)
+    }
+
+    /// Record everything sent on `broadcasts` until the channel closes.
+    pub fn spawn_recorder(&self, mut broadcasts: broadcast::Receiver<Broadcast>) -> JoinHandle<()> {
+        let log = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match broadcasts.recv().await {
+                    Ok(broadcast) => {
+                        log.record(broadcast);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!("Event log skipped {} broadcasts", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_line(message: &str) -> Broadcast {
+        Broadcast::Log {
+            level: "info".to_string(),
+            message: message.to_string(),
+            source: "test".to_string(),
+        }
+    }
+
+    fn ids(events: &[NumberedBroadcast]) -> Vec<u64> {
+        events.iter().map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn resume_replays_after_last_id() {
+        let log = EventLog::new(8);
+        for i in 0..5 {
+            log.record(log_line(&i.to_string()));
+        }
+
+        let (missed, _) = log.resume(Some(3));
+        assert_eq!(ids(&missed), vec![4, 5]);
+        assert_eq!(missed[0].1, log_line("3"));
+
+        let (missed, _) = log.resume(None);
+        assert!(missed.is_empty());
+    }
+
+    #[test]
+    fn oldest_events_fall_out_of_the_window() {
+        let log = EventLog::new(3);
+        for i in 0..5 {
+            log.record(log_line(&i.to_string()));
+        }
+
+        // 2 is gone; the rest of the window is all that can be replayed
+        let (missed, _) = log.resume(Some(1));
+        assert_eq!(ids(&missed), vec![3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn live_events_follow_the_replay() {
+        let log = EventLog::new(8);
+        log.record(log_line("before"));
+
+        let (missed, mut live) = log.resume(Some(0));
+        assert_eq!(ids(&missed), vec![1]);
+
+        let (tx, _) = broadcast::channel(8);
+        log.spawn_recorder(tx.subscribe());
+        tx.send(log_line("after")).unwrap();
+
+        let (id, broadcast) = live.recv().await.unwrap();
+        assert_eq!(id, 2);
+        assert_eq!(broadcast, log_line("after"));
+    }
+}
@@ -3,14 +3,30 @@
 //! Resources provide grounding for agents to understand context. They're curated
 //! views into session state, not exhaustive listings of all data.
 
-use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceContents};
+use rmcp::model::{
+    AnnotateAble, Content, RawResource, RawResourceTemplate, Resource, ResourceContents,
+};
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::debug;
 
 use crate::backend::BackendPool;
-use hooteproto::{request::ToolRequest, Payload};
+use hooteproto::responses::ArtifactInfoResponse;
+use hooteproto::{request::ToolRequest, Payload, ResponseEnvelope, ToolResponse};
+
+/// Single artifact by ID.
+pub const ARTIFACT_TEMPLATE: &str = "holler://artifact/{id}";
+
+/// SoundFont details by CAS hash.
+pub const SOUNDFONT_TEMPLATE: &str = "holler://soundfont/{hash}";
+
+/// Most artifact links attached to one tool result.
+const MAX_ARTIFACT_LINKS: usize = 16;
+
+/// Templates `read` tries, in order, after the static resources.
+const TEMPLATES: &[&str] = &[ARTIFACT_TEMPLATE, SOUNDFONT_TEMPLATE];
 
 /// Registry of available MCP resources.
 pub struct ResourceRegistry {
@@ -98,7 +114,7 @@ impl ResourceRegistry {
     pub fn list_resource_templates() -> Vec<rmcp::model::ResourceTemplate> {
         vec![
             RawResourceTemplate {
-                uri_template: "holler://artifact/{id}".into(),
+                uri_template: ARTIFACT_TEMPLATE.into(),
                 name: "Artifact".into(),
                 title: Some("Single Artifact".into()),
                 description: Some(
@@ -109,7 +125,7 @@ impl ResourceRegistry {
             }
             .no_annotation(),
             RawResourceTemplate {
-                uri_template: "holler://soundfont/{hash}".into(),
+                uri_template: SOUNDFONT_TEMPLATE.into(),
                 name: "SoundFont Details".into(),
                 title: Some("SoundFont Details".into()),
                 description: Some(
@@ -132,19 +148,34 @@ impl ResourceRegistry {
             "holler://status" => self.read_status().await,
             "holler://garden/transport" => self.read_transport().await,
             "holler://garden/graph" => self.read_graph().await,
-            _ if uri.starts_with("holler://artifact/") => {
-                let id = uri
-                    .strip_prefix("holler://artifact/")
-                    .ok_or_else(|| ResourceError::NotFound(uri.to_string()))?;
-                self.read_artifact(id).await
-            }
-            _ if uri.starts_with("holler://soundfont/") => {
-                let hash = uri
-                    .strip_prefix("holler://soundfont/")
-                    .ok_or_else(|| ResourceError::NotFound(uri.to_string()))?;
-                self.read_soundfont(hash).await
+            _ => {
+                for template in TEMPLATES {
+                    if let Some(params) = UriTemplate::new(template).matches(uri) {
+                        return self.read_templated_resource(template, &params).await;
+                    }
+                }
+                Err(ResourceError::NotFound(uri.to_string()))
             }
-            _ => Err(ResourceError::NotFound(uri.to_string())),
+        }
+    }
+
+    /// Read a parameterized resource, given the template it matched and the
+    /// parameters extracted from the URI.
+    pub async fn read_templated_resource(
+        &self,
+        template: &str,
+        params: &HashMap<String, String>,
+    ) -> Result<String, ResourceError> {
+        let param = |name: &str| {
+            params.get(name).ok_or_else(|| {
+                ResourceError::NotFound(format!("{} (missing {{{}}})", template, name))
+            })
+        };
+
+        match template {
+            ARTIFACT_TEMPLATE => self.read_artifact(param("id")?).await,
+            SOUNDFONT_TEMPLATE => self.read_soundfont(param("hash")?).await,
+            _ => Err(ResourceError::NotFound(template.to_string())),
         }
     }
 
@@ -219,6 +250,39 @@ impl ResourceRegistry {
         self.execute_tool(request).await
     }
 
+    /// Most recent artifacts, optionally only those carrying `tag`.
+    ///
+    /// Typed rather than rendered JSON, for completion suggestions.
+    pub async fn list_artifacts(
+        &self,
+        tag: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ArtifactInfoResponse>, ResourceError> {
+        let request = ToolRequest::ArtifactList(hooteproto::request::ArtifactListRequest {
+            tag: tag.map(str::to_string),
+            creator: None,
+            limit: Some(limit),
+        });
+        let name = request.name();
+        let backend = self
+            .backends
+            .read()
+            .await
+            .route_tool(name)
+            .ok_or_else(|| ResourceError::BackendUnavailable(name.to_string()))?;
+
+        match backend.request(Payload::ToolRequest(request)).await {
+            Ok(Payload::TypedResponse(ResponseEnvelope::Success {
+                response: ToolResponse::ArtifactList(list),
+            })) => Ok(list.artifacts),
+            Ok(other) => Err(ResourceError::Internal(format!(
+                "Unexpected response: {:?}",
+                other
+            ))),
+            Err(e) => Err(ResourceError::BackendError(e.to_string())),
+        }
+    }
+
     /// Execute a tool request against the backend.
     async fn execute_tool(&self, request: ToolRequest) -> Result<String, ResourceError> {
         let name = request.name();
@@ -253,6 +317,95 @@ impl ResourceRegistry {
     }
 }
 
+/// A resource URI template using RFC 6570 level 1 (simple `{var}`) syntax.
+///
+/// Matching is the inverse of expansion: each variable captures the
+/// non-empty text up to the next literal, and may not span a `/`. Values
+/// are returned as they appear in the URI, without percent-decoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    Var(String),
+}
+
+impl UriTemplate {
+    /// Parse a template. An unterminated `{` is treated as literal text.
+    pub fn new(template: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}') else {
+                break;
+            };
+            if open > 0 {
+                parts.push(TemplatePart::Literal(rest[..open].to_string()));
+            }
+            parts.push(TemplatePart::Var(rest[open + 1..open + close].to_string()));
+            rest = &rest[open + close + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Literal(rest.to_string()));
+        }
+
+        Self { parts }
+    }
+
+    /// Variable names, in template order.
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            TemplatePart::Var(name) => Some(name.as_str()),
+            TemplatePart::Literal(_) => None,
+        })
+    }
+
+    /// Match a concrete URI, returning the value of each variable.
+    pub fn matches(&self, uri: &str) -> Option<HashMap<String, String>> {
+        let mut params = HashMap::new();
+        let mut rest = uri;
+
+        for (i, part) in self.parts.iter().enumerate() {
+            match part {
+                TemplatePart::Literal(text) => {
+                    rest = rest.strip_prefix(text.as_str())?;
+                }
+                TemplatePart::Var(name) => {
+                    let end = match self.parts.get(i + 1) {
+                        Some(TemplatePart::Literal(next)) => rest.find(next.as_str())?,
+                        // Adjacent variables have no delimiter to split on
+                        Some(TemplatePart::Var(_)) => return None,
+                        None => rest.len(),
+                    };
+                    let value = &rest[..end];
+                    if value.is_empty() || value.contains('/') {
+                        return None;
+                    }
+                    params.insert(name.clone(), value.to_string());
+                    rest = &rest[end..];
+                }
+            }
+        }
+
+        rest.is_empty().then_some(params)
+    }
+
+    /// Substitute variables; missing ones expand to the empty string.
+    pub fn expand(&self, params: &HashMap<String, String>) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.as_str(),
+                TemplatePart::Var(name) => params.get(name).map_or("", String::as_str),
+            })
+            .collect()
+    }
+}
+
 /// Resource reading errors.
 #[derive(Debug)]
 pub enum ResourceError {
@@ -289,3 +442,137 @@ impl std::error::Error for ResourceError {}
 pub fn text_resource_contents(text: String, uri: &str) -> ResourceContents {
     ResourceContents::text(text, uri.to_string())
 }
+
+/// Resource links to the artifacts a tool result names.
+///
+/// Picks up `artifact_id` fields and `artifact_ids` arrays at any depth, in
+/// order and without duplicates. Each link points at the artifact template,
+/// so a client can follow it with `resources/read` instead of holler
+/// inlining the content.
+pub fn artifact_links(result: &serde_json::Value) -> Vec<Content> {
+    let mut ids = Vec::new();
+    collect_artifact_ids(result, &mut ids);
+    let template = UriTemplate::new(ARTIFACT_TEMPLATE);
+    ids.into_iter()
+        .take(MAX_ARTIFACT_LINKS)
+        .map(|id| {
+            let params = HashMap::from([("id".to_string(), id.clone())]);
+            let mut link = RawResource::new(template.expand(&params), id);
+            link.mime_type = Some("application/json".into());
+            Content::resource_link(link)
+        })
+        .collect()
+}
+
+fn collect_artifact_ids(value: &serde_json::Value, ids: &mut Vec<String>) {
+    let mut push = |id: &str| {
+        if !ids.iter().any(|seen| seen == id) {
+            ids.push(id.to_string());
+        }
+    };
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(id) = map.get("artifact_id").and_then(|v| v.as_str()) {
+                push(id);
+            }
+            if let Some(list) = map.get("artifact_ids").and_then(|v| v.as_array()) {
+                list.iter().filter_map(|v| v.as_str()).for_each(&mut push);
+            }
+            for v in map.values() {
+                collect_artifact_ids(v, ids);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_artifact_ids(item, ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_extracts_params() {
+        let template = UriTemplate::new("artifact://{id}");
+        let params = template.matches("artifact://abc123").unwrap();
+        assert_eq!(params.get("id").map(String::as_str), Some("abc123"));
+        assert_eq!(template.variables().collect::<Vec<_>>(), vec!["id"]);
+
+        assert!(template.matches("artifact://").is_none());
+        assert!(template.matches("artifact://abc/123").is_none());
+        assert!(template.matches("cas://abc123").is_none());
+    }
+
+    #[test]
+    fn test_template_with_multiple_vars() {
+        let template = UriTemplate::new("holler://garden/{node}/param/{name}.json");
+        let params = template
+            .matches("holler://garden/osc-1/param/cutoff.json")
+            .unwrap();
+        assert_eq!(params["node"], "osc-1");
+        assert_eq!(params["name"], "cutoff");
+        assert_eq!(
+            template.expand(&params),
+            "holler://garden/osc-1/param/cutoff.json"
+        );
+
+        assert!(template
+            .matches("holler://garden/osc-1/param/cutoff")
+            .is_none());
+        assert!(UriTemplate::new("x://{a}{b}").matches("x://ab").is_none());
+    }
+
+    #[test]
+    fn test_registered_templates_match() {
+        let params = UriTemplate::new(ARTIFACT_TEMPLATE)
+            .matches("holler://artifact/artifact_42")
+            .unwrap();
+        assert_eq!(params["id"], "artifact_42");
+
+        let params = UriTemplate::new(SOUNDFONT_TEMPLATE)
+            .matches("holler://soundfont/deadbeef")
+            .unwrap();
+        assert_eq!(params["hash"], "deadbeef");
+
+        assert!(UriTemplate::new(ARTIFACT_TEMPLATE)
+            .matches("holler://soundfont/deadbeef")
+            .is_none());
+    }
+
+    #[test]
+    fn test_artifact_links() {
+        let result = serde_json::json!({
+            "artifact_id": "artifact_a",
+            "stems": [
+                { "artifact_id": "artifact_b" },
+                { "artifact_id": "artifact_a" }
+            ],
+            "job": { "artifact_ids": ["artifact_c", "artifact_b"] },
+            "id": "artifact_not_linked"
+        });
+        let uris: Vec<String> = artifact_links(&result)
+            .iter()
+            .map(|c| match &c.raw {
+                rmcp::model::RawContent::ResourceLink(link) => link.uri.clone(),
+                other => panic!("expected a resource link, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(uris[0], "holler://artifact/artifact_a");
+        let mut sorted = uris.clone();
+        sorted.sort();
+        assert_eq!(
+            sorted,
+            vec![
+                "holler://artifact/artifact_a",
+                "holler://artifact/artifact_b",
+                "holler://artifact/artifact_c",
+            ]
+        );
+
+        assert!(artifact_links(&serde_json::json!({ "status": "ok" })).is_empty());
+    }
+}
@@ -9,21 +9,43 @@
 //! - Services can start in any order
 
 use anyhow::{Context, Result};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::{routing::get, Router};
+use futures::Stream;
+use hooteproto::Broadcast;
 use rmcp::transport::streamable_http_server::{
     StreamableHttpServerConfig, StreamableHttpService,
     session::local::LocalSessionManager,
 };
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, watch, RwLock};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::backend::BackendPool;
-use crate::handler::{new_tool_cache, refresh_tools_into, ZmqHandler};
+use crate::batch::BatchService;
+use crate::handler::{new_tool_cache, refresh_tools_into, ToolCache, ZmqHandler};
+use crate::inflight::InFlightCalls;
+use crate::intercept;
+use crate::replay::EventLog;
 use crate::subscriber::spawn_subscribers;
 
+/// Interval between SSE keep-alive comments, well under common proxy idle timeouts
+const SSE_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Broadcasts kept for `/events` clients resuming with `Last-Event-Id`
+const EVENT_REPLAY_CAPACITY: usize = 256;
+
+/// How long shutdown waits for in-flight tool calls before cancelling them
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Server configuration
 ///
 /// Holler connects only to hootenanny, which proxies to vibeweaver and chaosgarden.
@@ -35,6 +57,12 @@ pub struct ServeConfig {
     pub hootenanny_pub: Option<String>,
     /// Request timeout in milliseconds (should be > inner service timeouts)
     pub timeout_ms: u64,
+    /// Deadline for a whole tool call, for tools whose timing class sets none
+    pub tool_timeout_ms: u64,
+    /// Most items per page of an MCP list response
+    pub list_page_size: usize,
+    /// Per-session limit on tool calls (off unless enabled)
+    pub rate_limit: hooteconf::infra::RateLimitConfig,
     /// Only expose DAW tools (sample, extend, analyze, bridge, project, schedule)
     pub daw_only: bool,
     /// Base URL for artifact access (e.g., "http://localhost:8082")
@@ -49,23 +77,91 @@ pub struct ServeConfig {
 pub struct HealthState {
     pub backends: Arc<RwLock<BackendPool>>,
     pub start_time: Instant,
+    /// Cached tool list (before any DAW-only filtering)
+    pub tools: ToolCache,
+    /// Running tool calls, and whether shutdown has begun
+    pub in_flight: InFlightCalls,
+    /// Open MCP sessions
+    pub sessions: Arc<LocalSessionManager>,
+    /// MCP sessions started since holler came up
+    pub sessions_created: Arc<AtomicU64>,
 }
 
 /// Health check endpoint
+///
+/// Answers 503 once shutdown has begun so load balancers stop routing new
+/// sessions here; a degraded backend still answers 200, since holler keeps
+/// serving the cached tool list and failing calls fast.
 pub async fn handle_health(
     axum::extract::State(state): axum::extract::State<HealthState>,
-) -> axum::Json<serde_json::Value> {
+) -> (StatusCode, axum::Json<serde_json::Value>) {
     let uptime = state.start_time.elapsed();
     let backends = state.backends.read().await;
     let backends_health = backends.health().await;
     let all_alive = backends.all_alive();
+    let draining = state.in_flight.is_draining();
 
-    axum::Json(serde_json::json!({
-        "status": if all_alive { "healthy" } else { "degraded" },
+    let (code, status) = match (draining, all_alive) {
+        (true, _) => (StatusCode::SERVICE_UNAVAILABLE, "draining"),
+        (false, true) => (StatusCode::OK, "healthy"),
+        (false, false) => (StatusCode::OK, "degraded"),
+    };
+    let body = serde_json::json!({
+        "status": status,
         "uptime_secs": uptime.as_secs(),
         "version": env!("CARGO_PKG_VERSION"),
         "backends": backends_health,
-    }))
+        "tools": state.tools.read().await.len(),
+        "in_flight_calls": state.in_flight.active(),
+        "sessions": {
+            "active": state.sessions.sessions.read().await.len(),
+            "created": state.sessions_created.load(Ordering::Relaxed),
+        },
+    });
+    (code, axum::Json(body))
+}
+
+/// SSE stream of backend broadcasts
+///
+/// Each broadcast is sent as a JSON `data:` event (tagged by its `type`
+/// field) with a numeric `id:`. A reconnect carrying `Last-Event-Id` first
+/// gets the buffered events it missed (see [`crate::replay`]). Idle streams
+/// get a comment every [`SSE_KEEPALIVE_INTERVAL`].
+pub async fn handle_events(
+    axum::extract::State(events): axum::extract::State<EventLog>,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let last_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    let (missed, live) = events.resume(last_id);
+    if !missed.is_empty() {
+        debug!("Replaying {} missed broadcasts to SSE client", missed.len());
+    }
+
+    let live = BroadcastStream::new(live).filter_map(|item| match item {
+        Ok(numbered) => Some(numbered),
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            warn!("SSE client lagged, skipped {} broadcasts", skipped);
+            None
+        }
+    });
+    let stream = tokio_stream::iter(missed)
+        .chain(live)
+        .filter_map(|(id, broadcast)| {
+            Event::default()
+                .id(id.to_string())
+                .json_data(&broadcast)
+                .ok()
+                .map(Ok)
+        });
+
+    Sse::new(stream).keep_alive(
+        KeepAlive::new()
+            .interval(SSE_KEEPALIVE_INTERVAL)
+            .text("keep-alive"),
+    )
 }
 
 /// Run the MCP gateway server
@@ -108,10 +204,13 @@ pub async fn run(config: ServeConfig) -> Result<()> {
 
     // Spawn health task for hootenanny with connect callback
     {
-        let backends_guard = backends.read().await;
+        let mut backends_guard = backends.write().await;
         backends_guard.spawn_health_task(shutdown_tx.subscribe(), Some(on_connected));
     }
 
+    // Backend liveness for the broadcast subscriber (SUB sockets can't tell)
+    let (backend_alive_tx, backend_alive_rx) = watch::channel(true);
+
     // Spawn periodic recreation check - recovers Dead connections
     {
         let backends_for_recreation = Arc::clone(&backends);
@@ -119,9 +218,15 @@ pub async fn run(config: ServeConfig) -> Result<()> {
             let mut interval = tokio::time::interval(Duration::from_secs(10));
             loop {
                 interval.tick().await;
-                let needs_recreation = {
-                    backends_for_recreation.read().await.needs_recreation()
+                let (needs_recreation, alive) = {
+                    let backends = backends_for_recreation.read().await;
+                    (backends.needs_recreation(), backends.all_alive())
                 };
+                backend_alive_tx.send_if_modified(|was_alive| {
+                    let changed = *was_alive != alive;
+                    *was_alive = alive;
+                    changed
+                });
                 if needs_recreation {
                     warn!("Backend marked dead, attempting recreation");
                     let mut backends_mut = backends_for_recreation.write().await;
@@ -147,16 +252,17 @@ pub async fn run(config: ServeConfig) -> Result<()> {
         });
     }
 
-    // Spawn ZMQ SUB subscriber for hootenanny broadcasts
+    // Spawn ZMQ SUB subscriber for hootenanny broadcasts (forwarded to /events)
+    let (broadcast_tx, _) = broadcast::channel::<Broadcast>(256);
     if let Some(ref hootenanny_pub) = config.hootenanny_pub {
         info!(
             "   Subscribing to Hootenanny broadcasts at {}",
             hootenanny_pub
         );
-        let (broadcast_tx, _) = tokio::sync::broadcast::channel::<hooteproto::Broadcast>(256);
         spawn_subscribers(
-            broadcast_tx,
+            broadcast_tx.clone(),
             Some(hootenanny_pub.clone()),
+            Some(backend_alive_rx),
             None, // chaosgarden_pub - direct connection removed
         );
     }
@@ -164,20 +270,40 @@ pub async fn run(config: ServeConfig) -> Result<()> {
     // Create cancellation token for graceful shutdown
     let cancel_token = CancellationToken::new();
 
+    // Tool calls in progress, drained before the listener goes away
+    let in_flight = InFlightCalls::new();
+
     // Create MCP service using rmcp's StreamableHttpService
     // The service factory creates a fresh handler for each session
     let backends_for_factory = Arc::clone(&backends);
     let cache_for_factory = tool_cache.clone();
+    let broadcasts_for_factory = broadcast_tx.clone();
     let daw_only = config.daw_only;
     let artifact_base_url = config.artifact_base_url.clone();
+    let in_flight_for_factory = in_flight.clone();
+    let call_timeout = Duration::from_millis(config.tool_timeout_ms);
+    let page_size = config.list_page_size;
+    // One interceptor for every session, so limits hold across them
+    let interceptor = intercept::from_config(&config.rate_limit);
+    let sessions = Arc::new(LocalSessionManager::default());
+    let sessions_created = Arc::new(AtomicU64::new(0));
+    let sessions_created_for_factory = Arc::clone(&sessions_created);
     let service = StreamableHttpService::new(
-        move || Ok(ZmqHandler::with_shared_cache(
-            Arc::clone(&backends_for_factory),
-            cache_for_factory.clone(),
-            daw_only,
-            artifact_base_url.clone(),
-        )),
-        LocalSessionManager::default().into(),
+        move || {
+            sessions_created_for_factory.fetch_add(1, Ordering::Relaxed);
+            Ok(ZmqHandler::with_shared_cache(
+                Arc::clone(&backends_for_factory),
+                cache_for_factory.clone(),
+                daw_only,
+                artifact_base_url.clone(),
+            )
+            .with_broadcasts(broadcasts_for_factory.clone())
+            .with_in_flight(in_flight_for_factory.clone())
+            .with_call_timeout(call_timeout)
+            .with_page_size(page_size)
+            .with_interceptor(Arc::clone(&interceptor)))
+        },
+        Arc::clone(&sessions),
         StreamableHttpServerConfig {
             cancellation_token: cancel_token.child_token(),
             stateful_mode: true, // Full MCP protocol for clients that support it
@@ -189,6 +315,10 @@ pub async fn run(config: ServeConfig) -> Result<()> {
     let health_state = HealthState {
         backends: Arc::clone(&backends),
         start_time: Instant::now(),
+        tools: tool_cache.clone(),
+        in_flight: in_flight.clone(),
+        sessions,
+        sessions_created,
     };
 
     // Build router - nest MCP service at /mcp, add health endpoint
@@ -196,9 +326,16 @@ pub async fn run(config: ServeConfig) -> Result<()> {
         .route("/health", get(handle_health))
         .with_state(health_state);
 
+    let events = EventLog::new(EVENT_REPLAY_CAPACITY);
+    events.spawn_recorder(broadcast_tx.subscribe());
+    let events_router = Router::new()
+        .route("/events", get(handle_events))
+        .with_state(events);
+
     let app = Router::new()
-        .nest_service("/mcp", service)
-        .merge(health_router);
+        .nest_service("/mcp", BatchService::new(service))
+        .merge(health_router)
+        .merge(events_router);
 
     // Bind and serve
     let addr = format!("127.0.0.1:{}", config.port);
@@ -223,12 +360,13 @@ pub async fn run(config: ServeConfig) -> Result<()> {
         info!("🔐 Holler ready with TLS!");
         info!("   MCP (Streamable): POST https://{}/mcp", addr);
         info!("   Health: GET https://{}/health", addr);
+        info!("   Events (SSE): GET https://{}/events", addr);
 
         // Use Handle for graceful shutdown with axum_server
         let handle = axum_server::Handle::new();
         let shutdown_handle = handle.clone();
         tokio::spawn(async move {
-            shutdown_signal(cancel_token).await;
+            shutdown_signal(cancel_token, in_flight).await;
             shutdown_handle.graceful_shutdown(None);
         });
 
@@ -245,9 +383,10 @@ pub async fn run(config: ServeConfig) -> Result<()> {
         info!("🎺 Holler ready!");
         info!("   MCP (Streamable): POST http://{}/mcp", addr);
         info!("   Health: GET http://{}/health", addr);
+        info!("   Events (SSE): GET http://{}/events", addr);
 
         axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown_signal(cancel_token))
+            .with_graceful_shutdown(shutdown_signal(cancel_token, in_flight))
             .await
             .context("Server error")?;
     }
@@ -256,7 +395,8 @@ pub async fn run(config: ServeConfig) -> Result<()> {
     Ok(())
 }
 
-async fn shutdown_signal(cancel_token: CancellationToken) {
+/// Wait for SIGINT/SIGTERM, let running tool calls finish, then cancel.
+async fn shutdown_signal(cancel_token: CancellationToken, in_flight: InFlightCalls) {
     tokio::select! {
         _ = tokio::signal::ctrl_c() => {
             info!("Received SIGINT, shutting down...");
@@ -276,5 +416,17 @@ async fn shutdown_signal(cancel_token: CancellationToken) {
             info!("Received SIGTERM, shutting down...");
         }
     }
+
+    let active = in_flight.active();
+    if active > 0 {
+        info!("Draining {} in-flight tool calls...", active);
+    }
+    if !in_flight.drain(SHUTDOWN_DRAIN_TIMEOUT).await {
+        warn!(
+            "{} tool calls still running after {:?}, cancelling",
+            in_flight.active(),
+            SHUTDOWN_DRAIN_TIMEOUT
+        );
+    }
     cancel_token.cancel();
 }
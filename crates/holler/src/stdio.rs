@@ -4,14 +4,16 @@
 //! Both stdio and stateful HTTP support server-initiated notifications/push.
 
 use anyhow::{Context, Result};
-use rmcp::{transport::stdio, ServiceExt};
+use rmcp::ServiceExt;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
 use crate::backend::BackendPool;
+use crate::batch;
 use crate::handler::{new_tool_cache, refresh_tools_into, ZmqHandler};
+use crate::intercept;
 
 /// Configuration for stdio MCP server
 pub struct StdioConfig {
@@ -19,6 +21,12 @@ pub struct StdioConfig {
     pub hootenanny: String,
     /// Request timeout in milliseconds
     pub timeout_ms: u64,
+    /// Deadline for a whole tool call, for tools whose timing class sets none
+    pub tool_timeout_ms: u64,
+    /// Most items per page of an MCP list response
+    pub list_page_size: usize,
+    /// Per-session limit on tool calls (off unless enabled)
+    pub rate_limit: hooteconf::infra::RateLimitConfig,
     /// Only expose DAW tools (sample, extend, analyze, bridge, project, schedule)
     pub daw_only: bool,
 }
@@ -59,7 +67,7 @@ pub async fn run(config: StdioConfig) -> Result<()> {
 
     // Spawn health task for hootenanny with connect callback
     {
-        let backends_guard = backends.read().await;
+        let mut backends_guard = backends.write().await;
         backends_guard.spawn_health_task(shutdown_tx.subscribe(), Some(on_connected));
     }
 
@@ -123,11 +131,14 @@ pub async fn run(config: StdioConfig) -> Result<()> {
 
     // Create handler with shared cache and daw_only filter
     // Note: artifact_base_url is None for stdio mode (no HTTP access)
-    let handler = ZmqHandler::with_shared_cache(Arc::clone(&backends), tool_cache, config.daw_only, None);
+    let handler = ZmqHandler::with_shared_cache(Arc::clone(&backends), tool_cache, config.daw_only, None)
+        .with_call_timeout(Duration::from_millis(config.tool_timeout_ms))
+        .with_page_size(config.list_page_size)
+        .with_interceptor(intercept::from_config(&config.rate_limit));
 
-    // Serve via stdio - rmcp handles JSON-RPC framing
+    // Serve via stdio - rmcp handles JSON-RPC framing, batches are split first
     let service = handler
-        .serve(stdio())
+        .serve(batch::stdio_relay(tokio::io::stdin(), tokio::io::stdout()))
         .await
         .context("Failed to start stdio MCP service")?;
 
@@ -5,11 +5,18 @@
 
 use anyhow::{Context as AnyhowContext, Result};
 use futures::StreamExt;
-use hooteproto::socket_config::{create_subscriber_and_connect, ZmqContext};
-use hooteproto::{broadcast_capnp, capnp_to_broadcast, Broadcast};
-use tokio::sync::broadcast;
+use hooteproto::socket_config::{create_subscriber_and_connect, Multipart, ZmqContext};
+use hooteproto::{broadcast_capnp, capnp_to_broadcast, Broadcast, LazyPirateConfig};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch};
 use tracing::{debug, error, info, warn};
 
+/// Initial delay before recreating a lost SUB socket
+const RECONNECT_BACKOFF_BASE: Duration = Duration::from_millis(250);
+
+/// Cap on the delay between SUB socket recreations
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(10);
+
 /// Configuration for a PUB/SUB subscription
 #[derive(Debug, Clone)]
 pub struct SubscriberConfig {
@@ -17,63 +24,163 @@ pub struct SubscriberConfig {
     pub name: String,
     /// ZMQ PUB endpoint to subscribe to
     pub endpoint: String,
+    /// Backend liveness from the ROUTER heartbeat (`false` = peer dead).
+    ///
+    /// SUB sockets never report a dead peer, so this is how a silent link
+    /// gets noticed. Without it, only socket errors trigger a reconnect.
+    pub liveness: Option<watch::Receiver<bool>>,
 }
 
 /// Subscribe to a backend's PUB socket and forward broadcasts
+///
+/// Runs for the life of the process. When the link is lost (socket
+/// error, stream end, or liveness going false) the socket is recreated with
+/// Lazy Pirate backoff, and a synthetic `Broadcast::Log` tells SSE clients
+/// that the link dropped and, later, that it recovered.
 pub async fn subscribe_to_backend(
     config: SubscriberConfig,
     broadcast_tx: broadcast::Sender<Broadcast>,
 ) -> Result<()> {
+    let SubscriberConfig {
+        name,
+        endpoint,
+        mut liveness,
+    } = config;
+    let backoff = LazyPirateConfig {
+        backoff_base: RECONNECT_BACKOFF_BASE,
+        backoff_max: RECONNECT_BACKOFF_MAX,
+        ..Default::default()
+    };
     let context = ZmqContext::new();
-    let mut socket =
-        create_subscriber_and_connect(&context, &config.endpoint, &config.name)?;
-
-    info!(
-        "Subscribed to {} broadcasts at {}",
-        config.name, config.endpoint
-    );
+    let mut attempt = 0u32;
+    let mut link_down = false;
 
     loop {
-        match socket.next().await {
-            Some(Ok(multipart)) => {
-                // The multipart message should have one frame: the Cap'n Proto broadcast
-                for msg in multipart {
-                    let bytes: &[u8] = msg.as_ref();
-                    if bytes.is_empty() {
-                        continue;
-                    }
+        if attempt > 0 {
+            let delay = backoff.backoff_for_attempt(attempt);
+            debug!("Reconnecting {} SUB in {:?} (attempt {})", name, delay, attempt);
+            tokio::time::sleep(delay).await;
+        }
+
+        // No point resubscribing while heartbeats say the backend is gone
+        wait_for_backend(&mut liveness).await;
+
+        let mut socket = match create_subscriber_and_connect(&context, &endpoint, &name) {
+            Ok(socket) => socket,
+            Err(e) => {
+                warn!("Failed to create {} SUB socket: {}", name, e);
+                attempt = attempt.saturating_add(1);
+                continue;
+            }
+        };
+
+        info!("Subscribed to {} broadcasts at {}", name, endpoint);
+        if link_down {
+            link_down = false;
+            announce_link(&broadcast_tx, "info", format!("{} broadcast link restored", name));
+        }
 
-                    // Parse Cap'n Proto broadcast
-                    match parse_capnp_broadcast(bytes) {
-                        Ok(broadcast) => {
-                            debug!("Received {} broadcast: {:?}", config.name, broadcast);
-                            if let Err(e) = broadcast_tx.send(broadcast) {
-                                debug!("No SSE clients connected: {}", e);
-                            }
-                        }
-                        Err(e) => {
-                            warn!(
-                                "Failed to parse broadcast from {}: {} ({} bytes)",
-                                config.name,
-                                e,
-                                bytes.len()
-                            );
-                        }
+        let connected_at = Instant::now();
+        let reason = loop {
+            tokio::select! {
+                msg = socket.next() => match msg {
+                    Some(Ok(multipart)) => forward_multipart(multipart, &name, &broadcast_tx),
+                    Some(Err(e)) => break format!("receive error: {}", e),
+                    None => break "stream ended".to_string(),
+                },
+                down = backend_down(&mut liveness) => {
+                    if down {
+                        break "backend heartbeat lost".to_string();
                     }
                 }
             }
-            Some(Err(e)) => {
-                error!("Error receiving from {} SUB socket: {}", config.name, e);
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        };
+
+        warn!("{} broadcast link lost ({}), reconnecting", name, reason);
+        link_down = true;
+        announce_link(
+            &broadcast_tx,
+            "warn",
+            format!("{} broadcast link lost: {}", name, reason),
+        );
+
+        // A link that flaps right after reconnecting backs off further
+        attempt = if connected_at.elapsed() < RECONNECT_BACKOFF_MAX {
+            attempt.saturating_add(1)
+        } else {
+            1
+        };
+    }
+}
+
+/// Forward each frame of a multipart message as a parsed broadcast
+fn forward_multipart(
+    multipart: Multipart,
+    name: &str,
+    broadcast_tx: &broadcast::Sender<Broadcast>,
+) {
+    // The multipart message should have one frame: the Cap'n Proto broadcast
+    for msg in multipart {
+        let bytes: &[u8] = msg.as_ref();
+        if bytes.is_empty() {
+            continue;
+        }
+
+        // Parse Cap'n Proto broadcast
+        match parse_capnp_broadcast(bytes) {
+            Ok(broadcast) => {
+                debug!("Received {} broadcast: {:?}", name, broadcast);
+                if let Err(e) = broadcast_tx.send(broadcast) {
+                    debug!("No SSE clients connected: {}", e);
+                }
             }
-            None => {
-                warn!("SUB socket stream ended for {}", config.name);
-                break;
+            Err(e) => {
+                warn!(
+                    "Failed to parse broadcast from {}: {} ({} bytes)",
+                    name,
+                    e,
+                    bytes.len()
+                );
             }
         }
     }
+}
+
+/// Send a link-status `Broadcast::Log` so the UI can show connectivity
+fn announce_link(broadcast_tx: &broadcast::Sender<Broadcast>, level: &str, message: String) {
+    let log = Broadcast::Log {
+        level: level.to_string(),
+        message,
+        source: "holler".to_string(),
+    };
+    if let Err(e) = broadcast_tx.send(log) {
+        debug!("No SSE clients connected: {}", e);
+    }
+}
+
+/// Wait until liveness reports the backend alive (immediately if untracked)
+async fn wait_for_backend(liveness: &mut Option<watch::Receiver<bool>>) {
+    if let Some(rx) = liveness {
+        if rx.wait_for(|alive| *alive).await.is_err() {
+            // Sender gone: stop gating on liveness
+            *liveness = None;
+        }
+    }
+}
 
-    Ok(())
+/// Resolve `true` once liveness reports the backend dead.
+///
+/// Never resolves when liveness is untracked; resolves `false` (once) if the
+/// liveness sender goes away.
+async fn backend_down(liveness: &mut Option<watch::Receiver<bool>>) -> bool {
+    let Some(rx) = liveness else {
+        return std::future::pending().await;
+    };
+    if rx.wait_for(|alive| !*alive).await.is_ok() {
+        return true;
+    }
+    *liveness = None;
+    false
 }
 
 /// Parse Cap'n Proto broadcast bytes into Broadcast enum
@@ -92,9 +199,13 @@ fn parse_capnp_broadcast(mut bytes: &[u8]) -> Result<Broadcast> {
 }
 
 /// Spawn subscriber tasks for all configured backends
+///
+/// `hootenanny_liveness` comes from the hootenanny heartbeat and lets the
+/// subscriber notice a dead PUB peer.
 pub fn spawn_subscribers(
     broadcast_tx: broadcast::Sender<Broadcast>,
     hootenanny_pub: Option<String>,
+    hootenanny_liveness: Option<watch::Receiver<bool>>,
     chaosgarden_pub: Option<String>,
 ) {
     if let Some(endpoint) = hootenanny_pub {
@@ -103,6 +214,7 @@ pub fn spawn_subscribers(
             let config = SubscriberConfig {
                 name: "hootenanny".to_string(),
                 endpoint,
+                liveness: hootenanny_liveness,
             };
             if let Err(e) = subscribe_to_backend(config, tx).await {
                 error!("Hootenanny subscriber failed: {}", e);
@@ -116,6 +228,7 @@ pub fn spawn_subscribers(
             let config = SubscriberConfig {
                 name: "chaosgarden".to_string(),
                 endpoint,
+                liveness: None,
             };
             if let Err(e) = subscribe_to_backend(config, tx).await {
                 error!("Chaosgarden subscriber failed: {}", e);
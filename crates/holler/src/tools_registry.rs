@@ -6,6 +6,10 @@
 
 use crate::manual_schemas;
 use hooteproto::ToolInfo;
+use rmcp::model::ToolAnnotations;
+
+/// Most "did you mean" suggestions offered for an unknown tool name.
+pub const MAX_SUGGESTIONS: usize = 3;
 
 /// List all tools supported by hootenanny
 pub fn list_tools() -> Vec<ToolInfo> {
@@ -678,7 +682,7 @@ pub fn list_tools() -> Vec<ToolInfo> {
                 "properties": {
                     "artifact_id": { "type": "string", "description": "Artifact ID of MIDI file" },
                     "hash": { "type": "string", "description": "CAS hash of MIDI file (alternative to artifact_id)" },
-                    "method": { "type": "string", "description": "Separation method: auto (default), channel_split, pitch_contiguity, skyline, bassline" },
+                    "method": { "type": "string", "description": "Separation method: auto (default), channel_split, pitch_contiguity, skyline, bassline, drum_kit" },
                     "max_pitch_jump": { "type": "integer", "description": "Max pitch jump in semitones before new voice (default 12)" },
                     "max_gap_beats": { "type": "number", "description": "Max gap in beats before voice is stale (default 4.0)" },
                     "max_voices": { "type": "integer", "description": "Max voices to extract per track (default 8)" },
@@ -862,3 +866,171 @@ pub fn list_tools() -> Vec<ToolInfo> {
         },
     ]
 }
+
+/// Tools that only report state and change nothing.
+const READ_ONLY_TOOLS: &[&str] = &[
+    "artifact_list",
+    "artifact_get",
+    "soundfont_inspect",
+    "job_list",
+    "job_poll",
+    "event_poll",
+    "abc_validate",
+    "status",
+    "garden_graph",
+    "time_convert",
+    "audio_output_status",
+    "audio_input_status",
+    "audio_list_devices",
+    "midi_list_ports",
+    "midi_status",
+    "timeline_region_list",
+    "storage_stats",
+    "midi_info",
+    "audio_info",
+    "rave_stream_status",
+    "help",
+];
+
+/// Tools whose effect can't be undone by another call.
+const DESTRUCTIVE_TOOLS: &[&str] = &[
+    "timeline_region_delete",
+    "timeline_clear",
+    "job_cancel",
+    "kernel_reset",
+];
+
+/// MCP behaviour hints for a tool.
+///
+/// Read-only tools are also idempotent. Generation tools aren't, even though
+/// the backend coalesces identical concurrent calls: each run makes a new
+/// artifact.
+pub fn annotations(name: &str) -> ToolAnnotations {
+    let read_only = READ_ONLY_TOOLS.contains(&name);
+    ToolAnnotations::new()
+        .read_only(read_only)
+        .destructive(DESTRUCTIVE_TOOLS.contains(&name))
+        .idempotent(read_only)
+}
+
+/// Registered tool names nearest to `name` by edit distance, closest first.
+///
+/// Names more than half the input's length away (minimum 2) are left out, so
+/// an unrelated word gets no suggestions rather than arbitrary ones.
+pub fn suggest_tools(name: &str, limit: usize) -> Vec<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 2).max(2);
+
+    let mut scored: Vec<(usize, String)> = list_tools()
+        .into_iter()
+        .map(|t| (edit_distance(&name, &t.name), t.name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    scored.sort();
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, tool)| tool)
+        .collect()
+}
+
+/// Error message for an unknown tool, naming the nearest registered tools.
+pub fn unknown_tool_message(name: &str) -> String {
+    let suggestions = suggest_tools(name, MAX_SUGGESTIONS);
+    match suggestions.as_slice() {
+        [] => format!("Unknown tool: {}. Call help for the tool index.", name),
+        [only] => format!("Unknown tool: {}. Did you mean {}?", name, only),
+        _ => format!(
+            "Unknown tool: {}. Did you mean one of: {}?",
+            name,
+            suggestions.join(", ")
+        ),
+    }
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitute.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "play"), 4);
+        assert_eq!(edit_distance("play", "play"), 0);
+        assert_eq!(edit_distance("midi_rendr", "midi_render"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_suggest_near_miss() {
+        let suggestions = suggest_tools("midi_rendr", MAX_SUGGESTIONS);
+        assert_eq!(suggestions.first().map(String::as_str), Some("midi_render"));
+        assert!(suggestions.len() <= MAX_SUGGESTIONS);
+
+        assert_eq!(suggest_tools("MIDI_RENDER", 1), vec!["midi_render"]);
+    }
+
+    #[test]
+    fn test_suggest_unrelated_is_empty() {
+        assert!(suggest_tools("reticulate_splines", MAX_SUGGESTIONS).is_empty());
+    }
+
+    #[test]
+    fn test_annotation_lists_name_registered_tools() {
+        let names: Vec<String> = list_tools().into_iter().map(|t| t.name).collect();
+        for tool in READ_ONLY_TOOLS.iter().chain(DESTRUCTIVE_TOOLS) {
+            assert!(
+                names.iter().any(|n| n == tool),
+                "{} is not registered",
+                tool
+            );
+            assert!(
+                !(READ_ONLY_TOOLS.contains(tool) && DESTRUCTIVE_TOOLS.contains(tool)),
+                "{} is both read-only and destructive",
+                tool
+            );
+        }
+    }
+
+    #[test]
+    fn test_annotations() {
+        let clear = annotations("timeline_clear");
+        assert_eq!(clear.destructive_hint, Some(true));
+        assert_eq!(clear.read_only_hint, Some(false));
+
+        let list = annotations("artifact_list");
+        assert_eq!(list.read_only_hint, Some(true));
+        assert_eq!(list.idempotent_hint, Some(true));
+        assert_eq!(list.destructive_hint, Some(false));
+
+        let generate = annotations("orpheus_generate");
+        assert_eq!(generate.idempotent_hint, Some(false));
+        assert_eq!(generate.destructive_hint, Some(false));
+    }
+
+    #[test]
+    fn test_unknown_tool_message() {
+        let message = unknown_tool_message("job_pol");
+        assert!(message.contains("Did you mean"), "{}", message);
+        assert!(message.contains("job_poll"), "{}", message);
+
+        let message = unknown_tool_message("reticulate_splines");
+        assert!(message.contains("help"), "{}", message);
+    }
+}
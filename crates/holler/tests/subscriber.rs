@@ -203,7 +203,7 @@ async fn test_subscriber_parses_artifact_created() {
     let broadcast_msg = Broadcast::ArtifactCreated {
         artifact_id: "art_abc123".to_string(),
         content_hash: "sha256_def456".to_string(),
-        tags: vec!["type:midi".to_string(), "vibe:jazzy".to_string()],
+        tags: vec!["type:midi".to_string(), "vibe:jazzy".to_string()].into(),
         creator: Some("claude".to_string()),
     };
 
@@ -1,8 +1,10 @@
 //! Bootstrap configuration - seeds runtime state, then runtime owns it.
 
+use crate::duration::HumanDuration;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Model service endpoints.
 ///
@@ -173,11 +175,17 @@ impl Default for ConnectionsConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaConfig {
     /// Directories to search for SoundFonts (.sf2, .sf3)
-    #[serde(default = "MediaConfig::default_soundfont_dirs")]
+    #[serde(
+        default = "MediaConfig::default_soundfont_dirs",
+        deserialize_with = "crate::loader::deserialize_path_list"
+    )]
     pub soundfont_dirs: Vec<PathBuf>,
 
     /// Directories to search for samples (.wav, .flac, etc)
-    #[serde(default = "MediaConfig::default_sample_dirs")]
+    #[serde(
+        default = "MediaConfig::default_sample_dirs",
+        deserialize_with = "crate::loader::deserialize_path_list"
+    )]
     pub sample_dirs: Vec<PathBuf>,
 }
 
@@ -221,29 +229,94 @@ impl Default for MediaConfig {
 pub struct DefaultsConfig {
     /// Lua script execution timeout
     #[serde(default = "DefaultsConfig::default_lua_timeout")]
-    pub lua_timeout: String,
+    pub lua_timeout: HumanDuration,
 
     /// Session expiration time
     #[serde(default = "DefaultsConfig::default_session_expiration")]
-    pub session_expiration: String,
+    pub session_expiration: HumanDuration,
 
     /// Maximum concurrent background jobs
     #[serde(default = "DefaultsConfig::default_max_concurrent_jobs")]
     pub max_concurrent_jobs: u32,
+
+    /// How often the GPU observer is sampled for scheduling history
+    #[serde(default = "DefaultsConfig::default_gpu_sample_interval")]
+    pub gpu_sample_interval: HumanDuration,
+
+    /// VRAM usage (percent of total) that triggers a broadcast warning
+    #[serde(default = "DefaultsConfig::default_gpu_vram_alert_pct")]
+    pub gpu_vram_alert_pct: f64,
+
+    /// How long a resolved latent region waits for approval (unset = forever)
+    #[serde(default)]
+    pub approval_timeout: Option<HumanDuration>,
+
+    /// What an unanswered latent approval resolves to once it times out
+    #[serde(default)]
+    pub approval_timeout_decision: ApprovalTimeoutDecision,
+}
+
+/// Decision applied to a latent approval nobody answered in time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalTimeoutDecision {
+    Approve,
+    #[default]
+    Reject,
+}
+
+impl std::str::FromStr for ApprovalTimeoutDecision {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "approve" => Ok(Self::Approve),
+            "reject" => Ok(Self::Reject),
+            other => Err(format!("expected \"approve\" or \"reject\", got {:?}", other)),
+        }
+    }
 }
 
 impl DefaultsConfig {
-    fn default_lua_timeout() -> String {
-        "30s".to_string()
+    /// Lua script execution timeout as a `Duration`.
+    pub fn lua_timeout(&self) -> Duration {
+        self.lua_timeout.as_duration()
     }
 
-    fn default_session_expiration() -> String {
-        "5m".to_string()
+    /// Session expiration time as a `Duration`.
+    pub fn session_expiration(&self) -> Duration {
+        self.session_expiration.as_duration()
+    }
+
+    /// GPU sampling interval as a `Duration`.
+    pub fn gpu_sample_interval(&self) -> Duration {
+        self.gpu_sample_interval.as_duration()
+    }
+
+    /// Latent approval timeout as a `Duration`, if one is set.
+    pub fn approval_timeout(&self) -> Option<Duration> {
+        self.approval_timeout.map(|t| t.as_duration())
+    }
+
+    fn default_lua_timeout() -> HumanDuration {
+        HumanDuration::from_secs(30)
+    }
+
+    fn default_session_expiration() -> HumanDuration {
+        HumanDuration::from_secs(5 * 60)
     }
 
     fn default_max_concurrent_jobs() -> u32 {
         4
     }
+
+    fn default_gpu_sample_interval() -> HumanDuration {
+        HumanDuration::from_secs(5)
+    }
+
+    fn default_gpu_vram_alert_pct() -> f64 {
+        90.0
+    }
 }
 
 impl Default for DefaultsConfig {
@@ -252,6 +325,10 @@ impl Default for DefaultsConfig {
             lua_timeout: Self::default_lua_timeout(),
             session_expiration: Self::default_session_expiration(),
             max_concurrent_jobs: Self::default_max_concurrent_jobs(),
+            gpu_sample_interval: Self::default_gpu_sample_interval(),
+            gpu_vram_alert_pct: Self::default_gpu_vram_alert_pct(),
+            approval_timeout: None,
+            approval_timeout_decision: ApprovalTimeoutDecision::default(),
         }
     }
 }
@@ -323,8 +400,15 @@ mod tests {
     #[test]
     fn test_defaults_config() {
         let defaults = DefaultsConfig::default();
-        assert_eq!(defaults.lua_timeout, "30s");
-        assert_eq!(defaults.session_expiration, "5m");
+        assert_eq!(defaults.lua_timeout(), Duration::from_secs(30));
+        assert_eq!(defaults.session_expiration(), Duration::from_secs(300));
+        assert_eq!(defaults.lua_timeout.to_string(), "30s");
+        assert_eq!(defaults.session_expiration.to_string(), "5m");
         assert_eq!(defaults.max_concurrent_jobs, 4);
+        assert_eq!(defaults.approval_timeout(), None);
+        assert_eq!(
+            defaults.approval_timeout_decision,
+            ApprovalTimeoutDecision::Reject
+        );
     }
 }
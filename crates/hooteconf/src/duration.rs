@@ -0,0 +1,166 @@
+//! Human-readable durations ("30s", "5m", "1h30m") for config values.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Error parsing a human-readable duration.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid duration {input:?}: {reason}")]
+pub struct DurationParseError {
+    pub input: String,
+    pub reason: &'static str,
+}
+
+/// A `Duration` written as `<number><unit>` components, e.g. `"1h30m"`.
+///
+/// Units: `ms`, `s`, `m`, `h`, `d`. Components may repeat in any order and are
+/// summed; a bare number without a unit is rejected as ambiguous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HumanDuration(Duration);
+
+impl HumanDuration {
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(Duration::from_secs(secs))
+    }
+
+    pub const fn as_duration(&self) -> Duration {
+        self.0
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(duration: HumanDuration) -> Self {
+        duration.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = DurationParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |reason| DurationParseError {
+            input: s.to_string(),
+            reason,
+        };
+
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Err(err("empty duration"));
+        }
+
+        let mut total = Duration::ZERO;
+        while !rest.is_empty() {
+            let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            if digits == 0 {
+                return Err(err("expected a number"));
+            }
+            let value: u64 = rest[..digits].parse().map_err(|_| err("number too large"))?;
+            rest = &rest[digits..];
+
+            let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+            let component = match &rest[..unit_len] {
+                "ms" => Duration::from_millis(value),
+                "s" => Duration::from_secs(value),
+                "m" => Duration::from_secs(value.saturating_mul(60)),
+                "h" => Duration::from_secs(value.saturating_mul(3600)),
+                "d" => Duration::from_secs(value.saturating_mul(86400)),
+                "" => return Err(err("missing unit (ms, s, m, h, d)")),
+                _ => return Err(err("unknown unit (expected ms, s, m, h, d)")),
+            };
+            rest = &rest[unit_len..];
+
+            total = total
+                .checked_add(component)
+                .ok_or_else(|| err("duration too large"))?;
+        }
+
+        Ok(Self(total))
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let millis = self.0.subsec_millis();
+        if secs == 0 && millis == 0 {
+            return write!(f, "0s");
+        }
+
+        let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+        if hours > 0 {
+            write!(f, "{}h", hours)?;
+        }
+        if minutes > 0 {
+            write!(f, "{}m", minutes)?;
+        }
+        if seconds > 0 {
+            write!(f, "{}s", seconds)?;
+        }
+        if millis > 0 {
+            write!(f, "{}ms", millis)?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for HumanDuration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HumanDuration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Duration, DurationParseError> {
+        s.parse::<HumanDuration>().map(|d| d.as_duration())
+    }
+
+    #[test]
+    fn test_parse_single_units() {
+        assert_eq!(parse("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(parse("1d").unwrap(), Duration::from_secs(86400));
+    }
+
+    #[test]
+    fn test_parse_compound() {
+        assert_eq!(parse("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse(" 1m30s500ms ").unwrap(), Duration::from_millis(90_500));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed() {
+        for bad in ["", "30", "30ss", "s", "5 m", "1.5s", "-3s", "10y"] {
+            assert!(parse(bad).is_err(), "{:?} should not parse", bad);
+        }
+        assert_eq!(parse("30ss").unwrap_err().input, "30ss");
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for s in ["0s", "30s", "5m", "1h30m", "2h5s", "1s250ms"] {
+            let parsed: HumanDuration = s.parse().unwrap();
+            assert_eq!(parsed.to_string(), s);
+        }
+    }
+}
@@ -1,25 +1,89 @@
 //! Infrastructure configuration - things that cannot change at runtime.
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 use std::path::PathBuf;
 
+/// Check that a ZMQ endpoint has the form `tcp://host:port` or `ipc://path`.
+///
+/// `*` is accepted as a wildcard host or port, as ZMQ allows when binding.
+pub fn validate_zmq_endpoint(endpoint: &str) -> Result<(), String> {
+    if let Some(addr) = endpoint.strip_prefix("tcp://") {
+        let (host, port) = addr
+            .rsplit_once(':')
+            .ok_or_else(|| format!("{:?} is missing a port (expected tcp://host:port)", endpoint))?;
+        if host.is_empty() {
+            return Err(format!("{:?} is missing a host (expected tcp://host:port)", endpoint));
+        }
+        if port != "*" {
+            let port: i64 = port
+                .parse()
+                .map_err(|_| format!("{:?} has a non-numeric port", endpoint))?;
+            validate_port(port).map_err(|e| format!("{:?}: {}", endpoint, e))?;
+        }
+        Ok(())
+    } else if let Some(path) = endpoint.strip_prefix("ipc://") {
+        if path.is_empty() {
+            return Err(format!("{:?} is missing a socket path", endpoint));
+        }
+        Ok(())
+    } else {
+        Err(format!("{:?} must be tcp://host:port or ipc://path", endpoint))
+    }
+}
+
+/// Check that a port number is usable (1-65535).
+pub fn validate_port(port: i64) -> Result<u16, String> {
+    match u16::try_from(port) {
+        Ok(port) if port != 0 => Ok(port),
+        _ => Err(format!("port {} is out of range (1-65535)", port)),
+    }
+}
+
+fn deserialize_zmq_endpoint<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let endpoint = String::deserialize(deserializer)?;
+    validate_zmq_endpoint(&endpoint).map_err(de::Error::custom)?;
+    Ok(endpoint)
+}
+
+fn deserialize_port<'de, D>(deserializer: D) -> Result<u16, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    validate_port(i64::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
 /// Filesystem paths for Hootenanny state and data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PathsConfig {
     /// Base directory for runtime state (sled databases, artifact store).
     /// Default: ~/.local/share/hootenanny
-    #[serde(default = "PathsConfig::default_state_dir")]
+    #[serde(
+        default = "PathsConfig::default_state_dir",
+        deserialize_with = "crate::loader::deserialize_path"
+    )]
     pub state_dir: PathBuf,
 
     /// Content-addressable storage directory.
     /// Default: ~/.hootenanny/cas
-    #[serde(default = "PathsConfig::default_cas_dir")]
+    #[serde(
+        default = "PathsConfig::default_cas_dir",
+        deserialize_with = "crate::loader::deserialize_path"
+    )]
     pub cas_dir: PathBuf,
 
     /// Directory for IPC sockets (chaosgarden).
     /// REQUIRED - must be set in config file. No default.
     /// Example: /tmp or /run/hootenanny
+    #[serde(default, deserialize_with = "crate::loader::deserialize_opt_path")]
     pub socket_dir: Option<PathBuf>,
+
+    /// SQLite file for persisting async jobs across restarts.
+    /// Default: none (jobs live in memory only)
+    #[serde(default, deserialize_with = "crate::loader::deserialize_opt_path")]
+    pub job_db: Option<PathBuf>,
 }
 
 impl PathsConfig {
@@ -57,6 +121,7 @@ impl Default for PathsConfig {
             state_dir: Self::default_state_dir(),
             cas_dir: Self::default_cas_dir(),
             socket_dir: None, // Must be explicitly configured
+            job_db: None,
         }
     }
 }
@@ -72,17 +137,23 @@ pub struct BindConfig {
 
     /// HTTP port for artifacts and health endpoints.
     /// Default: 8082
-    #[serde(default = "BindConfig::default_http_port")]
+    #[serde(default = "BindConfig::default_http_port", deserialize_with = "deserialize_port")]
     pub http_port: u16,
 
     /// ZMQ ROUTER address for hooteproto gateway.
     /// Default: tcp://127.0.0.1:5580
-    #[serde(default = "BindConfig::default_zmq_router")]
+    #[serde(
+        default = "BindConfig::default_zmq_router",
+        deserialize_with = "deserialize_zmq_endpoint"
+    )]
     pub zmq_router: String,
 
     /// ZMQ PUB address for event broadcasts.
     /// Default: tcp://127.0.0.1:5581
-    #[serde(default = "BindConfig::default_zmq_pub")]
+    #[serde(
+        default = "BindConfig::default_zmq_pub",
+        deserialize_with = "deserialize_zmq_endpoint"
+    )]
     pub zmq_pub: String,
 
     /// TLS configuration for HTTPS.
@@ -251,6 +322,49 @@ impl TlsConfig {
     }
 }
 
+/// Per-session rate limit on MCP tool calls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Enable the limit. Default: false (opt-in)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Calls a session may make back to back.
+    /// Default: 10
+    #[serde(default = "RateLimitConfig::default_burst")]
+    pub burst: u32,
+
+    /// Calls per second a session may sustain after the burst.
+    /// Default: 1.0
+    #[serde(default = "RateLimitConfig::default_per_second")]
+    pub per_second: f64,
+
+    /// Tools the limit applies to; empty limits every tool.
+    /// Default: empty
+    #[serde(default)]
+    pub tools: Vec<String>,
+}
+
+impl RateLimitConfig {
+    fn default_burst() -> u32 {
+        10
+    }
+
+    fn default_per_second() -> f64 {
+        1.0
+    }
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            burst: Self::default_burst(),
+            per_second: Self::default_per_second(),
+            tools: Vec::new(),
+        }
+    }
+}
 
 /// Gateway (holler) configuration.
 ///
@@ -259,17 +373,23 @@ impl TlsConfig {
 pub struct GatewayConfig {
     /// HTTP port for MCP and health endpoints.
     /// Default: 8080
-    #[serde(default = "GatewayConfig::default_http_port")]
+    #[serde(default = "GatewayConfig::default_http_port", deserialize_with = "deserialize_port")]
     pub http_port: u16,
 
     /// Hootenanny ZMQ ROUTER endpoint to connect to.
     /// Default: tcp://localhost:5580
-    #[serde(default = "GatewayConfig::default_hootenanny")]
+    #[serde(
+        default = "GatewayConfig::default_hootenanny",
+        deserialize_with = "deserialize_zmq_endpoint"
+    )]
     pub hootenanny: String,
 
     /// Hootenanny ZMQ PUB endpoint for broadcasts.
     /// Default: tcp://localhost:5581
-    #[serde(default = "GatewayConfig::default_hootenanny_pub")]
+    #[serde(
+        default = "GatewayConfig::default_hootenanny_pub",
+        deserialize_with = "deserialize_zmq_endpoint"
+    )]
     pub hootenanny_pub: String,
 
     /// Request timeout in milliseconds.
@@ -278,6 +398,21 @@ pub struct GatewayConfig {
     #[serde(default = "GatewayConfig::default_timeout_ms")]
     pub timeout_ms: u64,
 
+    /// Overall deadline for an MCP tool call, across retries.
+    /// Only for tools whose timing class (see `hooteproto::timing`) sets no deadline.
+    /// Default: 120000
+    #[serde(default = "GatewayConfig::default_tool_timeout_ms")]
+    pub tool_timeout_ms: u64,
+
+    /// Most items per page of an MCP list response (tools, resources, prompts).
+    /// Default: 100
+    #[serde(default = "GatewayConfig::default_list_page_size")]
+    pub list_page_size: usize,
+
+    /// Rate limit on tool calls, per MCP session.
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+
     /// TLS configuration for HTTPS.
     #[serde(default)]
     pub tls: TlsConfig,
@@ -299,6 +434,14 @@ impl GatewayConfig {
     fn default_timeout_ms() -> u64 {
         35_000
     }
+
+    fn default_tool_timeout_ms() -> u64 {
+        120_000
+    }
+
+    fn default_list_page_size() -> usize {
+        100
+    }
 }
 
 impl Default for GatewayConfig {
@@ -308,6 +451,9 @@ impl Default for GatewayConfig {
             hootenanny: Self::default_hootenanny(),
             hootenanny_pub: Self::default_hootenanny_pub(),
             timeout_ms: Self::default_timeout_ms(),
+            tool_timeout_ms: Self::default_tool_timeout_ms(),
+            list_page_size: Self::default_list_page_size(),
+            rate_limit: RateLimitConfig::default(),
             tls: TlsConfig::default(),
         }
     }
@@ -335,6 +481,11 @@ pub struct VibeweaverConfig {
     /// Default: 30000
     #[serde(default = "VibeweaverConfig::default_timeout_ms")]
     pub timeout_ms: u64,
+
+    /// Overall deadline for a tool call from Python, across retries.
+    /// Default: 120000
+    #[serde(default = "VibeweaverConfig::default_tool_timeout_ms")]
+    pub tool_timeout_ms: u64,
 }
 
 impl VibeweaverConfig {
@@ -353,6 +504,10 @@ impl VibeweaverConfig {
     fn default_timeout_ms() -> u64 {
         30000
     }
+
+    fn default_tool_timeout_ms() -> u64 {
+        120000
+    }
 }
 
 impl Default for VibeweaverConfig {
@@ -362,6 +517,7 @@ impl Default for VibeweaverConfig {
             hootenanny: Self::default_hootenanny(),
             hootenanny_pub: Self::default_hootenanny_pub(),
             timeout_ms: Self::default_timeout_ms(),
+            tool_timeout_ms: Self::default_tool_timeout_ms(),
         }
     }
 }
@@ -452,6 +608,37 @@ mod tests {
         assert!(paths.socket_dir.is_none());
     }
 
+    #[test]
+    fn test_paths_deserialize_expands() {
+        let paths: PathsConfig = toml::from_str(
+            r#"
+cas_dir = "~/cas"
+socket_dir = "/run/${HOOTECONF_TEST_NEVER_SET}hootenanny"
+"#,
+        )
+        .unwrap();
+        assert!(!paths.cas_dir.to_string_lossy().starts_with('~'));
+        assert!(paths.cas_dir.ends_with("cas"));
+        assert_eq!(paths.socket_dir, Some(PathBuf::from("/run/hootenanny")));
+    }
+
+    #[test]
+    fn test_validate_zmq_endpoint() {
+        for ok in ["tcp://0.0.0.0:5580", "tcp://localhost:5581", "tcp://*:*", "ipc:///tmp/x.sock"] {
+            assert!(validate_zmq_endpoint(ok).is_ok(), "{} should be valid", ok);
+        }
+        for bad in ["tcp//0.0.0.0:5580", "tcp://0.0.0.0", "tcp://:5580", "tcp://h:0", "ipc://"] {
+            assert!(validate_zmq_endpoint(bad).is_err(), "{} should be invalid", bad);
+        }
+    }
+
+    #[test]
+    fn test_bind_deserialize_rejects_bad_endpoint() {
+        let err = toml::from_str::<BindConfig>("zmq_router = \"tcp//0.0.0.0:5580\"").unwrap_err();
+        assert!(err.to_string().contains("tcp//0.0.0.0:5580"));
+        assert!(toml::from_str::<BindConfig>("http_port = 70000").is_err());
+    }
+
     #[test]
     fn test_require_socket_dir_missing() {
         let paths = PathsConfig::default();
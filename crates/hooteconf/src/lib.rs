@@ -31,13 +31,49 @@
 //! }
 //! ```
 //!
+//! # Reloading Bootstrap Values
+//!
+//! Bootstrap values can be re-read without a restart via
+//! [`HootConfig::reload_bootstrap`]. Binaries that want this keep the
+//! bootstrap in an `ArcSwap` and swap it from a SIGHUP handler:
+//!
+//! ```rust,ignore
+//! let bootstrap = Arc::new(ArcSwap::from_pointee(config.bootstrap.clone()));
+//!
+//! let reloadable = bootstrap.clone();
+//! tokio::spawn(async move {
+//!     let mut hup = signal(SignalKind::hangup()).expect("install SIGHUP handler");
+//!     while hup.recv().await.is_some() {
+//!         match config.reload_bootstrap() {
+//!             Ok(fresh) => reloadable.store(Arc::new(fresh)),
+//!             Err(e) => tracing::warn!("bootstrap reload failed, keeping old values: {}", e),
+//!         }
+//!     }
+//! });
+//!
+//! // Readers load the current snapshot per use, e.g. model endpoints:
+//! let orpheus = bootstrap.load().models.get("orpheus").cloned();
+//! ```
+//!
+//! Infra (paths, bind addresses, telemetry) is never reloaded.
+//!
 //! # Config File Locations
 //!
 //! Files are loaded in order (later wins):
 //! 1. `/etc/hootenanny/config.toml` (system)
 //! 2. `~/.config/hootenanny/config.toml` (user)
 //! 3. `./hootenanny.toml` (local override)
-//! 4. Environment variables (`HOOTENANNY_*`)
+//! 4. Profile overlays, when `HOOTENANNY_PROFILE=<name>` is set:
+//!    `~/.config/hootenanny/config.<name>.toml`, then `./hootenanny.<name>.toml`
+//!    (missing files are skipped)
+//! 5. Environment variables (`HOOTENANNY_*`)
+//!
+//! # Path Expansion
+//!
+//! Path fields (`[paths]`, `[bootstrap.media]`, TLS cert/key paths) expand a
+//! leading `~` to the home directory and `$VAR` / `${VAR}` from the
+//! environment. Unset variables expand to empty and are reported as
+//! warnings in [`ConfigSources::warnings`].
 //!
 //! # Example Config
 //!
@@ -62,10 +98,15 @@
 //! ```
 
 pub mod bootstrap;
+pub mod duration;
 pub mod infra;
 pub mod loader;
 
-pub use bootstrap::{BootstrapConfig, ConnectionsConfig, DefaultsConfig, MediaConfig, ModelsConfig};
+pub use bootstrap::{
+    ApprovalTimeoutDecision, BootstrapConfig, ConnectionsConfig, DefaultsConfig, MediaConfig,
+    ModelsConfig,
+};
+pub use duration::{DurationParseError, HumanDuration};
 pub use infra::{
     BindConfig, ChaosgardenConfig, GatewayConfig, HttpConfig, InfraConfig, PathsConfig,
     ServicesConfig, TelemetryConfig, VibeweaverConfig,
@@ -99,6 +140,10 @@ pub struct HootConfig {
     /// Bootstrap - seeds runtime state.
     #[serde(default)]
     pub bootstrap: BootstrapConfig,
+
+    /// Explicit config path this was loaded with, reused by `reload_bootstrap`.
+    #[serde(skip)]
+    pub(crate) config_path: Option<PathBuf>,
 }
 
 impl HootConfig {
@@ -109,7 +154,8 @@ impl HootConfig {
     /// 2. `/etc/hootenanny/config.toml`
     /// 3. `~/.config/hootenanny/config.toml`
     /// 4. `./hootenanny.toml`
-    /// 5. Environment variables
+    /// 5. Profile overlays selected by `HOOTENANNY_PROFILE`
+    /// 6. Environment variables
     pub fn load() -> Result<Self, ConfigError> {
         let (config, _sources) = Self::load_with_sources_from(None)?;
         Ok(config)
@@ -132,23 +178,72 @@ impl HootConfig {
     /// Load configuration from optional path and return information about sources.
     pub fn load_with_sources_from(
         config_path: Option<&std::path::Path>,
+    ) -> Result<(Self, ConfigSources), ConfigError> {
+        Self::load_with_profile_var(config_path, |name| std::env::var(name).ok())
+    }
+
+    /// [`Self::load_with_sources_from`], reading `HOOTENANNY_PROFILE` through
+    /// `var` rather than straight from the process environment.
+    fn load_with_profile_var(
+        config_path: Option<&std::path::Path>,
+        var: impl Fn(&str) -> Option<String>,
     ) -> Result<(Self, ConfigSources), ConfigError> {
         let mut sources = ConfigSources::default();
         let mut config = HootConfig::default();
 
         // Load config files in order
         for path in loader::discover_config_files_with_override(config_path) {
-            let file_config = loader::load_from_file(&path)?;
-            config = loader::merge_configs(config, file_config);
+            config = Self::overlay_file(config, &path, &mut sources)?;
             sources.files.push(path);
         }
 
+        // Profile overlays go last so they win over the shared files
+        if let Some(profile) = var("HOOTENANNY_PROFILE").filter(|p| !p.is_empty()) {
+            if profile.contains(['/', '\\']) {
+                sources
+                    .warnings
+                    .push(format!("HOOTENANNY_PROFILE={:?} is not a valid profile name", profile));
+            } else {
+                for path in loader::discover_profile_files(&profile) {
+                    config = Self::overlay_file(config, &path, &mut sources)?;
+                    sources.profile_files.push(path);
+                }
+                sources.profile = Some(profile);
+            }
+        }
+
         // Apply environment variable overrides
         loader::apply_env_overrides(&mut config, &mut sources);
 
+        config.config_path = config_path.map(|p| p.to_path_buf());
         Ok((config, sources))
     }
 
+    /// Load one config file and merge it over `config`, collecting its warnings.
+    fn overlay_file(
+        config: Self,
+        path: &std::path::Path,
+        sources: &mut ConfigSources,
+    ) -> Result<Self, ConfigError> {
+        let (file_config, warnings) = loader::load_from_file_checked(path)?;
+        sources.warnings.extend(
+            warnings
+                .into_iter()
+                .map(|warning| format!("{}: {}", path.display(), warning)),
+        );
+        Ok(loader::merge_configs(config, file_config))
+    }
+
+    /// Re-read the bootstrap portion of the config from the same sources.
+    ///
+    /// Files and env vars are loaded again exactly as at startup, but only
+    /// the bootstrap section is returned: infra stays fixed for the life of
+    /// the process, so changes to it are ignored until restart.
+    pub fn reload_bootstrap(&self) -> Result<BootstrapConfig, ConfigError> {
+        let (reloaded, _sources) = Self::load_with_sources_from(self.config_path.as_deref())?;
+        Ok(reloaded.bootstrap)
+    }
+
     /// Serialize config to TOML string.
     pub fn to_toml(&self) -> String {
         // Build TOML manually for nicer formatting
@@ -168,6 +263,9 @@ impl HootConfig {
         if let Some(socket_dir) = &self.infra.paths.socket_dir {
             output.push_str(&format!("socket_dir = \"{}\"\n", socket_dir.display()));
         }
+        if let Some(job_db) = &self.infra.paths.job_db {
+            output.push_str(&format!("job_db = \"{}\"\n", job_db.display()));
+        }
 
         output.push_str("\n[bind]\n");
         output.push_str(&format!("http_address = \"{}\"\n", self.infra.bind.http_address));
@@ -204,6 +302,26 @@ impl HootConfig {
             "hootenanny_pub = \"{}\"\n",
             self.infra.gateway.hootenanny_pub
         ));
+        output.push_str(&format!(
+            "tool_timeout_ms = {}\n",
+            self.infra.gateway.tool_timeout_ms
+        ));
+        output.push_str(&format!(
+            "list_page_size = {}\n",
+            self.infra.gateway.list_page_size
+        ));
+
+        let rate_limit = &self.infra.gateway.rate_limit;
+        output.push_str("\n[gateway.rate_limit]\n");
+        output.push_str(&format!("enabled = {}\n", rate_limit.enabled));
+        output.push_str(&format!("burst = {}\n", rate_limit.burst));
+        output.push_str(&format!("per_second = {:?}\n", rate_limit.per_second));
+        let tools: Vec<String> = rate_limit
+            .tools
+            .iter()
+            .map(|t| format!("{:?}", t))
+            .collect();
+        output.push_str(&format!("tools = [{}]\n", tools.join(", ")));
 
         output.push_str("\n[bootstrap.models]\n");
         let mut models: Vec<_> = self.bootstrap.models.iter().collect();
@@ -247,6 +365,24 @@ impl HootConfig {
             "max_concurrent_jobs = {}\n",
             self.bootstrap.defaults.max_concurrent_jobs
         ));
+        output.push_str(&format!(
+            "gpu_sample_interval = \"{}\"\n",
+            self.bootstrap.defaults.gpu_sample_interval
+        ));
+        output.push_str(&format!(
+            "gpu_vram_alert_pct = {:?}\n",
+            self.bootstrap.defaults.gpu_vram_alert_pct
+        ));
+        if let Some(timeout) = &self.bootstrap.defaults.approval_timeout {
+            output.push_str(&format!("approval_timeout = \"{}\"\n", timeout));
+        }
+        output.push_str(&format!(
+            "approval_timeout_decision = \"{}\"\n",
+            match self.bootstrap.defaults.approval_timeout_decision {
+                ApprovalTimeoutDecision::Approve => "approve",
+                ApprovalTimeoutDecision::Reject => "reject",
+            }
+        ));
 
         output.push_str("\n[services.vibeweaver]\n");
         output.push_str(&format!(
@@ -265,6 +401,10 @@ impl HootConfig {
             "timeout_ms = {}\n",
             self.infra.services.vibeweaver.timeout_ms
         ));
+        output.push_str(&format!(
+            "tool_timeout_ms = {}\n",
+            self.infra.services.vibeweaver.tool_timeout_ms
+        ));
 
         output.push_str("\n[services.chaosgarden]\n");
         output.push_str(&format!(
@@ -283,6 +423,7 @@ impl HootConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
 
     #[test]
     fn test_default_config() {
@@ -301,6 +442,58 @@ mod tests {
         assert!(toml.contains("gpu_observer"));
     }
 
+    #[test]
+    fn test_to_toml_round_trips_approval_defaults() {
+        let mut config = HootConfig::default();
+        config.bootstrap.defaults.approval_timeout = Some(HumanDuration::from_secs(120));
+        config.bootstrap.defaults.approval_timeout_decision = ApprovalTimeoutDecision::Approve;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hootenanny.toml");
+        std::fs::write(&path, config.to_toml()).unwrap();
+
+        let loaded = HootConfig::load_from(Some(&path)).unwrap();
+        assert_eq!(
+            loaded.bootstrap.defaults.approval_timeout(),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            loaded.bootstrap.defaults.approval_timeout_decision,
+            ApprovalTimeoutDecision::Approve
+        );
+    }
+
+    #[test]
+    fn test_reload_bootstrap_rereads_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hootenanny.toml");
+        std::fs::write(&path, "[bootstrap.models]\nreload_test = \"http://a:1\"\n").unwrap();
+
+        let config = HootConfig::load_from(Some(&path)).unwrap();
+        assert_eq!(config.bootstrap.models.get("reload_test").unwrap(), "http://a:1");
+
+        std::fs::write(
+            &path,
+            "[paths]\nstate_dir = \"/elsewhere\"\n\n\
+             [bootstrap.models]\nreload_test = \"http://b:2\"\n",
+        )
+        .unwrap();
+
+        let bootstrap = config.reload_bootstrap().unwrap();
+        assert_eq!(bootstrap.models.get("reload_test").unwrap(), "http://b:2");
+        assert_ne!(config.infra.paths.state_dir, PathBuf::from("/elsewhere"));
+    }
+
+    #[test]
+    fn test_missing_profile_files_are_skipped() {
+        let (_config, sources) = HootConfig::load_with_profile_var(None, |name| {
+            (name == "HOOTENANNY_PROFILE").then(|| "no-such-profile-for-tests".to_string())
+        })
+        .unwrap();
+        assert_eq!(sources.profile.as_deref(), Some("no-such-profile-for-tests"));
+        assert!(sources.profile_files.is_empty());
+    }
+
     #[test]
     fn test_load_defaults() {
         // Load should work even with no config files
@@ -1,10 +1,11 @@
 //! Config file discovery, loading, and environment variable overlay.
 
 use crate::infra::{
-    BindConfig, ChaosgardenConfig, GatewayConfig, HttpConfig, TelemetryConfig,
-    VibeweaverConfig,
+    validate_port, validate_zmq_endpoint, BindConfig, ChaosgardenConfig, GatewayConfig,
+    HttpConfig, TelemetryConfig, VibeweaverConfig,
 };
-use crate::{BootstrapConfig, ConfigError, HootConfig, InfraConfig};
+use crate::{BootstrapConfig, ConfigError, HootConfig, HumanDuration, InfraConfig};
+use serde::Deserialize;
 use std::env;
 use std::path::{Path, PathBuf};
 
@@ -15,6 +16,12 @@ pub struct ConfigSources {
     pub files: Vec<PathBuf>,
     /// Environment variables that overrode config values
     pub env_overrides: Vec<String>,
+    /// Problems that didn't stop loading, e.g. unknown keys (prefixed with the file)
+    pub warnings: Vec<String>,
+    /// Profile selected via `HOOTENANNY_PROFILE`, if any
+    pub profile: Option<String>,
+    /// Profile overlay files that were applied (in order)
+    pub profile_files: Vec<PathBuf>,
 }
 
 /// Discover config files in standard locations.
@@ -63,36 +70,162 @@ pub fn discover_config_files_with_override(cli_path: Option<&Path>) -> Vec<PathB
     files
 }
 
+/// Discover overlay files for a config profile (e.g. `prod`).
+///
+/// Returns paths in load order (user, local), only those that exist:
+/// `~/.config/hootenanny/config.<profile>.toml`, then `./hootenanny.<profile>.toml`.
+pub fn discover_profile_files(profile: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    if let Some(config_dir) = directories::BaseDirs::new().map(|d| d.config_dir().to_path_buf()) {
+        let user = config_dir.join(format!("hootenanny/config.{}.toml", profile));
+        if user.exists() {
+            files.push(user);
+        }
+    }
+
+    let local = PathBuf::from(format!("hootenanny.{}.toml", profile));
+    if local.exists() {
+        files.push(local);
+    }
+
+    files
+}
+
 /// Load config from a TOML file.
 pub fn load_from_file(path: &Path) -> Result<HootConfig, ConfigError> {
+    load_from_file_checked(path).map(|(config, _unknown)| config)
+}
+
+/// Load config from a TOML file, also returning warnings about its contents.
+///
+/// Warnings cover unknown keys, named by dotted path (`bootstrap.modls`) so
+/// typos surface instead of silently falling back to defaults, and unset
+/// variables referenced by path fields.
+pub fn load_from_file_checked(path: &Path) -> Result<(HootConfig, Vec<String>), ConfigError> {
     let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::FileRead {
         path: path.to_path_buf(),
         source: e,
     })?;
 
-    parse_toml(&contents, path)
+    let table = parse_table(&contents, path)?;
+    let mut warnings: Vec<String> = unknown_keys(&table)
+        .into_iter()
+        .map(|key| format!("unknown key `{}`", key))
+        .collect();
+    let config = config_from_table(&table, path, &mut warnings)?;
+    Ok((config, warnings))
+}
+
+/// Keys accepted in each config section, by dotted section path.
+///
+/// `bootstrap.models` is absent on purpose: it maps arbitrary model names
+/// to endpoints, so any key is legitimate there.
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    ("", &["paths", "bind", "http", "telemetry", "gateway", "services", "bootstrap"]),
+    ("paths", &["state_dir", "cas_dir", "socket_dir", "job_db"]),
+    ("bind", &["http_address", "http_port", "zmq_router", "zmq_pub", "tls"]),
+    ("bind.tls", &["enabled", "cert_path", "key_path"]),
+    ("http", &["hostname", "port", "scheme"]),
+    ("telemetry", &["otlp_endpoint", "log_level"]),
+    (
+        "gateway",
+        &[
+            "http_port",
+            "hootenanny",
+            "hootenanny_pub",
+            "timeout_ms",
+            "tool_timeout_ms",
+            "list_page_size",
+            "rate_limit",
+            "tls",
+        ],
+    ),
+    (
+        "gateway.rate_limit",
+        &["enabled", "burst", "per_second", "tools"],
+    ),
+    ("gateway.tls", &["enabled", "cert_path", "key_path"]),
+    ("services", &["vibeweaver", "chaosgarden"]),
+    (
+        "services.vibeweaver",
+        &["zmq_router", "hootenanny", "hootenanny_pub", "timeout_ms", "tool_timeout_ms"],
+    ),
+    ("services.chaosgarden", &["zmq_router", "ipc_socket"]),
+    ("bootstrap", &["models", "connections", "media", "defaults"]),
+    (
+        "bootstrap.connections",
+        &[
+            "chaosgarden", "vibeweaver", "rave", "rave_streaming", "orpheus", "beatthis",
+            "musicgen", "clap", "audioldm2", "anticipatory", "demucs", "yue", "midi_role",
+        ],
+    ),
+    ("bootstrap.media", &["soundfont_dirs", "sample_dirs"]),
+    (
+        "bootstrap.defaults",
+        &[
+            "lua_timeout", "session_expiration", "max_concurrent_jobs", "gpu_sample_interval",
+            "gpu_vram_alert_pct", "approval_timeout", "approval_timeout_decision",
+        ],
+    ),
+];
+
+/// Find keys in a parsed config table that no section recognizes.
+pub fn unknown_keys(table: &toml::Table) -> Vec<String> {
+    let mut unknown = Vec::new();
+    collect_unknown_keys(table, "", &mut unknown);
+    unknown
 }
 
-/// Parse config from TOML string.
-fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
-    // Parse as raw TOML table first to handle nested structure
-    let table: toml::Table = contents.parse().map_err(|e: toml::de::Error| ConfigError::Parse {
+fn collect_unknown_keys(table: &toml::Table, section: &str, unknown: &mut Vec<String>) {
+    let Some((_, known)) = KNOWN_KEYS.iter().find(|(name, _)| *name == section) else {
+        // Open-ended section
+        return;
+    };
+
+    for (key, value) in table {
+        let key_path = if section.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", section, key)
+        };
+
+        if !known.contains(&key.as_str()) {
+            unknown.push(key_path);
+        } else if let Some(child) = value.as_table() {
+            collect_unknown_keys(child, &key_path, unknown);
+        }
+    }
+}
+
+fn parse_table(contents: &str, path: &Path) -> Result<toml::Table, ConfigError> {
+    contents.parse().map_err(|e: toml::de::Error| ConfigError::Parse {
         path: path.to_path_buf(),
         message: e.to_string(),
-    })?;
+    })
+}
 
+/// Build a config from a raw TOML table, handling the nested structure by hand.
+fn config_from_table(
+    table: &toml::Table,
+    path: &Path,
+    warnings: &mut Vec<String>,
+) -> Result<HootConfig, ConfigError> {
     // Extract sections
     let infra: InfraConfig = if let Some(paths) = table.get("paths") {
         let mut infra = InfraConfig::default();
         if let Some(paths_table) = paths.as_table() {
             if let Some(v) = paths_table.get("state_dir").and_then(|v| v.as_str()) {
-                infra.paths.state_dir = expand_path(v);
+                infra.paths.state_dir = expand_field(v, "paths.state_dir", warnings);
             }
             if let Some(v) = paths_table.get("cas_dir").and_then(|v| v.as_str()) {
-                infra.paths.cas_dir = expand_path(v);
+                infra.paths.cas_dir = expand_field(v, "paths.cas_dir", warnings);
             }
             if let Some(v) = paths_table.get("socket_dir").and_then(|v| v.as_str()) {
-                infra.paths.socket_dir = Some(expand_path(v));
+                infra.paths.socket_dir = Some(expand_field(v, "paths.socket_dir", warnings));
+            }
+            if let Some(v) = paths_table.get("job_db").and_then(|v| v.as_str()) {
+                infra.paths.job_db = Some(expand_field(v, "paths.job_db", warnings));
             }
         }
 
@@ -101,13 +234,13 @@ fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
                 infra.bind.http_address = v.to_string();
             }
             if let Some(v) = bind.get("http_port").and_then(|v| v.as_integer()) {
-                infra.bind.http_port = v as u16;
+                infra.bind.http_port = port_field(v, "bind.http_port", path)?;
             }
             if let Some(v) = bind.get("zmq_router").and_then(|v| v.as_str()) {
-                infra.bind.zmq_router = v.to_string();
+                infra.bind.zmq_router = zmq_field(v, "bind.zmq_router", path)?;
             }
             if let Some(v) = bind.get("zmq_pub").and_then(|v| v.as_str()) {
-                infra.bind.zmq_pub = v.to_string();
+                infra.bind.zmq_pub = zmq_field(v, "bind.zmq_pub", path)?;
             }
             // TLS config
             if let Some(tls) = bind.get("tls").and_then(|v| v.as_table()) {
@@ -115,10 +248,10 @@ fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
                     infra.bind.tls.enabled = v;
                 }
                 if let Some(v) = tls.get("cert_path").and_then(|v| v.as_str()) {
-                    infra.bind.tls.cert_path = Some(expand_path(v));
+                    infra.bind.tls.cert_path = Some(expand_field(v, "bind.tls.cert_path", warnings));
                 }
                 if let Some(v) = tls.get("key_path").and_then(|v| v.as_str()) {
-                    infra.bind.tls.key_path = Some(expand_path(v));
+                    infra.bind.tls.key_path = Some(expand_field(v, "bind.tls.key_path", warnings));
                 }
             }
         }
@@ -128,7 +261,7 @@ fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
                 infra.http.hostname = Some(v.to_string());
             }
             if let Some(v) = http.get("port").and_then(|v| v.as_integer()) {
-                infra.http.port = Some(v as u16);
+                infra.http.port = Some(port_field(v, "http.port", path)?);
             }
             if let Some(v) = http.get("scheme").and_then(|v| v.as_str()) {
                 infra.http.scheme = v.to_string();
@@ -146,13 +279,76 @@ fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
 
         if let Some(gateway) = table.get("gateway").and_then(|v| v.as_table()) {
             if let Some(v) = gateway.get("http_port").and_then(|v| v.as_integer()) {
-                infra.gateway.http_port = v as u16;
+                infra.gateway.http_port = port_field(v, "gateway.http_port", path)?;
             }
             if let Some(v) = gateway.get("hootenanny").and_then(|v| v.as_str()) {
-                infra.gateway.hootenanny = v.to_string();
+                infra.gateway.hootenanny = zmq_field(v, "gateway.hootenanny", path)?;
             }
             if let Some(v) = gateway.get("hootenanny_pub").and_then(|v| v.as_str()) {
-                infra.gateway.hootenanny_pub = v.to_string();
+                infra.gateway.hootenanny_pub = zmq_field(v, "gateway.hootenanny_pub", path)?;
+            }
+            if let Some(v) = gateway.get("tool_timeout_ms").and_then(|v| v.as_integer()) {
+                infra.gateway.tool_timeout_ms = u64::try_from(v)
+                    .ok()
+                    .filter(|ms| *ms > 0)
+                    .ok_or_else(|| ConfigError::Parse {
+                        path: path.to_path_buf(),
+                        message: format!(
+                            "gateway.tool_timeout_ms: expected a positive number of milliseconds, got {}",
+                            v
+                        ),
+                    })?;
+            }
+            if let Some(v) = gateway.get("list_page_size").and_then(|v| v.as_integer()) {
+                infra.gateway.list_page_size = usize::try_from(v)
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| ConfigError::Parse {
+                        path: path.to_path_buf(),
+                        message: format!(
+                            "gateway.list_page_size: expected a positive number of items, got {}",
+                            v
+                        ),
+                    })?;
+            }
+            if let Some(limit) = gateway.get("rate_limit").and_then(|v| v.as_table()) {
+                let rate_limit = &mut infra.gateway.rate_limit;
+                if let Some(v) = limit.get("enabled").and_then(|v| v.as_bool()) {
+                    rate_limit.enabled = v;
+                }
+                if let Some(v) = limit.get("burst").and_then(|v| v.as_integer()) {
+                    rate_limit.burst = u32::try_from(v)
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| ConfigError::Parse {
+                            path: path.to_path_buf(),
+                            message: format!(
+                                "gateway.rate_limit.burst: expected a positive number of calls, got {}",
+                                v
+                            ),
+                        })?;
+                }
+                let per_second = limit
+                    .get("per_second")
+                    .and_then(|v| v.as_float().or_else(|| v.as_integer().map(|n| n as f64)));
+                if let Some(v) = per_second {
+                    if !(v.is_finite() && v >= 0.0) {
+                        return Err(ConfigError::Parse {
+                            path: path.to_path_buf(),
+                            message: format!(
+                                "gateway.rate_limit.per_second: expected a rate of zero or more, got {}",
+                                v
+                            ),
+                        });
+                    }
+                    rate_limit.per_second = v;
+                }
+                if let Some(v) = limit.get("tools").and_then(|v| v.as_array()) {
+                    rate_limit.tools = v
+                        .iter()
+                        .filter_map(|t| t.as_str().map(String::from))
+                        .collect();
+                }
             }
             // TLS config
             if let Some(tls) = gateway.get("tls").and_then(|v| v.as_table()) {
@@ -160,10 +356,12 @@ fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
                     infra.gateway.tls.enabled = v;
                 }
                 if let Some(v) = tls.get("cert_path").and_then(|v| v.as_str()) {
-                    infra.gateway.tls.cert_path = Some(expand_path(v));
+                    infra.gateway.tls.cert_path =
+                        Some(expand_field(v, "gateway.tls.cert_path", warnings));
                 }
                 if let Some(v) = tls.get("key_path").and_then(|v| v.as_str()) {
-                    infra.gateway.tls.key_path = Some(expand_path(v));
+                    infra.gateway.tls.key_path =
+                        Some(expand_field(v, "gateway.tls.key_path", warnings));
                 }
             }
         }
@@ -201,28 +399,56 @@ fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
                 bootstrap.media.soundfont_dirs = dirs
                     .iter()
                     .filter_map(|v| v.as_str())
-                    .map(expand_path)
+                    .map(|v| expand_field(v, "bootstrap.media.soundfont_dirs", warnings))
                     .collect();
             }
             if let Some(dirs) = media.get("sample_dirs").and_then(|v| v.as_array()) {
                 bootstrap.media.sample_dirs = dirs
                     .iter()
                     .filter_map(|v| v.as_str())
-                    .map(expand_path)
+                    .map(|v| expand_field(v, "bootstrap.media.sample_dirs", warnings))
                     .collect();
             }
         }
 
         if let Some(defaults) = bootstrap_section.get("defaults").and_then(|v| v.as_table()) {
             if let Some(v) = defaults.get("lua_timeout").and_then(|v| v.as_str()) {
-                bootstrap.defaults.lua_timeout = v.to_string();
+                bootstrap.defaults.lua_timeout =
+                    parse_duration(v, "bootstrap.defaults.lua_timeout", path)?;
             }
             if let Some(v) = defaults.get("session_expiration").and_then(|v| v.as_str()) {
-                bootstrap.defaults.session_expiration = v.to_string();
+                bootstrap.defaults.session_expiration =
+                    parse_duration(v, "bootstrap.defaults.session_expiration", path)?;
             }
             if let Some(v) = defaults.get("max_concurrent_jobs").and_then(|v| v.as_integer()) {
                 bootstrap.defaults.max_concurrent_jobs = v as u32;
             }
+            if let Some(v) = defaults.get("gpu_sample_interval").and_then(|v| v.as_str()) {
+                bootstrap.defaults.gpu_sample_interval =
+                    parse_interval(v, "bootstrap.defaults.gpu_sample_interval", path)?;
+            }
+            if let Some(v) = defaults.get("gpu_vram_alert_pct") {
+                if let Some(pct) = v.as_float().or_else(|| v.as_integer().map(|i| i as f64)) {
+                    bootstrap.defaults.gpu_vram_alert_pct = pct;
+                }
+            }
+            if let Some(v) = defaults.get("approval_timeout").and_then(|v| v.as_str()) {
+                bootstrap.defaults.approval_timeout = Some(parse_interval(
+                    v,
+                    "bootstrap.defaults.approval_timeout",
+                    path,
+                )?);
+            }
+            if let Some(v) = defaults
+                .get("approval_timeout_decision")
+                .and_then(|v| v.as_str())
+            {
+                bootstrap.defaults.approval_timeout_decision =
+                    v.parse().map_err(|e| ConfigError::Parse {
+                        path: path.to_path_buf(),
+                        message: format!("bootstrap.defaults.approval_timeout_decision: {}", e),
+                    })?;
+            }
         }
 
         bootstrap
@@ -230,13 +456,55 @@ fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
         BootstrapConfig::default()
     };
 
-    Ok(HootConfig { infra, bootstrap })
+    Ok(HootConfig {
+        infra,
+        bootstrap,
+        config_path: None,
+    })
+}
+
+/// Validate a port field, naming the field in the error.
+fn port_field(value: i64, field: &str, path: &Path) -> Result<u16, ConfigError> {
+    validate_port(value).map_err(|e| ConfigError::Parse {
+        path: path.to_path_buf(),
+        message: format!("{}: {}", field, e),
+    })
+}
+
+/// Validate a ZMQ endpoint field, naming the field in the error.
+fn zmq_field(value: &str, field: &str, path: &Path) -> Result<String, ConfigError> {
+    validate_zmq_endpoint(value).map_err(|e| ConfigError::Parse {
+        path: path.to_path_buf(),
+        message: format!("{}: {}", field, e),
+    })?;
+    Ok(value.to_string())
+}
+
+/// Parse a duration field, naming the field in the error.
+fn parse_duration(value: &str, field: &str, path: &Path) -> Result<HumanDuration, ConfigError> {
+    value.parse().map_err(|e| ConfigError::Parse {
+        path: path.to_path_buf(),
+        message: format!("{}: {}", field, e),
+    })
+}
+
+/// Parse a timer period; zero would make the timer spin (or panic).
+fn parse_interval(value: &str, field: &str, path: &Path) -> Result<HumanDuration, ConfigError> {
+    let interval = parse_duration(value, field, path)?;
+    if interval.as_duration().is_zero() {
+        return Err(ConfigError::Parse {
+            path: path.to_path_buf(),
+            message: format!("{}: must be greater than zero, got {:?}", field, value),
+        });
+    }
+    Ok(interval)
 }
 
 /// Merge two configs, with `overlay` taking precedence.
+///
+/// Merging is field by field: a field the overlay leaves at its default
+/// keeps the base value, so an overlay only needs the settings it changes.
 pub fn merge_configs(base: HootConfig, overlay: HootConfig) -> HootConfig {
-    // For simplicity, overlay completely replaces base for now
-    // A more sophisticated merge could be field-by-field
     HootConfig {
         infra: InfraConfig {
             paths: crate::infra::PathsConfig {
@@ -255,6 +523,7 @@ pub fn merge_configs(base: HootConfig, overlay: HootConfig) -> HootConfig {
                 } else {
                     base.infra.paths.socket_dir
                 },
+                job_db: overlay.infra.paths.job_db.or(base.infra.paths.job_db),
             },
             bind: crate::infra::BindConfig {
                 http_address: if overlay.infra.bind.http_address != BindConfig::default().http_address {
@@ -343,6 +612,21 @@ pub fn merge_configs(base: HootConfig, overlay: HootConfig) -> HootConfig {
                 } else {
                     base.infra.gateway.timeout_ms
                 },
+                tool_timeout_ms: if overlay.infra.gateway.tool_timeout_ms != GatewayConfig::default().tool_timeout_ms {
+                    overlay.infra.gateway.tool_timeout_ms
+                } else {
+                    base.infra.gateway.tool_timeout_ms
+                },
+                list_page_size: if overlay.infra.gateway.list_page_size != GatewayConfig::default().list_page_size {
+                    overlay.infra.gateway.list_page_size
+                } else {
+                    base.infra.gateway.list_page_size
+                },
+                rate_limit: if overlay.infra.gateway.rate_limit != GatewayConfig::default().rate_limit {
+                    overlay.infra.gateway.rate_limit
+                } else {
+                    base.infra.gateway.rate_limit
+                },
                 tls: crate::infra::TlsConfig {
                     enabled: overlay.infra.gateway.tls.enabled || base.infra.gateway.tls.enabled,
                     cert_path: overlay
@@ -381,6 +665,11 @@ pub fn merge_configs(base: HootConfig, overlay: HootConfig) -> HootConfig {
                     } else {
                         base.infra.services.vibeweaver.timeout_ms
                     },
+                    tool_timeout_ms: if overlay.infra.services.vibeweaver.tool_timeout_ms != VibeweaverConfig::default().tool_timeout_ms {
+                        overlay.infra.services.vibeweaver.tool_timeout_ms
+                    } else {
+                        base.infra.services.vibeweaver.tool_timeout_ms
+                    },
                 },
                 chaosgarden: crate::infra::ChaosgardenConfig {
                     zmq_router: if overlay.infra.services.chaosgarden.zmq_router != ChaosgardenConfig::default().zmq_router {
@@ -396,7 +685,154 @@ pub fn merge_configs(base: HootConfig, overlay: HootConfig) -> HootConfig {
                 },
             },
         },
-        bootstrap: overlay.bootstrap, // Bootstrap fully replaces for now
+        bootstrap: merge_bootstrap(base.bootstrap, overlay.bootstrap),
+        config_path: overlay.config_path.or(base.config_path),
+    }
+}
+
+/// Merge bootstrap sections field by field, like [`merge_configs`] does infra.
+///
+/// Models merge by name: the overlay adds or replaces entries, and a default
+/// entry it merely inherited doesn't undo the base's setting.
+fn merge_bootstrap(base: BootstrapConfig, overlay: BootstrapConfig) -> BootstrapConfig {
+    let default = BootstrapConfig::default();
+
+    let mut models = base.models;
+    for (name, url) in overlay.models {
+        if default.models.get(&name) != Some(&url) {
+            models.insert(name, url);
+        }
+    }
+
+    BootstrapConfig {
+        models,
+        connections: crate::bootstrap::ConnectionsConfig {
+            chaosgarden: if overlay.connections.chaosgarden != default.connections.chaosgarden {
+                overlay.connections.chaosgarden
+            } else {
+                base.connections.chaosgarden
+            },
+            vibeweaver: if overlay.connections.vibeweaver != default.connections.vibeweaver {
+                overlay.connections.vibeweaver
+            } else {
+                base.connections.vibeweaver
+            },
+            rave: if overlay.connections.rave != default.connections.rave {
+                overlay.connections.rave
+            } else {
+                base.connections.rave
+            },
+            rave_streaming: if overlay.connections.rave_streaming
+                != default.connections.rave_streaming
+            {
+                overlay.connections.rave_streaming
+            } else {
+                base.connections.rave_streaming
+            },
+            orpheus: if overlay.connections.orpheus != default.connections.orpheus {
+                overlay.connections.orpheus
+            } else {
+                base.connections.orpheus
+            },
+            beatthis: if overlay.connections.beatthis != default.connections.beatthis {
+                overlay.connections.beatthis
+            } else {
+                base.connections.beatthis
+            },
+            musicgen: if overlay.connections.musicgen != default.connections.musicgen {
+                overlay.connections.musicgen
+            } else {
+                base.connections.musicgen
+            },
+            clap: if overlay.connections.clap != default.connections.clap {
+                overlay.connections.clap
+            } else {
+                base.connections.clap
+            },
+            audioldm2: if overlay.connections.audioldm2 != default.connections.audioldm2 {
+                overlay.connections.audioldm2
+            } else {
+                base.connections.audioldm2
+            },
+            anticipatory: if overlay.connections.anticipatory != default.connections.anticipatory {
+                overlay.connections.anticipatory
+            } else {
+                base.connections.anticipatory
+            },
+            demucs: if overlay.connections.demucs != default.connections.demucs {
+                overlay.connections.demucs
+            } else {
+                base.connections.demucs
+            },
+            yue: if overlay.connections.yue != default.connections.yue {
+                overlay.connections.yue
+            } else {
+                base.connections.yue
+            },
+            midi_role: if overlay.connections.midi_role != default.connections.midi_role {
+                overlay.connections.midi_role
+            } else {
+                base.connections.midi_role
+            },
+        },
+        media: crate::bootstrap::MediaConfig {
+            soundfont_dirs: if overlay.media.soundfont_dirs != default.media.soundfont_dirs {
+                overlay.media.soundfont_dirs
+            } else {
+                base.media.soundfont_dirs
+            },
+            sample_dirs: if overlay.media.sample_dirs != default.media.sample_dirs {
+                overlay.media.sample_dirs
+            } else {
+                base.media.sample_dirs
+            },
+        },
+        defaults: crate::bootstrap::DefaultsConfig {
+            lua_timeout: if overlay.defaults.lua_timeout != default.defaults.lua_timeout {
+                overlay.defaults.lua_timeout
+            } else {
+                base.defaults.lua_timeout
+            },
+            session_expiration: if overlay.defaults.session_expiration
+                != default.defaults.session_expiration
+            {
+                overlay.defaults.session_expiration
+            } else {
+                base.defaults.session_expiration
+            },
+            max_concurrent_jobs: if overlay.defaults.max_concurrent_jobs
+                != default.defaults.max_concurrent_jobs
+            {
+                overlay.defaults.max_concurrent_jobs
+            } else {
+                base.defaults.max_concurrent_jobs
+            },
+            gpu_sample_interval: if overlay.defaults.gpu_sample_interval
+                != default.defaults.gpu_sample_interval
+            {
+                overlay.defaults.gpu_sample_interval
+            } else {
+                base.defaults.gpu_sample_interval
+            },
+            gpu_vram_alert_pct: if overlay.defaults.gpu_vram_alert_pct
+                != default.defaults.gpu_vram_alert_pct
+            {
+                overlay.defaults.gpu_vram_alert_pct
+            } else {
+                base.defaults.gpu_vram_alert_pct
+            },
+            approval_timeout: overlay
+                .defaults
+                .approval_timeout
+                .or(base.defaults.approval_timeout),
+            approval_timeout_decision: if overlay.defaults.approval_timeout_decision
+                != default.defaults.approval_timeout_decision
+            {
+                overlay.defaults.approval_timeout_decision
+            } else {
+                base.defaults.approval_timeout_decision
+            },
+        },
     }
 }
 
@@ -404,22 +840,32 @@ pub fn merge_configs(base: HootConfig, overlay: HootConfig) -> HootConfig {
 pub fn apply_env_overrides(config: &mut HootConfig, sources: &mut ConfigSources) {
     // Infrastructure paths
     if let Ok(v) = env::var("HOOTENANNY_STATE_DIR") {
-        config.infra.paths.state_dir = expand_path(&v);
+        config.infra.paths.state_dir =
+            expand_field(&v, "HOOTENANNY_STATE_DIR", &mut sources.warnings);
         sources.env_overrides.push("HOOTENANNY_STATE_DIR".to_string());
     }
     if let Ok(v) = env::var("HOOTENANNY_CAS_DIR") {
-        config.infra.paths.cas_dir = expand_path(&v);
+        config.infra.paths.cas_dir = expand_field(&v, "HOOTENANNY_CAS_DIR", &mut sources.warnings);
         sources.env_overrides.push("HOOTENANNY_CAS_DIR".to_string());
     }
     // Legacy support
     if let Ok(v) = env::var("HOOTENANNY_CAS_PATH") {
-        config.infra.paths.cas_dir = expand_path(&v);
+        config.infra.paths.cas_dir = expand_field(&v, "HOOTENANNY_CAS_PATH", &mut sources.warnings);
         sources.env_overrides.push("HOOTENANNY_CAS_PATH".to_string());
     }
     if let Ok(v) = env::var("HOOTENANNY_SOCKET_DIR") {
-        config.infra.paths.socket_dir = Some(expand_path(&v));
+        config.infra.paths.socket_dir = Some(expand_field(
+            &v,
+            "HOOTENANNY_SOCKET_DIR",
+            &mut sources.warnings,
+        ));
         sources.env_overrides.push("HOOTENANNY_SOCKET_DIR".to_string());
     }
+    if let Ok(v) = env::var("HOOTENANNY_JOB_DB") {
+        config.infra.paths.job_db =
+            Some(expand_field(&v, "HOOTENANNY_JOB_DB", &mut sources.warnings));
+        sources.env_overrides.push("HOOTENANNY_JOB_DB".to_string());
+    }
 
     // Bind addresses
     if let Ok(v) = env::var("HOOTENANNY_HTTP_ADDRESS") {
@@ -447,11 +893,19 @@ pub fn apply_env_overrides(config: &mut HootConfig, sources: &mut ConfigSources)
         sources.env_overrides.push("HOOTENANNY_TLS_ENABLED".to_string());
     }
     if let Ok(v) = env::var("HOOTENANNY_TLS_CERT") {
-        config.infra.bind.tls.cert_path = Some(expand_path(&v));
+        config.infra.bind.tls.cert_path = Some(expand_field(
+            &v,
+            "HOOTENANNY_TLS_CERT",
+            &mut sources.warnings,
+        ));
         sources.env_overrides.push("HOOTENANNY_TLS_CERT".to_string());
     }
     if let Ok(v) = env::var("HOOTENANNY_TLS_KEY") {
-        config.infra.bind.tls.key_path = Some(expand_path(&v));
+        config.infra.bind.tls.key_path = Some(expand_field(
+            &v,
+            "HOOTENANNY_TLS_KEY",
+            &mut sources.warnings,
+        ));
         sources.env_overrides.push("HOOTENANNY_TLS_KEY".to_string());
     }
 
@@ -513,11 +967,13 @@ pub fn apply_env_overrides(config: &mut HootConfig, sources: &mut ConfigSources)
         sources.env_overrides.push("HOLLER_TLS_ENABLED".to_string());
     }
     if let Ok(v) = env::var("HOLLER_TLS_CERT") {
-        config.infra.gateway.tls.cert_path = Some(expand_path(&v));
+        config.infra.gateway.tls.cert_path =
+            Some(expand_field(&v, "HOLLER_TLS_CERT", &mut sources.warnings));
         sources.env_overrides.push("HOLLER_TLS_CERT".to_string());
     }
     if let Ok(v) = env::var("HOLLER_TLS_KEY") {
-        config.infra.gateway.tls.key_path = Some(expand_path(&v));
+        config.infra.gateway.tls.key_path =
+            Some(expand_field(&v, "HOLLER_TLS_KEY", &mut sources.warnings));
         sources.env_overrides.push("HOLLER_TLS_KEY".to_string());
     }
 
@@ -531,39 +987,113 @@ pub fn apply_env_overrides(config: &mut HootConfig, sources: &mut ConfigSources)
     }
 }
 
-/// Expand ~ and environment variables in a path.
+/// Expand `~` and environment variables in a path.
+///
+/// A leading `~` (alone or before `/`) becomes the home directory, and
+/// `$VAR` / `${VAR}` are substituted anywhere in the string. Unset variables
+/// expand to empty; use [`expand_path_checked`] to learn which ones were.
+/// Config loading reports them in [`ConfigSources::warnings`].
 pub fn expand_path(path: &str) -> PathBuf {
-    let expanded = if let Some(stripped) = path.strip_prefix("~/") {
-        if let Some(home) = directories::BaseDirs::new().map(|d| d.home_dir().to_path_buf()) {
-            home.join(stripped)
-        } else {
-            PathBuf::from(path)
+    expand_path_checked(path).0
+}
+
+/// Expand a path like [`expand_path`], returning the names of unset variables.
+pub fn expand_path_checked(path: &str) -> (PathBuf, Vec<String>) {
+    let mut unset = Vec::new();
+    let mut out = String::with_capacity(path.len());
+    let mut rest = path;
+
+    if let Some(after) = path.strip_prefix('~') {
+        if after.is_empty() || after.starts_with('/') {
+            if let Some(base) = directories::BaseDirs::new() {
+                out.push_str(&base.home_dir().to_string_lossy());
+                rest = after;
+            }
         }
-    } else if let Some(stripped) = path.strip_prefix('$') {
-        // Handle $VAR/rest/of/path
-        if let Some(slash_pos) = stripped.find('/') {
-            let var_name = &stripped[..slash_pos];
-            if let Ok(var_value) = env::var(var_name) {
-                PathBuf::from(var_value).join(&stripped[slash_pos + 1..])
-            } else {
-                PathBuf::from(path)
+    }
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        let (name, remainder) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", after),
             }
         } else {
-            env::var(stripped)
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| PathBuf::from(path))
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+
+        // A lone `$` (or unterminated `${`) is kept literally
+        if name.is_empty() {
+            out.push('$');
+            rest = after;
+            continue;
         }
-    } else {
-        PathBuf::from(path)
-    };
 
+        match env::var(name) {
+            Ok(value) => out.push_str(&value),
+            Err(_) => unset.push(name.to_string()),
+        }
+        rest = remainder;
+    }
+    out.push_str(rest);
+
+    (PathBuf::from(out), unset)
+}
+
+/// Expand a config path field, recording unset variables as warnings.
+fn expand_field(value: &str, field: &str, warnings: &mut Vec<String>) -> PathBuf {
+    let (expanded, unset) = expand_path_checked(value);
+    warnings.extend(
+        unset
+            .into_iter()
+            .map(|var| format!("{}: ${} is unset, expanded to empty", field, var)),
+    );
     expanded
 }
 
+/// Serde adapter that expands a path field on deserialize.
+pub(crate) fn deserialize_path<'de, D>(deserializer: D) -> Result<PathBuf, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    Ok(expand_path(&raw))
+}
+
+/// Serde adapter for optional path fields.
+pub(crate) fn deserialize_opt_path<'de, D>(deserializer: D) -> Result<Option<PathBuf>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = Option::<String>::deserialize(deserializer)?;
+    Ok(raw.as_deref().map(expand_path))
+}
+
+/// Serde adapter for lists of path fields.
+pub(crate) fn deserialize_path_list<'de, D>(deserializer: D) -> Result<Vec<PathBuf>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = Vec::<String>::deserialize(deserializer)?;
+    Ok(raw.iter().map(|p| expand_path(p)).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Parse config from a TOML string, discarding warnings.
+    fn parse_toml(contents: &str, path: &Path) -> Result<HootConfig, ConfigError> {
+        let table = parse_table(contents, path)?;
+        config_from_table(&table, path, &mut Vec::new())
+    }
+
     #[test]
     fn test_expand_path_tilde() {
         let expanded = expand_path("~/test/path");
@@ -571,6 +1101,50 @@ mod tests {
         assert!(expanded.to_string_lossy().contains("test/path"));
     }
 
+    #[test]
+    fn test_expand_path_bare_tilde() {
+        let home = directories::BaseDirs::new().unwrap().home_dir().to_path_buf();
+        assert_eq!(expand_path("~"), home);
+        assert_eq!(expand_path("/srv/~user"), PathBuf::from("/srv/~user"));
+    }
+
+    #[test]
+    fn test_expand_path_env_vars() {
+        env::set_var("HOOTECONF_TEST_ROOT", "/data");
+        assert_eq!(expand_path("$HOOTECONF_TEST_ROOT/cas"), PathBuf::from("/data/cas"));
+        assert_eq!(
+            expand_path("/mnt${HOOTECONF_TEST_ROOT}/x$HOOTECONF_TEST_ROOT"),
+            PathBuf::from("/mnt/data/x/data")
+        );
+        assert_eq!(expand_path("/lone/$/${unclosed"), PathBuf::from("/lone/$/${unclosed"));
+    }
+
+    #[test]
+    fn test_expand_path_unset_var_is_empty_and_reported() {
+        env::remove_var("HOOTECONF_TEST_UNSET");
+        let (expanded, unset) = expand_path_checked("${HOOTECONF_TEST_UNSET}/state");
+        assert_eq!(expanded, PathBuf::from("/state"));
+        assert_eq!(unset, vec!["HOOTECONF_TEST_UNSET"]);
+    }
+
+    #[test]
+    fn test_unset_vars_in_config_paths_are_warnings() {
+        let toml = r#"
+[paths]
+state_dir = "/data"
+cas_dir = "${HOOTECONF_TEST_NEVER_SET}/cas"
+"#;
+        let table = parse_table(toml, Path::new("test.toml")).unwrap();
+        let mut warnings = Vec::new();
+        let config = config_from_table(&table, Path::new("test.toml"), &mut warnings).unwrap();
+
+        assert_eq!(config.infra.paths.cas_dir, PathBuf::from("/cas"));
+        assert_eq!(
+            warnings,
+            vec!["paths.cas_dir: $HOOTECONF_TEST_NEVER_SET is unset, expanded to empty"]
+        );
+    }
+
     #[test]
     fn test_expand_path_absolute() {
         let expanded = expand_path("/absolute/path");
@@ -583,6 +1157,11 @@ mod tests {
         let _files = discover_config_files();
     }
 
+    #[test]
+    fn test_discover_profile_files_skips_missing() {
+        assert!(discover_profile_files("no-such-profile-for-tests").is_empty());
+    }
+
     #[test]
     fn test_parse_minimal_toml() {
         let toml = r#"
@@ -622,6 +1201,10 @@ soundfont_dirs = ["/my/soundfonts", "/other/sf2"]
 [bootstrap.defaults]
 lua_timeout = "60s"
 max_concurrent_jobs = 8
+gpu_sample_interval = "2s"
+gpu_vram_alert_pct = 85
+approval_timeout = "2m"
+approval_timeout_decision = "approve"
 "#;
         let config = parse_toml(toml, Path::new("test.toml")).unwrap();
 
@@ -635,7 +1218,269 @@ max_concurrent_jobs = 8
         assert_eq!(config.bootstrap.models.get("custom_model"), Some(&"http://custom:3000".to_string()));
         assert_eq!(config.bootstrap.connections.chaosgarden, "tcp://localhost:5555");
         assert_eq!(config.bootstrap.media.soundfont_dirs.len(), 2);
-        assert_eq!(config.bootstrap.defaults.lua_timeout, "60s");
+        assert_eq!(config.bootstrap.defaults.lua_timeout(), std::time::Duration::from_secs(60));
         assert_eq!(config.bootstrap.defaults.max_concurrent_jobs, 8);
+        assert_eq!(
+            config.bootstrap.defaults.gpu_sample_interval(),
+            std::time::Duration::from_secs(2)
+        );
+        assert_eq!(config.bootstrap.defaults.gpu_vram_alert_pct, 85.0);
+        assert_eq!(
+            config.bootstrap.defaults.approval_timeout(),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(
+            config.bootstrap.defaults.approval_timeout_decision,
+            crate::bootstrap::ApprovalTimeoutDecision::Approve
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed_duration_names_field() {
+        let toml = r#"
+[bootstrap.defaults]
+lua_timeout = "30ss"
+"#;
+        let err = parse_toml(toml, Path::new("test.toml")).unwrap_err();
+        match err {
+            ConfigError::Parse { path, message } => {
+                assert_eq!(path, PathBuf::from("test.toml"));
+                assert!(message.contains("bootstrap.defaults.lua_timeout"));
+                assert!(message.contains("30ss"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_zero_gpu_sample_interval_is_rejected() {
+        let toml = r#"
+[bootstrap.defaults]
+gpu_sample_interval = "0s"
+"#;
+        let err = parse_toml(toml, Path::new("test.toml")).unwrap_err();
+        match err {
+            ConfigError::Parse { message, .. } => {
+                assert!(message.contains("bootstrap.defaults.gpu_sample_interval"));
+                assert!(message.contains("greater than zero"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_bad_approval_timeout_decision_names_field() {
+        let toml = r#"
+[bootstrap.defaults]
+approval_timeout_decision = "shrug"
+"#;
+        let err = parse_toml(toml, Path::new("test.toml")).unwrap_err();
+        match err {
+            ConfigError::Parse { message, .. } => {
+                assert!(message.contains("bootstrap.defaults.approval_timeout_decision"));
+                assert!(message.contains("shrug"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_bootstrap_the_overlay_leaves_out() {
+        let base = parse_toml(
+            r#"
+[bootstrap.models]
+custom = "http://custom:1"
+
+[bootstrap.connections]
+rave = "tcp://gpu:5581"
+
+[bootstrap.media]
+sample_dirs = ["/data/samples"]
+
+[bootstrap.defaults]
+approval_timeout = "2m"
+approval_timeout_decision = "approve"
+max_concurrent_jobs = 8
+"#,
+            Path::new("base.toml"),
+        )
+        .unwrap();
+        let overlay = parse_toml(
+            r#"
+[paths]
+state_dir = "/prod"
+
+[bootstrap.models]
+other = "http://other:2"
+
+[bootstrap.defaults]
+lua_timeout = "1m"
+"#,
+            Path::new("overlay.toml"),
+        )
+        .unwrap();
+
+        let merged = merge_configs(base, overlay).bootstrap;
+        assert_eq!(merged.models.get("custom").unwrap(), "http://custom:1");
+        assert_eq!(merged.models.get("other").unwrap(), "http://other:2");
+        assert!(merged.models.contains_key("gpu_observer"));
+        assert_eq!(merged.connections.rave, "tcp://gpu:5581");
+        assert_eq!(
+            merged.media.sample_dirs,
+            vec![PathBuf::from("/data/samples")]
+        );
+        assert_eq!(
+            merged.defaults.approval_timeout(),
+            Some(std::time::Duration::from_secs(120))
+        );
+        assert_eq!(
+            merged.defaults.approval_timeout_decision,
+            crate::ApprovalTimeoutDecision::Approve
+        );
+        assert_eq!(merged.defaults.max_concurrent_jobs, 8);
+        assert_eq!(
+            merged.defaults.lua_timeout(),
+            std::time::Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn test_parse_gateway_tool_timeout() {
+        let toml = r#"
+[paths]
+state_dir = "/data"
+
+[gateway]
+tool_timeout_ms = 45000
+"#;
+        let config = parse_toml(toml, Path::new("test.toml")).unwrap();
+        assert_eq!(config.infra.gateway.tool_timeout_ms, 45_000);
+
+        let toml = toml.replace("45000", "0");
+        let err = parse_toml(&toml, Path::new("test.toml")).unwrap_err();
+        match err {
+            ConfigError::Parse { message, .. } => {
+                assert!(message.contains("gateway.tool_timeout_ms"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_gateway_list_page_size() {
+        let toml = r#"
+[paths]
+state_dir = "/data"
+
+[gateway]
+list_page_size = 25
+"#;
+        let config = parse_toml(toml, Path::new("test.toml")).unwrap();
+        assert_eq!(config.infra.gateway.list_page_size, 25);
+
+        let toml = toml.replace("25", "-1");
+        let err = parse_toml(&toml, Path::new("test.toml")).unwrap_err();
+        match err {
+            ConfigError::Parse { message, .. } => {
+                assert!(message.contains("gateway.list_page_size"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_gateway_rate_limit() {
+        let toml = r#"
+[paths]
+state_dir = "/data"
+
+[gateway.rate_limit]
+enabled = true
+burst = 3
+per_second = 0.5
+tools = ["orpheus_generate", "musicgen_generate"]
+"#;
+        let config = parse_toml(toml, Path::new("test.toml")).unwrap();
+        let limit = &config.infra.gateway.rate_limit;
+        assert!(limit.enabled);
+        assert_eq!(limit.burst, 3);
+        assert_eq!(limit.per_second, 0.5);
+        assert_eq!(limit.tools, vec!["orpheus_generate", "musicgen_generate"]);
+
+        let reparsed = parse_toml(&config.to_toml(), Path::new("out.toml")).unwrap();
+        assert_eq!(reparsed.infra.gateway.rate_limit, *limit);
+
+        let toml = toml.replace("per_second = 0.5", "per_second = -1.0");
+        let err = parse_toml(&toml, Path::new("test.toml")).unwrap_err();
+        match err {
+            ConfigError::Parse { message, .. } => {
+                assert!(message.contains("gateway.rate_limit.per_second"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_bad_zmq_endpoint_names_field() {
+        let toml = r#"
+[paths]
+state_dir = "/data"
+
+[bind]
+zmq_router = "tcp//0.0.0.0:5580"
+"#;
+        let err = parse_toml(toml, Path::new("test.toml")).unwrap_err();
+        match err {
+            ConfigError::Parse { message, .. } => {
+                assert!(message.contains("bind.zmq_router"));
+                assert!(message.contains("tcp//0.0.0.0:5580"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_out_of_range_port_names_field() {
+        let toml = r#"
+[paths]
+state_dir = "/data"
+
+[gateway]
+http_port = 80800
+"#;
+        let err = parse_toml(toml, Path::new("test.toml")).unwrap_err();
+        assert!(err.to_string().contains("gateway.http_port"));
+    }
+
+    #[test]
+    fn test_unknown_keys_reported_with_paths() {
+        let toml = r#"
+[paths]
+state_dir = "/data"
+stat_dir = "/typo"
+
+[bootstrap.modls]
+orpheus = "http://gpu:2000"
+
+[bootstrap.models]
+anything_goes = "http://custom:3000"
+
+[bind.tls]
+enabeld = true
+
+[bootstrap.defaults]
+lua_timeout = "30s"
+"#;
+        let table = parse_table(toml, Path::new("test.toml")).unwrap();
+        let mut unknown = unknown_keys(&table);
+        unknown.sort();
+        assert_eq!(unknown, vec!["bind.tls.enabeld", "bootstrap.modls", "paths.stat_dir"]);
+    }
+
+    #[test]
+    fn test_generated_config_has_no_unknown_keys() {
+        let toml = HootConfig::default().to_toml();
+        let table = parse_table(&toml, Path::new("generated.toml")).unwrap();
+        assert_eq!(unknown_keys(&table), Vec::<String>::new());
     }
 }
@@ -14,7 +14,7 @@
 use crate::api::service::EventDualityServer;
 use hooteproto::{
     envelope::ResponseEnvelope, request::ToolRequest, responses::ToolResponse, timing::ToolTiming,
-    Payload, ToolError,
+    Payload, ToolError, Validate,
 };
 use std::sync::Arc;
 
@@ -30,11 +30,22 @@ impl TypedDispatcher {
 
     /// Main dispatch entry point - fully typed, no JSON.
     ///
-    /// Routes to appropriate handler based on timing classification.
+    /// Rejects requests that fail `Validate`, then routes to the appropriate
+    /// handler based on timing classification.
     pub async fn dispatch(&self, request: ToolRequest) -> ResponseEnvelope {
         let timing = request.timing();
         let name = request.name();
 
+        if let Err(e) = request.validate() {
+            tracing::debug!(
+                tool = name,
+                field = ?e.field,
+                "Rejecting invalid request: {}",
+                e.message
+            );
+            return ResponseEnvelope::error(ToolError::Validation(e));
+        }
+
         tracing::debug!(tool = name, ?timing, "Dispatching typed request");
 
         match timing {
@@ -759,6 +770,10 @@ impl TypedDispatcher {
                     Err(e) => ResponseEnvelope::error(e),
                 }
             }
+            ToolRequest::MusicAnalyze(_) => ResponseEnvelope::error(ToolError::validation(
+                "wrong_peer",
+                "music_analyze is served by analysis model workers, not hootenanny",
+            )),
 
             // === Graph / Time Utilities ===
             ToolRequest::GardenGraph => {
@@ -205,6 +205,11 @@ impl EventDualityServer {
     /// - Subscribes to IOPub events from garden_manager
     /// - Handles StreamChunkFull by rotating chunks
     /// - Logs StreamHeadPosition for monitoring
+    /// - Stores ArtifactCreated (e.g. finished bounces) in the artifact store
+    /// - Re-broadcasts ArtifactCreated, meter
+    ///   levels, transport changes and timeline markers to holler
+    /// - Re-broadcasts warnings (e.g. ring overruns) as warn-level logs
+    /// - Applies graph deltas to garden_manager's cached graph
     ///
     /// Must be called after garden_manager.start_event_listener().
     pub async fn start_stream_event_handler(&self) -> anyhow::Result<()> {
@@ -232,6 +237,8 @@ impl EventDualityServer {
 
         // Clone garden_manager for sending commands
         let garden_for_rotation = garden_manager.clone();
+        let broadcaster = self.broadcaster.clone();
+        let artifact_store = Arc::clone(&self.artifact_store);
 
         tokio::spawn(async move {
             while let Some(event) = events.next().await {
@@ -292,6 +299,101 @@ impl EventDualityServer {
                         }
                     }
 
+                    IOPubEvent::ArtifactCreated {
+                        artifact_id,
+                        content_hash,
+                        tags,
+                        creator,
+                        mime_type,
+                    } => {
+                        info!(artifact.id = %artifact_id, "Artifact created by chaosgarden");
+                        if let Err(e) = Self::store_garden_artifact(
+                            &artifact_store,
+                            &artifact_id,
+                            &content_hash,
+                            &tags,
+                            creator.as_deref(),
+                            mime_type.as_deref(),
+                        ) {
+                            warn!("Failed to store artifact {}: {}", artifact_id, e);
+                            continue;
+                        }
+                        if let Some(ref broadcaster) = broadcaster {
+                            if let Err(e) = broadcaster
+                                .artifact_created(&artifact_id, &content_hash, tags, creator)
+                                .await
+                            {
+                                warn!("Failed to broadcast artifact {}: {}", artifact_id, e);
+                            }
+                        }
+                    }
+
+                    IOPubEvent::MeterUpdate {
+                        channel,
+                        peak_db,
+                        rms_db,
+                    } => {
+                        if let Some(ref broadcaster) = broadcaster {
+                            let meter = hooteproto::Broadcast::MeterUpdate {
+                                channel,
+                                peak_db,
+                                rms_db,
+                            };
+                            if let Err(e) = broadcaster.publish(meter).await {
+                                debug!("Failed to broadcast meter update: {}", e);
+                            }
+                        }
+                    }
+
+                    IOPubEvent::TransportStateChanged {
+                        state,
+                        position_beats,
+                        tempo_bpm,
+                        beats_per_bar,
+                    } => {
+                        if let Some(ref broadcaster) = broadcaster {
+                            let transport = hooteproto::Broadcast::TransportStateChanged {
+                                state,
+                                position_beats,
+                                tempo_bpm,
+                                beats_per_bar,
+                            };
+                            if let Err(e) = broadcaster.publish(transport).await {
+                                debug!("Failed to broadcast transport state: {}", e);
+                            }
+                        }
+                    }
+
+                    IOPubEvent::MarkerReached {
+                        position_beats,
+                        marker_type,
+                        metadata,
+                    } => {
+                        if let Some(ref broadcaster) = broadcaster {
+                            let marker = hooteproto::Broadcast::MarkerReached {
+                                position_beats,
+                                marker_type,
+                                metadata,
+                            };
+                            if let Err(e) = broadcaster.publish(marker).await {
+                                debug!("Failed to broadcast marker: {}", e);
+                            }
+                        }
+                    }
+
+                    IOPubEvent::GraphDelta { delta } => {
+                        garden_manager.apply_graph_delta(&delta).await;
+                    }
+
+                    IOPubEvent::Warning { message } => {
+                        warn!("chaosgarden: {}", message);
+                        if let Some(ref broadcaster) = broadcaster {
+                            if let Err(e) = broadcaster.log("warn", &message, "chaosgarden").await {
+                                debug!("Failed to broadcast chaosgarden warning: {}", e);
+                            }
+                        }
+                    }
+
                     _ => {
                         // Ignore other event types
                     }
@@ -304,6 +406,69 @@ impl EventDualityServer {
         Ok(())
     }
 
+    /// Record an artifact chaosgarden created in the artifact store
+    ///
+    /// Its `parent:<hash>` tags name the content it was made from. The
+    /// oldest artifact holding the first such content becomes `parent_id`;
+    /// every artifact holding any of it is listed under `parents` in the
+    /// metadata.
+    fn store_garden_artifact(
+        artifact_store: &RwLock<FileStore>,
+        artifact_id: &str,
+        content_hash: &str,
+        tags: &[String],
+        creator: Option<&str>,
+        mime_type: Option<&str>,
+    ) -> anyhow::Result<()> {
+        use crate::artifact_store::{Artifact, ArtifactStore};
+        use crate::types::{ArtifactId, ContentHash};
+
+        let store = artifact_store
+            .write()
+            .map_err(|_| anyhow::anyhow!("artifact store lock poisoned"))?;
+
+        let parent_hashes: Vec<&str> = tags
+            .iter()
+            .filter_map(|tag| tag.strip_prefix("parent:"))
+            .collect();
+        let mut parents: Vec<ArtifactId> = Vec::new();
+        if !parent_hashes.is_empty() {
+            let mut existing = store.all()?;
+            existing.sort_by_key(|a| a.created_at);
+            for hash in &parent_hashes {
+                parents.extend(
+                    existing
+                        .iter()
+                        .filter(|a| {
+                            a.content_hash.as_str() == *hash && a.id.as_str() != artifact_id
+                        })
+                        .map(|a| a.id.clone()),
+                );
+            }
+        }
+
+        let mut metadata = serde_json::json!({
+            "source": "chaosgarden",
+            "parents": parents.iter().map(|p| p.as_str()).collect::<Vec<_>>(),
+        });
+        if let Some(mime_type) = mime_type {
+            metadata["mime_type"] = serde_json::json!(mime_type);
+        }
+
+        let mut artifact = Artifact::new(
+            ArtifactId::new(artifact_id),
+            ContentHash::new(content_hash),
+            creator.unwrap_or("chaosgarden"),
+            metadata,
+        )
+        .with_tags(tags.iter().cloned());
+        if let Some(parent) = parents.first() {
+            artifact = artifact.with_parent(parent.clone());
+        }
+
+        store.put(artifact)
+    }
+
     /// Handle chunk rotation when a chunk becomes full
     ///
     /// Steps:
@@ -363,3 +528,54 @@ impl EventDualityServer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact_store::{Artifact, ArtifactStore};
+    use crate::types::{ArtifactId, ContentHash};
+
+    #[tokio::test]
+    async fn test_garden_artifact_is_fetchable_with_lineage() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cas = cas::FileStore::at_path(temp.path().join("cas")).unwrap();
+        let artifact_store = Arc::new(RwLock::new(
+            FileStore::new(temp.path().join("artifacts.json")).unwrap(),
+        ));
+        let source = Artifact::new(
+            ArtifactId::new("artifact_source"),
+            ContentHash::new("source_hash"),
+            "test",
+            serde_json::json!({}),
+        );
+        artifact_store.read().unwrap().put(source).unwrap();
+
+        let tags = vec![
+            "type:audio".to_string(),
+            "bounce:master".to_string(),
+            "parent:source_hash".to_string(),
+            "parent:unknown_hash".to_string(),
+        ];
+        EventDualityServer::store_garden_artifact(
+            &artifact_store,
+            "artifact_bounce",
+            "bounce_hash",
+            &tags,
+            Some("chaosgarden"),
+            Some("audio/wav"),
+        )
+        .unwrap();
+
+        let server = EventDualityServer::new(
+            cas,
+            artifact_store,
+            Arc::new(JobStore::new()),
+            Arc::new(GpuMonitor::new()),
+        );
+        let info = server.artifact_get_typed("artifact_bounce").await.unwrap();
+        assert_eq!(info.content_hash, "bounce_hash");
+        assert_eq!(info.parent_id.as_deref(), Some("artifact_source"));
+        assert_eq!(info.tags, tags);
+        assert_eq!(info.creator, "chaosgarden");
+    }
+}
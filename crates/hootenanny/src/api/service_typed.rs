@@ -314,7 +314,7 @@ impl EventDualityServer {
         }
     }
 
-    /// Return the audio processing graph (nodes + edges) from a GardenSnapshot
+    /// Return the audio processing graph (nodes + edges), kept current by IOPub deltas
     pub async fn garden_graph_typed(
         &self,
     ) -> Result<hooteproto::responses::GardenGraphResponse, ToolError> {
@@ -322,15 +322,10 @@ impl EventDualityServer {
             ToolError::validation("not_connected", "Not connected to chaosgarden")
         })?;
 
-        let snapshot = manager
-            .get_snapshot()
+        manager
+            .graph()
             .await
-            .map_err(|e| ToolError::service("chaosgarden", "snapshot_failed", e.to_string()))?;
-
-        Ok(hooteproto::responses::GardenGraphResponse {
-            nodes: snapshot.nodes,
-            edges: snapshot.edges,
-        })
+            .map_err(|e| ToolError::service("chaosgarden", "graph_failed", e.to_string()))
     }
 
     /// Convert between beats and seconds using the current tempo map
@@ -1476,6 +1471,7 @@ impl EventDualityServer {
             ticks_per_beat: 480,
             channel: channel.unwrap_or(0),
             program: None, // Use default (piano) - abc_to_midi doesn't have program param yet
+            ..Default::default()
         };
 
         // Generate MIDI bytes
@@ -4262,13 +4258,14 @@ impl EventDualityServer {
         let key_str = format!("{} {}", understanding.key.root, understanding.key.mode);
         let meter_str = format!("{}/{}", understanding.meter.numerator, understanding.meter.denominator);
         let summary = format!(
-            "Key: {} (confidence {:.2}), Meter: {} (confidence {:.2}), {} chords, {} voices",
+            "Key: {} (confidence {:.2}), Meter: {} (confidence {:.2}), {} chords, {} voices\nProgression: {}",
             key_str,
             understanding.key.confidence,
             meter_str,
             understanding.meter.confidence,
             understanding.chords.len(),
             understanding.voices.len(),
+            understanding.progression_string(),
         );
 
         Ok(hooteproto::responses::MidiUnderstoodResponse {
@@ -4313,6 +4310,7 @@ impl EventDualityServer {
             "pitch_contiguity" => Some(midi_analysis::SeparationMethod::PitchContiguity),
             "skyline" => Some(midi_analysis::SeparationMethod::Skyline),
             "bassline" => Some(midi_analysis::SeparationMethod::Bassline),
+            "drum_kit" => Some(midi_analysis::SeparationMethod::DrumKit),
             _ => None,
         });
 
@@ -4709,6 +4707,7 @@ impl EventDualityServer {
                                     features: features.get(i).cloned()
                                         .unwrap_or_default(),
                                     alternative_roles,
+                                    sub_label: None,
                                 }
                             })
                             .collect();
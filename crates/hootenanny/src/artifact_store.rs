@@ -8,14 +8,17 @@
 //! - Every artifact has tags (arbitrary metadata)
 //! - Access tracking for observability
 //! - Annotations for subjective descriptions (vibe, mood, etc.)
+//! - Server-side queries via [`ArtifactFilter`], backed by an in-memory tag index
 
 use crate::types::{ArtifactId, ContentHash, VariationSetId};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, RwLock};
 
 /// Annotation data transfer object.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -228,6 +231,86 @@ impl Artifact {
     pub fn phase(&self) -> Option<&str> {
         self.tags_with_prefix("phase:").first().copied()
     }
+
+    /// MIME type recorded in metadata by the tool that created this artifact
+    pub fn mime_type(&self) -> Option<&str> {
+        self.metadata.get("mime_type").and_then(|v| v.as_str())
+    }
+}
+
+/// Result ordering for [`ArtifactFilter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactSort {
+    /// Most recently created first
+    #[default]
+    Newest,
+    /// Oldest first
+    Oldest,
+    /// Highest access count first
+    MostAccessed,
+    /// Most recently accessed first (never-accessed last)
+    RecentlyAccessed,
+}
+
+/// Server-side artifact query. Empty fields don't constrain the result.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactFilter {
+    /// Artifact must carry every one of these tags
+    pub tags: Vec<String>,
+    pub creator: Option<String>,
+    /// Exact MIME type, or a prefix wildcard like `audio/*`
+    pub mime_type: Option<String>,
+    pub variation_set_id: Option<String>,
+    /// Inclusive lower bound on `created_at`
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`
+    pub created_before: Option<DateTime<Utc>>,
+    pub sort: ArtifactSort,
+    pub limit: Option<usize>,
+}
+
+impl ArtifactFilter {
+    /// Check everything except tags, which the index handles
+    fn matches(&self, artifact: &Artifact) -> bool {
+        if self.creator.as_ref().is_some_and(|c| &artifact.creator != c) {
+            return false;
+        }
+        if let Some(ref wanted) = self.mime_type {
+            let Some(mime) = artifact.mime_type() else {
+                return false;
+            };
+            let matched = match wanted.strip_suffix('*') {
+                Some(prefix) => mime.starts_with(prefix),
+                None => mime == wanted,
+            };
+            if !matched {
+                return false;
+            }
+        }
+        if let Some(ref set_id) = self.variation_set_id {
+            if artifact.variation_set_id.as_ref().map(|s| s.as_str()) != Some(set_id.as_str()) {
+                return false;
+            }
+        }
+        if self.created_after.is_some_and(|t| artifact.created_at < t) {
+            return false;
+        }
+        if self.created_before.is_some_and(|t| artifact.created_at >= t) {
+            return false;
+        }
+        true
+    }
+
+    fn sort(&self, artifacts: &mut [Artifact]) {
+        use std::cmp::Reverse;
+        match self.sort {
+            ArtifactSort::Newest => artifacts.sort_by_key(|a| Reverse(a.created_at)),
+            ArtifactSort::Oldest => artifacts.sort_by_key(|a| a.created_at),
+            ArtifactSort::MostAccessed => artifacts.sort_by_key(|a| Reverse(a.access_count)),
+            ArtifactSort::RecentlyAccessed => artifacts.sort_by_key(|a| Reverse(a.last_accessed)),
+        }
+    }
 }
 
 /// Trait for artifact storage backends
@@ -272,26 +355,111 @@ pub trait ArtifactStore: Send + Sync {
     }
 }
 
+/// Artifact IDs by tag
+type TagIndex = HashMap<String, HashSet<String>>;
+
 /// In-memory artifact store (HashMap-backed)
+///
+/// Keeps a tag → IDs index alongside the artifacts. It is only written
+/// while the artifacts write lock is held, so the two never disagree.
 #[derive(Debug)]
 pub struct InMemoryStore {
     artifacts: RwLock<HashMap<String, Artifact>>,
+    by_tag: RwLock<TagIndex>,
 }
 
 impl InMemoryStore {
     pub fn new() -> Self {
         Self {
             artifacts: RwLock::new(HashMap::new()),
+            by_tag: RwLock::new(HashMap::new()),
         }
     }
 
     pub fn from_artifacts(artifacts: Vec<Artifact>) -> Self {
+        let mut by_tag = TagIndex::new();
+        for artifact in &artifacts {
+            index_tags(&mut by_tag, artifact);
+        }
         let map = artifacts
             .into_iter()
             .map(|a| (a.id.as_str().to_string(), a))
             .collect();
         Self {
             artifacts: RwLock::new(map),
+            by_tag: RwLock::new(by_tag),
+        }
+    }
+
+    /// Artifacts matching `filter`, sorted and limited as it asks
+    pub fn query(&self, filter: &ArtifactFilter) -> Result<Vec<Artifact>> {
+        let artifacts = self
+            .artifacts
+            .read()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned in artifact_store.query: {}", e))?;
+
+        let mut matched: Vec<Artifact> = if filter.tags.is_empty() {
+            artifacts
+                .values()
+                .filter(|a| filter.matches(a))
+                .cloned()
+                .collect()
+        } else {
+            let by_tag = self
+                .by_tag
+                .read()
+                .map_err(|e| anyhow::anyhow!("Lock poisoned in artifact_store.query: {}", e))?;
+
+            // Walk the rarest tag's IDs and check the rest against the index
+            let mut sets = Vec::with_capacity(filter.tags.len());
+            for tag in &filter.tags {
+                match by_tag.get(tag) {
+                    Some(ids) => sets.push(ids),
+                    None => return Ok(Vec::new()),
+                }
+            }
+            sets.sort_by_key(|ids| ids.len());
+            let (smallest, rest) = sets.split_first().expect("tags is non-empty");
+
+            smallest
+                .iter()
+                .filter(|id| rest.iter().all(|ids| ids.contains(*id)))
+                .filter_map(|id| artifacts.get(id))
+                .filter(|a| filter.matches(a))
+                .cloned()
+                .collect()
+        };
+
+        filter.sort(&mut matched);
+        if let Some(limit) = filter.limit {
+            matched.truncate(limit);
+        }
+        Ok(matched)
+    }
+
+    fn tag_index(&self, op: &str) -> Result<std::sync::RwLockWriteGuard<'_, TagIndex>> {
+        self.by_tag
+            .write()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned in artifact_store.{}: {}", op, e))
+    }
+}
+
+fn index_tags(by_tag: &mut TagIndex, artifact: &Artifact) {
+    for tag in &artifact.tags {
+        by_tag
+            .entry(tag.clone())
+            .or_default()
+            .insert(artifact.id.as_str().to_string());
+    }
+}
+
+fn unindex_tags(by_tag: &mut TagIndex, artifact: &Artifact) {
+    for tag in &artifact.tags {
+        if let Some(ids) = by_tag.get_mut(tag) {
+            ids.remove(artifact.id.as_str());
+            if ids.is_empty() {
+                by_tag.remove(tag);
+            }
         }
     }
 }
@@ -316,7 +484,14 @@ impl ArtifactStore for InMemoryStore {
             .artifacts
             .write()
             .map_err(|e| anyhow::anyhow!("Lock poisoned in artifact_store.put: {}", e))?;
-        artifacts.insert(artifact.id.as_str().to_string(), artifact);
+        let mut by_tag = self.tag_index("put")?;
+        let id = artifact.id.as_str().to_string();
+        // Un-index the old version so tags dropped by an update stop matching
+        if let Some(old) = artifacts.get(&id) {
+            unindex_tags(&mut by_tag, old);
+        }
+        index_tags(&mut by_tag, &artifact);
+        artifacts.insert(id, artifact);
         Ok(())
     }
 
@@ -325,7 +500,13 @@ impl ArtifactStore for InMemoryStore {
             .artifacts
             .write()
             .map_err(|e| anyhow::anyhow!("Lock poisoned in artifact_store.delete: {}", e))?;
-        Ok(artifacts.remove(id).is_some())
+        match artifacts.remove(id) {
+            Some(old) => {
+                unindex_tags(&mut *self.tag_index("delete")?, &old);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
     }
 
     fn all(&self) -> Result<Vec<Artifact>> {
@@ -353,12 +534,37 @@ impl ArtifactStore for InMemoryStore {
     }
 }
 
+/// Flushes append to the journal until it holds this many entries, then
+/// the next flush compacts everything into a fresh snapshot.
+const JOURNAL_COMPACT_AFTER: usize = 1000;
+
+/// One change recorded in the append-only journal
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalEntry {
+    Put { artifact: Artifact },
+    Delete { id: String },
+}
+
 /// File-backed artifact store (JSON + InMemoryStore)
+///
+/// On disk: an `artifacts.json` snapshot, the previous snapshot as
+/// `artifacts.bak`, and `artifacts.journal` with one JSON change per line
+/// since the snapshot. Flushes append to the journal instead of rewriting
+/// the snapshot; snapshots are written to a temp file and renamed into
+/// place. Loading falls back to the backup if the snapshot is unreadable
+/// and stops replaying the journal at a torn final line.
 pub struct FileStore {
     path: PathBuf,
     store: InMemoryStore,
     annotations_path: PathBuf,
     annotations: RwLock<Vec<AnnotationData>>,
+    annotations_dirty: AtomicBool,
+    journal_path: PathBuf,
+    /// Changes since the last flush
+    pending: Mutex<Vec<JournalEntry>>,
+    /// Entries in the journal file
+    journal_len: AtomicUsize,
 }
 
 impl FileStore {
@@ -366,30 +572,33 @@ impl FileStore {
     pub fn new(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref().to_path_buf();
 
-        let artifacts = if path.exists() {
-            let json = std::fs::read_to_string(&path)?;
-            serde_json::from_str::<Vec<Artifact>>(&json)?
-        } else {
-            Vec::new()
-        };
+        let artifacts: Vec<Artifact> = read_json_with_backup(&path)?.unwrap_or_default();
+        let store = InMemoryStore::from_artifacts(artifacts);
+
+        let journal_path = path.with_extension("journal");
+        let journal_len = replay_journal(&journal_path, &store)?;
 
         // Annotations file is alongside artifacts file
         let annotations_path = path.with_file_name("annotations.json");
-        let annotations = if annotations_path.exists() {
-            let json = std::fs::read_to_string(&annotations_path)?;
-            serde_json::from_str::<Vec<AnnotationData>>(&json)?
-        } else {
-            Vec::new()
-        };
+        let annotations = read_json_with_backup(&annotations_path)?.unwrap_or_default();
 
         Ok(Self {
             path,
-            store: InMemoryStore::from_artifacts(artifacts),
+            store,
             annotations_path,
             annotations: RwLock::new(annotations),
+            annotations_dirty: AtomicBool::new(false),
+            journal_path,
+            pending: Mutex::new(Vec::new()),
+            journal_len: AtomicUsize::new(journal_len),
         })
     }
 
+    /// Artifacts matching `filter`, using the in-memory tag index
+    pub fn query(&self, filter: &ArtifactFilter) -> Result<Vec<Artifact>> {
+        self.store.query(filter)
+    }
+
     /// Add an annotation to an artifact
     pub fn add_annotation(&self, annotation: AnnotationData) -> anyhow::Result<()> {
         let mut annotations = self
@@ -397,6 +606,7 @@ impl FileStore {
             .write()
             .map_err(|e| anyhow::anyhow!("Lock poisoned in add_annotation: {}", e))?;
         annotations.push(annotation);
+        self.annotations_dirty.store(true, Ordering::Release);
         Ok(())
     }
 
@@ -413,34 +623,199 @@ impl FileStore {
             .collect())
     }
 
-    /// Save to disk
+    /// Write a full snapshot and truncate the journal
     pub fn save(&self) -> Result<()> {
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned in file_store.save: {}", e))?;
+
         let artifacts = self.store.all()?;
         let json = serde_json::to_string_pretty(&artifacts)?;
+        write_atomic(&self.path, json.as_bytes())?;
 
-        // Ensure parent directory exists
-        if let Some(parent) = self.path.parent() {
-            std::fs::create_dir_all(parent)?;
+        // The snapshot now covers everything journaled so far
+        match std::fs::remove_file(&self.journal_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
         }
+        pending.clear();
+        self.journal_len.store(0, Ordering::Release);
 
-        // Atomic write: write to temp, then rename
-        let temp_path = self.path.with_extension("tmp");
-        std::fs::write(&temp_path, json)?;
-        std::fs::rename(&temp_path, &self.path)?;
+        self.save_annotations()
+    }
 
-        // Save annotations
+    /// Append pending changes to the journal, compacting when it grows long
+    fn append_journal(&self) -> Result<()> {
+        let mut pending = self
+            .pending
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned in file_store.flush: {}", e))?;
+
+        if self.journal_len.load(Ordering::Acquire) + pending.len() > JOURNAL_COMPACT_AFTER {
+            drop(pending);
+            return self.save();
+        }
+
+        if !pending.is_empty() {
+            let mut lines = String::new();
+            for entry in pending.iter() {
+                lines.push_str(&serde_json::to_string(entry)?);
+                lines.push('\n');
+            }
+
+            if let Some(parent) = self.journal_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut journal = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.journal_path)?;
+            journal.write_all(lines.as_bytes())?;
+            journal.sync_data()?;
+
+            self.journal_len.fetch_add(pending.len(), Ordering::AcqRel);
+            pending.clear();
+        }
+        drop(pending);
+
+        self.save_annotations()
+    }
+
+    fn save_annotations(&self) -> Result<()> {
+        if !self.annotations_dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
         let annotations = self.annotations.read().map_err(|e| {
             anyhow::anyhow!("Lock poisoned in file_store.save (annotations): {}", e)
         })?;
-        let annotations_json = serde_json::to_string_pretty(&*annotations)?;
-        let annotations_temp = self.annotations_path.with_extension("tmp");
-        std::fs::write(&annotations_temp, annotations_json)?;
-        std::fs::rename(&annotations_temp, &self.annotations_path)?;
-
+        let json = serde_json::to_string_pretty(&*annotations)?;
+        if let Err(e) = write_atomic(&self.annotations_path, json.as_bytes()) {
+            self.annotations_dirty.store(true, Ordering::Release);
+            return Err(e);
+        }
         Ok(())
     }
+
+    /// Pending journal entries. Hold this across a store change and its
+    /// entry, so concurrent changes are journaled in the order applied.
+    fn pending(&self) -> Result<MutexGuard<'_, Vec<JournalEntry>>> {
+        self.pending
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned in file_store.pending: {}", e))
+    }
+}
+
+/// Backup path kept alongside a snapshot
+fn backup_path(path: &Path) -> PathBuf {
+    path.with_extension("bak")
+}
+
+/// Write via temp file + fsync + rename, keeping the old file as `.bak`.
+fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = path.with_extension("tmp");
+    {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+    }
+
+    // A crash between these renames leaves only the backup, which
+    // read_json_with_backup picks up
+    if path.exists() {
+        std::fs::rename(path, backup_path(path))?;
+    }
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
 }
 
+/// Read a JSON snapshot, falling back to its `.bak` if missing or unparseable.
+///
+/// Returns `Ok(None)` when neither file exists.
+fn read_json_with_backup<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    let read = |p: &Path| -> Result<Option<T>> {
+        if !p.exists() {
+            return Ok(None);
+        }
+        let json = std::fs::read_to_string(p)?;
+        Ok(Some(serde_json::from_str(&json)?))
+    };
+
+    let primary_err = match read(path) {
+        Ok(Some(value)) => return Ok(Some(value)),
+        Ok(None) => None,
+        Err(e) => Some(e),
+    };
+
+    let backup = backup_path(path);
+    match read(&backup) {
+        Ok(Some(value)) => {
+            match &primary_err {
+                Some(e) => tracing::warn!(
+                    "Recovered {} from {} after read failure: {}",
+                    path.display(),
+                    backup.display(),
+                    e
+                ),
+                None => tracing::warn!(
+                    "{} missing, recovered from {}",
+                    path.display(),
+                    backup.display()
+                ),
+            }
+            Ok(Some(value))
+        }
+        _ => match primary_err {
+            Some(e) => Err(e.context(format!("Failed to load {}", path.display()))),
+            None => Ok(None),
+        },
+    }
+}
+
+/// Apply journaled changes on top of a loaded snapshot, returning how many
+/// entries were replayed.
+fn replay_journal(path: &Path, store: &InMemoryStore) -> Result<usize> {
+    let journal = match std::fs::read_to_string(path) {
+        Ok(journal) => journal,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut replayed = 0;
+    for (line_no, line) in journal.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<JournalEntry>(line) {
+            Ok(JournalEntry::Put { artifact }) => store.put(artifact)?,
+            Ok(JournalEntry::Delete { id }) => {
+                store.delete(&id)?;
+            }
+            Err(e) => {
+                // Only a torn final append should land here; nothing after
+                // it can be trusted either way
+                tracing::warn!(
+                    "Stopped replaying {} at line {}: {}",
+                    path.display(),
+                    line_no + 1,
+                    e
+                );
+                break;
+            }
+        }
+        replayed += 1;
+    }
+
+    if replayed > 0 {
+        tracing::info!("Replayed {} journal entries from {}", replayed, path.display());
+    }
+    Ok(replayed)
+}
 
 impl ArtifactStore for FileStore {
     fn get(&self, id: &str) -> Result<Option<Artifact>> {
@@ -448,11 +823,19 @@ impl ArtifactStore for FileStore {
     }
 
     fn put(&self, artifact: Artifact) -> Result<()> {
-        self.store.put(artifact)
+        let mut pending = self.pending()?;
+        self.store.put(artifact.clone())?;
+        pending.push(JournalEntry::Put { artifact });
+        Ok(())
     }
 
     fn delete(&self, id: &str) -> Result<bool> {
-        self.store.delete(id)
+        let mut pending = self.pending()?;
+        let deleted = self.store.delete(id)?;
+        if deleted {
+            pending.push(JournalEntry::Delete { id: id.to_string() });
+        }
+        Ok(deleted)
     }
 
     fn all(&self) -> Result<Vec<Artifact>> {
@@ -468,7 +851,7 @@ impl ArtifactStore for FileStore {
     }
 
     fn flush(&self) -> Result<()> {
-        self.save()
+        self.append_journal()
     }
 }
 
@@ -703,6 +1086,108 @@ mod tests {
 
         // Cleanup
         std::fs::remove_file(&path).ok();
+        std::fs::remove_file(path.with_extension("journal")).ok();
+    }
+
+    fn test_artifact(id: &str) -> Artifact {
+        Artifact::new(
+            ArtifactId::new(id),
+            ContentHash::new(format!("{:0<32}", id)),
+            "agent",
+            json!({}),
+        )
+    }
+
+    #[test]
+    fn test_file_store_journal_replay() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("artifacts.json");
+
+        {
+            let store = FileStore::new(&path).unwrap();
+            store.put(test_artifact("keep")).unwrap();
+            store.put(test_artifact("drop")).unwrap();
+            store.flush().unwrap();
+            store.delete("drop").unwrap();
+            store.put(test_artifact("keep").with_tag("updated")).unwrap();
+            store.flush().unwrap();
+        }
+
+        // Flushes only appended; no snapshot was rewritten
+        assert!(!path.exists());
+        let journal = path.with_extension("journal");
+        assert_eq!(std::fs::read_to_string(&journal).unwrap().lines().count(), 4);
+
+        // A torn final append is ignored
+        let mut file = std::fs::OpenOptions::new().append(true).open(&journal).unwrap();
+        file.write_all(b"{\"op\":\"put\",\"artif").unwrap();
+        drop(file);
+
+        let store = FileStore::new(&path).unwrap();
+        assert_eq!(ArtifactStore::count(&store).unwrap(), 1);
+        assert!(ArtifactStore::get(&store, "keep").unwrap().unwrap().has_tag("updated"));
+
+        // Compaction folds the journal into the snapshot
+        store.save().unwrap();
+        assert!(!journal.exists());
+        let store = FileStore::new(&path).unwrap();
+        assert_eq!(ArtifactStore::count(&store).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_file_store_journals_concurrent_puts_in_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("artifacts.json");
+        let store = std::sync::Arc::new(FileStore::new(&path).unwrap());
+
+        for round in 0..50 {
+            let writers: Vec<_> = (0..4)
+                .map(|writer| {
+                    let store = std::sync::Arc::clone(&store);
+                    std::thread::spawn(move || {
+                        let tag = format!("write:{}:{}", round, writer);
+                        store.put(test_artifact("contended").with_tag(tag)).unwrap();
+                    })
+                })
+                .collect();
+            for writer in writers {
+                writer.join().unwrap();
+            }
+            store.flush().unwrap();
+
+            // Replaying the journal lands on the write the store kept
+            let live = ArtifactStore::get(&*store, "contended").unwrap().unwrap();
+            let reloaded = FileStore::new(&path).unwrap();
+            let replayed = ArtifactStore::get(&reloaded, "contended").unwrap().unwrap();
+            assert_eq!(replayed.tags, live.tags, "round {}", round);
+        }
+    }
+
+    #[test]
+    fn test_file_store_recovers_from_backup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("artifacts.json");
+
+        {
+            let store = FileStore::new(&path).unwrap();
+            store.put(test_artifact("first")).unwrap();
+            store.save().unwrap();
+            store.put(test_artifact("second")).unwrap();
+            store.save().unwrap();
+        }
+        assert!(path.with_extension("bak").exists());
+
+        // Simulate a crash that left the snapshot half-written
+        let json = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &json[..json.len() / 2]).unwrap();
+
+        let store = FileStore::new(&path).unwrap();
+        assert_eq!(ArtifactStore::count(&store).unwrap(), 1);
+        assert!(ArtifactStore::exists(&store, "first").unwrap());
+
+        // Without a backup, corruption is still an error
+        std::fs::remove_file(path.with_extension("bak")).unwrap();
+        assert!(FileStore::new(&path).is_err());
     }
 
     #[test]
@@ -770,4 +1255,165 @@ mod tests {
         // Next index should be 3
         assert_eq!(store.next_variation_index("vset_exploration").unwrap(), 3);
     }
+
+    fn query_fixture() -> InMemoryStore {
+        let base: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+        let make = |id: &str, creator: &str, mime: &str, hours: i64, tags: &[&str]| {
+            let mut artifact = Artifact::new(
+                ArtifactId::new(id),
+                ContentHash::new(format!("{:0<32}", id)),
+                creator,
+                json!({"mime_type": mime}),
+            )
+            .with_tags(tags.iter().copied());
+            artifact.created_at = base + chrono::Duration::hours(hours);
+            artifact
+        };
+
+        let store = InMemoryStore::new();
+        store
+            .put(make("midi_a", "agent_a", "audio/midi", 0, &["type:midi", "phase:draft"])
+                .with_variation_set(VariationSetId::new("vset_1"), 0))
+            .unwrap();
+        store
+            .put(make("midi_b", "agent_b", "audio/midi", 1, &["type:midi", "phase:final"])
+                .with_variation_set(VariationSetId::new("vset_1"), 1))
+            .unwrap();
+        store
+            .put(make("wav_c", "agent_a", "audio/wav", 2, &["type:audio", "phase:final"]))
+            .unwrap();
+        store
+            .put(make("abc_d", "agent_b", "text/vnd.abc", 3, &["type:abc"]))
+            .unwrap();
+        store
+    }
+
+    fn ids(artifacts: &[Artifact]) -> Vec<&str> {
+        artifacts.iter().map(|a| a.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_by_tags() {
+        let store = query_fixture();
+
+        let filter = ArtifactFilter {
+            tags: vec!["type:midi".into()],
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&filter).unwrap()), vec!["midi_b", "midi_a"]);
+
+        let filter = ArtifactFilter {
+            tags: vec!["phase:final".into(), "type:midi".into()],
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&filter).unwrap()), vec!["midi_b"]);
+
+        let filter = ArtifactFilter {
+            tags: vec!["type:nope".into()],
+            ..Default::default()
+        };
+        assert!(store.query(&filter).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_query_tag_index_follows_updates() {
+        let store = query_fixture();
+        let mut retagged = store.get("wav_c").unwrap().unwrap().with_tag("keeper");
+        retagged.tags.retain(|t| t != "phase:final");
+        store.put(retagged).unwrap();
+        store.delete("midi_b").unwrap();
+
+        let final_phase = ArtifactFilter {
+            tags: vec!["phase:final".into()],
+            ..Default::default()
+        };
+        assert!(store.query(&final_phase).unwrap().is_empty());
+
+        let keeper = ArtifactFilter {
+            tags: vec!["keeper".into()],
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&keeper).unwrap()), vec!["wav_c"]);
+    }
+
+    #[test]
+    fn test_query_by_creator() {
+        let store = query_fixture();
+        let filter = ArtifactFilter {
+            creator: Some("agent_a".into()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&filter).unwrap()), vec!["wav_c", "midi_a"]);
+    }
+
+    #[test]
+    fn test_query_by_mime_type() {
+        let store = query_fixture();
+
+        let exact = ArtifactFilter {
+            mime_type: Some("audio/midi".into()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&exact).unwrap()), vec!["midi_b", "midi_a"]);
+
+        let wildcard = ArtifactFilter {
+            mime_type: Some("audio/*".into()),
+            ..Default::default()
+        };
+        assert_eq!(store.query(&wildcard).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_query_by_variation_set() {
+        let store = query_fixture();
+        let filter = ArtifactFilter {
+            variation_set_id: Some("vset_1".into()),
+            sort: ArtifactSort::Oldest,
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&filter).unwrap()), vec!["midi_a", "midi_b"]);
+    }
+
+    #[test]
+    fn test_query_by_time_range() {
+        let store = query_fixture();
+        let filter = ArtifactFilter {
+            created_after: Some("2024-01-01T01:00:00Z".parse().unwrap()),
+            created_before: Some("2024-01-01T03:00:00Z".parse().unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&filter).unwrap()), vec!["wav_c", "midi_b"]);
+    }
+
+    #[test]
+    fn test_query_sort_and_limit() {
+        let store = query_fixture();
+        let mut popular = store.get("abc_d").unwrap().unwrap();
+        popular.record_access();
+        popular.record_access();
+        store.put(popular).unwrap();
+        let mut touched = store.get("midi_a").unwrap().unwrap();
+        touched.record_access();
+        store.put(touched).unwrap();
+
+        let newest = ArtifactFilter {
+            limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&newest).unwrap()), vec!["abc_d", "wav_c"]);
+
+        let most_accessed = ArtifactFilter {
+            sort: ArtifactSort::MostAccessed,
+            limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&most_accessed).unwrap()), vec!["abc_d", "midi_a"]);
+
+        let recently_accessed = ArtifactFilter {
+            sort: ArtifactSort::RecentlyAccessed,
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(ids(&store.query(&recently_accessed).unwrap()), vec!["midi_a"]);
+    }
 }
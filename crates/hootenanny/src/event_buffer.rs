@@ -172,6 +172,7 @@ impl EventBuffer {
     /// Push a broadcast into the buffer
     ///
     /// Beat ticks are stored separately (latest only).
+    /// Meter updates are dropped (live-only).
     /// Transport state and device count are tracked separately.
     /// All other events go into the ring buffer.
     pub fn push(&mut self, broadcast: &Broadcast) {
@@ -193,11 +194,17 @@ impl EventBuffer {
             return;
         }
 
+        // Meter levels are live-only; replaying stale levels is meaningless
+        if matches!(broadcast, Broadcast::MeterUpdate { .. }) {
+            return;
+        }
+
         // Track transport state changes
         if let Broadcast::TransportStateChanged {
             state,
             position_beats,
             tempo_bpm,
+            ..
         } = broadcast
         {
             self.latest_transport = Some(TransportInfo {
@@ -455,6 +462,7 @@ fn broadcast_type_name(broadcast: &Broadcast) -> &'static str {
         Broadcast::Log { .. } => "log",
         Broadcast::DeviceConnected { .. } => "device_connected",
         Broadcast::DeviceDisconnected { .. } => "device_disconnected",
+        Broadcast::MeterUpdate { .. } => "meter_update",
     }
 }
 
@@ -491,6 +499,7 @@ pub fn validate_poll_params(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hooteproto::TagSet;
 
     #[test]
     fn test_push_and_poll() {
@@ -505,7 +514,7 @@ mod tests {
         buffer.push(&Broadcast::ArtifactCreated {
             artifact_id: "art1".to_string(),
             content_hash: "hash1".to_string(),
-            tags: vec!["test".to_string()],
+            tags: vec!["test".to_string()].into(),
             creator: Some("claude".to_string()),
         });
 
@@ -548,7 +557,7 @@ mod tests {
         buffer.push(&Broadcast::ArtifactCreated {
             artifact_id: "art1".to_string(),
             content_hash: "hash1".to_string(),
-            tags: vec![],
+            tags: TagSet::new(),
             creator: None,
         });
         buffer.push(&Broadcast::JobStateChanged {
@@ -590,6 +599,20 @@ mod tests {
         assert_eq!(result.latest_beat.unwrap().beat, 100);
     }
 
+    #[test]
+    fn test_meter_updates_not_buffered() {
+        let mut buffer = EventBuffer::new(100);
+
+        buffer.push(&Broadcast::MeterUpdate {
+            channel: "monitor".to_string(),
+            peak_db: -3.0,
+            rms_db: -9.0,
+        });
+
+        let result = buffer.poll(None, None, None, 100).unwrap();
+        assert!(result.events.is_empty());
+    }
+
     #[test]
     fn test_ring_buffer_eviction() {
         let mut buffer = EventBuffer::new(5);
@@ -672,6 +695,7 @@ mod tests {
             state: "playing".to_string(),
             position_beats: 100.5,
             tempo_bpm: 120.0,
+            beats_per_bar: 4,
         });
 
         // Latest transport should be tracked
@@ -748,7 +772,7 @@ mod tests {
         buffer.push(&Broadcast::ArtifactCreated {
             artifact_id: "art1".to_string(),
             content_hash: "hash1".to_string(),
-            tags: vec![],
+            tags: TagSet::new(),
             creator: None,
         });
         buffer.push(&Broadcast::JobStateChanged {
@@ -874,6 +898,7 @@ mod tests {
             state: "playing".to_string(),
             position_beats: 42.5,
             tempo_bpm: 120.0,
+            beats_per_bar: 4,
         });
 
         let transport = buffer.latest_transport().unwrap();
@@ -914,6 +939,7 @@ mod tests {
             state: "stopped".to_string(),
             position_beats: 0.0,
             tempo_bpm: 120.0,
+            beats_per_bar: 4,
         });
         assert_eq!(buffer.latest_transport().unwrap().state, "stopped");
 
@@ -922,6 +948,7 @@ mod tests {
             state: "playing".to_string(),
             position_beats: 0.0,
             tempo_bpm: 120.0,
+            beats_per_bar: 4,
         });
         assert_eq!(buffer.latest_transport().unwrap().state, "playing");
 
@@ -930,6 +957,7 @@ mod tests {
             state: "paused".to_string(),
             position_beats: 16.0,
             tempo_bpm: 120.0,
+            beats_per_bar: 4,
         });
         let transport = buffer.latest_transport().unwrap();
         assert_eq!(transport.state, "paused");
@@ -1,9 +1,16 @@
 #![allow(dead_code)]
 
+use crate::zmq::BroadcastPublisher;
 use anyhow::{Context, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+
+/// How far back sampled history is kept
+const HISTORY_RETENTION: Duration = Duration::from_secs(10 * 60);
 
 /// GPU and system status from the observer service (localhost:2099)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,10 +89,48 @@ pub struct Trends {
     pub activity: String,
 }
 
+/// One point in the rolling GPU history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpuSample {
+    pub taken_at: SystemTime,
+    pub vram_used_gb: f64,
+    pub vram_total_gb: f64,
+    pub util_pct: f64,
+}
+
+impl GpuSample {
+    pub fn from_status(status: &GpuStatus) -> Self {
+        Self {
+            taken_at: SystemTime::now(),
+            vram_used_gb: status.vram_used_gb,
+            vram_total_gb: status.vram_total_gb,
+            util_pct: status.util_pct,
+        }
+    }
+
+    /// VRAM in use as a percentage of total (0 when the total is unknown)
+    pub fn vram_pct(&self) -> f64 {
+        if self.vram_total_gb > 0.0 {
+            self.vram_used_gb / self.vram_total_gb * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Background sampling settings (`bootstrap.defaults.gpu_*`)
+#[derive(Debug, Clone, Copy)]
+pub struct SamplerConfig {
+    pub interval: Duration,
+    /// VRAM percentage that triggers a broadcast when crossed
+    pub vram_alert_pct: f64,
+}
+
 /// Client for the GPU observer service
 pub struct GpuMonitor {
     client: Client,
     base_url: String,
+    history: Mutex<VecDeque<GpuSample>>,
 }
 
 impl GpuMonitor {
@@ -104,6 +149,7 @@ impl GpuMonitor {
         Self {
             client,
             base_url: url.to_string(),
+            history: Mutex::new(VecDeque::new()),
         }
     }
 
@@ -146,6 +192,130 @@ impl GpuMonitor {
     pub async fn is_available(&self) -> bool {
         self.health().await.is_ok()
     }
+
+    /// Fetch the current status and append it to the history
+    pub async fn sample(&self) -> Result<GpuSample> {
+        let status = self.fetch_status().await?;
+        let sample = GpuSample::from_status(&status.gpu);
+        self.record(sample.clone());
+        Ok(sample)
+    }
+
+    /// Samples taken within the last `window`, oldest first
+    pub fn history(&self, window: Duration) -> Vec<GpuSample> {
+        let history = self.history.lock().unwrap();
+        let now = SystemTime::now();
+        history
+            .iter()
+            .filter(|s| now.duration_since(s.taken_at).unwrap_or_default() <= window)
+            .cloned()
+            .collect()
+    }
+
+    /// Most recent sample, if any
+    pub fn latest(&self) -> Option<GpuSample> {
+        self.history.lock().unwrap().back().cloned()
+    }
+
+    /// Free VRAM assuming usage returns to its peak over `window`
+    ///
+    /// Schedulers use this to defer a model that won't fit rather than
+    /// trusting a single reading taken between two other jobs' spikes.
+    pub fn vram_headroom_gb(&self, window: Duration) -> Option<f64> {
+        let samples = self.history(window);
+        let total = samples.last()?.vram_total_gb;
+        let peak = samples.iter().map(|s| s.vram_used_gb).fold(0.0, f64::max);
+        Some((total - peak).max(0.0))
+    }
+
+    fn record(&self, sample: GpuSample) {
+        let mut history = self.history.lock().unwrap();
+        history.push_back(sample);
+
+        let now = SystemTime::now();
+        while let Some(oldest) = history.front() {
+            if now.duration_since(oldest.taken_at).unwrap_or_default() <= HISTORY_RETENTION {
+                break;
+            }
+            history.pop_front();
+        }
+    }
+
+    /// Sample on a background task, broadcasting when VRAM crosses the alert level
+    ///
+    /// Broadcasts a `warn` log when usage rises past `vram_alert_pct` and an
+    /// `info` log when it falls back below, not on every sample.
+    pub fn spawn_sampler(
+        self: &Arc<Self>,
+        config: SamplerConfig,
+        broadcaster: Option<BroadcastPublisher>,
+    ) -> JoinHandle<()> {
+        let monitor = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut above = false;
+
+            loop {
+                interval.tick().await;
+
+                let sample = match monitor.sample().await {
+                    Ok(sample) => sample,
+                    Err(e) => {
+                        tracing::debug!("GPU sample failed: {:#}", e);
+                        continue;
+                    }
+                };
+
+                let Some(now_above) = vram_alert_transition(above, &sample, config.vram_alert_pct)
+                else {
+                    continue;
+                };
+                above = now_above;
+
+                let (level, message) = if now_above {
+                    (
+                        "warn",
+                        format!(
+                            "GPU VRAM at {:.0}% ({:.1}/{:.1}GB), above {:.0}% alert level",
+                            sample.vram_pct(),
+                            sample.vram_used_gb,
+                            sample.vram_total_gb,
+                            config.vram_alert_pct
+                        ),
+                    )
+                } else {
+                    (
+                        "info",
+                        format!(
+                            "GPU VRAM back to {:.0}% ({:.1}/{:.1}GB)",
+                            sample.vram_pct(),
+                            sample.vram_used_gb,
+                            sample.vram_total_gb
+                        ),
+                    )
+                };
+
+                if now_above {
+                    tracing::warn!("{}", message);
+                } else {
+                    tracing::info!("{}", message);
+                }
+
+                if let Some(broadcaster) = &broadcaster {
+                    if let Err(e) = broadcaster.log(level, &message, "gpu_monitor").await {
+                        tracing::warn!("Failed to broadcast GPU alert: {}", e);
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// New alert state if `sample` moves VRAM across `alert_pct`, else `None`
+fn vram_alert_transition(above: bool, sample: &GpuSample, alert_pct: f64) -> Option<bool> {
+    let now_above = sample.vram_pct() >= alert_pct;
+    (now_above != above).then_some(now_above)
 }
 
 impl Default for GpuMonitor {
@@ -178,6 +348,46 @@ mod tests {
         }
     }
 
+    fn sample(vram_used_gb: f64, seconds_ago: u64) -> GpuSample {
+        GpuSample {
+            taken_at: SystemTime::now() - Duration::from_secs(seconds_ago),
+            vram_used_gb,
+            vram_total_gb: 96.0,
+            util_pct: 50.0,
+        }
+    }
+
+    #[test]
+    fn test_history_window_and_headroom() {
+        let monitor = GpuMonitor::new();
+        assert!(monitor.vram_headroom_gb(Duration::from_secs(60)).is_none());
+
+        monitor.record(sample(80.0, 90));
+        monitor.record(sample(40.0, 20));
+        monitor.record(sample(50.0, 0));
+
+        assert_eq!(monitor.history(Duration::from_secs(60)).len(), 2);
+        assert_eq!(monitor.history(Duration::from_secs(120)).len(), 3);
+        assert_eq!(monitor.latest().unwrap().vram_used_gb, 50.0);
+        assert_eq!(monitor.vram_headroom_gb(Duration::from_secs(60)), Some(46.0));
+        assert_eq!(monitor.vram_headroom_gb(Duration::from_secs(120)), Some(16.0));
+
+        // Samples past the retention window are dropped on the next record
+        let monitor = GpuMonitor::new();
+        monitor.record(sample(10.0, HISTORY_RETENTION.as_secs() + 60));
+        monitor.record(sample(20.0, 0));
+        assert_eq!(monitor.history(Duration::from_secs(3600)).len(), 1);
+    }
+
+    #[test]
+    fn test_vram_alert_transition() {
+        // 90% of 96GB is 86.4GB
+        assert_eq!(vram_alert_transition(false, &sample(50.0, 0), 90.0), None);
+        assert_eq!(vram_alert_transition(false, &sample(90.0, 0), 90.0), Some(true));
+        assert_eq!(vram_alert_transition(true, &sample(91.0, 0), 90.0), None);
+        assert_eq!(vram_alert_transition(true, &sample(60.0, 0), 90.0), Some(false));
+    }
+
     #[test]
     fn test_deserialize_observer_status() {
         let json = r#"{
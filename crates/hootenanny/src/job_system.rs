@@ -4,6 +4,9 @@
 //! Tools return job IDs immediately, allowing agents to check status and retrieve results later.
 //!
 //! Uses canonical job types from hooteproto for interoperability.
+//!
+//! Jobs live in memory by default. With a [`JobDb`] attached every state
+//! change is written through to SQLite, so results survive a restart.
 
 use anyhow::Result;
 use hooteproto::responses::ToolResponse;
@@ -12,6 +15,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use tokio::task::JoinHandle;
 
+use crate::persistence::jobs::JobDb;
 use crate::zmq::BroadcastPublisher;
 use std::sync::RwLock;
 
@@ -21,6 +25,8 @@ pub struct JobStore {
     jobs: Arc<Mutex<HashMap<String, JobInfo>>>,
     handles: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
     broadcaster: Arc<RwLock<Option<BroadcastPublisher>>>,
+    /// Write-through persistence; `None` keeps jobs in memory only
+    db: Option<Arc<JobDb>>,
 }
 
 impl JobStore {
@@ -29,6 +35,59 @@ impl JobStore {
             jobs: Arc::new(Mutex::new(HashMap::new())),
             handles: Arc::new(Mutex::new(HashMap::new())),
             broadcaster: Arc::new(RwLock::new(None)),
+            db: None,
+        }
+    }
+
+    /// Create a store backed by `db`, loading the jobs it already holds.
+    ///
+    /// Jobs that were pending or running when the previous process exited
+    /// have no task left to finish them, so they come back as failed.
+    pub fn with_db(db: JobDb) -> Result<Self> {
+        let db = Arc::new(db);
+        let mut jobs = HashMap::new();
+        let mut interrupted = 0;
+
+        for mut job in db.load_all()? {
+            if matches!(job.status, JobStatus::Pending | JobStatus::Running) {
+                job.mark_failed("Interrupted by hootenanny restart".to_string());
+                db.save(&job)?;
+                interrupted += 1;
+            }
+            jobs.insert(job.job_id.as_str().to_string(), job);
+        }
+
+        tracing::info!(
+            jobs.loaded = jobs.len(),
+            jobs.interrupted = interrupted,
+            "Job store loaded from database"
+        );
+
+        Ok(Self {
+            jobs: Arc::new(Mutex::new(jobs)),
+            db: Some(db),
+            ..Self::new()
+        })
+    }
+
+    /// Write a job through to the database, if there is one.
+    ///
+    /// Failures are logged rather than returned: the in-memory state is
+    /// still authoritative for this process.
+    fn persist(&self, job: &JobInfo) {
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.save(job) {
+                tracing::warn!(job.id = %job.job_id, "Failed to persist job: {}", e);
+            }
+        }
+    }
+
+    /// Delete jobs from the database, if there is one.
+    fn forget(&self, job_ids: &[String]) {
+        if let Some(ref db) = self.db {
+            if let Err(e) = db.delete(job_ids) {
+                tracing::warn!(count = job_ids.len(), "Failed to delete persisted jobs: {}", e);
+            }
         }
     }
 
@@ -44,6 +103,7 @@ impl JobStore {
         let job_info = JobInfo::new(job_id.clone(), source.clone());
 
         let mut jobs = self.jobs.lock().unwrap();
+        self.persist(&job_info);
         jobs.insert(job_id.as_str().to_string(), job_info);
 
         tracing::info!(
@@ -64,6 +124,7 @@ impl JobStore {
 
         let source = job.source.clone();
         job.mark_running();
+        self.persist(job);
 
         tracing::info!(
             job.id = %job_id,
@@ -88,6 +149,7 @@ impl JobStore {
         let duration = job.duration_secs();
 
         job.mark_complete(result);
+        self.persist(job);
 
         tracing::info!(
             job.id = %job_id,
@@ -121,6 +183,7 @@ impl JobStore {
         let duration = job.duration_secs();
 
         job.mark_failed(error.clone());
+        self.persist(job);
 
         tracing::error!(
             job.id = %job_id,
@@ -177,6 +240,7 @@ impl JobStore {
         if let Some(job) = jobs.get_mut(job_id.as_str()) {
             let source = job.source.clone();
             job.mark_cancelled();
+            self.persist(job);
 
             tracing::warn!(
                 job.id = %job_id,
@@ -268,12 +332,13 @@ impl JobStore {
 
         let count = to_remove.len();
 
-        for id in to_remove {
-            jobs.remove(&id);
-            handles.remove(&id);
+        for id in &to_remove {
+            jobs.remove(id);
+            handles.remove(id);
         }
 
         if count > 0 {
+            self.forget(&to_remove);
             tracing::debug!(
                 removed = count,
                 success_max_age,
@@ -316,12 +381,13 @@ impl JobStore {
 
         let count = to_remove.len();
 
-        for id in to_remove {
-            jobs.remove(&id);
-            handles.remove(&id);
+        for id in &to_remove {
+            jobs.remove(id);
+            handles.remove(id);
         }
 
         if count > 0 {
+            self.forget(&to_remove);
             tracing::debug!(
                 removed = count,
                 source_prefix,
@@ -536,4 +602,56 @@ mod tests {
         assert!(store.get_job(&garden_job).is_err());
         assert!(store.get_job(&other_job).is_ok());
     }
+
+    #[test]
+    fn test_db_backed_jobs_survive_restart() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("jobs.sqlite");
+
+        let (done, in_flight) = {
+            let store = JobStore::with_db(JobDb::open(&path).unwrap()).unwrap();
+            let done = store.create_job("orpheus_generate".to_string());
+            store.mark_running(&done).unwrap();
+            store.mark_complete(&done, ToolResponse::ack("ok")).unwrap();
+
+            let in_flight = store.create_job("musicgen_generate".to_string());
+            store.mark_running(&in_flight).unwrap();
+            (done, in_flight)
+        };
+
+        let store = JobStore::with_db(JobDb::open(&path).unwrap()).unwrap();
+        let job = store.get_job(&done).unwrap();
+        assert_eq!(job.status, JobStatus::Complete);
+        assert!(job.result.is_some());
+
+        // Nothing is left to finish a job that was running at shutdown
+        let job = store.get_job(&in_flight).unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.error.unwrap().contains("restart"));
+    }
+
+    #[test]
+    fn test_cleanup_deletes_from_db() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("jobs.sqlite");
+
+        let store = JobStore::with_db(JobDb::open(&path).unwrap()).unwrap();
+        let job_id = store.create_job("test_tool".to_string());
+        store.mark_running(&job_id).unwrap();
+        store.mark_complete(&job_id, ToolResponse::ack("ok")).unwrap();
+
+        // Backdate in memory and in the database
+        {
+            let mut jobs = store.jobs.lock().unwrap();
+            let job = jobs.get_mut(job_id.as_str()).unwrap();
+            job.completed_at = job.completed_at.map(|t| t.saturating_sub(100));
+            store.persist(job);
+        }
+
+        assert_eq!(store.cleanup_completed_older_than(50), 1);
+        drop(store);
+
+        let reopened = JobDb::open(&path).unwrap();
+        assert!(reopened.load(job_id.as_str()).unwrap().is_none());
+    }
 }
@@ -26,7 +26,7 @@ use std::sync::{Arc, RwLock};
 use std::time::Instant;
 use streams::{SlicingEngine, StreamManager};
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{info, warn};
 
 /// The Hootenanny ZMQ Server
 ///
@@ -65,12 +65,24 @@ async fn main() -> Result<()> {
         for path in &sources.files {
             println!("#   - {}", path.display());
         }
+        if let Some(profile) = &sources.profile {
+            println!("# Profile overlays ({}):", profile);
+            for path in &sources.profile_files {
+                println!("#   - {}", path.display());
+            }
+        }
         if !sources.env_overrides.is_empty() {
             println!("# Environment overrides:");
             for var in &sources.env_overrides {
                 println!("#   - {}", var);
             }
         }
+        if !sources.warnings.is_empty() {
+            println!("# Warnings:");
+            for warning in &sources.warnings {
+                println!("#   - {}", warning);
+            }
+        }
         println!();
         println!("{}", config.to_toml());
         return Ok(());
@@ -88,6 +100,9 @@ async fn main() -> Result<()> {
     if !sources.env_overrides.is_empty() {
         info!("   Environment overrides: {:?}", sources.env_overrides);
     }
+    for warning in &sources.warnings {
+        warn!("   Config warning: {}", warning);
+    }
 
     // Create state directory
     let state_dir = &config.infra.paths.state_dir;
@@ -129,7 +144,16 @@ async fn main() -> Result<()> {
 
     // --- Job Store Initialization ---
     info!("⚙️  Initializing shared Job Store...");
-    let job_store = Arc::new(job_system::JobStore::new());
+    let job_store = match &config.infra.paths.job_db {
+        Some(path) => {
+            let db = persistence::jobs::JobDb::open(path)
+                .context("Failed to open job database")?;
+            info!("   Persisting jobs to {}", path.display());
+            job_system::JobStore::with_db(db).context("Failed to load persisted jobs")?
+        }
+        None => job_system::JobStore::new(),
+    };
+    let job_store = Arc::new(job_store);
 
     // Spawn background cleanup task (runs every 60s)
     let _cleanup_handle = job_system::spawn_cleanup_task(job_store.as_ref().clone(), 60);
@@ -335,12 +359,24 @@ async fn main() -> Result<()> {
     job_store.set_broadcaster(broadcast_publisher.clone());
     info!("   Job store connected to broadcaster");
 
+    // Sample the GPU in the background so schedulers see recent VRAM history
+    let sampler_config = gpu_monitor::SamplerConfig {
+        interval: config.bootstrap.defaults.gpu_sample_interval(),
+        vram_alert_pct: config.bootstrap.defaults.gpu_vram_alert_pct,
+    };
+    let _gpu_sampler = gpu_monitor.spawn_sampler(sampler_config, Some(broadcast_publisher.clone()));
+    info!(
+        "   GPU sampler running (every {}, alert at {:.0}% VRAM)",
+        config.bootstrap.defaults.gpu_sample_interval, sampler_config.vram_alert_pct
+    );
+
     // --- Stream Subsystems (for capture sessions) ---
     info!("🎙️  Initializing stream capture subsystems...");
     let cas_arc = Arc::new(cas.clone());
     let stream_manager = Arc::new(StreamManager::new(cas_arc.clone()));
     let session_manager = Arc::new(SessionManager::new(cas_arc.clone(), stream_manager.clone()));
-    let slicing_engine = Arc::new(SlicingEngine::new(cas.clone()));
+    let slicing_engine =
+        Arc::new(SlicingEngine::new(cas.clone()).with_sessions(session_manager.clone()));
     info!("   Stream manager ready");
     info!("   Session manager ready");
     info!("   Slicing engine ready");
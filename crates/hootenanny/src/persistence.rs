@@ -1,5 +1,7 @@
 //! The persistence layer for the hootenanny.
 //!
-//! This module is responsible for saving and loading state using sled.
+//! This module is responsible for saving and loading state using sled,
+//! plus SQLite for the async job table.
 
+pub mod jobs;
 pub mod snapshots;
@@ -0,0 +1,228 @@
+//! SQLite persistence for async jobs.
+//!
+//! `JobStore` writes every state change through to a `JobDb` when one is
+//! configured (`infra.paths.job_db`), so `job_status`/`job_poll` answers
+//! survive a restart. Results are stored as `ToolResponse` JSON.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use hooteproto::{JobId, JobInfo, JobStatus};
+use rusqlite::{params, Connection, OptionalExtension};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS jobs (
+    job_id       TEXT PRIMARY KEY,
+    status       TEXT NOT NULL,
+    source       TEXT NOT NULL,
+    result       TEXT,
+    error        TEXT,
+    created_at   INTEGER NOT NULL,
+    started_at   INTEGER,
+    completed_at INTEGER
+);
+CREATE INDEX IF NOT EXISTS jobs_completed_at ON jobs(completed_at);
+";
+
+/// SQLite-backed job table.
+///
+/// One connection behind a mutex: job writes are tiny and already
+/// serialized by `JobStore`'s own lock, so a pool would buy nothing.
+pub struct JobDb {
+    conn: Mutex<Connection>,
+}
+
+impl JobDb {
+    /// Open (or create) the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open job database {}", path.display()))?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        Self::init(conn)
+    }
+
+    /// Open an in-memory database (for testing).
+    pub fn open_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Insert or replace a job.
+    pub fn save(&self, job: &JobInfo) -> Result<()> {
+        let result = job
+            .result
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .context("Failed to serialize job result")?;
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO jobs
+                (job_id, status, source, result, error, created_at, started_at, completed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                job.job_id.as_str(),
+                job.status.to_string_lower(),
+                job.source,
+                result,
+                job.error,
+                job.created_at as i64,
+                job.started_at.map(|t| t as i64),
+                job.completed_at.map(|t| t as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Load a single job.
+    pub fn load(&self, job_id: &str) -> Result<Option<JobInfo>> {
+        let conn = self.conn.lock().unwrap();
+        let row = conn
+            .query_row(
+                "SELECT job_id, status, source, result, error, created_at, started_at, completed_at
+                 FROM jobs WHERE job_id = ?1",
+                params![job_id],
+                JobRow::from_row,
+            )
+            .optional()?;
+        row.map(JobRow::into_job).transpose()
+    }
+
+    /// Load every stored job.
+    pub fn load_all(&self) -> Result<Vec<JobInfo>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT job_id, status, source, result, error, created_at, started_at, completed_at
+             FROM jobs",
+        )?;
+        let rows = stmt.query_map([], JobRow::from_row)?;
+
+        let mut jobs = Vec::new();
+        for row in rows {
+            jobs.push(row?.into_job()?);
+        }
+        Ok(jobs)
+    }
+
+    /// Delete jobs by ID, returning how many rows went away.
+    pub fn delete(&self, job_ids: &[String]) -> Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let mut removed = 0;
+        {
+            let mut stmt = tx.prepare("DELETE FROM jobs WHERE job_id = ?1")?;
+            for id in job_ids {
+                removed += stmt.execute(params![id])?;
+            }
+        }
+        tx.commit()?;
+        Ok(removed)
+    }
+}
+
+/// Raw column values, converted to `JobInfo` outside rusqlite's error type.
+struct JobRow {
+    job_id: String,
+    status: String,
+    source: String,
+    result: Option<String>,
+    error: Option<String>,
+    created_at: i64,
+    started_at: Option<i64>,
+    completed_at: Option<i64>,
+}
+
+impl JobRow {
+    fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            job_id: row.get(0)?,
+            status: row.get(1)?,
+            source: row.get(2)?,
+            result: row.get(3)?,
+            error: row.get(4)?,
+            created_at: row.get(5)?,
+            started_at: row.get(6)?,
+            completed_at: row.get(7)?,
+        })
+    }
+
+    fn into_job(self) -> Result<JobInfo> {
+        let status = JobStatus::from_str_lower(&self.status).ok_or_else(|| {
+            anyhow::anyhow!("Job {} has unknown status {:?}", self.job_id, self.status)
+        })?;
+        let result = self
+            .result
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .with_context(|| format!("Failed to parse result of job {}", self.job_id))?;
+
+        Ok(JobInfo {
+            job_id: JobId::from_text(self.job_id),
+            status,
+            source: self.source,
+            result,
+            error: self.error,
+            created_at: self.created_at as u64,
+            started_at: self.started_at.map(|t| t as u64),
+            completed_at: self.completed_at.map(|t| t as u64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hooteproto::responses::ToolResponse;
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let db = JobDb::open_memory().unwrap();
+
+        let mut job = JobInfo::new(JobId::new(), "orpheus_generate".to_string());
+        db.save(&job).unwrap();
+        assert_eq!(db.load(job.job_id.as_str()).unwrap().unwrap().status, JobStatus::Pending);
+
+        job.mark_running();
+        job.mark_complete(ToolResponse::ack("done"));
+        db.save(&job).unwrap();
+
+        let loaded = db.load(job.job_id.as_str()).unwrap().unwrap();
+        assert_eq!(loaded.status, JobStatus::Complete);
+        assert_eq!(loaded.source, "orpheus_generate");
+        assert_eq!(loaded.started_at, job.started_at);
+        assert_eq!(loaded.completed_at, job.completed_at);
+        assert_eq!(
+            serde_json::to_value(&loaded.result).unwrap(),
+            serde_json::to_value(&job.result).unwrap()
+        );
+        assert_eq!(db.load_all().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_delete() {
+        let db = JobDb::open_memory().unwrap();
+        let job = JobInfo::new(JobId::new(), "tool".to_string());
+        db.save(&job).unwrap();
+
+        let ids = vec![job.job_id.as_str().to_string(), "missing".to_string()];
+        assert_eq!(db.delete(&ids).unwrap(), 1);
+        assert!(db.load(job.job_id.as_str()).unwrap().is_none());
+    }
+}
@@ -18,7 +18,6 @@ struct ActiveSession {
 /// Manager for capture session lifecycle
 pub struct SessionManager {
     cas: Arc<FileStore>,
-    #[allow(dead_code)]
     stream_manager: Arc<StreamManager>,
     active_sessions: Arc<RwLock<HashMap<SessionId, ActiveSession>>>,
 }
@@ -164,6 +163,11 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Stream manager backing this session's streams
+    pub fn stream_manager(&self) -> &Arc<StreamManager> {
+        &self.stream_manager
+    }
+
     /// Update the chunk range for the current segment
     ///
     /// This should be called when chunks are added to streams in the session.
@@ -156,6 +156,87 @@ impl Default for SessionTimeline {
     }
 }
 
+/// Tempo assumed when a session has no tempo map
+pub const DEFAULT_BPM: f64 = 120.0;
+
+/// A tempo change taking effect at a beat position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TempoChange {
+    pub beat: f64,
+    pub bpm: f64,
+}
+
+/// Maps musical beats onto the session's audio sample clock
+///
+/// Beat 0 sits at `origin_sample`; when that is unset, the first clock
+/// snapshot carrying an audio position is used instead. Tempo is constant
+/// between changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTempoMap {
+    pub origin_sample: Option<u64>,
+    pub changes: Vec<TempoChange>,
+}
+
+impl SessionTempoMap {
+    pub fn new(bpm: f64) -> Self {
+        Self {
+            origin_sample: None,
+            changes: vec![TempoChange { beat: 0.0, bpm }],
+        }
+    }
+
+    pub fn with_origin(mut self, sample: u64) -> Self {
+        self.origin_sample = Some(sample);
+        self
+    }
+
+    /// Add a tempo change, keeping changes ordered by beat
+    pub fn with_change(mut self, beat: f64, bpm: f64) -> Self {
+        let index = self.changes.partition_point(|c| c.beat <= beat);
+        self.changes.insert(index, TempoChange { beat, bpm });
+        self
+    }
+
+    /// Reject tempos that would make beat positions meaningless
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for change in &self.changes {
+            if !(change.bpm.is_finite() && change.bpm > 0.0) {
+                anyhow::bail!("invalid tempo {} bpm at beat {}", change.bpm, change.beat);
+            }
+            if !change.beat.is_finite() {
+                anyhow::bail!("invalid tempo change position: {}", change.beat);
+            }
+        }
+        Ok(())
+    }
+
+    /// Seconds from beat 0 to `beat`, integrating across tempo changes
+    pub fn seconds_at(&self, beat: f64) -> f64 {
+        let mut bpm = self.changes.first().map_or(DEFAULT_BPM, |c| c.bpm);
+        let mut position = 0.0;
+        let mut seconds = 0.0;
+
+        for change in &self.changes {
+            if change.beat >= beat {
+                break;
+            }
+            if change.beat > position {
+                seconds += (change.beat - position) * 60.0 / bpm;
+                position = change.beat;
+            }
+            bpm = change.bpm;
+        }
+
+        seconds + (beat - position) * 60.0 / bpm
+    }
+}
+
+impl Default for SessionTempoMap {
+    fn default() -> Self {
+        Self::new(DEFAULT_BPM)
+    }
+}
+
 /// A segment is a contiguous recording period within a session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionSegment {
@@ -193,6 +274,8 @@ pub struct CaptureSession {
     pub segments: Vec<SessionSegment>,
     pub timeline: SessionTimeline,
     pub status: SessionStatus,
+    #[serde(default)]
+    pub tempo_map: SessionTempoMap,
 }
 
 impl CaptureSession {
@@ -204,6 +287,7 @@ impl CaptureSession {
             segments: Vec::new(),
             timeline: SessionTimeline::new(),
             status: SessionStatus::Recording,
+            tempo_map: SessionTempoMap::default(),
         }
     }
 
@@ -249,6 +333,19 @@ impl CaptureSession {
     pub fn is_stopped(&self) -> bool {
         self.status == SessionStatus::Stopped
     }
+
+    /// Audio sample position of beat 0
+    pub fn beat_origin_sample(&self) -> u64 {
+        self.tempo_map
+            .origin_sample
+            .or_else(|| {
+                self.timeline
+                    .clock_snapshots
+                    .iter()
+                    .find_map(|s| s.audio_sample_position)
+            })
+            .unwrap_or(0)
+    }
 }
 
 #[cfg(test)]
@@ -349,6 +446,35 @@ mod tests {
         assert!(!session.segments[1].is_active());
     }
 
+    #[test]
+    fn test_tempo_map_integrates_changes() {
+        let map = SessionTempoMap::new(120.0).with_change(4.0, 60.0);
+
+        assert_eq!(map.seconds_at(0.0), 0.0);
+        assert_eq!(map.seconds_at(4.0), 2.0);
+        // Two beats at 60 bpm after the change
+        assert_eq!(map.seconds_at(6.0), 4.0);
+        assert!(map.validate().is_ok());
+        assert!(SessionTempoMap::new(0.0).validate().is_err());
+    }
+
+    #[test]
+    fn test_beat_origin_from_timeline() {
+        let mut session = CaptureSession::new(
+            SessionId::new("tempo-session"),
+            SessionMode::Passive,
+            vec![StreamUri::from("stream://test/audio")],
+        );
+        assert_eq!(session.beat_origin_sample(), 0);
+
+        let snapshot = ClockSnapshot::now(SessionCheckpoint::Named(1)).with_audio_position(480);
+        session.timeline.add_snapshot(snapshot);
+        assert_eq!(session.beat_origin_sample(), 480);
+
+        session.tempo_map = SessionTempoMap::new(90.0).with_origin(96);
+        assert_eq!(session.beat_origin_sample(), 96);
+    }
+
     #[test]
     fn test_passive_mode() {
         let session_id = SessionId::new("passive-session");
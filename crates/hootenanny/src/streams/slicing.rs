@@ -2,14 +2,20 @@
 //!
 //! Supports both materialized slices (new WAV/MIDI files) and virtual slices
 //! (chunk-reference manifests that can be rendered on demand).
+//!
+//! Audio belonging to a capture session can also be sliced by beat range,
+//! using the session's tempo map to find the sample boundaries.
 
 use super::manifest::{ChunkReference, StreamManifest};
-use super::types::{AudioFormat, StreamFormat, StreamUri};
+use super::types::{AudioFormat, SampleFormat, StreamFormat, StreamUri};
+use crate::sessions::types::{CaptureSession, SessionId, SessionMode};
+use crate::sessions::SessionManager;
 use anyhow::{Context, Result};
 use cas::{ContentHash, ContentStore, FileStore};
 use serde::{Deserialize, Serialize};
 use std::io::Write;
 use std::ops::Range;
+use std::sync::Arc;
 use std::time::SystemTime;
 use tracing::{debug, info};
 
@@ -80,14 +86,166 @@ pub struct ChunkSlice {
     pub sample_length: Option<u64>,
 }
 
+/// Lineage record linking a beat slice back to its capture session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeatSliceLineage {
+    pub session_id: SessionId,
+    pub stream_uri: StreamUri,
+    pub from_beats: f64,
+    pub to_beats: f64,
+    pub sample_range: Range<u64>,
+    pub slice_hash: ContentHash,
+    pub source_chunks: Vec<ContentHash>,
+    pub created_at: SystemTime,
+}
+
+/// Result of slicing a session by beat range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeatSliceResult {
+    pub slice: SliceResult,
+    /// Content hash of the stored `BeatSliceLineage`
+    pub lineage_hash: ContentHash,
+}
+
+/// How far either side of a beat boundary to look for a zero-crossing
+const ZERO_CROSSING_WINDOW_MS: u32 = 5;
+
 /// Engine for slicing streams
 pub struct SlicingEngine {
     cas: FileStore,
+    sessions: Option<Arc<SessionManager>>,
 }
 
 impl SlicingEngine {
     pub fn new(cas: FileStore) -> Self {
-        Self { cas }
+        Self {
+            cas,
+            sessions: None,
+        }
+    }
+
+    /// Resolve session IDs for `slice_beats` through this manager
+    pub fn with_sessions(mut self, sessions: Arc<SessionManager>) -> Self {
+        self.sessions = Some(sessions);
+        self
+    }
+
+    /// Slice a session's audio between two beat positions
+    ///
+    /// Uses the audio input for request/response sessions, otherwise the
+    /// first active audio stream in the session.
+    pub fn slice_beats(
+        &self,
+        session_id: &SessionId,
+        from_beats: f64,
+        to_beats: f64,
+    ) -> Result<BeatSliceResult> {
+        let sessions = self
+            .sessions
+            .as_ref()
+            .context("slicing engine has no session manager")?;
+
+        let session = sessions
+            .get_session(session_id)?
+            .with_context(|| format!("session not found: {}", session_id))?;
+
+        let preferred = match &session.mode {
+            SessionMode::RequestResponse { audio_in, .. } => Some(audio_in.clone()),
+            SessionMode::Passive => None,
+        };
+
+        for uri in preferred.into_iter().chain(session.streams.iter().cloned()) {
+            let Some(manifest) = sessions.stream_manager().get_manifest(&uri)? else {
+                continue;
+            };
+            if self.get_audio_format(&manifest)?.is_some() {
+                return self.slice_session_beats(&session, &manifest, from_beats, to_beats);
+            }
+        }
+
+        anyhow::bail!("session has no active audio stream: {}", session_id)
+    }
+
+    /// Slice `manifest` between two beats of `session`'s tempo map
+    ///
+    /// Boundaries are snapped to the nearest zero-crossing so the cut
+    /// doesn't click. The slice is materialized to CAS alongside a
+    /// `BeatSliceLineage` record pointing back at the session.
+    pub fn slice_session_beats(
+        &self,
+        session: &CaptureSession,
+        manifest: &StreamManifest,
+        from_beats: f64,
+        to_beats: f64,
+    ) -> Result<BeatSliceResult> {
+        if !(from_beats.is_finite() && to_beats.is_finite() && from_beats < to_beats) {
+            anyhow::bail!("invalid beat range: {} to {}", from_beats, to_beats);
+        }
+        session.tempo_map.validate()?;
+
+        let audio_format = self
+            .get_audio_format(manifest)?
+            .context("beat slicing only supported for audio streams")?;
+
+        let origin = session.beat_origin_sample() as f64;
+        let sample_at = |beat: f64| -> Result<u64> {
+            let seconds = session.tempo_map.seconds_at(beat);
+            let position = origin + seconds * audio_format.sample_rate as f64;
+            if position < 0.0 {
+                anyhow::bail!("beat {} falls before the start of the stream", beat);
+            }
+            Ok(position.round() as u64)
+        };
+
+        let from_sample = sample_at(from_beats)?;
+        let to_sample = sample_at(to_beats)?;
+
+        if let Some(total) = manifest.total_samples {
+            if to_sample > total {
+                anyhow::bail!(
+                    "beat {} (sample {}) is past the stream head ({})",
+                    to_beats,
+                    to_sample,
+                    total
+                );
+            }
+        }
+
+        let start = self.snap_to_zero_crossing(manifest, &audio_format, from_sample)?;
+        let end = self.snap_to_zero_crossing(manifest, &audio_format, to_sample)?;
+        if start >= end {
+            anyhow::bail!("beat range {} to {} is empty after snapping", from_beats, to_beats);
+        }
+
+        let slice = self.materialize_slice(manifest, Some(start..end))?;
+
+        let lineage = BeatSliceLineage {
+            session_id: session.id.clone(),
+            stream_uri: manifest.stream_uri.clone(),
+            from_beats,
+            to_beats,
+            sample_range: start..end,
+            slice_hash: slice.content_hash.clone(),
+            source_chunks: slice.source_chunks.clone(),
+            created_at: SystemTime::now(),
+        };
+
+        let lineage_json =
+            serde_json::to_vec(&lineage).context("failed to serialize beat slice lineage")?;
+        let lineage_hash = self
+            .cas
+            .store(&lineage_json, "application/json")
+            .context("failed to store beat slice lineage")?;
+
+        info!(
+            "sliced session {} beats {}..{} -> samples {:?} ({})",
+            session.id, from_beats, to_beats, lineage.sample_range, slice.content_hash
+        );
+
+        Ok(BeatSliceResult {
+            slice,
+            lineage_hash,
+        })
     }
 
     /// Slice a stream based on a request
@@ -395,6 +553,66 @@ impl SlicingEngine {
         Ok(chunk_slices)
     }
 
+    /// Move `target` to the nearest zero-crossing within a few milliseconds
+    ///
+    /// Only the first channel is inspected. Returns `target` unchanged when
+    /// the window holds no crossing.
+    fn snap_to_zero_crossing(
+        &self,
+        manifest: &StreamManifest,
+        format: &AudioFormat,
+        target: u64,
+    ) -> Result<u64> {
+        let window = (format.sample_rate * ZERO_CROSSING_WINDOW_MS / 1000) as u64;
+        let start = target.saturating_sub(window);
+        let mut end = target + window + 1;
+        if let Some(total) = manifest.total_samples {
+            end = end.min(total);
+        }
+        if end <= start + 1 {
+            return Ok(target);
+        }
+
+        let frames = self.read_first_channel(manifest, format, &(start..end))?;
+
+        let nearest = (1..frames.len())
+            .filter(|&i| frames[i] == 0.0 || (frames[i - 1] < 0.0) != (frames[i] < 0.0))
+            .map(|i| start + i as u64)
+            .min_by_key(|&position| position.abs_diff(target));
+
+        Ok(nearest.unwrap_or(target))
+    }
+
+    /// Decode the first channel of a sample range
+    fn read_first_channel(
+        &self,
+        manifest: &StreamManifest,
+        format: &AudioFormat,
+        sample_range: &Range<u64>,
+    ) -> Result<Vec<f32>> {
+        let sample_bytes = format.sample_format.bytes_per_sample();
+        let frame_bytes = sample_bytes * format.channels as usize;
+        let chunk_slices = self.compute_chunk_slices(manifest, sample_range, frame_bytes)?;
+
+        let mut frames = Vec::new();
+        for chunk_slice in &chunk_slices {
+            let chunk_data = self
+                .cas
+                .retrieve(&chunk_slice.chunk_hash)?
+                .with_context(|| format!("chunk {} not found in CAS", chunk_slice.chunk_hash))?;
+
+            let start = chunk_slice.byte_offset as usize;
+            let end = (start + chunk_slice.byte_length as usize).min(chunk_data.len());
+            frames.extend(
+                chunk_data[start..end]
+                    .chunks_exact(frame_bytes)
+                    .map(|frame| decode_sample(format.sample_format, &frame[..sample_bytes])),
+            );
+        }
+
+        Ok(frames)
+    }
+
     /// Write a WAV file header
     fn write_wav_header(
         &self,
@@ -434,10 +652,20 @@ impl SlicingEngine {
     }
 }
 
+/// Decode one little-endian sample; only its sign and magnitude matter here
+fn decode_sample(format: SampleFormat, bytes: &[u8]) -> f32 {
+    match format {
+        SampleFormat::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        SampleFormat::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+        SampleFormat::I24 => (i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8) as f32,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::types::{AudioFormat, SampleFormat, StreamDefinition, StreamFormat};
     use super::*;
+    use crate::sessions::types::SessionTempoMap;
     use tempfile::TempDir;
 
     fn setup_test_store() -> (TempDir, FileStore) {
@@ -635,4 +863,97 @@ mod tests {
         let result = engine.slice(request, &manifest);
         assert!(result.is_err());
     }
+
+    fn create_beat_session(
+        manifest: &StreamManifest,
+        tempo_map: SessionTempoMap,
+    ) -> CaptureSession {
+        let mut session = CaptureSession::new(
+            SessionId::new("beat-session"),
+            SessionMode::Passive,
+            vec![manifest.stream_uri.clone()],
+        );
+        session.tempo_map = tempo_map;
+        session
+    }
+
+    #[test]
+    fn test_slice_beats() {
+        let (_temp, store) = setup_test_store();
+        let engine = SlicingEngine::new(store.clone());
+
+        // 1kHz at 120 bpm = 500 samples per beat
+        let (manifest, _) = create_test_manifest(&store, 1000, 1000, 4);
+        let session = create_beat_session(&manifest, SessionTempoMap::new(120.0));
+
+        let result = engine
+            .slice_session_beats(&session, &manifest, 2.0, 4.0)
+            .unwrap();
+
+        assert_eq!(result.slice.sample_range, Some(1000..2000));
+        assert_eq!(result.slice.mime_type, "audio/wav");
+
+        let lineage_data = store.retrieve(&result.lineage_hash).unwrap().unwrap();
+        let lineage: BeatSliceLineage = serde_json::from_slice(&lineage_data).unwrap();
+        assert_eq!(lineage.session_id, session.id);
+        assert_eq!(lineage.slice_hash, result.slice.content_hash);
+        assert_eq!(lineage.sample_range, 1000..2000);
+    }
+
+    #[test]
+    fn test_slice_beats_across_tempo_change() {
+        let (_temp, store) = setup_test_store();
+        let engine = SlicingEngine::new(store.clone());
+
+        let (manifest, _) = create_test_manifest(&store, 1000, 1000, 4);
+        let tempo_map = SessionTempoMap::new(120.0)
+            .with_change(2.0, 60.0)
+            .with_origin(200);
+        let session = create_beat_session(&manifest, tempo_map);
+
+        // Beat 1 = 0.5s, beat 3 = 1s at 120 bpm + 1s at 60 bpm, both offset by the origin
+        let result = engine
+            .slice_session_beats(&session, &manifest, 1.0, 3.0)
+            .unwrap();
+        assert_eq!(result.slice.sample_range, Some(700..2200));
+
+        // Past the 4000-sample head
+        assert!(engine
+            .slice_session_beats(&session, &manifest, 0.0, 8.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_slice_beats_snaps_to_zero_crossing() {
+        let (_temp, store) = setup_test_store();
+        let engine = SlicingEngine::new(store.clone());
+
+        let (mut manifest, _) = create_test_manifest(&store, 1000, 1000, 1);
+
+        // Negative until sample 503, then positive: beat 1 (sample 500) snaps forward
+        let samples: Vec<f32> = (0..1000).map(|i| if i < 503 { -0.5 } else { 0.5 }).collect();
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let hash = store.store(&bytes, "audio/raw").unwrap();
+        manifest.chunks[0] = ChunkReference::Sealed {
+            hash,
+            byte_count: bytes.len() as u64,
+            sample_count: Some(1000),
+        };
+
+        let session = create_beat_session(&manifest, SessionTempoMap::new(120.0));
+        let result = engine
+            .slice_session_beats(&session, &manifest, 1.0, 2.0)
+            .unwrap();
+
+        assert_eq!(result.slice.sample_range, Some(503..1000));
+    }
+
+    #[test]
+    fn test_slice_beats_requires_session_manager() {
+        let (_temp, store) = setup_test_store();
+        let engine = SlicingEngine::new(store);
+
+        let result = engine.slice_beats(&SessionId::new("missing"), 0.0, 4.0);
+        assert!(result.is_err());
+    }
 }
@@ -5,14 +5,14 @@
 //!
 //! Note: MCP handlers have migrated to the baton crate.
 
-use crate::artifact_store::{ArtifactStore, FileStore};
+use crate::artifact_store::{ArtifactFilter, ArtifactSort, ArtifactStore, FileStore};
 use axum::{
     body::Body,
     extract::{
         ws::{Message, WebSocket, WebSocketUpgrade},
         Path, Query, State,
     },
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
@@ -23,6 +23,7 @@ use cas::{ContentStore, FileStore as CasFileStore};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tokio_util::io::ReaderStream;
 
 /// Shared state for web handlers
@@ -1084,10 +1085,67 @@ const UI_HTML: &str = r##"<!DOCTYPE html>
 </html>
 "##;
 
+/// Byte range selected by a `Range` request header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ByteRange {
+    /// No usable range; serve the whole file
+    Full,
+    /// Inclusive start and end offsets
+    Partial(u64, u64),
+    /// Range lies entirely outside the file (416)
+    Unsatisfiable,
+}
+
+/// Parse a single-range `bytes=` header against a file of `size` bytes.
+///
+/// Malformed headers, other units and multi-range requests fall back to
+/// serving the full file, which RFC 9110 permits.
+fn parse_byte_range(value: &str, size: u64) -> ByteRange {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((start, end)) = spec.split_once('-') else {
+        return ByteRange::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    if start.is_empty() {
+        // Suffix range: the last N bytes
+        return match end.parse::<u64>() {
+            Ok(0) => ByteRange::Unsatisfiable,
+            Ok(_) if size == 0 => ByteRange::Unsatisfiable,
+            Ok(n) => ByteRange::Partial(size.saturating_sub(n), size - 1),
+            Err(_) => ByteRange::Full,
+        };
+    }
+
+    let Ok(start) = start.parse::<u64>() else {
+        return ByteRange::Full;
+    };
+    let end = if end.is_empty() {
+        None
+    } else {
+        match end.parse::<u64>() {
+            Ok(end) if end >= start => Some(end),
+            _ => return ByteRange::Full,
+        }
+    };
+
+    if start >= size {
+        return ByteRange::Unsatisfiable;
+    }
+    let last = size - 1;
+    ByteRange::Partial(start, end.map_or(last, |end| end.min(last)))
+}
+
 /// Download artifact content
 ///
 /// Resolves artifact ID to CAS content and streams it with the correct MIME type.
-/// Records access in the artifact for tracking.
+/// Honors single-range `Range` headers with `206 Partial Content` so browsers
+/// can seek within audio. Records access in the artifact for tracking.
 #[tracing::instrument(
     name = "http.artifact.content",
     skip(state),
@@ -1098,7 +1156,11 @@ const UI_HTML: &str = r##"<!DOCTYPE html>
         artifact.access_count = tracing::field::Empty,
     )
 )]
-async fn download_artifact(State(state): State<WebState>, Path(id): Path<String>) -> Response {
+async fn download_artifact(
+    State(state): State<WebState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
     // Get artifact and update access
     let (content_hash, mime_type, path, access_count, artifact_id_str) = {
         let store = match state.artifact_store.write() {
@@ -1118,6 +1180,7 @@ async fn download_artifact(State(state): State<WebState>, Path(id): Path<String>
         let content_hash = artifact.content_hash.clone();
         let creator = artifact.creator.clone();
         let artifact_id_str = artifact.id.as_str().to_string();
+        let artifact_mime = artifact.mime_type().map(str::to_string);
 
         // Persist updated artifact
         if let Err(e) = store.put(artifact) {
@@ -1151,7 +1214,7 @@ async fn download_artifact(State(state): State<WebState>, Path(id): Path<String>
 
         (
             content_hash,
-            cas_ref.mime_type,
+            artifact_mime.unwrap_or(cas_ref.mime_type),
             path,
             access_count,
             artifact_id_str,
@@ -1159,17 +1222,54 @@ async fn download_artifact(State(state): State<WebState>, Path(id): Path<String>
     };
 
     // Stream content
-    let file = match tokio::fs::File::open(&path).await {
+    let mut file = match tokio::fs::File::open(&path).await {
         Ok(f) => f,
         Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
     };
+    let size = match file.metadata().await {
+        Ok(m) => m.len(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
 
-    let stream = ReaderStream::new(file);
-    let body = Body::from_stream(stream);
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map_or(ByteRange::Full, |v| parse_byte_range(v, size));
 
-    Response::builder()
-        .status(StatusCode::OK)
+    let mut builder = Response::builder()
         .header(header::CONTENT_TYPE, mime_type)
+        .header(header::ACCEPT_RANGES, "bytes");
+
+    let body = match range {
+        ByteRange::Full => {
+            builder = builder
+                .status(StatusCode::OK)
+                .header(header::CONTENT_LENGTH, size);
+            Body::from_stream(ReaderStream::new(file))
+        }
+        ByteRange::Partial(start, end) => {
+            if file.seek(std::io::SeekFrom::Start(start)).await.is_err() {
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+            let length = end - start + 1;
+            builder = builder
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(header::CONTENT_LENGTH, length)
+                .header(
+                    header::CONTENT_RANGE,
+                    format!("bytes {}-{}/{}", start, end, size),
+                );
+            Body::from_stream(ReaderStream::new(file.take(length)))
+        }
+        ByteRange::Unsatisfiable => {
+            builder = builder
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(header::CONTENT_RANGE, format!("bytes */{}", size));
+            Body::empty()
+        }
+    };
+
+    builder
         .header("X-Artifact-Id", artifact_id_str)
         .header("X-Content-Hash", content_hash.as_str())
         .header("X-Access-Count", access_count.to_string())
@@ -1271,24 +1371,62 @@ async fn artifact_meta(State(state): State<WebState>, Path(id): Path<String>) ->
 /// Query parameters for listing artifacts
 #[derive(Debug, Deserialize)]
 struct ListQuery {
+    /// Comma-separated; an artifact must carry all of them
     tag: Option<String>,
     creator: Option<String>,
+    /// Exact MIME type or a prefix wildcard like `audio/*`
+    mime_type: Option<String>,
+    variation_set_id: Option<String>,
+    /// RFC 3339, inclusive
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    /// RFC 3339, exclusive
+    until: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    sort: ArtifactSort,
     limit: Option<usize>,
 }
 
+impl ListQuery {
+    fn into_filter(self) -> ArtifactFilter {
+        let tags = self
+            .tag
+            .map(|t| {
+                t.split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        ArtifactFilter {
+            tags,
+            creator: self.creator,
+            mime_type: self.mime_type,
+            variation_set_id: self.variation_set_id,
+            created_after: self.since,
+            created_before: self.until,
+            sort: self.sort,
+            limit: Some(self.limit.unwrap_or(100)),
+        }
+    }
+}
+
 /// Artifact summary for list response
 #[derive(Serialize)]
 struct ArtifactSummary {
     id: String,
     content_hash: String,
     content_url: String,
+    mime_type: Option<String>,
+    variation_set_id: Option<String>,
     creator: String,
     created_at: String,
     tags: Vec<String>,
     access_count: u64,
 }
 
-/// List artifacts with optional filtering
+/// List artifacts with optional filtering, newest first by default
 #[tracing::instrument(name = "http.artifacts.list", skip(state))]
 async fn list_artifacts(
     State(state): State<WebState>,
@@ -1304,7 +1442,7 @@ async fn list_artifacts(
         }
     };
 
-    let all = match store.all() {
+    let matched = match store.query(&query.into_filter()) {
         Ok(a) => a,
         Err(e) => {
             return (
@@ -1314,15 +1452,14 @@ async fn list_artifacts(
         }
     };
 
-    let filtered: Vec<ArtifactSummary> = all
+    let filtered: Vec<ArtifactSummary> = matched
         .into_iter()
-        .filter(|a| query.tag.as_ref().is_none_or(|t| a.has_tag(t)))
-        .filter(|a| query.creator.as_ref().is_none_or(|c| &a.creator == c))
-        .take(query.limit.unwrap_or(100))
         .map(|a| ArtifactSummary {
             id: a.id.as_str().to_string(),
             content_hash: a.content_hash.as_str().to_string(),
             content_url: format!("/artifact/{}", a.id.as_str()),
+            mime_type: a.mime_type().map(String::from),
+            variation_set_id: a.variation_set_id.as_ref().map(|s| s.as_str().to_string()),
             creator: a.creator,
             created_at: a.created_at.to_rfc3339(),
             tags: a.tags,
@@ -1414,6 +1551,65 @@ mod tests {
         assert_eq!(&body[..], b"Hello, artifact world!");
     }
 
+    #[tokio::test]
+    async fn test_download_artifact_range() {
+        let (state, _temp_dir) = setup_test_state().await;
+        let app = router(state);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/artifact/test_artifact")
+                    .header("range", "bytes=7-14")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers().get("accept-ranges").unwrap(), "bytes");
+        assert_eq!(
+            response.headers().get("content-range").unwrap(),
+            "bytes 7-14/22"
+        );
+        assert_eq!(response.headers().get("content-length").unwrap(), "8");
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"artifact");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/artifact/test_artifact")
+                    .header("range", "bytes=100-")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(
+            response.headers().get("content-range").unwrap(),
+            "bytes */22"
+        );
+    }
+
+    #[test]
+    fn test_parse_byte_range() {
+        assert_eq!(parse_byte_range("bytes=0-99", 50), ByteRange::Partial(0, 49));
+        assert_eq!(parse_byte_range("bytes=10-", 50), ByteRange::Partial(10, 49));
+        assert_eq!(parse_byte_range("bytes=-5", 50), ByteRange::Partial(45, 49));
+        assert_eq!(parse_byte_range("bytes=-500", 50), ByteRange::Partial(0, 49));
+        assert_eq!(parse_byte_range("bytes=50-", 50), ByteRange::Unsatisfiable);
+        assert_eq!(parse_byte_range("bytes=-0", 50), ByteRange::Unsatisfiable);
+        assert_eq!(parse_byte_range("bytes=0-1,4-5", 50), ByteRange::Full);
+        assert_eq!(parse_byte_range("bytes=9-3", 50), ByteRange::Full);
+        assert_eq!(parse_byte_range("items=0-1", 50), ByteRange::Full);
+    }
+
     #[tokio::test]
     async fn test_artifact_meta() {
         let (state, _temp_dir) = setup_test_state().await;
@@ -1504,6 +1700,43 @@ mod tests {
         assert_eq!(json.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_list_artifacts_query_params() {
+        let (state, _temp_dir) = setup_test_state().await;
+        let app = router(state);
+
+        let cases = [
+            ("/artifacts?tag=type:text,test:yes&creator=test_creator", 1),
+            ("/artifacts?tag=type:text,type:audio", 0),
+            ("/artifacts?mime_type=audio/*", 0),
+            ("/artifacts?since=2000-01-01T00:00:00Z&sort=oldest", 1),
+            ("/artifacts?until=2000-01-01T00:00:00Z", 0),
+        ];
+        for (uri, expected) in cases {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let json: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+            assert_eq!(json.len(), expected, "{}", uri);
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/artifacts?sort=sideways")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn test_artifact_not_found() {
         let (state, _temp_dir) = setup_test_state().await;
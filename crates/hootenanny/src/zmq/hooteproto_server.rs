@@ -407,8 +407,11 @@ fn payload_type_name(payload: &Payload) -> &'static str {
         Payload::TransportStop => "transport_stop",
         Payload::TransportSeek { .. } => "transport_seek",
         Payload::TransportStatus => "transport_status",
+        Payload::TransportSetLoop { .. } => "transport_set_loop",
+        Payload::TransportRecord { .. } => "transport_record",
         Payload::TimelineQuery { .. } => "timeline_query",
         Payload::TimelineAddMarker { .. } => "timeline_add_marker",
+        Payload::TimelineSetSections { .. } => "timeline_set_sections",
         Payload::TimelineEvent { .. } => "timeline_event",
     }
 }
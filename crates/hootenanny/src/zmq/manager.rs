@@ -14,8 +14,9 @@ use tracing::{debug, error, info, warn};
 use hooteproto::garden::{
     ControlReply, ControlRequest, IOPubEvent, ShellReply, ShellRequest,
 };
+use hooteproto::garden_snapshot::GraphDelta;
 use hooteproto::request::ToolRequest;
-use hooteproto::responses::ToolResponse;
+use hooteproto::responses::{GardenGraphResponse, ToolResponse};
 use hooteproto::{GardenEndpoints, GardenPeer};
 
 /// Connection state to chaosgarden
@@ -33,12 +34,16 @@ pub enum ConnectionState {
 /// - Automatic reconnection
 /// - Connection state tracking
 /// - Event broadcasting
+/// - A cached audio graph kept current by IOPub graph deltas
 pub struct GardenManager {
     endpoints: GardenEndpoints,
     client: Arc<RwLock<Option<GardenPeer>>>,
     state: Arc<RwLock<ConnectionState>>,
     event_tx: mpsc::Sender<IOPubEvent>,
     event_rx: Arc<RwLock<Option<mpsc::Receiver<IOPubEvent>>>>,
+    /// Audio graph as of the last resync plus the deltas since; None until
+    /// first asked for, and again after the event stream drops
+    graph: Arc<RwLock<Option<GardenGraphResponse>>>,
 }
 
 impl GardenManager {
@@ -52,6 +57,7 @@ impl GardenManager {
             state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
             event_tx,
             event_rx: Arc::new(RwLock::new(Some(event_rx))),
+            graph: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -199,6 +205,7 @@ impl GardenManager {
 
         let event_tx = self.event_tx.clone();
         let state = self.state.clone();
+        let graph = self.graph.clone();
 
         // Reconnect with a fresh peer for request/control channels
         let new_client = GardenPeer::connect(&self.endpoints).await?;
@@ -220,6 +227,8 @@ impl GardenManager {
             }
 
             warn!("IOPub stream ended");
+            // Deltas may have been missed, so the next graph() resyncs
+            *graph.write().await = None;
             *state.write().await = ConnectionState::Disconnected;
         });
 
@@ -252,6 +261,41 @@ impl GardenManager {
             }
         }
     }
+
+    /// The audio graph, from the cache when there is one
+    pub async fn graph(&self) -> Result<GardenGraphResponse> {
+        if let Some(graph) = self.graph.read().await.as_ref() {
+            return Ok(graph.clone());
+        }
+        self.resync_graph().await
+    }
+
+    /// Fetch the full graph from chaosgarden and make it the cached copy
+    ///
+    /// The cache stays locked until the reply is in, so deltas that arrive
+    /// meanwhile are applied on top of it rather than lost.
+    pub async fn resync_graph(&self) -> Result<GardenGraphResponse> {
+        let mut cached = self.graph.write().await;
+        match self.tool_request(ToolRequest::GardenGraph).await? {
+            ToolResponse::GardenGraph(graph) => {
+                *cached = Some(graph.clone());
+                Ok(graph)
+            }
+            other => {
+                anyhow::bail!("unexpected response: {:?}", other)
+            }
+        }
+    }
+
+    /// Apply an IOPub graph delta to the cached graph
+    ///
+    /// Without a cache there is nothing to patch; the next `graph()` call
+    /// fetches the full graph instead.
+    pub async fn apply_graph_delta(&self, delta: &GraphDelta) {
+        if let Some(graph) = self.graph.write().await.as_mut() {
+            delta.apply(&mut graph.nodes, &mut graph.edges);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -271,6 +315,42 @@ mod tests {
         assert_eq!(manager.endpoints.shell, "tcp://192.168.1.100:5556");
     }
 
+    #[tokio::test]
+    async fn test_graph_deltas_patch_cached_graph() {
+        use hooteproto::garden_snapshot::GraphNode;
+
+        let node = |id: &str| GraphNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            type_id: "audio_file".to_string(),
+            inputs: vec![],
+            outputs: vec![],
+            latency_samples: 0,
+            can_realtime: true,
+            can_offline: true,
+        };
+        let delta = GraphDelta {
+            added_nodes: vec![node("b")],
+            removed_nodes: vec!["a".to_string()],
+            ..Default::default()
+        };
+
+        // Nothing cached yet: the delta is dropped rather than becoming the graph
+        let manager = GardenManager::from_socket_dir("/tmp");
+        manager.apply_graph_delta(&delta).await;
+        assert!(manager.graph.read().await.is_none());
+
+        *manager.graph.write().await = Some(GardenGraphResponse {
+            nodes: vec![node("a")],
+            edges: vec![],
+        });
+        manager.apply_graph_delta(&delta).await;
+
+        let graph = manager.graph().await.unwrap();
+        let ids: Vec<_> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["b"]);
+    }
+
     #[tokio::test]
     async fn test_initial_state_disconnected() {
         let manager = GardenManager::from_socket_dir("/tmp");
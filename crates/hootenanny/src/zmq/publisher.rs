@@ -8,7 +8,7 @@
 use anyhow::{Context as AnyhowContext, Result};
 use futures::SinkExt;
 use hooteproto::socket_config::{create_publisher_and_bind, ZmqContext, Multipart};
-use hooteproto::{broadcast_capnp, Broadcast};
+use hooteproto::{broadcast_capnp, tag_set_to_capnp, Broadcast};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
@@ -55,7 +55,7 @@ impl BroadcastPublisher {
         self.publish(Broadcast::ArtifactCreated {
             artifact_id: artifact_id.to_string(),
             content_hash: content_hash.to_string(),
-            tags,
+            tags: tags.into(),
             creator,
         })
         .await
@@ -219,11 +219,7 @@ fn broadcast_to_capnp(
             let mut artifact = builder.reborrow().init_artifact_created();
             artifact.set_artifact_id(artifact_id);
             artifact.set_content_hash(content_hash);
-
-            let mut tag_list = artifact.reborrow().init_tags(tags.len() as u32);
-            for (i, tag) in tags.iter().enumerate() {
-                tag_list.reborrow().set(i as u32, tag);
-            }
+            tag_set_to_capnp(tags, artifact.reborrow());
 
             artifact.set_creator(creator.as_deref().unwrap_or(""));
         }
@@ -231,11 +227,13 @@ fn broadcast_to_capnp(
             state,
             position_beats,
             tempo_bpm,
+            beats_per_bar,
         } => {
             let mut transport = builder.reborrow().init_transport_state_changed();
             transport.set_state(state);
             transport.set_position_beats(*position_beats);
             transport.set_tempo_bpm(*tempo_bpm);
+            transport.set_beats_per_bar(*beats_per_bar);
         }
         Broadcast::MarkerReached {
             position_beats,
@@ -295,6 +293,16 @@ fn broadcast_to_capnp(
             device.set_pipewire_id(*pipewire_id);
             device.set_name(name.as_deref().unwrap_or(""));
         }
+        Broadcast::MeterUpdate {
+            channel,
+            peak_db,
+            rms_db,
+        } => {
+            let mut meter = builder.reborrow().init_meter_update();
+            meter.set_channel(channel);
+            meter.set_peak_db(*peak_db);
+            meter.set_rms_db(*rms_db);
+        }
     }
     Ok(())
 }
@@ -314,5 +322,6 @@ fn broadcast_variant_name(broadcast: &Broadcast) -> &'static str {
         Broadcast::Log { .. } => "Log",
         Broadcast::DeviceConnected { .. } => "DeviceConnected",
         Broadcast::DeviceDisconnected { .. } => "DeviceDisconnected",
+        Broadcast::MeterUpdate { .. } => "MeterUpdate",
     }
 }
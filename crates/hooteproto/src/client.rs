@@ -29,6 +29,7 @@ use tokio::sync::{mpsc, oneshot, RwLock};
 use tracing::{debug, info, trace, warn};
 use uuid::Uuid;
 
+use crate::lazy_pirate::{LazyPirateMetrics, LazyPirateStats};
 use crate::{
     capnp_envelope_to_payload, envelope_capnp, payload_to_capnp_envelope,
     socket_config::{create_dealer_and_connect, DealerSocket, Multipart, ZmqContext},
@@ -353,6 +354,7 @@ pub struct HootClient {
     config: ClientConfig,
     cmd_tx: mpsc::Sender<ReactorCommand>,
     pub health: Arc<HealthTracker>,
+    stats: LazyPirateStats,
     /// ZMQ context must outlive the socket - keep it alive here
     #[allow(dead_code)]
     context: ZmqContext,
@@ -399,6 +401,7 @@ impl HootClient {
             config,
             cmd_tx,
             health,
+            stats: LazyPirateStats::new(),
             context,
         })
     }
@@ -425,6 +428,7 @@ impl HootClient {
         let timeout = Duration::from_millis(self.config.timeout_ms);
         let max_attempts = self.config.max_retries + 1;
         let mut attempts = 0;
+        self.stats.record_request();
 
         loop {
             attempts += 1;
@@ -440,12 +444,17 @@ impl HootClient {
                 self.config.name, request_id, attempts, max_attempts
             );
 
+            let sent_at = Instant::now();
             match self.send_single_request(frames, request_id, timeout).await {
                 Ok(response) => {
+                    self.stats.record_latency(sent_at.elapsed());
                     return Ok(response);
                 }
                 Err(e) => {
                     let error_msg = e.to_string();
+                    if error_msg.contains("timed out") {
+                        self.stats.record_timeout();
+                    }
 
                     // Connection lost is not retriable - fail immediately
                     if error_msg.contains("Connection lost") {
@@ -463,6 +472,7 @@ impl HootClient {
                             self.config.name, request_id, attempts, e
                         );
                         self.health.record_failure();
+                        self.stats.record_retry();
                         // Small delay before retry with backoff
                         tokio::time::sleep(Duration::from_millis(100 * attempts as u64)).await;
                     } else {
@@ -594,6 +604,11 @@ impl HootClient {
         self.health.is_connected()
     }
 
+    /// Retry, timeout, and latency statistics for requests on this client
+    pub fn metrics(&self) -> LazyPirateMetrics {
+        self.stats.snapshot()
+    }
+
     /// For compatibility - always returns true since socket is always created
     pub async fn is_socket_ready(&self) -> bool {
         true
@@ -638,6 +653,7 @@ pub fn spawn_health_task(
                                     info!("{}: Initial connection established", client.config.name);
                                 } else {
                                     info!("{}: Peer reconnected", client.config.name);
+                                    client.stats.record_reconnect();
                                 }
                                 if let Some(ref callback) = on_connected {
                                     callback();
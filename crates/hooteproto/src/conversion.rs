@@ -6,7 +6,7 @@
 //! - Payload ↔ Cap'n Proto (for wire serialization)
 
 use crate::{
-    Payload, SampleFormat, StreamDefinition, StreamFormat, TimelineEventType,
+    Payload, SampleFormat, StreamDefinition, StreamFormat, TimelineEventType, TimelineSection,
 };
 
 // Cap'n Proto imports for reading requests
@@ -28,10 +28,11 @@ pub fn payload_to_request(payload: &Payload) -> Result<Option<ToolRequest>, Tool
 /// Convert a ResponseEnvelope back to Payload for ZMQ transport.
 pub fn envelope_to_payload(envelope: ResponseEnvelope) -> Payload {
     match &envelope {
+        // Keep the full typed error in details so gateways can map its category
         ResponseEnvelope::Error(err) => Payload::Error {
             code: err.code().to_string(),
             message: err.message().to_string(),
-            details: None,
+            details: serde_json::to_value(err).ok(),
         },
         _ => Payload::TypedResponse(envelope),
     }
@@ -108,6 +109,23 @@ pub fn capnp_envelope_to_payload(
         envelope_capnp::payload::TransportStop(()) => Ok(Payload::TransportStop),
         envelope_capnp::payload::TransportStatus(()) => Ok(Payload::TransportStatus),
         envelope_capnp::payload::TransportSeek(seek) => Ok(Payload::TransportSeek { position_beats: seek?.get_position_beats() }),
+        envelope_capnp::payload::TransportSetLoop(set_loop) => {
+            let set_loop = set_loop?;
+            let end = set_loop.get_end_beats();
+            Ok(Payload::TransportSetLoop {
+                start_beats: set_loop.get_start_beats(),
+                end_beats: if end == 0.0 { None } else { Some(end) },
+                enabled: set_loop.get_enabled(),
+            })
+        }
+        envelope_capnp::payload::TransportRecord(record) => {
+            let record = record?;
+            let bus = record.get_bus()?.to_str()?;
+            Ok(Payload::TransportRecord {
+                enabled: record.get_enabled(),
+                bus: if bus.is_empty() { None } else { Some(bus.to_string()) },
+            })
+        }
         envelope_capnp::payload::TimelineQuery(query) => {
             let query = query?;
             Ok(Payload::TimelineQuery { from_beats: Some(query.get_from_beats()), to_beats: Some(query.get_to_beats()) })
@@ -120,6 +138,22 @@ pub fn capnp_envelope_to_payload(
                 metadata: serde_json::from_str(marker.get_metadata()?.to_str()?).unwrap_or_default(),
             })
         }
+        envelope_capnp::payload::TimelineSetSections(set) => {
+            let sections = set?
+                .get_sections()?
+                .iter()
+                .map(|section| {
+                    let crossfade = section.get_crossfade_beats();
+                    Ok(TimelineSection {
+                        name: section.get_name()?.to_str()?.to_string(),
+                        start_beats: section.get_start_beats(),
+                        end_beats: section.get_end_beats(),
+                        crossfade_beats: if crossfade < 0.0 { None } else { Some(crossfade) },
+                    })
+                })
+                .collect::<capnp::Result<Vec<_>>>()?;
+            Ok(Payload::TimelineSetSections { sections })
+        }
         envelope_capnp::payload::TimelineEvent(event) => {
             let event = event?;
             Ok(Payload::TimelineEvent {
@@ -177,10 +211,15 @@ pub fn capnp_envelope_to_payload(
         }
         envelope_capnp::payload::Error(error) => {
             let error = error?;
+            let details = error.get_details()?.to_str()?;
             Ok(Payload::Error {
                 code: error.get_code()?.to_str()?.to_string(),
                 message: error.get_message()?.to_str()?.to_string(),
-                details: None,
+                details: if details.is_empty() {
+                    None
+                } else {
+                    serde_json::from_str(details).ok()
+                },
             })
         }
         envelope_capnp::payload::ToolCall(call) => Err(capnp::Error::failed(format!("ToolCall deprecated: {}", call?.get_name()?.to_str()?))),
@@ -228,6 +267,17 @@ pub fn payload_to_capnp_envelope(
             Payload::TransportStop => payload_builder.set_transport_stop(()),
             Payload::TransportSeek { position_beats } => payload_builder.init_transport_seek().set_position_beats(*position_beats),
             Payload::TransportStatus => payload_builder.set_transport_status(()),
+            Payload::TransportSetLoop { start_beats, end_beats, enabled } => {
+                let mut l = payload_builder.init_transport_set_loop();
+                l.set_start_beats(*start_beats);
+                l.set_end_beats(end_beats.unwrap_or(0.0));
+                l.set_enabled(*enabled);
+            }
+            Payload::TransportRecord { enabled, bus } => {
+                let mut r = payload_builder.init_transport_record();
+                r.set_enabled(*enabled);
+                r.set_bus(bus.as_deref().unwrap_or(""));
+            }
             Payload::TimelineQuery { from_beats, to_beats } => {
                 let mut q = payload_builder.init_timeline_query();
                 q.set_from_beats(from_beats.unwrap_or(0.0));
@@ -239,6 +289,18 @@ pub fn payload_to_capnp_envelope(
                 m.set_marker_type(marker_type);
                 m.set_metadata(serde_json::to_string(metadata).unwrap_or_default());
             }
+            Payload::TimelineSetSections { sections } => {
+                let mut list = payload_builder
+                    .init_timeline_set_sections()
+                    .init_sections(sections.len() as u32);
+                for (i, section) in sections.iter().enumerate() {
+                    let mut s = list.reborrow().get(i as u32);
+                    s.set_name(&section.name);
+                    s.set_start_beats(section.start_beats);
+                    s.set_end_beats(section.end_beats);
+                    s.set_crossfade_beats(section.crossfade_beats.unwrap_or(-1.0));
+                }
+            }
             Payload::TimelineEvent { event_type, position_beats, tempo, metadata } => {
                 let mut e = payload_builder.init_timeline_event();
                 e.set_event_type(timeline_event_type_to_capnp(event_type));
@@ -566,6 +628,11 @@ fn request_to_capnp_tool_request(builder: &mut tools_capnp::tool_request::Builde
             m.set_artifact_id(req.artifact_id.as_deref().unwrap_or(""));
             m.set_hash(req.hash.as_deref().unwrap_or(""));
         }
+        ToolRequest::MusicAnalyze(req) => {
+            let mut m = builder.reborrow().init_music_analyze();
+            m.set_task(&req.task);
+            m.set_input_json(&req.input_json);
+        }
         ToolRequest::AudioListDevices => builder.reborrow().set_audio_list_devices(()),
         ToolRequest::GardenGraph => builder.reborrow().set_garden_graph(()),
         ToolRequest::TimeConvert(req) => {
@@ -1403,6 +1470,13 @@ fn capnp_tool_request_to_request(reader: tools_capnp::tool_request::Reader) -> c
                 hash: capnp_optional_string(m.get_hash()?),
             }))
         }
+        tools_capnp::tool_request::MusicAnalyze(m) => {
+            let m = m?;
+            Ok(ToolRequest::MusicAnalyze(MusicAnalyzeRequest {
+                task: m.get_task()?.to_str()?.to_string(),
+                input_json: m.get_input_json()?.to_str()?.to_string(),
+            }))
+        }
         tools_capnp::tool_request::AudioListDevices(()) => Ok(ToolRequest::AudioListDevices),
         tools_capnp::tool_request::GardenGraph(()) => Ok(ToolRequest::GardenGraph),
         tools_capnp::tool_request::TimeConvert(t) => {
@@ -1987,6 +2061,13 @@ fn response_to_capnp_tool_response(
             b.set_enabled(r.enabled);
             b.set_gain(r.gain);
         }
+        ToolResponse::GardenPendingApprovals(r) => {
+            let b = builder.reborrow().init_garden_pending_approvals();
+            let mut approvals = b.init_approvals(r.approvals.len() as u32);
+            for (i, approval) in r.approvals.iter().enumerate() {
+                approval.to_capnp(&mut approvals.reborrow().get(i as u32));
+            }
+        }
         ToolResponse::GardenAudioSnapshot(r) => {
             let mut b = builder.reborrow().init_garden_audio_snapshot();
             b.set_sample_rate(r.sample_rate);
@@ -2310,6 +2391,12 @@ fn response_to_capnp_tool_response(
             b.set_summary(&r.summary);
         }
 
+        ToolResponse::MusicAnalyzed(r) => {
+            let mut b = builder.reborrow().init_music_analyzed();
+            b.set_result_json(&r.result_json);
+            b.set_model(&r.model);
+        }
+
         ToolResponse::AudioDevices(r) => {
             let mut b = builder.reborrow().init_audio_devices();
             {
@@ -2898,6 +2985,17 @@ fn capnp_tool_response_to_response(
                 gain: r.get_gain(),
             }))
         }
+        Which::GardenPendingApprovals(r) => {
+            let r = r?;
+            let approvals = r
+                .get_approvals()?
+                .iter()
+                .map(crate::garden_snapshot::ApprovalInfo::from_capnp)
+                .collect::<capnp::Result<Vec<_>>>()?;
+            Ok(ToolResponse::GardenPendingApprovals(GardenPendingApprovalsResponse {
+                approvals,
+            }))
+        }
         Which::GardenAudioSnapshot(r) => {
             let r = r?;
             let samples_reader = r.get_samples()?;
@@ -3452,6 +3550,13 @@ fn capnp_tool_response_to_response(
                 summary: r.get_summary()?.to_string()?,
             }))
         }
+        Which::MusicAnalyzed(r) => {
+            let r = r?;
+            Ok(ToolResponse::MusicAnalyzed(MusicAnalyzedResponse {
+                result_json: r.get_result_json()?.to_string()?,
+                model: r.get_model()?.to_string()?,
+            }))
+        }
         Which::AudioDevices(r) => {
             let r = r?;
             let sources = r.get_sources()?;
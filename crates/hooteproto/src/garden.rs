@@ -322,6 +322,9 @@ pub struct PendingApproval {
     pub content_hash: String,
     pub content_type: ContentType,
     pub resolved_at: DateTime<Utc>,
+    /// When the approval timeout decides for you, if one is configured
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 /// Region behavior specification (for creation)
@@ -471,6 +474,9 @@ pub enum ShellRequest {
     GetSnapshot,
     /// Get audio graph nodes and edges.
     GetGraph,
+    /// Full graph as of the last `GraphDelta`, for (re)building a cached
+    /// copy. Deltas published after the reply apply on top of it.
+    ResyncGraph,
     /// Get I/O devices (outputs, inputs, MIDI).
     GetIOState,
 
@@ -846,6 +852,12 @@ pub enum IOPubEvent {
         region_id: Uuid,
         reason: Option<String>,
     },
+    /// Nobody answered in time; `LatentApproved` or `LatentRejected`
+    /// follows with the timeout decision
+    LatentApprovalTimedOut {
+        region_id: Uuid,
+        approved: bool,
+    },
 
     // Playback
     PlaybackStarted,
@@ -858,6 +870,20 @@ pub enum IOPubEvent {
         region_id: Uuid,
         at_beat: Beat,
     },
+    /// Transport played, paused, stopped or seeked, at the resolved position
+    TransportStateChanged {
+        /// "playing", "paused" or "stopped"
+        state: String,
+        position_beats: f64,
+        tempo_bpm: f64,
+        beats_per_bar: u32,
+    },
+    /// Timeline marker reached during playback (loop wrap, playlist transition)
+    MarkerReached {
+        position_beats: f64,
+        marker_type: String,
+        metadata: serde_json::Value,
+    },
 
     // Graph changes
     NodeAdded {
@@ -875,6 +901,10 @@ pub enum IOPubEvent {
         source: PortRef,
         dest: PortRef,
     },
+    /// Nodes and edges changed since the previous graph broadcast
+    GraphDelta {
+        delta: crate::garden_snapshot::GraphDelta,
+    },
 
     // Participant changes
     ParticipantOnline {
@@ -890,6 +920,25 @@ pub enum IOPubEvent {
         available: bool,
     },
 
+    // Metering
+    /// Post-fader levels for one mixer channel, throttled by the mixer
+    MeterUpdate {
+        channel: String,
+        peak_db: f32,
+        rms_db: f32,
+    },
+
+    // Artifacts
+    /// Content stored in CAS by chaosgarden (e.g. a finished bounce)
+    ArtifactCreated {
+        artifact_id: String,
+        content_hash: String,
+        tags: Vec<String>,
+        creator: Option<String>,
+        #[serde(default)]
+        mime_type: Option<String>,
+    },
+
     // Errors and warnings
     Error {
         error: String,
@@ -146,6 +146,8 @@ pub struct GraphEdge {
     pub source_port: String,
     pub dest_id: String,
     pub dest_port: String,
+    pub gain: f64,
+    pub active: bool,
 }
 
 /// Running latent job.
@@ -158,11 +160,36 @@ pub struct LatentJob {
 }
 
 /// Pending content approval.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApprovalInfo {
     pub region_id: String,
     pub content_hash: String,
     pub content_type: MediaType,
+    /// When the content resolved, in Unix milliseconds
+    pub resolved_at: u64,
+    /// When the approval timeout decides, in Unix milliseconds; `None`
+    /// waits indefinitely
+    pub expires_at: Option<u64>,
+}
+
+impl ApprovalInfo {
+    /// Snapshot form of a pending approval from the garden shell
+    pub fn from_pending(approval: &crate::garden::PendingApproval) -> Self {
+        use crate::garden::ContentType;
+        Self {
+            region_id: approval.region_id.to_string(),
+            content_hash: approval.content_hash.clone(),
+            content_type: match approval.content_type {
+                ContentType::Audio => MediaType::Audio,
+                ContentType::Midi => MediaType::Midi,
+                ContentType::Control => MediaType::Control,
+            },
+            resolved_at: approval.resolved_at.timestamp_millis().max(0) as u64,
+            expires_at: approval
+                .expires_at
+                .map(|at| at.timestamp_millis().max(0) as u64),
+        }
+    }
 }
 
 /// Audio output device.
@@ -270,6 +297,63 @@ pub enum IOPubEvent {
     // Errors
     Error { error: String, context: Option<String> },
     Warning { message: String },
+
+    /// Incremental graph change, relative to the previous graph broadcast.
+    /// Subscribers without a baseline fetch the full graph via `ResyncGraph`.
+    GraphDelta { delta: GraphDelta },
+}
+
+/// Nodes and edges changed since the previous graph broadcast.
+///
+/// Edges are matched by endpoints; a gain or mute change on an existing
+/// connection shows up in `changed_edges`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphDelta {
+    pub added_nodes: Vec<GraphNode>,
+    pub removed_nodes: Vec<String>,
+    pub changed_nodes: Vec<GraphNode>,
+    pub added_edges: Vec<GraphEdge>,
+    pub removed_edges: Vec<GraphEdge>,
+    pub changed_edges: Vec<GraphEdge>,
+}
+
+impl GraphEdge {
+    fn same_connection(&self, other: &GraphEdge) -> bool {
+        self.source_id == other.source_id
+            && self.source_port == other.source_port
+            && self.dest_id == other.dest_id
+            && self.dest_port == other.dest_port
+    }
+}
+
+impl GraphDelta {
+    /// Patch a cached graph in place.
+    ///
+    /// Idempotent, so a delta that overlaps a freshly fetched graph is harmless.
+    pub fn apply(&self, nodes: &mut Vec<GraphNode>, edges: &mut Vec<GraphEdge>) {
+        nodes.retain(|n| !self.removed_nodes.contains(&n.id));
+        for update in self.added_nodes.iter().chain(&self.changed_nodes) {
+            match nodes.iter_mut().find(|n| n.id == update.id) {
+                Some(node) => *node = update.clone(),
+                None => nodes.push(update.clone()),
+            }
+        }
+
+        edges.retain(|e| !self.removed_edges.iter().any(|r| r.same_connection(e)));
+        for update in self.added_edges.iter().chain(&self.changed_edges) {
+            match edges.iter_mut().find(|e| e.same_connection(update)) {
+                Some(edge) => *edge = update.clone(),
+                None => edges.push(update.clone()),
+            }
+        }
+    }
+}
+
+impl GardenSnapshot {
+    /// Patch the graph portion of a cached snapshot in place.
+    pub fn apply_graph_delta(&mut self, delta: &GraphDelta) {
+        delta.apply(&mut self.nodes, &mut self.edges);
+    }
 }
 
 impl IOPubEvent {
@@ -301,6 +385,9 @@ impl IOPubEvent {
             | IOPubEvent::AudioUnderrun { .. }
             | IOPubEvent::Error { .. }
             | IOPubEvent::Warning { .. } => false,
+
+            // Applied in place via GardenSnapshot::apply_graph_delta
+            IOPubEvent::GraphDelta { .. } => false,
         }
     }
 }
@@ -492,6 +579,51 @@ impl GraphEdge {
             source_port: reader.get_source_port()?.to_string()?,
             dest_id: reader.get_dest_id()?.to_string()?,
             dest_port: reader.get_dest_port()?.to_string()?,
+            gain: reader.get_gain(),
+            active: reader.get_active(),
+        })
+    }
+}
+
+impl GraphDelta {
+    pub fn from_capnp(reader: garden_capnp::graph_delta_event::Reader) -> capnp::Result<Self> {
+        let mut added_nodes = Vec::new();
+        for node_reader in reader.get_added_nodes()? {
+            added_nodes.push(GraphNode::from_capnp(node_reader)?);
+        }
+
+        let mut removed_nodes = Vec::new();
+        for id in reader.get_removed_nodes()? {
+            removed_nodes.push(id?.to_string()?);
+        }
+
+        let mut changed_nodes = Vec::new();
+        for node_reader in reader.get_changed_nodes()? {
+            changed_nodes.push(GraphNode::from_capnp(node_reader)?);
+        }
+
+        let mut added_edges = Vec::new();
+        for edge_reader in reader.get_added_edges()? {
+            added_edges.push(GraphEdge::from_capnp(edge_reader)?);
+        }
+
+        let mut removed_edges = Vec::new();
+        for edge_reader in reader.get_removed_edges()? {
+            removed_edges.push(GraphEdge::from_capnp(edge_reader)?);
+        }
+
+        let mut changed_edges = Vec::new();
+        for edge_reader in reader.get_changed_edges()? {
+            changed_edges.push(GraphEdge::from_capnp(edge_reader)?);
+        }
+
+        Ok(Self {
+            added_nodes,
+            removed_nodes,
+            changed_nodes,
+            added_edges,
+            removed_edges,
+            changed_edges,
         })
     }
 }
@@ -515,10 +647,13 @@ impl ApprovalInfo {
             garden_capnp::ContentTypeEnum::Control => MediaType::Control,
         };
 
+        let expires_at = reader.get_expires_at();
         Ok(Self {
             region_id: reader.get_region_id()?.to_string()?,
             content_hash: reader.get_content_hash()?.to_string()?,
             content_type,
+            resolved_at: reader.get_resolved_at(),
+            expires_at: (expires_at != 0).then_some(expires_at),
         })
     }
 }
@@ -732,6 +867,9 @@ impl IOPubEvent {
             Which::Warning(msg) => Ok(IOPubEvent::Warning {
                 message: msg?.to_string()?,
             }),
+            Which::GraphDelta(delta) => Ok(IOPubEvent::GraphDelta {
+                delta: GraphDelta::from_capnp(delta?)?,
+            }),
         }
     }
 }
@@ -897,6 +1035,42 @@ impl GraphEdge {
         builder.set_source_port(&self.source_port);
         builder.set_dest_id(&self.dest_id);
         builder.set_dest_port(&self.dest_port);
+        builder.set_gain(self.gain);
+        builder.set_active(self.active);
+    }
+}
+
+impl GraphDelta {
+    pub fn to_capnp(&self, builder: &mut garden_capnp::graph_delta_event::Builder) {
+        let mut added = builder.reborrow().init_added_nodes(self.added_nodes.len() as u32);
+        for (i, node) in self.added_nodes.iter().enumerate() {
+            node.to_capnp(&mut added.reborrow().get(i as u32));
+        }
+
+        let mut removed = builder.reborrow().init_removed_nodes(self.removed_nodes.len() as u32);
+        for (i, id) in self.removed_nodes.iter().enumerate() {
+            removed.set(i as u32, id);
+        }
+
+        let mut changed = builder.reborrow().init_changed_nodes(self.changed_nodes.len() as u32);
+        for (i, node) in self.changed_nodes.iter().enumerate() {
+            node.to_capnp(&mut changed.reborrow().get(i as u32));
+        }
+
+        let mut added = builder.reborrow().init_added_edges(self.added_edges.len() as u32);
+        for (i, edge) in self.added_edges.iter().enumerate() {
+            edge.to_capnp(&mut added.reborrow().get(i as u32));
+        }
+
+        let mut removed = builder.reborrow().init_removed_edges(self.removed_edges.len() as u32);
+        for (i, edge) in self.removed_edges.iter().enumerate() {
+            edge.to_capnp(&mut removed.reborrow().get(i as u32));
+        }
+
+        let mut changed = builder.reborrow().init_changed_edges(self.changed_edges.len() as u32);
+        for (i, edge) in self.changed_edges.iter().enumerate() {
+            edge.to_capnp(&mut changed.reborrow().get(i as u32));
+        }
     }
 }
 
@@ -918,6 +1092,8 @@ impl ApprovalInfo {
             MediaType::Midi => garden_capnp::ContentTypeEnum::Midi,
             MediaType::Control => garden_capnp::ContentTypeEnum::Control,
         });
+        builder.set_resolved_at(self.resolved_at);
+        builder.set_expires_at(self.expires_at.unwrap_or(0));
     }
 }
 
@@ -1079,6 +1255,9 @@ impl IOPubEvent {
             IOPubEvent::Warning { message } => {
                 builder.set_warning(message);
             }
+            IOPubEvent::GraphDelta { delta } => {
+                delta.to_capnp(&mut builder.init_graph_delta());
+            }
         }
     }
 }
@@ -1131,4 +1310,99 @@ mod tests {
         assert_eq!(parsed.id, region.id);
         assert_eq!(parsed.name, region.name);
     }
+
+    #[test]
+    fn test_approval_info_capnp_keeps_times() {
+        let resolved_at = chrono::Utc::now();
+        let pending = crate::garden::PendingApproval {
+            region_id: uuid::Uuid::new_v4(),
+            artifact_id: "artifact_1".to_string(),
+            content_hash: "abc123".to_string(),
+            content_type: crate::garden::ContentType::Audio,
+            resolved_at,
+            expires_at: Some(resolved_at + chrono::Duration::seconds(30)),
+        };
+        let waiting = ApprovalInfo::from_pending(&pending);
+        let forever = ApprovalInfo {
+            expires_at: None,
+            ..waiting.clone()
+        };
+        assert_eq!(waiting.expires_at, Some(waiting.resolved_at + 30_000));
+
+        for approval in [waiting, forever] {
+            let mut message = capnp::message::Builder::new_default();
+            approval.to_capnp(&mut message.init_root::<garden_capnp::approval_info::Builder>());
+            let reader = message
+                .get_root_as_reader::<garden_capnp::approval_info::Reader>()
+                .unwrap();
+            assert_eq!(ApprovalInfo::from_capnp(reader).unwrap(), approval);
+        }
+    }
+
+    fn node(id: &str, name: &str) -> GraphNode {
+        GraphNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            type_id: "test".to_string(),
+            inputs: vec![],
+            outputs: vec![],
+            latency_samples: 0,
+            can_realtime: true,
+            can_offline: true,
+        }
+    }
+
+    fn edge(source: &str, dest: &str) -> GraphEdge {
+        GraphEdge {
+            source_id: source.to_string(),
+            source_port: "out".to_string(),
+            dest_id: dest.to_string(),
+            dest_port: "in".to_string(),
+            gain: 1.0,
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_apply_graph_delta() {
+        let mut snapshot = GardenSnapshot {
+            version: 1,
+            transport: TransportSnapshot { playing: false, position: 0.0, tempo: 120.0 },
+            regions: vec![],
+            nodes: vec![node("a", "src"), node("b", "sink")],
+            edges: vec![edge("a", "b")],
+            latent_jobs: vec![],
+            pending_approvals: vec![],
+            outputs: vec![],
+            inputs: vec![],
+            midi_devices: vec![],
+            tempo_map: TempoMapSnapshot { default_tempo: 120.0, ticks_per_beat: 480, changes: vec![] },
+        };
+
+        let event = IOPubEvent::GraphDelta {
+            delta: GraphDelta {
+                added_nodes: vec![node("c", "fx")],
+                removed_nodes: vec!["b".to_string()],
+                changed_nodes: vec![node("a", "renamed")],
+                added_edges: vec![edge("a", "c")],
+                removed_edges: vec![edge("a", "b")],
+                changed_edges: vec![GraphEdge { gain: 0.5, ..edge("a", "c") }],
+            },
+        };
+        assert!(!event.invalidates_cache());
+
+        let IOPubEvent::GraphDelta { delta } = event else { unreachable!() };
+        snapshot.apply_graph_delta(&delta);
+
+        let names: Vec<&str> = snapshot.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["renamed", "fx"]);
+        assert_eq!(snapshot.edges.len(), 1);
+        assert_eq!(snapshot.edges[0].dest_id, "c");
+        assert_eq!(snapshot.edges[0].gain, 0.5);
+
+        // Applying the same delta again changes nothing
+        snapshot.apply_graph_delta(&delta);
+        assert_eq!(snapshot.nodes.len(), 2);
+        assert_eq!(snapshot.edges.len(), 1);
+    }
 }
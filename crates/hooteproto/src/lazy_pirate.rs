@@ -12,9 +12,11 @@
 //! - RECONNECT_IVL_MAX can be set to cap backoff
 //! - libzmq doesn't have the idle timeout issues that affect pure-Rust implementations
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 // Re-export ConnectionState from client module for consistency
 pub use crate::client::ConnectionState;
@@ -87,6 +89,108 @@ impl LazyPirateConfig {
     }
 }
 
+/// Latency histogram size. Bucket `i` counts replies that took under `2^i`
+/// microseconds (and at least half that), so 32 buckets reach ~36 minutes.
+const LATENCY_BUCKETS: usize = 32;
+
+/// Point-in-time view of a client's retry behavior.
+///
+/// Latency percentiles come from power-of-two buckets and report the
+/// bucket's upper bound, so they overestimate by at most 2x.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LazyPirateMetrics {
+    /// Requests issued by callers (not counting retries)
+    pub requests: u64,
+    /// Extra attempts made after a failed attempt
+    pub retries: u64,
+    /// Attempts that got no reply before their deadline
+    pub timeouts: u64,
+    /// Times the peer came back after being unresponsive
+    pub reconnects: u64,
+    /// Median round trip of successful attempts
+    pub p50_latency: Duration,
+    /// 99th percentile round trip of successful attempts
+    pub p99_latency: Duration,
+}
+
+/// Lock-free accumulator behind [`LazyPirateMetrics`].
+///
+/// Every update is a single relaxed atomic add, so clients can record on
+/// the request path without adding latency. Counters are independent; a
+/// snapshot taken mid-request may be off by one between fields.
+#[derive(Debug, Default)]
+pub struct LazyPirateStats {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    timeouts: AtomicU64,
+    reconnects: AtomicU64,
+    latency: [AtomicU64; LATENCY_BUCKETS],
+}
+
+impl LazyPirateStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_timeout(&self) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the round trip of a successful attempt
+    pub fn record_latency(&self, latency: Duration) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let bucket = (u64::BITS - micros.leading_zeros()) as usize;
+        self.latency[bucket.min(LATENCY_BUCKETS - 1)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LazyPirateMetrics {
+        let buckets: Vec<u64> = self
+            .latency
+            .iter()
+            .map(|b| b.load(Ordering::Relaxed))
+            .collect();
+
+        LazyPirateMetrics {
+            requests: self.requests.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            p50_latency: percentile(&buckets, 0.50),
+            p99_latency: percentile(&buckets, 0.99),
+        }
+    }
+}
+
+/// Upper bound of the bucket holding quantile `q`; zero with no samples.
+fn percentile(buckets: &[u64], q: f64) -> Duration {
+    let total: u64 = buckets.iter().sum();
+    if total == 0 {
+        return Duration::ZERO;
+    }
+    let rank = ((total as f64 * q).ceil() as u64).max(1);
+
+    let mut seen = 0;
+    for (i, count) in buckets.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            return Duration::from_micros(1u64 << i);
+        }
+    }
+    Duration::from_micros(1u64 << (buckets.len() - 1))
+}
+
 /// Result of a single request attempt (before retry logic).
 #[derive(Debug)]
 pub enum AttemptResult<T> {
@@ -134,6 +238,9 @@ pub trait LazyPirateClient: Send + Sync {
     /// is connected (ZMQ handles that automatically).
     fn health_state(&self) -> ConnectionState;
 
+    /// Retry, timeout, and latency statistics since the client was created.
+    fn metrics(&self) -> LazyPirateMetrics;
+
     /// Check if peer is currently responding
     fn is_connected(&self) -> bool {
         self.health_state() == ConnectionState::Connected
@@ -176,6 +283,44 @@ mod tests {
         assert_eq!(config.backoff_for_attempt(100), Duration::from_secs(5));
     }
 
+    #[test]
+    fn stats_count_timeouts_and_retries() {
+        let stats = LazyPirateStats::new();
+        assert_eq!(stats.snapshot(), LazyPirateMetrics::default());
+
+        // One request: first attempt times out, the retry succeeds
+        stats.record_request();
+        stats.record_timeout();
+        stats.record_retry();
+        stats.record_latency(Duration::from_millis(3));
+
+        let metrics = stats.snapshot();
+        assert_eq!(metrics.requests, 1);
+        assert_eq!(metrics.retries, 1);
+        assert_eq!(metrics.timeouts, 1);
+        assert_eq!(metrics.reconnects, 0);
+        // 3000us lands in the [2048, 4096) bucket
+        assert_eq!(metrics.p50_latency, Duration::from_micros(4096));
+    }
+
+    #[test]
+    fn latency_percentiles() {
+        let stats = LazyPirateStats::new();
+        for _ in 0..98 {
+            stats.record_latency(Duration::from_micros(100));
+        }
+        stats.record_latency(Duration::from_millis(50));
+        stats.record_latency(Duration::from_secs(2));
+
+        let metrics = stats.snapshot();
+        assert_eq!(metrics.p50_latency, Duration::from_micros(128));
+        assert_eq!(metrics.p99_latency, Duration::from_micros(65_536));
+
+        // Out-of-range samples land in the last bucket instead of panicking
+        stats.record_latency(Duration::MAX);
+        stats.record_latency(Duration::ZERO);
+    }
+
     #[test]
     fn default_config_values() {
         let config = LazyPirateConfig::default();
@@ -116,7 +116,9 @@ pub mod garden_snapshot;
 pub mod metadata;
 pub mod request;
 pub mod responses;
+pub mod tags;
 pub mod timing;
+pub mod validate;
 
 // Peer infrastructure - batteries included for building hootenanny peers
 #[cfg(feature = "peer")]
@@ -138,7 +140,9 @@ pub mod garden_listener;
 pub use client::{ClientConfig, ConnectionState, HealthTracker, HootClient, spawn_health_task};
 
 #[cfg(feature = "peer")]
-pub use lazy_pirate::{AttemptResult, LazyPirateClient, LazyPirateConfig};
+pub use lazy_pirate::{
+    AttemptResult, LazyPirateClient, LazyPirateConfig, LazyPirateMetrics, LazyPirateStats,
+};
 
 #[cfg(feature = "peer")]
 pub use garden_peer::GardenPeer;
@@ -159,11 +163,13 @@ pub use frame::{Command, ContentType, FrameError, HootFrame, ReadyPayload, PROTO
 pub use metadata::{GenerationParams, Metrics, StoredMetadata};
 pub use request::ToolRequest;
 pub use responses::ToolResponse;
+pub use tags::{TagDictionary, TagSet};
 pub use timing::ToolTiming;
+pub use validate::Validate;
 
 // Garden state snapshot types for query evaluation in hootenanny
 pub use garden_snapshot::{
-    ApprovalInfo, AudioInput, AudioOutput, BehaviorType, GardenSnapshot, GraphEdge,
+    ApprovalInfo, AudioInput, AudioOutput, BehaviorType, GardenSnapshot, GraphDelta, GraphEdge,
     GraphNode, IOPubEvent, IOPubMessage, LatentJob, LatentStatus, MediaType, MidiDeviceInfo,
     MidiDirection, Port, RegionSnapshot, SignalType, TempoChange, TempoMapSnapshot, TransportSnapshot,
 };
@@ -183,7 +189,7 @@ pub struct ArtifactMetadata {
     pub variation_set_id: Option<String>,
     pub parent_id: Option<String>,
     #[serde(default)]
-    pub tags: Vec<String>,
+    pub tags: TagSet,
     pub creator: Option<String>,
 }
 
@@ -290,6 +296,17 @@ pub enum Payload {
         position_beats: f64,
     },
     TransportStatus,
+    /// Set the transport loop region (end None = loop at the last region's end)
+    TransportSetLoop {
+        start_beats: f64,
+        end_beats: Option<f64>,
+        enabled: bool,
+    },
+    /// Arm or disarm bounce-to-disk (bus None = master output)
+    TransportRecord {
+        enabled: bool,
+        bus: Option<String>,
+    },
 
     // Timeline Tools (Holler → Chaosgarden) - Protocol commands
     TimelineQuery {
@@ -301,6 +318,10 @@ pub enum Payload {
         marker_type: String,
         metadata: serde_json::Value,
     },
+    /// Replace the arrangement sections playback crossfades between
+    TimelineSetSections {
+        sections: Vec<TimelineSection>,
+    },
 }
 
 /// Worker registration announcement
@@ -359,6 +380,17 @@ fn default_confidence() -> f64 {
     1.0
 }
 
+/// Arrangement section of the project timeline, as playback sees it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineSection {
+    pub name: String,
+    pub start_beats: f64,
+    pub end_beats: f64,
+    /// Beats to crossfade in from the previous section (None = engine default)
+    #[serde(default)]
+    pub crossfade_beats: Option<f64>,
+}
+
 /// Stream definition for audio/MIDI capture
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct StreamDefinition {
@@ -785,7 +817,7 @@ pub enum Broadcast {
     ArtifactCreated {
         artifact_id: String,
         content_hash: String,
-        tags: Vec<String>,
+        tags: TagSet,
         creator: Option<String>,
     },
 
@@ -794,6 +826,8 @@ pub enum Broadcast {
         state: String,
         position_beats: f64,
         tempo_bpm: f64,
+        /// Meter numerator, for bar-quantized scheduling
+        beats_per_bar: u32,
     },
 
     /// Timeline marker reached during playback
@@ -838,6 +872,61 @@ pub enum Broadcast {
         /// Device name (if known)
         name: Option<String>,
     },
+
+    /// Mixer channel levels (throttled, drives VU meters)
+    MeterUpdate {
+        /// Mixer channel name
+        channel: String,
+        /// Post-fader peak with decaying hold, in dBFS
+        peak_db: f32,
+        /// Post-fader RMS of the latest block, in dBFS
+        rms_db: f32,
+    },
+}
+
+/// Read an artifact's tags, falling back to the plain `tags` list from older senders.
+fn capnp_to_tag_set(artifact: broadcast_capnp::artifact_created::Reader) -> capnp::Result<TagSet> {
+    use broadcast_capnp::tag::Which;
+
+    let tag_set = artifact.get_tag_set()?;
+    if tag_set.is_empty() {
+        return Ok(artifact
+            .get_tags()?
+            .iter()
+            .filter_map(|t| t.ok().and_then(|s| s.to_string().ok()))
+            .collect());
+    }
+
+    let mut wire = Vec::with_capacity(tag_set.len() as usize);
+    for tag in tag_set.iter() {
+        wire.push(match tag.which()? {
+            Which::Known(id) => tags::WireTag::Known(id),
+            Which::Name(name) => tags::WireTag::Name(name?.to_string()?),
+        });
+    }
+    TagSet::from_wire(wire).map_err(|e| capnp::Error::failed(e.to_string()))
+}
+
+/// Write `tags` in the compact form: well-known ids, strings for the rest.
+///
+/// The full strings also go in the deprecated `tags` list so readers that
+/// predate `tagSet` still see every tag.
+pub fn tag_set_to_capnp(tags: &TagSet, mut artifact: broadcast_capnp::artifact_created::Builder) {
+    let strings = tags.to_strings();
+    let mut legacy = artifact.reborrow().init_tags(strings.len() as u32);
+    for (i, tag) in strings.iter().enumerate() {
+        legacy.set(i as u32, tag);
+    }
+
+    let wire = tags.to_wire();
+    let mut list = artifact.reborrow().init_tag_set(wire.len() as u32);
+    for (i, tag) in wire.iter().enumerate() {
+        let mut entry = list.reborrow().get(i as u32);
+        match tag {
+            tags::WireTag::Known(id) => entry.set_known(*id),
+            tags::WireTag::Name(name) => entry.set_name(name),
+        }
+    }
 }
 
 /// Parse a Cap'n Proto broadcast message into the Rust Broadcast enum
@@ -895,11 +984,7 @@ pub fn capnp_to_broadcast(
             let artifact = artifact?;
             let artifact_id = artifact.get_artifact_id()?.to_string()?;
             let content_hash = artifact.get_content_hash()?.to_string()?;
-            let tags: Vec<String> = artifact
-                .get_tags()?
-                .iter()
-                .filter_map(|t| t.ok().and_then(|s| s.to_string().ok()))
-                .collect();
+            let tags = capnp_to_tag_set(artifact)?;
             let creator_str = artifact.get_creator()?.to_string()?;
             let creator = if creator_str.is_empty() {
                 None
@@ -918,10 +1003,12 @@ pub fn capnp_to_broadcast(
             let state = transport.get_state()?.to_string()?;
             let position_beats = transport.get_position_beats();
             let tempo_bpm = transport.get_tempo_bpm();
+            let beats_per_bar = transport.get_beats_per_bar();
             Ok(Broadcast::TransportStateChanged {
                 state,
                 position_beats,
                 tempo_bpm,
+                beats_per_bar,
             })
         }
         Which::MarkerReached(marker) => {
@@ -1000,6 +1087,14 @@ pub fn capnp_to_broadcast(
             };
             Ok(Broadcast::DeviceDisconnected { pipewire_id, name })
         }
+        Which::MeterUpdate(meter) => {
+            let meter = meter?;
+            Ok(Broadcast::MeterUpdate {
+                channel: meter.get_channel()?.to_string()?,
+                peak_db: meter.get_peak_db(),
+                rms_db: meter.get_rms_db(),
+            })
+        }
         // Stream events are handled separately by chaosgarden, not needed here
         Which::StreamHeadPosition(_)
         | Which::StreamChunkFull(_)
@@ -1037,6 +1132,21 @@ mod tests {
         assert_eq!(envelope.payload, parsed.payload);
     }
 
+    #[test]
+    fn music_analyze_roundtrip() {
+        use crate::request::MusicAnalyzeRequest;
+
+        let envelope = Envelope::new(Payload::ToolRequest(ToolRequest::MusicAnalyze(
+            MusicAnalyzeRequest {
+                task: "analyze_key".to_string(),
+                input_json: r#"{"notes":[],"context":{}}"#.to_string(),
+            },
+        )));
+        let json = serde_json::to_string(&envelope).unwrap();
+        let parsed: Envelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(envelope.payload, parsed.payload);
+    }
+
     #[test]
     fn typed_response_roundtrip() {
         use crate::responses::ToolResponse;
@@ -1107,6 +1217,18 @@ mod tests {
         assert_eq!(broadcast, parsed);
     }
 
+    #[test]
+    fn meter_update_roundtrip() {
+        let broadcast = Broadcast::MeterUpdate {
+            channel: "monitor".to_string(),
+            peak_db: -6.0,
+            rms_db: -12.5,
+        };
+        let json = serde_json::to_string(&broadcast).unwrap();
+        let parsed: Broadcast = serde_json::from_str(&json).unwrap();
+        assert_eq!(broadcast, parsed);
+    }
+
     #[test]
     fn timeline_event_roundtrip() {
         let envelope = Envelope::new(Payload::TimelineEvent {
@@ -1122,4 +1244,126 @@ mod tests {
         let parsed: Envelope = serde_json::from_str(&json).unwrap();
         assert_eq!(envelope.payload, parsed.payload);
     }
+
+    #[test]
+    fn transport_set_loop_roundtrip() {
+        let envelope = Envelope::new(Payload::TransportSetLoop {
+            start_beats: 8.0,
+            end_beats: Some(16.0),
+            enabled: true,
+        });
+        let json = serde_json::to_string(&envelope).unwrap();
+        let parsed: Envelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(envelope.payload, parsed.payload);
+    }
+
+    #[test]
+    fn timeline_set_sections_roundtrip() {
+        let payload = Payload::TimelineSetSections {
+            sections: vec![
+                TimelineSection {
+                    name: "verse".to_string(),
+                    start_beats: 0.0,
+                    end_beats: 16.0,
+                    crossfade_beats: None,
+                },
+                TimelineSection {
+                    name: "chorus".to_string(),
+                    start_beats: 16.0,
+                    end_beats: 32.0,
+                    crossfade_beats: Some(0.0),
+                },
+            ],
+        };
+
+        let message = payload_to_capnp_envelope(Uuid::new_v4(), &payload).unwrap();
+        let reader = message
+            .get_root_as_reader::<envelope_capnp::envelope::Reader>()
+            .unwrap();
+        assert_eq!(capnp_envelope_to_payload(reader).unwrap(), payload);
+    }
+
+    #[test]
+    fn transport_record_roundtrip() {
+        let envelope = Envelope::new(Payload::TransportRecord {
+            enabled: true,
+            bus: Some(Uuid::new_v4().to_string()),
+        });
+        let json = serde_json::to_string(&envelope).unwrap();
+        let parsed: Envelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(envelope.payload, parsed.payload);
+    }
+
+    #[test]
+    fn artifact_created_tags_capnp_roundtrip() {
+        let tags: TagSet = ["type:midi", "lib-test:custom"].into_iter().collect();
+
+        let mut message = capnp::message::Builder::new_default();
+        {
+            let mut broadcast = message.init_root::<broadcast_capnp::broadcast::Builder>();
+            let mut artifact = broadcast.reborrow().init_artifact_created();
+            artifact.set_artifact_id("art_1");
+            tag_set_to_capnp(&tags, artifact.reborrow());
+        }
+
+        let reader = message
+            .get_root_as_reader::<broadcast_capnp::broadcast::Reader>()
+            .unwrap();
+        match capnp_to_broadcast(reader).unwrap() {
+            Broadcast::ArtifactCreated { tags: parsed, .. } => assert_eq!(parsed, tags),
+            other => panic!("expected ArtifactCreated, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn artifact_created_tags_readable_without_tag_set() {
+        let tags: TagSet = ["type:midi", "lib-test:legacy-reader"].into_iter().collect();
+
+        let mut message = capnp::message::Builder::new_default();
+        {
+            let mut broadcast = message.init_root::<broadcast_capnp::broadcast::Builder>();
+            let artifact = broadcast.reborrow().init_artifact_created();
+            tag_set_to_capnp(&tags, artifact);
+        }
+
+        // A reader that only knows `tags @2`
+        let reader = message
+            .get_root_as_reader::<broadcast_capnp::broadcast::Reader>()
+            .unwrap();
+        let artifact = match reader.which().unwrap() {
+            broadcast_capnp::broadcast::Which::ArtifactCreated(artifact) => artifact.unwrap(),
+            _ => panic!("expected ArtifactCreated"),
+        };
+        let mut legacy: Vec<String> = artifact
+            .get_tags()
+            .unwrap()
+            .iter()
+            .map(|t| t.unwrap().to_string().unwrap())
+            .collect();
+        legacy.sort();
+        let mut expected = tags.to_strings();
+        expected.sort();
+        assert_eq!(legacy, expected);
+    }
+
+    #[test]
+    fn artifact_created_reads_legacy_tag_list() {
+        let mut message = capnp::message::Builder::new_default();
+        {
+            let mut broadcast = message.init_root::<broadcast_capnp::broadcast::Builder>();
+            let artifact = broadcast.reborrow().init_artifact_created();
+            let mut list = artifact.init_tags(1);
+            list.set(0, "type:audio");
+        }
+
+        let reader = message
+            .get_root_as_reader::<broadcast_capnp::broadcast::Reader>()
+            .unwrap();
+        match capnp_to_broadcast(reader).unwrap() {
+            Broadcast::ArtifactCreated { tags, .. } => {
+                assert_eq!(tags.to_strings(), vec!["type:audio"])
+            }
+            other => panic!("expected ArtifactCreated, got {:?}", other),
+        }
+    }
 }
@@ -260,6 +260,8 @@ pub enum ToolRequest {
     MidiClassifyVoices(MidiClassifyVoicesRequest),
     /// Unified music understanding: key, meter, chords, voices
     MidiUnderstand(MidiUnderstandRequest),
+    /// Single analysis task forwarded to a model worker
+    MusicAnalyze(MusicAnalyzeRequest),
 
     // ==========================================================================
     // Audio Device Discovery
@@ -304,6 +306,7 @@ impl ToolRequest {
             Self::MidiInfo(_) => ToolTiming::AsyncShort,
             Self::AudioInfo(_) => ToolTiming::AsyncShort,
             Self::MidiAnalyze(_) | Self::MidiVoiceSeparate(_) | Self::MidiStemsExport(_) | Self::MidiClassifyVoices(_) | Self::MidiUnderstand(_) => ToolTiming::AsyncShort,
+            Self::MusicAnalyze(_) => ToolTiming::AsyncShort,
             Self::Ping | Self::ListResources => ToolTiming::AsyncShort,
             Self::ReadResource(_) => ToolTiming::AsyncShort,
             Self::CasStore(_) | Self::CasGet(_) | Self::CasUploadFile(_) | Self::CasStats => ToolTiming::AsyncShort,
@@ -456,6 +459,7 @@ impl ToolRequest {
             Self::MidiStemsExport(_) => "midi_stems_export",
             Self::MidiClassifyVoices(_) => "midi_classify_voices",
             Self::MidiUnderstand(_) => "midi_understand",
+            Self::MusicAnalyze(_) => "music_analyze",
             Self::RaveEncode(_) => "rave_encode",
             Self::RaveDecode(_) => "rave_decode",
             Self::RaveReconstruct(_) => "rave_reconstruct",
@@ -1248,7 +1252,7 @@ pub struct MidiAnalyzeRequest {
 pub struct MidiVoiceSeparateRequest {
     pub artifact_id: Option<String>,
     pub hash: Option<String>,
-    /// Separation method: "auto", "channel_split", "pitch_contiguity", "skyline", "bassline"
+    /// Separation method: "auto", "channel_split", "pitch_contiguity", "skyline", "bassline", "drum_kit"
     pub method: Option<String>,
     /// Max pitch jump in semitones before starting a new voice (default 12)
     pub max_pitch_jump: Option<u8>,
@@ -1296,3 +1300,12 @@ pub struct MidiUnderstandRequest {
     pub artifact_id: Option<String>,
     pub hash: Option<String>,
 }
+
+/// One music-understand analyzer call, served by a model worker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicAnalyzeRequest {
+    /// Analyzer method: "analyze_key" or "extract_chords"
+    pub task: String,
+    /// JSON-serialized task inputs (notes, context, key)
+    pub input_json: String,
+}
@@ -77,6 +77,7 @@ pub enum ToolResponse {
     GardenInputStatus(GardenInputStatusResponse),
     GardenMonitorStatus(GardenMonitorStatusResponse),
     GardenAudioSnapshot(GardenAudioSnapshotResponse),
+    GardenPendingApprovals(GardenPendingApprovalsResponse),
     AudioCaptured(AudioCapturedResponse),
 
     // === MIDI I/O ===
@@ -132,6 +133,7 @@ pub enum ToolResponse {
     MidiStemsExported(MidiStemsExportedResponse),
     MidiVoicesClassified(MidiVoicesClassifiedResponse),
     MidiUnderstood(MidiUnderstoodResponse),
+    MusicAnalyzed(MusicAnalyzedResponse),
 
     // === Audio Device Discovery ===
     AudioDevices(AudioDevicesResponse),
@@ -768,6 +770,11 @@ pub struct GardenMonitorStatusResponse {
     pub gain: f64,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GardenPendingApprovalsResponse {
+    pub approvals: Vec<crate::garden_snapshot::ApprovalInfo>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GardenAudioSnapshotResponse {
     pub sample_rate: u32,
@@ -1137,6 +1144,15 @@ pub struct MidiUnderstoodResponse {
     pub summary: String,
 }
 
+/// Result of a single worker-side analysis task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicAnalyzedResponse {
+    /// KeyDetection or Vec<ChordEvent> serialized as JSON
+    pub result_json: String,
+    /// Model that produced the result
+    pub model: String,
+}
+
 // =============================================================================
 // Audio Device Discovery
 // =============================================================================
@@ -0,0 +1,426 @@
+//! Interned artifact tags.
+//!
+//! Artifacts share a small vocabulary of tags (`type:midi`, `source:orpheus`,
+//! ...), so `TagSet` stores ids into a process-wide `TagDictionary` instead of
+//! a `String` per tag. The dictionary never frees, so it stops taking new tags
+//! after [`MAX_INTERNED_TAGS`]; sets hold anything past that as a plain string.
+//!
+//! # Wire forms
+//!
+//! - **Human-readable** (JSON): a plain string array, unchanged from `Vec<String>`.
+//! - **Binary** serde formats: each tag is a [`WireTag`]. Tags from the
+//!   well-known table go out as their id; anything else falls back to the
+//!   full string, since runtime ids are only meaningful inside one process.
+//!
+//! The well-known table is compiled in and append-only, so every process
+//! (and any cross-language client that copies it) agrees on those ids
+//! without a handshake.
+
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Tags with fixed ids shared by every process. Append only: reordering or
+/// removing entries changes the meaning of ids already on the wire.
+pub const WELL_KNOWN_TAGS: &[&str] = &[
+    "type:midi",
+    "type:audio",
+    "type:abc",
+    "type:text",
+    "type:soundfont",
+    "source:orpheus",
+    "source:anticipatory",
+    "source:musicgen",
+    "source:yue",
+    "source:audioldm2",
+    "source:abc",
+    "source:render",
+    "source:capture",
+    "phase:initial",
+    "phase:exploration",
+    "phase:draft",
+    "phase:final",
+    "model:orpheus",
+];
+
+/// Cap on tags interned at runtime, on top of the well-known table.
+///
+/// Tags are caller-supplied, so without a cap a stream of unique tags would
+/// grow the dictionary for the life of the process.
+pub const MAX_INTERNED_TAGS: usize = 4096;
+
+/// Id of an interned tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TagId(u32);
+
+impl TagId {
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+
+    /// Whether this id means the same tag in every process.
+    pub fn is_well_known(&self) -> bool {
+        (self.0 as usize) < WELL_KNOWN_TAGS.len()
+    }
+}
+
+/// Process-wide string interner for tags.
+///
+/// Ids below `WELL_KNOWN_TAGS.len()` are the well-known table; later ids are
+/// handed out in first-seen order and never reused, up to
+/// [`MAX_INTERNED_TAGS`] of them.
+pub struct TagDictionary {
+    names: Vec<Arc<str>>,
+    ids: HashMap<Arc<str>, TagId>,
+}
+
+static DICTIONARY: OnceLock<RwLock<TagDictionary>> = OnceLock::new();
+
+impl TagDictionary {
+    fn new() -> Self {
+        let mut dict = Self {
+            names: Vec::new(),
+            ids: HashMap::new(),
+        };
+        for tag in WELL_KNOWN_TAGS {
+            dict.insert(tag);
+        }
+        dict
+    }
+
+    fn global() -> &'static RwLock<TagDictionary> {
+        DICTIONARY.get_or_init(|| RwLock::new(TagDictionary::new()))
+    }
+
+    fn insert(&mut self, tag: &str) -> Option<TagId> {
+        if let Some(id) = self.ids.get(tag) {
+            return Some(*id);
+        }
+        if self.names.len() >= WELL_KNOWN_TAGS.len() + MAX_INTERNED_TAGS {
+            return None;
+        }
+        let id = TagId(self.names.len() as u32);
+        let name: Arc<str> = Arc::from(tag);
+        self.names.push(Arc::clone(&name));
+        self.ids.insert(name, id);
+        Some(id)
+    }
+
+    /// Intern `tag`, returning its id, or `None` once the dictionary is full.
+    pub fn intern(tag: &str) -> Option<TagId> {
+        if let Some(id) = Self::lookup(tag) {
+            return Some(id);
+        }
+        Self::global().write().unwrap().insert(tag)
+    }
+
+    /// Id of an already-interned tag.
+    pub fn lookup(tag: &str) -> Option<TagId> {
+        Self::global().read().unwrap().ids.get(tag).copied()
+    }
+
+    /// The string behind an id.
+    pub fn resolve(id: TagId) -> Option<Arc<str>> {
+        Self::global()
+            .read()
+            .unwrap()
+            .names
+            .get(id.0 as usize)
+            .cloned()
+    }
+
+    /// Number of interned tags, including the well-known table.
+    pub fn len() -> usize {
+        Self::global().read().unwrap().names.len()
+    }
+}
+
+/// One tag in the binary wire form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WireTag {
+    /// Index into `WELL_KNOWN_TAGS`
+    Known(u32),
+    /// Full tag string
+    Name(String),
+}
+
+/// One member of a [`TagSet`].
+///
+/// A tag is `Spilled` only if the dictionary was already full when it was
+/// first seen; since the dictionary never frees, that tag is spilled in every
+/// set from then on and equality stays consistent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Entry {
+    Interned(TagId),
+    Spilled(Arc<str>),
+}
+
+impl Entry {
+    fn new(tag: &str) -> Self {
+        match TagDictionary::intern(tag) {
+            Some(id) => Entry::Interned(id),
+            None => Entry::Spilled(Arc::from(tag)),
+        }
+    }
+
+    /// The entry `tag` would have, without interning it.
+    fn find(tag: &str) -> Self {
+        match TagDictionary::lookup(tag) {
+            Some(id) => Entry::Interned(id),
+            None => Entry::Spilled(Arc::from(tag)),
+        }
+    }
+
+    fn name(&self) -> Option<Arc<str>> {
+        match self {
+            Entry::Interned(id) => TagDictionary::resolve(*id),
+            Entry::Spilled(name) => Some(Arc::clone(name)),
+        }
+    }
+}
+
+/// Ordered, de-duplicated set of tags, stored as interned ids.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct TagSet(Vec<Entry>);
+
+impl TagSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a tag, keeping first-insertion order. Returns false if present.
+    pub fn insert(&mut self, tag: &str) -> bool {
+        let entry = Entry::new(tag);
+        if self.0.contains(&entry) {
+            return false;
+        }
+        self.0.push(entry);
+        true
+    }
+
+    pub fn remove(&mut self, tag: &str) -> bool {
+        let entry = Entry::find(tag);
+        let before = self.0.len();
+        self.0.retain(|t| *t != entry);
+        self.0.len() != before
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.contains(&Entry::find(tag))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Tags as strings, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = Arc<str>> + '_ {
+        self.0.iter().filter_map(Entry::name)
+    }
+
+    pub fn to_strings(&self) -> Vec<String> {
+        self.iter().map(|t| t.to_string()).collect()
+    }
+
+    /// Compact form: ids for well-known tags, strings for the rest.
+    pub fn to_wire(&self) -> Vec<WireTag> {
+        self.0
+            .iter()
+            .filter_map(|entry| match entry {
+                Entry::Interned(id) if id.is_well_known() => Some(WireTag::Known(id.0)),
+                _ => entry.name().map(|name| WireTag::Name(name.to_string())),
+            })
+            .collect()
+    }
+
+    /// Rebuild from the compact form, failing on ids outside the table.
+    pub fn from_wire(tags: Vec<WireTag>) -> Result<Self, UnknownTagId> {
+        let mut set = Self::new();
+        for tag in tags {
+            match tag {
+                WireTag::Known(id) => {
+                    let name = WELL_KNOWN_TAGS.get(id as usize).ok_or(UnknownTagId(id))?;
+                    set.insert(name);
+                }
+                WireTag::Name(name) => {
+                    set.insert(&name);
+                }
+            }
+        }
+        Ok(set)
+    }
+}
+
+/// A `WireTag::Known` id this build's table doesn't contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("unknown well-known tag id {0}")]
+pub struct UnknownTagId(pub u32);
+
+impl fmt::Debug for TagSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for TagSet {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for tag in iter {
+            set.insert(tag.as_ref());
+        }
+        set
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for TagSet {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for tag in iter {
+            self.insert(tag.as_ref());
+        }
+    }
+}
+
+impl From<Vec<String>> for TagSet {
+    fn from(tags: Vec<String>) -> Self {
+        tags.into_iter().collect()
+    }
+}
+
+impl From<&TagSet> for Vec<String> {
+    fn from(tags: &TagSet) -> Self {
+        tags.to_strings()
+    }
+}
+
+impl From<TagSet> for Vec<String> {
+    fn from(tags: TagSet) -> Self {
+        tags.to_strings()
+    }
+}
+
+impl Serialize for TagSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.iter().map(|t| t.to_string()))
+        } else {
+            self.to_wire().serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TagSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            Ok(Vec::<String>::deserialize(deserializer)?.into())
+        } else {
+            let wire = Vec::<WireTag>::deserialize(deserializer)?;
+            TagSet::from_wire(wire).map_err(de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_known_ids_are_stable() {
+        assert_eq!(TagDictionary::lookup("type:midi"), Some(TagId(0)));
+        assert!(TagDictionary::intern("phase:final")
+            .unwrap()
+            .is_well_known());
+        assert!(!TagDictionary::intern("tags-test:custom")
+            .unwrap()
+            .is_well_known());
+        assert!(TagDictionary::len() > WELL_KNOWN_TAGS.len());
+    }
+
+    #[test]
+    fn set_keeps_order_and_dedupes() {
+        let mut tags: TagSet = ["source:orpheus", "tags-test:jam", "type:midi"]
+            .into_iter()
+            .collect();
+        assert!(!tags.insert("tags-test:jam"));
+        assert!(tags.contains("type:midi"));
+        assert!(!tags.contains("tags-test:never-interned"));
+
+        assert_eq!(
+            tags.to_strings(),
+            vec!["source:orpheus", "tags-test:jam", "type:midi"]
+        );
+
+        assert!(tags.remove("source:orpheus"));
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[test]
+    fn json_form_is_string_array() {
+        let tags = TagSet::from(vec!["type:audio".to_string(), "tags-test:x".to_string()]);
+        let json = serde_json::to_value(&tags).unwrap();
+        assert_eq!(json, serde_json::json!(["type:audio", "tags-test:x"]));
+
+        let parsed: TagSet = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, tags);
+    }
+
+    #[test]
+    fn wire_form_falls_back_to_strings() {
+        let tags: TagSet = ["type:midi", "tags-test:wire"].into_iter().collect();
+        let wire = tags.to_wire();
+        assert_eq!(
+            wire,
+            vec![
+                WireTag::Known(0),
+                WireTag::Name("tags-test:wire".to_string())
+            ]
+        );
+        assert_eq!(TagSet::from_wire(wire).unwrap(), tags);
+
+        assert_eq!(
+            TagSet::from_wire(vec![WireTag::Known(u32::MAX)]),
+            Err(UnknownTagId(u32::MAX))
+        );
+    }
+
+    #[test]
+    fn dictionary_stops_growing_at_cap() {
+        let mut dict = TagDictionary::new();
+        for i in 0..MAX_INTERNED_TAGS {
+            assert!(dict.insert(&format!("tags-test:cap-{i}")).is_some());
+        }
+        assert_eq!(dict.names.len(), WELL_KNOWN_TAGS.len() + MAX_INTERNED_TAGS);
+
+        assert_eq!(dict.insert("tags-test:one-too-many"), None);
+        assert_eq!(
+            dict.insert("tags-test:cap-0"),
+            dict.ids.get("tags-test:cap-0").copied()
+        );
+        assert_eq!(dict.names.len(), WELL_KNOWN_TAGS.len() + MAX_INTERNED_TAGS);
+    }
+
+    #[test]
+    fn spilled_tags_behave_like_interned_ones() {
+        let mut tags: TagSet = ["type:midi"].into_iter().collect();
+        tags.0.push(Entry::Spilled(Arc::from("tags-test:spilled")));
+
+        assert!(tags.contains("tags-test:spilled"));
+        assert_eq!(tags.to_strings(), vec!["type:midi", "tags-test:spilled"]);
+        assert_eq!(
+            tags.to_wire(),
+            vec![
+                WireTag::Known(0),
+                WireTag::Name("tags-test:spilled".to_string())
+            ]
+        );
+
+        assert!(tags.remove("tags-test:spilled"));
+        assert_eq!(tags.len(), 1);
+    }
+}
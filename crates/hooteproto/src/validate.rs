@@ -0,0 +1,969 @@
+//! Runtime validation of tool request parameters.
+//!
+//! Request types only describe shape; `Validate` enforces the domain rules
+//! (tempo ranges, non-empty hashes and prompts, MIDI value ranges, ...) so a
+//! bad request is rejected once, before dispatch, with the offending field.
+//! The typed dispatcher calls `ToolRequest::validate()` on every request and
+//! returns `ToolError::Validation`, which the MCP edge maps to invalid_params.
+
+use crate::envelope::ValidationError;
+use crate::request::*;
+
+/// Slowest tempo accepted for playback
+pub const MIN_TEMPO_BPM: f64 = 20.0;
+/// Fastest tempo accepted for playback
+pub const MAX_TEMPO_BPM: f64 = 400.0;
+
+/// Domain checks beyond what deserialization enforces.
+pub trait Validate {
+    /// Returns the first invalid field, if any.
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+fn invalid(code: &str, field: &str, message: String) -> Result<(), ValidationError> {
+    Err(ValidationError {
+        code: code.to_string(),
+        message,
+        field: Some(field.to_string()),
+    })
+}
+
+fn non_empty(field: &str, value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return invalid("empty_field", field, format!("{} must not be empty", field));
+    }
+    Ok(())
+}
+
+fn in_range(field: &str, value: f64, min: f64, max: f64) -> Result<(), ValidationError> {
+    if !value.is_finite() || value < min || value > max {
+        return invalid(
+            "out_of_range",
+            field,
+            format!(
+                "{} must be between {} and {}, got {}",
+                field, min, max, value
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn positive(field: &str, value: f64) -> Result<(), ValidationError> {
+    if !value.is_finite() || value <= 0.0 {
+        return invalid(
+            "out_of_range",
+            field,
+            format!("{} must be greater than 0, got {}", field, value),
+        );
+    }
+    Ok(())
+}
+
+fn non_negative(field: &str, value: f64) -> Result<(), ValidationError> {
+    if !value.is_finite() || value < 0.0 {
+        return invalid(
+            "out_of_range",
+            field,
+            format!("{} must not be negative, got {}", field, value),
+        );
+    }
+    Ok(())
+}
+
+fn nonzero(field: &str, value: u64) -> Result<(), ValidationError> {
+    if value == 0 {
+        return invalid(
+            "out_of_range",
+            field,
+            format!("{} must be at least 1", field),
+        );
+    }
+    Ok(())
+}
+
+/// `type/subtype` with no whitespace, e.g. `audio/wav`
+///
+/// Empty is allowed and means the caller left the type unspecified.
+fn mime_type(field: &str, value: &str) -> Result<(), ValidationError> {
+    if value.is_empty() {
+        return Ok(());
+    }
+    let valid = match value.split_once('/') {
+        Some((kind, subtype)) => {
+            !kind.is_empty()
+                && !subtype.is_empty()
+                && !subtype.contains('/')
+                && !value.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        return invalid(
+            "invalid_mime_type",
+            field,
+            format!("{} must look like type/subtype, got {:?}", field, value),
+        );
+    }
+    Ok(())
+}
+
+/// At least one of two alternative identifiers must be present and non-empty
+fn one_of(
+    field_a: &str,
+    a: &Option<String>,
+    field_b: &str,
+    b: &Option<String>,
+) -> Result<(), ValidationError> {
+    let present = |v: &Option<String>| v.as_deref().is_some_and(|s| !s.trim().is_empty());
+    if !present(a) && !present(b) {
+        return invalid(
+            "missing_field",
+            field_a,
+            format!("either {} or {} is required", field_a, field_b),
+        );
+    }
+    Ok(())
+}
+
+fn midi_7bit(field: &str, value: u8) -> Result<(), ValidationError> {
+    if value > 127 {
+        return invalid(
+            "out_of_range",
+            field,
+            format!("{} must be 0-127, got {}", field, value),
+        );
+    }
+    Ok(())
+}
+
+fn midi_channel(field: &str, value: u8) -> Result<(), ValidationError> {
+    if value > 15 {
+        return invalid(
+            "out_of_range",
+            field,
+            format!("{} must be 0-15, got {}", field, value),
+        );
+    }
+    Ok(())
+}
+
+fn sampling(temperature: Option<f32>, top_p: Option<f32>) -> Result<(), ValidationError> {
+    if let Some(t) = temperature {
+        in_range("temperature", t as f64, 0.0, 2.0)?;
+    }
+    if let Some(p) = top_p {
+        in_range("top_p", p as f64, 0.0, 1.0)?;
+    }
+    Ok(())
+}
+
+fn opt_positive(field: &str, value: Option<f32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| positive(field, v as f64))
+}
+
+fn opt_nonzero(field: &str, value: Option<u32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| nonzero(field, v as u64))
+}
+
+fn sample_rate(field: &str, value: Option<u32>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| in_range(field, v as f64, 8_000.0, 192_000.0))
+}
+
+/// Request types whose every well-typed value is acceptable
+macro_rules! no_constraints {
+    ($($ty:ty),* $(,)?) => {
+        $(impl Validate for $ty {
+            fn validate(&self) -> Result<(), ValidationError> {
+                Ok(())
+            }
+        })*
+    };
+}
+
+no_constraints!(
+    ConfigGetRequest,
+    JobListRequest,
+    EventPollRequest,
+    WeaveResetRequest,
+    WeaveHelpRequest,
+    CompleteRequest,
+    GardenAttachInputRequest,
+    GetToolHelpRequest,
+);
+
+// --- CAS & artifacts ---
+
+impl Validate for CasStoreRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        mime_type("mime_type", &self.mime_type)
+    }
+}
+
+impl Validate for CasInspectRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("hash", &self.hash)
+    }
+}
+
+impl Validate for CasGetRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("hash", &self.hash)
+    }
+}
+
+impl Validate for CasUploadFileRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("file_path", &self.file_path)?;
+        mime_type("mime_type", &self.mime_type)
+    }
+}
+
+impl Validate for ArtifactUploadRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("file_path", &self.file_path)?;
+        mime_type("mime_type", &self.mime_type)
+    }
+}
+
+impl Validate for ArtifactGetRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("id", &self.id)
+    }
+}
+
+impl Validate for ArtifactListRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.limit.map_or(Ok(()), |l| nonzero("limit", l as u64))
+    }
+}
+
+impl Validate for ArtifactCreateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("cas_hash", &self.cas_hash)
+    }
+}
+
+// --- Orpheus ---
+
+impl Validate for OrpheusGenerateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        opt_nonzero("max_tokens", self.max_tokens)?;
+        opt_nonzero("num_variations", self.num_variations)?;
+        sampling(self.temperature, self.top_p)
+    }
+}
+
+impl Validate for OrpheusGenerateSeededRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("seed_hash", &self.seed_hash)?;
+        opt_nonzero("max_tokens", self.max_tokens)?;
+        opt_nonzero("num_variations", self.num_variations)?;
+        sampling(self.temperature, self.top_p)
+    }
+}
+
+impl Validate for OrpheusContinueRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("input_hash", &self.input_hash)?;
+        opt_nonzero("max_tokens", self.max_tokens)?;
+        opt_nonzero("num_variations", self.num_variations)?;
+        sampling(self.temperature, self.top_p)
+    }
+}
+
+impl Validate for OrpheusBridgeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("section_a_hash", &self.section_a_hash)?;
+        opt_nonzero("max_tokens", self.max_tokens)?;
+        sampling(self.temperature, self.top_p)
+    }
+}
+
+impl Validate for OrpheusLoopsRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        opt_nonzero("max_tokens", self.max_tokens)?;
+        opt_nonzero("num_variations", self.num_variations)?;
+        sampling(self.temperature, self.top_p)
+    }
+}
+
+impl Validate for OrpheusClassifyRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("midi_hash", &self.midi_hash)
+    }
+}
+
+// --- Audio conversion & generation ---
+
+impl Validate for MidiToWavRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("input_hash", &self.input_hash)?;
+        non_empty("soundfont_hash", &self.soundfont_hash)?;
+        sample_rate("sample_rate", self.sample_rate)
+    }
+}
+
+impl Validate for SoundfontInspectRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("soundfont_hash", &self.soundfont_hash)
+    }
+}
+
+impl Validate for SoundfontPresetInspectRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("soundfont_hash", &self.soundfont_hash)?;
+        in_range("bank", self.bank as f64, 0.0, 128.0)?;
+        in_range("program", self.program as f64, 0.0, 127.0)
+    }
+}
+
+impl Validate for MusicgenGenerateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        opt_positive("duration", self.duration)?;
+        opt_nonzero("top_k", self.top_k)?;
+        if let Some(g) = self.guidance_scale {
+            non_negative("guidance_scale", g as f64)?;
+        }
+        sampling(self.temperature, self.top_p)
+    }
+}
+
+impl Validate for YueGenerateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("lyrics", &self.lyrics)?;
+        opt_nonzero("max_new_tokens", self.max_new_tokens)?;
+        opt_nonzero("run_n_segments", self.run_n_segments)
+    }
+}
+
+impl Validate for Audioldm2GenerateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        opt_positive("duration", self.duration)?;
+        opt_nonzero("num_inference_steps", self.num_inference_steps)?;
+        if let Some(g) = self.guidance_scale {
+            non_negative("guidance_scale", g as f64)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for AnticipatoryGenerateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        opt_positive("length_seconds", self.length_seconds)?;
+        sampling(None, self.top_p)
+    }
+}
+
+impl Validate for AnticipatoryContinueRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("input_hash", &self.input_hash)?;
+        opt_positive("length_seconds", self.length_seconds)?;
+        opt_positive("prime_seconds", self.prime_seconds)?;
+        sampling(None, self.top_p)
+    }
+}
+
+impl Validate for AnticipatoryEmbedRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("input_hash", &self.input_hash)
+    }
+}
+
+impl Validate for DemucsSeparateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("audio_hash", &self.audio_hash)
+    }
+}
+
+// --- Analysis ---
+
+impl Validate for BeatthisAnalyzeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of(
+            "audio_hash",
+            &self.audio_hash,
+            "audio_path",
+            &self.audio_path,
+        )
+    }
+}
+
+impl Validate for ClapAnalyzeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("audio_hash", &self.audio_hash)
+    }
+}
+
+impl Validate for MidiInfoRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of("artifact_id", &self.artifact_id, "hash", &self.hash)
+    }
+}
+
+impl Validate for AudioInfoRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of("artifact_id", &self.artifact_id, "hash", &self.hash)
+    }
+}
+
+impl Validate for MidiAnalyzeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of("artifact_id", &self.artifact_id, "hash", &self.hash)?;
+        if let Some(t) = self.polyphony_threshold {
+            in_range("polyphony_threshold", t, 0.0, 1.0)?;
+        }
+        self.density_window_beats
+            .map_or(Ok(()), |w| positive("density_window_beats", w))
+    }
+}
+
+impl Validate for MidiVoiceSeparateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of("artifact_id", &self.artifact_id, "hash", &self.hash)?;
+        if let Some(gap) = self.max_gap_beats {
+            positive("max_gap_beats", gap)?;
+        }
+        self.max_voices
+            .map_or(Ok(()), |v| nonzero("max_voices", v as u64))
+    }
+}
+
+impl Validate for MidiStemsExportRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of("artifact_id", &self.artifact_id, "hash", &self.hash)?;
+        non_empty("voice_data", &self.voice_data)
+    }
+}
+
+impl Validate for MidiClassifyVoicesRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of("artifact_id", &self.artifact_id, "hash", &self.hash)?;
+        non_empty("voice_data", &self.voice_data)
+    }
+}
+
+impl Validate for MidiUnderstandRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        one_of("artifact_id", &self.artifact_id, "hash", &self.hash)
+    }
+}
+
+impl Validate for MusicAnalyzeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("task", &self.task)?;
+        non_empty("input_json", &self.input_json)
+    }
+}
+
+// --- ABC ---
+
+impl Validate for AbcParseRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("abc", &self.abc)
+    }
+}
+
+impl Validate for AbcValidateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("abc", &self.abc)
+    }
+}
+
+impl Validate for AbcTransposeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("abc", &self.abc)?;
+        if self.semitones.is_none() && self.target_key.is_none() {
+            return invalid(
+                "missing_field",
+                "semitones",
+                "either semitones or target_key is required".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Validate for AbcToMidiRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("abc", &self.abc)?;
+        if let Some(bpm) = self.tempo_override {
+            in_range("tempo_override", bpm as f64, MIN_TEMPO_BPM, MAX_TEMPO_BPM)?;
+        }
+        if let Some(v) = self.velocity {
+            midi_7bit("velocity", v)?;
+        }
+        self.channel.map_or(Ok(()), |c| midi_channel("channel", c))
+    }
+}
+
+// --- Garden ---
+
+impl Validate for GardenSeekRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_negative("beat", self.beat)
+    }
+}
+
+impl Validate for GardenSetTempoRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        in_range("bpm", self.bpm, MIN_TEMPO_BPM, MAX_TEMPO_BPM)
+    }
+}
+
+impl Validate for GardenGetRegionsRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if end < start {
+                return invalid(
+                    "out_of_range",
+                    "end",
+                    format!("end ({}) must not be before start ({})", end, start),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Validate for GardenCreateRegionRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_negative("position", self.position)?;
+        positive("duration", self.duration)?;
+        non_empty("behavior_type", &self.behavior_type)?;
+        non_empty("content_id", &self.content_id)
+    }
+}
+
+impl Validate for GardenDeleteRegionRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("region_id", &self.region_id)
+    }
+}
+
+impl Validate for GardenMoveRegionRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("region_id", &self.region_id)?;
+        non_negative("new_position", self.new_position)
+    }
+}
+
+impl Validate for TimeConvertRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_negative("value", self.value)?;
+        non_empty("from_unit", &self.from_unit)?;
+        non_empty("to_unit", &self.to_unit)
+    }
+}
+
+impl Validate for GardenAttachAudioRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        sample_rate("sample_rate", self.sample_rate)?;
+        opt_nonzero("latency_frames", self.latency_frames)
+    }
+}
+
+impl Validate for GardenSetMonitorRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.gain.map_or(Ok(()), |g| non_negative("gain", g as f64))
+    }
+}
+
+impl Validate for GardenGetAudioSnapshotRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        nonzero("frames", self.frames as u64)
+    }
+}
+
+impl Validate for AudioCaptureRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        positive("duration_seconds", self.duration_seconds as f64)
+    }
+}
+
+// --- MIDI I/O ---
+
+impl Validate for MidiAttachRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("port_pattern", &self.port_pattern)
+    }
+}
+
+impl Validate for MidiDetachRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("port_pattern", &self.port_pattern)
+    }
+}
+
+impl Validate for MidiSendRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        match &self.message {
+            MidiMessageSpec::NoteOff { channel, pitch } => {
+                midi_channel("message.channel", *channel)?;
+                midi_7bit("message.pitch", *pitch)
+            }
+            MidiMessageSpec::NoteOn {
+                channel,
+                pitch,
+                velocity,
+            } => {
+                midi_channel("message.channel", *channel)?;
+                midi_7bit("message.pitch", *pitch)?;
+                midi_7bit("message.velocity", *velocity)
+            }
+            MidiMessageSpec::ControlChange {
+                channel,
+                controller,
+                value,
+            } => {
+                midi_channel("message.channel", *channel)?;
+                midi_7bit("message.controller", *controller)?;
+                midi_7bit("message.value", *value)
+            }
+            MidiMessageSpec::ProgramChange { channel, program } => {
+                midi_channel("message.channel", *channel)?;
+                midi_7bit("message.program", *program)
+            }
+            MidiMessageSpec::PitchBend { channel, value } => {
+                midi_channel("message.channel", *channel)?;
+                in_range("message.value", *value as f64, -8192.0, 8191.0)
+            }
+            MidiMessageSpec::Raw { bytes } => {
+                if bytes.is_empty() {
+                    return invalid(
+                        "empty_field",
+                        "message.bytes",
+                        "message.bytes must not be empty".to_string(),
+                    );
+                }
+                Ok(())
+            }
+            MidiMessageSpec::Start
+            | MidiMessageSpec::Stop
+            | MidiMessageSpec::Continue
+            | MidiMessageSpec::TimingClock => Ok(()),
+        }
+    }
+}
+
+impl Validate for MidiPlayRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("artifact_id", &self.artifact_id)?;
+        non_negative("start_beat", self.start_beat)
+    }
+}
+
+impl Validate for MidiStopRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("region_id", &self.region_id)
+    }
+}
+
+// --- Jobs, annotations, vibeweaver, resources ---
+
+impl Validate for JobStatusRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("job_id", &self.job_id)
+    }
+}
+
+impl Validate for JobPollRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        match self.mode.as_deref() {
+            None | Some("any") | Some("all") => Ok(()),
+            Some(other) => invalid(
+                "invalid_value",
+                "mode",
+                format!("mode must be \"any\" or \"all\", got {:?}", other),
+            ),
+        }
+    }
+}
+
+impl Validate for JobCancelRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("job_id", &self.job_id)
+    }
+}
+
+impl Validate for AddAnnotationRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("artifact_id", &self.artifact_id)?;
+        non_empty("message", &self.message)
+    }
+}
+
+impl Validate for WeaveEvalRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("code", &self.code)
+    }
+}
+
+impl Validate for ReadResourceRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("uri", &self.uri)
+    }
+}
+
+impl Validate for SampleLlmRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("prompt", &self.prompt)?;
+        opt_nonzero("max_tokens", self.max_tokens)?;
+        self.temperature
+            .map_or(Ok(()), |t| in_range("temperature", t, 0.0, 2.0))
+    }
+}
+
+// --- RAVE ---
+
+impl Validate for RaveEncodeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("audio_hash", &self.audio_hash)
+    }
+}
+
+impl Validate for RaveDecodeRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("latent_hash", &self.latent_hash)
+    }
+}
+
+impl Validate for RaveReconstructRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("audio_hash", &self.audio_hash)
+    }
+}
+
+impl Validate for RaveGenerateRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        opt_positive("duration_seconds", self.duration_seconds)?;
+        opt_positive("temperature", self.temperature)
+    }
+}
+
+impl Validate for RaveStreamStartRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("input_identity", &self.input_identity)?;
+        non_empty("output_identity", &self.output_identity)?;
+        opt_nonzero("buffer_size", self.buffer_size)
+    }
+}
+
+impl Validate for RaveStreamStopRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("stream_id", &self.stream_id)
+    }
+}
+
+impl Validate for RaveStreamStatusRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_empty("stream_id", &self.stream_id)
+    }
+}
+
+impl Validate for ToolRequest {
+    fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            Self::CasStore(r) => r.validate(),
+            Self::CasInspect(r) => r.validate(),
+            Self::CasGet(r) => r.validate(),
+            Self::CasUploadFile(r) => r.validate(),
+            Self::ArtifactUpload(r) => r.validate(),
+            Self::ArtifactGet(r) => r.validate(),
+            Self::ArtifactList(r) => r.validate(),
+            Self::ArtifactCreate(r) => r.validate(),
+            Self::OrpheusGenerate(r) => r.validate(),
+            Self::OrpheusGenerateSeeded(r) => r.validate(),
+            Self::OrpheusContinue(r) => r.validate(),
+            Self::OrpheusBridge(r) => r.validate(),
+            Self::OrpheusLoops(r) => r.validate(),
+            Self::OrpheusClassify(r) => r.validate(),
+            Self::MidiToWav(r) => r.validate(),
+            Self::SoundfontInspect(r) => r.validate(),
+            Self::SoundfontPresetInspect(r) => r.validate(),
+            Self::MusicgenGenerate(r) => r.validate(),
+            Self::YueGenerate(r) => r.validate(),
+            Self::BeatthisAnalyze(r) => r.validate(),
+            Self::ClapAnalyze(r) => r.validate(),
+            Self::MidiInfo(r) => r.validate(),
+            Self::AudioInfo(r) => r.validate(),
+            Self::AbcParse(r) => r.validate(),
+            Self::AbcValidate(r) => r.validate(),
+            Self::AbcTranspose(r) => r.validate(),
+            Self::AbcToMidi(r) => r.validate(),
+            Self::GardenSeek(r) => r.validate(),
+            Self::GardenSetTempo(r) => r.validate(),
+            Self::GardenGetRegions(r) => r.validate(),
+            Self::GardenCreateRegion(r) => r.validate(),
+            Self::GardenDeleteRegion(r) => r.validate(),
+            Self::GardenMoveRegion(r) => r.validate(),
+            Self::GardenAttachAudio(r) => r.validate(),
+            Self::GardenAttachInput(r) => r.validate(),
+            Self::GardenSetMonitor(r) => r.validate(),
+            Self::GardenGetAudioSnapshot(r) => r.validate(),
+            Self::AudioCapture(r) => r.validate(),
+            Self::TimeConvert(r) => r.validate(),
+            Self::MidiInputAttach(r) | Self::MidiOutputAttach(r) => r.validate(),
+            Self::MidiInputDetach(r) | Self::MidiOutputDetach(r) => r.validate(),
+            Self::MidiSend(r) => r.validate(),
+            Self::MidiPlay(r) => r.validate(),
+            Self::MidiStop(r) => r.validate(),
+            Self::GetToolHelp(r) => r.validate(),
+            Self::JobStatus(r) => r.validate(),
+            Self::JobList(r) => r.validate(),
+            Self::JobPoll(r) => r.validate(),
+            Self::JobCancel(r) => r.validate(),
+            Self::EventPoll(r) => r.validate(),
+            Self::ConfigGet(r) => r.validate(),
+            Self::AddAnnotation(r) => r.validate(),
+            Self::WeaveEval(r) => r.validate(),
+            Self::WeaveReset(r) => r.validate(),
+            Self::WeaveHelp(r) => r.validate(),
+            Self::ReadResource(r) => r.validate(),
+            Self::Complete(r) => r.validate(),
+            Self::SampleLlm(r) => r.validate(),
+            Self::Audioldm2Generate(r) => r.validate(),
+            Self::AnticipatoryGenerate(r) => r.validate(),
+            Self::AnticipatoryContinue(r) => r.validate(),
+            Self::AnticipatoryEmbed(r) => r.validate(),
+            Self::DemucsSeparate(r) => r.validate(),
+            Self::MidiAnalyze(r) => r.validate(),
+            Self::MidiVoiceSeparate(r) => r.validate(),
+            Self::MidiStemsExport(r) => r.validate(),
+            Self::MidiClassifyVoices(r) => r.validate(),
+            Self::MidiUnderstand(r) => r.validate(),
+            Self::MusicAnalyze(r) => r.validate(),
+            Self::RaveEncode(r) => r.validate(),
+            Self::RaveDecode(r) => r.validate(),
+            Self::RaveReconstruct(r) => r.validate(),
+            Self::RaveGenerate(r) => r.validate(),
+            Self::RaveStreamStart(r) => r.validate(),
+            Self::RaveStreamStop(r) => r.validate(),
+            Self::RaveStreamStatus(r) => r.validate(),
+            Self::CasStats
+            | Self::GardenStatus
+            | Self::GardenPlay
+            | Self::GardenPause
+            | Self::GardenStop
+            | Self::GardenEmergencyPause
+            | Self::GardenDetachAudio
+            | Self::GardenAudioStatus
+            | Self::GardenDetachInput
+            | Self::GardenInputStatus
+            | Self::GardenGraph
+            | Self::GardenClearRegions
+            | Self::MidiListPorts
+            | Self::MidiStatus
+            | Self::WeaveSession
+            | Self::ListResources
+            | Self::Ping
+            | Self::AudioListDevices => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tempo_range() {
+        assert!(GardenSetTempoRequest { bpm: 120.0 }.validate().is_ok());
+
+        let err = GardenSetTempoRequest { bpm: 0.0 }.validate().unwrap_err();
+        assert_eq!(err.code, "out_of_range");
+        assert_eq!(err.field.as_deref(), Some("bpm"));
+
+        assert!(GardenSetTempoRequest { bpm: f64::NAN }.validate().is_err());
+    }
+
+    #[test]
+    fn negative_duration_rejected() {
+        let req = ToolRequest::MusicgenGenerate(MusicgenGenerateRequest {
+            prompt: Some("warm tape hiss".to_string()),
+            duration: Some(-5.0),
+            temperature: None,
+            top_k: None,
+            top_p: None,
+            guidance_scale: None,
+            do_sample: None,
+            tags: vec![],
+            creator: None,
+            parent_id: None,
+            variation_set_id: None,
+        });
+        let err = req.validate().unwrap_err();
+        assert_eq!(err.field.as_deref(), Some("duration"));
+    }
+
+    #[test]
+    fn empty_strings_and_mime_types() {
+        let err = AbcParseRequest {
+            abc: "  ".to_string(),
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(err.code, "empty_field");
+
+        let store = |mime: &str| CasStoreRequest {
+            data: vec![1, 2, 3],
+            mime_type: mime.to_string(),
+        };
+        assert!(store("audio/wav").validate().is_ok());
+        assert!(store("").validate().is_ok());
+        for bad in [" ", "audio", "audio/", "/wav", "audio/x/wav", "audio/ wav"] {
+            let err = store(bad).validate().unwrap_err();
+            assert_eq!(err.code, "invalid_mime_type", "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn one_of_fields() {
+        let req = MidiInfoRequest {
+            artifact_id: None,
+            hash: Some(String::new()),
+        };
+        let err = req.validate().unwrap_err();
+        assert_eq!(err.code, "missing_field");
+
+        let req = MidiInfoRequest {
+            artifact_id: None,
+            hash: Some("abc".to_string()),
+        };
+        assert!(req.validate().is_ok());
+    }
+
+    #[test]
+    fn midi_message_ranges() {
+        let send = |message| MidiSendRequest {
+            port_pattern: None,
+            message,
+        };
+        assert!(send(MidiMessageSpec::NoteOn {
+            channel: 9,
+            pitch: 36,
+            velocity: 127
+        })
+        .validate()
+        .is_ok());
+
+        let err = send(MidiMessageSpec::NoteOn {
+            channel: 16,
+            pitch: 60,
+            velocity: 100,
+        })
+        .validate()
+        .unwrap_err();
+        assert_eq!(err.field.as_deref(), Some("message.channel"));
+
+        assert!(send(MidiMessageSpec::Raw { bytes: vec![] })
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn unit_variants_pass() {
+        assert!(ToolRequest::Ping.validate().is_ok());
+        assert!(ToolRequest::GardenPlay.validate().is_ok());
+    }
+}
@@ -397,5 +397,12 @@ async fn test_retry_on_timeout() {
         "Should have waited for timeout before retry"
     );
 
+    // The dropped first attempt shows up in the client's metrics
+    let metrics = client.metrics();
+    assert_eq!(metrics.requests, 1);
+    assert_eq!(metrics.timeouts, 1);
+    assert_eq!(metrics.retries, 1);
+    assert!(metrics.p50_latency > Duration::ZERO);
+
     router_handle.abort();
 }
@@ -5,6 +5,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Parsed MIDI file context: timing, format, and tempo map.
+///
+/// From [`extract_notes`], `tempo_changes` and `time_signatures` are sorted
+/// by tick and always start at tick 0 — 120 BPM and 4/4 are filled in when
+/// the file doesn't say otherwise.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MidiFileContext {
     pub ppq: u16,
@@ -15,6 +19,51 @@ pub struct MidiFileContext {
     pub total_ticks: u64,
 }
 
+impl MidiFileContext {
+    /// Tempo in effect at `tick`.
+    pub fn bpm_at(&self, tick: u64) -> f64 {
+        self.tempo_changes
+            .iter()
+            .take_while(|t| t.tick <= tick)
+            .last()
+            .map_or(DEFAULT_BPM, |t| t.bpm)
+    }
+
+    /// Time signature `(numerator, denominator)` in effect at `tick`.
+    pub fn time_signature_at(&self, tick: u64) -> (u8, u8) {
+        self.time_signatures
+            .iter()
+            .take_while(|t| t.tick <= tick)
+            .last()
+            .map_or(DEFAULT_TIME_SIGNATURE, |t| (t.numerator, t.denominator))
+    }
+
+    /// Wall-clock seconds from the start of the file to `tick`, integrating
+    /// every tempo change along the way.
+    pub fn ticks_to_seconds(&self, tick: u64) -> f64 {
+        if self.ppq == 0 {
+            return 0.0;
+        }
+        let ppq = self.ppq as f64;
+
+        let mut seconds = 0.0;
+        let mut segment_start = 0u64;
+        let mut bpm = DEFAULT_BPM;
+        for change in self.tempo_changes.iter().take_while(|t| t.tick <= tick) {
+            seconds += (change.tick - segment_start) as f64 / ppq * 60.0 / bpm;
+            segment_start = change.tick;
+            bpm = change.bpm;
+        }
+        seconds + (tick - segment_start) as f64 / ppq * 60.0 / bpm
+    }
+}
+
+/// MIDI's implied tempo when a file has no tempo meta event.
+pub const DEFAULT_BPM: f64 = 120.0;
+
+/// MIDI's implied meter when a file has no time signature meta event.
+pub const DEFAULT_TIME_SIGNATURE: (u8, u8) = (4, 4);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TempoChange {
     pub tick: u64,
@@ -67,8 +116,12 @@ impl Default for PitchRange {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolyphonyProfile {
     pub max_simultaneous: usize,
+    /// Mean notes sounding at each note onset
     pub mean_simultaneous: f64,
     pub polyphonic_ratio: f64,
+    /// Time-weighted mean notes sounding, over ticks where anything sounds
+    #[serde(default)]
+    pub avg_polyphony: f64,
 }
 
 impl Default for PolyphonyProfile {
@@ -77,6 +130,7 @@ impl Default for PolyphonyProfile {
             max_simultaneous: 0,
             mean_simultaneous: 0.0,
             polyphonic_ratio: 0.0,
+            avg_polyphony: 0.0,
         }
     }
 }
@@ -209,6 +263,29 @@ pub fn extract_notes(smf: &Smf) -> (Vec<TimedNote>, MidiFileContext) {
     time_signatures.sort_by_key(|t| t.tick);
     time_signatures.dedup_by(|a, b| a.tick == b.tick);
 
+    // Anything before the first meta event plays at the MIDI defaults
+    if tempo_changes.first().is_none_or(|t| t.tick > 0) {
+        tempo_changes.insert(
+            0,
+            TempoChange {
+                tick: 0,
+                microseconds_per_beat: (60_000_000.0 / DEFAULT_BPM) as u32,
+                bpm: DEFAULT_BPM,
+            },
+        );
+    }
+    if time_signatures.first().is_none_or(|t| t.tick > 0) {
+        let (numerator, denominator) = DEFAULT_TIME_SIGNATURE;
+        time_signatures.insert(
+            0,
+            TimeSignature {
+                tick: 0,
+                numerator,
+                denominator,
+            },
+        );
+    }
+
     let context = MidiFileContext {
         ppq,
         format,
@@ -419,6 +496,38 @@ fn compute_polyphony(notes: &[&TimedNote]) -> PolyphonyProfile {
         max_simultaneous: max_sim,
         mean_simultaneous,
         polyphonic_ratio,
+        avg_polyphony: time_weighted_polyphony(&events),
+    }
+}
+
+/// Sweep sorted (tick, ±1) events and average the sounding count over time.
+///
+/// All events at a tick are applied before measuring the span to the next
+/// tick, so a note-off landing on another note's onset never counts as overlap.
+fn time_weighted_polyphony(events: &[(u64, i32)]) -> f64 {
+    let mut current = 0i32;
+    let mut weighted_ticks = 0u64;
+    let mut sounding_ticks = 0u64;
+
+    for (i, &(tick, delta)) in events.iter().enumerate() {
+        current += delta;
+
+        let Some(&(next_tick, _)) = events.get(i + 1) else {
+            break;
+        };
+        if next_tick == tick || current <= 0 {
+            continue;
+        }
+
+        let span = next_tick - tick;
+        weighted_ticks += span * current as u64;
+        sounding_ticks += span;
+    }
+
+    if sounding_ticks > 0 {
+        weighted_ticks as f64 / sounding_ticks as f64
+    } else {
+        0.0
     }
 }
 
@@ -454,6 +563,9 @@ fn compute_density(notes: &[&TimedNote], ppq: f64, total_ticks: u64) -> DensityP
     }
 }
 
+/// Simultaneous-note count at which voice separation gets unreliable.
+const DENSE_POLYPHONY: usize = 6;
+
 fn build_summary(
     tracks: &[TrackProfile],
     needing_separation: &[usize],
@@ -485,6 +597,16 @@ fn build_summary(
         ));
     }
 
+    for track in tracks
+        .iter()
+        .filter(|t| !t.is_percussion && t.polyphony.max_simultaneous >= DENSE_POLYPHONY)
+    {
+        summary.push_str(&format!(
+            ". Track {} reaches {} simultaneous notes (avg {:.1}) and may separate poorly",
+            track.track_index, track.polyphony.max_simultaneous, track.polyphony.avg_polyphony
+        ));
+    }
+
     summary
 }
 
@@ -621,6 +743,7 @@ mod tests {
 
         let track1 = &analysis.tracks[1];
         assert_eq!(track1.polyphony.max_simultaneous, 3);
+        assert!((track1.polyphony.avg_polyphony - 3.0).abs() < 1e-9);
         assert!(track1.merged_voices_likely);
         assert!(analysis.tracks_needing_separation.contains(&1));
     }
@@ -634,4 +757,105 @@ mod tests {
         assert_eq!(context.tempo_changes.len(), 1);
         assert!((context.tempo_changes[0].bpm - 120.0).abs() < 0.1);
     }
+
+    #[test]
+    fn missing_meta_events_default_to_120_and_four_four() {
+        // Single track, no tempo or time signature events
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"MThd");
+        buf.extend_from_slice(&6u32.to_be_bytes());
+        buf.extend_from_slice(&0u16.to_be_bytes());
+        buf.extend_from_slice(&1u16.to_be_bytes());
+        buf.extend_from_slice(&96u16.to_be_bytes());
+        let track = [0x00, 0x90, 60, 100, 0x60, 0x80, 60, 0, 0x00, 0xFF, 0x2F, 0x00];
+        buf.extend_from_slice(b"MTrk");
+        buf.extend_from_slice(&(track.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&track);
+
+        let smf = Smf::parse(&buf).unwrap();
+        let (_, context) = extract_notes(&smf);
+
+        assert_eq!(context.tempo_changes.len(), 1);
+        assert_eq!(context.tempo_changes[0].tick, 0);
+        assert_eq!(context.tempo_changes[0].microseconds_per_beat, 500_000);
+        assert_eq!(context.time_signature_at(0), (4, 4));
+        assert!((context.ticks_to_seconds(96) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ticks_to_seconds_integrates_tempo_map() {
+        // 120 BPM for two beats, then 60 BPM; 3/4 from bar 2
+        let context = MidiFileContext {
+            ppq: 480,
+            format: 1,
+            track_count: 1,
+            tempo_changes: vec![
+                TempoChange {
+                    tick: 0,
+                    microseconds_per_beat: 500_000,
+                    bpm: 120.0,
+                },
+                TempoChange {
+                    tick: 960,
+                    microseconds_per_beat: 1_000_000,
+                    bpm: 60.0,
+                },
+            ],
+            time_signatures: vec![
+                TimeSignature {
+                    tick: 0,
+                    numerator: 4,
+                    denominator: 4,
+                },
+                TimeSignature {
+                    tick: 1920,
+                    numerator: 3,
+                    denominator: 4,
+                },
+            ],
+            total_ticks: 3840,
+        };
+
+        assert!((context.bpm_at(959) - 120.0).abs() < 1e-9);
+        assert!((context.bpm_at(960) - 60.0).abs() < 1e-9);
+        assert_eq!(context.time_signature_at(1919), (4, 4));
+        assert_eq!(context.time_signature_at(1920), (3, 4));
+
+        // Two beats at 0.5 s each, then two beats at 1 s each
+        assert!((context.ticks_to_seconds(960) - 1.0).abs() < 1e-9);
+        assert!((context.ticks_to_seconds(1920) - 3.0).abs() < 1e-9);
+    }
+
+    fn note(onset: u64, offset: u64) -> TimedNote {
+        TimedNote {
+            onset_tick: onset,
+            offset_tick: offset,
+            pitch: 60,
+            velocity: 100,
+            channel: 0,
+            track_index: 0,
+        }
+    }
+
+    #[test]
+    fn legato_handoff_is_not_overlap() {
+        // Each note-off lands on the next note-on
+        let notes = [note(0, 480), note(480, 960), note(960, 1440)];
+        let refs: Vec<&TimedNote> = notes.iter().collect();
+        let polyphony = compute_polyphony(&refs);
+
+        assert_eq!(polyphony.max_simultaneous, 1);
+        assert!((polyphony.avg_polyphony - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn avg_polyphony_is_time_weighted() {
+        // Two voices for the first beat, one for the next three
+        let notes = [note(0, 1920), note(0, 480)];
+        let refs: Vec<&TimedNote> = notes.iter().collect();
+        let polyphony = compute_polyphony(&refs);
+
+        assert_eq!(polyphony.max_simultaneous, 2);
+        assert!((polyphony.avg_polyphony - 1.25).abs() < 1e-9);
+    }
 }
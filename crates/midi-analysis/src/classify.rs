@@ -1,4 +1,5 @@
 use crate::analyze::{MidiFileContext, TrackProfile};
+use crate::gm::percussion_family;
 use crate::note::{SeparatedVoice, TimedNote};
 use serde::{Deserialize, Serialize};
 
@@ -97,6 +98,10 @@ pub struct VoiceClassification {
     pub method: ClassificationMethod,
     pub features: VoiceFeatures,
     pub alternative_roles: Vec<(VoiceRole, f64)>,
+    /// Kit family for drum-channel percussion ("kick", "snare", "hi_hat", ...),
+    /// or "kit" when the voice mixes several families.
+    #[serde(default)]
+    pub sub_label: Option<String>,
 }
 
 /// Extract features from a single voice in the context of all sibling voices.
@@ -353,7 +358,25 @@ pub fn classify_voices(
         .map(|v| extract_features(v, voices, context, track_profiles))
         .collect();
 
-    classify_voices_with_features(features)
+    let mut classifications = classify_voices_with_features(features);
+    for (classification, voice) in classifications.iter_mut().zip(voices) {
+        if classification.role == VoiceRole::Percussion
+            && classification.features.is_drum_channel
+        {
+            classification.sub_label = percussion_sub_label(&voice.notes);
+        }
+    }
+    classifications
+}
+
+/// Name the kit family a drum voice covers, or "kit" if it spans several.
+fn percussion_sub_label(notes: &[TimedNote]) -> Option<String> {
+    let first = percussion_family(notes.first()?.pitch);
+    if notes.iter().all(|n| percussion_family(n.pitch) == first) {
+        Some(first.as_str().to_string())
+    } else {
+        Some("kit".to_string())
+    }
 }
 
 /// Classify voices from pre-computed feature vectors.
@@ -377,6 +400,7 @@ pub fn classify_voices_with_features(features: Vec<VoiceFeatures>) -> Vec<VoiceC
                 method: ClassificationMethod::Heuristic,
                 features: feat,
                 alternative_roles: alternatives,
+                sub_label: None,
             }
         })
         .collect();
@@ -649,7 +673,8 @@ mod tests {
         let context = make_context(480, 1920);
         let profiles = vec![make_track_profile(0, vec![], true)];
 
-        let features = extract_features(&voice, &[voice.clone()], &context, &profiles);
+        let features =
+            extract_features(&voice, std::slice::from_ref(&voice), &context, &profiles);
         assert!(features.is_drum_channel);
 
         let (role, confidence, _) = classify_heuristic(&features);
@@ -672,7 +697,8 @@ mod tests {
             make_track_profile(1, vec![33], false),
         ];
 
-        let features = extract_features(&voice, &[voice.clone()], &context, &profiles);
+        let features =
+            extract_features(&voice, std::slice::from_ref(&voice), &context, &profiles);
         assert_eq!(features.gm_program_category, 4);
 
         let (role, confidence, _) = classify_heuristic(&features);
@@ -868,7 +894,8 @@ mod tests {
             make_track_profile(1, vec![0], false),
         ];
 
-        let features = extract_features(&voice, &[voice.clone()], &context, &profiles);
+        let features =
+            extract_features(&voice, std::slice::from_ref(&voice), &context, &profiles);
 
         // IOI = 480 ticks / 480 ppq = 1.0 beat
         assert!((features.mean_ioi_beats - 1.0).abs() < 0.01);
@@ -891,7 +918,8 @@ mod tests {
             make_track_profile(1, vec![0], false),
         ];
 
-        let features = extract_features(&voice, &[voice.clone()], &context, &profiles);
+        let features =
+            extract_features(&voice, std::slice::from_ref(&voice), &context, &profiles);
         assert!((features.on_beat_fraction - 1.0).abs() < 0.01);
     }
 
@@ -915,6 +943,66 @@ mod tests {
         assert!(result.iter().all(|c| c.role == VoiceRole::Percussion));
     }
 
+    #[test]
+    fn mixed_file_splits_drums_by_kit_family() {
+        use crate::voice_separate::{separate_voices, SeparationParams};
+
+        // Track 0: a piano line. Track 1: kick, snare, and closed hats on channel 10.
+        let melody = make_notes(&[
+            (0, 480, 72, 0),
+            (480, 960, 74, 0),
+            (960, 1440, 76, 0),
+            (1440, 1920, 77, 0),
+        ]);
+        let mut drums = make_notes(&[
+            (0, 120, 36, 9),
+            (960, 1080, 36, 9),
+            (480, 600, 38, 9),
+            (1440, 1560, 38, 9),
+            (0, 60, 42, 9),
+            (480, 540, 42, 9),
+            (960, 1020, 42, 9),
+            (1440, 1500, 42, 9),
+        ]);
+        for note in &mut drums {
+            note.track_index = 1;
+        }
+
+        let params = SeparationParams::default();
+        let mut voices = separate_voices(&melody, 480, &params);
+        voices.extend(separate_voices(&drums, 480, &params));
+        assert_eq!(voices.len(), 4);
+
+        let context = make_context(480, 1920);
+        let mut drum_profile = make_track_profile(1, vec![], true);
+        drum_profile.channels_used = vec![9];
+        let profiles = vec![make_track_profile(0, vec![0], false), drum_profile];
+
+        let result = classify_voices(&voices, &context, &profiles);
+
+        assert_ne!(result[0].role, VoiceRole::Percussion);
+        assert_eq!(result[0].sub_label, None);
+
+        let drum_labels: Vec<Option<&str>> = result[1..]
+            .iter()
+            .inspect(|c| assert_eq!(c.role, VoiceRole::Percussion))
+            .map(|c| c.sub_label.as_deref())
+            .collect();
+        assert_eq!(drum_labels, vec![Some("kick"), Some("snare"), Some("hi_hat")]);
+    }
+
+    #[test]
+    fn mixed_kit_voice_labeled_kit() {
+        let notes = make_notes(&[(0, 120, 36, 9), (480, 600, 38, 9)]);
+        let mut voice = make_voice(notes, 0);
+        voice.source_channel = Some(9);
+        let context = make_context(480, 1920);
+        let profiles = vec![make_track_profile(0, vec![], true)];
+
+        let result = classify_voices(&[voice], &context, &profiles);
+        assert_eq!(result[0].sub_label.as_deref(), Some("kit"));
+    }
+
     #[test]
     fn rank_normalized_correct() {
         assert!((rank_normalized(1.0, &[1.0, 2.0, 3.0]) - 0.0).abs() < 0.01);
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// Look up the General MIDI program name for a program number (0–127).
 pub fn program_name(program: u8) -> &'static str {
     GM_PROGRAM_NAMES
@@ -154,6 +156,114 @@ const GM_PROGRAM_NAMES: [&str; 128] = [
     "Gunshot",
 ];
 
+/// Drum kit family for a GM percussion key, used to split drum tracks into
+/// individually addressable stems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PercussionFamily {
+    Kick,
+    Snare,
+    HiHat,
+    Tom,
+    Cymbal,
+    Auxiliary,
+    Other,
+}
+
+impl PercussionFamily {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Kick => "kick",
+            Self::Snare => "snare",
+            Self::HiHat => "hi_hat",
+            Self::Tom => "tom",
+            Self::Cymbal => "cymbal",
+            Self::Auxiliary => "auxiliary",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl std::fmt::Display for PercussionFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Map a channel-10 (index 9) note number to its kit family.
+pub fn percussion_family(key: u8) -> PercussionFamily {
+    match key {
+        35 | 36 => PercussionFamily::Kick,
+        37..=40 => PercussionFamily::Snare,
+        42 | 44 | 46 => PercussionFamily::HiHat,
+        41 | 43 | 45 | 47 | 48 | 50 => PercussionFamily::Tom,
+        49 | 51 | 52 | 53 | 55 | 57 | 59 => PercussionFamily::Cymbal,
+        54..=81 => PercussionFamily::Auxiliary,
+        _ => PercussionFamily::Other,
+    }
+}
+
+/// Look up the General MIDI percussion name for a channel-10 note number.
+pub fn percussion_name(key: u8) -> &'static str {
+    key.checked_sub(GM_PERCUSSION_FIRST_KEY)
+        .and_then(|i| GM_PERCUSSION_NAMES.get(i as usize))
+        .copied()
+        .unwrap_or("Unknown")
+}
+
+const GM_PERCUSSION_FIRST_KEY: u8 = 35;
+
+/// Standard General MIDI Level 1 percussion key map, keys 35–81.
+const GM_PERCUSSION_NAMES: [&str; 47] = [
+    "Acoustic Bass Drum",
+    "Bass Drum 1",
+    "Side Stick",
+    "Acoustic Snare",
+    "Hand Clap",
+    "Electric Snare",
+    "Low Floor Tom",
+    "Closed Hi-Hat",
+    "High Floor Tom",
+    "Pedal Hi-Hat",
+    "Low Tom",
+    "Open Hi-Hat",
+    "Low-Mid Tom",
+    "Hi-Mid Tom",
+    "Crash Cymbal 1",
+    "High Tom",
+    "Ride Cymbal 1",
+    "Chinese Cymbal",
+    "Ride Bell",
+    "Tambourine",
+    "Splash Cymbal",
+    "Cowbell",
+    "Crash Cymbal 2",
+    "Vibraslap",
+    "Ride Cymbal 2",
+    "Hi Bongo",
+    "Low Bongo",
+    "Mute Hi Conga",
+    "Open Hi Conga",
+    "Low Conga",
+    "High Timbale",
+    "Low Timbale",
+    "High Agogo",
+    "Low Agogo",
+    "Cabasa",
+    "Maracas",
+    "Short Whistle",
+    "Long Whistle",
+    "Short Guiro",
+    "Long Guiro",
+    "Claves",
+    "Hi Wood Block",
+    "Low Wood Block",
+    "Mute Cuica",
+    "Open Cuica",
+    "Mute Triangle",
+    "Open Triangle",
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,4 +282,24 @@ mod tests {
     fn trumpet() {
         assert_eq!(program_name(56), "Trumpet");
     }
+
+    #[test]
+    fn percussion_names_cover_key_map() {
+        assert_eq!(percussion_name(35), "Acoustic Bass Drum");
+        assert_eq!(percussion_name(42), "Closed Hi-Hat");
+        assert_eq!(percussion_name(81), "Open Triangle");
+        assert_eq!(percussion_name(34), "Unknown");
+        assert_eq!(percussion_name(82), "Unknown");
+    }
+
+    #[test]
+    fn percussion_families() {
+        assert_eq!(percussion_family(36), PercussionFamily::Kick);
+        assert_eq!(percussion_family(38), PercussionFamily::Snare);
+        assert_eq!(percussion_family(46), PercussionFamily::HiHat);
+        assert_eq!(percussion_family(45), PercussionFamily::Tom);
+        assert_eq!(percussion_family(49), PercussionFamily::Cymbal);
+        assert_eq!(percussion_family(54), PercussionFamily::Auxiliary);
+        assert_eq!(percussion_family(20), PercussionFamily::Other);
+    }
 }
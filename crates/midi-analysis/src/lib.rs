@@ -10,9 +10,10 @@ pub use classify::{
     classify_heuristic, classify_voices, classify_voices_with_features, extract_features,
     ClassificationMethod, VoiceClassification, VoiceFeatures, VoiceRole,
 };
-pub use midi_writer::{voices_to_midi, ExportOptions};
-pub use note::{SeparatedVoice, SeparationMethod, TimedNote, VoiceStats};
-pub use voice_separate::{separate_voices, SeparationParams};
+pub use gm::{percussion_family, percussion_name, PercussionFamily};
+pub use midi_writer::{default_role_programs, voices_to_midi, ExportOptions};
+pub use note::{quantize, Grid, SeparatedVoice, SeparationMethod, TimedNote, VoiceStats};
+pub use voice_separate::{separate_drums, separate_voices, SeparationParams};
 
 /// Errors from MIDI analysis operations.
 #[derive(Debug, thiserror::Error)]
@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+
 use crate::analyze::MidiFileContext;
+use crate::classify::VoiceRole;
 use crate::note::SeparatedVoice;
 use serde::{Deserialize, Serialize};
 
+/// GM percussion channel (0-indexed).
+const PERCUSSION_CHANNEL: u8 = 9;
+
 /// Options for MIDI export.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportOptions {
@@ -9,6 +15,13 @@ pub struct ExportOptions {
     pub include_tempo_map: bool,
     /// Assign unique GM program to each voice. Default: true.
     pub assign_programs: bool,
+    /// GM program per voice role. Default: see [`default_role_programs`].
+    #[serde(default = "default_role_programs")]
+    pub role_programs: HashMap<VoiceRole, u8>,
+    /// Role of each exported voice, parallel to the `voices` slice.
+    /// Voices without a role get program 0; percussion goes to channel 9.
+    #[serde(default)]
+    pub voice_roles: Vec<VoiceRole>,
 }
 
 impl Default for ExportOptions {
@@ -16,10 +29,27 @@ impl Default for ExportOptions {
         Self {
             include_tempo_map: true,
             assign_programs: true,
+            role_programs: default_role_programs(),
+            voice_roles: Vec::new(),
         }
     }
 }
 
+/// Role → GM program defaults that keep exported voices audibly distinct.
+pub fn default_role_programs() -> HashMap<VoiceRole, u8> {
+    HashMap::from([
+        (VoiceRole::Melody, 0),            // Acoustic Grand Piano
+        (VoiceRole::Bass, 32),             // Acoustic Bass
+        (VoiceRole::Countermelody, 73),    // Flute
+        (VoiceRole::HarmonicFill, 48),     // String Ensemble 1
+        (VoiceRole::Rhythm, 25),           // Acoustic Guitar (steel)
+        (VoiceRole::PrimaryHarmony, 4),    // Electric Piano 1
+        (VoiceRole::SecondaryHarmony, 49), // String Ensemble 2
+        (VoiceRole::Padding, 89),          // Pad 2 (warm)
+        (VoiceRole::Percussion, 0),        // Standard kit on channel 9
+    ])
+}
+
 /// Write separated voices to Standard MIDI File format 1 bytes.
 ///
 /// Track 0: tempo map + time signatures (from context).
@@ -39,22 +69,26 @@ pub fn voices_to_midi(
     // Assign channels (skip 9 for drums)
     let mut channel_alloc = 0u8;
 
-    for voice in voices {
-        let channel = if channel_alloc == 9 {
-            channel_alloc += 1;
-            channel_alloc - 1
+    for (index, voice) in voices.iter().enumerate() {
+        let role = options.voice_roles.get(index).copied();
+
+        let channel = if role == Some(VoiceRole::Percussion) {
+            PERCUSSION_CHANNEL
         } else {
             let ch = channel_alloc;
             channel_alloc += 1;
-            if channel_alloc == 9 {
-                channel_alloc = 10; // skip percussion channel
+            if channel_alloc == PERCUSSION_CHANNEL {
+                channel_alloc += 1; // skip percussion channel
             }
-            ch
+            // Cap at 15 (MIDI has 16 channels)
+            ch.min(15)
         };
-        // Cap at 15 (MIDI has 16 channels)
-        let channel = channel.min(15);
 
-        tracks.push(build_voice_track(voice, channel, options));
+        let program = role
+            .and_then(|r| options.role_programs.get(&r).copied())
+            .unwrap_or(0);
+
+        tracks.push(build_voice_track(voice, channel, program, options));
     }
 
     build_midi_file(context.ppq, &tracks)
@@ -125,7 +159,12 @@ fn build_tempo_track(context: &MidiFileContext) -> Vec<u8> {
 }
 
 /// Build a track for a single separated voice.
-fn build_voice_track(voice: &SeparatedVoice, channel: u8, options: &ExportOptions) -> Vec<u8> {
+fn build_voice_track(
+    voice: &SeparatedVoice,
+    channel: u8,
+    program: u8,
+    options: &ExportOptions,
+) -> Vec<u8> {
     let mut events: Vec<(u64, Vec<u8>)> = Vec::new();
 
     // Track name
@@ -136,13 +175,9 @@ fn build_voice_track(voice: &SeparatedVoice, channel: u8, options: &ExportOption
     name_event.extend_from_slice(name_bytes);
     events.push((0, name_event));
 
-    // Program change (use original if available, else piano)
+    // Program change (role-based, piano when the role is unknown)
     if options.assign_programs {
-        let program = voice
-            .source_channel
-            .map(|_| 0u8) // could be smarter in Phase 2
-            .unwrap_or(0);
-        events.push((0, vec![0xC0 | (channel & 0x0F), program]));
+        events.push((0, vec![0xC0 | (channel & 0x0F), program & 0x7F]));
     }
 
     // Note events
@@ -374,6 +409,86 @@ mod tests {
         assert_eq!(smf.tracks.len(), 3); // tempo + 2 voices
     }
 
+    /// (channel, program) of every Program Change in each track.
+    fn program_changes(smf: &Smf) -> Vec<Vec<(u8, u8)>> {
+        smf.tracks
+            .iter()
+            .map(|track| {
+                track
+                    .iter()
+                    .filter_map(|event| match event.kind {
+                        midly::TrackEventKind::Midi {
+                            channel,
+                            message: midly::MidiMessage::ProgramChange { program },
+                        } => Some((channel.as_int(), program.as_int())),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn role_programs_and_percussion_channel() {
+        let note = |pitch| TimedNote {
+            onset_tick: 0,
+            offset_tick: 480,
+            pitch,
+            velocity: 100,
+            channel: 0,
+            track_index: 0,
+        };
+        let voices = vec![
+            make_voice(vec![note(72)], 0),
+            make_voice(vec![note(36)], 1),
+            make_voice(vec![note(38)], 2),
+            make_voice(vec![note(60)], 3),
+        ];
+
+        let options = ExportOptions {
+            voice_roles: vec![VoiceRole::Melody, VoiceRole::Bass, VoiceRole::Percussion],
+            ..ExportOptions::default()
+        };
+        let midi_bytes = voices_to_midi(&voices, &make_context(), &options);
+        let smf = Smf::parse(&midi_bytes).unwrap();
+
+        let programs = program_changes(&smf);
+        assert!(programs[0].is_empty(), "tempo track has no program changes");
+        assert_eq!(programs[1], vec![(0, 0)]); // melody → piano
+        assert_eq!(programs[2], vec![(1, 32)]); // bass → acoustic bass
+        assert_eq!(programs[3], vec![(9, 0)]); // percussion → channel 9
+        assert_eq!(programs[4], vec![(2, 0)]); // no role → piano, next free channel
+    }
+
+    #[test]
+    fn melodic_channels_skip_percussion() {
+        let voices: Vec<_> = (0..11)
+            .map(|i| {
+                make_voice(
+                    vec![TimedNote {
+                        onset_tick: 0,
+                        offset_tick: 480,
+                        pitch: 60,
+                        velocity: 100,
+                        channel: 0,
+                        track_index: 0,
+                    }],
+                    i,
+                )
+            })
+            .collect();
+
+        let midi_bytes = voices_to_midi(&voices, &make_context(), &ExportOptions::default());
+        let smf = Smf::parse(&midi_bytes).unwrap();
+
+        let channels: Vec<u8> = program_changes(&smf)
+            .iter()
+            .skip(1)
+            .map(|track| track[0].0)
+            .collect();
+        assert_eq!(channels, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 10, 11]);
+    }
+
     #[test]
     fn vlq_encoding() {
         let mut buf = Vec::new();
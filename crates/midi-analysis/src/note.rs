@@ -17,6 +17,63 @@ impl TimedNote {
     }
 }
 
+/// Quantization grid, expressed relative to a quarter note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Grid {
+    /// 1/8 notes (two per beat)
+    Eighth,
+    /// 1/16 notes (four per beat)
+    Sixteenth,
+    /// 1/8 triplets (three per beat)
+    EighthTriplet,
+    /// 1/16 triplets (six per beat)
+    SixteenthTriplet,
+}
+
+impl Grid {
+    /// Grid spacing in ticks at the given resolution.
+    pub fn ticks(&self, ppq: u16) -> f64 {
+        let divisions = match self {
+            Grid::Eighth => 2.0,
+            Grid::Sixteenth => 4.0,
+            Grid::EighthTriplet => 3.0,
+            Grid::SixteenthTriplet => 6.0,
+        };
+        ppq as f64 / divisions
+    }
+}
+
+/// Pull note onsets and durations toward a grid.
+///
+/// `strength` blends between the raw timing (0.0) and a hard snap (1.0).
+/// Snapped durations are at least one grid step, and every note keeps at
+/// least one tick. Interpolating toward a monotonic snap never reorders
+/// onsets, so a sorted slice stays sorted.
+pub fn quantize(notes: &mut [TimedNote], grid: Grid, ppq: u16, strength: f32) {
+    let strength = strength.clamp(0.0, 1.0) as f64;
+    let step = grid.ticks(ppq);
+    if strength == 0.0 || step < 1.0 {
+        return;
+    }
+
+    let pull = |raw: f64, target: f64| (raw + (target - raw) * strength).round();
+
+    for note in notes.iter_mut() {
+        let onset = note.onset_tick as f64;
+        let duration = note.duration_ticks() as f64;
+
+        let snapped_onset = (onset / step).round() * step;
+        let snapped_duration = ((duration / step).round() * step).max(step);
+
+        let new_onset = pull(onset, snapped_onset).max(0.0) as u64;
+        let new_duration = (pull(duration, snapped_duration) as u64).max(1);
+
+        note.onset_tick = new_onset;
+        note.offset_tick = new_onset + new_duration;
+    }
+}
+
 /// How a voice was separated from its source material.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -31,6 +88,8 @@ pub enum SeparationMethod {
     Skyline,
     /// Lowest note at each onset (bass extraction)
     Bassline,
+    /// Channel-10 drums grouped by GM percussion family (kick, snare, hat, ...)
+    DrumKit,
 }
 
 /// Statistics about a separated voice.
@@ -92,3 +151,62 @@ pub struct SeparatedVoice {
     pub source_channel: Option<u8>,
     pub source_track: Option<usize>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(onset: u64, offset: u64) -> TimedNote {
+        TimedNote {
+            onset_tick: onset,
+            offset_tick: offset,
+            pitch: 60,
+            velocity: 100,
+            channel: 0,
+            track_index: 0,
+        }
+    }
+
+    #[test]
+    fn near_grid_onset_snaps() {
+        let mut notes = vec![note(245, 470)];
+        quantize(&mut notes, Grid::Eighth, 480, 1.0);
+        assert_eq!(notes[0].onset_tick, 240);
+        assert_eq!(notes[0].offset_tick, 480);
+    }
+
+    #[test]
+    fn half_strength_moves_halfway() {
+        // 300 is 60 ticks past the 240 grid line
+        let mut notes = vec![note(300, 540)];
+        quantize(&mut notes, Grid::Eighth, 480, 0.5);
+        assert_eq!(notes[0].onset_tick, 270);
+        assert_eq!(notes[0].duration_ticks(), 240);
+    }
+
+    #[test]
+    fn zero_strength_is_noop() {
+        let original = vec![note(13, 97), note(301, 377)];
+        let mut notes = original.clone();
+        quantize(&mut notes, Grid::Sixteenth, 480, 0.0);
+        assert_eq!(notes, original);
+    }
+
+    #[test]
+    fn triplet_grid_and_ordering() {
+        let mut notes = vec![note(0, 150), note(165, 300), note(330, 480)];
+        quantize(&mut notes, Grid::EighthTriplet, 480, 1.0);
+
+        let onsets: Vec<u64> = notes.iter().map(|n| n.onset_tick).collect();
+        assert_eq!(onsets, vec![0, 160, 320]);
+        assert!(notes.iter().all(|n| n.duration_ticks() >= 1));
+    }
+
+    #[test]
+    fn short_notes_keep_positive_duration() {
+        let mut notes = vec![note(100, 101)];
+        quantize(&mut notes, Grid::Sixteenth, 480, 1.0);
+        assert_eq!(notes[0].onset_tick, 120);
+        assert_eq!(notes[0].duration_ticks(), 120);
+    }
+}
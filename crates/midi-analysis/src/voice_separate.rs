@@ -1,6 +1,7 @@
+use crate::gm::{percussion_family, PercussionFamily};
 use crate::note::{SeparatedVoice, SeparationMethod, TimedNote, VoiceStats};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Parameters controlling voice separation behavior.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +18,9 @@ pub struct SeparationParams {
 }
 
 
+/// GM percussion channel (channel 10, zero-indexed).
+const DRUM_CHANNEL: u8 = 9;
+
 /// State of an active voice during pitch contiguity separation.
 struct VoiceState {
     notes: Vec<TimedNote>,
@@ -27,9 +31,10 @@ struct VoiceState {
 /// Separate notes from a single track into distinct musical voices.
 ///
 /// Strategy selection (when `params.method` is None):
-/// 1. Multiple MIDI channels → channel split
-/// 2. Max polyphony ≤ 1 → already monophonic
-/// 3. Otherwise → pitch contiguity
+/// 1. Everything on the GM drum channel → drum kit split
+/// 2. Multiple MIDI channels → channel split
+/// 3. Max polyphony ≤ 1 → already monophonic
+/// 4. Otherwise → pitch contiguity
 pub fn separate_voices(
     notes: &[TimedNote],
     ppq: u16,
@@ -62,10 +67,45 @@ pub fn separate_voices(
         }
         SeparationMethod::Skyline => skyline(notes, source_track),
         SeparationMethod::Bassline => bassline(notes, source_track),
+        SeparationMethod::DrumKit => separate_drums(notes),
     }
 }
 
+/// Split drum notes into one voice per GM percussion family.
+///
+/// Pitch contiguity is meaningless for drums — a key number names an
+/// instrument, not a pitch — so each kit piece family (kick, snare, hats,
+/// ...) becomes its own voice, ordered as [`PercussionFamily`] is.
+pub fn separate_drums(notes: &[TimedNote]) -> Vec<SeparatedVoice> {
+    let mut by_family: BTreeMap<PercussionFamily, Vec<TimedNote>> = BTreeMap::new();
+    for note in notes {
+        by_family
+            .entry(percussion_family(note.pitch))
+            .or_default()
+            .push(note.clone());
+    }
+
+    let source_track = notes.first().map(|n| n.track_index);
+
+    by_family
+        .into_values()
+        .enumerate()
+        .map(|(voice_index, voice_notes)| SeparatedVoice {
+            stats: VoiceStats::from_notes(&voice_notes),
+            source_channel: voice_notes.first().map(|n| n.channel),
+            notes: voice_notes,
+            method: SeparationMethod::DrumKit,
+            voice_index,
+            source_track,
+        })
+        .collect()
+}
+
 fn auto_select_method(notes: &[TimedNote]) -> SeparationMethod {
+    if notes.iter().all(|n| n.channel == DRUM_CHANNEL) {
+        return SeparationMethod::DrumKit;
+    }
+
     // Check for multiple channels
     let channels: Vec<u8> = notes
         .iter()
@@ -515,4 +555,29 @@ mod tests {
         assert_eq!(voices.len(), 1);
         assert_eq!(voices[0].method, SeparationMethod::AlreadyMonophonic);
     }
+
+    #[test]
+    fn drum_channel_splits_by_kit_family() {
+        // Kick on 1 and 3, snare on 2 and 4, closed hats on every eighth
+        let mut specs = vec![
+            (0, 120, 36, 9),
+            (960, 1080, 36, 9),
+            (480, 600, 38, 9),
+            (1440, 1560, 38, 9),
+        ];
+        for i in 0..8 {
+            specs.push((i * 240, i * 240 + 60, 42, 9));
+        }
+        let notes = make_notes(&specs);
+
+        let voices = separate_voices(&notes, 480, &SeparationParams::default());
+        assert_eq!(voices.len(), 3);
+        assert!(voices.iter().all(|v| v.method == SeparationMethod::DrumKit));
+        assert!(voices.iter().all(|v| v.source_channel == Some(9)));
+
+        let sizes: Vec<usize> = voices.iter().map(|v| v.notes.len()).collect();
+        assert_eq!(sizes, vec![2, 2, 8]);
+        assert_eq!(voices[0].notes[0].pitch, 36);
+        assert_eq!(voices[2].notes[0].pitch, 42);
+    }
 }
@@ -0,0 +1,616 @@
+//! Separated voices → ABC notation.
+//!
+//! The ABC counterpart to `midi_analysis::voices_to_midi`: each voice becomes
+//! a `V:` block under a shared header built from the detected key and the
+//! file's opening meter and tempo. Notes are snapped to a sixteenth grid
+//! (`L:1/16`), spelled against the key signature, and split at barlines
+//! with ties. Anything that can't be written faithfully — notes that collide
+//! after quantization, later tempo or meter changes — is reported in
+//! [`AbcExport::dropped`] and echoed as `%` comments in the tune.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use midi_analysis::{quantize, Grid, MidiFileContext, SeparatedVoice, SeparationMethod, TimedNote};
+
+use crate::key::key_to_abc;
+use crate::types::{KeyDetection, KeyMode};
+
+/// Grid the body is quantized to; one step is one `L:` unit.
+const GRID: Grid = Grid::Sixteenth;
+
+/// `L:` units per whole note.
+const UNITS_PER_WHOLE: u64 = 16;
+
+/// Note lengths, in units, that read as plain (optionally dotted) values.
+/// Longer or odd durations are split into these and tied.
+const WRITABLE_LENGTHS: [u64; 8] = [16, 12, 8, 6, 4, 3, 2, 1];
+
+/// Bars per line of ABC body.
+const BARS_PER_LINE: usize = 4;
+
+/// Voices with a lower mean pitch get a bass clef.
+const BASS_CLEF_BELOW: f64 = 55.0;
+
+const MAJOR_STEPS: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+const MINOR_STEPS: [u8; 7] = [0, 2, 3, 5, 7, 8, 10];
+
+/// Pitch class of each natural letter, C through B.
+const LETTER_PITCH: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+const LETTERS: [char; 7] = ['C', 'D', 'E', 'F', 'G', 'A', 'B'];
+
+/// Chromatic spellings as `(letter index, alteration)`.
+const SHARP_SPELLING: [(usize, i8); 12] = [
+    (0, 0),
+    (0, 1),
+    (1, 0),
+    (1, 1),
+    (2, 0),
+    (3, 0),
+    (3, 1),
+    (4, 0),
+    (4, 1),
+    (5, 0),
+    (5, 1),
+    (6, 0),
+];
+const FLAT_SPELLING: [(usize, i8); 12] = [
+    (0, 0),
+    (1, -1),
+    (1, 0),
+    (2, -1),
+    (2, 0),
+    (3, 0),
+    (4, -1),
+    (4, 0),
+    (5, -1),
+    (5, 0),
+    (6, -1),
+    (6, 0),
+];
+
+/// An ABC tune plus whatever didn't survive the conversion.
+#[derive(Debug, Clone)]
+pub struct AbcExport {
+    pub abc: String,
+    /// Human-readable notes on dropped or simplified material.
+    pub dropped: Vec<String>,
+}
+
+/// Render separated voices as an ABC tune string.
+///
+/// Shorthand for [`export_abc`] when the drop report isn't needed; the
+/// report is still present in the tune as `%` comments.
+pub fn voices_to_abc(
+    voices: &[SeparatedVoice],
+    context: &MidiFileContext,
+    key: &KeyDetection,
+) -> String {
+    export_abc(voices, context, key).abc
+}
+
+/// Render separated voices as an ABC tune, one `V:` per voice.
+///
+/// Voices are expected to be monophonic, as `separate_voices` produces.
+/// After quantization, a note that starts where an earlier note in the same
+/// voice starts is dropped, and overlapping notes are cut short at the next
+/// onset. Percussion voices get `clef=perc` and are written at their GM key
+/// numbers.
+pub fn export_abc(
+    voices: &[SeparatedVoice],
+    context: &MidiFileContext,
+    key: &KeyDetection,
+) -> AbcExport {
+    let mut dropped = Vec::new();
+    let ppq = context.ppq.max(1);
+    let unit_ticks = GRID.ticks(ppq);
+
+    let (numerator, denominator) = context.time_signature_at(0);
+    let bar_units = bar_units(numerator, denominator);
+    if bar_units.is_none() {
+        dropped.push(format!(
+            "meter {}/{} doesn't fit a 1/{} grid; barlines omitted",
+            numerator, denominator, UNITS_PER_WHOLE
+        ));
+    }
+    if context.time_signatures.len() > 1 {
+        dropped.push(format!(
+            "{} later meter change(s) ignored",
+            context.time_signatures.len() - 1
+        ));
+    }
+    if context.tempo_changes.len() > 1 {
+        dropped.push(format!(
+            "{} later tempo change(s) ignored",
+            context.tempo_changes.len() - 1
+        ));
+    }
+
+    let speller = Speller::new(key);
+    let mut bodies = Vec::with_capacity(voices.len());
+    for (index, voice) in voices.iter().enumerate() {
+        let events = voice_events(voice, ppq, unit_ticks, index, &mut dropped);
+        bodies.push(render_voice(&events, bar_units, &speller));
+    }
+
+    let mut abc = String::new();
+    let _ = writeln!(abc, "X:1");
+    let _ = writeln!(abc, "T:Transcription");
+    let _ = writeln!(abc, "M:{}/{}", numerator, denominator);
+    let _ = writeln!(abc, "L:1/{}", UNITS_PER_WHOLE);
+    let _ = writeln!(abc, "Q:1/4={}", context.bpm_at(0).round() as u32);
+    for note in &dropped {
+        let _ = writeln!(abc, "% {}", note);
+    }
+    let _ = writeln!(abc, "K:{}", key_to_abc(key));
+
+    for (index, (voice, body)) in voices.iter().zip(&bodies).enumerate() {
+        let _ = writeln!(abc, "V:{}{}", index + 1, clef_for(voice));
+        abc.push_str(body);
+    }
+
+    AbcExport { abc, dropped }
+}
+
+/// `L:` units per bar, if the meter lands on the grid.
+fn bar_units(numerator: u8, denominator: u8) -> Option<u64> {
+    if denominator == 0 || !UNITS_PER_WHOLE.is_multiple_of(denominator as u64) {
+        return None;
+    }
+    let units = numerator as u64 * (UNITS_PER_WHOLE / denominator as u64);
+    (units > 0).then_some(units)
+}
+
+fn clef_for(voice: &SeparatedVoice) -> &'static str {
+    if voice.method == SeparationMethod::DrumKit {
+        " clef=perc"
+    } else if voice.stats.note_count > 0 && voice.stats.mean_pitch < BASS_CLEF_BELOW {
+        " clef=bass"
+    } else {
+        ""
+    }
+}
+
+/// A note or rest in grid units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Event {
+    start: u64,
+    length: u64,
+    pitch: Option<u8>,
+    /// Tied into the next event (a note split at a barline)
+    tie: bool,
+}
+
+/// Quantize one voice and lay it out as a gapless run of notes and rests.
+fn voice_events(
+    voice: &SeparatedVoice,
+    ppq: u16,
+    unit_ticks: f64,
+    index: usize,
+    dropped: &mut Vec<String>,
+) -> Vec<Event> {
+    let mut notes: Vec<TimedNote> = voice.notes.clone();
+    quantize(&mut notes, GRID, ppq, 1.0);
+    notes.sort_by(|a, b| a.onset_tick.cmp(&b.onset_tick).then(b.pitch.cmp(&a.pitch)));
+
+    let to_units = |tick: u64| (tick as f64 / unit_ticks).round() as u64;
+
+    let mut events: Vec<Event> = Vec::with_capacity(notes.len() * 2);
+    let mut cursor = 0u64;
+    let mut collisions = 0usize;
+    let mut truncated = 0usize;
+
+    for (i, note) in notes.iter().enumerate() {
+        let start = to_units(note.onset_tick);
+        let mut end = to_units(note.offset_tick).max(start + 1);
+
+        if start < cursor {
+            collisions += 1;
+            continue;
+        }
+        if let Some(next) = notes[i + 1..]
+            .iter()
+            .map(|n| to_units(n.onset_tick))
+            .find(|&s| s > start)
+        {
+            if end > next {
+                end = next;
+                truncated += 1;
+            }
+        }
+
+        if start > cursor {
+            events.push(Event {
+                start: cursor,
+                length: start - cursor,
+                pitch: None,
+                tie: false,
+            });
+        }
+        events.push(Event {
+            start,
+            length: end - start,
+            pitch: Some(note.pitch),
+            tie: false,
+        });
+        cursor = end;
+    }
+
+    if collisions > 0 {
+        dropped.push(format!(
+            "V:{} dropped {} note(s) sharing an onset after quantization",
+            index + 1,
+            collisions
+        ));
+    }
+    if truncated > 0 {
+        dropped.push(format!(
+            "V:{} shortened {} overlapping note(s)",
+            index + 1,
+            truncated
+        ));
+    }
+    events
+}
+
+/// Write events as ABC, splitting at barlines and padding the last bar.
+fn render_voice(events: &[Event], bar_units: Option<u64>, speller: &Speller) -> String {
+    let mut out = String::new();
+    let mut bar_accidentals: HashMap<(usize, i32), i8> = HashMap::new();
+    let mut bars_on_line = 0usize;
+
+    let mut pieces: Vec<Event> = Vec::new();
+    for event in events {
+        split_at_bars(*event, bar_units, &mut pieces);
+    }
+    if let (Some(bar), Some(last)) = (bar_units, pieces.last().copied()) {
+        let end = last.start + last.length;
+        if end % bar != 0 {
+            pieces.push(Event {
+                start: end,
+                length: bar - end % bar,
+                pitch: None,
+                tie: false,
+            });
+        }
+    }
+
+    for (i, piece) in pieces.iter().enumerate() {
+        let lengths = split_length(piece.length);
+        for (j, &length) in lengths.iter().enumerate() {
+            match piece.pitch {
+                Some(pitch) => {
+                    out.push_str(&speller.note(pitch, &mut bar_accidentals));
+                    out.push_str(&length_suffix(length));
+                    if j + 1 < lengths.len() || piece.tie {
+                        out.push('-');
+                    }
+                }
+                None => {
+                    out.push('z');
+                    out.push_str(&length_suffix(length));
+                }
+            }
+        }
+
+        let end = piece.start + piece.length;
+        match bar_units {
+            Some(bar) if end % bar == 0 => {
+                bar_accidentals.clear();
+                bars_on_line += 1;
+                if i + 1 == pieces.len() {
+                    out.push_str(" |]\n");
+                } else if bars_on_line == BARS_PER_LINE {
+                    out.push_str(" |\n");
+                    bars_on_line = 0;
+                } else {
+                    out.push_str(" | ");
+                }
+            }
+            _ => out.push(' '),
+        }
+    }
+
+    if bar_units.is_none() || pieces.is_empty() {
+        let trimmed = out.trim_end().to_string();
+        out = trimmed;
+        out.push_str(" |]\n");
+    }
+    out
+}
+
+/// Split an event at every barline it crosses, tying note pieces together.
+fn split_at_bars(event: Event, bar_units: Option<u64>, out: &mut Vec<Event>) {
+    let Some(bar) = bar_units else {
+        out.push(event);
+        return;
+    };
+    let mut start = event.start;
+    let end = event.start + event.length;
+    while start < end {
+        let piece_end = end.min((start / bar + 1) * bar);
+        out.push(Event {
+            start,
+            length: piece_end - start,
+            pitch: event.pitch,
+            tie: event.pitch.is_some() && piece_end < end,
+        });
+        start = piece_end;
+    }
+}
+
+/// Break a length into writable values, longest first.
+fn split_length(mut length: u64) -> Vec<u64> {
+    let mut parts = Vec::new();
+    while length > 0 {
+        let part = WRITABLE_LENGTHS
+            .iter()
+            .copied()
+            .find(|&l| l <= length)
+            .unwrap_or(1);
+        parts.push(part);
+        length -= part;
+    }
+    parts
+}
+
+fn length_suffix(length: u64) -> String {
+    if length == 1 {
+        String::new()
+    } else {
+        length.to_string()
+    }
+}
+
+/// Spells MIDI pitches against a key signature.
+struct Speller {
+    /// Diatonic spelling per pitch class, for notes in the key.
+    scale: [Option<(usize, i8)>; 12],
+    /// Alteration the key signature gives each letter.
+    signature: [i8; 7],
+    prefer_flats: bool,
+}
+
+impl Speller {
+    fn new(key: &KeyDetection) -> Self {
+        let mut chars = key.root.chars();
+        let root_letter = chars
+            .next()
+            .and_then(|c| LETTERS.iter().position(|&l| l == c.to_ascii_uppercase()))
+            .unwrap_or(0);
+        let steps = match key.mode {
+            KeyMode::Major => MAJOR_STEPS,
+            KeyMode::Minor => MINOR_STEPS,
+        };
+
+        let mut scale = [None; 12];
+        let mut signature = [0i8; 7];
+        for (degree, step) in steps.iter().enumerate() {
+            let letter = (root_letter + degree) % 7;
+            let pitch_class = (key.root_pitch_class + step) % 12;
+            let mut alter = pitch_class as i8 - LETTER_PITCH[letter] as i8;
+            if alter > 6 {
+                alter -= 12;
+            } else if alter < -6 {
+                alter += 12;
+            }
+            scale[pitch_class as usize] = Some((letter, alter));
+            signature[letter] = alter;
+        }
+
+        let prefer_flats = signature.iter().any(|&a| a < 0);
+        Self {
+            scale,
+            signature,
+            prefer_flats,
+        }
+    }
+
+    /// ABC for one pitch, with an accidental only where the key signature
+    /// and earlier accidentals in the bar don't already imply it.
+    fn note(&self, pitch: u8, bar_accidentals: &mut HashMap<(usize, i32), i8>) -> String {
+        let pitch_class = (pitch % 12) as usize;
+        let (letter, alter) = self.scale[pitch_class].unwrap_or(if self.prefer_flats {
+            FLAT_SPELLING[pitch_class]
+        } else {
+            SHARP_SPELLING[pitch_class]
+        });
+
+        // Octave of the written letter: Cb4 sounds as B3, B#3 as C4
+        let natural = pitch as i32 - alter as i32;
+        let octave = natural.div_euclid(12) - 1;
+
+        let implied = bar_accidentals
+            .get(&(letter, octave))
+            .copied()
+            .unwrap_or(self.signature[letter]);
+
+        let mut out = String::new();
+        if alter != implied {
+            out.push_str(match alter {
+                -2 => "__",
+                -1 => "_",
+                1 => "^",
+                2 => "^^",
+                _ => "=",
+            });
+            bar_accidentals.insert((letter, octave), alter);
+        }
+
+        let name = LETTERS[letter];
+        if octave >= 5 {
+            out.push(name.to_ascii_lowercase());
+            for _ in 5..octave {
+                out.push('\'');
+            }
+        } else {
+            out.push(name);
+            for _ in octave..4 {
+                out.push(',');
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use midi_analysis::analyze::{TempoChange, TimeSignature};
+    use midi_analysis::VoiceStats;
+
+    fn make_note(pitch: u8, onset: u64, offset: u64) -> TimedNote {
+        TimedNote {
+            pitch,
+            onset_tick: onset,
+            offset_tick: offset,
+            velocity: 80,
+            channel: 0,
+            track_index: 0,
+        }
+    }
+
+    fn make_voice(index: usize, notes: Vec<TimedNote>) -> SeparatedVoice {
+        SeparatedVoice {
+            stats: VoiceStats::from_notes(&notes),
+            notes,
+            method: SeparationMethod::PitchContiguity,
+            voice_index: index,
+            source_channel: Some(0),
+            source_track: Some(0),
+        }
+    }
+
+    fn make_context(numerator: u8, denominator: u8) -> MidiFileContext {
+        MidiFileContext {
+            ppq: 480,
+            format: 1,
+            track_count: 1,
+            tempo_changes: vec![TempoChange {
+                tick: 0,
+                microseconds_per_beat: 500_000,
+                bpm: 120.0,
+            }],
+            time_signatures: vec![TimeSignature {
+                tick: 0,
+                numerator,
+                denominator,
+            }],
+            total_ticks: 0,
+        }
+    }
+
+    fn make_key(root: &str, root_pitch_class: u8, mode: KeyMode) -> KeyDetection {
+        KeyDetection {
+            root: root.into(),
+            root_pitch_class,
+            mode,
+            confidence: 1.0,
+            alternates: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn header_and_quarter_notes() {
+        let notes = (0..4)
+            .map(|i| make_note(60 + 2 * i as u8, i * 480, i * 480 + 480))
+            .collect();
+        let abc = voices_to_abc(
+            &[make_voice(0, notes)],
+            &make_context(4, 4),
+            &make_key("C", 0, KeyMode::Major),
+        );
+
+        assert_eq!(
+            abc,
+            "X:1\nT:Transcription\nM:4/4\nL:1/16\nQ:1/4=120\nK:C\nV:1\nC4 D4 E4 ^F4 |]\n"
+        );
+    }
+
+    #[test]
+    fn sloppy_timing_snaps_to_grid() {
+        // Slightly early/late eighths, then a half-note rest
+        let notes = vec![make_note(67, 5, 235), make_note(69, 250, 470)];
+        let export = export_abc(
+            &[make_voice(0, notes)],
+            &make_context(2, 4),
+            &make_key("G", 7, KeyMode::Major),
+        );
+
+        assert!(export.dropped.is_empty());
+        assert!(export.abc.ends_with("V:1\nG2 A2 z4 |]\n"), "{}", export.abc);
+    }
+
+    #[test]
+    fn notes_across_barline_are_tied() {
+        let notes = vec![make_note(62, 1440, 2400)];
+        let abc = voices_to_abc(
+            &[make_voice(0, notes)],
+            &make_context(4, 4),
+            &make_key("D", 2, KeyMode::Major),
+        );
+        assert!(abc.ends_with("V:1\nz12 D4- | D4 z12 |]\n"), "{}", abc);
+    }
+
+    #[test]
+    fn accidentals_follow_key_and_bar() {
+        // F major: Bb is in the key, B natural needs "=" once per bar
+        let notes = vec![
+            make_note(70, 0, 480),
+            make_note(71, 480, 960),
+            make_note(71, 960, 1440),
+            make_note(70, 1440, 1920),
+            make_note(71, 1920, 2400),
+        ];
+        let abc = voices_to_abc(
+            &[make_voice(0, notes)],
+            &make_context(4, 4),
+            &make_key("F", 5, KeyMode::Major),
+        );
+        assert!(abc.contains("V:1\nB4 =B4 B4 _B4 | =B4 z12 |]\n"), "{}", abc);
+    }
+
+    #[test]
+    fn octaves_use_abc_marks() {
+        let notes = vec![
+            make_note(36, 0, 240),
+            make_note(48, 240, 480),
+            make_note(72, 480, 720),
+            make_note(84, 720, 960),
+        ];
+        let abc = voices_to_abc(
+            &[make_voice(0, notes)],
+            &make_context(2, 4),
+            &make_key("C", 0, KeyMode::Major),
+        );
+        assert!(abc.contains("C,,2 C,2 c2 c'2 |]"), "{}", abc);
+    }
+
+    #[test]
+    fn collisions_are_reported() {
+        // Two notes land on the same sixteenth once quantized
+        let notes = vec![make_note(64, 0, 480), make_note(60, 10, 480)];
+        let export = export_abc(
+            &[make_voice(0, notes)],
+            &make_context(4, 4),
+            &make_key("C", 0, KeyMode::Major),
+        );
+        assert_eq!(export.dropped.len(), 1);
+        assert!(export.abc.contains("% V:1 dropped 1 note(s)"));
+        assert!(export.abc.contains("V:1\nE4 z12 |]"), "{}", export.abc);
+    }
+
+    #[test]
+    fn one_voice_block_per_voice() {
+        let melody = make_voice(0, vec![make_note(72, 0, 1920)]);
+        let bass = make_voice(1, vec![make_note(36, 0, 1920)]);
+        let abc = voices_to_abc(
+            &[melody, bass],
+            &make_context(4, 4),
+            &make_key("A", 9, KeyMode::Minor),
+        );
+        assert!(abc.contains("K:Am\n"));
+        assert!(abc.contains("V:1\nc16 |]\n"), "{}", abc);
+        assert!(abc.contains("V:2 clef=bass\nC,,16 |]\n"), "{}", abc);
+    }
+}
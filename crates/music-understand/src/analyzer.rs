@@ -71,6 +71,7 @@ impl MusicAnalyzer for HeuristicAnalyzer {
                 confidence: cls.confidence,
                 notes: voice.notes.clone(),
                 features: cls.features,
+                sub_label: cls.sub_label,
             })
             .collect()
     }
@@ -1,11 +1,30 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Mutex;
 
 use anyhow::{Context, Result};
 use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::types::MusicUnderstanding;
 
+/// A single slice of analysis that can be cached without the full bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisFacet {
+    Key,
+    Meter,
+}
+
+impl AnalysisFacet {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalysisFacet::Key => "key",
+            AnalysisFacet::Meter => "meter",
+        }
+    }
+}
+
 /// SQLite-backed cache for music understanding results.
 ///
 /// Cache key is `(content_hash, version)`. When algorithm version bumps,
@@ -31,6 +50,13 @@ impl AnalysisCache {
                     result_json  TEXT NOT NULL,
                     PRIMARY KEY (content_hash, version)
                 );
+                CREATE TABLE IF NOT EXISTS facets (
+                    content_hash TEXT NOT NULL,
+                    version      INTEGER NOT NULL,
+                    facet        TEXT NOT NULL,
+                    result_json  TEXT NOT NULL,
+                    PRIMARY KEY (content_hash, version, facet)
+                );
                 CREATE TABLE IF NOT EXISTS embeddings (
                     content_hash  TEXT NOT NULL,
                     model_name    TEXT NOT NULL,
@@ -73,6 +99,21 @@ impl AnalysisCache {
         }
     }
 
+    /// Highest cached version for a hash, or `None` if it was never analyzed.
+    pub fn latest_version(&self, content_hash: &str) -> Result<Option<u32>> {
+        let conn = self
+            .connection
+            .lock()
+            .map_err(|_| anyhow::anyhow!("cache mutex poisoned"))?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT MAX(version) FROM understanding WHERE content_hash = ?1",
+        )?;
+
+        stmt.query_row(rusqlite::params![content_hash], |row| row.get(0))
+            .context("querying latest cached version")
+    }
+
     /// Store a computed understanding result in the cache.
     pub fn put(&self, understanding: &MusicUnderstanding) -> Result<()> {
         let json =
@@ -92,6 +133,145 @@ impl AnalysisCache {
 
         Ok(())
     }
+
+    /// Look up many hashes at once inside a single read transaction.
+    ///
+    /// Returns only the hits, keyed by content hash. A row that fails to
+    /// deserialize is left out so the caller treats it as a miss and
+    /// overwrites it, rather than failing the whole batch.
+    pub fn get_many(
+        &self,
+        content_hashes: &[String],
+        version: u32,
+    ) -> Result<HashMap<String, MusicUnderstanding>> {
+        let mut conn = self
+            .connection
+            .lock()
+            .map_err(|_| anyhow::anyhow!("cache mutex poisoned"))?;
+
+        let tx = conn
+            .transaction()
+            .context("starting cache read transaction")?;
+        let mut hits = HashMap::new();
+        {
+            let mut stmt = tx.prepare_cached(
+                "SELECT result_json FROM understanding WHERE content_hash = ?1 AND version = ?2",
+            )?;
+
+            for content_hash in content_hashes {
+                if hits.contains_key(content_hash) {
+                    continue;
+                }
+                let result = stmt.query_row(rusqlite::params![content_hash, version], |row| {
+                    row.get::<_, String>(0)
+                });
+                let json = match result {
+                    Ok(json) => json,
+                    Err(rusqlite::Error::QueryReturnedNoRows) => continue,
+                    Err(e) => return Err(e).context("querying understanding cache"),
+                };
+                if let Ok(understanding) = serde_json::from_str(&json) {
+                    hits.insert(content_hash.clone(), understanding);
+                }
+            }
+        }
+        tx.commit().context("finishing cache read transaction")?;
+
+        Ok(hits)
+    }
+
+    /// Store many computed results in a single write transaction.
+    pub fn put_many<'a>(
+        &self,
+        understandings: impl IntoIterator<Item = &'a MusicUnderstanding>,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+
+        let mut conn = self
+            .connection
+            .lock()
+            .map_err(|_| anyhow::anyhow!("cache mutex poisoned"))?;
+
+        let tx = conn
+            .transaction()
+            .context("starting cache write transaction")?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT OR REPLACE INTO understanding (content_hash, version, created_at, result_json)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+
+            for understanding in understandings {
+                let json = serde_json::to_string(understanding)
+                    .context("serializing understanding for cache")?;
+                stmt.execute(rusqlite::params![
+                    understanding.content_hash,
+                    understanding.version,
+                    now,
+                    json
+                ])?;
+            }
+        }
+        tx.commit().context("committing cache write transaction")?;
+
+        Ok(())
+    }
+
+    /// Look up a cached analysis slice (key-only, meter-only, ...).
+    pub fn get_facet<T: DeserializeOwned>(
+        &self,
+        content_hash: &str,
+        version: u32,
+        facet: AnalysisFacet,
+    ) -> Result<Option<T>> {
+        let conn = self
+            .connection
+            .lock()
+            .map_err(|_| anyhow::anyhow!("cache mutex poisoned"))?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT result_json FROM facets
+             WHERE content_hash = ?1 AND version = ?2 AND facet = ?3",
+        )?;
+
+        let result = stmt.query_row(
+            rusqlite::params![content_hash, version, facet.as_str()],
+            |row| row.get::<_, String>(0),
+        );
+
+        match result {
+            Ok(json) => {
+                let value = serde_json::from_str(&json).context("deserializing cached facet")?;
+                Ok(Some(value))
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e).context("querying facet cache"),
+        }
+    }
+
+    /// Store a single analysis slice in the cache.
+    pub fn put_facet<T: Serialize>(
+        &self,
+        content_hash: &str,
+        version: u32,
+        facet: AnalysisFacet,
+        value: &T,
+    ) -> Result<()> {
+        let json = serde_json::to_string(value).context("serializing facet for cache")?;
+
+        let conn = self
+            .connection
+            .lock()
+            .map_err(|_| anyhow::anyhow!("cache mutex poisoned"))?;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO facets (content_hash, version, facet, result_json)
+             VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![content_hash, version, facet.as_str(), json],
+        )?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -118,6 +298,7 @@ mod tests {
                 root_pitch_class: 0,
                 mode: KeyMode::Major,
                 confidence: 0.9,
+                alternates: vec![],
             },
             meter: MeterDetection {
                 numerator: 4,
@@ -131,6 +312,7 @@ mod tests {
                 confidence: 0.85,
                 notes: vec![],
                 features: Default::default(),
+                sub_label: None,
             }],
             chords: vec![ChordEvent {
                 beat: 0.0,
@@ -174,4 +356,61 @@ mod tests {
         // Same hash, different version = miss
         assert!(cache.get("abc123", 2).unwrap().is_none());
     }
+
+    #[test]
+    fn latest_version_tracks_highest_entry() {
+        let dir = TempDir::new().unwrap();
+        let cache = AnalysisCache::open(&dir.path().join("test.db")).unwrap();
+        assert_eq!(cache.latest_version("abc123").unwrap(), None);
+
+        let mut understanding = sample_understanding();
+        cache.put(&understanding).unwrap();
+        assert_eq!(cache.latest_version("abc123").unwrap(), Some(1));
+
+        understanding.version = 3;
+        cache.put(&understanding).unwrap();
+        assert_eq!(cache.latest_version("abc123").unwrap(), Some(3));
+    }
+
+    #[test]
+    fn facet_roundtrip_is_independent_of_full_entry() {
+        let dir = TempDir::new().unwrap();
+        let cache = AnalysisCache::open(&dir.path().join("test.db")).unwrap();
+
+        let meter = sample_understanding().meter;
+        cache
+            .put_facet("abc123", 1, AnalysisFacet::Meter, &meter)
+            .unwrap();
+
+        let retrieved: MeterDetection = cache
+            .get_facet("abc123", 1, AnalysisFacet::Meter)
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved.numerator, 4);
+
+        let key: Option<KeyDetection> = cache.get_facet("abc123", 1, AnalysisFacet::Key).unwrap();
+        assert!(key.is_none());
+        assert!(cache.get("abc123", 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn batch_roundtrip_returns_only_hits() {
+        let dir = TempDir::new().unwrap();
+        let cache = AnalysisCache::open(&dir.path().join("test.db")).unwrap();
+
+        let first = sample_understanding();
+        let mut second = sample_understanding();
+        second.content_hash = "def456".into();
+        cache.put_many([&first, &second]).unwrap();
+
+        let hashes: Vec<String> = ["abc123", "missing", "def456"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        let hits = cache.get_many(&hashes, 1).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits["def456"].content_hash, "def456");
+        assert!(!hits.contains_key("missing"));
+        assert!(cache.get_many(&hashes, 2).unwrap().is_empty());
+    }
 }
@@ -1,7 +1,7 @@
 use midi_analysis::{MidiFileContext, TimedNote};
 
 use crate::chord_templates::{match_chord, FLAT_KEY_ROOTS};
-use crate::types::{ChordEvent, KeyDetection};
+use crate::types::{ChordEvent, KeyDetection, MeterDetection};
 
 /// Extract chord symbols at each beat position from harmony and bass voices.
 ///
@@ -102,10 +102,62 @@ pub fn extract_chords(
     chords
 }
 
+/// Render chord events as a bar-aligned progression: `| C | Am | Dm G7 |`.
+///
+/// Beats are quarter notes (as in `ChordEvent::beat`); bar length comes from
+/// the meter. A chord held across a bar line is repeated at the start of the
+/// next bar, and consecutive identical chords within a bar are collapsed.
+/// Bars with no chord sounding read `N.C.`.
+pub fn progression_string(chords: &[ChordEvent], meter: &MeterDetection, total_beats: f64) -> String {
+    let beats_per_bar = if meter.numerator == 0 || meter.denominator == 0 {
+        4.0
+    } else {
+        meter.numerator as f64 * 4.0 / meter.denominator as f64
+    };
+
+    let last_chord_beat = chords.last().map_or(0.0, |c| c.beat);
+    let span = total_beats.max(last_chord_beat + f64::EPSILON);
+    let bar_count = (span / beats_per_bar).ceil().max(1.0) as usize;
+
+    let mut bars = Vec::with_capacity(bar_count);
+    let mut next = 0;
+    let mut sounding: Option<&str> = None;
+
+    for bar in 0..bar_count {
+        let bar_start = bar as f64 * beats_per_bar;
+        let bar_end = bar_start + beats_per_bar;
+
+        // Carry the held chord unless a new one lands on the downbeat
+        let changes_on_downbeat = chords.get(next).is_some_and(|c| c.beat <= bar_start);
+        let mut symbols: Vec<&str> = if changes_on_downbeat {
+            Vec::new()
+        } else {
+            sounding.into_iter().collect()
+        };
+
+        while next < chords.len() && chords[next].beat < bar_end {
+            let symbol = chords[next].symbol.as_str();
+            if symbols.last() != Some(&symbol) {
+                symbols.push(symbol);
+            }
+            sounding = Some(symbol);
+            next += 1;
+        }
+
+        if symbols.is_empty() {
+            bars.push("N.C.".to_string());
+        } else {
+            bars.push(symbols.join(" "));
+        }
+    }
+
+    format!("| {} |", bars.join(" | "))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::KeyMode;
+    use crate::types::{ChordQuality, KeyMode};
 
     fn make_note(pitch: u8, onset: u64, offset: u64) -> TimedNote {
         TimedNote {
@@ -124,6 +176,7 @@ mod tests {
             root_pitch_class: 0,
             mode: KeyMode::Major,
             confidence: 0.9,
+            alternates: vec![],
         }
     }
 
@@ -214,4 +267,52 @@ mod tests {
         // With C in bass, should detect C major
         assert_eq!(chords[0].symbol, "C");
     }
+
+    fn chord(beat: f64, symbol: &str) -> ChordEvent {
+        ChordEvent {
+            beat,
+            symbol: symbol.into(),
+            root_pitch_class: 0,
+            quality: ChordQuality::Major,
+            confidence: 0.9,
+        }
+    }
+
+    fn meter(numerator: u8, denominator: u8) -> MeterDetection {
+        MeterDetection {
+            numerator,
+            denominator,
+            confidence: 0.9,
+            triplet_feel: 0.0,
+        }
+    }
+
+    #[test]
+    fn progression_aligns_to_bars() {
+        let chords = vec![
+            chord(0.0, "Cmaj7"),
+            chord(4.0, "Am7"),
+            chord(8.0, "Dm7"),
+            chord(10.0, "G7"),
+        ];
+        assert_eq!(
+            progression_string(&chords, &meter(4, 4), 12.0),
+            "| Cmaj7 | Am7 | Dm7 G7 |"
+        );
+    }
+
+    #[test]
+    fn progression_marks_silent_bars_and_holds_chords() {
+        let chords = vec![chord(3.0, "C"), chord(9.0, "F")];
+        // 3/4 bars: C enters at the top of bar 2 and is held through bar 3
+        assert_eq!(
+            progression_string(&chords, &meter(3, 4), 12.0),
+            "| N.C. | C | C | F |"
+        );
+    }
+
+    #[test]
+    fn progression_without_chords_is_no_chord() {
+        assert_eq!(progression_string(&[], &meter(4, 4), 8.0), "| N.C. | N.C. |");
+    }
 }
@@ -1,6 +1,6 @@
 use midi_analysis::{MidiFileContext, TimedNote};
 
-use crate::types::{KeyDetection, KeyMode};
+use crate::types::{KeyCandidate, KeyDetection, KeyMode};
 
 /// Krumhansl-Kessler major key profile (duration-weighted perception studies).
 const MAJOR_PROFILE: [f64; 12] = [6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88];
@@ -14,11 +14,14 @@ const NOTE_NAMES_FLAT: [&str; 12] = ["C", "Db", "D", "Eb", "E", "F", "Gb", "G",
 /// Pitch classes conventionally spelled with flats.
 const FLAT_ROOTS: [u8; 6] = [1, 3, 5, 6, 8, 10]; // Db, Eb, F, Gb, Ab, Bb
 
+/// How many runner-up keys to report alongside the winner.
+const ALTERNATE_COUNT: usize = 4;
+
 /// Detect the key of a piece using the Krumhansl-Schmuckler algorithm.
 ///
 /// Builds a duration-weighted pitch-class histogram and correlates it
 /// against all 24 major/minor key profiles. The best Pearson correlation
-/// determines the detected key.
+/// determines the detected key; the next few are kept as alternates.
 pub fn detect_key(notes: &[TimedNote], _context: &MidiFileContext) -> KeyDetection {
     if notes.is_empty() {
         return KeyDetection {
@@ -26,6 +29,7 @@ pub fn detect_key(notes: &[TimedNote], _context: &MidiFileContext) -> KeyDetecti
             root_pitch_class: 0,
             mode: KeyMode::Major,
             confidence: 0.0,
+            alternates: Vec::new(),
         };
     }
 
@@ -44,6 +48,7 @@ pub fn detect_key(notes: &[TimedNote], _context: &MidiFileContext) -> KeyDetecti
             root_pitch_class: 0,
             mode: KeyMode::Major,
             confidence: 0.0,
+            alternates: Vec::new(),
         };
     }
 
@@ -53,9 +58,7 @@ pub fn detect_key(notes: &[TimedNote], _context: &MidiFileContext) -> KeyDetecti
     }
 
     // Correlate against all 24 key profiles (12 roots × 2 modes)
-    let mut best_root: u8 = 0;
-    let mut best_mode = KeyMode::Major;
-    let mut best_corr = -1.0_f64;
+    let mut candidates = Vec::with_capacity(24);
 
     for root in 0..12u8 {
         // Rotate histogram so root = index 0
@@ -64,35 +67,46 @@ pub fn detect_key(notes: &[TimedNote], _context: &MidiFileContext) -> KeyDetecti
             rotated[i] = histogram[(i + root as usize) % 12];
         }
 
-        let major_corr = pearson(&rotated, &MAJOR_PROFILE);
-        if major_corr > best_corr {
-            best_corr = major_corr;
-            best_root = root;
-            best_mode = KeyMode::Major;
-        }
-
-        let minor_corr = pearson(&rotated, &MINOR_PROFILE);
-        if minor_corr > best_corr {
-            best_corr = minor_corr;
-            best_root = root;
-            best_mode = KeyMode::Minor;
-        }
+        candidates.push((root, KeyMode::Major, pearson(&rotated, &MAJOR_PROFILE)));
+        candidates.push((root, KeyMode::Minor, pearson(&rotated, &MINOR_PROFILE)));
     }
 
-    let root = if FLAT_ROOTS.contains(&best_root) {
-        NOTE_NAMES_FLAT[best_root as usize].to_string()
-    } else {
-        NOTE_NAMES_SHARP[best_root as usize].to_string()
-    };
+    // Stable sort keeps the lowest root / major-first on exact ties
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let (best_root, best_mode, best_corr) = candidates[0];
+    let alternates = candidates[1..]
+        .iter()
+        .take(ALTERNATE_COUNT)
+        .map(|&(root, mode, corr)| KeyCandidate {
+            root: spell_root(root),
+            root_pitch_class: root,
+            mode,
+            correlation: round_correlation(corr),
+        })
+        .collect();
 
     KeyDetection {
-        root,
+        root: spell_root(best_root),
         root_pitch_class: best_root,
         mode: best_mode,
-        confidence: (best_corr * 10000.0).round() / 10000.0,
+        confidence: round_correlation(best_corr),
+        alternates,
     }
 }
 
+fn spell_root(pitch_class: u8) -> String {
+    if FLAT_ROOTS.contains(&pitch_class) {
+        NOTE_NAMES_FLAT[pitch_class as usize].to_string()
+    } else {
+        NOTE_NAMES_SHARP[pitch_class as usize].to_string()
+    }
+}
+
+fn round_correlation(corr: f64) -> f64 {
+    (corr * 10000.0).round() / 10000.0
+}
+
 /// Pearson correlation coefficient between two 12-element arrays.
 fn pearson(x: &[f64; 12], y: &[f64; 12]) -> f64 {
     let x_mean: f64 = x.iter().sum::<f64>() / 12.0;
@@ -209,6 +223,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn alternates_are_ranked_below_winner() {
+        // C major scale — A minor (relative) should be a close runner-up
+        let pitches = [60, 62, 64, 65, 67, 69, 71];
+        let notes: Vec<_> = pitches
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| make_note(p, i as u64 * 480, (i as u64 + 1) * 480))
+            .collect();
+
+        let result = detect_key(&notes, &dummy_context());
+        assert_eq!(result.alternates.len(), ALTERNATE_COUNT);
+        assert!(result.alternates[0].correlation <= result.confidence);
+        for pair in result.alternates.windows(2) {
+            assert!(pair[0].correlation >= pair[1].correlation);
+        }
+        assert!(result
+            .alternates
+            .iter()
+            .any(|alt| alt.root_pitch_class == 9 && alt.mode == KeyMode::Minor));
+    }
+
+    #[test]
+    fn ambiguity_uses_alternate_margin() {
+        let mut det = detect_key(&[], &dummy_context());
+        assert!(!det.is_ambiguous(0.1), "no alternates means not ambiguous");
+
+        det.confidence = 0.80;
+        det.alternates.push(KeyCandidate {
+            root: "A".into(),
+            root_pitch_class: 9,
+            mode: KeyMode::Minor,
+            correlation: 0.78,
+        });
+        assert!(det.is_ambiguous(0.05));
+        assert!(!det.is_ambiguous(0.01));
+    }
+
     #[test]
     fn pearson_identical_arrays() {
         let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0];
@@ -223,6 +275,7 @@ mod tests {
             root_pitch_class: 1,
             mode: KeyMode::Minor,
             confidence: 0.9,
+            alternates: vec![],
         };
         assert_eq!(key_to_abc(&det), "Dbm");
 
@@ -231,6 +284,7 @@ mod tests {
             root_pitch_class: 7,
             mode: KeyMode::Major,
             confidence: 0.85,
+            alternates: vec![],
         };
         assert_eq!(key_to_abc(&det2), "G");
     }
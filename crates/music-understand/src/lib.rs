@@ -1,3 +1,4 @@
+pub mod abc_export;
 pub mod analyzer;
 pub mod cache;
 pub mod chord_templates;
@@ -5,23 +6,29 @@ pub mod chords;
 pub mod key;
 pub mod meter;
 pub mod types;
+#[cfg(feature = "zmq")]
+pub mod zmq_analyzer;
 
+pub use abc_export::{export_abc, voices_to_abc, AbcExport};
 pub use analyzer::{HeuristicAnalyzer, MusicAnalyzer};
-pub use cache::AnalysisCache;
+pub use cache::{AnalysisCache, AnalysisFacet};
 pub use key::key_to_abc;
+#[cfg(feature = "zmq")]
+pub use zmq_analyzer::ZmqAnalyzer;
 pub use types::{
-    ChordEvent, ChordQuality, ClassifiedVoice, KeyDetection, KeyMode, MeterDetection,
-    MusicUnderstanding,
+    ChordEvent, ChordQuality, ClassifiedVoice, KeyCandidate, KeyDetection, KeyMode,
+    MeterDetection, MusicUnderstanding, ReanalyzeReport,
 };
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use tracing::info;
+use tracing::{info, warn};
 
 /// Current algorithm version — bump to invalidate cache.
-pub const CURRENT_VERSION: u32 = 1;
+pub const CURRENT_VERSION: u32 = 3;
 
 /// Unified music understanding engine.
 ///
@@ -85,17 +92,229 @@ impl MusicUnderstandingEngine {
         Ok(understanding)
     }
 
+    /// Analyze many MIDI files, sharing one cache transaction for reads and one for writes.
+    ///
+    /// Results come back in the same order as `hashes`. Misses are computed
+    /// across a scoped thread pool sized to the available parallelism. Each
+    /// entry succeeds or fails on its own, so one unreadable or corrupt file
+    /// doesn't sink the batch.
+    pub fn understand_many(&self, hashes: &[String]) -> Vec<Result<MusicUnderstanding>> {
+        let cached = self
+            .cache
+            .get_many(hashes, CURRENT_VERSION)
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "batch cache read failed, computing every entry");
+                HashMap::new()
+            });
+
+        let mut results: Vec<Option<Result<MusicUnderstanding>>> = hashes
+            .iter()
+            .map(|hash| cached.get(hash).cloned().map(Ok))
+            .collect();
+        let misses: Vec<usize> = (0..hashes.len())
+            .filter(|&i| results[i].is_none())
+            .collect();
+
+        info!(
+            hits = hashes.len() - misses.len(),
+            misses = misses.len(),
+            "music understanding batch"
+        );
+
+        for (i, computed) in self.compute_misses(hashes, &misses) {
+            results[i] = Some(computed);
+        }
+
+        let fresh = misses
+            .iter()
+            .filter_map(|&i| results[i].as_ref().and_then(|r| r.as_ref().ok()));
+        if let Err(e) = self.cache.put_many(fresh) {
+            warn!(error = %e, "batch cache write failed");
+        }
+
+        results
+            .into_iter()
+            .map(|r| r.expect("every batch entry is a hit or a computed miss"))
+            .collect()
+    }
+
+    /// Read and compute the entries at `misses`, spread across worker threads.
+    fn compute_misses(
+        &self,
+        hashes: &[String],
+        misses: &[usize],
+    ) -> Vec<(usize, Result<MusicUnderstanding>)> {
+        let compute_one = |i: usize| {
+            let content_hash = &hashes[i];
+            let computed = self
+                .read_cas(content_hash)
+                .and_then(|bytes| self.compute(content_hash, &bytes));
+            if let Err(e) = &computed {
+                warn!(hash = %content_hash, error = %e, "batch understanding failed");
+            }
+            (i, computed)
+        };
+
+        let workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(misses.len());
+        if workers <= 1 {
+            return misses.iter().map(|&i| compute_one(i)).collect();
+        }
+
+        let chunk_size = misses.len().div_ceil(workers);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = misses
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(|| chunk.iter().map(|&i| compute_one(i)).collect::<Vec<_>>())
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().expect("understanding worker panicked"))
+                .collect()
+        })
+    }
+
+    /// Detect only the key, skipping chord extraction.
+    ///
+    /// Served from the full understanding when that is cached; otherwise the
+    /// key slice is computed and cached on its own.
+    pub fn analyze_key_only(&self, content_hash: &str) -> Result<KeyDetection> {
+        if let Some(cached) = self.cache.get(content_hash, CURRENT_VERSION)? {
+            return Ok(cached.key);
+        }
+        if let Some(key) = self
+            .cache
+            .get_facet(content_hash, CURRENT_VERSION, AnalysisFacet::Key)?
+        {
+            info!(hash = content_hash, "key facet cache hit");
+            return Ok(key);
+        }
+
+        let midi_bytes = self.read_cas(content_hash)?;
+        let smf = parse_smf(&midi_bytes)?;
+        let (all_notes, context) = midi_analysis::analyze::extract_notes(&smf);
+
+        // Key detection still needs voice roles to drop percussion
+        let classified = self.classify(&smf, &all_notes, &context);
+        let key = self
+            .analyzer
+            .analyze_key(&tonal_notes(&classified), &context);
+
+        self.cache
+            .put_facet(content_hash, CURRENT_VERSION, AnalysisFacet::Key, &key)?;
+        Ok(key)
+    }
+
+    /// Detect only the meter, skipping voice classification and chords.
+    ///
+    /// Served from the full understanding when that is cached; otherwise the
+    /// meter slice is computed and cached on its own.
+    pub fn analyze_meter_only(&self, content_hash: &str) -> Result<MeterDetection> {
+        if let Some(cached) = self.cache.get(content_hash, CURRENT_VERSION)? {
+            return Ok(cached.meter);
+        }
+        if let Some(meter) =
+            self.cache
+                .get_facet(content_hash, CURRENT_VERSION, AnalysisFacet::Meter)?
+        {
+            info!(hash = content_hash, "meter facet cache hit");
+            return Ok(meter);
+        }
+
+        let midi_bytes = self.read_cas(content_hash)?;
+        let smf = parse_smf(&midi_bytes)?;
+        let (all_notes, context) = midi_analysis::analyze::extract_notes(&smf);
+        let meter = self.analyzer.analyze_meter(&all_notes, &context);
+
+        self.cache
+            .put_facet(content_hash, CURRENT_VERSION, AnalysisFacet::Meter, &meter)?;
+        Ok(meter)
+    }
+
+    /// Recompute cache entries written by an older algorithm version.
+    ///
+    /// Lets an operator pre-warm the cache after bumping `CURRENT_VERSION`
+    /// instead of paying the latency on the next read of each file. Hashes
+    /// that were never analyzed are skipped; a failure on one hash is
+    /// recorded in the report and does not stop the rest.
+    pub fn reanalyze_stale(&self, hashes: &[String]) -> Result<ReanalyzeReport> {
+        let mut report = ReanalyzeReport::default();
+
+        for content_hash in hashes {
+            match self.cache.latest_version(content_hash)? {
+                None => report.not_cached += 1,
+                Some(version) if version >= CURRENT_VERSION => report.up_to_date += 1,
+                Some(version) => {
+                    info!(hash = %content_hash, from_version = version, "reanalyzing stale entry");
+                    let refreshed = self
+                        .read_cas(content_hash)
+                        .and_then(|bytes| self.compute(content_hash, &bytes))
+                        .and_then(|understanding| self.cache.put(&understanding));
+
+                    match refreshed {
+                        Ok(()) => report.refreshed += 1,
+                        Err(e) => {
+                            warn!(hash = %content_hash, error = %e, "stale reanalysis failed");
+                            report.failed.push((content_hash.clone(), format!("{:#}", e)));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
     /// Compute understanding from raw MIDI bytes (no cache interaction).
     pub fn compute(&self, content_hash: &str, midi_bytes: &[u8]) -> Result<MusicUnderstanding> {
-        let smf = midly::Smf::parse(midi_bytes)
-            .map_err(|e| anyhow::anyhow!("MIDI parse error: {}", e))?;
+        let smf = parse_smf(midi_bytes)?;
 
         // Extract notes and context
         let (all_notes, context) = midi_analysis::analyze::extract_notes(&smf);
 
+        let classified = self.classify(&smf, &all_notes, &context);
+
+        // Analyze key (using all non-percussion notes)
+        let key_detection = self
+            .analyzer
+            .analyze_key(&tonal_notes(&classified), &context);
+
+        // Analyze meter (using all notes for onset density)
+        let meter_detection = self.analyzer.analyze_meter(&all_notes, &context);
+
+        // Extract chords (using harmony + bass partitioning)
+        let (harmony_refs, bass_refs) = analyzer::partition_voices(&classified);
+        let harmony_notes: Vec<_> = harmony_refs.into_iter().cloned().collect();
+        let bass_notes: Vec<_> = bass_refs.into_iter().cloned().collect();
+        let chord_events =
+            self.analyzer
+                .extract_chords(&harmony_notes, &bass_notes, &context, &key_detection);
+
+        Ok(MusicUnderstanding {
+            content_hash: content_hash.to_string(),
+            version: CURRENT_VERSION,
+            context,
+            key: key_detection,
+            meter: meter_detection,
+            voices: classified,
+            chords: chord_events,
+        })
+    }
+
+    /// Profile tracks, separate merged voices, and classify every voice.
+    fn classify(
+        &self,
+        smf: &midly::Smf,
+        all_notes: &[midi_analysis::TimedNote],
+        context: &midi_analysis::MidiFileContext,
+    ) -> Vec<ClassifiedVoice> {
         // Profile tracks
         let track_profiles =
-            midi_analysis::analyze::profile_tracks(&smf, &all_notes, &context, 0.3);
+            midi_analysis::analyze::profile_tracks(smf, all_notes, context, 0.3);
 
         // Separate voices from tracks that need it
         let mut all_voices = Vec::new();
@@ -112,7 +331,8 @@ impl MusicUnderstandingEngine {
                 continue;
             }
 
-            if profile.merged_voices_likely {
+            // Drum tracks split by kit family so each stem stays addressable
+            if profile.is_percussion || profile.merged_voices_likely {
                 let voices =
                     midi_analysis::separate_voices(&track_notes, context.ppq, &params);
                 all_voices.extend(voices);
@@ -129,53 +349,196 @@ impl MusicUnderstandingEngine {
             }
         }
 
-        // Classify voices
-        let classified =
-            self.analyzer
-                .classify_voices(&all_voices, &context, &track_profiles);
+        self.analyzer
+            .classify_voices(&all_voices, context, &track_profiles)
+    }
 
-        // Analyze key (using all non-percussion notes)
-        let analysis_notes: Vec<_> = classified
+    fn read_cas(&self, content_hash: &str) -> Result<Vec<u8>> {
+        // CAS stores files at {cas_dir}/{prefix}/{hash}
+        // The prefix is the first 2 chars of the hash
+        let prefix = &content_hash[..2.min(content_hash.len())];
+        let path = self.cas_dir.join(prefix).join(content_hash);
+
+        std::fs::read(&path).with_context(|| format!("reading CAS content: {}", path.display()))
+    }
+}
+
+fn parse_smf(midi_bytes: &[u8]) -> Result<midly::Smf<'_>> {
+    midly::Smf::parse(midi_bytes).map_err(|e| anyhow::anyhow!("MIDI parse error: {}", e))
+}
+
+/// Notes from voices that carry pitch content (everything but percussion/rhythm).
+fn tonal_notes(classified: &[ClassifiedVoice]) -> Vec<midi_analysis::TimedNote> {
+    classified
+        .iter()
+        .filter(|v| {
+            !matches!(
+                v.role,
+                midi_analysis::VoiceRole::Percussion | midi_analysis::VoiceRole::Rhythm
+            )
+        })
+        .flat_map(|v| v.notes.iter().cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use midly::{Format, Header, MetaMessage, MidiMessage, Smf, Timing, TrackEvent, TrackEventKind};
+    use tempfile::TempDir;
+
+    fn simple_midi() -> Vec<u8> {
+        let mut track = Vec::new();
+        for key in [60u8, 64, 67, 72] {
+            track.push(TrackEvent {
+                delta: 0.into(),
+                kind: TrackEventKind::Midi {
+                    channel: 0.into(),
+                    message: MidiMessage::NoteOn {
+                        key: key.into(),
+                        vel: 80.into(),
+                    },
+                },
+            });
+            track.push(TrackEvent {
+                delta: 480.into(),
+                kind: TrackEventKind::Midi {
+                    channel: 0.into(),
+                    message: MidiMessage::NoteOff {
+                        key: key.into(),
+                        vel: 0.into(),
+                    },
+                },
+            });
+        }
+        track.push(TrackEvent {
+            delta: 0.into(),
+            kind: TrackEventKind::Meta(MetaMessage::EndOfTrack),
+        });
+
+        let mut smf = Smf::new(Header::new(Format::SingleTrack, Timing::Metrical(480.into())));
+        smf.tracks.push(track);
+
+        let mut bytes = Vec::new();
+        smf.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn write_cas(cas_dir: &std::path::Path, content_hash: &str, bytes: &[u8]) {
+        let prefix_dir = cas_dir.join(&content_hash[..2]);
+        std::fs::create_dir_all(&prefix_dir).unwrap();
+        std::fs::write(prefix_dir.join(content_hash), bytes).unwrap();
+    }
+
+    #[test]
+    fn reanalyze_stale_refreshes_old_versions() {
+        let dir = TempDir::new().unwrap();
+        let cas_dir = dir.path().join("cas");
+        let engine =
+            MusicUnderstandingEngine::new(cas_dir.clone(), dir.path().join("cache.db")).unwrap();
+
+        let midi = simple_midi();
+        write_cas(&cas_dir, "aa11", &midi);
+        write_cas(&cas_dir, "bb22", &midi);
+
+        // aa11: written by an older algorithm version
+        let mut stale = engine.compute("aa11", &midi).unwrap();
+        stale.version = CURRENT_VERSION - 1;
+        engine.cache.put(&stale).unwrap();
+
+        // bb22: already current
+        engine.understand("bb22").unwrap();
+
+        // dd44: stale, but its CAS content has gone missing
+        let mut orphan = engine.compute("dd44", &midi).unwrap();
+        orphan.version = CURRENT_VERSION - 1;
+        engine.cache.put(&orphan).unwrap();
+
+        let hashes: Vec<String> = ["aa11", "bb22", "cc33", "dd44"]
             .iter()
-            .filter(|v| {
-                !matches!(
-                    v.role,
-                    midi_analysis::VoiceRole::Percussion | midi_analysis::VoiceRole::Rhythm
-                )
-            })
-            .flat_map(|v| v.notes.iter().cloned())
+            .map(|h| h.to_string())
             .collect();
+        let report = engine.reanalyze_stale(&hashes).unwrap();
 
-        let key_detection = self.analyzer.analyze_key(&analysis_notes, &context);
+        assert_eq!(report.refreshed, 1);
+        assert_eq!(report.up_to_date, 1);
+        assert_eq!(report.not_cached, 1);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "dd44");
+        assert!(engine.cache.get("aa11", CURRENT_VERSION).unwrap().is_some());
 
-        // Analyze meter (using all notes for onset density)
-        let meter_detection = self.analyzer.analyze_meter(&all_notes, &context);
+        // A second pass has nothing left to refresh
+        let again = engine.reanalyze_stale(&hashes[..2]).unwrap();
+        assert_eq!(again.refreshed, 0);
+        assert_eq!(again.up_to_date, 2);
+    }
 
-        // Extract chords (using harmony + bass partitioning)
-        let (harmony_refs, bass_refs) = analyzer::partition_voices(&classified);
-        let harmony_notes: Vec<_> = harmony_refs.into_iter().cloned().collect();
-        let bass_notes: Vec<_> = bass_refs.into_iter().cloned().collect();
-        let chord_events =
-            self.analyzer
-                .extract_chords(&harmony_notes, &bass_notes, &context, &key_detection);
+    #[test]
+    fn granular_analysis_matches_full_understanding() {
+        let dir = TempDir::new().unwrap();
+        let cas_dir = dir.path().join("cas");
+        let engine =
+            MusicUnderstandingEngine::new(cas_dir.clone(), dir.path().join("cache.db")).unwrap();
 
-        Ok(MusicUnderstanding {
-            content_hash: content_hash.to_string(),
-            version: CURRENT_VERSION,
-            context,
-            key: key_detection,
-            meter: meter_detection,
-            voices: classified,
-            chords: chord_events,
-        })
+        let midi = simple_midi();
+        write_cas(&cas_dir, "ee55", &midi);
+
+        let key = engine.analyze_key_only("ee55").unwrap();
+        let meter = engine.analyze_meter_only("ee55").unwrap();
+
+        // Slices are cached on their own, without the full bundle
+        assert!(engine.cache.get("ee55", CURRENT_VERSION).unwrap().is_none());
+        let cached_key: Option<KeyDetection> = engine
+            .cache
+            .get_facet("ee55", CURRENT_VERSION, AnalysisFacet::Key)
+            .unwrap();
+        assert!(cached_key.is_some());
+
+        let full = engine.understand("ee55").unwrap();
+        assert_eq!(key.root, full.key.root);
+        assert_eq!(key.mode, full.key.mode);
+        assert_eq!(meter.numerator, full.meter.numerator);
+        assert_eq!(meter.denominator, full.meter.denominator);
     }
 
-    fn read_cas(&self, content_hash: &str) -> Result<Vec<u8>> {
-        // CAS stores files at {cas_dir}/{prefix}/{hash}
-        // The prefix is the first 2 chars of the hash
-        let prefix = &content_hash[..2.min(content_hash.len())];
-        let path = self.cas_dir.join(prefix).join(content_hash);
+    #[test]
+    fn understand_many_mixes_cached_and_uncached() {
+        let dir = TempDir::new().unwrap();
+        let cas_dir = dir.path().join("cas");
+        let engine =
+            MusicUnderstandingEngine::new(cas_dir.clone(), dir.path().join("cache.db")).unwrap();
 
-        std::fs::read(&path).with_context(|| format!("reading CAS content: {}", path.display()))
+        let midi = simple_midi();
+        write_cas(&cas_dir, "aa11", &midi);
+        write_cas(&cas_dir, "bb22", &midi);
+        write_cas(&cas_dir, "cc33", b"not a midi file");
+
+        // aa11 is already cached; bb22 is not
+        engine.understand("aa11").unwrap();
+        assert!(engine.cache.get("bb22", CURRENT_VERSION).unwrap().is_none());
+
+        let hashes: Vec<String> = ["aa11", "bb22", "cc33", "dd44", "aa11"]
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+        let results = engine.understand_many(&hashes);
+
+        assert_eq!(results.len(), hashes.len());
+        assert_eq!(results[0].as_ref().unwrap().content_hash, "aa11");
+        assert_eq!(results[1].as_ref().unwrap().content_hash, "bb22");
+        assert_eq!(results[4].as_ref().unwrap().content_hash, "aa11");
+
+        // Corrupt MIDI and missing CAS content fail on their own
+        let corrupt = results[2].as_ref().unwrap_err();
+        assert!(format!("{:#}", corrupt).contains("MIDI parse error"));
+        assert!(results[3].is_err());
+
+        // The newly computed entry was written back; failures were not
+        assert!(engine.cache.get("bb22", CURRENT_VERSION).unwrap().is_some());
+        assert!(engine.cache.latest_version("cc33").unwrap().is_none());
+        assert_eq!(
+            results[1].as_ref().unwrap().key.root,
+            results[0].as_ref().unwrap().key.root
+        );
     }
 }
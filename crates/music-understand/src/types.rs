@@ -18,6 +18,29 @@ pub struct MusicUnderstanding {
     pub chords: Vec<ChordEvent>,
 }
 
+impl MusicUnderstanding {
+    /// Bar-aligned chord chart, e.g. `| Cmaj7 | Am7 | Dm7 G7 |`.
+    ///
+    /// Bars follow the detected meter; bars with nothing sounding read `N.C.`.
+    pub fn progression_string(&self) -> String {
+        let total_beats = self.context.total_ticks as f64 / self.context.ppq.max(1) as f64;
+        crate::chords::progression_string(&self.chords, &self.meter, total_beats)
+    }
+}
+
+/// Outcome of [`MusicUnderstandingEngine::reanalyze_stale`](crate::MusicUnderstandingEngine::reanalyze_stale).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReanalyzeReport {
+    /// Hashes whose cached result was older than `CURRENT_VERSION` and got recomputed
+    pub refreshed: usize,
+    /// Hashes already cached at `CURRENT_VERSION`
+    pub up_to_date: usize,
+    /// Hashes with no cache entry at all — left for lazy analysis
+    pub not_cached: usize,
+    /// `(content_hash, error)` for stale entries that failed to recompute
+    pub failed: Vec<(String, String)>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum KeyMode {
@@ -43,6 +66,28 @@ pub struct KeyDetection {
     pub mode: KeyMode,
     /// Pearson correlation with best-matching key profile
     pub confidence: f64,
+    /// Runner-up keys, best first — near-ties signal ambiguous tonality
+    #[serde(default)]
+    pub alternates: Vec<KeyCandidate>,
+}
+
+impl KeyDetection {
+    /// True when the best alternate correlates within `margin` of the winner
+    /// (e.g. relative major/minor near-ties).
+    pub fn is_ambiguous(&self, margin: f64) -> bool {
+        self.alternates
+            .first()
+            .is_some_and(|alt| self.confidence - alt.correlation <= margin)
+    }
+}
+
+/// A candidate key and its correlation with the pitch-class histogram.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyCandidate {
+    pub root: String,
+    pub root_pitch_class: u8,
+    pub mode: KeyMode,
+    pub correlation: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,4 +163,7 @@ pub struct ClassifiedVoice {
     pub confidence: f64,
     pub notes: Vec<midi_analysis::TimedNote>,
     pub features: VoiceFeatures,
+    /// Kit family for drum voices ("kick", "snare", "hi_hat", ...).
+    #[serde(default)]
+    pub sub_label: Option<String>,
 }
@@ -0,0 +1,197 @@
+//! ZmqAnalyzer: forwards key and chord analysis to a model worker.
+//!
+//! The worker receives `ToolRequest::MusicAnalyze` over hooteproto and
+//! answers with `ToolResponse::MusicAnalyzed`. Voice classification and
+//! meter detection stay heuristic. Whenever the worker is unreachable or
+//! returns something unusable, the heuristic analyzer answers instead, so
+//! the engine never fails on account of the ML backend.
+
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use hooteproto::request::MusicAnalyzeRequest;
+use hooteproto::{HootClient, Payload, ResponseEnvelope, ToolRequest, ToolResponse};
+use midi_analysis::{MidiFileContext, SeparatedVoice, TimedNote, TrackProfile};
+use serde::de::DeserializeOwned;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::warn;
+
+use crate::analyzer::{HeuristicAnalyzer, MusicAnalyzer};
+use crate::types::{ChordEvent, ClassifiedVoice, KeyDetection, MeterDetection};
+
+/// Analyzer methods a worker can serve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisTask {
+    AnalyzeKey,
+    ExtractChords,
+}
+
+impl AnalysisTask {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnalysisTask::AnalyzeKey => "analyze_key",
+            AnalysisTask::ExtractChords => "extract_chords",
+        }
+    }
+}
+
+/// `MusicAnalyzer` backed by a model worker, with heuristic fallback.
+///
+/// Inject via `MusicUnderstandingEngine::with_analyzer`.
+pub struct ZmqAnalyzer {
+    client: Arc<HootClient>,
+    runtime: Handle,
+    fallback: HeuristicAnalyzer,
+}
+
+impl ZmqAnalyzer {
+    /// `runtime` drives the client's requests when called off-runtime.
+    pub fn new(client: Arc<HootClient>, runtime: Handle) -> Self {
+        Self {
+            client,
+            runtime,
+            fallback: HeuristicAnalyzer,
+        }
+    }
+
+    /// Send one task to the worker and decode its JSON result.
+    fn request<T: DeserializeOwned>(
+        &self,
+        task: AnalysisTask,
+        input: serde_json::Value,
+    ) -> Result<T> {
+        if !self.client.health.is_alive() {
+            anyhow::bail!("worker {} marked dead", self.client.name());
+        }
+
+        let payload = Payload::ToolRequest(ToolRequest::MusicAnalyze(MusicAnalyzeRequest {
+            task: task.as_str().to_string(),
+            input_json: serde_json::to_string(&input).context("serializing analysis input")?,
+        }));
+
+        let response = self.block_on_request(payload)?;
+        decode_response(response)
+    }
+
+    /// The trait is sync but the client is async; bridge without starving
+    /// the runtime we may already be running on.
+    fn block_on_request(&self, payload: Payload) -> Result<Payload> {
+        let request = self.client.request(payload);
+        match Handle::try_current() {
+            Ok(current) if current.runtime_flavor() == RuntimeFlavor::CurrentThread => {
+                anyhow::bail!("cannot block on a current-thread runtime")
+            }
+            Ok(_) => tokio::task::block_in_place(|| self.runtime.block_on(request)),
+            Err(_) => self.runtime.block_on(request),
+        }
+    }
+
+    fn log_fallback(&self, task: AnalysisTask, error: &anyhow::Error) {
+        warn!(
+            worker = self.client.name(),
+            task = task.as_str(),
+            error = %error,
+            "ML analyzer unavailable, falling back to heuristic"
+        );
+    }
+}
+
+/// Extract the task result from a worker's reply.
+fn decode_response<T: DeserializeOwned>(response: Payload) -> Result<T> {
+    match response {
+        Payload::TypedResponse(ResponseEnvelope::Success {
+            response: ToolResponse::MusicAnalyzed(analyzed),
+        }) => serde_json::from_str(&analyzed.result_json)
+            .with_context(|| format!("decoding result from model {}", analyzed.model)),
+        Payload::TypedResponse(ResponseEnvelope::Error(err)) => {
+            anyhow::bail!("worker error: {}", err.message())
+        }
+        other => anyhow::bail!("unexpected worker reply: {:?}", other),
+    }
+}
+
+impl MusicAnalyzer for ZmqAnalyzer {
+    fn analyze_key(&self, notes: &[TimedNote], context: &MidiFileContext) -> KeyDetection {
+        let input = serde_json::json!({ "notes": notes, "context": context });
+        match self.request(AnalysisTask::AnalyzeKey, input) {
+            Ok(key) => key,
+            Err(e) => {
+                self.log_fallback(AnalysisTask::AnalyzeKey, &e);
+                self.fallback.analyze_key(notes, context)
+            }
+        }
+    }
+
+    fn analyze_meter(&self, notes: &[TimedNote], context: &MidiFileContext) -> MeterDetection {
+        self.fallback.analyze_meter(notes, context)
+    }
+
+    fn extract_chords(
+        &self,
+        harmony_notes: &[TimedNote],
+        bass_notes: &[TimedNote],
+        context: &MidiFileContext,
+        key: &KeyDetection,
+    ) -> Vec<ChordEvent> {
+        let input = serde_json::json!({
+            "harmony_notes": harmony_notes,
+            "bass_notes": bass_notes,
+            "context": context,
+            "key": key,
+        });
+        match self.request(AnalysisTask::ExtractChords, input) {
+            Ok(chords) => chords,
+            Err(e) => {
+                self.log_fallback(AnalysisTask::ExtractChords, &e);
+                self.fallback.extract_chords(harmony_notes, bass_notes, context, key)
+            }
+        }
+    }
+
+    fn classify_voices(
+        &self,
+        voices: &[SeparatedVoice],
+        context: &MidiFileContext,
+        track_profiles: &[TrackProfile],
+    ) -> Vec<ClassifiedVoice> {
+        self.fallback.classify_voices(voices, context, track_profiles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hooteproto::responses::MusicAnalyzedResponse;
+    use hooteproto::ToolError;
+
+    #[test]
+    fn decodes_successful_reply() {
+        let reply = Payload::TypedResponse(ResponseEnvelope::success(
+            ToolResponse::MusicAnalyzed(MusicAnalyzedResponse {
+                result_json: r#"[{"beat":0.0,"symbol":"C","root_pitch_class":0,"quality":"major","confidence":0.9}]"#
+                    .to_string(),
+                model: "chord-gnn".to_string(),
+            }),
+        ));
+
+        let chords: Vec<ChordEvent> = decode_response(reply).unwrap();
+        assert_eq!(chords.len(), 1);
+        assert_eq!(chords[0].symbol, "C");
+    }
+
+    #[test]
+    fn worker_error_is_surfaced() {
+        let reply = Payload::TypedResponse(ResponseEnvelope::error(ToolError::internal(
+            "model not loaded",
+        )));
+
+        let result: Result<KeyDetection> = decode_response(reply);
+        assert!(result.unwrap_err().to_string().contains("model not loaded"));
+    }
+
+    #[test]
+    fn unexpected_reply_is_rejected() {
+        let result: Result<KeyDetection> = decode_response(Payload::Ping);
+        assert!(result.is_err());
+    }
+}
@@ -3,12 +3,15 @@
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use serde_json::json;
+use std::time::Duration;
 use tracing::debug;
 
 use crate::async_bridge::{create_job_awaitable, Artifact as AsyncArtifact, JobFuture};
 use crate::broadcast::BroadcastHandler;
 use crate::callbacks::CallbackRegistry;
-use crate::tool_bridge;
+use crate::scheduler::{Quant, Scheduler};
+use crate::session::Action;
+use crate::tool_bridge::{self, ToolCallError};
 
 /// Read-only beat state
 #[pyclass]
@@ -182,12 +185,46 @@ pub fn session(
     })
 }
 
+/// How often a waiting tool call wakes to check for Python signals
+const SIGNAL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Call a hootenanny tool with the GIL released
+///
+/// Broadcast callbacks can take the GIL while we wait. The wait wakes
+/// every [`SIGNAL_POLL_INTERVAL`] to run Python signal handlers, and a
+/// handler's exception (Ctrl-C's `KeyboardInterrupt`) aborts the call.
+/// Timeouts surface as `TimeoutError` and cancelled calls as
+/// `KeyboardInterrupt`.
+fn call_tool(name: &str, args: serde_json::Value) -> PyResult<serde_json::Value> {
+    Python::with_gil(|py| {
+        let mut call = tool_bridge::start_call(name, args).map_err(tool_error)?;
+        loop {
+            if let Some(result) = py.allow_threads(|| call.wait(SIGNAL_POLL_INTERVAL)) {
+                return result.map_err(tool_error);
+            }
+            // Returning here drops `call`, which aborts it
+            py.check_signals()?;
+        }
+    })
+}
+
+fn tool_error(e: anyhow::Error) -> PyErr {
+    match e.downcast_ref::<ToolCallError>() {
+        Some(ToolCallError::Timeout { .. }) => {
+            PyErr::new::<pyo3::exceptions::PyTimeoutError, _>(e.to_string())
+        }
+        Some(ToolCallError::Cancelled { .. }) => {
+            PyErr::new::<pyo3::exceptions::PyKeyboardInterrupt, _>(e.to_string())
+        }
+        None => PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()),
+    }
+}
+
 /// Set session tempo
 #[pyfunction]
 pub fn tempo(bpm: f64) -> PyResult<()> {
     debug!("Setting tempo to {} BPM", bpm);
-    tool_bridge::call_tool("garden_set_tempo", json!({ "bpm": bpm }))
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    call_tool("garden_set_tempo", json!({ "bpm": bpm }))?;
     Ok(())
 }
 
@@ -245,8 +282,7 @@ pub fn sample<'py>(
     }
 
     // Call tool_bridge to start the job
-    let result = tool_bridge::call_tool("sample", args)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    let result = call_tool("sample", args)?;
 
     // Extract job_id from response
     // Response structure: { "kind": "success", "response": { "type": "job_started", "job_id": "..." } }
@@ -364,8 +400,7 @@ pub fn schedule(
     }
 
     // Call tool_bridge to schedule
-    tool_bridge::call_tool("schedule", args)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    call_tool("schedule", args)?;
 
     Ok(())
 }
@@ -393,32 +428,28 @@ pub fn marker(name: String, beat: f64, metadata: Option<Bound<'_, PyDict>>) -> P
 #[pyfunction]
 pub fn play() -> PyResult<()> {
     debug!("play()");
-    tool_bridge::call_tool("garden_play", json!({}))
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    call_tool("garden_play", json!({}))?;
     Ok(())
 }
 
 #[pyfunction]
 pub fn pause() -> PyResult<()> {
     debug!("pause()");
-    tool_bridge::call_tool("garden_pause", json!({}))
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    call_tool("garden_pause", json!({}))?;
     Ok(())
 }
 
 #[pyfunction]
 pub fn stop() -> PyResult<()> {
     debug!("stop()");
-    tool_bridge::call_tool("garden_stop", json!({}))
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    call_tool("garden_stop", json!({}))?;
     Ok(())
 }
 
 #[pyfunction]
 pub fn seek(beat: f64) -> PyResult<()> {
     debug!("seek({})", beat);
-    tool_bridge::call_tool("garden_seek", json!({ "beat": beat }))
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+    call_tool("garden_seek", json!({ "beat": beat }))?;
     Ok(())
 }
 
@@ -539,6 +570,54 @@ pub fn on_artifact(_py: Python<'_>, tag: Option<String>) -> PyResult<ArtifactDec
     Ok(ArtifactDecorator { tag })
 }
 
+/// Run a callback once on the next bar, half-bar or beat
+///
+/// Returns the beat the callback will fire on. Calling it exactly on (or
+/// just after) a boundary targets the following one. Bars follow the
+/// transport's meter.
+///
+/// Usage:
+/// ```python
+/// at_next("bar", lambda beat: play())
+/// ```
+#[pyfunction]
+pub fn at_next(py: Python<'_>, quant: String, func: PyObject) -> PyResult<f64> {
+    let quant = Quant::parse(&quant).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "unknown quantization '{}' (expected bar, half_bar or beat)",
+            quant
+        ))
+    })?;
+
+    let scheduler = Scheduler::global().ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Scheduler not initialized")
+    })?;
+    let mut scheduler = scheduler
+        .lock()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+    let registry = CallbackRegistry::global();
+    let callback_id = registry
+        .write()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        .register_quantized(func.clone_ref(py));
+
+    let (_, target) = scheduler.at_next(
+        quant,
+        Action::Callback {
+            id: callback_id.clone(),
+        },
+    );
+    debug!(
+        "at_next({}) -> beat {}, id={}",
+        quant.as_str(),
+        target,
+        callback_id
+    );
+
+    Ok(target)
+}
+
 // --- Async helpers ---
 
 /// Wait for multiple awaitables
@@ -583,6 +662,7 @@ pub fn vibeweaver(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(on_beat, m)?)?;
     m.add_function(wrap_pyfunction!(on_marker, m)?)?;
     m.add_function(wrap_pyfunction!(on_artifact, m)?)?;
+    m.add_function(wrap_pyfunction!(at_next, m)?)?;
     m.add_function(wrap_pyfunction!(gather, m)?)?;
 
     Ok(())
@@ -82,6 +82,7 @@ impl BroadcastHandler {
             Broadcast::TransportStateChanged {
                 state,
                 position_beats,
+                ..
             } => {
                 let transport = Transport::parse(&state);
                 self.apply_transport(transport, position_beats).await;
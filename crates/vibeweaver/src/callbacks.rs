@@ -16,6 +16,7 @@ pub enum CallbackType {
     Beat,
     Marker,
     Artifact,
+    Quantized,
 }
 
 /// A registered callback
@@ -40,6 +41,8 @@ pub struct CallbackRegistry {
     marker_callbacks: HashMap<String, Vec<Callback>>,
     /// Artifact callbacks (None key = all artifacts)
     artifact_callbacks: Vec<Callback>,
+    /// One-shot callbacks waiting on a scheduler `Action::Callback`, by ID
+    quantized_callbacks: HashMap<String, Callback>,
 }
 
 impl CallbackRegistry {
@@ -105,6 +108,26 @@ impl CallbackRegistry {
         id
     }
 
+    /// Register a one-shot callback for the scheduler to fire by ID
+    pub fn register_quantized(&mut self, func: Py<PyAny>) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let callback = Callback {
+            id: id.clone(),
+            callback_type: CallbackType::Quantized,
+            divisor: 0,
+            name: None,
+            func,
+        };
+        self.quantized_callbacks.insert(id.clone(), callback);
+        info!("Registered quantized callback: id={}", id);
+        id
+    }
+
+    /// Remove a quantized callback so it can be fired
+    pub fn take_quantized(&mut self, id: &str) -> Option<Callback> {
+        self.quantized_callbacks.remove(id)
+    }
+
     /// Get all beat callbacks that should fire for a given beat
     pub fn get_beat_callbacks(&self, beat: f64) -> Vec<&Callback> {
         let beat_int = beat.floor() as u32;
@@ -170,6 +193,12 @@ impl CallbackRegistry {
             return true;
         }
 
+        // Check quantized callbacks
+        if self.quantized_callbacks.remove(id).is_some() {
+            info!("Removed quantized callback: id={}", id);
+            return true;
+        }
+
         false
     }
 
@@ -178,6 +207,7 @@ impl CallbackRegistry {
         self.beat_callbacks.clear();
         self.marker_callbacks.clear();
         self.artifact_callbacks.clear();
+        self.quantized_callbacks.clear();
         info!("Cleared all callbacks");
     }
 
@@ -190,6 +220,30 @@ impl CallbackRegistry {
     }
 }
 
+/// Fire the quantized callback behind a scheduler `Action::Callback`
+pub fn fire_quantized_callback(id: &str, beat: f64) {
+    // Take it under a short write lock, then call it unlocked so it can
+    // register follow-ups
+    let callback = match CallbackRegistry::global().write() {
+        Ok(mut g) => g.take_quantized(id),
+        Err(e) => {
+            error!("Failed to lock callback registry: {}", e);
+            return;
+        }
+    };
+    let Some(callback) = callback else {
+        debug!("Quantized callback {} was removed before it fired", id);
+        return;
+    };
+
+    Python::with_gil(|py| {
+        if let Err(e) = callback.func.call1(py, (beat,)) {
+            warn!("Quantized callback {} failed: {}", callback.id, e);
+            e.print(py);
+        }
+    });
+}
+
 /// Fire callbacks for a beat tick
 pub fn fire_beat_callbacks(beat: f64) {
     let registry = CallbackRegistry::global();
@@ -314,6 +368,16 @@ mod tests {
         assert!(registry.get_beat_callbacks(4.0).is_empty());
     }
 
+    #[test]
+    fn test_quantized_callbacks_fire_once() {
+        let mut registry = CallbackRegistry::new();
+
+        let id = Python::with_gil(|py| registry.register_quantized(py.None()));
+        assert!(registry.take_quantized(&id).is_some());
+        assert!(registry.take_quantized(&id).is_none());
+        assert!(!registry.remove(&id));
+    }
+
     #[test]
     fn test_callback_counts() {
         let registry = CallbackRegistry::new();
@@ -15,7 +15,8 @@ use crate::session::{
 pub struct Database {
     path: PathBuf,
     /// For in-memory databases, we keep a persistent connection
-    /// since each new in-memory connection creates a fresh database
+    /// since each new in-memory connection creates a fresh database.
+    /// Transactions also pin one connection here while they run.
     memory_conn: Option<Mutex<Connection>>,
 }
 
@@ -62,17 +63,47 @@ impl Database {
             let conn = mutex.lock().unwrap();
             f(&conn)
         } else {
-            let conn = Connection::open(&self.path)?;
-            conn.execute_batch(
-                "PRAGMA journal_mode = WAL;
-                 PRAGMA synchronous = NORMAL;
-                 PRAGMA foreign_keys = ON;
-                 PRAGMA busy_timeout = 5000;",
-            )?;
-            f(&conn)
+            f(&self.open_conn()?)
         }
     }
 
+    fn open_conn(&self) -> Result<Connection> {
+        let conn = Connection::open(&self.path)?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA synchronous = NORMAL;
+             PRAGMA foreign_keys = ON;
+             PRAGMA busy_timeout = 5000;",
+        )?;
+        Ok(conn)
+    }
+
+    /// Run `f` in a single transaction
+    ///
+    /// Every write `f` makes through the `Database` it is given commits
+    /// together, or rolls back if `f` returns an error.
+    pub fn transaction<T>(&self, f: impl FnOnce(&Database) -> Result<T>) -> Result<T> {
+        if self.memory_conn.is_some() {
+            self.with_conn(|conn| Ok(conn.execute_batch("BEGIN")?))?;
+            return self.finish_transaction(f(self));
+        }
+
+        let conn = self.open_conn()?;
+        conn.execute_batch("BEGIN")?;
+        let pinned = Database {
+            path: self.path.clone(),
+            memory_conn: Some(Mutex::new(conn)),
+        };
+        let result = f(&pinned);
+        pinned.finish_transaction(result)
+    }
+
+    fn finish_transaction<T>(&self, result: Result<T>) -> Result<T> {
+        let end = if result.is_ok() { "COMMIT" } else { "ROLLBACK" };
+        self.with_conn(|conn| Ok(conn.execute_batch(end)?))?;
+        result
+    }
+
     /// Initialize schema
     pub fn init_schema(&self) -> Result<()> {
         // For memory databases, schema is initialized in open_memory
@@ -156,6 +187,33 @@ impl Database {
         })
     }
 
+    /// Insert a session with its existing ID and timestamps (snapshot restore)
+    pub fn insert_session(&self, session: &Session) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO sessions (id, name, vibe, tempo_bpm, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    session.id.as_str(),
+                    session.name,
+                    session.vibe,
+                    session.tempo_bpm,
+                    session.created_at.to_rfc3339(),
+                    session.updated_at.to_rfc3339(),
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Delete a session; rules, markers, history and snapshots cascade
+    pub fn delete_session(&self, id: &SessionId) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM sessions WHERE id = ?1", params![id.as_str()])?;
+            Ok(())
+        })
+    }
+
     pub fn list_sessions(&self) -> Result<Vec<Session>> {
         self.with_conn(|conn| {
             let mut stmt = conn.prepare(
@@ -227,6 +285,24 @@ impl Database {
         })
     }
 
+    /// All rules for a session, including disabled ones
+    pub fn get_all_rules(&self, session_id: &SessionId) -> Result<Vec<Rule>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, session_id, trigger_type, trigger_params, action_type, action_params,
+                        priority, enabled, one_shot, fired_count, last_fired_at, created_at
+                 FROM rules WHERE session_id = ?1
+                 ORDER BY created_at ASC",
+            )?;
+
+            let rules = stmt
+                .query_map(params![session_id.as_str()], parse_rule_row)?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(rules)
+        })
+    }
+
     pub fn get_rules_by_trigger(
         &self,
         session_id: &SessionId,
@@ -466,6 +542,7 @@ fn action_type_str(action: &Action) -> &'static str {
         Action::Seek { .. } => "seek",
         Action::Audition { .. } => "audition",
         Action::Notify { .. } => "notify",
+        Action::Callback { .. } => "callback",
     }
 }
 
@@ -11,6 +11,7 @@ pub mod db;
 pub mod kernel;
 pub mod scheduler;
 pub mod session;
+pub mod snapshot;
 pub mod state;
 pub mod tool_bridge;
 pub mod zmq_client;
@@ -19,5 +20,6 @@ pub mod zmq_server;
 pub use db::Database;
 pub use kernel::Kernel;
 pub use session::{Session, SessionId};
+pub use snapshot::SessionSnapshot;
 pub use state::KernelState;
 pub use zmq_server::{Server, ServerConfig};
@@ -4,15 +4,18 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use hooteconf::HootConfig;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Handle;
-use tracing::info;
+use tracing::{info, warn};
 use vibeweaver::{
     broadcast::BroadcastHandler,
+    scheduler::Scheduler,
     session::SessionId,
     state::KernelState,
-    tool_bridge::ToolBridge,
+    tool_bridge::{self, ToolBridge},
     zmq_client,
-    Kernel, Server, ServerConfig,
+    Database, Kernel, Server, ServerConfig,
 };
 
 /// Vibeweaver - Python Kernel for AI Music Agents
@@ -63,12 +66,24 @@ async fn main() -> Result<()> {
         for path in &sources.files {
             println!("#   - {}", path.display());
         }
+        if let Some(profile) = &sources.profile {
+            println!("# Profile overlays ({}):", profile);
+            for path in &sources.profile_files {
+                println!("#   - {}", path.display());
+            }
+        }
         if !sources.env_overrides.is_empty() {
             println!("# Environment overrides:");
             for var in &sources.env_overrides {
                 println!("#   - {}", var);
             }
         }
+        if !sources.warnings.is_empty() {
+            println!("# Warnings:");
+            for warning in &sources.warnings {
+                println!("#   - {}", warning);
+            }
+        }
         println!();
         println!("{}", config.to_toml());
         return Ok(());
@@ -82,7 +97,11 @@ async fn main() -> Result<()> {
     info!("  bind: {}", vibeweaver_config.zmq_router);
     info!("  hootenanny: {}", vibeweaver_config.hootenanny);
     info!("  broadcasts: {}", vibeweaver_config.hootenanny_pub);
-    info!("  db: {}", args.db);
+    let (db_path, unset) = hooteconf::loader::expand_path_checked(&args.db);
+    for var in unset {
+        warn!("--db: ${} is unset, expanded to empty", var);
+    }
+    info!("  db: {}", db_path.display());
     if let Some(ref session) = args.session {
         info!("  session: {}", session);
     }
@@ -97,17 +116,24 @@ async fn main() -> Result<()> {
     info!("  Configured hootenanny connection at {}", vibeweaver_config.hootenanny);
 
     // Initialize tool bridge (makes tools available to Python API)
-    let bridge = ToolBridge::new(zmq_client, Handle::current());
+    let bridge = ToolBridge::new(zmq_client, Handle::current())
+        .with_timeout(Duration::from_millis(vibeweaver_config.tool_timeout_ms));
     ToolBridge::init_global(bridge)?;
     info!("  Tool bridge initialized");
 
     // Initialize broadcast handler (handles job completion waiters)
+    let session_id = SessionId::new();
     let session_name = args.session.clone().unwrap_or_else(|| "default".to_string());
-    let initial_state = KernelState::new(SessionId::new(), session_name, 120.0);
+    let initial_state = KernelState::new(session_id.clone(), session_name, 120.0);
     let handler = BroadcastHandler::new(initial_state);
     BroadcastHandler::init_global(handler)?;
     info!("  Broadcast handler initialized");
 
+    // Initialize scheduler (quantized callbacks follow the transport through it)
+    let db = Database::open(&db_path).context("Failed to open vibeweaver database")?;
+    Scheduler::init_global(Scheduler::new(Arc::new(db), session_id)?)?;
+    info!("  Scheduler initialized");
+
     // Initialize Python kernel
     info!("Initializing Python kernel...");
     let kernel = Kernel::new()?;
@@ -128,6 +154,8 @@ async fn main() -> Result<()> {
     tokio::spawn(async move {
         tokio::signal::ctrl_c().await.ok();
         info!("Received shutdown signal");
+        // Unblock Python code waiting on hootenanny so the kernel can exit
+        tool_bridge::cancel_pending();
         let _ = shutdown_tx_signal.send(());
     });
 
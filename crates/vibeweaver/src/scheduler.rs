@@ -2,7 +2,7 @@
 
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use anyhow::Result;
 use chrono::Utc;
@@ -131,6 +131,53 @@ impl PartialEq for PendingAction {
     }
 }
 
+/// Musical grid for quantized dispatch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quant {
+    Beat,
+    HalfBar,
+    Bar,
+}
+
+impl Quant {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "beat" => Some(Quant::Beat),
+            "half_bar" | "halfbar" => Some(Quant::HalfBar),
+            "bar" | "downbeat" => Some(Quant::Bar),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Quant::Beat => "beat",
+            Quant::HalfBar => "half_bar",
+            Quant::Bar => "bar",
+        }
+    }
+
+    /// Grid spacing in beats
+    pub fn length_beats(&self, beats_per_bar: u32) -> f64 {
+        let bar = beats_per_bar.max(1) as f64;
+        match self {
+            Quant::Beat => 1.0,
+            Quant::HalfBar => bar / 2.0,
+            Quant::Bar => bar,
+        }
+    }
+
+    /// First grid line strictly after `position_beats`
+    ///
+    /// A position already on (or just past) a grid line resolves to the
+    /// following one, so late additions roll over instead of firing in
+    /// the past.
+    pub fn next_boundary(&self, position_beats: f64, beats_per_bar: u32) -> f64 {
+        let length = self.length_beats(beats_per_bar);
+        ((position_beats / length).floor() + 1.0) * length
+    }
+}
+
 /// Generation timing statistics
 #[derive(Debug, Clone)]
 pub struct GenerationStats {
@@ -139,6 +186,9 @@ pub struct GenerationStats {
     pub sample_count: u64,
 }
 
+/// Global scheduler instance
+static SCHEDULER: OnceLock<Mutex<Scheduler>> = OnceLock::new();
+
 /// The scheduler
 pub struct Scheduler {
     db: Arc<Database>,
@@ -147,6 +197,11 @@ pub struct Scheduler {
     agenda: BinaryHeap<PendingAction>,
     generation_stats: HashMap<String, GenerationStats>,
     current_tempo_bpm: f64,
+    /// Latest transport position from `BeatTick`/transport broadcasts
+    position_beats: f64,
+    beats_per_bar: u32,
+    /// Quantized actions waiting for their grid line
+    quantized: BinaryHeap<PendingAction>,
 }
 
 impl Scheduler {
@@ -159,9 +214,24 @@ impl Scheduler {
             agenda: BinaryHeap::new(),
             generation_stats: HashMap::new(),
             current_tempo_bpm: 120.0,
+            position_beats: 0.0,
+            beats_per_bar: 4,
+            quantized: BinaryHeap::new(),
         })
     }
 
+    /// Initialize the global scheduler (call once at startup)
+    pub fn init_global(scheduler: Scheduler) -> Result<()> {
+        SCHEDULER
+            .set(Mutex::new(scheduler))
+            .map_err(|_| anyhow::anyhow!("Scheduler already initialized"))
+    }
+
+    /// Get the global scheduler
+    pub fn global() -> Option<&'static Mutex<Scheduler>> {
+        SCHEDULER.get()
+    }
+
     /// Load rules from database into index
     pub fn load_rules(&mut self) -> Result<()> {
         let rules = self.db.get_rules_by_session(&self.session_id)?;
@@ -196,6 +266,8 @@ impl Scheduler {
         match broadcast {
             Broadcast::BeatTick { beat, tempo_bpm } => {
                 self.current_tempo_bpm = *tempo_bpm;
+                self.position_beats = *beat;
+                actions.extend(self.take_due_quantized(*beat));
 
                 for rule in self.index.get(TriggerType::Beat) {
                     if let Trigger::Beat { divisor } = &rule.trigger {
@@ -267,7 +339,14 @@ impl Scheduler {
                     }
                 }
             }
-            Broadcast::TransportStateChanged { state, .. } => {
+            Broadcast::TransportStateChanged {
+                state,
+                position_beats,
+                beats_per_bar,
+            } => {
+                self.position_beats = *position_beats;
+                self.set_beats_per_bar(*beats_per_bar);
+
                 for rule in self.index.get(TriggerType::Transport) {
                     if let Trigger::Transport {
                         state: trigger_state,
@@ -324,6 +403,48 @@ impl Scheduler {
         actions
     }
 
+    /// Dispatch `action` on the next `quant` grid line
+    ///
+    /// The target is resolved from the latest transport position; returns
+    /// the ID of the pending action and the beat it will fire on.
+    pub fn at_next(&mut self, quant: Quant, action: Action) -> (RuleId, f64) {
+        let target = quant.next_boundary(self.position_beats, self.beats_per_bar);
+        let id = RuleId::new();
+
+        self.quantized.push(PendingAction {
+            rule_id: id.clone(),
+            action,
+            priority: Priority::Critical,
+            deadline_beat: Some(target),
+            start_by_beat: target,
+        });
+
+        (id, target)
+    }
+
+    /// Pop quantized actions whose grid line has been reached
+    fn take_due_quantized(&mut self, position_beats: f64) -> Vec<Action> {
+        let mut due = Vec::new();
+        while self
+            .quantized
+            .peek()
+            .is_some_and(|p| p.start_by_beat <= position_beats)
+        {
+            due.push(self.quantized.pop().unwrap().action);
+        }
+        due
+    }
+
+    /// Latest transport position
+    pub fn position_beats(&self) -> f64 {
+        self.position_beats
+    }
+
+    /// Set the meter used for bar/half-bar quantization
+    pub fn set_beats_per_bar(&mut self, beats_per_bar: u32) {
+        self.beats_per_bar = beats_per_bar.max(1);
+    }
+
     /// Update generation stats after job completes
     pub fn record_generation_time(&mut self, space: &str, duration_ms: u64) -> Result<()> {
         let stats = self
@@ -455,6 +576,67 @@ mod tests {
         assert_eq!(beat_rules[0].priority, Priority::High);
     }
 
+    #[test]
+    fn test_quant_resolution() {
+        assert_eq!(Quant::Beat.next_boundary(0.0, 4), 1.0);
+        assert_eq!(Quant::Beat.next_boundary(2.5, 4), 3.0);
+        assert_eq!(Quant::Bar.next_boundary(5.0, 4), 8.0);
+        assert_eq!(Quant::HalfBar.next_boundary(5.0, 4), 6.0);
+        assert_eq!(Quant::Bar.next_boundary(1.0, 3), 3.0);
+
+        // On or just past a downbeat rolls to the next one
+        assert_eq!(Quant::Bar.next_boundary(4.0, 4), 8.0);
+        assert_eq!(Quant::Bar.next_boundary(4.02, 4), 8.0);
+
+        assert_eq!(Quant::parse("half-bar"), Some(Quant::HalfBar));
+        assert_eq!(Quant::parse("Downbeat"), Some(Quant::Bar));
+        assert_eq!(Quant::parse("tick"), None);
+    }
+
+    #[test]
+    fn test_at_next_dispatches_on_beat_tick() {
+        let db = Arc::new(Database::open_memory().unwrap());
+        let session = db.create_session("test", None, 120.0).unwrap();
+        let mut scheduler = Scheduler::new(db, session.id).unwrap();
+
+        scheduler.process_broadcast(&Broadcast::BeatTick {
+            beat: 5.0,
+            tempo_bpm: 120.0,
+        });
+        let (_, target) = scheduler.at_next(Quant::Bar, Action::Play);
+        assert_eq!(target, 8.0);
+
+        let actions = scheduler.process_broadcast(&Broadcast::BeatTick {
+            beat: 7.0,
+            tempo_bpm: 120.0,
+        });
+        assert!(actions.is_empty());
+
+        let actions = scheduler.process_broadcast(&Broadcast::BeatTick {
+            beat: 8.0,
+            tempo_bpm: 120.0,
+        });
+        assert!(matches!(actions.as_slice(), [Action::Play]));
+
+        // A seek moves the reference point for the next resolution
+        scheduler.process_broadcast(&Broadcast::TransportStateChanged {
+            state: "playing".to_string(),
+            position_beats: 32.5,
+            beats_per_bar: 4,
+        });
+        let (_, target) = scheduler.at_next(Quant::HalfBar, Action::Stop);
+        assert_eq!(target, 34.0);
+
+        // The meter comes from the transport too
+        scheduler.process_broadcast(&Broadcast::TransportStateChanged {
+            state: "playing".to_string(),
+            position_beats: 1.0,
+            beats_per_bar: 3,
+        });
+        let (_, target) = scheduler.at_next(Quant::Bar, Action::Stop);
+        assert_eq!(target, 3.0);
+    }
+
     #[test]
     fn test_pending_action_ordering() {
         let mut heap = BinaryHeap::new();
@@ -158,6 +158,10 @@ pub enum Action {
     Notify {
         message: String,
     },
+    /// Call a Python function held in the `CallbackRegistry`
+    Callback {
+        id: String,
+    },
 }
 
 /// Rule priority levels
@@ -0,0 +1,295 @@
+//! Session snapshots in CAS - checkpoint, restore and fork
+//!
+//! A snapshot is a JSON document holding everything about a session that can
+//! be rebuilt without a live Python interpreter:
+//!
+//! | Captured                          | Re-derived after restore                 |
+//! |-----------------------------------|------------------------------------------|
+//! | Session row (name, vibe, tempo)   | Python globals, imports, live objects    |
+//! | Rules, including disabled ones    | Transport state (next transport event)   |
+//! | Markers                           | Beat position (next `BeatTick`)          |
+//! | Last [`HISTORY_LIMIT`] history    | Job states (`JobStateChanged` broadcasts)|
+//! | `KernelState` recent artifacts    |                                          |
+//!
+//! Python state is deliberately left out: it can hold sockets, threads and
+//! closures that have no faithful serialized form. Agents rebuild it by
+//! replaying their setup code, using the restored history as a guide.
+
+use anyhow::{Context, Result};
+use cas::{ContentHash, ContentStore};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+use crate::session::{HistoryEntry, Marker, MarkerId, Rule, RuleId, Session};
+use crate::state::{KernelState, Transport};
+
+/// Snapshot format version, bumped on incompatible changes
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Most recent history entries kept in a snapshot
+pub const HISTORY_LIMIT: usize = 500;
+
+const SNAPSHOT_MIME: &str = "application/vnd.vibeweaver.session+json";
+
+/// Serializable portion of a vibeweaver session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub version: u32,
+    pub session: Session,
+    pub rules: Vec<Rule>,
+    pub markers: Vec<Marker>,
+    /// Oldest first
+    pub history: Vec<HistoryEntry>,
+    pub kernel_state: Option<KernelState>,
+    pub captured_at: DateTime<Utc>,
+}
+
+impl SessionSnapshot {
+    /// Gather a session's state from the database
+    pub fn capture(db: &Database, session: &Session) -> Result<Self> {
+        let mut history = db.get_recent_history(&session.id, HISTORY_LIMIT)?;
+        history.reverse();
+
+        let kernel_state = db
+            .load_snapshot(&session.id)?
+            .map(|bytes| KernelState::from_capnp(&bytes))
+            .transpose()
+            .context("failed to decode kernel snapshot")?;
+
+        Ok(Self {
+            version: SNAPSHOT_VERSION,
+            session: session.clone(),
+            rules: db.get_all_rules(&session.id)?,
+            markers: db.get_markers(&session.id)?,
+            history,
+            kernel_state,
+            captured_at: Utc::now(),
+        })
+    }
+
+    /// Load a snapshot from CAS
+    pub fn load(store: &impl ContentStore, hash: &ContentHash) -> Result<Self> {
+        let bytes = store
+            .retrieve(hash)?
+            .with_context(|| format!("session snapshot {} not found in CAS", hash))?;
+        let snapshot: Self =
+            serde_json::from_slice(&bytes).context("failed to parse session snapshot")?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            anyhow::bail!(
+                "unsupported session snapshot version {} (expected {})",
+                snapshot.version,
+                SNAPSHOT_VERSION
+            );
+        }
+        Ok(snapshot)
+    }
+
+    /// Write this snapshot into the database as `session`
+    ///
+    /// Any existing session with the same ID is replaced. The replacement
+    /// is one transaction, so a failed restore leaves the old session intact.
+    fn apply(self, db: &Database, session: Session) -> Result<Session> {
+        db.transaction(|db| {
+            let id = session.id.clone();
+            db.delete_session(&id)?;
+            db.insert_session(&session)?;
+
+            for rule in &self.rules {
+                db.insert_rule(rule)?;
+            }
+            for marker in &self.markers {
+                db.insert_marker(marker)?;
+            }
+            for entry in &self.history {
+                db.append_history(entry)?;
+            }
+
+            if let Some(mut state) = self.kernel_state {
+                state.session_id = id.clone();
+                state.session_name = session.name.clone();
+                state.session_vibe = session.vibe.clone();
+                state.tempo_bpm = session.tempo_bpm;
+                state.transport = Transport::Stopped;
+                state.beat.current = 0.0;
+                state.jobs.clear();
+                state.captured_at = Utc::now();
+                db.save_snapshot(&id, &state.to_capnp()?)?;
+            }
+
+            Ok(session)
+        })
+    }
+}
+
+impl Session {
+    /// Checkpoint this session to CAS, returning the snapshot hash
+    pub fn snapshot(&self, db: &Database, store: &impl ContentStore) -> Result<ContentHash> {
+        let snapshot = SessionSnapshot::capture(db, self)?;
+        let json = serde_json::to_vec(&snapshot).context("failed to serialize session snapshot")?;
+        store.store(&json, SNAPSHOT_MIME)
+    }
+
+    /// Restore a snapshot under its original session ID
+    pub fn restore(db: &Database, store: &impl ContentStore, hash: &ContentHash) -> Result<Self> {
+        let snapshot = SessionSnapshot::load(store, hash)?;
+        let mut session = snapshot.session.clone();
+        session.updated_at = Utc::now();
+        snapshot.apply(db, session)
+    }
+
+    /// Restore a snapshot as a new, independent session
+    ///
+    /// Rules, markers and history are copied under fresh IDs so the fork
+    /// and the original can diverge.
+    pub fn fork(
+        db: &Database,
+        store: &impl ContentStore,
+        hash: &ContentHash,
+        name: impl Into<String>,
+    ) -> Result<Self> {
+        let mut snapshot = SessionSnapshot::load(store, hash)?;
+        let session = Session::new(
+            name,
+            snapshot.session.vibe.clone(),
+            snapshot.session.tempo_bpm,
+        );
+
+        for rule in &mut snapshot.rules {
+            rule.id = RuleId::new();
+            rule.session_id = session.id.clone();
+        }
+        for marker in &mut snapshot.markers {
+            marker.id = MarkerId::new();
+            marker.session_id = session.id.clone();
+        }
+        for entry in &mut snapshot.history {
+            entry.session_id = session.id.clone();
+        }
+
+        snapshot.apply(db, session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{Action, Trigger};
+    use crate::state::ArtifactInfo;
+    use cas::FileStore;
+    use tempfile::TempDir;
+
+    fn populated_session(db: &Database) -> Session {
+        let session = db.create_session("jam", Some("dub techno"), 124.0).unwrap();
+
+        let rule = Rule::new(
+            session.id.clone(),
+            Trigger::Beat { divisor: 16 },
+            Action::Notify {
+                message: "phrase".to_string(),
+            },
+        );
+        db.insert_rule(&rule).unwrap();
+        db.set_rule_enabled(&rule.id, false).unwrap();
+        db.insert_marker(&Marker::new(session.id.clone(), "drop", 64.0))
+            .unwrap();
+        db.append_history(&HistoryEntry {
+            id: 0,
+            session_id: session.id.clone(),
+            action: "sample".to_string(),
+            params: Some(serde_json::json!({"space": "orpheus"})),
+            result: None,
+            success: true,
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+        let mut state = KernelState::new(session.id.clone(), "jam".to_string(), 124.0);
+        state.transport = Transport::Playing;
+        state.add_artifact(ArtifactInfo {
+            id: "artifact_1".to_string(),
+            content_hash: "abc".to_string(),
+            tags: vec![],
+            created_at: Utc::now(),
+        });
+        db.save_snapshot(&session.id, &state.to_capnp().unwrap())
+            .unwrap();
+
+        session
+    }
+
+    #[test]
+    fn test_snapshot_restore_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let store = FileStore::at_path(temp.path()).unwrap();
+        let db = Database::open_memory().unwrap();
+        let session = populated_session(&db);
+
+        let hash = session.snapshot(&db, &store).unwrap();
+
+        // A fresh database stands in for a restarted vibeweaver
+        let fresh = Database::open_memory().unwrap();
+        let restored = Session::restore(&fresh, &store, &hash).unwrap();
+
+        assert_eq!(restored.id, session.id);
+        assert_eq!(restored.vibe.as_deref(), Some("dub techno"));
+        assert_eq!(fresh.get_all_rules(&session.id).unwrap().len(), 1);
+        assert!(fresh.get_rules_by_session(&session.id).unwrap().is_empty());
+        assert_eq!(fresh.get_markers(&session.id).unwrap()[0].name, "drop");
+        assert_eq!(fresh.get_recent_history(&session.id, 10).unwrap().len(), 1);
+
+        let bytes = fresh.load_snapshot(&session.id).unwrap().unwrap();
+        let state = KernelState::from_capnp(&bytes).unwrap();
+        assert_eq!(state.recent_artifacts.len(), 1);
+        assert_eq!(state.transport, Transport::Stopped);
+
+        // Restoring again replaces rather than duplicating
+        Session::restore(&fresh, &store, &hash).unwrap();
+        assert_eq!(fresh.get_all_rules(&session.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_fork_is_independent() {
+        let temp = TempDir::new().unwrap();
+        let store = FileStore::at_path(temp.path()).unwrap();
+        let db = Database::open_memory().unwrap();
+        let session = populated_session(&db);
+
+        let hash = session.snapshot(&db, &store).unwrap();
+        let fork = Session::fork(&db, &store, &hash, "jam (branch)").unwrap();
+
+        assert_ne!(fork.id, session.id);
+        assert_eq!(fork.name, "jam (branch)");
+        assert_eq!(fork.tempo_bpm, 124.0);
+
+        let original_marker = &db.get_markers(&session.id).unwrap()[0];
+        let forked_marker = &db.get_markers(&fork.id).unwrap()[0];
+        assert_ne!(original_marker.id, forked_marker.id);
+
+        let bytes = db.load_snapshot(&fork.id).unwrap().unwrap();
+        let state = KernelState::from_capnp(&bytes).unwrap();
+        assert_eq!(state.session_id, fork.id);
+        assert_eq!(state.session_name, "jam (branch)");
+
+        db.delete_session(&fork.id).unwrap();
+        assert_eq!(db.get_all_rules(&session.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_failed_apply_keeps_existing_session() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::open(temp.path().join("vibeweaver.db")).unwrap();
+        let session = populated_session(&db);
+
+        // A duplicate rule ID fails partway through the rewrite
+        let mut snapshot = SessionSnapshot::capture(&db, &session).unwrap();
+        snapshot.rules.push(snapshot.rules[0].clone());
+        assert!(snapshot.apply(&db, session.clone()).is_err());
+
+        assert!(db.get_session(&session.id).unwrap().is_some());
+        assert_eq!(db.get_all_rules(&session.id).unwrap().len(), 1);
+        assert_eq!(db.get_markers(&session.id).unwrap().len(), 1);
+        assert!(db.load_snapshot(&session.id).unwrap().is_some());
+    }
+}
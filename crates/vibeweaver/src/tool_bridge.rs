@@ -2,19 +2,71 @@
 //! 
 //! Provides synchronous access to async ZMQ tools from Python context.
 //! Uses tokio's block_on() to bridge the sync/async boundary.
+//!
+//! Every call has an overall deadline (`tool_timeout_ms` in config) on top of
+//! the lazy-pirate client's per-attempt timeout, and can be aborted with
+//! `cancel_pending()` or by dropping its [`PendingCall`]. Aborting drops the
+//! in-flight request; the client's reactor discards the orphaned reply if one
+//! ever arrives.
 
 use anyhow::Result;
 use hooteproto::request::{GardenSeekRequest, GardenSetTempoRequest, ToolRequest};
 use hooteproto::Payload;
 use serde_json::Value as JsonValue;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 use tokio::runtime::Handle;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
 
 use crate::zmq_client::ZmqClient;
 
 /// Global bridge context (set once at startup)
 static BRIDGE: OnceLock<ToolBridge> = OnceLock::new();
 
+/// Deadline for a tool call when none is configured
+pub const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Why a tool call ended without a response
+#[derive(Debug, thiserror::Error)]
+pub enum ToolCallError {
+    #[error("{tool} timed out after {timeout:?}")]
+    Timeout { tool: String, timeout: Duration },
+    #[error("{tool} was cancelled")]
+    Cancelled { tool: String },
+}
+
+/// A tool call running on the bridge's runtime.
+///
+/// Lets the caller wait in slices (e.g. to service Python signals between
+/// them). Dropping it aborts the call.
+pub struct PendingCall {
+    tool: String,
+    result: mpsc::Receiver<Result<JsonValue>>,
+    task: JoinHandle<()>,
+}
+
+impl PendingCall {
+    /// Wait up to `timeout` for the result; `None` if the call is still running.
+    pub fn wait(&mut self, timeout: Duration) -> Option<Result<JsonValue>> {
+        match self.result.recv_timeout(timeout) {
+            Ok(result) => Some(result),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => Some(Err(anyhow::anyhow!(
+                "{} ended without a response",
+                self.tool
+            ))),
+        }
+    }
+}
+
+impl Drop for PendingCall {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 /// Bridge context for calling hootenanny tools from Python.
 #[derive(Clone)]
 pub struct ToolBridge {
@@ -22,12 +74,34 @@ pub struct ToolBridge {
     client: Arc<ZmqClient>,
     /// Tokio runtime handle for block_on
     runtime: Handle,
+    /// Overall deadline for one call, across client retries
+    timeout: Duration,
+    /// Wakes every in-flight call so it can abort
+    cancel: Arc<Notify>,
 }
 
 impl ToolBridge {
     /// Create a new tool bridge.
     pub fn new(client: Arc<ZmqClient>, runtime: Handle) -> Self {
-        Self { client, runtime }
+        Self {
+            client,
+            runtime,
+            timeout: DEFAULT_TOOL_TIMEOUT,
+            cancel: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Set the per-call deadline.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Abort every tool call currently waiting on hootenanny.
+    ///
+    /// Calls made afterwards are unaffected.
+    pub fn cancel_pending(&self) {
+        self.cancel.notify_waiters();
     }
 
     /// Initialize the global bridge (call once at startup).
@@ -47,37 +121,90 @@ impl ToolBridge {
     /// Uses block_in_place to allow blocking within the tokio runtime,
     /// avoiding deadlocks when called from async context (via Python).
     pub fn call_tool(&self, name: &str, args: JsonValue) -> Result<JsonValue> {
+        self.call_tool_with_timeout(name, args, self.timeout)
+    }
+
+    /// Call a hootenanny tool with an explicit deadline.
+    ///
+    /// Fails with [`ToolCallError`] on timeout or `cancel_pending()`.
+    pub fn call_tool_with_timeout(
+        &self,
+        name: &str,
+        args: JsonValue,
+        timeout: Duration,
+    ) -> Result<JsonValue> {
         // Convert tool name + JSON args to typed Payload
         let payload = args_to_payload(name, args)?;
 
         // block_in_place allows blocking in a multi-threaded runtime
         // by moving the current task to a blocking thread
-        tokio::task::block_in_place(|| {
-            self.runtime.block_on(async {
-                match self.client.request(payload).await? {
-                    Payload::TypedResponse(envelope) => Ok(envelope.to_json()),
-                    Payload::Error {
+        tokio::task::block_in_place(|| self.runtime.block_on(self.request(name, payload, timeout)))
+    }
+
+    /// Start a tool call on the runtime without blocking.
+    ///
+    /// The call keeps the bridge's deadline and `cancel_pending()` handling.
+    pub fn start_call(&self, name: &str, args: JsonValue) -> Result<PendingCall> {
+        let payload = args_to_payload(name, args)?;
+        let (tx, rx) = mpsc::sync_channel(1);
+
+        let bridge = self.clone();
+        let tool = name.to_string();
+        let task = self.runtime.spawn(async move {
+            let result = bridge.request(&tool, payload, bridge.timeout).await;
+            let _ = tx.send(result);
+        });
+
+        Ok(PendingCall {
+            tool: name.to_string(),
+            result: rx,
+            task,
+        })
+    }
+
+    /// Send one request, bounded by `timeout` and `cancel_pending()`.
+    async fn request(&self, name: &str, payload: Payload, timeout: Duration) -> Result<JsonValue> {
+        // Register for cancellation before sending so a cancel racing
+        // the request can't slip through
+        let cancelled = self.cancel.notified();
+
+        let response = tokio::select! {
+            response = self.client.request(payload) => response?,
+            _ = tokio::time::sleep(timeout) => {
+                return Err(anyhow::Error::new(ToolCallError::Timeout {
+                    tool: name.to_string(),
+                    timeout,
+                }));
+            }
+            _ = cancelled => {
+                return Err(anyhow::Error::new(ToolCallError::Cancelled {
+                    tool: name.to_string(),
+                }));
+            }
+        };
+
+        match response {
+            Payload::TypedResponse(envelope) => Ok(envelope.to_json()),
+            Payload::Error {
+                code,
+                message,
+                details,
+            } => {
+                let error_msg = if let Some(d) = details {
+                    format!(
+                        "{}: {}
+{}",
                         code,
                         message,
-                        details,
-                    } => {
-                        let error_msg = if let Some(d) = details {
-                            format!(
-                                "{}: {}
-{}",
-                                code,
-                                message,
-                                serde_json::to_string_pretty(&d)?
-                            )
-                        } else {
-                            format!("{}: {}", code, message)
-                        };
-                        anyhow::bail!(error_msg)
-                    }
-                    other => anyhow::bail!("Unexpected response: {:?}", other),
-                }
-            })
-        })
+                        serde_json::to_string_pretty(&d)?
+                    )
+                } else {
+                    format!("{}: {}", code, message)
+                };
+                anyhow::bail!(error_msg)
+            }
+            other => anyhow::bail!("Unexpected response: {:?}", other),
+        }
     }
 }
 
@@ -119,7 +246,115 @@ pub fn call_tool(name: &str, args: JsonValue) -> Result<JsonValue> {
     bridge.call_tool(name, args)
 }
 
+/// Start a hootenanny tool call on the global bridge without blocking.
+pub fn start_call(name: &str, args: JsonValue) -> Result<PendingCall> {
+    let bridge = ToolBridge::global().ok_or_else(|| {
+        anyhow::anyhow!("Tool bridge not initialized - vibeweaver not connected to hootenanny")
+    })?;
+    bridge.start_call(name, args)
+}
+
+/// Abort pending tool calls on the global bridge, if any.
+pub fn cancel_pending() {
+    if let Some(bridge) = BRIDGE.get() {
+        bridge.cancel_pending();
+    }
+}
+
 /// Check if the bridge is initialized.
 pub fn is_connected() -> bool {
     BRIDGE.get().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zmq_client;
+    use serde_json::json;
+    use std::time::Instant;
+
+    /// A ROUTER that accepts requests and never answers
+    fn silent_endpoint() -> (zmq::Context, zmq::Socket, String) {
+        let context = zmq::Context::new();
+        let socket = context.socket(zmq::ROUTER).unwrap();
+        socket.bind("tcp://127.0.0.1:*").unwrap();
+        let endpoint = socket.get_last_endpoint().unwrap().unwrap();
+        (context, socket, endpoint)
+    }
+
+    fn bridge_to(endpoint: &str, runtime: &tokio::runtime::Runtime) -> ToolBridge {
+        let client = runtime.block_on(zmq_client::connect(endpoint, 30_000));
+        ToolBridge::new(client, runtime.handle().clone())
+    }
+
+    #[test]
+    fn test_call_times_out_against_silent_endpoint() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (_context, _socket, endpoint) = silent_endpoint();
+        let bridge = bridge_to(&endpoint, &runtime).with_timeout(Duration::from_millis(200));
+
+        let started = Instant::now();
+        let err = bridge.call_tool("garden_status", json!({})).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ToolCallError>(),
+            Some(ToolCallError::Timeout { tool, .. }) if tool == "garden_status"
+        ));
+        // Well under the client's 30s per-attempt timeout
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_cancel_pending_aborts_call() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (_context, _socket, endpoint) = silent_endpoint();
+        let bridge = Arc::new(bridge_to(&endpoint, &runtime));
+
+        let caller = {
+            let bridge = Arc::clone(&bridge);
+            std::thread::spawn(move || bridge.call_tool("garden_status", json!({})))
+        };
+
+        // The call may not have registered yet, so keep cancelling until it ends
+        while !caller.is_finished() {
+            bridge.cancel_pending();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let err = caller.join().unwrap().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ToolCallError>(),
+            Some(ToolCallError::Cancelled { .. })
+        ));
+    }
+
+    #[test]
+    fn test_pending_call_waits_in_slices() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (_context, _socket, endpoint) = silent_endpoint();
+        let bridge = bridge_to(&endpoint, &runtime).with_timeout(Duration::from_millis(200));
+
+        let mut call = bridge.start_call("garden_status", json!({})).unwrap();
+        assert!(call.wait(Duration::from_millis(20)).is_none());
+
+        let err = loop {
+            if let Some(result) = call.wait(Duration::from_millis(50)) {
+                break result.unwrap_err();
+            }
+        };
+        assert!(matches!(
+            err.downcast_ref::<ToolCallError>(),
+            Some(ToolCallError::Timeout { .. })
+        ));
+
+        // Dropping an unfinished call aborts its task
+        let call = bridge.start_call("garden_status", json!({})).unwrap();
+        let task = call.task.abort_handle();
+        drop(call);
+        let started = Instant::now();
+        while !task.is_finished() {
+            assert!(started.elapsed() < Duration::from_secs(1));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
 }
\ No newline at end of file
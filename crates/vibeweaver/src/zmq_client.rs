@@ -44,6 +44,7 @@ pub enum Broadcast {
     TransportStateChanged {
         state: String,
         position_beats: f64,
+        beats_per_bar: u32,
     },
     BeatTick {
         beat: f64,
@@ -143,15 +144,17 @@ fn hoot_broadcast_to_vibeweaver(broadcast: HootBroadcast) -> Broadcast {
         } => Broadcast::ArtifactCreated {
             artifact_id,
             content_hash,
-            tags,
+            tags: tags.into(),
         },
         HootBroadcast::TransportStateChanged {
             state,
             position_beats,
+            beats_per_bar,
             ..
         } => Broadcast::TransportStateChanged {
             state,
             position_beats,
+            beats_per_bar,
         },
         HootBroadcast::BeatTick {
             position_beats,
@@ -25,9 +25,13 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::broadcast::BroadcastHandler;
-use crate::callbacks::{fire_artifact_callbacks, fire_beat_callbacks, fire_marker_callbacks};
+use crate::callbacks::{
+    fire_artifact_callbacks, fire_beat_callbacks, fire_marker_callbacks, fire_quantized_callback,
+};
 use crate::kernel::Kernel;
-use crate::session::Session;
+use crate::scheduler::Scheduler;
+use crate::session::{Action, Session};
+use crate::tool_bridge;
 use crate::zmq_client::{Broadcast, BroadcastReceiver};
 
 /// Boxed sink type for sending messages
@@ -346,6 +350,9 @@ impl Server {
 
     /// Reset the kernel
     async fn weave_reset(&self, _clear_session: bool) -> Payload {
+        // Abort tool calls still blocking earlier evals
+        tool_bridge::cancel_pending();
+
         let kernel = self.kernel.read().await;
         match kernel.clear() {
             Ok(()) => Payload::TypedResponse(ResponseEnvelope::success(
@@ -468,6 +475,26 @@ Use weave_help(topic="api|session|examples") for more info.
             }
         }
 
+        // Then let the scheduler resolve quantized actions; the lock is
+        // released before any Python runs so callbacks can schedule more
+        let (actions, position_beats) = match Scheduler::global().map(|s| s.lock()) {
+            Some(Ok(mut scheduler)) => {
+                let actions = scheduler.process_broadcast(&broadcast);
+                (actions, scheduler.position_beats())
+            }
+            Some(Err(e)) => {
+                error!("Failed to lock scheduler: {}", e);
+                (Vec::new(), 0.0)
+            }
+            None => (Vec::new(), 0.0),
+        };
+        for action in actions {
+            match action {
+                Action::Callback { id } => fire_quantized_callback(&id, position_beats),
+                other => debug!("No executor for scheduled action {:?}", other),
+            }
+        }
+
         // Then fire Python callbacks
         match broadcast {
             Broadcast::BeatTick { beat, tempo_bpm: _ } => {
@@ -486,7 +513,11 @@ Use weave_help(topic="api|session|examples") for more info.
             Broadcast::JobStateChanged { job_id, state, .. } => {
                 debug!("Job {} state changed to {}", job_id, state);
             }
-            Broadcast::TransportStateChanged { state, position_beats } => {
+            Broadcast::TransportStateChanged {
+                state,
+                position_beats,
+                ..
+            } => {
                 debug!("Transport {} at beat {}", state, position_beats);
             }
             Broadcast::Unknown { topic, .. } => {